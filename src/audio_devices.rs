@@ -0,0 +1,196 @@
+//! Input device discovery and the input-loss state shown in the overlay.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait};
+
+/// How often the watched device is checked for disappearance
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// The watched input device is missing from the device list
+static DEVICE_MISSING: AtomicBool = AtomicBool::new(false);
+/// The capture stream stopped delivering audio while recording
+static STREAM_STALLED: AtomicBool = AtomicBool::new(false);
+/// The capture device is held open by the speech runtime
+static DEVICE_OPEN: AtomicBool = AtomicBool::new(false);
+
+/// Why the input is unavailable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputLoss {
+    /// The device was unplugged or removed
+    Disconnected,
+    /// The device is listed but no audio arrives
+    Stalled,
+}
+
+/// Current input outage, if any; a missing device wins over a stalled stream
+pub fn input_loss() -> Option<InputLoss> {
+    if DEVICE_MISSING.load(Ordering::Relaxed) {
+        Some(InputLoss::Disconnected)
+    } else if STREAM_STALLED.load(Ordering::Relaxed) {
+        Some(InputLoss::Stalled)
+    } else {
+        None
+    }
+}
+
+/// Record whether the capture stream is stalled (see `capture_monitor`)
+pub fn set_stream_stalled(stalled: bool) {
+    STREAM_STALLED.store(stalled, Ordering::Relaxed);
+}
+
+/// Whether the capture device is open, i.e. the OS mic-in-use indicator is lit
+pub fn device_open() -> bool {
+    DEVICE_OPEN.load(Ordering::Relaxed)
+}
+
+/// Record that the capture device was opened or closed, logging the change
+pub fn set_device_open(open: bool) {
+    if DEVICE_OPEN.swap(open, Ordering::Relaxed) != open {
+        if open {
+            tracing::info!("Capture device opened");
+        } else {
+            tracing::info!("Capture device closed");
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputDevice {
+    pub name: String,
+    pub is_default: bool,
+    /// Rate of the device's default input configuration, if it reports one
+    pub native_sample_rate: Option<u32>,
+    /// Channel count of the device's default input configuration, if it reports one
+    pub channels: Option<u16>,
+}
+
+/// Enumerate the input devices of the default host
+pub fn list_input_devices() -> Result<Vec<InputDevice>, String> {
+    let host = cpal::default_host();
+    let default_name = host
+        .default_input_device()
+        .and_then(|device| device.name().ok());
+    let devices = host
+        .input_devices()
+        .map_err(|e| format!("Failed to enumerate input devices: {}", e))?;
+    Ok(devices
+        .filter_map(|device| {
+            let name = device.name().ok()?;
+            let default_config = device.default_input_config().ok();
+            Some(InputDevice {
+                is_default: default_name.as_deref() == Some(name.as_str()),
+                name,
+                native_sample_rate: default_config.as_ref().map(|config| config.sample_rate().0),
+                channels: default_config.as_ref().map(|config| config.channels()),
+            })
+        })
+        .collect())
+}
+
+/// Input devices enumerated once at startup (empty if enumeration failed)
+pub fn startup_input_devices() -> &'static [InputDevice] {
+    static DEVICES: OnceLock<Vec<InputDevice>> = OnceLock::new();
+    DEVICES.get_or_init(|| {
+        list_input_devices().unwrap_or_else(|e| {
+            tracing::warn!("{}", e);
+            Vec::new()
+        })
+    })
+}
+
+/// Whether a default input is listed
+fn default_input_present(devices: &[InputDevice]) -> bool {
+    devices.iter().any(|device| device.is_default)
+}
+
+/// The input whose disappearance is reported
+#[derive(Debug, Clone, Copy)]
+enum WatchedInput {
+    /// The default input of the audio host
+    Default,
+    /// The PipeWire node chosen with `audio_capture_config.target_object`
+    Node(&'static str),
+}
+
+impl WatchedInput {
+    fn current() -> Self {
+        match crate::pipewire_capture::capture_target() {
+            Some(name) => Self::Node(name),
+            None => Self::Default,
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            Self::Default => "Default input".to_string(),
+            Self::Node(name) => format!("Input {}", name),
+        }
+    }
+
+    fn present(self) -> Result<bool, String> {
+        match self {
+            Self::Default => list_input_devices().map(|devices| default_input_present(&devices)),
+            Self::Node(name) => crate::pipewire_capture::list_sources()
+                .map(|nodes| nodes.iter().any(|node| node.name == name)),
+        }
+    }
+}
+
+/// Warn (log, toast and overlay) when the watched input (the configured
+/// PipeWire target, else the default input) disappears, and again when it returns
+pub async fn run_device_monitor(running: Arc<AtomicBool>) {
+    let watched = WatchedInput::current();
+    let mut interval = tokio::time::interval(DEVICE_POLL_INTERVAL);
+    // A capture target is only recorded when it was found at startup
+    let mut present = match watched {
+        WatchedInput::Default => default_input_present(startup_input_devices()),
+        WatchedInput::Node(_) => true,
+    };
+    DEVICE_MISSING.store(!present, Ordering::Relaxed);
+
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+        let now_present = match tokio::task::spawn_blocking(move || watched.present()).await {
+            Ok(Ok(found)) => found,
+            _ => continue,
+        };
+
+        if present && !now_present {
+            tracing::warn!(
+                "{} disconnected; recording will not capture audio until it returns",
+                watched.label()
+            );
+            crate::toast::show(format!("{} disconnected", watched.label()));
+        } else if !present && now_present {
+            tracing::info!("{} is available again", watched.label());
+        }
+        DEVICE_MISSING.store(!now_present, Ordering::Relaxed);
+        present = now_present;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(name: &str) -> InputDevice {
+        InputDevice {
+            name: name.to_string(),
+            is_default: false,
+            native_sample_rate: None,
+            channels: None,
+        }
+    }
+
+    #[test]
+    fn only_a_default_input_counts_as_present() {
+        let mut devices = vec![device("USB Mic")];
+        assert!(!default_input_present(&devices));
+
+        devices[0].is_default = true;
+        assert!(default_input_present(&devices));
+    }
+}
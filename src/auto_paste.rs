@@ -0,0 +1,43 @@
+//! The auto-paste switch (`portal_config.auto_paste`, the tray and a shortcut).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Delivery sink recorded in the usage log while auto-paste is off
+pub const CLIPBOARD_ONLY_SINK: &str = "clipboard_only";
+
+static AUTO_PASTE: AtomicBool = AtomicBool::new(true);
+
+/// Whether the next segment is pasted, not only copied
+pub fn enabled() -> bool {
+    AUTO_PASTE.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(enabled: bool) {
+    AUTO_PASTE.store(enabled, Ordering::Relaxed);
+}
+
+/// Flip auto-paste and say so in the status bar; returns the new state
+pub fn toggle() -> bool {
+    let enabled = !AUTO_PASTE.fetch_xor(true, Ordering::Relaxed);
+    tracing::info!("Auto-paste {}", if enabled { "on" } else { "off" });
+    crate::toast::show(if enabled {
+        "Auto-paste on"
+    } else {
+        "Auto-paste off (clipboard only)"
+    });
+    enabled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggling_flips_the_shared_state() {
+        set_enabled(true);
+        assert!(!toggle());
+        assert!(!enabled());
+        assert!(toggle());
+        assert!(enabled());
+    }
+}
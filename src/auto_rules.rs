@@ -0,0 +1,366 @@
+//! Per-session model and enhancement template overrides by time of day.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Weekday};
+use speechcore::BackendCommand;
+
+use crate::config::{AutoRule, AutoRuleCondition, ConfigStore};
+use crate::enhancement::MagicModeEnhancer;
+
+/// Re-evaluate rules this often while idle so model reloads happen before a session
+const IDLE_EVALUATION_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Local time range; `start > end` wraps past midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeRange {
+    /// Parse "HH:MM-HH:MM"
+    pub fn parse(range: &str) -> Result<Self, String> {
+        let (start, end) = range
+            .split_once('-')
+            .ok_or_else(|| format!("time range '{}' must look like HH:MM-HH:MM", range))?;
+        let parse_time = |t: &str| {
+            NaiveTime::parse_from_str(t.trim(), "%H:%M")
+                .map_err(|_| format!("invalid time '{}' in range '{}'", t.trim(), range))
+        };
+        let range = Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        };
+        if range.start == range.end {
+            return Err(format!(
+                "time range '{}-{}' is empty",
+                range.start.format("%H:%M"),
+                range.end.format("%H:%M")
+            ));
+        }
+        Ok(range)
+    }
+
+    fn wraps_midnight(&self) -> bool {
+        self.start > self.end
+    }
+}
+
+fn parse_weekday(name: &str) -> Result<Weekday, String> {
+    name.trim()
+        .parse::<Weekday>()
+        .map_err(|_| format!("unknown weekday '{}'", name.trim()))
+}
+
+/// Expand weekday entries such as "mon", "friday" or "mon-fri" (ranges may wrap, e.g. "sat-mon")
+pub fn parse_weekdays(entries: &[String]) -> Result<Vec<Weekday>, String> {
+    let mut days = Vec::new();
+    for entry in entries {
+        match entry.split_once('-') {
+            Some((from, to)) => {
+                let mut day = parse_weekday(from)?;
+                let last = parse_weekday(to)?;
+                days.push(day);
+                while day != last {
+                    day = day.succ();
+                    days.push(day);
+                }
+            }
+            None => days.push(parse_weekday(entry)?),
+        }
+    }
+    Ok(days)
+}
+
+/// Display name of a rule: its `name`, or "rule N" (1-based)
+pub fn rule_label(rule: &AutoRule, index: usize) -> String {
+    rule.name
+        .clone()
+        .unwrap_or_else(|| format!("rule {}", index + 1))
+}
+
+/// Check every rule; returns one message per problem
+pub fn validate_rules(rules: &[AutoRule], templates: &BTreeMap<String, String>) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (index, rule) in rules.iter().enumerate() {
+        let label = rule_label(rule, index);
+        if let Err(e) = parse_weekdays(&rule.when.weekdays) {
+            errors.push(format!("auto rule '{}': {}", label, e));
+        }
+        if let Some(time) = &rule.when.time {
+            if let Err(e) = TimeRange::parse(time) {
+                errors.push(format!("auto rule '{}': {}", label, e));
+            }
+        }
+        let set = &rule.set;
+        if set.model.is_none() && set.template.is_none() {
+            errors.push(format!("auto rule '{}': 'set' has no overrides", label));
+        }
+        if let Some(template) = &set.template {
+            if !templates.contains_key(template) {
+                errors.push(format!(
+                    "auto rule '{}': unknown template '{}' (define it in [enhancement_config.templates])",
+                    label, template
+                ));
+            }
+        }
+    }
+    errors
+}
+
+fn condition_matches(condition: &AutoRuleCondition, now: NaiveDateTime) -> bool {
+    let Ok(days) = parse_weekdays(&condition.weekdays) else {
+        return false;
+    };
+    let day_allowed = |day: Weekday| days.is_empty() || days.contains(&day);
+
+    let Some(time) = &condition.time else {
+        return day_allowed(now.weekday());
+    };
+    let Ok(range) = TimeRange::parse(time) else {
+        return false;
+    };
+
+    let t = now.time();
+    if !range.wraps_midnight() {
+        return day_allowed(now.weekday()) && t >= range.start && t < range.end;
+    }
+
+    // A wrapping range belongs to the day it starts on: "fri 22:00-02:00"
+    // covers Friday night and the early hours of Saturday.
+    (t >= range.start && day_allowed(now.weekday()))
+        || (t < range.end && day_allowed(now.weekday().pred()))
+}
+
+/// Index of the first rule whose condition matches the local time `now`
+pub fn matching_rule(rules: &[AutoRule], now: NaiveDateTime) -> Option<usize> {
+    rules
+        .iter()
+        .position(|rule| condition_matches(&rule.when, now))
+}
+
+/// Everything the monitor needs to apply overrides
+pub struct AutoRulesContext {
+    pub rules: Vec<AutoRule>,
+    /// Read at apply time so settings-panel and file-watch edits are honoured
+    pub config: ConfigStore,
+    pub recording: Arc<AtomicBool>,
+    pub running: Arc<AtomicBool>,
+    pub backend_command_tx: Option<tokio::sync::mpsc::UnboundedSender<BackendCommand>>,
+    pub enhancer: Option<Arc<MagicModeEnhancer>>,
+}
+
+struct AppliedState {
+    rule: Option<usize>,
+    model: String,
+    /// A model switch held back because a session was running
+    reload_pending: bool,
+}
+
+impl AutoRulesContext {
+    /// Apply `rule`'s overrides; model switches wait while `reload_allowed` is false
+    fn apply(&self, state: &mut AppliedState, rule: Option<usize>, reload_allowed: bool) {
+        let config = self.config.snapshot();
+        let overrides = rule.map(|i| &self.rules[i].set);
+        let label = rule.map(|i| rule_label(&self.rules[i], i));
+
+        // Model: reuse the settings quick-switch path (backend reload)
+        let model = overrides
+            .and_then(|o| o.model.clone())
+            .unwrap_or_else(|| config.general_config.model.clone());
+        state.reload_pending = false;
+        if model != state.model {
+            match &self.backend_command_tx {
+                Some(_) if !reload_allowed => {
+                    tracing::info!(
+                        "Auto rules: switch to {} waits until recording stops",
+                        model
+                    );
+                    state.reload_pending = true;
+                }
+                Some(tx) => {
+                    tracing::info!("Auto rules: switching model {} -> {}", state.model, model);
+                    let _ = tx.send(BackendCommand::Reload {
+                        backend_config: crate::gpu_probe::effective_backend_config(&config),
+                        model_name: model.clone(),
+                    });
+                    state.model = model;
+                }
+                None => tracing::warn!("Auto rules: cannot switch model (no backend control)"),
+            }
+        }
+
+        if let Some(enhancer) = &self.enhancer {
+            let prompt = overrides
+                .and_then(|o| o.template.as_ref())
+                .and_then(|name| config.enhancement_config.templates.get(name))
+                .cloned();
+            enhancer.set_system_prompt_override(prompt);
+        }
+
+        match &label {
+            Some(label) => tracing::info!("Auto rules: '{}' applied", label),
+            None if state.rule.is_some() => tracing::info!("Auto rules: defaults restored"),
+            None => {}
+        }
+        state.rule = rule;
+    }
+
+    fn toast_text(&self, index: usize) -> String {
+        let rule = &self.rules[index];
+        let mut changes = Vec::new();
+        if let Some(model) = &rule.set.model {
+            changes.push(format!("model {}", model));
+        }
+        if let Some(template) = &rule.set.template {
+            changes.push(format!("template {}", template));
+        }
+        format!("Rule '{}': {}", rule_label(rule, index), changes.join(", "))
+    }
+}
+
+/// Evaluate rules while idle and at each recording start, applying overrides per session.
+/// Model reloads only happen between sessions; idle evaluation usually loads a rule's model
+/// before the session it is meant for.
+pub async fn run_auto_rules_monitor(ctx: AutoRulesContext) {
+    let mut interval = tokio::time::interval(Duration::from_millis(100));
+    let mut state = AppliedState {
+        rule: None,
+        model: ctx.config.snapshot().general_config.model,
+        reload_pending: false,
+    };
+    let mut was_recording = false;
+    let mut last_idle_evaluation: Option<std::time::Instant> = None;
+
+    while ctx.running.load(Ordering::Relaxed) {
+        interval.tick().await;
+        let is_recording = ctx.recording.load(Ordering::Relaxed);
+        let now = chrono::Local::now().naive_local();
+
+        if is_recording && !was_recording {
+            // Session start / recording resume: the rule in effect now wins
+            let rule = matching_rule(&ctx.rules, now);
+            if rule != state.rule {
+                ctx.apply(&mut state, rule, false);
+            }
+            if let Some(index) = rule {
+                crate::toast::show(ctx.toast_text(index));
+            }
+        } else if !is_recording && was_recording && state.reload_pending {
+            // The session that held back a model switch is over; reload now
+            last_idle_evaluation = None;
+        }
+
+        if !is_recording
+            && last_idle_evaluation.is_none_or(|t| t.elapsed() >= IDLE_EVALUATION_INTERVAL)
+        {
+            last_idle_evaluation = Some(std::time::Instant::now());
+            let rule = matching_rule(&ctx.rules, now);
+            if rule != state.rule || state.reload_pending {
+                ctx.apply(&mut state, rule, true);
+            }
+        }
+
+        was_recording = is_recording;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AutoRuleOverrides;
+    use chrono::{DateTime, FixedOffset, NaiveDate};
+
+    fn rule(weekdays: &[&str], time: Option<&str>, model: &str) -> AutoRule {
+        AutoRule {
+            name: Some(model.to_string()),
+            when: AutoRuleCondition {
+                weekdays: weekdays.iter().map(|d| d.to_string()).collect(),
+                time: time.map(str::to_string),
+            },
+            set: AutoRuleOverrides {
+                model: Some(model.to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// 2024-01-01 was a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn weekday_and_time_window() {
+        let rules = vec![rule(&["mon-fri"], Some("09:00-09:30"), "small")];
+
+        assert_eq!(matching_rule(&rules, at(1, 9, 0)), Some(0));
+        assert_eq!(matching_rule(&rules, at(1, 9, 29)), Some(0));
+        assert_eq!(matching_rule(&rules, at(1, 9, 30)), None);
+        assert_eq!(matching_rule(&rules, at(6, 9, 15)), None); // Saturday
+    }
+
+    #[test]
+    fn overlapping_rules_resolve_by_order() {
+        let rules = vec![
+            rule(&["mon"], Some("09:00-09:30"), "small"),
+            rule(&[], Some("08:00-18:00"), "base"),
+        ];
+
+        assert_eq!(matching_rule(&rules, at(1, 9, 10)), Some(0));
+        assert_eq!(matching_rule(&rules, at(2, 9, 10)), Some(1));
+    }
+
+    #[test]
+    fn wrapping_range_belongs_to_start_day() {
+        let rules = vec![rule(&["fri"], Some("22:00-02:00"), "small")];
+
+        assert_eq!(matching_rule(&rules, at(5, 23, 0)), Some(0)); // Friday night
+        assert_eq!(matching_rule(&rules, at(6, 1, 59)), Some(0)); // early Saturday
+        assert_eq!(matching_rule(&rules, at(6, 2, 0)), None);
+        assert_eq!(matching_rule(&rules, at(5, 1, 0)), None); // early Friday belongs to Thursday
+        assert_eq!(matching_rule(&rules, at(6, 23, 0)), None);
+    }
+
+    #[test]
+    fn matching_uses_local_wall_time_across_timezone_changes() {
+        let rules = vec![rule(&["mon"], Some("09:00-09:30"), "small")];
+        let instant = DateTime::parse_from_rfc3339("2024-01-01T08:15:00Z").unwrap();
+
+        let berlin = instant.with_timezone(&FixedOffset::east_opt(3600).unwrap());
+        let london = instant.with_timezone(&FixedOffset::east_opt(0).unwrap());
+
+        assert_eq!(matching_rule(&rules, berlin.naive_local()), Some(0));
+        assert_eq!(matching_rule(&rules, london.naive_local()), None);
+    }
+
+    #[test]
+    fn validation_rejects_malformed_rules() {
+        let templates = BTreeMap::from([("email".to_string(), "Write an email".to_string())]);
+        let mut bad_template = rule(&[], None, "small");
+        bad_template.set.template = Some("memo".to_string());
+
+        let rules = vec![
+            rule(&["mon"], Some("9-10"), "small"),
+            rule(&["mon"], Some("25:00-26:00"), "small"),
+            rule(&["mon"], Some("10:00-10:00"), "small"),
+            rule(&["someday"], None, "small"),
+            AutoRule::default(),
+            bad_template,
+        ];
+
+        assert_eq!(validate_rules(&rules, &templates).len(), 6);
+        assert!(validate_rules(
+            &[rule(&["sat-mon"], Some("23:00-01:00"), "small")],
+            &templates
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,453 @@
+//! Live captions over WebSocket (`captions_config`), e.g. for an OBS browser source.
+
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::config::CaptionsConfig;
+
+/// Events queued per client before further events are dropped
+const CLIENT_QUEUE: usize = 64;
+/// Events a client may miss in a row before it is disconnected
+const MAX_DROPPED: u64 = 256;
+/// Longest request head read before the connection is refused
+const HEADER_LIMIT: usize = 8192;
+const HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+const CAPTION_PAGE: &str = include_str!("../assets/captions.html");
+
+/// One message sent to caption clients
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CaptionEvent {
+    Segment {
+        session_id: Option<String>,
+        text: String,
+        /// False for a partial result that a later segment replaces; every
+        /// segment is final until the speech runtime streams partials
+        #[serde(rename = "final")]
+        is_final: bool,
+        /// RFC 3339 local time the segment was delivered
+        timestamp: String,
+        /// How long the page should show the caption before fading it out
+        lifetime_ms: u64,
+    },
+    /// The session was wiped; remove every caption
+    Clear,
+}
+
+struct Client {
+    id: u64,
+    event_tx: mpsc::Sender<String>,
+    /// Events dropped since the client last kept up
+    dropped: u64,
+}
+
+struct Broadcast {
+    clients: Vec<Client>,
+    next_id: u64,
+    lifetime_ms: u64,
+}
+
+/// Set while the server runs
+static BROADCAST: Mutex<Option<Broadcast>> = Mutex::new(None);
+
+/// Send a delivered segment to every client; a no-op while the server is off
+pub fn record_segment(session_id: Option<&str>, text: &str) {
+    broadcast(|lifetime_ms| CaptionEvent::Segment {
+        session_id: session_id.map(str::to_string),
+        text: text.to_string(),
+        is_final: true,
+        timestamp: chrono::Local::now().to_rfc3339(),
+        lifetime_ms,
+    });
+}
+
+/// Tell every client to remove its captions (privacy wipe)
+pub fn clear() {
+    broadcast(|_| CaptionEvent::Clear);
+}
+
+fn broadcast(event: impl FnOnce(u64) -> CaptionEvent) {
+    let mut broadcast = BROADCAST.lock();
+    let Some(broadcast) = broadcast.as_mut() else {
+        return;
+    };
+    if broadcast.clients.is_empty() {
+        return;
+    }
+    let message = match serde_json::to_string(&event(broadcast.lifetime_ms)) {
+        Ok(message) => message,
+        Err(e) => {
+            tracing::warn!("Failed to encode caption event: {}", e);
+            return;
+        }
+    };
+
+    broadcast
+        .clients
+        .retain_mut(|client| match client.event_tx.try_send(message.clone()) {
+            Ok(()) => {
+                client.dropped = 0;
+                true
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                if client.dropped == 0 {
+                    tracing::warn!(
+                        "Caption client {} is not keeping up; dropping captions",
+                        client.id
+                    );
+                }
+                client.dropped += 1;
+                if client.dropped >= MAX_DROPPED {
+                    tracing::warn!("Caption client {} disconnected: too slow", client.id);
+                    return false;
+                }
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+}
+
+/// Serve captions until shutdown
+pub async fn run(config: &CaptionsConfig, running: Arc<AtomicBool>) -> Result<()> {
+    let allowed_origins: Arc<[String]> = config.allowed_origins.clone().into();
+    let listener = TcpListener::bind((config.bind_address.as_str(), config.port))
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to listen on {}:{} (captions_config)",
+                config.bind_address, config.port
+            )
+        })?;
+    tracing::info!(
+        "Captions available at http://{}/",
+        listener
+            .local_addr()
+            .context("Failed to read caption address")?
+    );
+
+    *BROADCAST.lock() = Some(Broadcast {
+        clients: Vec::new(),
+        next_id: 1,
+        lifetime_ms: (config.caption_lifetime_secs.max(0.0) * 1000.0) as u64,
+    });
+
+    loop {
+        tokio::select! {
+            accept_result = listener.accept() => {
+                match accept_result {
+                    Ok((stream, _)) => {
+                        tokio::spawn(serve_connection(stream, allowed_origins.clone()));
+                    }
+                    Err(e) => tracing::warn!("Caption server accept error: {}", e),
+                }
+            }
+            _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Dropping the senders closes every client
+    *BROADCAST.lock() = None;
+    tracing::info!("Caption server shut down");
+    Ok(())
+}
+
+/// What the request head asks for
+#[derive(Debug, PartialEq, Eq)]
+enum Request {
+    WebSocket,
+    Page,
+    NotFound,
+    BadMethod,
+}
+
+/// Classify an HTTP request head, or `None` if it is not one
+fn parse_request(head: &str) -> Option<Request> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let (method, target) = (request_line.next()?, request_line.next()?);
+    if !request_line.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+    let upgrade = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("upgrade")
+                && value.trim().eq_ignore_ascii_case("websocket")
+        })
+    });
+    let path = target.split('?').next().unwrap_or_default();
+    Some(match (method, path) {
+        ("GET", _) if upgrade => Request::WebSocket,
+        ("GET" | "HEAD", "/" | "/captions" | "/captions.html") => Request::Page,
+        ("GET" | "HEAD", _) => Request::NotFound,
+        _ => Request::BadMethod,
+    })
+}
+
+/// Value of the header `name` in a request head
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+/// Whether a WebSocket upgrade may subscribe. Browsers always send `Origin`,
+/// so a missing one is a local tool, not a web page. The caption page itself
+/// is accepted when its origin names the host it was requested from and that
+/// host is an address or `localhost` (a domain name could be rebound to this
+/// machine); anything else has to be in `allowed_origins`.
+fn origin_allowed(head: &str, allowed_origins: &[String]) -> bool {
+    let Some(origin) = header(head, "origin") else {
+        return true;
+    };
+    if allowed_origins
+        .iter()
+        .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+    {
+        return true;
+    }
+    let Some(authority) = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+    else {
+        return false;
+    };
+    let own_page = header(head, "host").is_some_and(|host| host.eq_ignore_ascii_case(authority));
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|b| b.is_ascii_digit()) => host,
+        _ => authority,
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    own_page && (host.eq_ignore_ascii_case("localhost") || host.parse::<IpAddr>().is_ok())
+}
+
+/// Peek at the request head and its length in bytes without consuming it, so
+/// the WebSocket handshake can still read it
+async fn peek_request_head(stream: &TcpStream) -> Result<(String, usize)> {
+    tokio::time::timeout(HEADER_TIMEOUT, wait_for_request_head(stream))
+        .await
+        .context("timed out reading the request")?
+}
+
+async fn wait_for_request_head(stream: &TcpStream) -> Result<(String, usize)> {
+    let mut buffer = vec![0; HEADER_LIMIT];
+    loop {
+        let read = stream.peek(&mut buffer).await?;
+        if read == 0 {
+            anyhow::bail!("connection closed");
+        }
+        let peeked = &buffer[..read];
+        if let Some(end) = peeked.windows(4).position(|window| window == b"\r\n\r\n") {
+            let head = &peeked[..end + 4];
+            return Ok((String::from_utf8_lossy(head).into_owned(), head.len()));
+        }
+        if read == buffer.len() {
+            anyhow::bail!("request head is too long");
+        }
+        // The rest of the head has not arrived yet
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+async fn serve_connection(mut stream: TcpStream, allowed_origins: Arc<[String]>) {
+    let (head, head_len) = match peek_request_head(&stream).await {
+        Ok(head) => head,
+        Err(e) => {
+            tracing::debug!("Caption server: dropped a connection: {}", e);
+            return;
+        }
+    };
+    let (status, content_type, body) = match parse_request(&head) {
+        Some(Request::WebSocket) if origin_allowed(&head, &allowed_origins) => {
+            return serve_client(stream).await
+        }
+        Some(Request::WebSocket) => {
+            tracing::warn!(
+                "Caption server: refused a WebSocket from origin {}; see allowed_origins",
+                header(&head, "origin").unwrap_or_default()
+            );
+            ("403 Forbidden", "text/plain", "Origin not allowed\n")
+        }
+        Some(Request::Page) => ("200 OK", "text/html; charset=utf-8", CAPTION_PAGE),
+        Some(Request::NotFound) => ("404 Not Found", "text/plain", "Not found\n"),
+        Some(Request::BadMethod) => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method not allowed\n",
+        ),
+        None => ("400 Bad Request", "text/plain", "Bad request\n"),
+    };
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\n\
+         Connection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    if !head.starts_with("HEAD ") {
+        response.push_str(body);
+    }
+    // Consume the head that was only peeked at before answering
+    let mut discard = vec![0; head_len];
+    if stream.read_exact(&mut discard).await.is_ok() {
+        let _ = stream.write_all(response.as_bytes()).await;
+        let _ = stream.shutdown().await;
+    }
+}
+
+async fn serve_client(stream: TcpStream) {
+    let socket = match tokio_tungstenite::accept_async(stream).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            tracing::debug!("Caption server: WebSocket handshake failed: {}", e);
+            return;
+        }
+    };
+
+    let (event_tx, mut event_rx) = mpsc::channel(CLIENT_QUEUE);
+    let id = {
+        let mut broadcast = BROADCAST.lock();
+        let Some(broadcast) = broadcast.as_mut() else {
+            return;
+        };
+        let id = broadcast.next_id;
+        broadcast.next_id += 1;
+        broadcast.clients.push(Client {
+            id,
+            event_tx,
+            dropped: 0,
+        });
+        id
+    };
+    tracing::info!("Caption client {} connected", id);
+
+    let (mut writer, mut reader) = socket.split();
+    loop {
+        tokio::select! {
+            event = event_rx.recv() => {
+                // Closed when the client was dropped or the server stopped
+                let Some(event) = event else {
+                    let _ = writer.send(Message::Close(None)).await;
+                    break;
+                };
+                if writer.send(Message::text(event)).await.is_err() {
+                    break;
+                }
+            }
+            // The page sends nothing; reading keeps pings answered and notices a close
+            message = reader.next() => {
+                match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+
+    if let Some(broadcast) = BROADCAST.lock().as_mut() {
+        broadcast.clients.retain(|client| client.id != id);
+    }
+    tracing::info!("Caption client {} disconnected", id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_routed_by_path_and_upgrade() {
+        let upgrade = "GET /?lines=2 HTTP/1.1\r\nHost: localhost\r\nConnection: Upgrade\r\n\
+                       upgrade: WebSocket\r\nSec-WebSocket-Version: 13\r\n\r\n";
+        assert_eq!(parse_request(upgrade), Some(Request::WebSocket));
+        assert_eq!(
+            parse_request("GET /?lines=2 HTTP/1.1\r\nHost: localhost\r\n\r\n"),
+            Some(Request::Page)
+        );
+        assert_eq!(
+            parse_request("GET /favicon.ico HTTP/1.1\r\n\r\n"),
+            Some(Request::NotFound)
+        );
+        assert_eq!(
+            parse_request("POST / HTTP/1.1\r\n\r\n"),
+            Some(Request::BadMethod)
+        );
+        assert_eq!(parse_request("\u{16}\u{3}\u{1}garbage\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn upgrades_from_foreign_pages_are_refused() {
+        let upgrade = |host: &str, origin: Option<&str>| {
+            let origin = origin
+                .map(|origin| format!("Origin: {}\r\n", origin))
+                .unwrap_or_default();
+            format!(
+                "GET / HTTP/1.1\r\nHost: {}\r\n{}Upgrade: websocket\r\n\r\n",
+                host, origin
+            )
+        };
+        let allowed = ["https://overlay.example".to_string()];
+
+        // The caption page itself, e.g. in an OBS browser source
+        let own = upgrade("127.0.0.1:7878", Some("http://127.0.0.1:7878"));
+        assert!(origin_allowed(&own, &[]));
+        assert!(origin_allowed(
+            &upgrade("[::1]:7878", Some("http://[::1]:7878")),
+            &[]
+        ));
+        assert!(origin_allowed(
+            &upgrade("localhost:7878", Some("http://localhost:7878")),
+            &[]
+        ));
+        // A tool without a browser
+        assert!(origin_allowed(&upgrade("127.0.0.1:7878", None), &[]));
+
+        let foreign = upgrade("127.0.0.1:7878", Some("https://evil.example"));
+        assert!(!origin_allowed(&foreign, &[]));
+        assert!(!origin_allowed(&foreign, &allowed));
+        // A rebound domain names its own host, but is no address
+        let rebound = upgrade("evil.example:7878", Some("http://evil.example:7878"));
+        assert!(!origin_allowed(&rebound, &[]));
+        assert!(!origin_allowed(
+            &upgrade("127.0.0.1:7878", Some("null")),
+            &[]
+        ));
+
+        let listed = upgrade("127.0.0.1:7878", Some("https://overlay.example"));
+        assert!(origin_allowed(&listed, &allowed));
+    }
+
+    #[test]
+    fn segments_carry_the_final_flag_and_lifetime() {
+        let event = CaptionEvent::Segment {
+            session_id: None,
+            text: "Hello world.".to_string(),
+            is_final: true,
+            timestamp: "2026-10-16T14:03:12+02:00".to_string(),
+            lifetime_ms: 6000,
+        };
+        let json: serde_json::Value = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "segment");
+        assert_eq!(json["final"], true);
+        assert_eq!(json["lifetime_ms"], 6000);
+        assert_eq!(
+            serde_json::to_string(&CaptionEvent::Clear).unwrap(),
+            r#"{"type":"clear"}"#
+        );
+    }
+}
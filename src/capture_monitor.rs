@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::RwLock;
+use speechcore::AudioVisualizationData;
+
+/// A recording whose audio has not changed for this long counts as a stalled stream
+const STALL_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Cheap fingerprint of the visualization buffer, used to detect fresh audio
+pub(crate) fn samples_fingerprint(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s.abs()).sum()
+}
+
+/// Flag the input as lost when the capture stream stops delivering audio while
+/// recording, e.g. after the device died without leaving the device list.
+///
+/// A frozen non-zero buffer means the stream stopped; an all-zero one is a muted
+/// input and is not reported.
+pub async fn run_stall_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    audio_data: Arc<RwLock<AudioVisualizationData>>,
+) {
+    let mut interval = tokio::time::interval(Duration::from_millis(250));
+    let mut last_fingerprint = 0.0;
+    let mut last_change = Instant::now();
+    let mut stalled = false;
+
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+
+        let fingerprint = samples_fingerprint(&audio_data.read().samples);
+        let now_stalled = if !recording.load(Ordering::Relaxed) || fingerprint != last_fingerprint {
+            last_fingerprint = fingerprint;
+            last_change = Instant::now();
+            false
+        } else {
+            fingerprint > 0.0 && last_change.elapsed() >= STALL_TIMEOUT
+        };
+
+        if now_stalled && !stalled {
+            tracing::warn!(
+                "Capture stream delivered no audio for {} s while recording",
+                STALL_TIMEOUT.as_secs()
+            );
+        } else if !now_stalled && stalled && recording.load(Ordering::Relaxed) {
+            tracing::info!("Capture stream is delivering audio again");
+        }
+        if now_stalled != stalled {
+            crate::audio_devices::set_stream_stalled(now_stalled);
+            stalled = now_stalled;
+        }
+    }
+}
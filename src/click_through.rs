@@ -0,0 +1,24 @@
+//! Click-through state of the overlay.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Flip click-through and say so in the status bar; returns the new state
+pub fn toggle() -> bool {
+    let enabled = !ENABLED.fetch_xor(true, Ordering::Relaxed);
+    crate::toast::show(if enabled {
+        "Click-through on"
+    } else {
+        "Click-through off"
+    });
+    enabled
+}
@@ -0,0 +1,287 @@
+//! Detects input that is already clipped at the source (e.g. mic gain too high in
+//! the hardware mixer), which software gain cannot repair.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use parking_lot::{Mutex, RwLock};
+use speechcore::AudioVisualizationData;
+
+/// Absolute sample level treated as "at the rail"
+pub const NEAR_FULL_SCALE: f32 = 0.98;
+
+/// Maximum difference between neighbouring samples of a flat top
+const FLAT_EPSILON: f32 = 1e-4;
+
+/// Shortest run of flat samples near full scale that counts as a clipped peak
+pub const MIN_FLAT_RUN: usize = 3;
+
+/// Fraction of samples in flat-top runs above which a window counts as clipped
+pub const CLIPPED_FRACTION_THRESHOLD: f64 = 0.002;
+
+/// Consecutive clipped windows before the warning is raised
+const SUSTAINED_WINDOWS: u32 = 3;
+
+/// Clean audio needed before the warning is cleared again
+const CLEAR_AFTER: Duration = Duration::from_secs(5);
+
+/// Relative change applied by the one-click source volume action
+pub const VOLUME_STEP_DB: i32 = -6;
+
+/// Clipping measurement for one window of samples
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ClippingMeasurement {
+    /// Fraction of samples at or above `NEAR_FULL_SCALE`
+    pub near_full_scale_fraction: f64,
+    /// Fraction of samples inside flat-top runs near full scale
+    pub flat_top_fraction: f64,
+    /// Longest flat-top run in samples
+    pub longest_flat_run: usize,
+}
+
+impl ClippingMeasurement {
+    pub fn is_clipped(&self) -> bool {
+        self.flat_top_fraction >= CLIPPED_FRACTION_THRESHOLD
+    }
+}
+
+/// Measure full-scale samples and flat-top runs in `samples`
+pub fn measure(samples: &[f32]) -> ClippingMeasurement {
+    if samples.is_empty() {
+        return ClippingMeasurement::default();
+    }
+
+    let mut near_full_scale = 0usize;
+    let mut flat_samples = 0usize;
+    let mut longest_flat_run = 0usize;
+    let mut run = 0usize;
+    let mut previous: Option<f32> = None;
+
+    let mut end_run = |run: usize| {
+        if run >= MIN_FLAT_RUN {
+            flat_samples += run;
+            longest_flat_run = longest_flat_run.max(run);
+        }
+    };
+
+    for &sample in samples {
+        if sample.abs() < NEAR_FULL_SCALE {
+            end_run(run);
+            run = 0;
+            previous = None;
+            continue;
+        }
+        near_full_scale += 1;
+        match previous {
+            Some(prev) if (sample - prev).abs() <= FLAT_EPSILON => run += 1,
+            _ => {
+                end_run(run);
+                run = 1;
+            }
+        }
+        previous = Some(sample);
+    }
+    end_run(run);
+
+    let total = samples.len() as f64;
+    ClippingMeasurement {
+        near_full_scale_fraction: near_full_scale as f64 / total,
+        flat_top_fraction: flat_samples as f64 / total,
+        longest_flat_run,
+    }
+}
+
+/// Turns per-window measurements into a sustained warning with hysteresis
+#[derive(Debug, Default)]
+pub struct ClippingDetector {
+    consecutive: u32,
+    active: bool,
+    last_clipped: Option<Instant>,
+}
+
+impl ClippingDetector {
+    /// Feed one window; returns whether the warning is active
+    pub fn update(&mut self, measurement: &ClippingMeasurement, now: Instant) -> bool {
+        if measurement.is_clipped() {
+            self.consecutive += 1;
+            self.last_clipped = Some(now);
+            if self.consecutive >= SUSTAINED_WINDOWS {
+                self.active = true;
+            }
+        } else {
+            self.consecutive = 0;
+            if self
+                .last_clipped
+                .is_none_or(|at| now.duration_since(at) >= CLEAR_AFTER)
+            {
+                self.active = false;
+            }
+        }
+        self.active
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+static WARNING: AtomicBool = AtomicBool::new(false);
+static PEAK_FLAT_TOP_FRACTION: Mutex<f64> = Mutex::new(0.0);
+
+/// Whether sustained clipping is currently detected
+pub fn warning_active() -> bool {
+    WARNING.load(Ordering::Relaxed)
+}
+
+/// Highest flat-top fraction seen since the last call (for usage statistics)
+pub fn take_peak_flat_top_fraction() -> f64 {
+    std::mem::take(&mut *PEAK_FLAT_TOP_FRACTION.lock())
+}
+
+/// Analyze captured audio while recording and raise the clipping warning
+pub async fn run_clipping_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    audio_data: Arc<RwLock<AudioVisualizationData>>,
+) {
+    let mut interval = tokio::time::interval(Duration::from_millis(250));
+    let mut detector = ClippingDetector::default();
+    let mut last_fingerprint = 0.0f32;
+    // Probe the mixer off the render thread so the warning can offer the volume action
+    tokio::task::spawn_blocking(mixer_available);
+
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+
+        if !recording.load(Ordering::Relaxed) {
+            detector.reset();
+            WARNING.store(false, Ordering::Relaxed);
+            continue;
+        }
+
+        let measurement = {
+            let audio_data = audio_data.read();
+            let fingerprint = crate::capture_monitor::samples_fingerprint(&audio_data.samples);
+            if fingerprint == last_fingerprint {
+                continue;
+            }
+            last_fingerprint = fingerprint;
+            measure(&audio_data.samples)
+        };
+
+        {
+            let mut peak = PEAK_FLAT_TOP_FRACTION.lock();
+            *peak = peak.max(measurement.flat_top_fraction);
+        }
+
+        let active = detector.update(&measurement, Instant::now());
+        if active && !WARNING.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                "Input clipping detected ({:.2}% flat-top samples, longest run {}): lower your microphone gain",
+                measurement.flat_top_fraction * 100.0,
+                measurement.longest_flat_run
+            );
+        } else if !active && WARNING.swap(false, Ordering::Relaxed) {
+            tracing::info!("Input clipping cleared");
+        }
+    }
+}
+
+/// Whether the PipeWire/PulseAudio volume API is reachable through `pactl`
+pub fn mixer_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        std::process::Command::new("pactl")
+            .arg("info")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
+
+/// Lower the default source volume by `VOLUME_STEP_DB` in the background
+pub fn spawn_reduce_source_volume() {
+    std::thread::spawn(|| {
+        let step = format!("{}dB", VOLUME_STEP_DB);
+        let result = std::process::Command::new("pactl")
+            .args(["set-source-volume", "@DEFAULT_SOURCE@", step.as_str()])
+            .status();
+        match result {
+            Ok(status) if status.success() => {
+                tracing::info!("Lowered microphone volume by {} dB", -VOLUME_STEP_DB);
+                crate::toast::show(format!(
+                    "Microphone volume lowered by {} dB",
+                    -VOLUME_STEP_DB
+                ));
+            }
+            Ok(status) => tracing::warn!("pactl set-source-volume failed: {}", status),
+            Err(e) => tracing::warn!("Could not run pactl: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = 16_000.0;
+
+    fn sine(amplitude: f32, frequency: f32, samples: usize) -> Vec<f32> {
+        (0..samples)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * frequency * i as f32 / RATE).sin())
+            .collect()
+    }
+
+    #[test]
+    fn loud_clean_signal_is_not_clipped() {
+        // Peaks touch full scale but never stay there
+        let measurement = measure(&sine(0.999, 220.0, 16_000));
+        assert!(measurement.near_full_scale_fraction > 0.0);
+        assert!(measurement.longest_flat_run < MIN_FLAT_RUN);
+        assert!(!measurement.is_clipped());
+    }
+
+    #[test]
+    fn hard_clipped_signal_has_flat_tops() {
+        let clipped: Vec<f32> = sine(2.5, 220.0, 16_000)
+            .into_iter()
+            .map(|s| s.clamp(-1.0, 32767.0 / 32768.0))
+            .collect();
+        let measurement = measure(&clipped);
+        assert!(measurement.is_clipped());
+        assert!(measurement.longest_flat_run > 10);
+        assert!(measurement.flat_top_fraction > 0.5);
+    }
+
+    #[test]
+    fn quiet_signal_measures_zero() {
+        let measurement = measure(&sine(0.3, 440.0, 4_000));
+        assert_eq!(measurement, ClippingMeasurement::default());
+        assert_eq!(measure(&[]), ClippingMeasurement::default());
+    }
+
+    #[test]
+    fn warning_needs_sustained_clipping_and_clears_after_quiet_period() {
+        let clipped = ClippingMeasurement {
+            flat_top_fraction: 0.05,
+            ..Default::default()
+        };
+        let clean = ClippingMeasurement::default();
+        let start = Instant::now();
+        let mut detector = ClippingDetector::default();
+
+        assert!(!detector.update(&clipped, start));
+        assert!(!detector.update(&clean, start + Duration::from_millis(250)));
+        for i in 0..SUSTAINED_WINDOWS {
+            detector.update(
+                &clipped,
+                start + Duration::from_millis(500 + 250 * i as u64),
+            );
+        }
+        let last_clip = start + Duration::from_millis(500 + 250 * (SUSTAINED_WINDOWS - 1) as u64);
+        assert!(detector.update(&clean, last_clip + Duration::from_secs(1)));
+        assert!(!detector.update(&clean, last_clip + CLEAR_AFTER));
+    }
+}
@@ -1,6 +1,12 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use speechcore::{BackendConfig, BackendType};
 
+use crate::profiles::Profile;
+
 /// Audio sample rate in Hz - hardcoded to 16000 (required by Silero VAD)
 pub const SAMPLE_RATE: usize = 16000;
 
@@ -13,11 +19,58 @@ pub struct AudioProcessorConfig {
     /// This is the fundamental audio processing block size in samples
     /// Also used for visualization sample count
     pub buffer_size: usize,
+    /// Unmute the system microphone when recording starts and mute it when
+    /// recording stops (needs `pactl`)
+    pub sync_os_mute: bool,
 }
 
 impl Default for AudioProcessorConfig {
     fn default() -> Self {
-        Self { buffer_size: 1024 }
+        Self {
+            buffer_size: 1024,
+            sync_os_mute: false,
+        }
+    }
+}
+
+/// How the capture stream reaches the sound server
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AudioCaptureConfig {
+    /// Audio API used for capture
+    pub api: CaptureApi,
+    /// PipeWire node to record from: a `node.name`, `object.serial` or node
+    /// description (see `sonori --list-audio-devices`); unset uses the default source
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_object: Option<String>,
+}
+
+/// Audio API used for capture
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureApi {
+    /// PipeWire when its daemon is running, else PortAudio/ALSA (default)
+    #[default]
+    Auto,
+    /// A named PipeWire client, targetable by WirePlumber rules and echo-cancel
+    Pipewire,
+    /// The plain PortAudio/ALSA path, with no PipeWire stream properties
+    Portaudio,
+}
+
+impl CaptureApi {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CaptureApi::Auto => "auto",
+            CaptureApi::Pipewire => "pipewire",
+            CaptureApi::Portaudio => "portaudio",
+        }
+    }
+}
+
+impl std::fmt::Display for CaptureApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -27,18 +80,35 @@ impl Default for AudioProcessorConfig {
 pub struct GeneralConfig {
     /// Main model to use for transcription
     pub model: String,
+    /// Local model file or directory loaded instead of `model`; nothing is downloaded or converted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<std::path::PathBuf>,
     /// Language for transcription
     pub language: String,
     /// Transcription mode: "realtime" or "manual"
     pub transcription_mode: String,
+    /// Ask before downloading models at least this large (MB); a tenth of it on metered networks
+    pub download_confirm_threshold_mb: u64,
+    /// Never touch the network for models; fail with the missing file paths instead
+    pub offline: bool,
+    /// Serve `dev.sonori.Control` on the session bus
+    pub enable_dbus: bool,
+    /// Language of the interface (e.g. "de"); unset follows $LANG, then English
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui_language: Option<String>,
 }
 
 impl Default for GeneralConfig {
     fn default() -> Self {
         Self {
             model: "small.en".to_string(),
+            model_path: None,
             language: "en".to_string(),
             transcription_mode: "manual".to_string(),
+            download_confirm_threshold_mb: 500,
+            offline: false,
+            enable_dbus: false,
+            ui_language: None,
         }
     }
 }
@@ -56,6 +126,278 @@ pub enum ShortcutMode {
     PushToTalk,
 }
 
+/// How transcribed text reaches the focused application
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum InjectionMode {
+    /// Copy to the clipboard and send the paste shortcut (default)
+    #[default]
+    Paste,
+    /// Type the text key by key through the portal; the clipboard is left alone
+    Type,
+}
+
+/// How long copied text stays on the clipboard
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardPersistence {
+    /// Serve the text until another client copies or sonori exits (default)
+    #[default]
+    App,
+    /// Clear the clipboard after the first paste
+    PasteOnce,
+}
+
+/// Clipboard ownership for copied and pasted text (`[portal_config.clipboard]`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ClipboardConfig {
+    /// Also set the primary selection (middle-click paste)
+    pub primary_selection: bool,
+    /// How long copied text stays available
+    pub persistence: ClipboardPersistence,
+    /// On exit, wait up to this long for a clipboard manager to take over text
+    /// sonori still owns (0 = exit right away)
+    pub exit_handoff_ms: u64,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            primary_selection: false,
+            persistence: ClipboardPersistence::App,
+            exit_handoff_ms: 0,
+        }
+    }
+}
+
+/// Subtitle file format for session exports
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    /// SubRip (.srt, default)
+    #[default]
+    Srt,
+    /// WebVTT (.vtt)
+    Vtt,
+}
+
+/// Configuration for SRT/VTT export of a recording's transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubtitleConfig {
+    /// Format written by the export command
+    pub format: SubtitleFormat,
+    /// Maximum characters per subtitle line; longer text wraps onto further lines and cues
+    pub max_line_length: usize,
+}
+
+impl Default for SubtitleConfig {
+    fn default() -> Self {
+        Self {
+            format: SubtitleFormat::Srt,
+            max_line_length: 42,
+        }
+    }
+}
+
+/// When the transcript auto-save starts a new file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptRotation {
+    /// One file per local day (default)
+    #[default]
+    Daily,
+    /// One file per recording session
+    Session,
+}
+
+/// Configuration for auto-saving the transcript to files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscriptConfig {
+    /// Append every delivered segment to a transcript file
+    pub enabled: bool,
+    /// Directory for transcript files (default: ~/.local/share/sonori/transcripts)
+    /// Skipped during serialization if using the default value to allow per-user paths
+    #[serde(skip_serializing_if = "is_default_transcript_dir")]
+    pub output_dir: String,
+    /// File name with strftime placeholders, expanded when a new file is started
+    pub filename_template: String,
+    /// Seconds between flushes of buffered text (also flushed on shutdown)
+    pub flush_interval_secs: u64,
+    /// Start a new file per day or per recording session
+    pub rotation: TranscriptRotation,
+    /// KB of transcript kept in memory for the overlay; older text is spilled
+    /// to a file in `output_dir` (0 = keep everything in memory)
+    pub history_memory_kb: u32,
+}
+
+impl Default for TranscriptConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            output_dir: default_transcript_dir(),
+            filename_template: "sonori-%Y-%m-%d.txt".to_string(),
+            flush_interval_secs: 5,
+            rotation: TranscriptRotation::Daily,
+            history_memory_kb: 512,
+        }
+    }
+}
+
+/// Audio file format of archived recordings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingFormat {
+    /// 16-bit mono WAV at 16 kHz (default)
+    #[default]
+    Wav,
+    Flac,
+    OggOpus,
+}
+
+/// Which audio is archived
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingMode {
+    /// One file per manual session (default)
+    #[default]
+    Manual,
+    /// Manual sessions and the speech of real-time recordings
+    All,
+}
+
+/// How real-time speech is split into files with `mode = "all"`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimeRecordingLayout {
+    /// One file per speech segment (default)
+    #[default]
+    PerSegment,
+    /// One file per recording period
+    Continuous,
+}
+
+/// What happens to the file of a cancelled manual session
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CancelledRecording {
+    /// Remove the file (default)
+    #[default]
+    Delete,
+    /// Keep it, renamed to `<name>.cancelled.<ext>`
+    Mark,
+}
+
+/// Configuration for archiving recorded audio next to the transcripts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingConfig {
+    /// Save recorded audio to `directory`
+    pub enabled: bool,
+    /// Directory for audio files (default: ~/.local/share/sonori/recordings)
+    #[serde(skip_serializing_if = "is_default_recording_dir")]
+    pub directory: String,
+    pub format: RecordingFormat,
+    pub mode: RecordingMode,
+    pub realtime_layout: RealtimeRecordingLayout,
+    pub on_cancel: CancelledRecording,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: default_recording_dir(),
+            format: RecordingFormat::Wav,
+            mode: RecordingMode::Manual,
+            realtime_layout: RealtimeRecordingLayout::PerSegment,
+            on_cancel: CancelledRecording::Delete,
+        }
+    }
+}
+
+/// Configuration for the transcript streaming socket
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct StreamConfig {
+    /// Stream every delivered segment as JSON lines to socket clients
+    pub enabled: bool,
+    /// Socket path (default: $XDG_RUNTIME_DIR/sonori.sock)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket_path: Option<std::path::PathBuf>,
+}
+
+/// Configuration for the WebSocket captions server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptionsConfig {
+    /// Serve live captions over WebSocket, with a caption page on the same port
+    pub enabled: bool,
+    /// Address to listen on; keep the loopback address unless other machines need it
+    pub bind_address: String,
+    pub port: u16,
+    /// Seconds a caption stays on the page before it fades out
+    pub caption_lifetime_secs: f32,
+    /// Page origins (e.g. `https://overlay.example`) allowed to subscribe besides
+    /// the caption page itself
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for CaptionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "127.0.0.1".to_string(),
+            port: 7878,
+            caption_lifetime_secs: 6.0,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+/// Configuration for desktop notifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    /// Send desktop notifications through the XDG Notification portal
+    pub enabled: bool,
+    /// Notify when a recording starts
+    pub session_started: bool,
+    /// Notify with a preview and a "Copy" button when a recording's transcript is final
+    pub transcription_complete: bool,
+    /// Notify when the transcription backend or the overlay fails to start
+    pub backend_error: bool,
+    /// Notify when a model download finishes
+    pub download_complete: bool,
+    /// Minimum seconds between two notifications of the same event
+    pub min_interval_secs: u64,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            session_started: false,
+            transcription_complete: true,
+            backend_error: true,
+            download_complete: true,
+            min_interval_secs: 10,
+        }
+    }
+}
+
+/// Settings profiles: files in `~/.config/sonori/profiles/` layered over this config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfilesConfig {
+    /// Profile names offered in the settings window, in order; empty lists every
+    /// `*.toml` in the profiles directory
+    pub available: Vec<String>,
+}
+
 /// Configuration for XDG Desktop Portal features
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -63,14 +405,45 @@ pub struct PortalConfig {
     /// Whether to enable XDG Desktop Portal for input injection
     /// When enabled, allows the application to inject keystrokes via portal
     pub enable_xdg_portal: bool,
+    /// Paste each segment after copying it; when off, segments are only copied.
+    /// Toggled at runtime from the tray or `auto_paste_accelerator`
+    pub auto_paste: bool,
     /// Whether to enable xdg-desktop-portal Global Shortcuts
     pub enable_global_shortcuts: bool,
     /// Accelerator string for manual toggle (e.g., "<Super>Tab")
     pub manual_toggle_accelerator: String,
+    /// Accelerator for the privacy wipe (portal only; empty = not bound)
+    pub privacy_wipe_accelerator: String,
+    /// Accelerator that copies the transcript (portal only; empty = not bound)
+    pub copy_transcript_accelerator: String,
+    /// Accelerator that clears the transcript (portal only; empty = not bound)
+    pub reset_transcript_accelerator: String,
+    /// Accelerator that switches between manual and real-time mode (portal only; empty = not bound)
+    pub switch_mode_accelerator: String,
+    /// Accelerator that toggles the click-through overlay (portal only; empty = not bound)
+    pub click_through_accelerator: String,
+    /// Accelerator that toggles auto-paste (portal only; empty = not bound)
+    pub auto_paste_accelerator: String,
     /// Shortcut activation mode: Toggle (press to start/stop) or PushToTalk (hold to record)
     pub shortcut_mode: ShortcutMode,
+    /// Fall back to binding the shortcut through Hyprland/sway IPC when the portal is unavailable
+    pub enable_compositor_shortcuts: bool,
+    /// Last-resort fallback: read keyboards directly via evdev (requires `input` group membership)
+    pub enable_evdev_shortcuts: bool,
     /// Paste shortcut to use: "ctrl_shift_v" (default, works in terminals) or "ctrl_v"
     pub paste_shortcut: String,
+    /// Paste via the clipboard or type key by key (typing needs the XDG portal)
+    pub injection_mode: InjectionMode,
+    /// Delay between typed keys in `type` injection mode, in milliseconds
+    pub type_key_delay_ms: u64,
+    /// App-id patterns (`*` wildcard) to paste into; empty = every app not blocked
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub paste_allowlist: Vec<String>,
+    /// App-id patterns never pasted into; the text is only copied to the clipboard
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub paste_blocklist: Vec<String>,
+    /// Primary selection, persistence and clipboard manager handoff
+    pub clipboard: ClipboardConfig,
 }
 
 /// Configuration for real-time transcription mode
@@ -101,6 +474,10 @@ pub struct ManualModeConfig {
     /// Buffer size is calculated as: max_recording_duration_secs * sample_rate
     pub max_recording_duration_secs: u32,
 
+    /// Percentage of `max_recording_duration_secs` at which to warn that the
+    /// session is about to stop (default: 90; 0 = no warning)
+    pub timeout_warning_percent: f32,
+
     /// Whether to clear previous transcript when starting new session
     pub clear_on_new_session: bool,
 
@@ -129,18 +506,50 @@ impl Default for PortalConfig {
     fn default() -> Self {
         Self {
             enable_xdg_portal: true, // Default to enabled for better UX
+            auto_paste: true,
             enable_global_shortcuts: true,
             manual_toggle_accelerator: "<Super>backslash".to_string(),
+            privacy_wipe_accelerator: String::new(),
+            copy_transcript_accelerator: String::new(),
+            reset_transcript_accelerator: String::new(),
+            switch_mode_accelerator: String::new(),
+            click_through_accelerator: String::new(),
+            auto_paste_accelerator: String::new(),
             shortcut_mode: ShortcutMode::default(),
+            enable_compositor_shortcuts: true,
+            enable_evdev_shortcuts: false,
             paste_shortcut: "ctrl_shift_v".to_string(), // Default: Ctrl+Shift+V (works in terminals)
+            injection_mode: InjectionMode::Paste,
+            type_key_delay_ms: 5,
+            paste_allowlist: Vec::new(),
+            paste_blocklist: Vec::new(),
+            clipboard: ClipboardConfig::default(),
         }
     }
 }
 
+/// Longest manual session the recording buffer is sized for (one hour, about
+/// 230 MB of f32 samples at `SAMPLE_RATE`)
+pub const MAX_RECORDING_DURATION_CAP_SECS: u32 = 3600;
+
+impl ManualModeConfig {
+    /// `max_recording_duration_secs` limited to `MAX_RECORDING_DURATION_CAP_SECS`
+    pub fn recording_duration_secs(&self) -> u32 {
+        self.max_recording_duration_secs
+            .min(MAX_RECORDING_DURATION_CAP_SECS)
+    }
+
+    /// Megabytes of f32 samples a session buffer of `secs` seconds holds
+    pub fn buffer_megabytes(secs: u32) -> u64 {
+        secs as u64 * SAMPLE_RATE as u64 * std::mem::size_of::<f32>() as u64 / 1_000_000
+    }
+}
+
 impl Default for ManualModeConfig {
     fn default() -> Self {
         Self {
             max_recording_duration_secs: 120,
+            timeout_warning_percent: 90.0,
             clear_on_new_session: true,
             chunk_duration_seconds: 29.0, // 29s avoids edge case at exactly 30s boundary
             enable_chunk_overlap: true,   // Enable overlap by default
@@ -167,6 +576,42 @@ fn default_transcript_history_path() -> String {
     path.to_string_lossy().to_string()
 }
 
+/// Get the default transcript auto-save directory for the current user
+fn default_transcript_dir() -> String {
+    data_subdir("transcripts")
+}
+
+/// Get the default recording archive directory for the current user
+fn default_recording_dir() -> String {
+    data_subdir("recordings")
+}
+
+/// `name` below Sonori's XDG data directory
+fn data_subdir(name: &str) -> String {
+    let path = if let Some(data_home) = std::env::var_os("XDG_DATA_HOME") {
+        std::path::PathBuf::from(data_home)
+            .join("sonori")
+            .join(name)
+    } else if let Some(home) = std::env::var_os("HOME") {
+        std::path::PathBuf::from(home)
+            .join(".local")
+            .join("share")
+            .join("sonori")
+            .join(name)
+    } else {
+        std::path::PathBuf::from(name)
+    };
+    path.to_string_lossy().to_string()
+}
+
+fn is_default_transcript_dir(path: &str) -> bool {
+    path == default_transcript_dir()
+}
+
+fn is_default_recording_dir(path: &str) -> bool {
+    path == default_recording_dir()
+}
+
 /// Check if a transcript history path matches the default for the current user
 fn is_default_transcript_history_path(path: &str) -> bool {
     path == default_transcript_history_path()
@@ -188,6 +633,33 @@ pub struct DebugConfig {
     /// Skipped during serialization if using the default value to allow per-user paths
     #[serde(skip_serializing_if = "is_default_transcript_history_path")]
     pub transcript_history_path: String,
+    /// Number of past sessions kept in memory for the History tab and the CLI `h` command
+    pub session_history_limit: usize,
+    /// Whether to write an anonymous per-session usage log (no transcript text or audio)
+    /// to ~/.local/state/sonori/usage.jsonl; read it back with `sonori --usage-summary`
+    pub usage_log: bool,
+    /// File the run's transcription statistics are written to on shutdown
+    /// (JSON, or a CSV row appended when it ends in `.csv`; empty = off)
+    pub stats_export_path: String,
+    /// Include one record per segment in the exported JSON statistics
+    pub stats_detailed: bool,
+    /// Print one line per segment with its latency broken down by stage
+    pub log_latency_per_segment: bool,
+    /// `tracing` filter for diagnostics, e.g. `"speechcore=debug,info"`
+    /// (empty = Sonori and speechcore at `info`; `RUST_LOG`, `--verbose` and `-q`
+    /// take precedence)
+    pub log_level: String,
+    /// File diagnostics are also written to, rotated at 10 MB (empty = stderr only)
+    pub log_file: String,
+}
+
+/// Configuration for dictation usage goals
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageConfig {
+    /// Words to dictate per day; shown as a progress bar in the overlay and in
+    /// the tray tooltip (0 = no goal)
+    pub daily_word_goal: u64,
 }
 
 /// Configuration for sound settings
@@ -208,12 +680,19 @@ impl Default for DebugConfig {
             recording_dir: "recordings".to_string(),
             save_transcript_history: false,
             transcript_history_path: default_transcript_history_path(),
+            session_history_limit: 20,
+            usage_log: false,
+            stats_export_path: String::new(),
+            stats_detailed: false,
+            log_latency_per_segment: false,
+            log_level: String::new(),
+            log_file: String::new(),
         }
     }
 }
 
 /// Configuration for transcription post-processing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct PostProcessConfig {
     /// Enable post-processing of transcriptions
@@ -224,6 +703,27 @@ pub struct PostProcessConfig {
     pub remove_trailing_dashes: bool,
     /// Normalize whitespace (collapse multiple spaces, remove leading/trailing)
     pub normalize_whitespace: bool,
+    /// Paths to experimental stage plugins (cdylibs), run after the built-in stages
+    /// in order; requires the `post-stage-plugins` build feature
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<String>,
+    /// Write spoken numbers, amounts, times and dates as digits (English only)
+    pub inverse_text_normalization: bool,
+    /// Turn spoken commands ("comma", "new line") into punctuation and line breaks
+    pub dictation_commands_enabled: bool,
+    /// Spoken command -> inserted text; replaces the English defaults when set
+    pub dictation_commands: std::collections::BTreeMap<String, String>,
+    /// Regexes for phantom phrases; a committed segment matching one is dropped
+    pub hallucination_patterns: Vec<String>,
+    /// Drop a committed segment that repeats the previous one
+    pub drop_repeated_segments: bool,
+    /// Words or regexes rewritten in every transcript
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub replacements: Vec<Replacement>,
+    /// Replace common swear words with asterisks
+    pub mask_profanity: bool,
+    /// Restore sentence punctuation and capitalization for backends that emit neither
+    pub punctuation_mode: PunctuationMode,
 }
 
 impl Default for PostProcessConfig {
@@ -233,10 +733,56 @@ impl Default for PostProcessConfig {
             remove_leading_dashes: true,
             remove_trailing_dashes: true,
             normalize_whitespace: true,
+            plugins: Vec::new(),
+            inverse_text_normalization: false,
+            dictation_commands_enabled: false,
+            dictation_commands: crate::post_stages::dictation::default_commands(),
+            hallucination_patterns: crate::post_stages::hallucination::default_patterns(),
+            drop_repeated_segments: true,
+            replacements: Vec::new(),
+            mask_profanity: false,
+            punctuation_mode: PunctuationMode::Off,
         }
     }
 }
 
+/// How `punctuation` restores punctuation and capitalization
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PunctuationMode {
+    #[default]
+    Off,
+    /// Sentence ends at segment ends, capitalized sentence starts and vocabulary names
+    Rules,
+}
+
+impl PunctuationMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PunctuationMode::Off => "off",
+            PunctuationMode::Rules => "rules",
+        }
+    }
+}
+
+impl std::fmt::Display for PunctuationMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One `post_process_config.replacements` entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replacement {
+    /// Word or phrase, or a regex when `is_regex` is set; matched ignoring case
+    pub pattern: String,
+    /// Inserted text; `$1`-style capture references work for regexes
+    #[serde(default)]
+    pub replacement: String,
+    #[serde(default)]
+    pub is_regex: bool,
+}
+
 /// Configuration for transcription enhancement ("Magic Mode")
 /// Uses llama.cpp with GGUF models for GPU-accelerated inference
 pub const DEFAULT_ENHANCEMENT_SYSTEM_PROMPT: &str = "Rewrite the transcript into clean, natural text while preserving the speaker's meaning. Fix obvious transcription artifacts, punctuation, and casing. Do not add facts, explanations, or commentary.";
@@ -252,11 +798,19 @@ pub struct EnhancementConfig {
     pub enabled: bool,
     /// Model identifier (HuggingFace format): "owner/repo/filename.gguf"
     pub model: Option<String>,
+    /// Local GGUF file used instead of `model`; never downloaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<std::path::PathBuf>,
     /// Custom system prompt for the enhancement model
     #[serde(default = "default_enhancement_system_prompt")]
     pub system_prompt: Option<String>,
     /// Maximum tokens to generate (default: 256)
     pub max_tokens: usize,
+    /// Seconds to wait for the enhanced text before delivering the raw segment
+    pub timeout_secs: u64,
+    /// Named alternative system prompts, selectable per session by `auto_rules`
+    #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub templates: std::collections::BTreeMap<String, String>,
 }
 
 impl Default for EnhancementConfig {
@@ -264,8 +818,11 @@ impl Default for EnhancementConfig {
         Self {
             enabled: false,
             model: None,
+            model_path: None,
             system_prompt: default_enhancement_system_prompt(),
             max_tokens: 256,
+            timeout_secs: 10,
+            templates: std::collections::BTreeMap::new(),
         }
     }
 }
@@ -279,6 +836,44 @@ impl Default for SoundConfig {
     }
 }
 
+/// Rule that applies session overrides when its condition matches the local time.
+/// Rules are checked in order; the first match wins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AutoRule {
+    /// Name shown when the rule fires (defaults to "rule N")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// When the rule applies
+    pub when: AutoRuleCondition,
+    /// What the rule changes for the session
+    pub set: AutoRuleOverrides,
+}
+
+/// Condition of an auto rule; empty fields match always
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AutoRuleCondition {
+    /// Days the rule applies, e.g. ["mon", "tue"] or ["mon-fri"]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub weekdays: Vec<String>,
+    /// Local time range "HH:MM-HH:MM"; ranges may wrap past midnight
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+}
+
+/// Session overrides applied by an auto rule
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AutoRuleOverrides {
+    /// Transcription model, switched via a backend reload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Name of an `enhancement_config.templates` system prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
 /// VAD sensitivity presets for different acoustic environments
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum VadSensitivity {
@@ -376,17 +971,33 @@ pub struct DisplayConfig {
     /// TopLeft, TopCenter, TopRight, MiddleLeft, MiddleCenter, MiddleRight, Custom
     pub window_position: WindowPosition,
 
+    /// Output (wl_output name, e.g. "DP-1") to show the overlay on; unset or
+    /// disconnected lets the compositor choose. See `sonori --list-outputs`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_name: Option<String>,
+
     /// Position used when window_position is Custom.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_window_position: Option<CustomWindowPosition>,
+    /// Fall back to a software adapter (e.g. llvmpipe) when no hardware GPU works
+    pub allow_software_rendering: bool,
+
+    /// Overlay render resolution relative to the window size (0.5-1.0).
+    /// Lower values save GPU memory on weak GPUs; the frame is scaled up to the window.
+    /// Unset picks automatically: 0.5 on software renderers, 0.75 on adapters with
+    /// very low limits, 1.0 otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub render_scale: Option<f32>,
 }
 
-/// Configuration for system tray behavior
+/// Configuration for system tray and overlay window behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct WindowBehaviorConfig {
     /// Whether to show the application icon in the system tray
     pub show_in_system_tray: bool,
+    /// Start with pointer events passing through the overlay (toggle from the tray or a shortcut)
+    pub click_through: bool,
 }
 
 impl Default for DisplayConfig {
@@ -396,6 +1007,9 @@ impl Default for DisplayConfig {
             target_fps: 60,                    // Cap at 60 FPS when vsync disabled
             window_position: WindowPosition::default(),
             custom_window_position: None,
+            allow_software_rendering: true,
+            render_scale: None,
+            output_name: None,
         }
     }
 }
@@ -404,6 +1018,7 @@ impl Default for WindowBehaviorConfig {
     fn default() -> Self {
         Self {
             show_in_system_tray: true, // Show tray icon by default
+            click_through: false,
         }
     }
 }
@@ -424,6 +1039,19 @@ pub enum SpectrogramSkin {
     Meter,
 }
 
+/// Frequency axis of the spectrogram
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SpectrogramScale {
+    /// The sample envelope across the bars, without frequency analysis
+    #[default]
+    Linear,
+    /// FFT bins spread on the mel scale
+    Mel,
+    /// FFT bins spread logarithmically
+    Log,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ResolvedVisualTheme {
     pub speaking_color: [f32; 4],
@@ -457,6 +1085,48 @@ impl VisualThemePreset {
     }
 }
 
+/// Built-in overlay palettes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    /// Light text on a dark panel (default)
+    #[default]
+    Dark,
+    /// Dark text on a light panel, for light wallpapers
+    Light,
+}
+
+/// Overlay colors (`[theme]`): a preset, then per-key overrides as [r, g, b, a] (0.0-1.0)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub preset: ThemePreset,
+    /// Transcript text while idle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_idle: Option<[f32; 4]>,
+    /// Transcript text while speech is detected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_speaking: Option<[f32; 4]>,
+    /// Transcript text of low-confidence segments (once the runtime reports confidence)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_low_confidence: Option<[f32; 4]>,
+    /// Transcript and spectrogram panels; alpha scales their opacity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub panel_bg: Option<[f32; 4]>,
+    /// Background of the button row; alpha is its opacity while hovered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub button_bg: Option<[f32; 4]>,
+    /// Highlights in the settings window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent: Option<[f32; 4]>,
+    /// Spectrogram bars at low amplitude
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spectrogram_low: Option<[f32; 4]>,
+    /// Spectrogram bars at full amplitude
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spectrogram_high: Option<[f32; 4]>,
+}
+
 /// Configuration for UI appearance settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -467,6 +1137,12 @@ pub struct UiConfig {
     /// Spectrogram rendering skin
     pub spectrogram_skin: SpectrogramSkin,
 
+    /// Spectrogram frequency axis: linear, mel or log
+    pub spectrogram_scale: SpectrogramScale,
+
+    /// Dynamic range in dB mapped onto bar height and color with the mel and log scales
+    pub spectrogram_dynamic_range_db: f32,
+
     /// Base font size for transcript text (default: 10.0)
     /// Actual rendered size is font_size * display_scale
     pub font_size: f32,
@@ -492,6 +1168,8 @@ impl Default for UiConfig {
         Self {
             visual_theme: VisualThemePreset::Focus,
             spectrogram_skin: SpectrogramSkin::Bars,
+            spectrogram_scale: SpectrogramScale::Linear,
+            spectrogram_dynamic_range_db: 60.0,
             font_size: 10.0,
             speaking_color: [0.1, 0.9, 0.5, 1.0], // Teal-green
             idle_color: [1.0, 0.85, 0.15, 1.0],   // Gold
@@ -567,14 +1245,14 @@ impl DisplayConfig {
         } else {
             // Fallback to Fifo (guaranteed to be available), or first available
             if available_modes.contains(&wgpu::PresentMode::Fifo) {
-                println!(
-                    "Warning: Preferred vsync mode '{}' not available, falling back to Fifo",
+                tracing::warn!(
+                    "Preferred vsync mode '{}' not available, falling back to Fifo",
                     self.vsync_mode
                 );
                 wgpu::PresentMode::Fifo
             } else {
-                println!(
-                    "Warning: Preferred vsync mode '{}' not available, using first available mode",
+                tracing::warn!(
+                    "Preferred vsync mode '{}' not available, using first available mode",
                     self.vsync_mode
                 );
                 available_modes
@@ -598,6 +1276,9 @@ pub struct AppConfig {
     /// Audio processing configuration
     pub audio_processor_config: AudioProcessorConfig,
 
+    /// Capture API and PipeWire source selection
+    pub audio_capture_config: AudioCaptureConfig,
+
     /// Real-time transcription mode configuration
     pub realtime_mode_config: RealtimeModeConfig,
 
@@ -649,6 +1330,37 @@ pub struct AppConfig {
     /// UI appearance configuration
     pub ui_config: UiConfig,
 
+    /// Overlay colors
+    pub theme: ThemeConfig,
+
+    /// Daily word goal
+    pub usage: UsageConfig,
+
+    /// Subtitle export of a recording's transcript
+    pub subtitle_config: SubtitleConfig,
+
+    /// Transcript auto-save to rotating files
+    pub transcript_config: TranscriptConfig,
+
+    /// JSON-lines transcript stream over a Unix socket
+    pub stream_config: StreamConfig,
+
+    /// Live captions over WebSocket, e.g. for an OBS browser source
+    pub captions_config: CaptionsConfig,
+
+    /// Audio archive of recordings
+    pub recording_config: RecordingConfig,
+
+    /// Desktop notifications for key events
+    pub notifications_config: NotificationsConfig,
+
+    /// Settings profiles
+    pub profiles: ProfilesConfig,
+
+    /// Time-of-day rules that override the model/template for a session
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub auto_rules: Vec<AutoRule>,
+
     /// Deprecated legacy field - use backend_config instead
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compute_type: Option<String>,
@@ -669,6 +1381,7 @@ impl Default for AppConfig {
             general_config: GeneralConfig::default(),
             backend_config,
             audio_processor_config: AudioProcessorConfig::default(),
+            audio_capture_config: AudioCaptureConfig::default(),
             realtime_mode_config: RealtimeModeConfig::default(),
             manual_mode_config: ManualModeConfig::default(),
             vad_config: VadConfigSerde::default(),
@@ -686,6 +1399,16 @@ impl Default for AppConfig {
             post_process_config: PostProcessConfig::default(),
             enhancement_config: EnhancementConfig::default(),
             ui_config: UiConfig::default(),
+            theme: ThemeConfig::default(),
+            usage: UsageConfig::default(),
+            subtitle_config: SubtitleConfig::default(),
+            transcript_config: TranscriptConfig::default(),
+            stream_config: StreamConfig::default(),
+            captions_config: CaptionsConfig::default(),
+            recording_config: RecordingConfig::default(),
+            notifications_config: NotificationsConfig::default(),
+            profiles: ProfilesConfig::default(),
+            auto_rules: Vec::new(),
             compute_type: None,
             device: None,
         }
@@ -694,6 +1417,11 @@ impl Default for AppConfig {
 
 impl From<AppConfig> for speechcore::SpeechConfig {
     fn from(config: AppConfig) -> Self {
+        // whisper.cpp is the only backend whose options carry a prompt
+        let whisper_prompt = config
+            .common_transcription_options
+            .effective_initial_prompt()
+            .or(config.whisper_cpp_options.initial_prompt);
         Self {
             general_config: speechcore::config::GeneralConfig {
                 model: config.general_config.model,
@@ -709,7 +1437,7 @@ impl From<AppConfig> for speechcore::SpeechConfig {
                 max_segment_count: config.realtime_mode_config.max_segment_count,
             },
             manual_mode_config: speechcore::config::ManualModeConfig {
-                max_recording_duration_secs: config.manual_mode_config.max_recording_duration_secs,
+                max_recording_duration_secs: config.manual_mode_config.recording_duration_secs(),
                 clear_on_new_session: config.manual_mode_config.clear_on_new_session,
                 chunk_duration_seconds: config.manual_mode_config.chunk_duration_seconds,
                 enable_chunk_overlap: config.manual_mode_config.enable_chunk_overlap,
@@ -735,7 +1463,7 @@ impl From<AppConfig> for speechcore::SpeechConfig {
                 suppress_blank: config.whisper_cpp_options.suppress_blank,
                 no_context: config.whisper_cpp_options.no_context,
                 max_tokens: config.whisper_cpp_options.max_tokens,
-                initial_prompt: config.whisper_cpp_options.initial_prompt,
+                initial_prompt: whisper_prompt,
             },
             moonshine_options: speechcore::config::MoonshineOptions {
                 enable_cache: config.moonshine_options.enable_cache,
@@ -746,11 +1474,19 @@ impl From<AppConfig> for speechcore::SpeechConfig {
             },
             debug_config: speechcore::config::DebugConfig {
                 log_stats_enabled: config.debug_config.log_stats_enabled,
-                save_manual_audio_debug: config.debug_config.save_manual_audio_debug,
-                recording_dir: config.debug_config.recording_dir,
+                // speechcore's manual-session WAV writer also backs the recording archive
+                save_manual_audio_debug: config.debug_config.save_manual_audio_debug
+                    || config.recording_config.enabled,
+                recording_dir: if config.recording_config.enabled {
+                    config.recording_config.directory
+                } else {
+                    config.debug_config.recording_dir
+                },
             },
             post_process_config: speechcore::config::PostProcessConfig {
-                enabled: config.post_process_config.enabled,
+                // Applied app-side by `post_stages::PostPipeline` so custom stages
+                // can be ordered around the built-in ones
+                enabled: false,
                 remove_leading_dashes: config.post_process_config.remove_leading_dashes,
                 remove_trailing_dashes: config.post_process_config.remove_trailing_dashes,
                 normalize_whitespace: config.post_process_config.normalize_whitespace,
@@ -779,6 +1515,10 @@ pub struct CommonTranscriptionOptions {
     pub beam_size: usize,
     /// Beam search patience factor
     pub patience: f32,
+    /// Text that biases whisper.cpp decoding toward its style and spelling
+    pub initial_prompt: Option<String>,
+    /// Words and names appended to the initial prompt (e.g. jargon, proper nouns)
+    pub vocabulary: Vec<String>,
 }
 
 impl Default for CommonTranscriptionOptions {
@@ -786,6 +1526,31 @@ impl Default for CommonTranscriptionOptions {
         Self {
             beam_size: 5,
             patience: 1.0,
+            initial_prompt: None,
+            vocabulary: Vec::new(),
+        }
+    }
+}
+
+impl CommonTranscriptionOptions {
+    /// `initial_prompt` followed by the comma-separated vocabulary, or `None` if both are empty
+    pub fn effective_initial_prompt(&self) -> Option<String> {
+        let vocabulary: Vec<&str> = self
+            .vocabulary
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let prompt = self
+            .initial_prompt
+            .as_deref()
+            .map(str::trim)
+            .filter(|prompt| !prompt.is_empty());
+        match (prompt, vocabulary.is_empty()) {
+            (None, true) => None,
+            (Some(prompt), true) => Some(prompt.to_string()),
+            (None, false) => Some(vocabulary.join(", ")),
+            (Some(prompt), false) => Some(format!("{} {}", prompt, vocabulary.join(", "))),
         }
     }
 }
@@ -811,6 +1576,43 @@ pub const WHISPER_ENTROPY_THOLD: f32 = 2.4;
 pub const WHISPER_LOGPROB_THOLD: f32 = -1.0;
 pub const WHISPER_NO_SPEECH_THOLD: f32 = 0.6;
 
+/// GPU API whisper.cpp should run on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuBackend {
+    /// Vulkan, then CUDA, then the CPU, whichever is available first (default)
+    #[default]
+    Auto,
+    Vulkan,
+    Cuda,
+    /// Always the CPU
+    None,
+}
+
+impl GpuBackend {
+    pub const ALL: [GpuBackend; 4] = [
+        GpuBackend::Auto,
+        GpuBackend::Vulkan,
+        GpuBackend::Cuda,
+        GpuBackend::None,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GpuBackend::Auto => "auto",
+            GpuBackend::Vulkan => "vulkan",
+            GpuBackend::Cuda => "cuda",
+            GpuBackend::None => "none",
+        }
+    }
+}
+
+impl std::fmt::Display for GpuBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Whisper.cpp-specific transcription options
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -822,6 +1624,9 @@ pub struct WhisperCppOptions {
     /// Initial prompt to condition the model (used internally for chunk continuity)
     #[serde(skip)]
     pub initial_prompt: Option<String>,
+    /// GPU API requested, checked against this build and the devices found. Only
+    /// decides whether the GPU is used: the API itself is fixed by the build
+    pub gpu_backend: GpuBackend,
 }
 
 impl Default for WhisperCppOptions {
@@ -832,6 +1637,7 @@ impl Default for WhisperCppOptions {
             no_context: true,     // Disable context to prevent double transcriptions
             max_tokens: 0,        // No limit
             initial_prompt: None, // Set dynamically for chunk continuity
+            gpu_backend: GpuBackend::Auto,
         }
     }
 }
@@ -905,9 +1711,10 @@ impl AppConfig {
                 && !self.backend_config.gpu_enabled;
 
             if is_default_config {
-                println!(
+                tracing::info!(
                     "Migrating legacy config fields (compute_type={}, device={}) to backend_config",
-                    compute_type, device
+                    compute_type,
+                    device
                 );
 
                 #[cfg(feature = "backend-ctranslate2")]
@@ -920,7 +1727,7 @@ impl AppConfig {
 
                 #[cfg(not(feature = "backend-ctranslate2"))]
                 {
-                    println!(
+                    tracing::info!(
                         "Skipping legacy CTranslate2 config migration because backend-ctranslate2 is disabled"
                     );
                 }
@@ -929,7 +1736,7 @@ impl AppConfig {
 
         // Ensure whisper.cpp does not reuse context across sessions (prevents duplicate transcriptions)
         if !self.whisper_cpp_options.no_context {
-            println!(
+            tracing::info!(
                 "Enabling whisper_cpp_options.no_context to prevent cross-session duplication"
             );
             self.whisper_cpp_options.no_context = true;
@@ -950,14 +1757,14 @@ fn find_config_path() -> Option<std::path::PathBuf> {
     if let Ok(custom_path) = std::env::var("SONORI_CONFIG_PATH") {
         let path = PathBuf::from(custom_path);
         if path.exists() {
-            println!(
+            tracing::info!(
                 "Loading configuration from SONORI_CONFIG_PATH: {}",
                 path.display()
             );
             return Some(path);
         } else {
-            eprintln!(
-                "Warning: SONORI_CONFIG_PATH set to {} but file does not exist. Falling back to defaults.",
+            tracing::warn!(
+                "SONORI_CONFIG_PATH set to {} but file does not exist. Falling back to defaults.",
                 path.display()
             );
             return None;
@@ -986,7 +1793,7 @@ fn find_config_path() -> Option<std::path::PathBuf> {
     None
 }
 
-fn user_config_path() -> Option<std::path::PathBuf> {
+pub(crate) fn user_config_path() -> Option<std::path::PathBuf> {
     use std::path::PathBuf;
 
     if let Some(config_home) = std::env::var_os("XDG_CONFIG_HOME") {
@@ -1024,7 +1831,7 @@ fn ensure_user_config() {
 
     // Create config directory
     if let Err(e) = std::fs::create_dir_all(user_config_dir) {
-        eprintln!("Failed to create config directory: {}", e);
+        tracing::warn!("Failed to create config directory: {}", e);
         return;
     }
 
@@ -1032,14 +1839,17 @@ fn ensure_user_config() {
     let default_config = AppConfig::default();
     match toml::to_string_pretty(&default_config) {
         Ok(toml_string) => match std::fs::write(&user_config_path, toml_string) {
-            Ok(_) => println!("Created default config at: {}", user_config_path.display()),
-            Err(e) => eprintln!("Failed to write default config: {}", e),
+            Ok(_) => tracing::info!("Created default config at: {}", user_config_path.display()),
+            Err(e) => tracing::warn!("Failed to write default config: {}", e),
         },
-        Err(e) => eprintln!("Failed to serialize default config: {}", e),
+        Err(e) => tracing::warn!("Failed to serialize default config: {}", e),
     }
 }
 
 /// Helper function to read the application configuration
+///
+/// Parses the file; call it once at launch (or in one-shot commands) and share
+/// the result through a [`ConfigStore`].
 pub fn read_app_config() -> AppConfig {
     let (config, _path) = read_app_config_with_path();
     config
@@ -1054,11 +1864,11 @@ pub fn read_app_config_with_path() -> (AppConfig, Option<std::path::PathBuf>) {
 
     let config_str = match config_path.as_ref() {
         Some(path) => {
-            println!("Loading configuration from: {}", path.display());
+            tracing::info!("Loading configuration from: {}", path.display());
             match std::fs::read_to_string(path) {
                 Ok(content) => content,
                 Err(e) => {
-                    println!(
+                    tracing::warn!(
                         "Failed to read config from {}: {}. Using default configuration.",
                         path.display(),
                         e
@@ -1068,7 +1878,7 @@ pub fn read_app_config_with_path() -> (AppConfig, Option<std::path::PathBuf>) {
             }
         }
         None => {
-            println!("No config.toml found. Using default configuration.");
+            tracing::info!("No config.toml found. Using default configuration.");
             return (AppConfig::default(), None);
         }
     };
@@ -1079,14 +1889,14 @@ pub fn read_app_config_with_path() -> (AppConfig, Option<std::path::PathBuf>) {
 
             if let (Some(path), Some(updated_toml)) = (config_path.as_ref(), updated_toml) {
                 if let Err(e) = std::fs::write(path, updated_toml) {
-                    eprintln!("Failed to update config with new defaults: {}", e);
+                    tracing::warn!("Failed to update config with new defaults: {}", e);
                 }
             }
 
             config
         }
         Err(e) => {
-            println!(
+            tracing::warn!(
                 "Failed to parse config.toml: {}. Using default configuration.",
                 e
             );
@@ -1117,6 +1927,198 @@ pub fn write_app_config(config: &AppConfig) -> Result<(), String> {
     Ok(())
 }
 
+/// Parse config file contents like [`read_app_config`], without writing defaults back
+pub fn parse_app_config(config_str: &str) -> Result<AppConfig, String> {
+    let (mut config, _updated_toml) =
+        build_config_with_defaults(config_str).map_err(|e| e.to_string())?;
+    config.migrate_legacy_config();
+    Ok(config)
+}
+
+/// The configuration parsed once at launch, shared by every runtime reader and writer.
+///
+/// Readers take whole snapshots, so a component never combines fields from two
+/// versions; writers persist and publish under the same lock, so concurrent
+/// updates cannot interleave or lose each other's changes. Runtime code must go
+/// through the store instead of re-reading the config file; edits to the file
+/// reach it through `config_watch`. Every publish bumps [`ConfigStore::generation`],
+/// so subsystems can poll for changes without cloning the configuration.
+#[derive(Clone)]
+pub struct ConfigStore {
+    config: Arc<RwLock<AppConfig>>,
+    /// Profile layered over the config file; see [`crate::profiles`]
+    profile: Arc<RwLock<Option<Profile>>>,
+    generation: Arc<AtomicU64>,
+    persist: bool,
+}
+
+impl ConfigStore {
+    /// A store whose updates are written back to the config file
+    pub fn new(config: AppConfig) -> Self {
+        Self {
+            config: Arc::new(RwLock::new(config)),
+            profile: Arc::new(RwLock::new(None)),
+            generation: Arc::new(AtomicU64::new(0)),
+            persist: true,
+        }
+    }
+
+    /// A store that never touches the config file
+    pub fn in_memory(config: AppConfig) -> Self {
+        Self {
+            config: Arc::new(RwLock::new(config)),
+            profile: Arc::new(RwLock::new(None)),
+            generation: Arc::new(AtomicU64::new(0)),
+            persist: false,
+        }
+    }
+
+    /// Mark `profile` as layered into the configuration the store was created with
+    pub fn with_profile(self, profile: Option<Profile>) -> Self {
+        *self.profile.write() = profile;
+        self
+    }
+
+    /// A consistent copy of the current configuration
+    pub fn snapshot(&self) -> AppConfig {
+        self.config.read().clone()
+    }
+
+    /// Bumped on every published change
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Name of the active profile, if any
+    pub fn profile_name(&self) -> Option<String> {
+        self.profile
+            .read()
+            .as_ref()
+            .map(|profile| profile.name().to_string())
+    }
+
+    /// Apply `change` to a copy, persist it, then publish it; on a write error
+    /// the published configuration stays unchanged. With a profile active, only
+    /// the changed fields are written: to the profile when it sets them, to the
+    /// config file otherwise.
+    pub fn update(&self, change: impl FnOnce(&mut AppConfig)) -> Result<AppConfig, String> {
+        let mut config = self.config.write();
+        let mut next = config.clone();
+        change(&mut next);
+        if self.persist {
+            match self.profile.write().as_mut() {
+                Some(profile) => write_layered(&config, &next, profile)?,
+                None => write_app_config(&next)?,
+            }
+        }
+        *config = next.clone();
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        Ok(next)
+    }
+
+    /// Publish a configuration derived from the current one without writing
+    /// the config file, e.g. one read back from it; on an error nothing changes
+    pub fn publish(
+        &self,
+        change: impl FnOnce(&AppConfig) -> Result<AppConfig, String>,
+    ) -> Result<(), String> {
+        let mut config = self.config.write();
+        *config = change(&config)?;
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Persist and publish a complete configuration
+    pub fn replace(&self, next: AppConfig) -> Result<(), String> {
+        self.update(|config| *config = next).map(|_| ())
+    }
+
+    /// `base`, e.g. re-read from the config file, with the active profile applied
+    pub fn layered(&self, base: AppConfig) -> Result<AppConfig, String> {
+        match self.profile.read().as_ref() {
+            Some(profile) => profile.apply(&base),
+            None => Ok(base),
+        }
+    }
+
+    /// The current configuration with `profile` in place of the active one.
+    /// Fields the active profile set go back to the config file's values; other
+    /// runtime changes, such as command-line overrides, are kept.
+    pub fn profile_config(&self, profile: Option<&Profile>) -> Result<AppConfig, String> {
+        let mut config = config_value(&self.config.read());
+        if let Some(active) = self.profile.read().as_ref() {
+            let base = config_value(&read_base_config());
+            for field in active.fields() {
+                set_field(&mut config, &field, field_value(&base, &field).cloned());
+            }
+        }
+        let config: AppConfig = config
+            .try_into()
+            .map_err(|e: toml::de::Error| e.to_string())?;
+        match profile {
+            Some(profile) => profile.apply(&config),
+            None => Ok(config),
+        }
+    }
+
+    /// Publish `config`, built by [`Self::profile_config`], and make `profile`
+    /// the active one; nothing is written
+    pub fn switch_profile(&self, profile: Option<Profile>, config: AppConfig) {
+        let mut current = self.config.write();
+        *current = config;
+        *self.profile.write() = profile;
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// The config file without a profile, for reverting a profile's fields
+fn read_base_config() -> AppConfig {
+    find_config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| parse_app_config(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Write the fields that differ between `previous` and `next`: to `profile`
+/// when it sets them, to the config file otherwise
+fn write_layered(
+    previous: &AppConfig,
+    next: &AppConfig,
+    profile: &mut Profile,
+) -> Result<(), String> {
+    let next_value = config_value(next);
+    let (profile_fields, base_fields): (Vec<String>, Vec<String>) = changed_fields(previous, next)
+        .into_iter()
+        .partition(|field| profile.sets(field));
+
+    if !profile_fields.is_empty() {
+        for field in &profile_fields {
+            profile.set(field, field_value(&next_value, field).cloned());
+        }
+        profile.save()?;
+    }
+    if base_fields.is_empty() {
+        return Ok(());
+    }
+
+    let config_path = find_config_path()
+        .or_else(user_config_path)
+        .ok_or_else(|| {
+            "Unable to determine config path. Set SONORI_CONFIG_PATH or HOME.".to_string()
+        })?;
+    let mut base = match std::fs::read_to_string(&config_path) {
+        Ok(contents) => toml::from_str::<toml::Value>(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", config_path.display(), e))?,
+        Err(_) => config_value(&AppConfig::default()),
+    };
+    for field in &base_fields {
+        set_field(&mut base, field, field_value(&next_value, field).cloned());
+    }
+    let toml_string =
+        toml::to_string_pretty(&base).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    std::fs::write(&config_path, toml_string).map_err(|e| format!("Failed to write config: {}", e))
+}
+
 fn build_config_with_defaults(
     config_str: &str,
 ) -> Result<(AppConfig, Option<String>), toml::de::Error> {
@@ -1143,7 +2145,8 @@ fn build_config_with_defaults(
     Ok((config, updated_toml))
 }
 
-fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+/// Merge `overlay` into `base` field by field; nested tables are merged, other values replaced
+pub(crate) fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
     match (base, overlay) {
         (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
             for (key, value) in overlay_table {
@@ -1161,10 +2164,125 @@ fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
     }
 }
 
+/// A configuration as a TOML tree, for field-wise diffs and merges
+pub(crate) fn config_value(config: &AppConfig) -> toml::Value {
+    toml::Value::try_from(config).unwrap_or_else(|_| toml::Value::Table(Default::default()))
+}
+
+/// Dotted paths of the fields that differ between two configurations
+pub(crate) fn changed_fields(current: &AppConfig, next: &AppConfig) -> Vec<String> {
+    let mut fields = Vec::new();
+    collect_changed_fields(
+        "",
+        Some(&config_value(current)),
+        Some(&config_value(next)),
+        &mut fields,
+    );
+    fields
+}
+
+fn collect_changed_fields(
+    path: &str,
+    current: Option<&toml::Value>,
+    next: Option<&toml::Value>,
+    fields: &mut Vec<String>,
+) {
+    match (current, next) {
+        (Some(toml::Value::Table(current)), Some(toml::Value::Table(next))) => {
+            let mut keys: Vec<&String> = current.keys().chain(next.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_changed_fields(&field, current.get(key), next.get(key), fields);
+            }
+        }
+        (current, next) if current != next => fields.push(path.to_string()),
+        _ => {}
+    }
+}
+
+/// Value at a dotted path
+pub(crate) fn field_value<'a>(root: &'a toml::Value, field: &str) -> Option<&'a toml::Value> {
+    field.split('.').try_fold(root, |value, key| value.get(key))
+}
+
+/// Set the value at a dotted path, creating tables on the way; `None` removes it
+pub(crate) fn set_field(root: &mut toml::Value, field: &str, value: Option<toml::Value>) {
+    let path: Vec<&str> = field.split('.').collect();
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut table = root;
+    for key in parents {
+        let Some(parent) = table.as_table_mut() else {
+            return;
+        };
+        table = parent
+            .entry(key.to_string())
+            .or_insert_with(|| toml::Value::Table(Default::default()));
+    }
+    let Some(table) = table.as_table_mut() else {
+        return;
+    };
+    match value {
+        Some(value) => {
+            table.insert(last.to_string(), value);
+        }
+        None => {
+            table.remove(*last);
+        }
+    }
+}
+
+/// `current` with `fields` taken from `next`
+pub(crate) fn with_fields(
+    current: &AppConfig,
+    next: &AppConfig,
+    fields: &[String],
+) -> Result<AppConfig, String> {
+    let mut merged = config_value(current);
+    let next = config_value(next);
+    for field in fields {
+        set_field(&mut merged, field, field_value(&next, field).cloned());
+    }
+    merged
+        .try_into()
+        .map_err(|e: toml::de::Error| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn recording_duration_is_capped() {
+        let mut manual = ManualModeConfig::default();
+        assert_eq!(manual.recording_duration_secs(), 120);
+        manual.max_recording_duration_secs = u32::MAX;
+        assert_eq!(
+            manual.recording_duration_secs(),
+            MAX_RECORDING_DURATION_CAP_SECS
+        );
+        assert_eq!(ManualModeConfig::buffer_megabytes(120), 7);
+    }
+
+    #[test]
+    fn unsupported_punctuation_mode_is_rejected() {
+        let parse = |mode: &str| {
+            toml::from_str::<PostProcessConfig>(&format!("punctuation_mode = \"{}\"", mode))
+        };
+        assert_eq!(
+            parse("rules").unwrap().punctuation_mode,
+            PunctuationMode::Rules
+        );
+        assert!(parse("model").is_err());
+    }
+
     #[test]
     fn ui_theme_defaults_to_focus() {
         let config = AppConfig::default();
@@ -1177,6 +2295,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn vocabulary_is_joined_into_the_prompt() {
+        let mut options = CommonTranscriptionOptions::default();
+        assert_eq!(options.effective_initial_prompt(), None);
+
+        options.vocabulary = vec!["Kubernetes".into(), " ".into(), "wgpu".into()];
+        assert_eq!(
+            options.effective_initial_prompt().as_deref(),
+            Some("Kubernetes, wgpu")
+        );
+
+        options.initial_prompt = Some("Notes from the platform standup.".into());
+        assert_eq!(
+            options.effective_initial_prompt().as_deref(),
+            Some("Notes from the platform standup. Kubernetes, wgpu")
+        );
+
+        let mut config = AppConfig::default();
+        config.common_transcription_options = options;
+        let speech_config = speechcore::SpeechConfig::from(config);
+        assert_eq!(
+            speech_config.whisper_cpp_options.initial_prompt.as_deref(),
+            Some("Notes from the platform standup. Kubernetes, wgpu")
+        );
+    }
+
+    #[test]
+    fn store_readers_never_observe_mixed_versions() {
+        // Chunking and VAD settings are read together by the capture pipeline;
+        // every snapshot must carry all of them from the same version
+        fn version_of(config: &AppConfig) -> Option<u32> {
+            let chunk = config.manual_mode_config.chunk_duration_seconds as u32;
+            let overlap = config.manual_mode_config.chunk_overlap_seconds as u32;
+            let hangover = config.vad_config.hangover_frames as u32;
+            let silence = config.vad_config.silence_tolerance_frames as u32;
+            (chunk == overlap && overlap == hangover && hangover == silence).then_some(chunk)
+        }
+        fn set_version(config: &mut AppConfig, version: u32) {
+            config.manual_mode_config.chunk_duration_seconds = version as f32;
+            config.manual_mode_config.chunk_overlap_seconds = version as f32;
+            config.vad_config.hangover_frames = version as usize;
+            config.vad_config.silence_tolerance_frames = version as usize;
+        }
+
+        let mut initial = AppConfig::default();
+        set_version(&mut initial, 1);
+        let store = ConfigStore::in_memory(initial);
+
+        std::thread::scope(|scope| {
+            let writer = scope.spawn(|| {
+                for version in 2..=200 {
+                    store.update(|config| set_version(config, version)).unwrap();
+                }
+            });
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let mut last = 1;
+                    for _ in 0..500 {
+                        let version = version_of(&store.snapshot()).expect("mixed config");
+                        assert!(version >= last, "config went backwards");
+                        last = version;
+                    }
+                });
+            }
+            writer.join().unwrap();
+        });
+
+        assert_eq!(version_of(&store.snapshot()), Some(200));
+    }
+
     #[test]
     fn default_backend_is_whisper_cpp() {
         let config = AppConfig::default();
@@ -0,0 +1,241 @@
+//! Live reload of the config file.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::config::{changed_fields, parse_app_config, with_fields, AppConfig, ConfigStore};
+use crate::i18n::tr;
+
+/// Editors save in several steps; wait for the burst to end before reading
+const SETTLE_DELAY: Duration = Duration::from_millis(250);
+
+/// What it takes for a changed field to take effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadKind {
+    /// Picked up by the running app
+    Live,
+    /// Needs the speech backend to be reloaded
+    Backend,
+    Restart,
+}
+
+/// Which part of a config change applies how
+fn reload_kind(field: &str) -> ReloadKind {
+    let mut parts = field.split('.');
+    let section = parts.next().unwrap_or_default();
+    let key = parts.next().unwrap_or_default();
+    match (section, key) {
+        // The surface and adapter are set up once
+        ("display_config", "vsync_mode" | "allow_software_rendering") => ReloadKind::Restart,
+        ("post_process_config" | "sound_config" | "ui_config" | "theme" | "display_config", _) => {
+            ReloadKind::Live
+        }
+        ("portal_config", "auto_paste" | "injection_mode")
+        | ("window_behavior_config", "click_through")
+        | ("profiles", _) => ReloadKind::Live,
+        ("general_config", "model" | "model_path" | "language")
+        | (
+            "backend_config"
+            | "common_transcription_options"
+            | "ctranslate2_options"
+            | "whisper_cpp_options"
+            | "moonshine_options"
+            | "parakeet_options"
+            | "nemotron_options",
+            _,
+        ) => ReloadKind::Backend,
+        // The speech runtime reads the VAD settings once when it starts and
+        // offers no way to change them while it runs
+        ("vad_config", _) => ReloadKind::Restart,
+        _ => ReloadKind::Restart,
+    }
+}
+
+/// Changed fields between two configurations, as dotted paths
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub live: Vec<String>,
+    pub backend: Vec<String>,
+    pub restart: Vec<String>,
+}
+
+impl ConfigDiff {
+    pub fn between(current: &AppConfig, next: &AppConfig) -> Self {
+        let mut diff = Self::default();
+        for field in changed_fields(current, next) {
+            match reload_kind(&field) {
+                ReloadKind::Live => diff.live.push(field),
+                ReloadKind::Backend => diff.backend.push(field),
+                ReloadKind::Restart => diff.restart.push(field),
+            }
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live.is_empty() && self.backend.is_empty() && self.restart.is_empty()
+    }
+}
+
+/// Re-read the config file and publish what can change while running.
+/// `file` is the file's previous contents: fields that need a reload or restart
+/// are only reported when they were edited in the file, not when the running
+/// configuration differs through command-line overrides or the settings window.
+fn reload(path: &Path, store: &ConfigStore, file: &mut AppConfig) {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            // Mid-save the file can be missing; the next event reads it again
+            tracing::debug!("Config reload: cannot read {}: {}", path.display(), e);
+            return;
+        }
+    };
+    let next = match parse_app_config(&contents).and_then(|next| store.layered(next)) {
+        Ok(next) => next,
+        Err(e) => {
+            tracing::warn!(
+                "Config reload: {} has errors, keeping the running configuration: {}",
+                path.display(),
+                e
+            );
+            crate::toast::show(tr("toast.config_invalid"));
+            return;
+        }
+    };
+
+    let mut diff = ConfigDiff::between(&store.snapshot(), &next);
+    let edited = ConfigDiff::between(file, &next);
+    diff.backend.retain(|field| edited.backend.contains(field));
+    diff.restart.retain(|field| edited.restart.contains(field));
+    *file = next.clone();
+    if diff.is_empty() {
+        tracing::debug!("Config reload: no changes");
+        return;
+    }
+    if !diff.backend.is_empty() {
+        tracing::warn!(
+            "Config reload: {} need a backend reload; restart Sonori to apply",
+            diff.backend.join(", ")
+        );
+    }
+    if !diff.restart.is_empty() {
+        tracing::warn!("Config reload: {} need a restart", diff.restart.join(", "));
+    }
+    if diff.live.is_empty() {
+        crate::toast::show(tr("toast.config_needs_restart"));
+        return;
+    }
+    match store.publish(|current| with_fields(current, &next, &diff.live)) {
+        Ok(()) => {
+            tracing::info!("Config reloaded: {}", diff.live.join(", "));
+            crate::toast::show(tr("toast.config_reloaded"));
+        }
+        Err(e) => tracing::warn!("Config reload failed: {}", e),
+    }
+}
+
+/// Watch `path` and publish its live-reloadable changes to `store`
+pub fn spawn(path: PathBuf, store: ConfigStore) {
+    let Some(dir) = path.parent().map(Path::to_path_buf) else {
+        return;
+    };
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Vec<PathBuf>>();
+    let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+        Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+            let _ = tx.send(event.paths);
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!("Config watcher error: {}", e),
+    });
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            tracing::warn!("Config live reload disabled: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+        tracing::warn!(
+            "Config live reload disabled: cannot watch {}: {}",
+            dir.display(),
+            e
+        );
+        return;
+    }
+    tracing::info!("Watching {} for changes", path.display());
+    let mut file = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| parse_app_config(&contents).ok())
+        .and_then(|file| store.layered(file).ok())
+        .unwrap_or_else(|| store.snapshot());
+
+    tokio::spawn(async move {
+        // Dropping the watcher stops the events
+        let _watcher = watcher;
+        while let Some(paths) = rx.recv().await {
+            if !paths
+                .iter()
+                .any(|changed| changed.file_name() == path.file_name())
+            {
+                continue;
+            }
+            tokio::time::sleep(SETTLE_DELAY).await;
+            while rx.try_recv().is_ok() {}
+            reload(&path, &store, &mut file);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_are_sorted_by_what_they_need() {
+        let current = AppConfig::default();
+        let mut next = current.clone();
+        next.sound_config.volume = 0.2;
+        next.post_process_config.mask_profanity = !current.post_process_config.mask_profanity;
+        next.display_config.vsync_mode = "Disabled".to_string();
+        next.backend_config.threads += 1;
+        next.vad_config.hangover_frames += 10;
+
+        let diff = ConfigDiff::between(&current, &next);
+        assert_eq!(
+            diff.live,
+            ["post_process_config.mask_profanity", "sound_config.volume"]
+        );
+        assert_eq!(diff.backend, ["backend_config.threads"]);
+        assert_eq!(
+            diff.restart,
+            ["display_config.vsync_mode", "vad_config.hangover_frames"]
+        );
+        assert!(ConfigDiff::between(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn only_live_fields_are_taken_over() {
+        let current = AppConfig::default();
+        let mut next = current.clone();
+        next.sound_config.enabled = !current.sound_config.enabled;
+        next.vad_config.hangover_frames += 10;
+
+        let diff = ConfigDiff::between(&current, &next);
+        let merged = with_fields(&current, &next, &diff.live).unwrap();
+        assert_eq!(merged.sound_config.enabled, next.sound_config.enabled);
+        assert_eq!(
+            merged.vad_config.hangover_frames,
+            current.vad_config.hangover_frames
+        );
+    }
+
+    #[test]
+    fn unparsable_files_are_rejected() {
+        assert!(parse_app_config("[sound_config\nvolume = 0.5").is_err());
+        assert!(parse_app_config("[sound_config]\nvolume = \"loud\"").is_err());
+        let config = parse_app_config("[sound_config]\nvolume = 0.5").unwrap();
+        assert_eq!(config.sound_config.volume, 0.5);
+    }
+}
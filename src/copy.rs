@@ -1,22 +1,545 @@
+//! Clipboard ownership without external tools.
+//!
+//! On Wayland the selection is set through wlr-data-control on a dedicated
+//! connection; on X11 (or Xwayland when the compositor lacks data-control) a
+//! hidden window owns `CLIPBOARD`. Either way a background thread serves paste
+//! requests until another client takes the selection, or until the first paste
+//! with `persistence = "paste_once"`. `[portal_config.clipboard]` also selects
+//! the primary selection and how long to wait at exit for a clipboard manager.
+
+use std::io::Write;
 use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::{wl_registry, wl_seat};
+use wayland_client::{event_created_child, Connection, Dispatch, Proxy, QueueHandle};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_device_v1::{
+    self, ZwlrDataControlDeviceV1,
+};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_manager_v1::{
+    self, ZwlrDataControlManagerV1,
+};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_offer_v1::{
+    self, ZwlrDataControlOfferV1,
+};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_source_v1::{
+    self, ZwlrDataControlSourceV1,
+};
+use x11rb::connection::Connection as _;
+use x11rb::protocol::xproto::{
+    self, AtomEnum, ConnectionExt as _, CreateWindowAux, EventMask, PropMode, SelectionNotifyEvent,
+    SelectionRequestEvent, WindowClass,
+};
+use x11rb::protocol::Event;
+use x11rb::wrapper::ConnectionExt as _;
+
+use crate::config::{ClipboardConfig, ClipboardPersistence};
+
+/// Text MIME types and X11 targets offered for the copied text
+const TEXT_MIME_TYPES: [&str; 5] = [
+    "text/plain;charset=utf-8",
+    "text/plain",
+    "UTF8_STRING",
+    "STRING",
+    "TEXT",
+];
+
+/// Data-control version that added the primary selection
+const PRIMARY_SELECTION_SINCE: u32 = 2;
+
+static OPTIONS: Mutex<Option<ClipboardConfig>> = Mutex::new(None);
+
+/// Selections (clipboard and primary) sonori currently serves
+static OWNED_SELECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Use `config` for every following copy
+pub fn configure(config: &ClipboardConfig) {
+    *OPTIONS.lock() = Some(config.clone());
+}
+
+fn options() -> ClipboardConfig {
+    OPTIONS.lock().clone().unwrap_or_default()
+}
+
+/// Give a clipboard manager up to `exit_handoff_ms` to take over text sonori still
+/// owns, so it outlives the process. Returns right away when nothing is owned.
+pub fn hand_off_on_exit() {
+    let timeout = Duration::from_millis(options().exit_handoff_ms);
+    if timeout.is_zero() || OWNED_SELECTIONS.load(Ordering::SeqCst) == 0 {
+        return;
+    }
+
+    let deadline = Instant::now() + timeout;
+    while OWNED_SELECTIONS.load(Ordering::SeqCst) > 0 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    if OWNED_SELECTIONS.load(Ordering::SeqCst) > 0 {
+        tracing::info!("No clipboard manager took over; copied text leaves with sonori");
+    }
+}
+
+/// What to do with a paste request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OfferAction {
+    Send,
+    /// Send, then give up every held selection (`paste_once`)
+    SendAndRelease,
+    /// Already pasted once; answer with nothing
+    Refuse,
+}
+
+/// Lifecycle of one copy: how many selections it still holds and whether it
+/// still answers paste requests
+#[derive(Debug)]
+struct OfferState {
+    persistence: ClipboardPersistence,
+    held: usize,
+    pasted: bool,
+}
+
+impl OfferState {
+    fn new(persistence: ClipboardPersistence, held: usize) -> Self {
+        OWNED_SELECTIONS.fetch_add(held, Ordering::SeqCst);
+        Self {
+            persistence,
+            held,
+            pasted: false,
+        }
+    }
+
+    fn on_request(&mut self) -> OfferAction {
+        match (self.persistence, self.pasted) {
+            (ClipboardPersistence::App, _) => OfferAction::Send,
+            (ClipboardPersistence::PasteOnce, false) => {
+                self.pasted = true;
+                OfferAction::SendAndRelease
+            }
+            (ClipboardPersistence::PasteOnce, true) => OfferAction::Refuse,
+        }
+    }
 
-/// Simple Wayland connection for clipboard operations
+    /// Another client took one of the held selections
+    fn on_cancelled(&mut self) {
+        if self.held > 0 {
+            self.held -= 1;
+            OWNED_SELECTIONS.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// The connection is gone; every held selection is lost
+    fn abandon(&mut self) {
+        OWNED_SELECTIONS.fetch_sub(self.held, Ordering::SeqCst);
+        self.held = 0;
+    }
+
+    fn finished(&self) -> bool {
+        self.held == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClipboardBackend {
+    Wayland,
+    X11,
+}
+
+/// Backends to try in order: Wayland first, then X11 (also reached through
+/// Xwayland, which compositors sync with the Wayland clipboard)
+fn clipboard_backends(wayland_display: bool, x_display: bool) -> Vec<ClipboardBackend> {
+    let mut backends = Vec::new();
+    if wayland_display {
+        backends.push(ClipboardBackend::Wayland);
+    }
+    if x_display {
+        backends.push(ClipboardBackend::X11);
+    }
+    backends
+}
+
+fn env_set(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
+/// Clipboard access shared by the GUI and CLI modes
 pub struct WlCopy;
 
 impl WlCopy {
-    /// Copy text to clipboard using wl-copy
+    /// Put text on the clipboard (and the primary selection if configured)
     pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
-        match Command::new("wl-copy").arg(text).status() {
-            Ok(status) if status.success() => {
-                println!("Copied '{}' to clipboard", text);
-                Ok(())
+        let backends = clipboard_backends(env_set("WAYLAND_DISPLAY"), env_set("DISPLAY"));
+        if backends.is_empty() {
+            return Err(
+                "no display to copy to: neither WAYLAND_DISPLAY nor DISPLAY is set \
+                 (started from a TTY or over SSH?)"
+                    .to_string(),
+            );
+        }
+
+        let options = options();
+        let mut errors = Vec::new();
+        for backend in backends {
+            let result = match backend {
+                ClipboardBackend::Wayland => copy_wayland(text, &options),
+                ClipboardBackend::X11 => copy_x11(text, &options),
+            };
+            match result {
+                Ok(()) => {
+                    tracing::debug!("Copied '{}' to clipboard", text);
+                    return Ok(());
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+        Err(errors.join("; "))
+    }
+}
+
+fn copy_wayland(text: &str, options: &ClipboardConfig) -> Result<(), String> {
+    let connection = Connection::connect_to_env()
+        .map_err(|e| format!("Wayland clipboard unavailable (WAYLAND_DISPLAY): {}", e))?;
+    let (globals, mut queue) = registry_queue_init::<ClipboardSource>(&connection)
+        .map_err(|e| format!("Wayland registry failed: {}", e))?;
+    let qh = queue.handle();
+    let manager: ZwlrDataControlManagerV1 = globals.bind(&qh, 1..=2, ()).map_err(|_| {
+        "the Wayland compositor does not support wlr-data-control; \
+         set DISPLAY to copy through Xwayland"
+            .to_string()
+    })?;
+    let seat: wl_seat::WlSeat = globals
+        .bind(&qh, 1..=7, ())
+        .map_err(|_| "the Wayland compositor has no seat to copy on".to_string())?;
+
+    // A data-control source serves exactly one selection
+    let new_source = || {
+        let source = manager.create_data_source(&qh, ());
+        for mime_type in TEXT_MIME_TYPES {
+            source.offer(mime_type.to_string());
+        }
+        source
+    };
+
+    let device = manager.get_data_device(&seat, &qh, ());
+    device.set_selection(Some(&new_source()));
+    let mut held = 1;
+    if options.primary_selection {
+        if manager.version() >= PRIMARY_SELECTION_SINCE {
+            device.set_primary_selection(Some(&new_source()));
+            held += 1;
+        } else {
+            tracing::warn!(
+                "The compositor's data-control has no primary selection; clipboard only"
+            );
+        }
+    }
+
+    let mut state = ClipboardSource {
+        text: text.to_string(),
+        offer: OfferState::new(options.persistence, held),
+        device: device.clone(),
+        primary: held > 1,
+    };
+    if let Err(e) = queue.roundtrip(&mut state) {
+        state.offer.abandon();
+        return Err(format!("Wayland clipboard failed: {}", e));
+    }
+    if state.offer.finished() {
+        return Err("the Wayland compositor rejected the clipboard selection".to_string());
+    }
+
+    std::thread::Builder::new()
+        .name("clipboard-wayland".to_string())
+        .spawn(move || {
+            while !state.offer.finished() {
+                if queue.blocking_dispatch(&mut state).is_err() {
+                    state.offer.abandon();
+                }
+            }
+            device.destroy();
+            manager.destroy();
+        })
+        .map_err(|e| format!("Failed to start clipboard thread: {}", e))?;
+    Ok(())
+}
+
+/// Serves the copied text until another client takes the selection
+struct ClipboardSource {
+    text: String,
+    offer: OfferState,
+    device: ZwlrDataControlDeviceV1,
+    /// Whether a primary selection source was set as well
+    primary: bool,
+}
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for ClipboardSource {
+    fn event(
+        _: &mut Self,
+        _: &wl_registry::WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<wl_seat::WlSeat, ()> for ClipboardSource {
+    fn event(
+        _: &mut Self,
+        _: &wl_seat::WlSeat,
+        _: wl_seat::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrDataControlManagerV1, ()> for ClipboardSource {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlManagerV1,
+        _: zwlr_data_control_manager_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrDataControlDeviceV1, ()> for ClipboardSource {
+    fn event(
+        state: &mut Self,
+        _: &ZwlrDataControlDeviceV1,
+        event: zwlr_data_control_device_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        // Offers from other clients are never read
+        match event {
+            zwlr_data_control_device_v1::Event::Selection { id: Some(offer) }
+            | zwlr_data_control_device_v1::Event::PrimarySelection { id: Some(offer) } => {
+                offer.destroy();
+            }
+            zwlr_data_control_device_v1::Event::Finished => state.offer.abandon(),
+            _ => {}
+        }
+    }
+
+    event_created_child!(ClipboardSource, ZwlrDataControlDeviceV1, [
+        zwlr_data_control_device_v1::EVT_DATA_OFFER_OPCODE => (ZwlrDataControlOfferV1, ()),
+    ]);
+}
+
+impl Dispatch<ZwlrDataControlOfferV1, ()> for ClipboardSource {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlOfferV1,
+        _: zwlr_data_control_offer_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrDataControlSourceV1, ()> for ClipboardSource {
+    fn event(
+        state: &mut Self,
+        source: &ZwlrDataControlSourceV1,
+        event: zwlr_data_control_source_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_data_control_source_v1::Event::Send { fd, .. } => {
+                let action = state.offer.on_request();
+                if action == OfferAction::Refuse {
+                    // Closing the pipe unsent hands the reader an empty paste
+                    return;
+                }
+                let mut pipe = std::fs::File::from(fd);
+                if let Err(e) = pipe.write_all(state.text.as_bytes()) {
+                    tracing::warn!("Failed to send clipboard contents: {}", e);
+                }
+                if action == OfferAction::SendAndRelease {
+                    // The compositor answers with `cancelled` for each source
+                    state.device.set_selection(None);
+                    if state.primary {
+                        state.device.set_primary_selection(None);
+                    }
+                }
+            }
+            zwlr_data_control_source_v1::Event::Cancelled => {
+                source.destroy();
+                state.offer.on_cancelled();
             }
-            Ok(status) => Err(format!("wl-copy failed with status {}", status)),
-            Err(e) => Err(format!("Error executing wl-copy: {}", e)),
+            _ => {}
         }
     }
 }
 
+fn copy_x11(text: &str, options: &ClipboardConfig) -> Result<(), String> {
+    let x11_error = |e: &dyn std::fmt::Display| format!("X11 clipboard failed: {}", e);
+    let (connection, screen_num) =
+        x11rb::connect(None).map_err(|e| format!("X11 clipboard unavailable (DISPLAY): {}", e))?;
+    let root = connection.setup().roots[screen_num].root;
+
+    let intern = |name: &str| -> Result<xproto::Atom, String> {
+        Ok(connection
+            .intern_atom(false, name.as_bytes())
+            .map_err(|e| x11_error(&e))?
+            .reply()
+            .map_err(|e| x11_error(&e))?
+            .atom)
+    };
+    let clipboard = intern("CLIPBOARD")?;
+    let targets = intern("TARGETS")?;
+    let text_targets = TEXT_MIME_TYPES
+        .iter()
+        .map(|name| intern(name))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let window = connection.generate_id().map_err(|e| x11_error(&e))?;
+    connection
+        .create_window(
+            x11rb::COPY_DEPTH_FROM_PARENT,
+            window,
+            root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_OUTPUT,
+            x11rb::COPY_FROM_PARENT,
+            &CreateWindowAux::new(),
+        )
+        .map_err(|e| x11_error(&e))?;
+
+    let mut selections = vec![clipboard];
+    if options.primary_selection {
+        selections.push(AtomEnum::PRIMARY.into());
+    }
+    for &selection in &selections {
+        connection
+            .set_selection_owner(window, selection, x11rb::CURRENT_TIME)
+            .map_err(|e| x11_error(&e))?;
+    }
+    let owner = connection
+        .get_selection_owner(clipboard)
+        .map_err(|e| x11_error(&e))?
+        .reply()
+        .map_err(|e| x11_error(&e))?
+        .owner;
+    if owner != window {
+        return Err("the X server did not hand over the clipboard".to_string());
+    }
+
+    let text = text.to_string();
+    let mut offer = OfferState::new(options.persistence, selections.len());
+    std::thread::Builder::new()
+        .name("clipboard-x11".to_string())
+        .spawn(move || {
+            while !offer.finished() {
+                match connection.wait_for_event() {
+                    Ok(Event::SelectionRequest(request)) => {
+                        // TARGETS queries are not pastes and leave a one-shot offer alone
+                        let action = if text_targets.contains(&request.target) {
+                            offer.on_request()
+                        } else {
+                            OfferAction::Send
+                        };
+                        let payload = (action != OfferAction::Refuse).then_some(text.as_bytes());
+                        let served = serve_x11_request(
+                            &connection,
+                            &request,
+                            targets,
+                            &text_targets,
+                            payload,
+                        );
+                        if let Err(e) = served {
+                            tracing::warn!("Failed to send clipboard contents: {}", e);
+                        }
+                        if action == OfferAction::SendAndRelease {
+                            // The server answers with SelectionClear for each
+                            for &selection in &selections {
+                                let _ = connection.set_selection_owner(
+                                    x11rb::NONE,
+                                    selection,
+                                    x11rb::CURRENT_TIME,
+                                );
+                            }
+                            let _ = connection.flush();
+                        }
+                    }
+                    Ok(Event::SelectionClear(_)) => offer.on_cancelled(),
+                    Ok(_) => {}
+                    Err(_) => offer.abandon(),
+                }
+            }
+        })
+        .map_err(|e| format!("Failed to start clipboard thread: {}", e))?;
+    Ok(())
+}
+
+/// Answer one selection request; `text` is `None` once a one-shot offer was pasted
+fn serve_x11_request(
+    connection: &impl x11rb::connection::Connection,
+    request: &SelectionRequestEvent,
+    targets: xproto::Atom,
+    text_targets: &[xproto::Atom],
+    text: Option<&[u8]>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Obsolete clients pass no property and expect the target to be used
+    let mut property = if request.property == x11rb::NONE {
+        request.target
+    } else {
+        request.property
+    };
+
+    match text {
+        _ if request.target == targets => {
+            let mut supported = vec![targets];
+            supported.extend_from_slice(text_targets);
+            connection.change_property32(
+                PropMode::REPLACE,
+                request.requestor,
+                property,
+                AtomEnum::ATOM,
+                &supported,
+            )?;
+        }
+        Some(text) if text_targets.contains(&request.target) => {
+            connection.change_property8(
+                PropMode::REPLACE,
+                request.requestor,
+                property,
+                request.target,
+                text,
+            )?;
+        }
+        _ => property = x11rb::NONE,
+    }
+
+    let notify = SelectionNotifyEvent {
+        response_type: xproto::SELECTION_NOTIFY_EVENT,
+        sequence: 0,
+        time: request.time,
+        requestor: request.requestor,
+        selection: request.selection,
+        target: request.target,
+        property,
+    };
+    connection.send_event(false, request.requestor, EventMask::NO_EVENT, notify)?;
+    connection.flush()?;
+    Ok(())
+}
+
 /// Simulate a paste keystroke using available tools (wtype → dotool fallback chain)
 pub fn paste_via_keystroke(paste_shortcut: &str) -> Result<(), String> {
     if paste_shortcut == "ctrl_v" {
@@ -36,7 +559,6 @@ fn paste_ctrl_v() -> Result<(), String> {
         }
     }
 
-    use std::io::Write;
     use std::process::Stdio;
     let mut child = Command::new("dotool")
         .stdin(Stdio::piped())
@@ -67,7 +589,6 @@ fn paste_ctrl_shift_v() -> Result<(), String> {
         }
     }
 
-    use std::io::Write;
     use std::process::Stdio;
     let mut child = Command::new("dotool")
         .stdin(Stdio::piped())
@@ -85,3 +606,42 @@ fn paste_ctrl_shift_v() -> Result<(), String> {
         Err(format!("dotool exited with status {}", status))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clipboard_backends_prefer_wayland_then_x11() {
+        assert_eq!(
+            clipboard_backends(true, true),
+            vec![ClipboardBackend::Wayland, ClipboardBackend::X11]
+        );
+        assert_eq!(clipboard_backends(false, true), vec![ClipboardBackend::X11]);
+        assert!(clipboard_backends(false, false).is_empty());
+    }
+
+    #[test]
+    fn app_persistence_serves_until_every_selection_is_taken() {
+        let mut offer = OfferState::new(ClipboardPersistence::App, 2);
+
+        assert_eq!(offer.on_request(), OfferAction::Send);
+        assert_eq!(offer.on_request(), OfferAction::Send);
+        offer.on_cancelled();
+        assert!(!offer.finished());
+        offer.on_cancelled();
+        assert!(offer.finished());
+    }
+
+    #[test]
+    fn paste_once_releases_after_the_first_paste() {
+        let mut offer = OfferState::new(ClipboardPersistence::PasteOnce, 1);
+
+        assert_eq!(offer.on_request(), OfferAction::SendAndRelease);
+        assert_eq!(offer.on_request(), OfferAction::Refuse);
+        assert!(!offer.finished());
+        offer.on_cancelled();
+        offer.on_cancelled();
+        assert!(offer.finished());
+    }
+}
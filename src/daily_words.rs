@@ -0,0 +1,232 @@
+//! Words dictated per local day, persisted across restarts, with an optional daily goal.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDate};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// Days of history kept for streak calculation
+const RETAINED_DAYS: i64 = 400;
+
+fn day_key(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Word counts keyed by local date ("YYYY-MM-DD", which sorts chronologically)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyWords {
+    pub days: BTreeMap<String, u64>,
+}
+
+impl DailyWords {
+    pub fn add(&mut self, date: NaiveDate, words: u64) {
+        *self.days.entry(day_key(date)).or_insert(0) += words;
+        if let Some(cutoff) = date.checked_sub_signed(chrono::Duration::days(RETAINED_DAYS)) {
+            let cutoff = day_key(cutoff);
+            self.days.retain(|day, _| *day > cutoff);
+        }
+    }
+
+    /// Take back words that were wiped; never goes below zero
+    pub fn subtract(&mut self, date: NaiveDate, words: u64) {
+        let key = day_key(date);
+        if let Some(count) = self.days.get_mut(&key) {
+            *count = count.saturating_sub(words);
+            if *count == 0 {
+                self.days.remove(&key);
+            }
+        }
+    }
+
+    pub fn words_on(&self, date: NaiveDate) -> u64 {
+        self.days.get(&day_key(date)).copied().unwrap_or(0)
+    }
+
+    /// Consecutive days up to `today` on which the goal was met.
+    ///
+    /// Today only breaks the streak once it is over, so an unfinished day
+    /// continues yesterday's streak.
+    pub fn streak(&self, today: NaiveDate, goal: u64) -> u32 {
+        if goal == 0 {
+            return 0;
+        }
+        let mut day = if self.words_on(today) >= goal {
+            today
+        } else {
+            match today.pred_opt() {
+                Some(yesterday) => yesterday,
+                None => return 0,
+            }
+        };
+        let mut streak = 0;
+        while self.words_on(day) >= goal {
+            streak += 1;
+            match day.pred_opt() {
+                Some(previous) => day = previous,
+                None => break,
+            }
+        }
+        streak
+    }
+}
+
+/// `daily_words.json` next to the usage log
+pub fn daily_words_path() -> Option<PathBuf> {
+    crate::usage_log::usage_log_path().map(|path| path.with_file_name("daily_words.json"))
+}
+
+/// Load the counters, starting empty if the file is missing or unreadable
+pub fn load(path: &Path) -> DailyWords {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(words: &DailyWords, path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(words).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+struct Tracker {
+    words: DailyWords,
+    goal: u64,
+    path: Option<PathBuf>,
+}
+
+static TRACKER: Mutex<Option<Tracker>> = Mutex::new(None);
+
+/// Load persisted counts and set the daily goal (0 = no goal)
+pub fn init(goal: u64) {
+    let path = daily_words_path();
+    let words = path.as_deref().map(load).unwrap_or_default();
+    *TRACKER.lock() = Some(Tracker { words, goal, path });
+}
+
+/// Count delivered text towards today's total
+pub fn record_words(text: &str) {
+    let count = text.split_whitespace().count() as u64;
+    if count == 0 {
+        return;
+    }
+    let mut tracker = TRACKER.lock();
+    let Some(tracker) = tracker.as_mut() else {
+        return;
+    };
+    tracker.words.add(Local::now().date_naive(), count);
+    if let Some(path) = &tracker.path {
+        if let Err(e) = save(&tracker.words, path) {
+            tracing::warn!("Failed to save daily word count: {}", e);
+        }
+    }
+}
+
+/// Remove wiped words from today's total
+pub fn forget_words(count: u64) {
+    if count == 0 {
+        return;
+    }
+    let mut tracker = TRACKER.lock();
+    let Some(tracker) = tracker.as_mut() else {
+        return;
+    };
+    tracker.words.subtract(Local::now().date_naive(), count);
+    if let Some(path) = &tracker.path {
+        if let Err(e) = save(&tracker.words, path) {
+            tracing::warn!("Failed to save daily word count: {}", e);
+        }
+    }
+}
+
+/// Words dictated today and the daily goal, if a goal is set
+pub fn today() -> Option<(u64, u64)> {
+    let tracker = TRACKER.lock();
+    let tracker = tracker.as_ref().filter(|t| t.goal > 0)?;
+    Some((
+        tracker.words.words_on(Local::now().date_naive()),
+        tracker.goal,
+    ))
+}
+
+fn with_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// e.g. "2,340 / 5,000 words today"
+pub fn format_progress(words: u64, goal: u64) -> String {
+    format!(
+        "{} / {} words today",
+        with_thousands(words),
+        with_thousands(goal)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn counts_roll_over_by_local_date() {
+        let mut words = DailyWords::default();
+        words.add(date(1), 120);
+        words.add(date(1), 30);
+        words.add(date(2), 5);
+
+        assert_eq!(words.words_on(date(1)), 150);
+        assert_eq!(words.words_on(date(2)), 5);
+        assert_eq!(words.words_on(date(3)), 0);
+    }
+
+    #[test]
+    fn streak_counts_consecutive_goal_days() {
+        let mut words = DailyWords::default();
+        words.add(date(1), 600);
+        words.add(date(3), 500);
+        words.add(date(4), 700);
+
+        // Today not yet met: yesterday's streak still stands
+        words.add(date(5), 100);
+        assert_eq!(words.streak(date(5), 500), 2);
+
+        words.add(date(5), 400);
+        assert_eq!(words.streak(date(5), 500), 3);
+        assert_eq!(words.streak(date(7), 500), 0);
+        assert_eq!(words.streak(date(5), 0), 0);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut words = DailyWords::default();
+        words.add(date(1), 42);
+        let json = serde_json::to_string(&words).unwrap();
+        assert_eq!(json, "{\"days\":{\"2026-03-01\":42}}");
+        assert_eq!(serde_json::from_str::<DailyWords>(&json).unwrap(), words);
+    }
+
+    #[test]
+    fn formats_progress_with_separators() {
+        assert_eq!(format_progress(2340, 5000), "2,340 / 5,000 words today");
+        assert_eq!(format_progress(0, 750), "0 / 750 words today");
+        assert_eq!(
+            format_progress(1234567, 1000),
+            "1,234,567 / 1,000 words today"
+        );
+    }
+}
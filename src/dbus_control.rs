@@ -0,0 +1,193 @@
+//! Optional D-Bus control service (`general_config.enable_dbus`).
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use parking_lot::{Mutex, RwLock};
+use tokio::sync::mpsc;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::Value;
+use zbus::{connection, fdo, interface, Connection};
+
+use crate::ipc::{IpcResponse, SessionControl};
+
+pub const BUS_NAME: &str = "dev.sonori.Control";
+pub const OBJECT_PATH: &str = "/dev/sonori/Control";
+
+/// Segments waiting to be emitted; set while the service is running
+static SEGMENT_TX: Mutex<Option<mpsc::UnboundedSender<String>>> = Mutex::new(None);
+
+/// Emit a delivered segment as `TranscriptSegment`; a no-op without the service
+pub fn record_segment(text: &str) {
+    if let Some(segment_tx) = SEGMENT_TX.lock().as_ref() {
+        let _ = segment_tx.send(text.to_string());
+    }
+}
+
+pub struct ControlService {
+    control: SessionControl,
+    transcript: Arc<RwLock<String>>,
+}
+
+impl ControlService {
+    pub fn new(control: SessionControl, transcript: Arc<RwLock<String>>) -> Self {
+        Self {
+            control,
+            transcript,
+        }
+    }
+}
+
+fn into_reply(response: IpcResponse) -> fdo::Result<String> {
+    let message = response.message.unwrap_or_default();
+    if response.success {
+        Ok(message)
+    } else {
+        Err(fdo::Error::Failed(message))
+    }
+}
+
+#[interface(name = "dev.sonori.Control")]
+impl ControlService {
+    async fn start_session(&self) -> fdo::Result<String> {
+        into_reply(self.control.start().await)
+    }
+
+    async fn stop_session(&self) -> fdo::Result<String> {
+        into_reply(self.control.stop().await)
+    }
+
+    async fn cancel_session(&self) -> fdo::Result<String> {
+        into_reply(self.control.cancel().await)
+    }
+
+    async fn toggle_recording(&self) -> fdo::Result<String> {
+        into_reply(self.control.toggle().await)
+    }
+
+    async fn get_transcript(&self) -> String {
+        crate::transcript_spill::full_transcript(&self.transcript.read())
+    }
+
+    /// `mode` ("manual" or "realtime"), `recording` and `session` ("recording" or "idle")
+    async fn get_status(&self) -> HashMap<String, Value<'static>> {
+        let Some(status) = self.control.status().status else {
+            return HashMap::new();
+        };
+        let session = if status.recording {
+            "recording"
+        } else {
+            "idle"
+        };
+        HashMap::from([
+            ("mode".to_string(), Value::from(status.mode)),
+            ("recording".to_string(), Value::from(status.recording)),
+            ("session".to_string(), Value::from(session.to_string())),
+        ])
+    }
+
+    #[zbus(signal)]
+    async fn transcript_segment(emitter: &SignalEmitter<'_>, text: &str) -> zbus::Result<()>;
+}
+
+/// Own `dev.sonori.Control` on the session bus and emit segments until shutdown
+pub async fn run(service: ControlService, running: Arc<AtomicBool>) -> Result<()> {
+    let conn = connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, service)?
+        .build()
+        .await?;
+    tracing::info!("D-Bus control service available as {}", BUS_NAME);
+
+    let (segment_tx, mut segment_rx) = mpsc::unbounded_channel();
+    *SEGMENT_TX.lock() = Some(segment_tx);
+
+    let mut shutdown_check = tokio::time::interval(Duration::from_millis(100));
+    while running.load(Ordering::Relaxed) {
+        tokio::select! {
+            Some(text) = segment_rx.recv() => {
+                if let Err(e) = emit_segment(&conn, &text).await {
+                    tracing::warn!("Failed to emit TranscriptSegment: {}", e);
+                }
+            }
+            _ = shutdown_check.tick() => {}
+        }
+    }
+
+    *SEGMENT_TX.lock() = None;
+    Ok(())
+}
+
+async fn emit_segment(conn: &Connection, text: &str) -> zbus::Result<()> {
+    let service = conn
+        .object_server()
+        .interface::<_, ControlService>(OBJECT_PATH)
+        .await?;
+    ControlService::transcript_segment(service.signal_emitter(), text).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU8;
+
+    use speechcore::{ManualSessionCommand, TranscriptionMode};
+
+    #[tokio::test]
+    async fn session_methods_reach_the_manual_session() {
+        let (command_tx, mut command_rx) = mpsc::channel(4);
+        let mode = Arc::new(AtomicU8::new(TranscriptionMode::Manual.as_u8()));
+        let recording = Arc::new(AtomicBool::new(false));
+        let service = ControlService::new(
+            SessionControl::new(command_tx, mode.clone(), recording.clone()),
+            Arc::new(RwLock::new("hello world".to_string())),
+        );
+
+        // A private peer-to-peer bus between two ends of a socket pair
+        let (server_stream, client_stream) = tokio::net::UnixStream::pair().unwrap();
+        let server = connection::Builder::unix_stream(server_stream)
+            .server(zbus::Guid::generate())
+            .unwrap()
+            .p2p()
+            .serve_at(OBJECT_PATH, service)
+            .unwrap()
+            .build();
+        let client = connection::Builder::unix_stream(client_stream)
+            .p2p()
+            .build();
+        let (_server, client) = tokio::try_join!(server, client).unwrap();
+        let proxy = zbus::Proxy::new(&client, BUS_NAME, OBJECT_PATH, BUS_NAME)
+            .await
+            .unwrap();
+
+        let reply: String = proxy.call("StartSession", &()).await.unwrap();
+        assert_eq!(reply, "Recording started");
+        assert!(matches!(
+            command_rx.recv().await,
+            Some(ManualSessionCommand::StartSession { .. })
+        ));
+
+        recording.store(true, Ordering::Relaxed);
+        let status: HashMap<String, zbus::zvariant::OwnedValue> =
+            proxy.call("GetStatus", &()).await.unwrap();
+        assert_eq!(
+            status["session"].downcast_ref::<&str>().unwrap(),
+            "recording"
+        );
+        let transcript: String = proxy.call("GetTranscript", &()).await.unwrap();
+        assert_eq!(transcript, "hello world");
+
+        let _: String = proxy.call("StopSession", &()).await.unwrap();
+        assert!(matches!(
+            command_rx.recv().await,
+            Some(ManualSessionCommand::StopSession { .. })
+        ));
+
+        mode.store(TranscriptionMode::RealTime.as_u8(), Ordering::Relaxed);
+        let result: zbus::Result<String> = proxy.call("StartSession", &()).await;
+        assert!(result.is_err());
+    }
+}
@@ -57,7 +57,7 @@ impl LlamaCppModel {
             EnhancementError::InferenceError(format!("Failed to load GGUF model: {:?}", e))
         })?;
 
-        println!("Loaded GGUF model from {} (CPU mode)", path.display());
+        tracing::info!("Loaded GGUF model from {} (CPU mode)", path.display());
 
         Ok(Self {
             backend,
@@ -128,7 +128,7 @@ impl LlamaCppModel {
             EnhancementError::InferenceError(format!("Failed to decode prompt: {:?}", e))
         })?;
 
-        println!(
+        tracing::debug!(
             "[LlamaCpp] Prefill ({} tokens): {:?}",
             tokens.len(),
             start.elapsed()
@@ -197,9 +197,11 @@ impl LlamaCppModel {
             0.0
         };
 
-        println!(
+        tracing::debug!(
             "[LlamaCpp] Decode ({} tokens): {:?} ({:.1} tok/s)",
-            token_count, decode_elapsed, tokens_per_sec
+            token_count,
+            decode_elapsed,
+            tokens_per_sec
         );
 
         Ok(result.trim().to_string())
@@ -9,6 +9,7 @@ use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::AsyncWriteExt;
 
 #[derive(Debug)]
@@ -42,6 +43,10 @@ pub struct MagicModeEnhancer {
     config: EnhancementConfig,
     enabled: Arc<AtomicBool>,
     model: Mutex<Option<Box<dyn EnhancementModel>>>,
+    /// Session-scoped system prompt (e.g. a template chosen by an auto rule)
+    prompt_override: Mutex<Option<String>>,
+    /// Never download a missing model (`general_config.offline`)
+    offline: bool,
 }
 
 impl MagicModeEnhancer {
@@ -50,12 +55,38 @@ impl MagicModeEnhancer {
             config,
             enabled,
             model: Mutex::new(None),
+            prompt_override: Mutex::new(None),
+            offline: false,
         }
     }
 
+    /// Fail instead of downloading a missing model
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Use `prompt` instead of the configured system prompt until cleared with `None`
+    pub fn set_system_prompt_override(&self, prompt: Option<String>) {
+        *self.prompt_override.lock() = prompt;
+    }
+
+    /// Whether Magic Mode is switched on, i.e. `enhance` will run the model
+    pub fn is_active(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// How long the transcript consumer waits for `enhance` before using the raw text
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs)
+    }
+
     fn model_path(&self) -> Result<Option<PathBuf>, EnhancementError> {
+        if let Some(path) = &self.config.model_path {
+            return Ok(Some(path.clone()));
+        }
         let Some(model) = self.config.model.as_deref() else {
-            eprintln!("Magic Mode enabled but no enhancement model is configured");
+            tracing::warn!("Magic Mode enabled but no enhancement model is configured");
             return Ok(None);
         };
 
@@ -63,7 +94,16 @@ impl MagicModeEnhancer {
             return get_enhancement_gguf_path(model).map(Some);
         }
 
-        eprintln!("Enhancement model not found, attempting download: {model}");
+        if self.offline {
+            let path = get_enhancement_gguf_path(model)?;
+            return Err(EnhancementError::ModelNotAvailable(format!(
+                "offline mode: place {} at {} to use Magic Mode",
+                model,
+                path.display()
+            )));
+        }
+
+        tracing::warn!("Enhancement model not found, attempting download: {model}");
         download_enhancement_gguf_blocking(model).map(Some)
     }
 
@@ -84,7 +124,7 @@ impl MagicModeEnhancer {
             )));
         }
 
-        println!("Loading Sonori Magic Mode model from: {}", path.display());
+        tracing::info!("Loading Sonori Magic Mode model from: {}", path.display());
         *model = Some(Box::new(LlamaCppModel::from_file_with_options(
             &path,
             self.config.max_tokens,
@@ -107,16 +147,16 @@ impl MagicModeEnhancer {
             return Ok(transcription.to_string());
         };
 
-        let system_prompt = self
-            .config
-            .system_prompt
+        let prompt_override = self.prompt_override.lock().clone();
+        let system_prompt = prompt_override
             .as_deref()
+            .or(self.config.system_prompt.as_deref())
             .or(Some(DEFAULT_ENHANCEMENT_SYSTEM_PROMPT));
 
         match model.enhance(transcription, system_prompt) {
             Ok(enhanced) => Ok(enhanced),
             Err(e) => {
-                eprintln!("Magic Mode enhancement failed: {e}");
+                tracing::warn!("Magic Mode enhancement failed: {e}");
                 Ok(transcription.to_string())
             }
         }
@@ -216,7 +256,7 @@ async fn download_enhancement_gguf(model: &str) -> Result<PathBuf, EnhancementEr
     }
 
     let url = format!("https://huggingface.co/{repo}/resolve/main/{filename}");
-    println!("Downloading Sonori Magic Mode model from: {url}");
+    tracing::info!("Downloading Sonori Magic Mode model from: {url}");
 
     let response = reqwest::get(&url)
         .await
@@ -0,0 +1,271 @@
+//! Minimal evdev key listener reading `/dev/input/event*` directly.
+//! Needs membership in the `input` group, so callers must make it opt-in.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use crate::hotkeys::{Accelerator, ShortcutEvent};
+
+/// `struct input_event` on 64-bit Linux: timeval (16) + type (2) + code (2) + value (4)
+const INPUT_EVENT_SIZE: usize = 24;
+const EV_KEY: u16 = 1;
+
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_RIGHTCTRL: u16 = 97;
+const KEY_LEFTSHIFT: u16 = 42;
+const KEY_RIGHTSHIFT: u16 = 54;
+const KEY_LEFTALT: u16 = 56;
+const KEY_RIGHTALT: u16 = 100;
+const KEY_LEFTMETA: u16 = 125;
+const KEY_RIGHTMETA: u16 = 126;
+
+/// Raw key transition from a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub code: u16,
+    /// 0 = release, 1 = press, 2 = autorepeat
+    pub value: i32,
+}
+
+/// Map an xkb-style key name from the config to a Linux key code
+pub fn key_code(name: &str) -> Option<u16> {
+    let name = name.to_lowercase();
+    let letters = "qwertyuiop";
+    let home_row = "asdfghjkl";
+    let bottom_row = "zxcvbnm";
+
+    if name.len() == 1 {
+        let c = name.chars().next()?;
+        if let Some(i) = letters.find(c) {
+            return Some(16 + i as u16);
+        }
+        if let Some(i) = home_row.find(c) {
+            return Some(30 + i as u16);
+        }
+        if let Some(i) = bottom_row.find(c) {
+            return Some(44 + i as u16);
+        }
+        if c == '0' {
+            return Some(11);
+        }
+        if let Some(d) = c.to_digit(10) {
+            return Some(1 + d as u16);
+        }
+    }
+
+    if let Some(n) = name.strip_prefix('f').and_then(|n| n.parse::<u16>().ok()) {
+        return match n {
+            1..=10 => Some(58 + n),
+            11 => Some(87),
+            12 => Some(88),
+            13..=24 => Some(170 + n),
+            _ => None,
+        };
+    }
+
+    let code = match name.as_str() {
+        "escape" => 1,
+        "minus" => 12,
+        "equal" => 13,
+        "backspace" => 14,
+        "tab" => 15,
+        "bracketleft" => 26,
+        "bracketright" => 27,
+        "return" | "enter" => 28,
+        "semicolon" => 39,
+        "apostrophe" => 40,
+        "grave" => 41,
+        "backslash" => 43,
+        "comma" => 51,
+        "period" => 52,
+        "slash" => 53,
+        "space" => 57,
+        "caps_lock" => 58,
+        "home" => 102,
+        "up" => 103,
+        "page_up" | "prior" => 104,
+        "left" => 105,
+        "right" => 106,
+        "end" => 107,
+        "down" => 108,
+        "page_down" | "next" => 109,
+        "insert" => 110,
+        "delete" => 111,
+        "pause" => 119,
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Tracks modifier state and turns raw key events into shortcut activations
+#[derive(Debug)]
+pub struct HotkeyMatcher {
+    accelerator: Accelerator,
+    key: u16,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    logo: bool,
+    active: bool,
+}
+
+impl HotkeyMatcher {
+    pub fn new(accelerator: &Accelerator) -> Option<Self> {
+        Some(Self {
+            key: key_code(&accelerator.key)?,
+            accelerator: accelerator.clone(),
+            ctrl: false,
+            alt: false,
+            shift: false,
+            logo: false,
+            active: false,
+        })
+    }
+
+    pub fn feed(&mut self, event: KeyEvent) -> Option<ShortcutEvent> {
+        let pressed = event.value != 0;
+        match event.code {
+            KEY_LEFTCTRL | KEY_RIGHTCTRL => self.ctrl = pressed,
+            KEY_LEFTALT | KEY_RIGHTALT => self.alt = pressed,
+            KEY_LEFTSHIFT | KEY_RIGHTSHIFT => self.shift = pressed,
+            KEY_LEFTMETA | KEY_RIGHTMETA => self.logo = pressed,
+            code if code == self.key => {
+                let wanted = self.accelerator.modifiers;
+                let modifiers_match = self.ctrl == wanted.ctrl
+                    && self.alt == wanted.alt
+                    && self.shift == wanted.shift
+                    && self.logo == wanted.logo;
+                return match event.value {
+                    1 if modifiers_match && !self.active => {
+                        self.active = true;
+                        Some(ShortcutEvent::Pressed)
+                    }
+                    0 if self.active => {
+                        self.active = false;
+                        Some(ShortcutEvent::Released)
+                    }
+                    _ => None,
+                };
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// List `/dev/input/event*` device nodes
+pub fn event_devices() -> io::Result<Vec<PathBuf>> {
+    let mut devices: Vec<PathBuf> = std::fs::read_dir("/dev/input")?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("event"))
+        })
+        .collect();
+    devices.sort();
+    Ok(devices)
+}
+
+/// Whether a device reports letter keys (i.e. looks like a keyboard or pedal with keys)
+fn has_keys(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let caps = format!("/sys/class/input/{}/device/capabilities/key", name);
+    std::fs::read_to_string(caps)
+        .map(|bits| bits.split_whitespace().any(|word| word != "0"))
+        .unwrap_or(false)
+}
+
+/// Open every readable key-capable device and forward key events.
+///
+/// Returns the number of devices opened; fails with `PermissionDenied` if
+/// devices exist but none could be read (missing `input` group membership).
+pub fn spawn_key_readers(
+    tx: mpsc::Sender<KeyEvent>,
+    running: Arc<AtomicBool>,
+) -> io::Result<usize> {
+    let mut opened = 0;
+    let mut denied = 0;
+
+    for path in event_devices()?.into_iter().filter(|p| has_keys(p)) {
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                denied += 1;
+                continue;
+            }
+            Err(_) => continue,
+        };
+        opened += 1;
+
+        let tx = tx.clone();
+        let running = running.clone();
+        std::thread::spawn(move || read_device(file, tx, running));
+    }
+
+    if opened == 0 && denied > 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "no readable input devices (add your user to the 'input' group)",
+        ));
+    }
+    Ok(opened)
+}
+
+fn read_device(mut file: File, tx: mpsc::Sender<KeyEvent>, running: Arc<AtomicBool>) {
+    let mut buffer = [0u8; INPUT_EVENT_SIZE];
+    // Reads block until the next event, so shutdown is noticed on the next key press
+    while running.load(Ordering::Relaxed) {
+        if file.read_exact(&mut buffer).is_err() {
+            break;
+        }
+        let event_type = u16::from_ne_bytes([buffer[16], buffer[17]]);
+        if event_type != EV_KEY {
+            continue;
+        }
+        let event = KeyEvent {
+            code: u16::from_ne_bytes([buffer[18], buffer[19]]),
+            value: i32::from_ne_bytes([buffer[20], buffer[21], buffer[22], buffer[23]]),
+        };
+        if tx.blocking_send(event).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matcher_requires_exact_modifiers_and_reports_release() {
+        let accelerator = Accelerator::parse("<Super>backslash").unwrap();
+        let mut matcher = HotkeyMatcher::new(&accelerator).unwrap();
+        let key = |code, value| KeyEvent { code, value };
+
+        assert_eq!(matcher.feed(key(43, 1)), None);
+        assert_eq!(matcher.feed(key(43, 0)), None);
+
+        matcher.feed(key(KEY_LEFTMETA, 1));
+        assert_eq!(matcher.feed(key(43, 1)), Some(ShortcutEvent::Pressed));
+        assert_eq!(matcher.feed(key(43, 2)), None);
+        matcher.feed(key(KEY_LEFTMETA, 0));
+        assert_eq!(matcher.feed(key(43, 0)), Some(ShortcutEvent::Released));
+    }
+
+    #[test]
+    fn maps_common_key_names() {
+        assert_eq!(key_code("a"), Some(30));
+        assert_eq!(key_code("Q"), Some(16));
+        assert_eq!(key_code("1"), Some(2));
+        assert_eq!(key_code("F13"), Some(183));
+        assert_eq!(key_code("space"), Some(57));
+    }
+}
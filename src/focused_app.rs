@@ -0,0 +1,225 @@
+//! Focused application for the per-app paste rules
+//! (`portal_config.paste_allowlist` / `paste_blocklist`).
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use wayland_client::backend::ObjectId;
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::wl_registry;
+use wayland_client::{event_created_child, Connection, Dispatch, Proxy, QueueHandle};
+use wayland_protocols_wlr::foreign_toplevel::v1::client::zwlr_foreign_toplevel_handle_v1::{
+    self, ZwlrForeignToplevelHandleV1,
+};
+use wayland_protocols_wlr::foreign_toplevel::v1::client::zwlr_foreign_toplevel_manager_v1::{
+    self, ZwlrForeignToplevelManagerV1,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FocusedApp {
+    pub app_id: String,
+    pub title: String,
+}
+
+static FOCUSED: Mutex<Option<FocusedApp>> = Mutex::new(None);
+
+/// The activated toplevel, if the compositor reports one
+pub fn focused() -> Option<FocusedApp> {
+    FOCUSED.lock().clone()
+}
+
+/// Whether pasting into `app` is allowed: a blocklist match denies, and a
+/// non-empty allowlist denies everything it does not match
+pub fn paste_allowed(app: &FocusedApp, allowlist: &[String], blocklist: &[String]) -> bool {
+    let matches = |pattern: &String| matches_pattern(pattern, &app.app_id);
+    if blocklist.iter().any(matches) {
+        return false;
+    }
+    allowlist.is_empty() || allowlist.iter().any(matches)
+}
+
+/// The focused app if the rules exclude it from pasting; `None` when pasting is
+/// allowed or the focused app is unknown
+pub fn paste_blocked(allowlist: &[String], blocklist: &[String]) -> Option<FocusedApp> {
+    if allowlist.is_empty() && blocklist.is_empty() {
+        return None;
+    }
+    focused().filter(|app| !paste_allowed(app, allowlist, blocklist))
+}
+
+/// Case-insensitive app-id match where `*` stands for any run of characters
+fn matches_pattern(pattern: &str, app_id: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let app_id = app_id.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = app_id.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Follow the activated toplevel on a background thread
+pub fn start_tracking() {
+    let spawned = std::thread::Builder::new()
+        .name("focused-app".to_string())
+        .spawn(|| {
+            if let Err(e) = track() {
+                tracing::warn!("Per-app paste rules inactive: {}", e);
+            }
+            *FOCUSED.lock() = None;
+        });
+    if let Err(e) = spawned {
+        tracing::warn!("Failed to start focused app tracking: {}", e);
+    }
+}
+
+fn track() -> Result<(), Box<dyn std::error::Error>> {
+    let connection = Connection::connect_to_env()?;
+    let (globals, mut queue) = registry_queue_init::<Tracker>(&connection)?;
+    let _manager: ZwlrForeignToplevelManagerV1 = globals
+        .bind(&queue.handle(), 1..=3, ())
+        .map_err(|_| "the compositor does not support wlr-foreign-toplevel-management")?;
+
+    let mut tracker = Tracker::default();
+    loop {
+        queue.blocking_dispatch(&mut tracker)?;
+    }
+}
+
+#[derive(Default)]
+struct Toplevel {
+    app: FocusedApp,
+    activated: bool,
+}
+
+#[derive(Default)]
+struct Tracker {
+    toplevels: HashMap<ObjectId, Toplevel>,
+}
+
+impl Tracker {
+    fn publish(&self) {
+        *FOCUSED.lock() = self
+            .toplevels
+            .values()
+            .find(|toplevel| toplevel.activated)
+            .map(|toplevel| toplevel.app.clone());
+    }
+}
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for Tracker {
+    fn event(
+        _: &mut Self,
+        _: &wl_registry::WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrForeignToplevelManagerV1, ()> for Tracker {
+    fn event(
+        tracker: &mut Self,
+        _: &ZwlrForeignToplevelManagerV1,
+        event: zwlr_foreign_toplevel_manager_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let zwlr_foreign_toplevel_manager_v1::Event::Toplevel { toplevel } = event {
+            tracker.toplevels.insert(toplevel.id(), Toplevel::default());
+        }
+    }
+
+    event_created_child!(Tracker, ZwlrForeignToplevelManagerV1, [
+        zwlr_foreign_toplevel_manager_v1::EVT_TOPLEVEL_OPCODE => (ZwlrForeignToplevelHandleV1, ()),
+    ]);
+}
+
+impl Dispatch<ZwlrForeignToplevelHandleV1, ()> for Tracker {
+    fn event(
+        tracker: &mut Self,
+        handle: &ZwlrForeignToplevelHandleV1,
+        event: zwlr_foreign_toplevel_handle_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        use zwlr_foreign_toplevel_handle_v1::Event;
+
+        match event {
+            Event::Closed => {
+                tracker.toplevels.remove(&handle.id());
+                handle.destroy();
+                tracker.publish();
+            }
+            // Changes are applied atomically on `done`
+            Event::Done => tracker.publish(),
+            event => {
+                let toplevel = tracker.toplevels.entry(handle.id()).or_default();
+                match event {
+                    Event::Title { title } => toplevel.app.title = title,
+                    Event::AppId { app_id } => toplevel.app.app_id = app_id,
+                    Event::State { state } => {
+                        let activated = zwlr_foreign_toplevel_handle_v1::State::Activated as u32;
+                        toplevel.activated = state.chunks_exact(4).any(|raw| {
+                            u32::from_ne_bytes([raw[0], raw[1], raw[2], raw[3]]) == activated
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(app_id: &str) -> FocusedApp {
+        FocusedApp {
+            app_id: app_id.to_string(),
+            title: String::new(),
+        }
+    }
+
+    fn list(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn matches_app_id_globs() {
+        assert!(matches_pattern(
+            "org.keepassxc.*",
+            "org.keepassxc.KeePassXC"
+        ));
+        assert!(matches_pattern("*bitwarden*", "com.Bitwarden.desktop"));
+        assert!(matches_pattern("foot", "Foot"));
+        assert!(!matches_pattern("foot", "footclient"));
+        assert!(!matches_pattern("*.desktop", "desktop"));
+    }
+
+    #[test]
+    fn blocklist_wins_over_allowlist() {
+        let allow = list(&["code*", "foot"]);
+        let block = list(&["*keepassxc*"]);
+        assert!(paste_allowed(&app("code-oss"), &allow, &block));
+        assert!(!paste_allowed(&app("firefox"), &allow, &block));
+        assert!(!paste_allowed(&app("org.keepassxc.KeePassXC"), &[], &block));
+        assert!(paste_allowed(&app("firefox"), &[], &block));
+    }
+}
@@ -2,14 +2,25 @@ use anyhow::{Context, Result};
 use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
 use ashpd::ActivationToken;
 use futures_util::StreamExt;
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use parking_lot::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::{sleep, Duration};
 use zbus::zvariant::OwnedValue;
 
-use sonori::config::ShortcutMode;
-use speechcore::{ManualSessionCommand, TranscriptionMode};
+use sonori::config::{ConfigStore, PortalConfig};
+use sonori::evdev_input::{self, HotkeyMatcher};
+use sonori::hotkeys::{
+    detect_compositor, Accelerator, CompositorBinding, HotkeyProvider, HotkeyStatus,
+    ShortcutAction, ShortcutEvent,
+};
+use sonori::portal_status::{self, PortalCommand, SessionState};
+
+/// How long to wait for the portal to answer the bind request (includes the user dialog)
+const PORTAL_BIND_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long a replaced chain gets to release its session before it is aborted
+const CHAIN_STOP_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Manages global shortcuts through the XDG Desktop Portal.
 ///
@@ -21,10 +32,9 @@ use speechcore::{ManualSessionCommand, TranscriptionMode};
 /// - Clean shutdown
 pub struct GlobalShortcutsManager {
     accelerator: String,
-    shortcut_mode: ShortcutMode,
-    manual_session_tx: mpsc::Sender<ManualSessionCommand>,
-    transcription_mode: Arc<AtomicU8>,
-    recording: Arc<AtomicBool>,
+    wipe_accelerator: Option<String>,
+    action_accelerators: Vec<(ShortcutAction, String)>,
+    events_tx: mpsc::Sender<ShortcutEvent>,
     running: Arc<AtomicBool>,
 }
 
@@ -32,31 +42,48 @@ impl GlobalShortcutsManager {
     /// Create a new global shortcuts manager
     pub fn new(
         accelerator: String,
-        shortcut_mode: ShortcutMode,
-        manual_session_tx: mpsc::Sender<ManualSessionCommand>,
-        transcription_mode: Arc<AtomicU8>,
-        recording: Arc<AtomicBool>,
+        events_tx: mpsc::Sender<ShortcutEvent>,
         running: Arc<AtomicBool>,
     ) -> Self {
         Self {
             accelerator,
-            shortcut_mode,
-            manual_session_tx,
-            transcription_mode,
-            recording,
+            wipe_accelerator: None,
+            action_accelerators: Vec::new(),
+            events_tx,
             running,
         }
     }
 
-    /// Run the global shortcuts listener
-    pub async fn run(self) -> Result<()> {
+    /// Also bind the privacy wipe shortcut in the same portal request
+    pub fn with_wipe_accelerator(mut self, accelerator: String) -> Self {
+        if !accelerator.trim().is_empty() {
+            self.wipe_accelerator = Some(accelerator);
+        }
+        self
+    }
+
+    /// Also bind every configured action shortcut; unset ones are skipped
+    pub fn with_action_accelerators(mut self, portal_config: &PortalConfig) -> Self {
+        self.action_accelerators = ShortcutAction::ALL
+            .into_iter()
+            .map(|action| (action, action.accelerator(portal_config).trim().to_string()))
+            .filter(|(_, accelerator)| !accelerator.is_empty())
+            .collect();
+        self
+    }
+
+    /// Run the global shortcuts listener.
+    ///
+    /// `bound_tx` receives the registered trigger once binding succeeded; if
+    /// binding fails it is dropped, which lets the fallback chain move on.
+    pub async fn run(self, bound_tx: oneshot::Sender<String>) -> Result<()> {
         // Try once to bind shortcuts - no retries
         // If the user declines the portal dialog, we shouldn't keep asking them
-        self.run_session().await
+        self.run_session(bound_tx).await
     }
 
     /// Run a single session with the portal
-    async fn run_session(&self) -> Result<()> {
+    async fn run_session(&self, bound_tx: oneshot::Sender<String>) -> Result<()> {
         let normalized_accelerator = normalize_accelerator_for_portal(&self.accelerator);
 
         let gs = GlobalShortcuts::new()
@@ -72,9 +99,31 @@ impl GlobalShortcutsManager {
         // Bind our shortcut
         let shortcut = NewShortcut::new("toggle_manual", "Toggle Manual Transcription Session")
             .preferred_trigger(Some(normalized_accelerator.as_str()));
+        let mut new_shortcuts = vec![shortcut];
+        let normalized_wipe = self
+            .wipe_accelerator
+            .as_deref()
+            .map(normalize_accelerator_for_portal);
+        if let Some(wipe) = &normalized_wipe {
+            new_shortcuts.push(
+                NewShortcut::new("privacy_wipe", "Wipe the Current Session")
+                    .preferred_trigger(Some(wipe.as_str())),
+            );
+        }
+        let normalized_actions: Vec<(ShortcutAction, String)> = self
+            .action_accelerators
+            .iter()
+            .map(|(action, accelerator)| (*action, normalize_accelerator_for_portal(accelerator)))
+            .collect();
+        for (action, accelerator) in &normalized_actions {
+            new_shortcuts.push(
+                NewShortcut::new(action.id(), action.description())
+                    .preferred_trigger(Some(accelerator.as_str())),
+            );
+        }
 
         let request = gs
-            .bind_shortcuts(&session, &[shortcut], None)
+            .bind_shortcuts(&session, &new_shortcuts, None)
             .await
             .context("Failed to bind shortcuts")?;
 
@@ -85,19 +134,38 @@ impl GlobalShortcutsManager {
         // Check what was actually bound
         let shortcuts = response.shortcuts();
 
-        let bound_exists = shortcuts.iter().any(|s| s.id() == "toggle_manual");
-
-        if !bound_exists {
+        let Some(bound) = shortcuts.iter().find(|s| s.id() == "toggle_manual") else {
             // User likely declined the portal dialog or binding was rejected
-            eprintln!(
+            tracing::warn!(
                 "Shortcut '{}' was not bound by portal - user may have declined permission",
                 normalized_accelerator
             );
             return Err(anyhow::anyhow!(
                 "Shortcut binding was not approved by user or portal"
             ));
+        };
+
+        if normalized_wipe.is_some() && !shortcuts.iter().any(|s| s.id() == "privacy_wipe") {
+            tracing::warn!("Privacy wipe shortcut was not bound by the portal");
+        }
+        for (action, accelerator) in &normalized_actions {
+            if !shortcuts.iter().any(|s| s.id() == action.id()) {
+                tracing::warn!(
+                    "Shortcut '{}' ({}) was not bound by the portal",
+                    accelerator,
+                    action.id()
+                );
+            }
         }
 
+        // The portal may assign a different trigger than the one we asked for
+        let trigger = bound.trigger_description().to_string();
+        let _ = bound_tx.send(if trigger.is_empty() {
+            normalized_accelerator.clone()
+        } else {
+            trigger
+        });
+
         // Listen to all portal signals
         let mut activated_stream = gs
             .receive_activated()
@@ -144,6 +212,16 @@ impl GlobalShortcutsManager {
 
     /// Handle shortcut activation (key pressed)
     async fn handle_activated(&self, activated: ashpd::desktop::global_shortcuts::Activated) {
+        if activated.shortcut_id() == "privacy_wipe" {
+            sonori::privacy_wipe::wipe();
+            return;
+        }
+        if let Some(action) = ShortcutAction::from_id(activated.shortcut_id()) {
+            if let Err(e) = self.events_tx.send(ShortcutEvent::Action(action)).await {
+                tracing::warn!("Failed to forward shortcut activation: {}", e);
+            }
+            return;
+        }
         if activated.shortcut_id() != "toggle_manual" {
             return;
         }
@@ -153,30 +231,8 @@ impl GlobalShortcutsManager {
             // TODO: Use token to request window focus via portal or Wayland protocol
         }
 
-        // Only act in Manual mode
-        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
-        if mode != TranscriptionMode::Manual {
-            return;
-        }
-
-        let command = match self.shortcut_mode {
-            ShortcutMode::Toggle => {
-                // Toggle: start if not recording, stop if recording
-                let is_recording = self.recording.load(Ordering::Relaxed);
-                if is_recording {
-                    ManualSessionCommand::StopSession { responder: None }
-                } else {
-                    ManualSessionCommand::StartSession { responder: None }
-                }
-            }
-            ShortcutMode::PushToTalk => {
-                // Push-to-talk: always start on press
-                ManualSessionCommand::StartSession { responder: None }
-            }
-        };
-
-        if let Err(e) = self.manual_session_tx.send(command).await {
-            eprintln!("Failed to send manual session command: {}", e);
+        if let Err(e) = self.events_tx.send(ShortcutEvent::Pressed).await {
+            tracing::warn!("Failed to forward shortcut activation: {}", e);
         }
     }
 
@@ -186,20 +242,8 @@ impl GlobalShortcutsManager {
             return;
         }
 
-        // Only act in Manual mode with push-to-talk
-        if self.shortcut_mode != ShortcutMode::PushToTalk {
-            return;
-        }
-
-        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
-        if mode != TranscriptionMode::Manual {
-            return;
-        }
-
-        // Stop recording on key release
-        let command = ManualSessionCommand::StopSession { responder: None };
-        if let Err(e) = self.manual_session_tx.send(command).await {
-            eprintln!("Failed to send stop command: {}", e);
+        if let Err(e) = self.events_tx.send(ShortcutEvent::Released).await {
+            tracing::warn!("Failed to forward shortcut release: {}", e);
         }
     }
 
@@ -263,23 +307,220 @@ fn normalize_accelerator_for_portal(accelerator: &str) -> String {
     normalized
 }
 
-/// Legacy function for backwards compatibility - spawns the manager in a task
-pub async fn run_listener(
-    accelerator: &str,
-    shortcut_mode: ShortcutMode,
-    manual_session_tx: mpsc::Sender<ManualSessionCommand>,
-    transcription_mode_ref: Arc<std::sync::atomic::AtomicU8>,
-    recording: Arc<AtomicBool>,
+/// Run the hotkey fallback chain: portal, then compositor IPC, then evdev (opt-in).
+///
+/// Whichever provider wins delivers activations through `events_tx`; the
+/// outcome is logged and recorded in `status` for `--capabilities`.
+pub async fn run_hotkey_chain(
+    portal_config: PortalConfig,
+    events_tx: mpsc::Sender<ShortcutEvent>,
+    status: Arc<RwLock<HotkeyStatus>>,
     running: Arc<AtomicBool>,
-) -> Result<()> {
-    let manager = GlobalShortcutsManager::new(
-        accelerator.to_string(),
-        shortcut_mode,
-        manual_session_tx,
-        transcription_mode_ref,
-        recording,
-        running,
-    );
-
-    manager.run().await
+) {
+    let accelerator_text = portal_config.manual_toggle_accelerator.clone();
+
+    // 1. XDG GlobalShortcuts portal
+    let (bound_tx, bound_rx) = oneshot::channel();
+    let manager =
+        GlobalShortcutsManager::new(accelerator_text.clone(), events_tx.clone(), running.clone())
+            .with_wipe_accelerator(portal_config.privacy_wipe_accelerator.clone())
+            .with_action_accelerators(&portal_config);
+    let portal_task = tokio::spawn(manager.run(bound_tx));
+
+    match tokio::time::timeout(PORTAL_BIND_TIMEOUT, bound_rx).await {
+        Ok(Ok(trigger)) => {
+            status
+                .write()
+                .set_active(HotkeyProvider::Portal, vec![trigger]);
+            if let Ok(Err(e)) = portal_task.await {
+                tracing::warn!("Global shortcuts portal session ended: {}", e);
+            }
+            return;
+        }
+        Ok(Err(_)) => {
+            // Sender dropped: the task failed before binding
+            let error = match portal_task.await {
+                Ok(Err(e)) => format!("{:#}", e),
+                _ => "portal session ended before binding".to_string(),
+            };
+            status.write().record_failure(HotkeyProvider::Portal, error);
+        }
+        Err(_) => {
+            portal_task.abort();
+            status
+                .write()
+                .record_failure(HotkeyProvider::Portal, "timed out waiting for the portal");
+        }
+    }
+
+    let Some(accelerator) = Accelerator::parse(&accelerator_text) else {
+        tracing::warn!(
+            "Global shortcuts: cannot parse accelerator '{}' for fallback providers",
+            accelerator_text
+        );
+        return;
+    };
+
+    // 2. Compositor IPC binding (Hyprland / sway)
+    if portal_config.enable_compositor_shortcuts {
+        if let Some(provider) = detect_compositor() {
+            match CompositorBinding::bind(provider, &accelerator, portal_config.shortcut_mode) {
+                Ok(binding) => {
+                    status.write().set_active(provider, vec![accelerator_text]);
+                    // Activations arrive via `sonori shortcut press|release` over IPC;
+                    // hold the binding until shutdown so it can be removed again
+                    while running.load(Ordering::Relaxed) {
+                        sleep(Duration::from_millis(200)).await;
+                    }
+                    drop(binding);
+                    return;
+                }
+                Err(e) => status.write().record_failure(provider, format!("{:#}", e)),
+            }
+        }
+    }
+
+    // 3. evdev listener (explicit opt-in: needs input group membership)
+    if !portal_config.enable_evdev_shortcuts {
+        status.write().record_failure(
+            HotkeyProvider::Evdev,
+            "disabled (set enable_evdev_shortcuts = true to opt in)",
+        );
+        return;
+    }
+
+    let Some(mut matcher) = HotkeyMatcher::new(&accelerator) else {
+        status.write().record_failure(
+            HotkeyProvider::Evdev,
+            format!("unsupported key '{}'", accelerator.key),
+        );
+        return;
+    };
+
+    let (key_tx, mut key_rx) = mpsc::channel(256);
+    match evdev_input::spawn_key_readers(key_tx, running.clone()) {
+        Ok(0) => {
+            status
+                .write()
+                .record_failure(HotkeyProvider::Evdev, "no keyboard devices found");
+        }
+        Ok(count) => {
+            status
+                .write()
+                .set_active(HotkeyProvider::Evdev, vec![accelerator_text]);
+            tracing::info!("Global shortcuts: listening on {} input device(s)", count);
+            while let Some(key_event) = key_rx.recv().await {
+                if let Some(event) = matcher.feed(key_event) {
+                    if events_tx.send(event).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        Err(e) => status.write().record_failure(HotkeyProvider::Evdev, e),
+    }
+}
+
+/// Run the hotkey chain until shutdown, starting it over whenever the settings
+/// panel asks to re-bind shortcuts.
+///
+/// Each run gets its own running flag, so a re-bind ends the previous portal
+/// session, compositor binding or evdev readers before the next run asks again.
+/// Every run reads the accelerators from `config_store`, so a shortcut changed
+/// in the settings panel is bound without a restart.
+pub async fn supervise_hotkey_chain(
+    config_store: ConfigStore,
+    events_tx: mpsc::Sender<ShortcutEvent>,
+    status: Arc<RwLock<HotkeyStatus>>,
+    running: Arc<AtomicBool>,
+) {
+    let mut rebind = portal_status::listen(PortalCommand::RebindShortcuts);
+    let mut shutdown_check = tokio::time::interval(Duration::from_millis(200));
+
+    while running.load(Ordering::Relaxed) {
+        *status.write() = HotkeyStatus::default();
+        portal_status::update(|portal| {
+            portal.shortcuts = SessionState::Connecting;
+            portal.shortcut_provider = None;
+            portal.accelerators.clear();
+        });
+
+        let attempt = Arc::new(AtomicBool::new(true));
+        let mut chain = tokio::spawn(run_hotkey_chain(
+            config_store.snapshot().portal_config,
+            events_tx.clone(),
+            status.clone(),
+            attempt.clone(),
+        ));
+        let mut chain_done = false;
+
+        loop {
+            tokio::select! {
+                _ = &mut chain, if !chain_done => {
+                    chain_done = true;
+                    // No provider won, or the winning session ended
+                    if running.load(Ordering::Relaxed) {
+                        portal_status::update(|portal| portal.shortcuts = SessionState::Failed);
+                    }
+                }
+                Some(()) = rebind.recv() => {
+                    tracing::info!("Global shortcuts: re-binding on request");
+                    break;
+                }
+                _ = shutdown_check.tick() => {
+                    if !running.load(Ordering::Relaxed) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        attempt.store(false, Ordering::Relaxed);
+        if !chain_done
+            && tokio::time::timeout(CHAIN_STOP_TIMEOUT, &mut chain)
+                .await
+                .is_err()
+        {
+            chain.abort();
+        }
+    }
+}
+
+/// Human-readable probe of the providers available in this session
+pub async fn probe_hotkey_providers(portal_config: &PortalConfig) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    let portal = match GlobalShortcuts::new().await {
+        Ok(_) => "available".to_string(),
+        Err(e) => format!("unavailable ({})", e),
+    };
+    lines.push(format!("{}: {}", HotkeyProvider::Portal, portal));
+
+    let compositor = match (
+        portal_config.enable_compositor_shortcuts,
+        detect_compositor(),
+    ) {
+        (false, _) => "disabled in config".to_string(),
+        (true, Some(provider)) => format!("{} detected", provider),
+        (true, None) => "no supported compositor detected".to_string(),
+    };
+    lines.push(format!("compositor IPC: {}", compositor));
+
+    let evdev = if !portal_config.enable_evdev_shortcuts {
+        "disabled (opt-in via enable_evdev_shortcuts)".to_string()
+    } else {
+        match evdev_input::event_devices() {
+            Ok(devices) => {
+                let readable = devices
+                    .iter()
+                    .filter(|p| std::fs::File::open(p).is_ok())
+                    .count();
+                format!("{} of {} input devices readable", readable, devices.len())
+            }
+            Err(e) => format!("unavailable ({})", e),
+        }
+    };
+    lines.push(format!("{}: {}", HotkeyProvider::Evdev, evdev));
+
+    lines
 }
@@ -0,0 +1,335 @@
+//! GPU selection for the transcription backends (`whisper_cpp_options.gpu_backend`).
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use speechcore::{BackendConfig, BackendType};
+
+use crate::config::{AppConfig, GpuBackend};
+
+/// whisper.cpp GPU APIs linked into this build. The `backend-whisper-cpp`
+/// feature only enables speechcore's Vulkan build; there is no CUDA feature.
+const WHISPER_VULKAN_BUILT: bool = cfg!(feature = "backend-whisper-cpp");
+const WHISPER_CUDA_BUILT: bool = false;
+/// ONNX Runtime backends run on CUDA when one of these execution providers is built in
+const ORT_CUDA_BUILT: bool = cfg!(any(feature = "ort-cuda", feature = "ort-tensorrt"));
+
+/// GPUs found on this machine, per API
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GpuDevices {
+    /// Vulkan devices, discrete GPUs listed first
+    pub vulkan: Vec<String>,
+    pub cuda: Vec<String>,
+}
+
+/// Device a backend runs on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GpuDevice {
+    Vulkan(String),
+    Cuda(String),
+    Cpu,
+}
+
+impl GpuDevice {
+    pub fn is_gpu(&self) -> bool {
+        !matches!(self, GpuDevice::Cpu)
+    }
+
+    /// API name without the device, e.g. for the settings panel
+    pub fn api(&self) -> &'static str {
+        match self {
+            GpuDevice::Vulkan(_) => "Vulkan",
+            GpuDevice::Cuda(_) => "CUDA",
+            GpuDevice::Cpu => "CPU",
+        }
+    }
+}
+
+impl fmt::Display for GpuDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuDevice::Vulkan(name) | GpuDevice::Cuda(name) => {
+                write!(f, "{} ({})", self.api(), name)
+            }
+            GpuDevice::Cpu => f.write_str("CPU"),
+        }
+    }
+}
+
+/// Outcome of a GPU request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuResolution {
+    pub device: GpuDevice,
+    /// Why the request could not be honoured, if it was not
+    pub fallback: Option<String>,
+}
+
+/// Resolve a GPU request against the APIs a backend was built with and the probed devices
+fn resolve_request(
+    requested: GpuBackend,
+    gpu_enabled: bool,
+    vulkan_built: bool,
+    cuda_built: bool,
+    devices: &GpuDevices,
+) -> GpuResolution {
+    let vulkan = || {
+        devices
+            .vulkan
+            .first()
+            .filter(|_| vulkan_built)
+            .map(|name| GpuDevice::Vulkan(name.clone()))
+    };
+    let cuda = || {
+        devices
+            .cuda
+            .first()
+            .filter(|_| cuda_built)
+            .map(|name| GpuDevice::Cuda(name.clone()))
+    };
+    let resolved = |device: Option<GpuDevice>, fallback: Option<String>| GpuResolution {
+        device: device.unwrap_or(GpuDevice::Cpu),
+        fallback,
+    };
+
+    if !gpu_enabled {
+        let fallback = matches!(requested, GpuBackend::Vulkan | GpuBackend::Cuda)
+            .then(|| format!("{} requested, but gpu_enabled = false", requested));
+        return resolved(None, fallback);
+    }
+    match requested {
+        GpuBackend::None => resolved(None, None),
+        GpuBackend::Auto => resolved(vulkan().or_else(cuda), None),
+        GpuBackend::Vulkan => match vulkan() {
+            Some(device) => resolved(Some(device), None),
+            None => {
+                let reason = if vulkan_built {
+                    "no Vulkan device found"
+                } else {
+                    "this build has no Vulkan support"
+                };
+                resolved(cuda(), Some(format!("vulkan requested, but {}", reason)))
+            }
+        },
+        GpuBackend::Cuda => match cuda() {
+            Some(device) => resolved(Some(device), None),
+            None => {
+                let reason = if cuda_built {
+                    "no CUDA device found"
+                } else {
+                    "this build has no CUDA support"
+                };
+                resolved(vulkan(), Some(format!("cuda requested, but {}", reason)))
+            }
+        },
+    }
+}
+
+/// Vulkan adapters as reported by the driver, discrete GPUs first; software renderers are skipped
+fn probe_vulkan() -> Vec<String> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::VULKAN,
+        ..Default::default()
+    });
+    let mut adapters: Vec<_> = instance
+        .enumerate_adapters(wgpu::Backends::VULKAN)
+        .into_iter()
+        .map(|adapter| adapter.get_info())
+        .filter(|info| info.device_type != wgpu::DeviceType::Cpu)
+        .collect();
+    adapters.sort_by_key(|info| info.device_type != wgpu::DeviceType::DiscreteGpu);
+    adapters.into_iter().map(|info| info.name).collect()
+}
+
+/// NVIDIA GPUs known to the loaded kernel driver
+fn probe_cuda() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir("/proc/driver/nvidia/gpus") else {
+        return Vec::new();
+    };
+    let mut gpus: Vec<String> = entries
+        .filter_map(Result::ok)
+        .map(|entry| {
+            std::fs::read_to_string(entry.path().join("information"))
+                .ok()
+                .and_then(|info| {
+                    info.lines()
+                        .find_map(|line| line.strip_prefix("Model:"))
+                        .map(|model| model.trim().to_string())
+                })
+                .unwrap_or_else(|| entry.file_name().to_string_lossy().into_owned())
+        })
+        .collect();
+    gpus.sort();
+    gpus
+}
+
+/// Probe once; later calls reuse the result
+pub fn devices() -> &'static GpuDevices {
+    static DEVICES: OnceLock<GpuDevices> = OnceLock::new();
+    DEVICES.get_or_init(|| GpuDevices {
+        vulkan: probe_vulkan(),
+        cuda: probe_cuda(),
+    })
+}
+
+/// Device `backend` would run on with this configuration
+pub fn resolve(config: &AppConfig, backend: BackendType) -> GpuResolution {
+    let gpu_enabled = config.backend_config.gpu_enabled;
+    let devices = devices();
+    match backend {
+        BackendType::WhisperCpp => resolve_request(
+            config.whisper_cpp_options.gpu_backend,
+            gpu_enabled,
+            WHISPER_VULKAN_BUILT,
+            WHISPER_CUDA_BUILT,
+            devices,
+        ),
+        // CTranslate2 asks for CUDA when the GPU is enabled
+        BackendType::CTranslate2 => {
+            resolve_request(GpuBackend::Cuda, gpu_enabled, false, true, devices)
+        }
+        BackendType::Moonshine | BackendType::Parakeet | BackendType::Nemotron => resolve_request(
+            GpuBackend::Cuda,
+            gpu_enabled,
+            false,
+            ORT_CUDA_BUILT,
+            devices,
+        ),
+    }
+}
+
+/// `backend_config` with `gpu_enabled` set to what the resolved device allows.
+/// The speech runtime takes no API choice, so this is all a request can change.
+pub fn effective_backend_config(config: &AppConfig) -> BackendConfig {
+    let mut backend_config = config.backend_config.clone();
+    if backend_config.backend == BackendType::WhisperCpp {
+        backend_config.gpu_enabled = resolve(config, BackendType::WhisperCpp).device.is_gpu();
+    }
+    backend_config
+}
+
+/// Log the device the configured backend will use, warning about a fallback
+pub fn log_resolution(config: &AppConfig) {
+    let backend = config.backend_config.backend;
+    let resolution = resolve(config, backend);
+    match &resolution.fallback {
+        Some(reason) => tracing::warn!(
+            "GPU: {}; {:?} falls back to {}",
+            reason,
+            backend,
+            resolution.device
+        ),
+        None => tracing::info!("GPU: {:?} runs on {}", backend, resolution.device),
+    }
+}
+
+/// Lines printed by `--probe-gpu` and `--capabilities`
+pub fn describe(config: &AppConfig) -> Vec<String> {
+    let devices = devices();
+    let list = |names: &[String]| {
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+    let mut lines = vec![
+        format!("Vulkan devices: {}", list(&devices.vulkan)),
+        format!("CUDA devices: {}", list(&devices.cuda)),
+        format!(
+            "whisper.cpp GPU APIs built in: {}",
+            match (WHISPER_VULKAN_BUILT, WHISPER_CUDA_BUILT) {
+                (true, true) => "vulkan, cuda",
+                (true, false) => "vulkan",
+                (false, true) => "cuda",
+                (false, false) => "none",
+            }
+        ),
+        format!(
+            "gpu_enabled = {}, gpu_backend = \"{}\"",
+            config.backend_config.gpu_enabled, config.whisper_cpp_options.gpu_backend
+        ),
+    ];
+    for backend in [
+        BackendType::WhisperCpp,
+        BackendType::CTranslate2,
+        BackendType::Moonshine,
+        BackendType::Parakeet,
+        BackendType::Nemotron,
+    ] {
+        let resolution = resolve(config, backend);
+        let marker = if backend == config.backend_config.backend {
+            " (configured)"
+        } else {
+            ""
+        };
+        let mut line = format!("{:?}{}: {}", backend, marker, resolution.device);
+        if let Some(reason) = resolution.fallback {
+            line.push_str(&format!(" [fallback: {}]", reason));
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(vulkan: &[&str], cuda: &[&str]) -> GpuDevices {
+        GpuDevices {
+            vulkan: vulkan.iter().map(|name| name.to_string()).collect(),
+            cuda: cuda.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn auto_prefers_vulkan_then_cuda_then_cpu() {
+        let both = found(&["AMD Radeon RX 6800"], &["RTX 3060"]);
+        let resolution = resolve_request(GpuBackend::Auto, true, true, true, &both);
+        assert_eq!(
+            resolution.device,
+            GpuDevice::Vulkan("AMD Radeon RX 6800".to_string())
+        );
+        assert_eq!(resolution.fallback, None);
+
+        let resolution = resolve_request(GpuBackend::Auto, true, false, true, &both);
+        assert_eq!(resolution.device, GpuDevice::Cuda("RTX 3060".to_string()));
+
+        let resolution = resolve_request(GpuBackend::Auto, true, true, true, &found(&[], &[]));
+        assert_eq!(resolution.device, GpuDevice::Cpu);
+        assert_eq!(resolution.fallback, None);
+    }
+
+    #[test]
+    fn unavailable_requests_fall_back_with_a_reason() {
+        let amd = found(&["AMD Radeon RX 6800"], &[]);
+        let resolution = resolve_request(GpuBackend::Cuda, true, true, false, &amd);
+        assert_eq!(
+            resolution.device,
+            GpuDevice::Vulkan("AMD Radeon RX 6800".to_string())
+        );
+        assert!(resolution.fallback.unwrap().contains("no CUDA support"));
+
+        let resolution = resolve_request(GpuBackend::Vulkan, true, true, false, &found(&[], &[]));
+        assert_eq!(resolution.device, GpuDevice::Cpu);
+        assert!(resolution.fallback.unwrap().contains("no Vulkan device"));
+    }
+
+    #[test]
+    fn disabled_gpu_runs_on_the_cpu() {
+        let amd = found(&["AMD Radeon RX 6800"], &[]);
+        let resolution = resolve_request(GpuBackend::None, true, true, false, &amd);
+        assert_eq!(
+            resolution,
+            GpuResolution {
+                device: GpuDevice::Cpu,
+                fallback: None,
+            }
+        );
+        let resolution = resolve_request(GpuBackend::Vulkan, false, true, false, &amd);
+        assert_eq!(resolution.device, GpuDevice::Cpu);
+        assert!(resolution.fallback.is_some());
+        let resolution = resolve_request(GpuBackend::Auto, false, true, false, &amd);
+        assert_eq!(resolution.fallback, None);
+    }
+}
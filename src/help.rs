@@ -0,0 +1,243 @@
+use speechcore::TranscriptionMode;
+
+use crate::config::{AppConfig, ShortcutMode};
+use crate::i18n::{tr, tr_args};
+
+/// A titled group of help entries (e.g. "Global shortcuts")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpSection {
+    pub title: &'static str,
+    pub entries: Vec<HelpEntry>,
+}
+
+/// A single binding and the action it performs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpEntry {
+    pub keys: String,
+    pub action: String,
+}
+
+impl HelpEntry {
+    fn new(keys: impl Into<String>, action: impl Into<String>) -> Self {
+        Self {
+            keys: keys.into(),
+            action: action.into(),
+        }
+    }
+}
+
+/// Build the quick help shown in the overlay from the live runtime configuration.
+///
+/// Everything is derived from `config` and the current mode so customized
+/// accelerators and hidden buttons are reflected accurately.
+pub fn quick_help(config: &AppConfig, mode: TranscriptionMode) -> Vec<HelpSection> {
+    vec![
+        global_shortcuts_section(config),
+        HelpSection {
+            title: tr("help.overlay_keys"),
+            entries: overlay_key_entries(mode),
+        },
+        HelpSection {
+            title: tr("help.mouse"),
+            entries: vec![
+                HelpEntry::new(tr("help.key.hover"), tr("help.show_controls")),
+                HelpEntry::new(tr("help.key.scroll"), tr("help.scroll")),
+                HelpEntry::new(tr("help.key.drag"), tr("help.move_overlay")),
+            ],
+        },
+        HelpSection {
+            title: button_section_title(mode),
+            entries: button_entries(config, mode),
+        },
+    ]
+}
+
+/// Build the help printed by the CLI `?` command.
+pub fn cli_help(config: &AppConfig, mode: TranscriptionMode) -> Vec<HelpSection> {
+    let mut entries = Vec::new();
+    if mode == TranscriptionMode::Manual {
+        entries.push(HelpEntry::new("SPACE", tr("help.start_stop_recording")));
+        entries.push(HelpEntry::new("c", tr("help.copy_transcript")));
+        entries.push(HelpEntry::new("r", tr("help.reset_transcript")));
+        entries.push(HelpEntry::new("e", tr("help.export_subtitles")));
+    }
+    entries.push(HelpEntry::new("u", tr("help.undo")));
+    entries.push(HelpEntry::new("h", tr("help.list_sessions")));
+    entries.push(HelpEntry::new("?", tr("help.show_help")));
+    entries.push(HelpEntry::new("q", tr("help.quit")));
+    entries.push(HelpEntry::new("Ctrl+C", tr("help.quit")));
+
+    vec![
+        HelpSection {
+            title: tr("help.cli_commands"),
+            entries,
+        },
+        global_shortcuts_section(config),
+    ]
+}
+
+fn global_shortcuts_section(config: &AppConfig) -> HelpSection {
+    let portal = &config.portal_config;
+    let entries = if portal.enable_global_shortcuts {
+        let action = match portal.shortcut_mode {
+            ShortcutMode::Toggle => tr("help.toggle_manual"),
+            ShortcutMode::PushToTalk => tr("help.push_to_talk"),
+        };
+        let mut entries = vec![HelpEntry::new(
+            portal.manual_toggle_accelerator.clone(),
+            tr_args("help.manual_mode", &[("action", &action)]),
+        )];
+        let optional = [
+            (&portal.privacy_wipe_accelerator, "help.privacy_wipe"),
+            (&portal.copy_transcript_accelerator, "help.copy_transcript"),
+            (
+                &portal.reset_transcript_accelerator,
+                "help.reset_transcript",
+            ),
+            (&portal.switch_mode_accelerator, "help.switch_mode"),
+            (&portal.click_through_accelerator, "help.click_through"),
+            (&portal.auto_paste_accelerator, "help.auto_paste"),
+        ];
+        for (accelerator, action) in optional {
+            if !accelerator.trim().is_empty() {
+                entries.push(HelpEntry::new(accelerator.clone(), tr(action)));
+            }
+        }
+        entries
+    } else {
+        vec![HelpEntry::new(
+            tr("help.key.none"),
+            tr("help.shortcuts_disabled"),
+        )]
+    };
+
+    HelpSection {
+        title: tr("help.global_shortcuts"),
+        entries,
+    }
+}
+
+fn overlay_key_entries(mode: TranscriptionMode) -> Vec<HelpEntry> {
+    let mut entries = Vec::new();
+    if mode == TranscriptionMode::Manual {
+        entries.push(HelpEntry::new("Tab", tr("help.toggle_manual")));
+        entries.push(HelpEntry::new("Esc", tr("help.cancel_manual")));
+    }
+    entries.push(HelpEntry::new("Ctrl+Z", tr("help.undo")));
+    entries.push(HelpEntry::new("Ctrl+Shift+Delete", tr("help.privacy_wipe")));
+    entries.push(HelpEntry::new("?", tr("help.toggle_help")));
+    entries
+}
+
+fn button_section_title(mode: TranscriptionMode) -> &'static str {
+    match mode {
+        TranscriptionMode::RealTime => tr("help.buttons_realtime"),
+        TranscriptionMode::Manual => tr("help.buttons_manual"),
+    }
+}
+
+fn button_entries(config: &AppConfig, mode: TranscriptionMode) -> Vec<HelpEntry> {
+    let mut entries = Vec::new();
+    match mode {
+        TranscriptionMode::RealTime => {
+            entries.push(HelpEntry::new("Pause/Play", tr("help.pause_resume")));
+        }
+        TranscriptionMode::Manual => {
+            entries.push(HelpEntry::new("Record", tr("help.start_stop_recording")));
+            if config.enhancement_config.enabled {
+                entries.push(HelpEntry::new("Magic Mode", tr("help.toggle_magic")));
+            }
+        }
+    }
+    entries.push(HelpEntry::new("Copy", tr("help.copy_clipboard")));
+    entries.push(HelpEntry::new("Reset", tr("help.clear_transcript")));
+    entries.push(HelpEntry::new("R/M", tr("help.switch_mode")));
+    entries.push(HelpEntry::new("Settings", tr("help.open_settings")));
+    entries.push(HelpEntry::new("\u{2715}", tr("help.quit_sonori")));
+    entries
+}
+
+/// Render help sections as plain text (used by the CLI)
+pub fn format_help_text(sections: &[HelpSection]) -> String {
+    let key_width = sections
+        .iter()
+        .flat_map(|section| section.entries.iter())
+        .map(|entry| entry.keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut text = String::new();
+    for (i, section) in sections.iter().enumerate() {
+        if i > 0 {
+            text.push('\n');
+        }
+        text.push_str(section.title);
+        text.push_str(":\n");
+        for entry in &section.entries {
+            text.push_str(&format!(
+                "  {:width$}  {}\n",
+                entry.keys,
+                entry.action,
+                width = key_width
+            ));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn help_reflects_custom_accelerator() {
+        let mut config = AppConfig::default();
+        config.portal_config.manual_toggle_accelerator = "<Ctrl><Alt>space".to_string();
+
+        let sections = quick_help(&config, TranscriptionMode::Manual);
+        let shortcuts = &sections[0];
+
+        assert_eq!(shortcuts.entries[0].keys, "<Ctrl><Alt>space");
+    }
+
+    #[test]
+    fn help_lists_bound_action_accelerators() {
+        let mut config = AppConfig::default();
+        config.portal_config.copy_transcript_accelerator = "<Super>c".to_string();
+        config.portal_config.auto_paste_accelerator = "<Super>p".to_string();
+
+        let sections = quick_help(&config, TranscriptionMode::RealTime);
+        let keys: Vec<&str> = sections[0]
+            .entries
+            .iter()
+            .map(|e| e.keys.as_str())
+            .collect();
+
+        assert_eq!(keys, ["<Super>backslash", "<Super>c", "<Super>p"]);
+        assert_eq!(sections[0].entries[1].action, tr("help.copy_transcript"));
+    }
+
+    #[test]
+    fn magic_mode_button_hidden_unless_enhancement_enabled() {
+        let mut config = AppConfig::default();
+        let has_magic = |config: &AppConfig| {
+            quick_help(config, TranscriptionMode::Manual)
+                .iter()
+                .flat_map(|s| s.entries.iter())
+                .any(|e| e.keys == "Magic Mode")
+        };
+
+        assert!(!has_magic(&config));
+        config.enhancement_config.enabled = true;
+        assert!(has_magic(&config));
+    }
+
+    #[test]
+    fn cli_help_text_lists_help_command() {
+        let config = AppConfig::default();
+        let text = format_help_text(&cli_help(&config, TranscriptionMode::RealTime));
+
+        assert!(text.contains("CLI commands:"));
+        assert!(text.contains("Show this help"));
+    }
+}
@@ -0,0 +1,880 @@
+//! Provider-agnostic global hotkey plumbing.
+
+use std::fmt;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::config::{PortalConfig, ShortcutMode};
+use speechcore::{AudioVisualizationData, ManualSessionCommand, TranscriptionMode};
+
+/// A hotkey activation, independent of the provider that produced it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutEvent {
+    Pressed,
+    Released,
+    /// One of the optional action shortcuts was pressed
+    Action(ShortcutAction),
+}
+
+/// Optional one-shot shortcuts; they work in either transcription mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    CopyTranscript,
+    ResetTranscript,
+    SwitchMode,
+    ToggleClickThrough,
+    ToggleAutoPaste,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 5] = [
+        ShortcutAction::CopyTranscript,
+        ShortcutAction::ResetTranscript,
+        ShortcutAction::SwitchMode,
+        ShortcutAction::ToggleClickThrough,
+        ShortcutAction::ToggleAutoPaste,
+    ];
+
+    /// Shortcut id registered with the portal
+    pub fn id(self) -> &'static str {
+        match self {
+            ShortcutAction::CopyTranscript => "copy_transcript",
+            ShortcutAction::ResetTranscript => "reset_transcript",
+            ShortcutAction::SwitchMode => "switch_mode",
+            ShortcutAction::ToggleClickThrough => "toggle_click_through",
+            ShortcutAction::ToggleAutoPaste => "toggle_auto_paste",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    /// Description shown in the portal's shortcut dialog
+    pub fn description(self) -> &'static str {
+        match self {
+            ShortcutAction::CopyTranscript => "Copy the Transcript",
+            ShortcutAction::ResetTranscript => "Reset the Transcript",
+            ShortcutAction::SwitchMode => "Switch Transcription Mode",
+            ShortcutAction::ToggleClickThrough => "Toggle Click-through Overlay",
+            ShortcutAction::ToggleAutoPaste => "Toggle Auto-paste",
+        }
+    }
+
+    /// Configured accelerator; empty means the shortcut is not bound
+    pub fn accelerator(self, config: &PortalConfig) -> &str {
+        match self {
+            ShortcutAction::CopyTranscript => &config.copy_transcript_accelerator,
+            ShortcutAction::ResetTranscript => &config.reset_transcript_accelerator,
+            ShortcutAction::SwitchMode => &config.switch_mode_accelerator,
+            ShortcutAction::ToggleClickThrough => &config.click_through_accelerator,
+            ShortcutAction::ToggleAutoPaste => &config.auto_paste_accelerator,
+        }
+    }
+}
+
+/// State the action shortcuts operate on
+pub struct ShortcutTargets {
+    pub transcript_history: Arc<RwLock<String>>,
+    pub audio_data: Arc<RwLock<AudioVisualizationData>>,
+}
+
+/// A hotkey provider in the fallback chain, in order of preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyProvider {
+    Portal,
+    Hyprland,
+    Sway,
+    Evdev,
+}
+
+impl fmt::Display for HotkeyProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HotkeyProvider::Portal => "XDG GlobalShortcuts portal",
+            HotkeyProvider::Hyprland => "Hyprland IPC",
+            HotkeyProvider::Sway => "sway IPC",
+            HotkeyProvider::Evdev => "evdev",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Why a provider in the chain was skipped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderAttempt {
+    pub provider: HotkeyProvider,
+    pub error: String,
+}
+
+/// Outcome of the fallback chain, shared with IPC for `--capabilities`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotkeyStatus {
+    /// Provider currently delivering activations, if any
+    pub active: Option<HotkeyProvider>,
+    /// Accelerators registered with the active provider
+    pub accelerators: Vec<String>,
+    /// Providers that were tried and failed, in chain order
+    pub attempts: Vec<ProviderAttempt>,
+}
+
+impl HotkeyStatus {
+    pub fn record_failure(&mut self, provider: HotkeyProvider, error: impl fmt::Display) {
+        tracing::warn!("Global shortcuts: {} unavailable: {}", provider, error);
+        if provider == HotkeyProvider::Portal {
+            crate::portal_status::record_error("GlobalShortcuts", &error);
+        }
+        self.attempts.push(ProviderAttempt {
+            provider,
+            error: error.to_string(),
+        });
+    }
+
+    pub fn set_active(&mut self, provider: HotkeyProvider, accelerators: Vec<String>) {
+        tracing::info!(
+            "Global shortcuts: using {} ({})",
+            provider,
+            accelerators.join(", ")
+        );
+        crate::portal_status::update(|status| {
+            status.shortcuts = crate::portal_status::SessionState::Active;
+            status.shortcut_provider = Some(provider);
+            status.accelerators = accelerators.clone();
+        });
+        self.active = Some(provider);
+        self.accelerators = accelerators;
+    }
+
+    /// Human-readable lines for logs and `--capabilities`
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        match self.active {
+            Some(provider) => lines.push(format!(
+                "active provider: {} [{}]",
+                provider,
+                self.accelerators.join(", ")
+            )),
+            None => lines.push("active provider: none".to_string()),
+        }
+        for attempt in &self.attempts {
+            lines.push(format!("skipped {}: {}", attempt.provider, attempt.error));
+        }
+        lines
+    }
+}
+
+/// Modifier set of an accelerator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub logo: bool,
+}
+
+/// Parsed accelerator such as `<Super>backslash` or `Ctrl+Alt+space`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accelerator {
+    pub modifiers: Modifiers,
+    /// Key name as written in the config (xkb keysym name, e.g. "backslash", "F13")
+    pub key: String,
+}
+
+impl Accelerator {
+    pub fn parse(accelerator: &str) -> Option<Self> {
+        let mut modifiers = Modifiers::default();
+        let mut rest = accelerator.trim();
+
+        // GTK style: <Super><Ctrl>key
+        while let Some(stripped) = rest.strip_prefix('<') {
+            let end = stripped.find('>')?;
+            apply_modifier(&mut modifiers, &stripped[..end])?;
+            rest = &stripped[end + 1..];
+        }
+
+        // Plus style: Super+Ctrl+key
+        let mut parts: Vec<&str> = rest.split('+').collect();
+        let key = parts.pop()?.trim();
+        for part in parts {
+            apply_modifier(&mut modifiers, part.trim())?;
+        }
+
+        if key.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            modifiers,
+            key: key.to_string(),
+        })
+    }
+
+    /// Hyprland `bind` arguments: `SUPER CTRL,backslash`
+    pub fn to_hyprland(&self) -> String {
+        let mut mods = Vec::new();
+        if self.modifiers.logo {
+            mods.push("SUPER");
+        }
+        if self.modifiers.ctrl {
+            mods.push("CTRL");
+        }
+        if self.modifiers.alt {
+            mods.push("ALT");
+        }
+        if self.modifiers.shift {
+            mods.push("SHIFT");
+        }
+        format!("{},{}", mods.join(" "), self.key)
+    }
+
+    /// sway `bindsym` key combination: `Mod4+Ctrl+backslash`
+    pub fn to_sway(&self) -> String {
+        let mut parts = Vec::new();
+        if self.modifiers.logo {
+            parts.push("Mod4");
+        }
+        if self.modifiers.ctrl {
+            parts.push("Ctrl");
+        }
+        if self.modifiers.alt {
+            parts.push("Mod1");
+        }
+        if self.modifiers.shift {
+            parts.push("Shift");
+        }
+        parts.push(&self.key);
+        parts.join("+")
+    }
+
+    /// Same key combination, ignoring how the key name is capitalized
+    pub fn same_combo(&self, other: &Accelerator) -> bool {
+        self.modifiers == other.modifiers && self.key.eq_ignore_ascii_case(&other.key)
+    }
+
+    /// Why this combination cannot serve as a global shortcut, if it cannot.
+    ///
+    /// The key must be one the evdev fallback knows, and everything but a
+    /// function key needs Super, Ctrl or Alt so the shortcut does not swallow
+    /// ordinary typing.
+    pub fn global_shortcut_problem(&self) -> Option<&'static str> {
+        if crate::evdev_input::key_code(&self.key).is_none() {
+            return Some("Unsupported key");
+        }
+        let function_key = self.key.len() > 1
+            && self.key.starts_with(['F', 'f'])
+            && self.key[1..].parse::<u8>().is_ok();
+        let Modifiers {
+            ctrl,
+            alt,
+            shift,
+            logo,
+        } = self.modifiers;
+        if !(ctrl || alt || logo || function_key) {
+            return Some(if shift {
+                "Shift alone would block typing; add Super, Ctrl or Alt"
+            } else {
+                "Add a modifier: Super, Ctrl or Alt"
+            });
+        }
+        None
+    }
+}
+
+/// Config form understood by every provider: `<Super><Ctrl>backslash`
+impl fmt::Display for Accelerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = [
+            (self.modifiers.logo, "<Super>"),
+            (self.modifiers.ctrl, "<Ctrl>"),
+            (self.modifiers.alt, "<Alt>"),
+            (self.modifiers.shift, "<Shift>"),
+        ];
+        for (held, name) in modifiers {
+            if held {
+                f.write_str(name)?;
+            }
+        }
+        f.write_str(&self.key)
+    }
+}
+
+fn apply_modifier(modifiers: &mut Modifiers, name: &str) -> Option<()> {
+    match name.to_lowercase().as_str() {
+        "super" | "meta" | "logo" | "mod4" => modifiers.logo = true,
+        "ctrl" | "control" | "primary" => modifiers.ctrl = true,
+        "alt" | "mod1" => modifiers.alt = true,
+        "shift" => modifiers.shift = true,
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Detect a compositor whose IPC can bind keys for us
+pub fn detect_compositor() -> Option<HotkeyProvider> {
+    if std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some() {
+        Some(HotkeyProvider::Hyprland)
+    } else if std::env::var_os("SWAYSOCK").is_some() {
+        Some(HotkeyProvider::Sway)
+    } else {
+        None
+    }
+}
+
+/// Key bindings registered with a compositor, removed again on drop
+pub struct CompositorBinding {
+    provider: HotkeyProvider,
+    accelerator: Accelerator,
+    /// The combination was unbound before, so dropping may unbind it
+    owns_combo: bool,
+    /// A separate release binding was added (push-to-talk on sway)
+    release_bound: bool,
+}
+
+impl CompositorBinding {
+    /// Bind `accelerator` so pressing (and releasing, for push-to-talk) runs
+    /// `sonori shortcut press|release`, which reaches us through the IPC socket
+    pub fn bind(
+        provider: HotkeyProvider,
+        accelerator: &Accelerator,
+        shortcut_mode: ShortcutMode,
+    ) -> Result<Self> {
+        let exe = std::env::current_exe().context("Failed to resolve sonori executable")?;
+        let exe = shell_quote(&exe.to_string_lossy());
+        let push_to_talk = shortcut_mode == ShortcutMode::PushToTalk;
+        let mut owns_combo = true;
+        let mut release_bound = false;
+
+        match provider {
+            HotkeyProvider::Hyprland => {
+                let combo = accelerator.to_hyprland();
+                // `unbind` removes every binding of a combination, the user's included
+                if hyprland_has_bind(&hyprland_request("j/binds")?, accelerator) {
+                    tracing::warn!(
+                        "{} is already bound in Hyprland; both bindings will run, and the \
+                         combination stays bound after Sonori exits",
+                        accelerator
+                    );
+                    owns_combo = false;
+                }
+                hyprland_command(&format!(
+                    "keyword bind {},exec,{} shortcut press",
+                    combo, exe
+                ))?;
+                if push_to_talk {
+                    hyprland_command(&format!(
+                        "keyword bindr {},exec,{} shortcut release",
+                        combo, exe
+                    ))?;
+                }
+            }
+            HotkeyProvider::Sway => {
+                let combo = accelerator.to_sway();
+                // A second `bindsym` replaces the user's binding instead of adding to it
+                if sway_has_bind(&sway_request(SWAY_GET_CONFIG, "")?, accelerator) {
+                    return Err(anyhow!(
+                        "{} is already bound in sway; leaving that binding in place",
+                        accelerator
+                    ));
+                }
+                sway_command(&format!(
+                    "bindsym --no-repeat {} exec {}",
+                    combo,
+                    sway_quote(&format!("{} shortcut press", exe))
+                ))?;
+                if push_to_talk {
+                    sway_command(&format!(
+                        "bindsym --release {} exec {}",
+                        combo,
+                        sway_quote(&format!("{} shortcut release", exe))
+                    ))?;
+                    release_bound = true;
+                }
+            }
+            other => return Err(anyhow!("{} is not a compositor provider", other)),
+        }
+
+        Ok(Self {
+            provider,
+            accelerator: accelerator.clone(),
+            owns_combo,
+            release_bound,
+        })
+    }
+}
+
+impl Drop for CompositorBinding {
+    fn drop(&mut self) {
+        // Best-effort: leave the user's compositor config as we found it
+        let result = match self.provider {
+            HotkeyProvider::Hyprland if self.owns_combo => hyprland_command(&format!(
+                "keyword unbind {}",
+                self.accelerator.to_hyprland()
+            )),
+            HotkeyProvider::Sway if self.owns_combo => {
+                let combo = self.accelerator.to_sway();
+                sway_command(&format!("unbindsym {}", combo)).and_then(|_| {
+                    if self.release_bound {
+                        sway_command(&format!("unbindsym --release {}", combo))
+                    } else {
+                        Ok(())
+                    }
+                })
+            }
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to remove compositor shortcut: {}", e);
+        }
+    }
+}
+
+fn hyprland_socket_path() -> Result<PathBuf> {
+    let signature = std::env::var("HYPRLAND_INSTANCE_SIGNATURE")
+        .context("HYPRLAND_INSTANCE_SIGNATURE is not set")?;
+    let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_default();
+    let candidates = [
+        PathBuf::from(runtime_dir).join("hypr").join(&signature),
+        PathBuf::from("/tmp/hypr").join(&signature),
+    ];
+    candidates
+        .iter()
+        .map(|dir| dir.join(".socket.sock"))
+        .find(|path| path.exists())
+        .ok_or_else(|| anyhow!("Hyprland control socket not found"))
+}
+
+/// Send a request to Hyprland's control socket and return its reply
+fn hyprland_request(request: &str) -> Result<String> {
+    let mut stream = UnixStream::connect(hyprland_socket_path()?)
+        .context("Failed to connect to Hyprland socket")?;
+    stream.write_all(request.as_bytes())?;
+    stream.shutdown(std::net::Shutdown::Write)?;
+
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply)?;
+    Ok(reply)
+}
+
+fn hyprland_command(command: &str) -> Result<()> {
+    let reply = hyprland_request(command)?;
+    if reply.trim() == "ok" {
+        Ok(())
+    } else {
+        Err(anyhow!("Hyprland rejected '{}': {}", command, reply.trim()))
+    }
+}
+
+/// Whether Hyprland's `j/binds` reply binds the same combination as `accelerator`
+fn hyprland_has_bind(binds: &str, accelerator: &Accelerator) -> bool {
+    let Ok(binds) = serde_json::from_str::<Vec<serde_json::Value>>(binds) else {
+        return false;
+    };
+    // Hyprland's modmask bits: SHIFT 1, CTRL 4, ALT 8, SUPER 64
+    let modifiers = &accelerator.modifiers;
+    let modmask = [
+        (modifiers.shift, 1),
+        (modifiers.ctrl, 4),
+        (modifiers.alt, 8),
+        (modifiers.logo, 64),
+    ]
+    .iter()
+    .filter(|(held, _)| *held)
+    .map(|(_, bit)| bit)
+    .sum::<u64>();
+    binds.iter().any(|bind| {
+        bind["modmask"].as_u64() == Some(modmask)
+            && bind["key"]
+                .as_str()
+                .is_some_and(|key| key.eq_ignore_ascii_case(&accelerator.key))
+    })
+}
+
+/// Quote `value` as one `sh` word
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Quote a sway command argument so spaces, `;` and `,` stay inside it
+fn sway_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', r"\\").replace('"', "\\\""))
+}
+
+/// i3-ipc RUN_COMMAND message type
+const SWAY_RUN_COMMAND: u32 = 0;
+/// i3-ipc GET_CONFIG message type
+const SWAY_GET_CONFIG: u32 = 9;
+
+/// Send one i3-ipc message to sway and return its JSON reply
+fn sway_request(message_type: u32, payload: &str) -> Result<serde_json::Value> {
+    let socket = std::env::var("SWAYSOCK").context("SWAYSOCK is not set")?;
+    let mut stream = UnixStream::connect(socket).context("Failed to connect to sway socket")?;
+
+    let mut message = Vec::with_capacity(14 + payload.len());
+    message.extend_from_slice(b"i3-ipc");
+    message.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+    message.extend_from_slice(&message_type.to_ne_bytes());
+    message.extend_from_slice(payload.as_bytes());
+    stream.write_all(&message)?;
+
+    let mut header = [0u8; 14];
+    stream.read_exact(&mut header)?;
+    let length = u32::from_ne_bytes([header[6], header[7], header[8], header[9]]) as usize;
+    let mut reply = vec![0u8; length];
+    stream.read_exact(&mut reply)?;
+
+    serde_json::from_slice(&reply).context("Invalid sway IPC reply")
+}
+
+/// Whether sway's GET_CONFIG reply has a default-mode `bindsym` for the same
+/// combination as `accelerator`; bindings added at runtime are not in the reply
+fn sway_has_bind(reply: &serde_json::Value, accelerator: &Accelerator) -> bool {
+    let included = reply["included_configs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|included| included["raw_contents"].as_str());
+    let configs: Vec<&str> = reply["config"]
+        .as_str()
+        .into_iter()
+        .chain(included)
+        .collect();
+    let lines = || {
+        configs
+            .iter()
+            .flat_map(|config| config.lines())
+            .map(str::trim)
+    };
+
+    let mut variables: Vec<(&str, &str)> = lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            match (words.next(), words.next(), words.next()) {
+                (Some("set"), Some(name), Some(value)) if name.starts_with('$') => {
+                    Some((name, value))
+                }
+                _ => None,
+            }
+        })
+        .collect();
+    // Longest names first so `$mod` does not replace the start of `$mod2`
+    variables.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+    let same_combo = |combo: &str| {
+        let combo = variables
+            .iter()
+            .fold(combo.to_string(), |combo, (name, value)| {
+                combo.replace(name, value)
+            });
+        let mut parts: Vec<&str> = combo.split('+').collect();
+        let key = parts.pop().unwrap_or_default();
+        let mut modifiers = Modifiers::default();
+        for part in parts {
+            if apply_modifier(&mut modifiers, part).is_none() {
+                return false;
+            }
+        }
+        modifiers == accelerator.modifiers && key.eq_ignore_ascii_case(&accelerator.key)
+    };
+
+    // Blocks opened by `mode "name" {` hold bindings of other modes
+    let mut depth = 0usize;
+    let mut mode_depth: Option<usize> = None;
+    let mut in_bindsym_block = false;
+    for line in lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line == "}" {
+            if mode_depth == Some(depth) {
+                mode_depth = None;
+            }
+            depth = depth.saturating_sub(1);
+            in_bindsym_block = false;
+            continue;
+        }
+        let mut words = line.split_whitespace().peekable();
+        let first = words.peek().copied().unwrap_or_default();
+        if line.ends_with('{') {
+            depth += 1;
+            if first == "mode" && mode_depth.is_none() {
+                mode_depth = Some(depth);
+            }
+            in_bindsym_block = first == "bindsym";
+            continue;
+        }
+        if mode_depth.is_some() {
+            continue;
+        }
+        if !in_bindsym_block {
+            if first != "bindsym" {
+                continue;
+            }
+            words.next();
+        }
+        // The combination is the first word that is not a flag such as `--release`
+        if words
+            .find(|word| !word.starts_with("--"))
+            .is_some_and(same_combo)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+fn sway_command(command: &str) -> Result<()> {
+    let replies: Vec<serde_json::Value> =
+        serde_json::from_value(sway_request(SWAY_RUN_COMMAND, command)?)
+            .context("Invalid sway IPC reply")?;
+    match replies
+        .iter()
+        .find(|r| r.get("success") != Some(&serde_json::Value::Bool(true)))
+    {
+        None => Ok(()),
+        Some(failed) => Err(anyhow!(
+            "sway rejected '{}': {}",
+            command,
+            failed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown error")
+        )),
+    }
+}
+
+/// Run an action shortcut the same way its overlay button does
+async fn perform_action(
+    action: ShortcutAction,
+    targets: &ShortcutTargets,
+    manual_session_tx: &mpsc::Sender<ManualSessionCommand>,
+    transcription_mode: &AtomicU8,
+) {
+    match action {
+        ShortcutAction::CopyTranscript => {
+            let transcript =
+                crate::transcript_spill::full_transcript(&targets.transcript_history.read());
+            if transcript.trim().is_empty() {
+                tracing::info!("Copy shortcut: transcript is empty");
+                return;
+            }
+            match tokio::task::spawn_blocking(move || {
+                crate::copy::WlCopy::copy_to_clipboard(&transcript)
+            })
+            .await
+            {
+                Ok(Ok(())) => crate::toast::show(crate::i18n::tr("toast.copied")),
+                Ok(Err(e)) => tracing::warn!("Copy shortcut failed: {}", e),
+                Err(e) => tracing::warn!("Copy shortcut worker failed: {}", e),
+            }
+        }
+        ShortcutAction::ResetTranscript => {
+            targets.transcript_history.write().clear();
+            let mut audio_data = targets.audio_data.write();
+            audio_data.transcript.clear();
+            audio_data.reset_requested = true;
+            drop(audio_data);
+            crate::session_history::transcript_reset();
+        }
+        ShortcutAction::SwitchMode => {
+            let new_mode =
+                match TranscriptionMode::from_u8(transcription_mode.load(Ordering::Relaxed)) {
+                    TranscriptionMode::RealTime => TranscriptionMode::Manual,
+                    TranscriptionMode::Manual => TranscriptionMode::RealTime,
+                };
+            if let Err(e) = manual_session_tx
+                .send(ManualSessionCommand::SwitchMode(new_mode))
+                .await
+            {
+                tracing::warn!("Failed to send mode switch command: {}", e);
+            }
+        }
+        ShortcutAction::ToggleClickThrough => {
+            crate::click_through::toggle();
+        }
+        ShortcutAction::ToggleAutoPaste => {
+            crate::auto_paste::toggle();
+        }
+    }
+}
+
+/// Turn provider-agnostic shortcut events into manual session commands and actions
+pub async fn run_dispatcher(
+    mut events: mpsc::Receiver<ShortcutEvent>,
+    shortcut_mode: ShortcutMode,
+    manual_session_tx: mpsc::Sender<ManualSessionCommand>,
+    transcription_mode: Arc<AtomicU8>,
+    recording: Arc<AtomicBool>,
+    targets: ShortcutTargets,
+) {
+    while let Some(event) = events.recv().await {
+        if let ShortcutEvent::Action(action) = event {
+            perform_action(action, &targets, &manual_session_tx, &transcription_mode).await;
+            continue;
+        }
+
+        // Only act in Manual mode
+        let mode = TranscriptionMode::from_u8(transcription_mode.load(Ordering::Relaxed));
+        if mode != TranscriptionMode::Manual {
+            continue;
+        }
+
+        let command = match (event, shortcut_mode) {
+            (ShortcutEvent::Pressed, ShortcutMode::Toggle) => {
+                // Toggle: start if not recording, stop if recording
+                if recording.load(Ordering::Relaxed) {
+                    ManualSessionCommand::StopSession { responder: None }
+                } else {
+                    ManualSessionCommand::StartSession { responder: None }
+                }
+            }
+            // Push-to-talk: start on press, stop on release
+            (ShortcutEvent::Pressed, ShortcutMode::PushToTalk) => {
+                ManualSessionCommand::StartSession { responder: None }
+            }
+            (ShortcutEvent::Released, ShortcutMode::PushToTalk) => {
+                ManualSessionCommand::StopSession { responder: None }
+            }
+            (ShortcutEvent::Released, ShortcutMode::Toggle) | (ShortcutEvent::Action(_), _) => {
+                continue
+            }
+        };
+
+        if let Err(e) = manual_session_tx.send(command).await {
+            tracing::warn!("Failed to send manual session command: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gtk_and_plus_style_accelerators() {
+        let gtk = Accelerator::parse("<Super><Ctrl>backslash").unwrap();
+        let plus = Accelerator::parse("Super+Ctrl+backslash").unwrap();
+
+        assert_eq!(gtk, plus);
+        assert!(gtk.modifiers.logo && gtk.modifiers.ctrl);
+        assert_eq!(gtk.key, "backslash");
+        assert!(Accelerator::parse("<Hyper>x").is_none());
+    }
+
+    #[test]
+    fn formats_and_checks_captured_accelerators() {
+        let accelerator = Accelerator::parse("Ctrl+Super+backslash").unwrap();
+        assert_eq!(accelerator.to_string(), "<Super><Ctrl>backslash");
+        assert_eq!(
+            Accelerator::parse(&accelerator.to_string()),
+            Some(accelerator.clone())
+        );
+        assert!(accelerator.same_combo(&Accelerator::parse("<Ctrl><Super>Backslash").unwrap()));
+        assert_eq!(accelerator.global_shortcut_problem(), None);
+
+        assert_eq!(
+            Accelerator::parse("F13").unwrap().global_shortcut_problem(),
+            None
+        );
+        assert!(Accelerator::parse("a")
+            .unwrap()
+            .global_shortcut_problem()
+            .is_some());
+        assert!(Accelerator::parse("<Shift>a")
+            .unwrap()
+            .global_shortcut_problem()
+            .is_some_and(|problem| problem.starts_with("Shift alone")));
+        assert_eq!(
+            Accelerator::parse("<Super>Print")
+                .unwrap()
+                .global_shortcut_problem(),
+            Some("Unsupported key")
+        );
+    }
+
+    #[test]
+    fn formats_compositor_bindings() {
+        let accelerator = Accelerator::parse("<Super><Alt>space").unwrap();
+
+        assert_eq!(accelerator.to_hyprland(), "SUPER ALT,space");
+        assert_eq!(accelerator.to_sway(), "Mod4+Mod1+space");
+
+        assert_eq!(
+            shell_quote("/opt/my apps/it's/sonori"),
+            r"'/opt/my apps/it'\''s/sonori'"
+        );
+        assert_eq!(
+            sway_quote(r#"'/opt/a "b"/sonori' shortcut press"#),
+            r#""'/opt/a \"b\"/sonori' shortcut press""#
+        );
+    }
+
+    #[test]
+    fn detects_existing_hyprland_binds() {
+        let binds = r#"[
+            {"modmask": 72, "key": "space", "dispatcher": "exec", "arg": "wofi"},
+            {"modmask": 64, "key": "Return", "dispatcher": "exec", "arg": "foot"}
+        ]"#;
+        let bound =
+            |accelerator| hyprland_has_bind(binds, &Accelerator::parse(accelerator).unwrap());
+
+        assert!(bound("<Super><Alt>space"));
+        assert!(bound("<Super>return"));
+        assert!(!bound("<Super>space"));
+        assert!(!bound("<Super><Shift>Return"));
+        assert!(!hyprland_has_bind(
+            "not json",
+            &Accelerator::parse("<Super>x").unwrap()
+        ));
+    }
+
+    #[test]
+    fn detects_existing_sway_binds() {
+        let config = r#"
+            set $mod Mod4
+            set $mod2 Mod1
+            bindsym $mod+Return exec foot
+            bindsym --release $mod2+Shift+x exec grim
+            bindsym {
+                $mod+Ctrl+q kill
+            }
+            mode "resize" {
+                bindsym $mod+space mode default
+            }
+        "#;
+        let reply = serde_json::json!({ "config": config });
+        let bound = |accelerator| sway_has_bind(&reply, &Accelerator::parse(accelerator).unwrap());
+
+        assert!(bound("<Super>return"));
+        assert!(bound("<Alt><Shift>x"));
+        assert!(bound("<Super><Ctrl>q"));
+        // Other modes and other modifiers do not count
+        assert!(!bound("<Super>space"));
+        assert!(!bound("<Super><Shift>Return"));
+
+        let included = serde_json::json!({
+            "config": "include ~/.config/sway/keys",
+            "included_configs": [{ "raw_contents": "bindsym Mod4+backslash exec x" }]
+        });
+        assert!(sway_has_bind(
+            &included,
+            &Accelerator::parse("<Super>backslash").unwrap()
+        ));
+    }
+
+    #[test]
+    fn action_ids_round_trip() {
+        for action in ShortcutAction::ALL {
+            assert_eq!(ShortcutAction::from_id(action.id()), Some(action));
+        }
+        assert_eq!(ShortcutAction::from_id("toggle_manual"), None);
+    }
+}
@@ -0,0 +1,730 @@
+//! Translations of the user-facing strings: CLI prompts and help, overlay status
+//! text and settings panel labels.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+    /// Parse a language tag such as `de`, `de-AT` or `de_DE.UTF-8`
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let language = tag
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "de" => Some(Locale::De),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::De => DE,
+        }
+    }
+
+    /// Translation of `key` in this locale only
+    pub fn lookup(self, key: &str) -> Option<&'static str> {
+        self.catalog()
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, text)| *text)
+    }
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Pick the UI locale; later calls keep the first choice
+pub fn init(ui_language: Option<&str>) -> Locale {
+    let locale = resolve(ui_language, |name| std::env::var(name).ok());
+    *LOCALE.get_or_init(|| locale)
+}
+
+fn resolve(ui_language: Option<&str>, env: impl Fn(&str) -> Option<String>) -> Locale {
+    if let Some(tag) = ui_language.map(str::trim).filter(|tag| !tag.is_empty()) {
+        match Locale::parse(tag) {
+            Some(locale) => return locale,
+            None => tracing::warn!(
+                "general_config.ui_language = \"{}\" has no translation; using the system language",
+                tag
+            ),
+        }
+    }
+    // The first variable that is set decides, as for other programs
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|name| env(name).filter(|value| !value.is_empty()))
+        .and_then(|value| Locale::parse(&value))
+        .unwrap_or(Locale::En)
+}
+
+pub fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or(Locale::En)
+}
+
+/// Translation of `key` in the UI locale
+pub fn tr(key: &'static str) -> &'static str {
+    locale()
+        .lookup(key)
+        .or_else(|| Locale::En.lookup(key))
+        .unwrap_or(key)
+}
+
+/// Translation of `key` with its `{name}` placeholders filled in
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = tr(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+const EN: &[(&str, &str)] = &[
+    // Overlay
+    ("overlay.ready", "Sonori is ready"),
+    ("overlay.countdown", "{secs}s left"),
+    ("status.downloading", "Downloading {percent}%"),
+    ("status.ready", "Ready"),
+    ("status.loading", "Loading..."),
+    ("status.loading_detail", "Loading: {detail}"),
+    ("status.mic_disconnected", "Mic disconnected"),
+    ("status.mic_stalled", "Mic stopped sending audio"),
+    ("status.mic_muted", "Mic muted"),
+    (
+        "status.clipping",
+        "Input clipping \u{2014} lower your microphone gain",
+    ),
+    ("status.clipping_click", " (click: {db} dB)"),
+    ("toast.copied", "Copied to clipboard"),
+    ("toast.nothing_to_undo", "Nothing to undo"),
+    ("toast.removed_segment", "Removed last segment"),
+    ("toast.session_wiped", "Session wiped"),
+    ("toast.pipeline_crashed", "Speech pipeline crashed"),
+    ("toast.config_reloaded", "Config reloaded"),
+    (
+        "toast.config_needs_restart",
+        "Config changed \u{2014} restart to apply",
+    ),
+    (
+        "toast.config_invalid",
+        "Config has errors \u{2014} kept running settings",
+    ),
+    (
+        "toast.post_process_invalid",
+        "Post-processing config invalid \u{2014} kept old stages",
+    ),
+    ("toast.profile_switched", "Profile: {profile}"),
+    (
+        "toast.profile_invalid",
+        "Profile has errors \u{2014} not switched",
+    ),
+    (
+        "toast.mic_muted_paused",
+        "Microphone muted \u{2014} recording paused",
+    ),
+    // Settings panel
+    ("settings.tab.backend", "Backend"),
+    ("settings.tab.audio", "Audio"),
+    ("settings.tab.behavior", "Behavior"),
+    ("settings.tab.display", "Display"),
+    ("settings.tab.appearance", "Appearance"),
+    ("settings.tab.history", "History"),
+    ("settings.tab.integration", "Integration"),
+    ("settings.reset", "Reset"),
+    ("settings.apply", "Apply"),
+    ("settings.backend", "Backend"),
+    ("settings.english_only", "English only"),
+    ("settings.model", "Model"),
+    ("settings.language", "Language"),
+    ("settings.gpu", "GPU acceleration"),
+    ("settings.gpu_backend", "GPU backend"),
+    (
+        "settings.gpu_backend_resolved",
+        "GPU backend (using {device})",
+    ),
+    ("settings.threads", "Threads"),
+    ("settings.vad_sensitivity", "VAD sensitivity"),
+    ("settings.sound_feedback", "Sound feedback"),
+    ("settings.volume", "Volume"),
+    ("settings.input_device", "Input device"),
+    ("settings.auto_paste", "Auto-paste"),
+    ("settings.clear_on_session", "Clear on new session"),
+    ("settings.post_processing", "Post-processing"),
+    ("settings.typewriter", "Typewriter effect"),
+    ("settings.shortcut_mode", "Shortcut mode"),
+    ("settings.toggle_shortcut", "Toggle shortcut"),
+    ("settings.paste_shortcut", "Paste shortcut"),
+    ("settings.magic_mode", "Magic mode"),
+    ("settings.vsync", "VSync mode"),
+    ("settings.target_fps", "Target FPS"),
+    ("settings.system_tray", "System tray"),
+    ("settings.spectrogram_scale", "Spectrogram scale"),
+    ("settings.visual_theme", "Visual theme"),
+    ("settings.spectrogram_skin", "Spectrogram skin"),
+    ("settings.window_position", "Window position"),
+    ("settings.font_size", "Font size"),
+    ("settings.recording_indicator", "Recording indicator"),
+    ("settings.profile", "Profile"),
+    ("settings.profile_none", "None"),
+    ("tip.backend", "Transcription engine"),
+    ("tip.english_only", "English-only models are faster"),
+    ("tip.model", "Larger models are more accurate, but slower"),
+    ("tip.language", "Language used for transcription"),
+    ("tip.gpu", "Use GPU when supported"),
+    (
+        "tip.gpu_backend",
+        "Expected GPU API; the build decides, this only checks it",
+    ),
+    ("tip.threads", "CPU threads used for transcription"),
+    ("tip.vad_sensitivity", "Voice detection sensitivity"),
+    ("tip.sound_feedback", "Play start and stop sounds"),
+    ("tip.volume", "Sound feedback volume"),
+    (
+        "tip.input_device",
+        "PipeWire source to record from (applies on restart)",
+    ),
+    (
+        "tip.auto_paste",
+        "Paste into the focused app; off copies only",
+    ),
+    (
+        "tip.clear_on_session",
+        "Clear old transcript on new recording",
+    ),
+    ("tip.post_processing", "Clean transcription artifacts"),
+    ("tip.typewriter", "Animate text character by character"),
+    (
+        "tip.shortcut_mode",
+        "Toggle records until stopped; push-to-talk holds",
+    ),
+    (
+        "tip.toggle_shortcut",
+        "Click, then press the new shortcut; Esc cancels",
+    ),
+    ("tip.paste_shortcut", "Shortcut sent when pasting"),
+    ("tip.magic_mode", "Enhance transcripts with local AI"),
+    ("tip.vsync", "Sync frames to display refresh"),
+    ("tip.target_fps", "Frame-rate cap when VSync is off"),
+    ("tip.system_tray", "Show a system tray icon"),
+    ("tip.spectrogram_scale", "Frequency axis for Bars and Meter"),
+    ("tip.visual_theme", "Curated overlay appearance"),
+    ("tip.spectrogram_skin", "Audio visualization style"),
+    ("tip.window_position", "Overlay position preset"),
+    ("tip.font_size", "Transcription text size"),
+    ("tip.recording_indicator", "Show recording indicator"),
+    (
+        "tip.profile",
+        "Settings layered over config.toml; switches at once",
+    ),
+    ("dialog.profile_title", "Switch profile?"),
+    (
+        "dialog.profile_reload",
+        "Switching to {profile} reloads the transcription backend.",
+    ),
+    (
+        "dialog.profile_download",
+        "{model} needs a {size} download.",
+    ),
+    ("dialog.profile_confirm", "Switch"),
+    // Integration tab
+    ("integration.application_id", "Application ID"),
+    ("integration.global_shortcuts", "Global shortcuts"),
+    ("integration.bound_shortcuts", "Bound shortcuts"),
+    ("integration.paste_session", "Paste session"),
+    ("integration.rebind", "Re-bind shortcuts"),
+    ("integration.reconnect", "Reconnect paste"),
+    ("integration.active_via", "active via {provider}"),
+    ("integration.none", "none"),
+    ("integration.last_error", "Last error: {error}"),
+    ("integration.no_errors", "No portal errors"),
+    ("session.disabled", "disabled"),
+    ("session.connecting", "connecting"),
+    ("session.active", "active"),
+    ("session.failed", "not available"),
+    // Quick help
+    ("help.global_shortcuts", "Global shortcuts"),
+    ("help.overlay_keys", "Overlay keys"),
+    ("help.mouse", "Mouse"),
+    ("help.buttons_realtime", "Buttons (real-time mode)"),
+    ("help.buttons_manual", "Buttons (manual mode)"),
+    ("help.cli_commands", "CLI commands"),
+    ("help.key.hover", "Hover transcript"),
+    ("help.key.scroll", "Scroll wheel"),
+    ("help.key.drag", "Alt/Super + drag"),
+    ("help.key.none", "(none)"),
+    ("help.show_controls", "Show control buttons"),
+    ("help.scroll", "Scroll transcript (pauses auto-scroll)"),
+    ("help.move_overlay", "Move the overlay"),
+    ("help.start_stop_recording", "Start/stop recording session"),
+    ("help.copy_transcript", "Copy current transcript"),
+    ("help.reset_transcript", "Reset transcript"),
+    (
+        "help.export_subtitles",
+        "Export subtitles of the last recording",
+    ),
+    ("help.undo", "Undo last segment"),
+    ("help.list_sessions", "List past sessions"),
+    ("help.show_help", "Show this help"),
+    ("help.quit", "Quit"),
+    ("help.toggle_manual", "Start/stop manual session"),
+    ("help.push_to_talk", "Hold to record (push-to-talk)"),
+    ("help.manual_mode", "{action} (manual mode)"),
+    ("help.privacy_wipe", "Privacy wipe (clear this session)"),
+    ("help.click_through", "Toggle click-through overlay"),
+    ("help.auto_paste", "Toggle auto-paste"),
+    ("help.shortcuts_disabled", "Global shortcuts are disabled"),
+    ("help.cancel_manual", "Cancel manual session"),
+    ("help.toggle_help", "Show/hide this help"),
+    ("help.pause_resume", "Pause or resume listening"),
+    ("help.toggle_magic", "Toggle LLM enhancement"),
+    ("help.copy_clipboard", "Copy transcript to clipboard"),
+    ("help.clear_transcript", "Clear transcript"),
+    ("help.switch_mode", "Switch real-time/manual mode"),
+    ("help.open_settings", "Open settings"),
+    ("help.quit_sonori", "Quit Sonori"),
+    // CLI
+    (
+        "cli.realtime_intro",
+        "Running in real-time CLI mode. Press Ctrl+C to exit, ? for help.",
+    ),
+    (
+        "cli.transcription_below",
+        "Transcription will appear below:",
+    ),
+    ("cli.manual_intro", "Running in manual CLI mode. Controls:"),
+    ("cli.shutting_down", "Shutting down..."),
+    ("cli.quit_requested", "Quit requested"),
+    ("cli.removed", "Removed: {segment}"),
+    ("cli.status", "Status: {status} | Transcript: {transcript}"),
+    ("cli.status_ready", "Ready"),
+    ("cli.status_recording", "Recording"),
+    ("cli.status_processing", "Processing"),
+    ("cli.status_active", "Session Active"),
+    ("cli.space_pressed", "Space pressed - toggling session..."),
+    (
+        "cli.session_stopped",
+        "Manual session stopped and processing...",
+    ),
+    ("cli.stop_failed", "Failed to stop manual session: {error}"),
+    ("cli.session_started", "Started new manual session: {id}"),
+    (
+        "cli.start_failed",
+        "Failed to start manual session: {error}",
+    ),
+    ("cli.copy_requested", "Copy transcript requested"),
+    ("cli.copied", "Transcript copied to clipboard successfully"),
+    ("cli.copy_failed", "Failed to copy transcript: {error}"),
+    ("cli.clipboard_failed", "Clipboard worker failed: {error}"),
+    (
+        "cli.nothing_to_copy",
+        "No transcript to copy (transcript is empty)",
+    ),
+    ("cli.subtitles_exported", "Subtitles exported to {path}"),
+    (
+        "cli.subtitle_export_failed",
+        "Subtitle export failed: {error}",
+    ),
+    ("cli.reset_requested", "Reset transcript requested"),
+    ("cli.reset_done", "Transcript reset successfully"),
+    (
+        "cli.unknown_command",
+        "Unknown command: '{input}'. Use SPACE, c, r, e, u, h, ?, or q.",
+    ),
+    ("cli.no_sessions", "No finished sessions yet"),
+    ("cli.past_sessions", "Past sessions (oldest first):"),
+    ("cli.download_prompt", "Download now? [y/N] "),
+];
+
+const DE: &[(&str, &str)] = &[
+    // Overlay
+    ("overlay.ready", "Sonori ist bereit"),
+    ("overlay.countdown", "noch {secs} s"),
+    ("status.downloading", "Download {percent} %"),
+    ("status.ready", "Bereit"),
+    ("status.loading", "Wird geladen..."),
+    ("status.loading_detail", "Lädt: {detail}"),
+    ("status.mic_disconnected", "Mikrofon getrennt"),
+    ("status.mic_stalled", "Mikrofon liefert kein Audio mehr"),
+    ("status.mic_muted", "Mikrofon stumm"),
+    (
+        "status.clipping",
+        "Eingang übersteuert \u{2014} Mikrofonpegel senken",
+    ),
+    ("status.clipping_click", " (Klick: {db} dB)"),
+    ("toast.copied", "In die Zwischenablage kopiert"),
+    ("toast.nothing_to_undo", "Nichts rückgängig zu machen"),
+    ("toast.removed_segment", "Letztes Segment entfernt"),
+    ("toast.session_wiped", "Sitzung gelöscht"),
+    ("toast.pipeline_crashed", "Sprachverarbeitung abgestürzt"),
+    ("toast.config_reloaded", "Konfiguration neu geladen"),
+    (
+        "toast.config_needs_restart",
+        "Konfiguration geändert \u{2014} Neustart nötig",
+    ),
+    (
+        "toast.config_invalid",
+        "Konfiguration fehlerhaft \u{2014} bisherige bleibt aktiv",
+    ),
+    (
+        "toast.post_process_invalid",
+        "Nachbearbeitung fehlerhaft \u{2014} bisherige Stufen bleiben",
+    ),
+    ("toast.profile_switched", "Profil: {profile}"),
+    (
+        "toast.profile_invalid",
+        "Profil fehlerhaft \u{2014} nicht gewechselt",
+    ),
+    (
+        "toast.mic_muted_paused",
+        "Mikrofon stumm \u{2014} Aufnahme pausiert",
+    ),
+    // Settings panel
+    ("settings.tab.backend", "Backend"),
+    ("settings.tab.audio", "Audio"),
+    ("settings.tab.behavior", "Verhalten"),
+    ("settings.tab.display", "Anzeige"),
+    ("settings.tab.appearance", "Aussehen"),
+    ("settings.tab.history", "Verlauf"),
+    ("settings.tab.integration", "Integration"),
+    ("settings.reset", "Zurücksetzen"),
+    ("settings.apply", "Übernehmen"),
+    ("settings.backend", "Backend"),
+    ("settings.english_only", "Nur Englisch"),
+    ("settings.model", "Modell"),
+    ("settings.language", "Sprache"),
+    ("settings.gpu", "GPU-Beschleunigung"),
+    ("settings.gpu_backend", "GPU-Backend"),
+    (
+        "settings.gpu_backend_resolved",
+        "GPU-Backend (nutzt {device})",
+    ),
+    ("settings.threads", "Threads"),
+    ("settings.vad_sensitivity", "VAD-Empfindlichkeit"),
+    ("settings.sound_feedback", "Tonsignale"),
+    ("settings.volume", "Lautstärke"),
+    ("settings.input_device", "Eingabegerät"),
+    ("settings.auto_paste", "Automatisch einfügen"),
+    ("settings.clear_on_session", "Bei neuer Sitzung leeren"),
+    ("settings.post_processing", "Nachbearbeitung"),
+    ("settings.typewriter", "Schreibmaschineneffekt"),
+    ("settings.shortcut_mode", "Kürzelmodus"),
+    ("settings.toggle_shortcut", "Umschaltkürzel"),
+    ("settings.paste_shortcut", "Einfügekürzel"),
+    ("settings.magic_mode", "Magic Mode"),
+    ("settings.vsync", "VSync-Modus"),
+    ("settings.target_fps", "Ziel-FPS"),
+    ("settings.system_tray", "Systemleiste"),
+    ("settings.spectrogram_scale", "Spektrogramm-Skala"),
+    ("settings.visual_theme", "Farbschema"),
+    ("settings.spectrogram_skin", "Spektrogramm-Stil"),
+    ("settings.window_position", "Fensterposition"),
+    ("settings.font_size", "Schriftgröße"),
+    ("settings.recording_indicator", "Aufnahmeanzeige"),
+    ("settings.profile", "Profil"),
+    ("settings.profile_none", "Keines"),
+    ("tip.backend", "Transkriptions-Engine"),
+    ("tip.english_only", "Reine Englisch-Modelle sind schneller"),
+    ("tip.model", "Größere Modelle sind genauer, aber langsamer"),
+    ("tip.language", "Sprache der Transkription"),
+    ("tip.gpu", "GPU nutzen, wenn unterstützt"),
+    (
+        "tip.gpu_backend",
+        "Erwartete GPU-API; der Build entscheidet, hier wird nur geprüft",
+    ),
+    ("tip.threads", "CPU-Threads für die Transkription"),
+    ("tip.vad_sensitivity", "Empfindlichkeit der Spracherkennung"),
+    ("tip.sound_feedback", "Start- und Stopptöne abspielen"),
+    ("tip.volume", "Lautstärke der Tonsignale"),
+    (
+        "tip.input_device",
+        "PipeWire-Quelle für die Aufnahme (gilt nach Neustart)",
+    ),
+    (
+        "tip.auto_paste",
+        "In die fokussierte App einfügen; aus kopiert nur",
+    ),
+    (
+        "tip.clear_on_session",
+        "Altes Transkript bei neuer Aufnahme leeren",
+    ),
+    ("tip.post_processing", "Transkriptionsartefakte bereinigen"),
+    ("tip.typewriter", "Text Zeichen für Zeichen einblenden"),
+    (
+        "tip.shortcut_mode",
+        "Umschalten nimmt bis zum Stopp auf, Push-to-Talk beim Halten",
+    ),
+    (
+        "tip.toggle_shortcut",
+        "Klicken, dann das neue Kürzel drücken; Esc bricht ab",
+    ),
+    ("tip.paste_shortcut", "Beim Einfügen gesendetes Kürzel"),
+    ("tip.magic_mode", "Transkripte mit lokaler KI verbessern"),
+    (
+        "tip.vsync",
+        "Bilder mit der Bildwiederholrate synchronisieren",
+    ),
+    ("tip.target_fps", "Bildratenlimit, wenn VSync aus ist"),
+    ("tip.system_tray", "Symbol in der Systemleiste anzeigen"),
+    ("tip.spectrogram_scale", "Frequenzachse für Bars und Meter"),
+    ("tip.visual_theme", "Vorgefertigtes Overlay-Aussehen"),
+    ("tip.spectrogram_skin", "Stil der Audiovisualisierung"),
+    ("tip.window_position", "Vorgabe für die Overlay-Position"),
+    ("tip.font_size", "Textgröße der Transkription"),
+    ("tip.recording_indicator", "Aufnahmeanzeige einblenden"),
+    (
+        "tip.profile",
+        "Einstellungen über config.toml; wechselt sofort",
+    ),
+    ("dialog.profile_title", "Profil wechseln?"),
+    (
+        "dialog.profile_reload",
+        "Der Wechsel zu {profile} lädt das Transkriptions-Backend neu.",
+    ),
+    (
+        "dialog.profile_download",
+        "{model} benötigt einen Download von {size}.",
+    ),
+    ("dialog.profile_confirm", "Wechseln"),
+    // Integration tab
+    ("integration.application_id", "Anwendungs-ID"),
+    ("integration.global_shortcuts", "Globale Kürzel"),
+    ("integration.bound_shortcuts", "Belegte Kürzel"),
+    ("integration.paste_session", "Einfügesitzung"),
+    ("integration.rebind", "Kürzel neu binden"),
+    ("integration.reconnect", "Einfügen verbinden"),
+    ("integration.active_via", "aktiv über {provider}"),
+    ("integration.none", "keine"),
+    ("integration.last_error", "Letzter Fehler: {error}"),
+    ("integration.no_errors", "Keine Portal-Fehler"),
+    ("session.disabled", "deaktiviert"),
+    ("session.connecting", "verbindet"),
+    ("session.active", "aktiv"),
+    ("session.failed", "nicht verfügbar"),
+    // Quick help
+    ("help.global_shortcuts", "Globale Tastenkürzel"),
+    ("help.overlay_keys", "Overlay-Tasten"),
+    ("help.mouse", "Maus"),
+    ("help.buttons_realtime", "Schaltflächen (Echtzeitmodus)"),
+    ("help.buttons_manual", "Schaltflächen (manueller Modus)"),
+    ("help.cli_commands", "CLI-Befehle"),
+    ("help.key.hover", "Über dem Transkript"),
+    ("help.key.scroll", "Mausrad"),
+    ("help.key.drag", "Alt/Super + Ziehen"),
+    ("help.key.none", "(keine)"),
+    ("help.show_controls", "Schaltflächen einblenden"),
+    ("help.scroll", "Transkript scrollen (hält Auto-Scroll an)"),
+    ("help.move_overlay", "Overlay verschieben"),
+    (
+        "help.start_stop_recording",
+        "Aufnahmesitzung starten/stoppen",
+    ),
+    ("help.copy_transcript", "Aktuelles Transkript kopieren"),
+    ("help.reset_transcript", "Transkript zurücksetzen"),
+    (
+        "help.export_subtitles",
+        "Untertitel der letzten Aufnahme exportieren",
+    ),
+    ("help.undo", "Letztes Segment rückgängig machen"),
+    ("help.list_sessions", "Frühere Sitzungen auflisten"),
+    ("help.show_help", "Diese Hilfe anzeigen"),
+    ("help.quit", "Beenden"),
+    ("help.toggle_manual", "Manuelle Sitzung starten/stoppen"),
+    ("help.push_to_talk", "Halten zum Aufnehmen (Push-to-Talk)"),
+    ("help.manual_mode", "{action} (manueller Modus)"),
+    ("help.privacy_wipe", "Datenschutz-Löschung (Sitzung leeren)"),
+    (
+        "help.click_through",
+        "Klickdurchlässiges Overlay umschalten",
+    ),
+    ("help.auto_paste", "Automatisches Einfügen umschalten"),
+    (
+        "help.shortcuts_disabled",
+        "Globale Tastenkürzel sind deaktiviert",
+    ),
+    ("help.cancel_manual", "Manuelle Sitzung abbrechen"),
+    ("help.toggle_help", "Diese Hilfe ein-/ausblenden"),
+    ("help.pause_resume", "Zuhören anhalten oder fortsetzen"),
+    ("help.toggle_magic", "LLM-Verbesserung umschalten"),
+    (
+        "help.copy_clipboard",
+        "Transkript in die Zwischenablage kopieren",
+    ),
+    ("help.clear_transcript", "Transkript leeren"),
+    ("help.switch_mode", "Zwischen Echtzeit und manuell wechseln"),
+    ("help.open_settings", "Einstellungen öffnen"),
+    ("help.quit_sonori", "Sonori beenden"),
+    // CLI
+    (
+        "cli.realtime_intro",
+        "Echtzeit-CLI-Modus. Strg+C beendet, ? zeigt die Hilfe.",
+    ),
+    (
+        "cli.transcription_below",
+        "Die Transkription erscheint hier:",
+    ),
+    ("cli.manual_intro", "Manueller CLI-Modus. Steuerung:"),
+    ("cli.shutting_down", "Wird beendet..."),
+    ("cli.quit_requested", "Beenden angefordert"),
+    ("cli.removed", "Entfernt: {segment}"),
+    ("cli.status", "Status: {status} | Transkript: {transcript}"),
+    ("cli.status_ready", "Bereit"),
+    ("cli.status_recording", "Aufnahme"),
+    ("cli.status_processing", "Verarbeitung"),
+    ("cli.status_active", "Sitzung aktiv"),
+    (
+        "cli.space_pressed",
+        "Leertaste gedrückt - Sitzung wird umgeschaltet...",
+    ),
+    (
+        "cli.session_stopped",
+        "Manuelle Sitzung gestoppt, wird verarbeitet...",
+    ),
+    (
+        "cli.stop_failed",
+        "Manuelle Sitzung konnte nicht gestoppt werden: {error}",
+    ),
+    (
+        "cli.session_started",
+        "Neue manuelle Sitzung gestartet: {id}",
+    ),
+    (
+        "cli.start_failed",
+        "Manuelle Sitzung konnte nicht gestartet werden: {error}",
+    ),
+    ("cli.copy_requested", "Transkript wird kopiert"),
+    ("cli.copied", "Transkript in die Zwischenablage kopiert"),
+    (
+        "cli.copy_failed",
+        "Transkript konnte nicht kopiert werden: {error}",
+    ),
+    (
+        "cli.clipboard_failed",
+        "Zwischenablage-Worker fehlgeschlagen: {error}",
+    ),
+    (
+        "cli.nothing_to_copy",
+        "Nichts zu kopieren (Transkript ist leer)",
+    ),
+    (
+        "cli.subtitles_exported",
+        "Untertitel exportiert nach {path}",
+    ),
+    (
+        "cli.subtitle_export_failed",
+        "Untertitel-Export fehlgeschlagen: {error}",
+    ),
+    ("cli.reset_requested", "Transkript wird zurückgesetzt"),
+    ("cli.reset_done", "Transkript zurückgesetzt"),
+    (
+        "cli.unknown_command",
+        "Unbekannter Befehl: '{input}'. Verwende SPACE, c, r, e, u, h, ? oder q.",
+    ),
+    ("cli.no_sessions", "Noch keine abgeschlossenen Sitzungen"),
+    ("cli.past_sessions", "Frühere Sitzungen (älteste zuerst):"),
+    ("cli.download_prompt", "Jetzt herunterladen? [y/N] "),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_key_exists_in_every_locale() {
+        for locale in Locale::ALL {
+            let keys: HashSet<&str> = locale.catalog().iter().map(|(key, _)| *key).collect();
+            assert_eq!(
+                keys.len(),
+                locale.catalog().len(),
+                "duplicate key in {}",
+                locale.code()
+            );
+            for other in Locale::ALL {
+                for (key, _) in other.catalog() {
+                    assert!(
+                        keys.contains(key),
+                        "{} is missing \"{}\"",
+                        locale.code(),
+                        key
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn translations_keep_their_placeholders() {
+        for (key, english) in EN {
+            for locale in Locale::ALL {
+                let text = locale.lookup(key).unwrap();
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(english),
+                    "{} in {}",
+                    key,
+                    locale.code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn locale_comes_from_config_then_environment() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            resolve(Some("de"), env(&[("LANG", "en_US.UTF-8")])),
+            Locale::De
+        );
+        assert_eq!(resolve(None, env(&[("LANG", "de_AT.UTF-8")])), Locale::De);
+        assert_eq!(resolve(Some("xx"), env(&[("LANG", "de_DE")])), Locale::De);
+        assert_eq!(
+            resolve(None, env(&[("LC_ALL", "C"), ("LANG", "de_DE")])),
+            Locale::En
+        );
+        assert_eq!(resolve(None, env(&[("LANG", "fr_FR.UTF-8")])), Locale::En);
+        assert_eq!(resolve(None, env(&[])), Locale::En);
+    }
+
+    #[test]
+    fn arguments_fill_placeholders() {
+        assert_eq!(tr_args("overlay.countdown", &[("secs", &15)]), "15s left");
+        assert_eq!(
+            Locale::De.lookup("overlay.countdown"),
+            Some("noch {secs} s")
+        );
+    }
+}
@@ -10,10 +10,13 @@ use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::Arc;
+
+use parking_lot::RwLock;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::mpsc;
 
+use crate::hotkeys::{HotkeyStatus, ShortcutEvent};
 use speechcore::{ManualSessionCommand, TranscriptionMode};
 
 /// IPC command sent from CLI client to running instance
@@ -28,10 +31,20 @@ pub enum IpcCommand {
     Stop,
     /// Cancel current session without processing
     Cancel,
+    /// Privacy wipe: cancel the session and clear every trace of it
+    Wipe,
     /// Get current status
     Status,
     /// Switch transcription mode
     SwitchMode { mode: String },
+    /// Global shortcut pressed (sent by compositor key bindings)
+    ShortcutPressed,
+    /// Global shortcut released (sent by compositor key bindings)
+    ShortcutReleased,
+    /// Report which global shortcut provider is active
+    Hotkeys,
+    /// Shut the running instance down (used by `--replace`)
+    Quit,
 }
 
 /// Response from running instance to CLI client
@@ -42,6 +55,8 @@ pub struct IpcResponse {
     pub message: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<IpcStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hotkeys: Option<HotkeyStatus>,
 }
 
 /// Current status of the running instance
@@ -59,6 +74,7 @@ impl IpcResponse {
             success: true,
             message: Some(message.into()),
             status: None,
+            hotkeys: None,
         }
     }
 
@@ -67,6 +83,16 @@ impl IpcResponse {
             success: true,
             message: None,
             status: Some(status),
+            hotkeys: None,
+        }
+    }
+
+    pub fn success_with_hotkeys(hotkeys: HotkeyStatus) -> Self {
+        Self {
+            success: true,
+            message: None,
+            status: None,
+            hotkeys: Some(hotkeys),
         }
     }
 
@@ -75,12 +101,13 @@ impl IpcResponse {
             success: false,
             message: Some(message.into()),
             status: None,
+            hotkeys: None,
         }
     }
 }
 
-/// Get the default socket path
-pub fn get_socket_path() -> PathBuf {
+/// `$XDG_RUNTIME_DIR`, or `/run/user/<uid>` when it is not set
+pub fn runtime_dir() -> PathBuf {
     let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| {
         // Fallback: try to determine UID from /proc/self
         let uid = std::fs::read_to_string("/proc/self/loginuid")
@@ -90,17 +117,133 @@ pub fn get_socket_path() -> PathBuf {
         format!("/run/user/{}", uid)
     });
     PathBuf::from(runtime_dir)
-        .join("sonori")
-        .join("control.sock")
 }
 
-/// IPC server that listens for commands from CLI clients
-pub struct IpcServer {
-    socket_path: PathBuf,
+/// Get the default socket path
+pub fn get_socket_path() -> PathBuf {
+    runtime_dir().join("sonori").join("control.sock")
+}
+
+/// Session commands shared by the socket and the D-Bus service
+#[derive(Clone)]
+pub struct SessionControl {
     manual_session_tx: mpsc::Sender<ManualSessionCommand>,
     transcription_mode: Arc<AtomicU8>,
     recording: Arc<AtomicBool>,
+}
+
+impl SessionControl {
+    pub fn new(
+        manual_session_tx: mpsc::Sender<ManualSessionCommand>,
+        transcription_mode: Arc<AtomicU8>,
+        recording: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            manual_session_tx,
+            transcription_mode,
+            recording,
+        }
+    }
+
+    pub async fn toggle(&self) -> IpcResponse {
+        let is_recording = self.recording.load(Ordering::Relaxed);
+        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
+
+        // In manual mode, toggle the session
+        if mode == TranscriptionMode::Manual {
+            let command = if is_recording {
+                ManualSessionCommand::StopSession { responder: None }
+            } else {
+                ManualSessionCommand::StartSession { responder: None }
+            };
+
+            if let Err(e) = self.manual_session_tx.send(command).await {
+                return IpcResponse::error(format!("Failed to send command: {}", e));
+            }
+
+            if is_recording {
+                IpcResponse::success("Recording stopped")
+            } else {
+                IpcResponse::success("Recording started")
+            }
+        } else {
+            // In realtime mode, just report status
+            IpcResponse::error(
+                "Toggle only works in manual mode. Use 'sonori switch-mode manual' first.",
+            )
+        }
+    }
+
+    pub async fn start(&self) -> IpcResponse {
+        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
+
+        if mode != TranscriptionMode::Manual {
+            return IpcResponse::error("Start only works in manual mode");
+        }
+
+        let command = ManualSessionCommand::StartSession { responder: None };
+        if let Err(e) = self.manual_session_tx.send(command).await {
+            return IpcResponse::error(format!("Failed to send command: {}", e));
+        }
+
+        IpcResponse::success("Recording started")
+    }
+
+    pub async fn stop(&self) -> IpcResponse {
+        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
+
+        if mode != TranscriptionMode::Manual {
+            return IpcResponse::error("Stop only works in manual mode");
+        }
+
+        let command = ManualSessionCommand::StopSession { responder: None };
+        if let Err(e) = self.manual_session_tx.send(command).await {
+            return IpcResponse::error(format!("Failed to send command: {}", e));
+        }
+
+        IpcResponse::success("Recording stopped")
+    }
+
+    pub async fn cancel(&self) -> IpcResponse {
+        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
+
+        if mode != TranscriptionMode::Manual {
+            return IpcResponse::error("Cancel only works in manual mode");
+        }
+
+        let command = ManualSessionCommand::CancelSession { responder: None };
+        if let Err(e) = self.manual_session_tx.send(command).await {
+            return IpcResponse::error(format!("Failed to send command: {}", e));
+        }
+        crate::recording_archive::session_cancelled();
+
+        IpcResponse::success("Session cancelled")
+    }
+
+    pub fn status(&self) -> IpcResponse {
+        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
+        let recording = self.recording.load(Ordering::Relaxed);
+
+        let status = IpcStatus {
+            mode: match mode {
+                TranscriptionMode::Manual => "manual".to_string(),
+                TranscriptionMode::RealTime => "realtime".to_string(),
+            },
+            recording,
+            session_id: None, // Could be extended to include session ID
+        };
+
+        IpcResponse::success_with_status(status)
+    }
+}
+
+/// IPC server that listens for commands from CLI clients
+pub struct IpcServer {
+    socket_path: PathBuf,
+    control: SessionControl,
     running: Arc<AtomicBool>,
+    shortcut_events_tx: Option<mpsc::Sender<ShortcutEvent>>,
+    hotkey_status: Option<Arc<RwLock<HotkeyStatus>>>,
 }
 
 impl IpcServer {
@@ -112,13 +255,29 @@ impl IpcServer {
     ) -> Self {
         Self {
             socket_path: get_socket_path(),
-            manual_session_tx,
-            transcription_mode,
-            recording,
+            control: SessionControl::new(manual_session_tx, transcription_mode, recording),
             running,
+            shortcut_events_tx: None,
+            hotkey_status: None,
         }
     }
 
+    /// Session commands for other control surfaces (the D-Bus service)
+    pub fn session_control(&self) -> SessionControl {
+        self.control.clone()
+    }
+
+    /// Route `shortcut press|release` commands into the global shortcut channel
+    pub fn with_shortcut_events(
+        mut self,
+        events_tx: mpsc::Sender<ShortcutEvent>,
+        status: Arc<RwLock<HotkeyStatus>>,
+    ) -> Self {
+        self.shortcut_events_tx = Some(events_tx);
+        self.hotkey_status = Some(status);
+        self
+    }
+
     /// Run the IPC server, listening for commands until shutdown
     pub async fn run(&self) -> Result<()> {
         // Create socket directory
@@ -137,7 +296,7 @@ impl IpcServer {
         std::fs::set_permissions(&self.socket_path, std::fs::Permissions::from_mode(0o600))
             .context("Failed to set socket permissions")?;
 
-        println!("IPC server listening on {:?}", self.socket_path);
+        tracing::info!("IPC server listening on {:?}", self.socket_path);
 
         // Accept connections until shutdown.
         // Handle each connection inline so the response is sent before accepting
@@ -150,11 +309,11 @@ impl IpcServer {
                     match accept_result {
                         Ok((stream, _)) => {
                             if let Err(e) = self.handle_connection(stream).await {
-                                eprintln!("IPC connection error: {}", e);
+                                tracing::warn!("IPC connection error: {}", e);
                             }
                         }
                         Err(e) => {
-                            eprintln!("IPC accept error: {}", e);
+                            tracing::warn!("IPC accept error: {}", e);
                         }
                     }
                 }
@@ -168,7 +327,7 @@ impl IpcServer {
 
         // Cleanup socket on shutdown
         let _ = std::fs::remove_file(&self.socket_path);
-        println!("IPC server shut down");
+        tracing::info!("IPC server shut down");
         Ok(())
     }
 
@@ -213,103 +372,39 @@ impl IpcServer {
 
     async fn execute_command(&self, cmd: IpcCommand) -> IpcResponse {
         match cmd {
-            IpcCommand::Toggle => self.handle_toggle().await,
-            IpcCommand::Start => self.handle_start().await,
-            IpcCommand::Stop => self.handle_stop().await,
-            IpcCommand::Cancel => self.handle_cancel().await,
-            IpcCommand::Status => self.handle_status(),
-            IpcCommand::SwitchMode { mode } => self.handle_switch_mode(&mode).await,
-        }
-    }
-
-    async fn handle_toggle(&self) -> IpcResponse {
-        let is_recording = self.recording.load(Ordering::Relaxed);
-        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
-
-        // In manual mode, toggle the session
-        if mode == TranscriptionMode::Manual {
-            let command = if is_recording {
-                ManualSessionCommand::StopSession { responder: None }
-            } else {
-                ManualSessionCommand::StartSession { responder: None }
-            };
-
-            if let Err(e) = self.manual_session_tx.send(command).await {
-                return IpcResponse::error(format!("Failed to send command: {}", e));
+            IpcCommand::Toggle => self.control.toggle().await,
+            IpcCommand::Start => self.control.start().await,
+            IpcCommand::Stop => self.control.stop().await,
+            IpcCommand::Cancel => self.control.cancel().await,
+            IpcCommand::Wipe => {
+                crate::privacy_wipe::wipe();
+                IpcResponse::success("Session wiped")
             }
-
-            if is_recording {
-                IpcResponse::success("Recording stopped")
-            } else {
-                IpcResponse::success("Recording started")
+            IpcCommand::Status => self.control.status(),
+            IpcCommand::SwitchMode { mode } => self.handle_switch_mode(&mode).await,
+            IpcCommand::ShortcutPressed => self.handle_shortcut(ShortcutEvent::Pressed).await,
+            IpcCommand::ShortcutReleased => self.handle_shortcut(ShortcutEvent::Released).await,
+            IpcCommand::Hotkeys => match &self.hotkey_status {
+                Some(status) => IpcResponse::success_with_hotkeys(status.read().clone()),
+                None => IpcResponse::error("Global shortcuts are disabled"),
+            },
+            IpcCommand::Quit => {
+                self.running.store(false, Ordering::Relaxed);
+                IpcResponse::success("Shutting down")
             }
-        } else {
-            // In realtime mode, just report status
-            IpcResponse::error(
-                "Toggle only works in manual mode. Use 'sonori switch-mode manual' first.",
-            )
-        }
-    }
-
-    async fn handle_start(&self) -> IpcResponse {
-        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
-
-        if mode != TranscriptionMode::Manual {
-            return IpcResponse::error("Start only works in manual mode");
         }
-
-        let command = ManualSessionCommand::StartSession { responder: None };
-        if let Err(e) = self.manual_session_tx.send(command).await {
-            return IpcResponse::error(format!("Failed to send command: {}", e));
-        }
-
-        IpcResponse::success("Recording started")
     }
 
-    async fn handle_stop(&self) -> IpcResponse {
-        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
-
-        if mode != TranscriptionMode::Manual {
-            return IpcResponse::error("Stop only works in manual mode");
-        }
+    async fn handle_shortcut(&self, event: ShortcutEvent) -> IpcResponse {
+        let Some(events_tx) = &self.shortcut_events_tx else {
+            return IpcResponse::error("Global shortcuts are disabled");
+        };
 
-        let command = ManualSessionCommand::StopSession { responder: None };
-        if let Err(e) = self.manual_session_tx.send(command).await {
-            return IpcResponse::error(format!("Failed to send command: {}", e));
+        if let Err(e) = events_tx.send(event).await {
+            return IpcResponse::error(format!("Failed to forward shortcut: {}", e));
         }
 
-        IpcResponse::success("Recording stopped")
-    }
-
-    async fn handle_cancel(&self) -> IpcResponse {
-        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
-
-        if mode != TranscriptionMode::Manual {
-            return IpcResponse::error("Cancel only works in manual mode");
-        }
-
-        let command = ManualSessionCommand::CancelSession { responder: None };
-        if let Err(e) = self.manual_session_tx.send(command).await {
-            return IpcResponse::error(format!("Failed to send command: {}", e));
-        }
-
-        IpcResponse::success("Session cancelled")
-    }
-
-    fn handle_status(&self) -> IpcResponse {
-        let mode = TranscriptionMode::from_u8(self.transcription_mode.load(Ordering::Relaxed));
-        let recording = self.recording.load(Ordering::Relaxed);
-
-        let status = IpcStatus {
-            mode: match mode {
-                TranscriptionMode::Manual => "manual".to_string(),
-                TranscriptionMode::RealTime => "realtime".to_string(),
-            },
-            recording,
-            session_id: None, // Could be extended to include session ID
-        };
-
-        IpcResponse::success_with_status(status)
+        IpcResponse::success("Shortcut forwarded")
     }
 
     async fn handle_switch_mode(&self, mode_str: &str) -> IpcResponse {
@@ -325,7 +420,7 @@ impl IpcServer {
         };
 
         let command = ManualSessionCommand::SwitchMode(new_mode);
-        if let Err(e) = self.manual_session_tx.send(command).await {
+        if let Err(e) = self.control.manual_session_tx.send(command).await {
             return IpcResponse::error(format!("Failed to send command: {}", e));
         }
 
@@ -342,6 +437,28 @@ impl Drop for IpcServer {
 
 /// Send a command to the running Sonori instance
 pub async fn send_command(cmd: IpcCommand) -> Result<IpcResponse> {
+    exchange(connect().await?, &cmd).await
+}
+
+/// Send a command to the instance with process ID `pid`; fails if the control
+/// socket belongs to another process
+pub async fn send_command_to(cmd: IpcCommand, pid: u32) -> Result<IpcResponse> {
+    let stream = connect().await?;
+    let owner = stream
+        .peer_cred()
+        .context("Failed to identify the socket owner")?
+        .pid();
+    if owner != Some(pid as i32) {
+        return Err(anyhow!(
+            "The control socket belongs to PID {:?}, not {}",
+            owner,
+            pid
+        ));
+    }
+    exchange(stream, &cmd).await
+}
+
+async fn connect() -> Result<UnixStream> {
     let socket_path = get_socket_path();
 
     if !socket_path.exists() {
@@ -351,14 +468,16 @@ pub async fn send_command(cmd: IpcCommand) -> Result<IpcResponse> {
         ));
     }
 
-    let stream = UnixStream::connect(&socket_path)
+    UnixStream::connect(&socket_path)
         .await
-        .context("Failed to connect to Sonori (is it running?)")?;
+        .context("Failed to connect to Sonori (is it running?)")
+}
 
+async fn exchange(stream: UnixStream, cmd: &IpcCommand) -> Result<IpcResponse> {
     let (reader, mut writer) = stream.into_split();
 
     // Send command
-    let cmd_json = serde_json::to_string(&cmd)?;
+    let cmd_json = serde_json::to_string(cmd)?;
     writer.write_all(cmd_json.as_bytes()).await?;
     writer.write_all(b"\n").await?;
     writer.flush().await?;
@@ -1,13 +1,53 @@
+pub mod audio_devices;
+pub mod auto_paste;
+pub mod auto_rules;
+pub mod caption_server;
+pub mod capture_monitor;
+pub mod click_through;
+pub mod clipping;
 pub mod config;
+pub mod config_watch;
 pub mod copy;
+pub mod daily_words;
+pub mod dbus_control;
 pub mod enhancement;
+pub mod evdev_input;
+pub mod focused_app;
+pub mod gpu_probe;
+pub mod help;
+pub mod hotkeys;
+pub mod i18n;
 pub mod ipc;
+pub mod logging;
+pub mod mic_mute;
+pub mod model_catalog;
+pub mod notifications;
+pub mod pipeline_guard;
+pub mod pipewire_capture;
 pub mod portal_input;
+pub mod portal_status;
 pub mod portal_tokens;
+pub mod post_stages;
+pub mod privacy_wipe;
+pub mod profiles;
+pub mod recording_archive;
+pub mod segment_undo;
+pub mod session_history;
+pub mod session_timeout;
+pub mod single_instance;
 pub mod sound_generator;
 pub mod sound_player;
+pub mod subtitle_track;
 pub mod system_tray;
+pub mod toast;
+pub mod transcript_spill;
+pub mod transcript_stream;
 pub mod transcript_writer;
+pub mod transcription_stats;
 pub mod ui;
+pub mod usage_log;
+pub mod vad_model;
+pub mod wayland_outputs;
+pub mod whisper_model;
 
 pub use config::read_app_config;
@@ -0,0 +1,223 @@
+//! Diagnostics through `tracing`.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::OnceLock;
+
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::config::DebugConfig;
+
+/// Filter used when neither the command line, `RUST_LOG` nor the config sets one;
+/// other crates (wgpu, zbus, ...) only report warnings
+pub const DEFAULT_FILTER: &str = "sonori=info,speechcore=info,warn";
+/// Filter for `--verbose`: Sonori and the speech runtime, without dependency noise
+pub const VERBOSE_FILTER: &str = "sonori=debug,speechcore=debug,warn";
+/// Filter for `-q`
+pub const QUIET_FILTER: &str = "warn";
+
+/// The log file is rotated once it grows past this size
+const LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+/// Rotated files kept next to the log file (`<name>.1` is the newest)
+const LOG_FILE_KEEP: usize = 3;
+/// Formatted events buffered for the file writer thread
+const LOG_FILE_QUEUE: usize = 1024;
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+/// Set by `--verbose`/`-q`; wins over `RUST_LOG` and the config
+static COMMAND_LINE_FILTER: OnceLock<&'static str> = OnceLock::new();
+static LOG_FILE_TX: OnceLock<SyncSender<Vec<u8>>> = OnceLock::new();
+static DROPPED_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+/// Install the subscriber; `command_line_filter` comes from `--verbose`/`-q`
+pub fn init(command_line_filter: Option<&'static str>) {
+    if let Some(filter) = command_line_filter {
+        let _ = COMMAND_LINE_FILTER.set(filter);
+    }
+    let filter = match command_line_filter {
+        Some(filter) => EnvFilter::new(filter),
+        None => {
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER))
+        }
+    };
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let initialized = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(io::stderr))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(LogFileWriter),
+        )
+        .try_init()
+        .is_ok();
+    if initialized {
+        let _ = FILTER_HANDLE.set(handle);
+    }
+}
+
+/// Apply the `[debug_config]` log settings
+pub fn configure(config: &DebugConfig) {
+    let level = config.log_level.trim();
+    let overridden = COMMAND_LINE_FILTER.get().is_some() || std::env::var_os("RUST_LOG").is_some();
+    if !level.is_empty() && !overridden {
+        match EnvFilter::try_new(level) {
+            Ok(filter) => {
+                if let Some(handle) = FILTER_HANDLE.get() {
+                    let _ = handle.reload(filter);
+                }
+            }
+            Err(e) => tracing::warn!("Ignoring invalid log_level {:?}: {}", level, e),
+        }
+    }
+
+    let path = config.log_file.trim();
+    if !path.is_empty() {
+        let path = Path::new(path);
+        if let Err(e) = open_log_file(path) {
+            tracing::warn!("Cannot write log file {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Events dropped because the log file writer fell behind
+pub fn dropped_events() -> u64 {
+    DROPPED_EVENTS.load(Ordering::Relaxed)
+}
+
+fn open_log_file(path: &Path) -> io::Result<()> {
+    if LOG_FILE_TX.get().is_some() {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = RotatingFile::open(path.to_path_buf())?;
+    let (tx, rx) = mpsc::sync_channel::<Vec<u8>>(LOG_FILE_QUEUE);
+    std::thread::Builder::new()
+        .name("sonori-log".to_string())
+        .spawn(move || {
+            while let Ok(line) = rx.recv() {
+                if file.write_line(&line).is_err() {
+                    DROPPED_EVENTS.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        })?;
+    let _ = LOG_FILE_TX.set(tx);
+    Ok(())
+}
+
+/// Log file that moves itself to `<name>.1` once it reaches `LOG_FILE_MAX_BYTES`
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self { path, file, len })
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        if self.len > 0 && self.len + line.len() as u64 > LOG_FILE_MAX_BYTES {
+            self.rotate()?;
+        }
+        self.file.write_all(line)?;
+        self.len += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for index in (1..LOG_FILE_KEEP).rev() {
+            let _ = std::fs::rename(
+                rotated_path(&self.path, index),
+                rotated_path(&self.path, index + 1),
+            );
+        }
+        std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        *self = Self::open(self.path.clone())?;
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// `MakeWriter` for the file layer; discards events until a log file is open
+struct LogFileWriter;
+
+impl<'a> MakeWriter<'a> for LogFileWriter {
+    type Writer = LogFileWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogFileWriter
+    }
+}
+
+impl Write for LogFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(tx) = LOG_FILE_TX.get() {
+            match tx.try_send(buf.to_vec()) {
+                Ok(()) | Err(TrySendError::Disconnected(_)) => {}
+                Err(TrySendError::Full(_)) => {
+                    DROPPED_EVENTS.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_shifts_older_files() {
+        let dir = std::env::temp_dir().join(format!("sonori-log-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sonori.log");
+
+        let mut file = RotatingFile::open(path.clone()).unwrap();
+        file.write_line(b"first\n").unwrap();
+        file.rotate().unwrap();
+        file.write_line(b"second\n").unwrap();
+        file.rotate().unwrap();
+        file.write_line(b"third\n").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "third\n");
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+            "second\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 2)).unwrap(),
+            "first\n"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn built_in_filters_parse() {
+        for filter in [DEFAULT_FILTER, VERBOSE_FILTER, QUIET_FILTER] {
+            assert!(EnvFilter::try_new(filter).is_ok(), "{}", filter);
+        }
+    }
+}
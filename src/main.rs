@@ -2,15 +2,19 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 // Use library modules (the binary should not redeclare modules)
-use sonori::config::{read_app_config_with_path, AppConfig};
+use sonori::config::{read_app_config_with_path, AppConfig, ConfigStore, InjectionMode};
 use sonori::copy;
+use sonori::i18n::{tr, tr_args};
 use sonori::ipc::{self, IpcCommand};
 use sonori::portal_input;
+use sonori::post_stages::{PostPipeline, SegmentContext};
+use sonori::profiles::Profile;
 use sonori::sound_player::SoundPlayer;
 use sonori::system_tray;
 use sonori::ui;
 use speechcore::{
-    init_all_models, FeedbackSink, RealTimeTranscriber, SpeechConfig, TranscriptionMode,
+    init_all_models, FeedbackSink, ManualSessionCommand, RealTimeTranscriber, SpeechConfig,
+    TranscriptionMode,
 };
 
 // Binary-specific modules (not in library)
@@ -20,7 +24,6 @@ use ashpd::register_host_app;
 use ashpd::AppID;
 use clap::{Parser, Subcommand, ValueEnum};
 use std::time::Duration;
-use tracing_subscriber::EnvFilter;
 
 #[derive(Debug, Clone, ValueEnum)]
 enum TranscriptionModeArg {
@@ -39,13 +42,29 @@ enum Command {
     Stop,
     /// Cancel the current session without processing
     Cancel,
+    /// Cancel the session and clear its transcript, history, counters and clipboard
+    Wipe,
     /// Get current status as JSON
     Status,
+    /// Shut down the running instance
+    Quit,
     /// Switch transcription mode
     SwitchMode {
         /// Mode to switch to: "manual" or "realtime"
         mode: String,
     },
+    /// Forward a global shortcut event (used by compositor key bindings)
+    #[command(hide = true)]
+    Shortcut {
+        #[arg(value_enum)]
+        action: ShortcutAction,
+    },
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+enum ShortcutAction {
+    Press,
+    Release,
 }
 
 #[derive(Parser)]
@@ -64,6 +83,27 @@ struct Args {
     )]
     cli: bool,
 
+    /// Log debug diagnostics from Sonori and the speech runtime
+    #[arg(
+        long,
+        short = 'v',
+        conflicts_with = "quiet",
+        help = "Log debug diagnostics (overrides RUST_LOG and log_level)"
+    )]
+    verbose: bool,
+
+    /// Log only warnings and errors
+    #[arg(
+        long,
+        short = 'q',
+        help = "Log only warnings and errors (overrides RUST_LOG and log_level)"
+    )]
+    quiet: bool,
+
+    /// Shut down an already running instance and take its place
+    #[arg(long, help = "Replace an already running Sonori instance")]
+    replace: bool,
+
     /// Transcription mode: realtime or manual
     #[arg(long, value_enum, help = "Set transcription mode")]
     mode: Option<TranscriptionModeArg>,
@@ -71,27 +111,102 @@ struct Args {
     /// Start in manual mode (shorthand for --mode manual)
     #[arg(long, help = "Start in manual transcription mode")]
     manual: bool,
+
+    /// Print aggregate statistics from the usage log and exit
+    #[arg(long, help = "Print dictation statistics from the usage log and exit")]
+    usage_summary: bool,
+
+    /// Only include sessions newer than this window (e.g. 12h, 30d, 2w)
+    #[arg(long, requires = "usage_summary", value_name = "WINDOW")]
+    since: Option<String>,
+
+    /// Print detected platform capabilities (global shortcut providers) and exit
+    #[arg(long, help = "Print detected platform capabilities and exit")]
+    capabilities: bool,
+
+    /// Print the GPU each transcription backend would use and exit
+    #[arg(long, help = "Print the GPU each backend would use and exit")]
+    probe_gpu: bool,
+
+    /// Print this run's transcription statistics as JSON on exit
+    #[arg(long, help = "Print transcription statistics as JSON on exit")]
+    stats_on_exit: bool,
+
+    /// Print the input devices and PipeWire sources Sonori can record from and exit
+    #[arg(long, help = "List audio input devices and exit")]
+    list_audio_devices: bool,
+
+    /// Print the Wayland outputs `display_config.output_name` can select and exit
+    #[arg(long, help = "List Wayland outputs and exit")]
+    list_outputs: bool,
+
+    /// Download large models without asking
+    #[arg(long, short = 'y', help = "Do not ask before downloading large models")]
+    assume_yes: bool,
+
+    /// Use only models that are already on disk
+    #[arg(long, help = "Never download models; fail if one is missing")]
+    offline: bool,
+
+    /// Layer ~/.config/sonori/profiles/<NAME>.toml over the config file
+    #[arg(long, value_name = "NAME", help = "Start with a settings profile")]
+    profile: Option<String>,
+
+    /// Write the effective configuration to ~/.config/sonori/profiles/<NAME>.toml and exit
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Save the effective configuration as a profile and exit"
+    )]
+    export_profile: Option<String>,
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    init_tracing();
+impl Args {
+    /// A subcommand or report that runs instead of the overlay
+    fn one_shot(&self) -> bool {
+        self.command.is_some()
+            || self.usage_summary
+            || self.capabilities
+            || self.probe_gpu
+            || self.list_audio_devices
+            || self.list_outputs
+            || self.export_profile.is_some()
+    }
+}
 
+fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    sonori::logging::init(if args.verbose {
+        Some(sonori::logging::VERBOSE_FILTER)
+    } else if args.quiet {
+        Some(sonori::logging::QUIET_FILTER)
+    } else {
+        None
+    });
 
-    // Handle IPC subcommands (control running instance)
-    if let Some(cmd) = args.command {
-        return handle_ipc_command(cmd).await;
+    if args.one_shot() {
+        return tokio_runtime()?.block_on(run_one_shot(args));
     }
 
-    println!("Loading configuration...");
+    tracing::info!("Loading configuration...");
     let (mut app_config, config_path) = read_app_config_with_path();
     match &config_path {
-        Some(path) => println!("Configuration loaded from {}", path.display()),
-        None => println!("Configuration: using defaults (no config file found)"),
+        Some(path) => tracing::info!("Configuration loaded from {}", path.display()),
+        None => tracing::info!("Configuration: using defaults (no config file found)"),
     }
+    let profile = match &args.profile {
+        Some(name) => {
+            let profile = Profile::load(name).map_err(anyhow::Error::msg)?;
+            app_config = profile.apply(&app_config).map_err(anyhow::Error::msg)?;
+            tracing::info!("Profile: {}", name);
+            Some(profile)
+        }
+        None => None,
+    };
 
-    // Set stable portal App ID env var early for consistent identity across launches
+    // The environment may only change while the process is single-threaded, so
+    // it is set here, before the tokio runtime and the file logger start threads.
+    // A stable portal App ID keeps the identity consistent across launches
     if std::env::var_os("XDG_DESKTOP_PORTAL_APPLICATION_ID").is_none() {
         std::env::set_var(
             "XDG_DESKTOP_PORTAL_APPLICATION_ID",
@@ -99,13 +214,75 @@ async fn main() -> anyhow::Result<()> {
         );
     }
 
+    // The ALSA plugin reads the stream properties when an input is opened
+    sonori::pipewire_capture::configure_stream(&app_config.audio_capture_config)
+        .map_err(anyhow::Error::msg)?;
+
+    sonori::logging::configure(&app_config.debug_config);
+    let locale = sonori::i18n::init(app_config.general_config.ui_language.as_deref());
+    tracing::info!("Interface language: {}", locale.code());
+
+    tokio_runtime()?.block_on(run(args, app_config, config_path, profile))
+}
+
+fn tokio_runtime() -> std::io::Result<tokio::runtime::Runtime> {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+}
+
+/// Run a subcommand or report (see `Args::one_shot`)
+async fn run_one_shot(args: Args) -> anyhow::Result<()> {
+    // Handle IPC subcommands (control running instance)
+    if let Some(cmd) = args.command {
+        return handle_ipc_command(cmd).await;
+    }
+
+    if args.usage_summary {
+        return print_usage_summary(args.since.as_deref());
+    }
+
+    if args.capabilities {
+        return print_capabilities().await;
+    }
+
+    if args.probe_gpu {
+        return print_gpu_probe();
+    }
+
+    if args.list_audio_devices {
+        return print_audio_devices();
+    }
+
+    if args.list_outputs {
+        return print_outputs();
+    }
+
+    match &args.export_profile {
+        Some(name) => export_profile(name, args.profile.as_deref()),
+        None => Ok(()),
+    }
+}
+
+async fn run(
+    args: Args,
+    mut app_config: AppConfig,
+    config_path: Option<std::path::PathBuf>,
+    profile: Option<Profile>,
+) -> anyhow::Result<()> {
+    // Held until exit; a second instance would fight this one over the mic
+    let _instance_lock = sonori::single_instance::acquire(args.replace).await?;
+
+    // Before the backend loads, so a panic while loading it is reported too
+    sonori::pipeline_guard::install(args.cli);
+
     // Register with the portal system for persistent permissions
     // This is critical for GlobalShortcuts and other portals to recognize the app across launches
     let app_id =
         AppID::try_from(sonori::config::APPLICATION_ID).expect("Invalid application ID constant");
     if let Err(e) = register_host_app(app_id).await {
-        eprintln!("Warning: Failed to register host app with portals: {}", e);
-        eprintln!("Portal permissions may not persist across restarts.");
+        tracing::warn!("Failed to register host app with portals: {}", e);
+        tracing::warn!("Portal permissions may not persist across restarts.");
     }
 
     // Override transcription mode from CLI arguments
@@ -126,17 +303,146 @@ async fn main() -> anyhow::Result<()> {
         TranscriptionMode::RealTime => "realtime".to_string(),
     };
 
-    println!("Transcription mode: {:?}", transcription_mode);
+    tracing::info!("Transcription mode: {:?}", transcription_mode);
+
+    if args.offline {
+        app_config.general_config.offline = true;
+    }
+    let offline = app_config.general_config.offline;
 
-    println!("Initializing models...");
-    let (transcription_model_path, _silero_model_path) = init_all_models(
-        Some(&app_config.general_config.model),
+    sonori::model_catalog::check_language(
         app_config.backend_config.backend,
-        &app_config.backend_config.quantization_level,
+        &app_config.general_config.model,
+        &app_config.general_config.language,
     )
-    .await?;
+    .map_err(anyhow::Error::msg)?;
 
-    println!(
+    if offline {
+        if let Some(missing) = sonori::model_catalog::offline_missing_model(&app_config) {
+            anyhow::bail!(missing);
+        }
+    }
+
+    if !args.cli {
+        sonori::notifications::init(&app_config.notifications_config);
+    }
+
+    let mut download_progress = None;
+    if let Some(download) = sonori::model_catalog::pending_download(&app_config) {
+        confirm_model_download(
+            &download,
+            app_config.general_config.download_confirm_threshold_mb,
+            args.assume_yes,
+        )?;
+        // The overlay starts only after the models are ready; until then GUI users
+        // see the progress as a desktop notification (the CLI prints its own)
+        if !args.cli {
+            let (progress_tx, progress_rx) = tokio::sync::watch::channel(
+                sonori::model_catalog::DownloadProgress::new(&download),
+            );
+            let progress_tx = Arc::new(progress_tx);
+            tokio::spawn(sonori::model_catalog::run_download_monitor(
+                download.clone(),
+                progress_tx.clone(),
+            ));
+            tokio::spawn(notify_download_progress(progress_rx));
+            download_progress = Some(progress_tx);
+        }
+        tokio::spawn(sonori::model_catalog::verify_download_size(download));
+    }
+
+    let custom_model_path = app_config.general_config.model_path.clone();
+    let (transcription_model_path, silero_model_path) = if let Some(path) = &custom_model_path {
+        sonori::model_catalog::check_model_path(app_config.backend_config.backend, path)
+            .map_err(anyhow::Error::msg)?;
+        tracing::info!("Using the local model at {} (model_path)", path.display());
+        let silero_model_path = sonori::vad_model::cached_path()
+            .filter(|path| path.exists())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "model_path is set, so nothing is downloaded, but the Silero VAD model is \
+                     missing from the model cache. Place silero_vad.onnx in {}, or start once \
+                     without model_path to download it",
+                    sonori::model_catalog::model_cache_dir()
+                        .map(|dir| dir.display().to_string())
+                        .unwrap_or_else(|| "$SPEECHCORE_MODEL_DIR".to_string())
+                )
+            })?;
+        (
+            path.to_string_lossy().into_owned(),
+            silero_model_path.to_string_lossy().into_owned(),
+        )
+    } else {
+        tracing::info!("Initializing models...");
+        let models = init_all_models(
+            Some(&app_config.general_config.model),
+            app_config.backend_config.backend,
+            &app_config.backend_config.quantization_level,
+        )
+        .await;
+        if let Some(progress) = &download_progress {
+            progress.send_modify(|progress| {
+                progress.phase = match &models {
+                    Ok(_) => sonori::model_catalog::DownloadPhase::Done,
+                    Err(e) => sonori::model_catalog::DownloadPhase::Failed(e.to_string()),
+                }
+            });
+            if models.is_ok() {
+                sonori::notifications::notify(
+                    sonori::notifications::NotificationEvent::DownloadComplete(
+                        app_config.general_config.model.clone(),
+                    ),
+                );
+            }
+        }
+        models?
+    };
+
+    // A truncated VAD model otherwise fails deep inside ONNX Runtime with no hint
+    let silero_model_path = std::path::PathBuf::from(&silero_model_path);
+    let model_name = app_config.general_config.model.clone();
+    let backend = app_config.backend_config.backend;
+    let quantization_level = app_config.backend_config.quantization_level.clone();
+    let custom_model = custom_model_path.is_some();
+    let redownload_models = || {
+        let (model_name, quantization_level) = (model_name.clone(), quantization_level.clone());
+        async move {
+            if offline {
+                return Err("offline mode is on, so it was not downloaded again".to_string());
+            }
+            if custom_model {
+                return Err("model_path is set, so it was not downloaded again".to_string());
+            }
+            init_all_models(Some(&model_name), backend, &quantization_level)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    };
+    sonori::vad_model::ensure_usable(&silero_model_path, redownload_models)
+        .await
+        .map_err(anyhow::Error::msg)?;
+
+    // Same for an interrupted whisper.cpp model download
+    let transcription_model_file = std::path::Path::new(&transcription_model_path);
+    if backend == speechcore::BackendType::WhisperCpp
+        && !custom_model
+        && transcription_model_file.is_file()
+    {
+        let expected_mb =
+            sonori::model_catalog::download_size_mb(&app_config.backend_config, &model_name);
+        sonori::whisper_model::ensure_usable(
+            transcription_model_file,
+            expected_mb,
+            redownload_models,
+        )
+        .await
+        .map_err(anyhow::Error::msg)?;
+    }
+    let vad_model_verified =
+        sonori::vad_model::check(&silero_model_path) == sonori::vad_model::VadModelCheck::Verified;
+
+    tracing::info!(
         "Transcription model ready at: {:?}",
         transcription_model_path
     );
@@ -144,75 +450,341 @@ async fn main() -> anyhow::Result<()> {
     // Initialize sound player
     let sound_player = match SoundPlayer::new(&app_config.sound_config) {
         Ok(player) => {
-            println!("Sound player initialized successfully");
+            tracing::info!("Sound player initialized successfully");
             Some(player)
         }
         Err(e) => {
-            eprintln!("Failed to initialize sound player: {}", e);
+            tracing::warn!("Failed to initialize sound player: {}", e);
             None
         }
     };
 
+    let warning_sound_player = sound_player.clone();
+    let sound_player_for_reload = sound_player.clone();
+    let error_sound_player = sound_player.clone();
     let feedback_sink = sound_player.map(|player| player as std::sync::Arc<dyn FeedbackSink>);
     let magic_mode_enabled = Arc::new(AtomicBool::new(false));
     let magic_mode_enhancer = if app_config.enhancement_config.enabled {
-        Some(Arc::new(sonori::enhancement::MagicModeEnhancer::new(
-            app_config.enhancement_config.clone(),
-            magic_mode_enabled.clone(),
-        )))
+        Some(Arc::new(
+            sonori::enhancement::MagicModeEnhancer::new(
+                app_config.enhancement_config.clone(),
+                magic_mode_enabled.clone(),
+            )
+            .with_offline(offline),
+        ))
     } else {
         None
     };
 
-    let speech_config: SpeechConfig = app_config.clone().into();
-    let mut transcriber =
-        RealTimeTranscriber::new(transcription_model_path, speech_config, feedback_sink)?;
+    if app_config.backend_config.backend != speechcore::BackendType::WhisperCpp {
+        if let Some(prompt) = app_config
+            .common_transcription_options
+            .effective_initial_prompt()
+        {
+            tracing::warn!(
+                "initial_prompt: only the whisper_cpp backend takes a prompt; the configured \
+                 prompt ({} chars) is ignored for {:?}",
+                prompt.chars().count(),
+                app_config.backend_config.backend
+            );
+        }
+    }
+
+    // Built before the backend starts so stage ordering or plugin errors fail fast
+    let post_pipeline = Arc::new(PostPipeline::from_app_config(&app_config)?);
+    tracing::info!(
+        "Post-processing stages: {}",
+        post_pipeline.stage_names().join(", ")
+    );
+
+    let manual_config = &app_config.manual_mode_config;
+    if manual_config.max_recording_duration_secs > sonori::config::MAX_RECORDING_DURATION_CAP_SECS {
+        tracing::warn!(
+            "max_recording_duration_secs = {} would need a {} MB session buffer; \
+             capped at {}s ({} MB)",
+            manual_config.max_recording_duration_secs,
+            sonori::config::ManualModeConfig::buffer_megabytes(
+                manual_config.max_recording_duration_secs
+            ),
+            manual_config.recording_duration_secs(),
+            sonori::config::ManualModeConfig::buffer_megabytes(
+                manual_config.recording_duration_secs()
+            ),
+        );
+    }
+
+    sonori::gpu_probe::log_resolution(&app_config);
+    let mut speech_config: SpeechConfig = app_config.clone().into();
+    speech_config.backend_config = sonori::gpu_probe::effective_backend_config(&app_config);
+    let mut transcriber = match RealTimeTranscriber::new(
+        transcription_model_path.clone(),
+        speech_config.clone(),
+        feedback_sink.clone(),
+    ) {
+        Ok(transcriber) => transcriber,
+        // A VAD model that never loaded may be damaged in ways the file checks miss
+        Err(e) if !vad_model_verified => {
+            tracing::warn!(
+                "Failed to start the speech runtime: {}. The Silero VAD model at {} has not \
+                 loaded successfully before; deleting it and downloading it again",
+                e,
+                silero_model_path.display()
+            );
+            sonori::vad_model::remove(&silero_model_path);
+            redownload_models().await.map_err(anyhow::Error::msg)?;
+            RealTimeTranscriber::new(transcription_model_path, speech_config, feedback_sink)
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Speech runtime still fails after downloading the Silero VAD model to {} again: {}",
+                        silero_model_path.display(),
+                        e
+                    )
+                })?
+        }
+        Err(e) => return Err(e.into()),
+    };
 
     transcriber.start()?;
+    // The runtime opens the capture stream on start and keeps it until shutdown
+    sonori::audio_devices::set_device_open(true);
+    tokio::spawn(sonori::pipeline_guard::run_monitor(
+        transcriber.get_running().clone(),
+        transcriber.get_recording().clone(),
+        transcriber.get_audio_visualization_data(),
+        error_sound_player,
+    ));
+    tokio::spawn(sonori::audio_devices::run_device_monitor(
+        transcriber.get_running().clone(),
+    ));
+    tokio::spawn(sonori::mic_mute::run_monitor(
+        transcriber.get_running().clone(),
+        transcriber.get_recording().clone(),
+        transcriber.get_transcription_mode_ref(),
+        transcriber.get_manual_session_sender(),
+        app_config.audio_processor_config.sync_os_mute,
+    ));
+    tokio::spawn(sonori::capture_monitor::run_stall_monitor(
+        transcriber.get_running().clone(),
+        transcriber.get_recording().clone(),
+        transcriber.get_audio_visualization_data(),
+    ));
+    sonori::transcript_writer::init_auto_save(&app_config.transcript_config);
+    sonori::recording_archive::init(&app_config.recording_config);
+    for note in sonori::recording_archive::unsupported_settings(&app_config.recording_config) {
+        tracing::warn!("{}", note);
+    }
+    tokio::spawn(sonori::recording_archive::run_monitor(
+        transcriber.get_running().clone(),
+        transcriber.get_recording().clone(),
+        transcriber.get_transcription_mode_ref(),
+    ));
+    sonori::transcript_spill::init(&app_config.transcript_config);
+    if app_config.transcript_config.enabled {
+        tokio::spawn(sonori::transcript_writer::run_auto_save_monitor(
+            transcriber.get_running().clone(),
+            transcriber.get_recording().clone(),
+            Duration::from_secs(app_config.transcript_config.flush_interval_secs),
+        ));
+    }
+    sonori::session_history::init(app_config.debug_config.session_history_limit);
+    copy::configure(&app_config.portal_config.clipboard);
+    tokio::spawn(sonori::session_timeout::run_monitor(
+        transcriber.get_running().clone(),
+        transcriber.get_recording().clone(),
+        transcriber.get_transcription_mode_ref(),
+        transcriber.get_manual_session_sender(),
+        app_config.manual_mode_config.clone(),
+        warning_sound_player,
+    ));
+    tokio::spawn(sonori::session_history::run_recording_monitor(
+        transcriber.get_running().clone(),
+        transcriber.get_recording().clone(),
+    ));
+    sonori::transcription_stats::init(
+        &app_config.backend_config.backend.to_string(),
+        &app_config.general_config.model,
+        app_config.debug_config.stats_detailed,
+        app_config.debug_config.log_latency_per_segment,
+    );
+    tokio::spawn(sonori::transcription_stats::run_monitor(
+        transcriber.get_running().clone(),
+        transcriber.get_recording().clone(),
+        transcriber.get_audio_visualization_data(),
+    ));
+    let stats_export_path = app_config.debug_config.stats_export_path.clone();
+    let log_stats = app_config.debug_config.log_stats_enabled;
+    if let Err(e) = sonori::vad_model::record_hash(&silero_model_path) {
+        tracing::warn!("Could not record Silero VAD model hash: {}", e);
+    }
 
     // Only auto-start recording in real-time mode
     // In manual mode, user explicitly starts/stops sessions
     if matches!(transcription_mode, TranscriptionMode::RealTime) {
-        println!("Starting real-time transcription automatically...");
+        tracing::info!("Starting real-time transcription automatically...");
         transcriber.toggle_recording();
     } else {
-        println!("Manual mode - ready to start recording on demand");
+        tracing::info!("Manual mode - ready to start recording on demand");
     }
 
     if args.cli {
         // CLI mode - no GUI
-        run_cli_mode(transcriber, transcription_mode).await?;
+        run_cli_mode(transcriber, transcription_mode, &app_config, post_pipeline).await?;
     } else {
         // GUI mode - existing behavior
         run_gui_mode(
             transcriber,
-            app_config,
+            ConfigStore::new(app_config).with_profile(profile),
             magic_mode_enabled,
             magic_mode_enhancer,
+            post_pipeline,
+            config_path,
+            sound_player_for_reload,
         )
         .await?;
     }
+    sonori::audio_devices::set_device_open(false);
+
+    if !stats_export_path.is_empty() {
+        match sonori::transcription_stats::export_to(std::path::Path::new(&stats_export_path)) {
+            Ok(()) => tracing::info!("Transcription statistics written to {}", stats_export_path),
+            Err(e) => tracing::warn!("Failed to write transcription statistics: {}", e),
+        }
+    }
+    if args.stats_on_exit {
+        println!("{}", sonori::transcription_stats::to_json());
+    }
+    if log_stats {
+        print!("{}", sonori::transcription_stats::report());
+    }
+
+    if let Err(e) = tokio::task::spawn_blocking(copy::hand_off_on_exit).await {
+        tracing::warn!("Clipboard handoff failed: {}", e);
+    }
 
     Ok(())
 }
 
+/// Ask on the terminal before a model download above the confirmation threshold
+fn confirm_model_download(
+    download: &sonori::model_catalog::PendingDownload,
+    threshold_mb: u64,
+    assume_yes: bool,
+) -> anyhow::Result<()> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let metered = sonori::model_catalog::network_is_metered();
+    let threshold = sonori::model_catalog::confirmation_threshold_mb(threshold_mb, metered);
+    if download.size_mb < threshold {
+        return Ok(());
+    }
+
+    println!(
+        "Model {} is not downloaded yet{}.",
+        download.describe(),
+        if metered {
+            " and your connection is metered"
+        } else {
+            ""
+        }
+    );
+    if assume_yes {
+        return Ok(());
+    }
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!(
+            "Refusing to download {} without confirmation; rerun with --assume-yes",
+            download.model
+        );
+    }
+
+    print!("{}", tr("cli.download_prompt"));
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Ok(()),
+        _ => anyhow::bail!("Download of {} declined", download.model),
+    }
+}
+
 async fn run_cli_mode(
     transcriber: RealTimeTranscriber,
     mode: TranscriptionMode,
+    app_config: &AppConfig,
+    post_pipeline: Arc<PostPipeline>,
 ) -> anyhow::Result<()> {
     match mode {
-        TranscriptionMode::RealTime => run_realtime_cli(transcriber).await,
-        TranscriptionMode::Manual => run_manual_cli(transcriber).await,
+        TranscriptionMode::RealTime => {
+            run_realtime_cli(transcriber, app_config, &post_pipeline).await
+        }
+        TranscriptionMode::Manual => run_manual_cli(transcriber, app_config, &post_pipeline).await,
     }
 }
 
-async fn run_realtime_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Result<()> {
-    println!("Running in real-time CLI mode. Press Ctrl+C to exit.");
-    println!("Transcription will appear below:");
+/// Print the quick help for the CLI `?` command
+fn print_session_history() {
+    let entries = sonori::session_history::entries();
+    if entries.is_empty() {
+        println!("\n{}", tr("cli.no_sessions"));
+        return;
+    }
+    println!("\n{}", tr("cli.past_sessions"));
+    for entry in &entries {
+        println!("  {}", sonori::session_history::format_entry(entry));
+    }
+}
+
+fn cli_status_line(status: &str, transcript: &str) -> String {
+    tr_args(
+        "cli.status",
+        &[("status", &status), ("transcript", &transcript)],
+    )
+}
+
+fn print_cli_help(app_config: &AppConfig, mode: TranscriptionMode) {
+    let sections = sonori::help::cli_help(app_config, mode);
+    println!("\n{}", sonori::help::format_help_text(&sections));
+}
+
+/// Read stdin lines on a blocking thread and forward them trimmed and lowercased
+fn spawn_stdin_reader(running: Arc<AtomicBool>) -> tokio::sync::mpsc::UnboundedReceiver<String> {
+    let (input_tx, input_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
+    std::thread::spawn(move || {
+        use std::io::{self, BufRead};
+        let stdin = io::stdin();
+
+        loop {
+            if !running.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let mut line = String::new();
+            match stdin.lock().read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => {
+                    let _ = input_tx.send(line.trim().to_lowercase());
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    input_rx
+}
+
+async fn run_realtime_cli(
+    mut transcriber: RealTimeTranscriber,
+    app_config: &AppConfig,
+    post_pipeline: &PostPipeline,
+) -> anyhow::Result<()> {
+    println!("{}", tr("cli.realtime_intro"));
+    println!("{}", tr("cli.transcription_below"));
     println!("=====================================");
 
     let mut transcript_rx = transcriber.get_transcript_rx();
     let running = transcriber.get_running();
+    let mut input_rx = spawn_stdin_reader(running.clone());
 
     // Set up Ctrl+C handler
     let running_clone = running.clone();
@@ -220,7 +792,7 @@ async fn run_realtime_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Resul
         tokio::signal::ctrl_c()
             .await
             .expect("Failed to listen for Ctrl+C");
-        println!("\nShutting down...");
+        println!("\n{}", tr("cli.shutting_down"));
         running_clone.store(false, Ordering::Relaxed);
     });
 
@@ -233,13 +805,54 @@ async fn run_realtime_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Resul
                 if !message.is_final {
                     continue; // CLI prints committed text only
                 }
+                let received = std::time::Instant::now();
                 // Clear the current line and print the new transcription
                 print!("\r{:100}\r", ""); // Clear line with spaces
+                let context = segment_context(message.session_id.as_deref());
+                let Some(text) = post_pipeline.process_final(&message.text, &context) else {
+                    sonori::transcription_stats::record_hallucination();
+                    continue;
+                };
+                let segment = sonori::transcription_stats::record_segment(&text, None, received);
+                sonori::transcription_stats::finish_segment(segment, false);
+                sonori::transcript_writer::auto_save_segment(&text);
+                sonori::session_history::record_segment(&text);
                 current_line.push(' ');
-                current_line.push_str(&message.text);
+                current_line.push_str(&text);
+                sonori::segment_undo::record_segment(&text);
                 print!("{}", current_line);
                 std::io::Write::flush(&mut std::io::stdout()).unwrap();
             }
+            Some(input) = input_rx.recv() => {
+                match input.as_str() {
+                    "?" | "help" => {
+                        print_cli_help(app_config, TranscriptionMode::RealTime);
+                        print!("{}", current_line);
+                        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+                    }
+                    "h" => {
+                        print_session_history();
+                        print!("{}", current_line);
+                        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+                    }
+                    "u" => {
+                        match sonori::segment_undo::undo_in(&mut current_line) {
+                            Some(removed) => {
+                                println!("\n{}", tr_args("cli.removed", &[("segment", &removed)]))
+                            }
+                            None => println!("\n{}", tr("toast.nothing_to_undo")),
+                        }
+                        print!("{}", current_line);
+                        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+                    }
+                    "q" | "quit" => {
+                        println!("\n{}", tr("cli.quit_requested"));
+                        running.store(false, Ordering::Relaxed);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
             _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
                 if !running.load(Ordering::Relaxed) {
                     break;
@@ -248,16 +861,29 @@ async fn run_realtime_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Resul
         }
     }
 
+    sonori::transcript_writer::flush_auto_save();
     transcriber.shutdown().await?;
     Ok(())
 }
 
-async fn run_manual_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Result<()> {
-    println!("Running in manual CLI mode. Controls:");
-    println!("  SPACE - Start/Stop recording session");
-    println!("  c     - Copy current transcript");
-    println!("  r     - Reset transcript");
-    println!("  q     - Quit");
+async fn run_manual_cli(
+    mut transcriber: RealTimeTranscriber,
+    app_config: &AppConfig,
+    post_pipeline: &PostPipeline,
+) -> anyhow::Result<()> {
+    println!("{}", tr("cli.manual_intro"));
+    for (key, action) in [
+        ("SPACE", "help.start_stop_recording"),
+        ("c", "help.copy_transcript"),
+        ("r", "help.reset_transcript"),
+        ("e", "help.export_subtitles"),
+        ("u", "help.undo"),
+        ("h", "help.list_sessions"),
+        ("?", "help.show_help"),
+        ("q", "help.quit"),
+    ] {
+        println!("  {:5} - {}", key, tr(action));
+    }
     println!("====================================");
 
     let mut transcript_rx = transcriber.get_transcript_rx();
@@ -271,42 +897,22 @@ async fn run_manual_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Result<
         tokio::signal::ctrl_c()
             .await
             .expect("Failed to listen for Ctrl+C");
-        println!("\nShutting down...");
+        println!("\n{}", tr("cli.shutting_down"));
         running_clone.store(false, Ordering::Relaxed);
     });
 
     // Set up keyboard input handling with blocking thread
-    let (input_tx, mut input_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
-    let running_for_input = running.clone();
-
-    // Spawn blocking task for stdin reading
-    std::thread::spawn(move || {
-        use std::io::{self, BufRead};
-        let stdin = io::stdin();
-
-        loop {
-            if !running_for_input.load(Ordering::Relaxed) {
-                break;
-            }
-
-            let mut line = String::new();
-            match stdin.lock().read_line(&mut line) {
-                Ok(_) => {
-                    let _ = input_tx.send(line.trim().to_lowercase());
-                }
-                Err(_) => break,
-            }
-        }
-    });
+    let mut input_rx = spawn_stdin_reader(running.clone());
+    tokio::spawn(sonori::subtitle_track::run_recording_monitor(
+        running.clone(),
+        transcriber.get_recording().clone(),
+    ));
 
     // Status display
     let mut current_transcript = String::new();
-    let mut session_status = "Ready";
+    let mut session_status = tr("cli.status_ready");
 
-    println!(
-        "\nStatus: {} | Transcript: {}",
-        session_status, current_transcript
-    );
+    println!("\n{}", cli_status_line(session_status, &current_transcript));
 
     // Main event loop
     loop {
@@ -315,18 +921,30 @@ async fn run_manual_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Result<
                 if !message.is_final {
                     continue; // CLI prints committed text only
                 }
+                let received = std::time::Instant::now();
+                let context = segment_context(message.session_id.as_deref());
+                let Some(text) = post_pipeline.process_final(&message.text, &context) else {
+                    sonori::transcription_stats::record_hallucination();
+                    continue;
+                };
+                let segment = sonori::transcription_stats::record_segment(&text, None, received);
+                sonori::transcription_stats::finish_segment(segment, false);
+                sonori::subtitle_track::record_segment(&text);
+                sonori::transcript_writer::auto_save_segment(&text);
+                sonori::session_history::record_segment(&text);
                 current_transcript.push(' ');
-                current_transcript.push_str(&message.text);
+                current_transcript.push_str(&text);
+                sonori::segment_undo::record_segment(&text);
 
                 // Clear previous line and print updated status
                 print!("\r{:100}\r", ""); // Clear line
-                print!("Status: {} | Transcript: {}", session_status, current_transcript);
+                print!("{}", cli_status_line(session_status, &current_transcript));
                 std::io::Write::flush(&mut std::io::stdout()).unwrap();
             }
             Some(input) = input_rx.recv() => {
                 match input.as_str() {
                     " " | "space" => {
-                        println!("\nSpace pressed - toggling session...");
+                        println!("\n{}", tr("cli.space_pressed"));
                         // Toggle manual session based on current state
                         let is_currently_recording = transcriber.get_recording().load(std::sync::atomic::Ordering::Relaxed);
 
@@ -334,51 +952,65 @@ async fn run_manual_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Result<
                             // Currently recording, stop the session
                             match transcriber.stop_manual_session().await {
                                 Ok(()) => {
-                                    session_status = "Processing";
-                                    println!("Manual session stopped and processing...");
+                                    session_status = tr("cli.status_processing");
+                                    println!("{}", tr("cli.session_stopped"));
                                 }
                                 Err(e) => {
-                                    eprintln!("Failed to stop manual session: {}", e);
+                                    eprintln!("{}", tr_args("cli.stop_failed", &[("error", &e)]));
                                 }
                             }
                         } else {
                             // Not recording, start a new session
                             match transcriber.start_manual_session().await {
                                 Ok(session_id) => {
-                                    session_status = "Recording";
-                                    println!("Started new manual session: {}", session_id);
+                                    session_status = tr("cli.status_recording");
+                                    println!("{}", tr_args("cli.session_started", &[("id", &session_id)]));
                                 }
                                 Err(e) => {
-                                    eprintln!("Failed to start manual session: {}", e);
+                                    eprintln!("{}", tr_args("cli.start_failed", &[("error", &e)]));
                                 }
                             }
                         }
                     }
                     "c" => {
-                        println!("\nCopy transcript requested");
+                        println!("\n{}", tr("cli.copy_requested"));
                         let transcript = transcriber.get_transcript();
                         if !transcript.is_empty() {
-                            match std::process::Command::new("wl-copy")
-                                .arg(&transcript)
-                                .spawn()
-                                .and_then(|mut child| child.wait())
+                            match tokio::task::spawn_blocking(move || {
+                                copy::WlCopy::copy_to_clipboard(&transcript)
+                            })
+                            .await
                             {
-                                Ok(exit_status) if exit_status.success() => {
-                                    println!("Transcript copied to clipboard successfully");
+                                Ok(Ok(())) => {
+                                    println!("{}", tr("cli.copied"));
                                 }
-                                Ok(_) => {
-                                    eprintln!("Failed to copy transcript: wl-copy exited with error");
+                                Ok(Err(e)) => {
+                                    eprintln!("{}", tr_args("cli.copy_failed", &[("error", &e)]));
                                 }
                                 Err(e) => {
-                                    eprintln!("Failed to copy transcript: {}", e);
+                                    eprintln!("{}", tr_args("cli.clipboard_failed", &[("error", &e)]));
                                 }
                             }
                         } else {
-                            println!("No transcript to copy (transcript is empty)");
+                            println!("{}", tr("cli.nothing_to_copy"));
                         }
                     }
+                    "e" => match sonori::subtitle_track::export(app_config) {
+                        Ok(path) => println!(
+                            "\n{}",
+                            tr_args("cli.subtitles_exported", &[("path", &path.display())])
+                        ),
+                        Err(e) => eprintln!("\n{}", tr_args("cli.subtitle_export_failed", &[("error", &e)])),
+                    },
+                    "h" => print_session_history(),
+                    "u" => match sonori::segment_undo::undo_in(&mut current_transcript) {
+                        Some(removed) => {
+                            println!("\n{}", tr_args("cli.removed", &[("segment", &removed)]))
+                        }
+                        None => println!("\n{}", tr("toast.nothing_to_undo")),
+                    },
                     "r" => {
-                        println!("\nReset transcript requested");
+                        println!("\n{}", tr("cli.reset_requested"));
                         // Clear the transcript history
                         let transcript_history = transcriber.get_transcript_history();
                         let mut history = transcript_history.write();
@@ -394,22 +1026,26 @@ async fn run_manual_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Result<
                         audio_data_lock.transcript.clear();
                         audio_data_lock.reset_requested = true;
                         drop(audio_data_lock);
+                        sonori::session_history::transcript_reset();
 
-                        println!("Transcript reset successfully");
+                        println!("{}", tr("cli.reset_done"));
+                    }
+                    "?" | "help" => {
+                        print_cli_help(app_config, TranscriptionMode::Manual);
                     }
                     "q" | "quit" => {
-                        println!("\nQuit requested");
+                        println!("\n{}", tr("cli.quit_requested"));
                         running.store(false, Ordering::Relaxed);
                         break;
                     }
                     _ => {
                         if !input.is_empty() {
-                            println!("\nUnknown command: '{}'. Use SPACE, c, r, or q.", input);
+                            println!("\n{}", tr_args("cli.unknown_command", &[("input", &input)]));
                         }
                     }
                 }
                 // Reprint status after command
-                print!("Status: {} | Transcript: {}", session_status, current_transcript);
+                print!("{}", cli_status_line(session_status, &current_transcript));
                 std::io::Write::flush(&mut std::io::stdout()).unwrap();
             }
             _ = tokio::time::sleep(tokio::time::Duration::from_millis(500)) => {
@@ -420,29 +1056,34 @@ async fn run_manual_cli(mut transcriber: RealTimeTranscriber) -> anyhow::Result<
                 // Update session status based on manual session state
                 if let Some(manual_status) = transcriber.get_manual_session_status() {
                     session_status = if manual_status.is_recording {
-                        "Recording"
+                        tr("cli.status_recording")
                     } else if manual_status.is_processing {
-                        "Processing"
+                        tr("cli.status_processing")
                     } else {
-                        "Session Active"
+                        tr("cli.status_active")
                     };
                 } else {
-                    session_status = "Ready";
+                    session_status = tr("cli.status_ready");
                 }
             }
         }
     }
 
+    sonori::transcript_writer::flush_auto_save();
     transcriber.shutdown().await?;
     Ok(())
 }
 
 async fn run_gui_mode(
     transcriber: RealTimeTranscriber,
-    app_config: AppConfig,
+    config_store: ConfigStore,
     magic_mode_enabled: Arc<AtomicBool>,
     magic_mode_enhancer: Option<Arc<sonori::enhancement::MagicModeEnhancer>>,
+    post_pipeline: Arc<PostPipeline>,
+    config_path: Option<std::path::PathBuf>,
+    sound_player: Option<Arc<SoundPlayer>>,
 ) -> anyhow::Result<()> {
+    let app_config = config_store.snapshot();
     // Set up shutdown channels and monitoring task
     let (_shutdown_tx, shutdown_rx) = tokio::sync::mpsc::channel::<()>(2);
     let transcript_history = transcriber.get_transcript_history();
@@ -450,6 +1091,7 @@ async fn run_gui_mode(
     let audio_visualization_data = transcriber.get_audio_visualization_data();
     let audio_visualization_data_for_thread = audio_visualization_data.clone();
     let running_for_shutdown = transcriber.get_running().clone();
+    let cli_post_pipeline = post_pipeline.clone();
 
     // Single unified shutdown task that handles all shutdown paths
     tokio::spawn(async move {
@@ -460,7 +1102,7 @@ async fn run_gui_mode(
         loop {
             tokio::select! {
                 Some(_) = shutdown_rx.recv() => {
-                    println!("Shutdown signal received, starting graceful shutdown...");
+                    tracing::info!("Shutdown signal received, starting graceful shutdown...");
                     break;
                 }
 
@@ -468,19 +1110,24 @@ async fn run_gui_mode(
                     let is_running = running_for_shutdown.load(Ordering::Relaxed);
 
                     if !is_running {
-                        println!("Running flag is now false, starting graceful shutdown...");
+                        tracing::info!("Running flag is now false, starting graceful shutdown...");
                         break;
                     }
                 }
             }
         }
 
-        println!("Shutdown monitor detected shutdown, waiting for event loop to exit");
+        // Write buffered transcript text before the process can exit
+        sonori::transcript_writer::flush_auto_save();
+        tracing::debug!("Shutdown monitor detected shutdown, waiting for event loop to exit");
     });
 
     // Single bounded queue for clipboard/paste work.
     // This avoids unbounded growth and keeps worker ownership simple.
-    let (paste_tx, mut paste_rx) = tokio::sync::mpsc::channel::<String>(128);
+    // Items are stamped with the privacy wipe generation so a wipe drops queued text,
+    // and carry the segment id that closes the segment's latency timeline.
+    let (paste_tx, mut paste_rx) =
+        tokio::sync::mpsc::channel::<sonori::privacy_wipe::Stamped<(u64, String)>>(128);
     let paste_tx_clone = paste_tx.clone();
     let audio_processor_for_session = transcriber.get_audio_processor();
 
@@ -488,16 +1135,78 @@ async fn run_gui_mode(
     let save_transcript_history = app_config.debug_config.save_transcript_history;
     let transcript_history_path = app_config.debug_config.transcript_history_path.clone();
 
+    // Usage analytics: the tracker only collects while the monitor has a period open
+    let usage_tracker = Arc::new(parking_lot::Mutex::new(
+        sonori::usage_log::UsageTracker::default(),
+    ));
+    let usage_tracker_for_consumer = usage_tracker.clone();
+    // Words delivered since start (or the last wipe), taken back from the counters by a wipe
+    let session_words = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let session_words_for_consumer = session_words.clone();
+    let transcript_history_for_wipe = transcript_history.clone();
+    let transcript_history_for_dbus = transcript_history.clone();
+    let transcript_history_for_stream = transcript_history.clone();
+    sonori::segment_undo::attach(sonori::segment_undo::UndoTargets {
+        transcript_history: transcript_history.clone(),
+        audio_data: audio_visualization_data.clone(),
+    });
+    let recording_for_consumer = transcriber.get_recording();
+    sonori::daily_words::init(app_config.usage.daily_word_goal);
+    let enhancer_for_rules = magic_mode_enhancer.clone();
+    // Runtime config changes go through the store; the consumer reads the post-processing
+    // stages and the paste worker the injection mode from it.
+    // Edits to the config file are published to it as well
+    let config_store_for_consumer = config_store.clone();
+    if let Some(path) = config_path {
+        sonori::config_watch::spawn(path, config_store.clone());
+    }
+    if let Some(player) = sound_player {
+        let config_store = config_store.clone();
+        tokio::spawn(async move {
+            let mut generation = config_store.generation();
+            let mut interval = tokio::time::interval(Duration::from_millis(250));
+            loop {
+                interval.tick().await;
+                if config_store.generation() != generation {
+                    generation = config_store.generation();
+                    player.apply_config(&config_store.snapshot().sound_config);
+                }
+            }
+        });
+    }
+    if app_config.debug_config.usage_log {
+        match sonori::usage_log::usage_log_path() {
+            Some(log_path) => {
+                let ctx = sonori::usage_log::UsageMonitorContext {
+                    tracker: usage_tracker.clone(),
+                    running: transcriber.get_running(),
+                    recording: transcriber.get_recording(),
+                    transcription_mode: transcriber.get_transcription_mode_ref(),
+                    audio_data: audio_visualization_data.clone(),
+                    backend: app_config.backend_config.backend.to_string(),
+                    model: app_config.general_config.model.clone(),
+                    log_path,
+                };
+                tokio::spawn(sonori::usage_log::run_usage_monitor(ctx));
+            }
+            None => tracing::warn!("Usage log disabled: cannot determine state directory"),
+        }
+    }
+
+    let mut pipeline_generation = config_store.generation();
+    let mut pipeline_inputs = post_pipeline_inputs(&app_config);
     tokio::spawn(async move {
+        let mut post_pipeline = post_pipeline;
         loop {
             let message = match transcript_rx.recv().await {
                 Ok(message) => message,
                 Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
-                    eprintln!("Transcript consumer lagged; skipped {} message(s)", skipped);
+                    tracing::warn!("Transcript consumer lagged; skipped {} message(s)", skipped);
                     continue;
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
             };
+            let received = std::time::Instant::now();
 
             // Get current session ID to filter stale transcriptions
             let current_session_id = if let Some(ref ap) = audio_processor_for_session {
@@ -506,27 +1215,63 @@ async fn run_gui_mode(
                 None
             };
 
+            // After a privacy wipe, text still in flight for the wiped session is
+            // dropped until a new recording starts
+            let wipe_registry = sonori::privacy_wipe::registry();
+            if wipe_registry.discarding() {
+                if recording_for_consumer.load(Ordering::Relaxed) {
+                    wipe_registry.resume();
+                } else {
+                    continue;
+                }
+            }
+
             // Discard transcriptions from old sessions
             if message.session_id != current_session_id {
                 if message.is_final {
-                    println!(
+                    sonori::transcription_stats::record_stale();
+                    tracing::debug!(
                         "Discarding stale transcription from session {:?} (current: {:?})",
-                        message.session_id, current_session_id
+                        message.session_id,
+                        current_session_id
                     );
                 }
                 continue;
             }
 
+            // Rebuild the post-processing stages when their config changed
+            if config_store_for_consumer.generation() != pipeline_generation {
+                pipeline_generation = config_store_for_consumer.generation();
+                let config = config_store_for_consumer.snapshot();
+                if post_pipeline_inputs(&config) != pipeline_inputs {
+                    match PostPipeline::from_app_config(&config) {
+                        Ok(pipeline) => {
+                            tracing::info!(
+                                "Post-processing stages: {}",
+                                pipeline.stage_names().join(", ")
+                            );
+                            post_pipeline = Arc::new(pipeline);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Keeping the previous post-processing stages: {}", e);
+                            sonori::toast::show(tr("toast.post_process_invalid"));
+                        }
+                    }
+                    pipeline_inputs = post_pipeline_inputs(&config);
+                }
+            }
+
             // Interim streaming hypotheses: show as a live preview only — no
             // history append, enhancement, file save, or clipboard paste. The
             // final message for this utterance commits and supersedes it.
             if !message.is_final {
+                let text = post_pipeline.process(&message.text);
                 let preview = {
                     let history = transcript_history.read();
                     if history.is_empty() {
-                        message.text
+                        text
                     } else {
-                        format!("{} {}", history, message.text)
+                        format!("{} {}", history, text)
                     }
                 };
                 // Trailing ellipsis marks the live, provisional tail; the final
@@ -535,20 +1280,44 @@ async fn run_gui_mode(
                 continue;
             }
 
-            let mut transcription = message.text;
-            if let Some(enhancer) = &magic_mode_enhancer {
+            // Dropped before the history and the clipboard/paste workers
+            let Some(mut transcription) = post_pipeline.process_final(
+                &message.text,
+                &segment_context(message.session_id.as_deref()),
+            ) else {
+                sonori::transcription_stats::record_hallucination();
+                continue;
+            };
+            if let Some(enhancer) = magic_mode_enhancer.as_ref().filter(|e| e.is_active()) {
+                // Show the raw segment while the model works; the enhanced text replaces it
+                let preview = {
+                    let history = transcript_history.read();
+                    if history.is_empty() {
+                        transcription.clone()
+                    } else {
+                        format!("{} {}", history, transcription)
+                    }
+                };
+                audio_visualization_data_for_thread.write().transcript = format!("{preview} …");
+
                 let raw_transcription = transcription.clone();
+                let timeout = enhancer.timeout();
                 let enhancer = Arc::clone(enhancer);
-                match tokio::task::spawn_blocking(move || enhancer.enhance(&raw_transcription))
-                    .await
-                {
-                    Ok(Ok(enhanced)) => {
+                let enhancement =
+                    tokio::task::spawn_blocking(move || enhancer.enhance(&raw_transcription));
+                // On timeout the worker finishes in the background and its result is dropped
+                match tokio::time::timeout(timeout, enhancement).await {
+                    Ok(Ok(Ok(enhanced))) => {
                         if !enhanced.trim().is_empty() {
                             transcription = enhanced;
                         }
                     }
-                    Ok(Err(e)) => eprintln!("Magic Mode enhancement failed: {e}"),
-                    Err(e) => eprintln!("Magic Mode enhancement worker failed: {e}"),
+                    Ok(Ok(Err(e))) => tracing::warn!("Magic Mode enhancement failed: {e}"),
+                    Ok(Err(e)) => tracing::warn!("Magic Mode enhancement worker failed: {e}"),
+                    Err(_) => tracing::warn!(
+                        "Magic Mode enhancement took longer than {}s; using the raw text",
+                        timeout.as_secs()
+                    ),
                 }
             }
 
@@ -561,32 +1330,68 @@ async fn run_gui_mode(
                     history.push(' ');
                 }
                 history.push_str(&transcription);
+                sonori::segment_undo::record_segment(&transcription);
+                sonori::transcript_spill::enforce(&mut history);
                 history.clone()
             };
             {
                 let mut audio_data = audio_visualization_data_for_thread.write();
                 audio_data.transcript = updated_transcript;
             }
+            {
+                let mut usage = usage_tracker_for_consumer.lock();
+                usage.add_words(&transcription);
+                usage.add_sink("overlay");
+            }
+            sonori::daily_words::record_words(&transcription);
+            sonori::subtitle_track::record_segment(&transcription);
+            sonori::session_history::record_segment(&transcription);
+            sonori::system_tray::record_segment(&transcription);
+            sonori::notifications::record_segment(&transcription);
+            sonori::dbus_control::record_segment(&transcription);
+            sonori::transcript_stream::record_segment(
+                message.session_id.as_deref(),
+                &transcription,
+            );
+            sonori::caption_server::record_segment(message.session_id.as_deref(), &transcription);
+            sonori::transcript_writer::auto_save_segment(&transcription);
+            session_words_for_consumer.fetch_add(
+                transcription.split_whitespace().count() as u64,
+                Ordering::Relaxed,
+            );
 
             // Save transcript to history file if enabled
-            if let Err(e) = sonori::transcript_writer::append_to_transcript_history(
+            match sonori::transcript_writer::append_to_transcript_history(
                 &transcription,
                 &transcript_history_path,
                 save_transcript_history,
             ) {
-                eprintln!("Failed to save transcript history: {}", e);
+                Ok(()) if save_transcript_history => {
+                    usage_tracker_for_consumer.lock().add_sink("history_file");
+                }
+                Ok(()) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to save transcript history: {}", e);
+                    usage_tracker_for_consumer.lock().add_error();
+                }
             }
 
+            let segment =
+                sonori::transcription_stats::record_segment(&transcription, None, received);
+
             // Forward chunk to clipboard and portal workers with leading space (except for first segment)
             let segment_with_space = if history_len_before > 0 {
                 format!(" {}", transcription)
             } else {
                 transcription
             };
+            let segment_with_space =
+                sonori::privacy_wipe::registry().stamp((segment, segment_with_space));
             if let Err(e) = paste_tx_clone.try_send(segment_with_space) {
                 match e {
                     tokio::sync::mpsc::error::TrySendError::Full(_) => {
-                        eprintln!("Paste queue full; dropping transcript paste update");
+                        tracing::warn!("Paste queue full; dropping transcript paste update");
+                        sonori::transcription_stats::finish_segment(segment, false);
                     }
                     tokio::sync::mpsc::error::TrySendError::Closed(_) => break,
                 }
@@ -594,83 +1399,127 @@ async fn run_gui_mode(
         }
     });
 
+    if !app_config.portal_config.paste_allowlist.is_empty()
+        || !app_config.portal_config.paste_blocklist.is_empty()
+    {
+        sonori::focused_app::start_tracking();
+    }
+
+    // Toggled at runtime from the tray, a shortcut or the settings panel
+    sonori::auto_paste::set_enabled(app_config.portal_config.auto_paste);
+
     // Paste worker: establish portal session when enabled, otherwise use key injection fallback.
     let paste_shortcut = app_config.portal_config.paste_shortcut.clone();
     if app_config.portal_config.enable_xdg_portal {
         let paste_shortcut = paste_shortcut.clone();
+        let usage_tracker = usage_tracker.clone();
+        let config_store = config_store.clone();
         tokio::spawn(async move {
-            let portal = match portal_input::PortalInput::new().await {
-                Ok(p) => Some(p),
-                Err(e) => {
-                    eprintln!(
-                        "Portal integration disabled: {}. Falling back to wtype/dotool.",
-                        e
-                    );
-                    None
-                }
-            };
-
-            while let Some(text) = paste_rx.recv().await {
-                let text_for_copy = text.clone();
-                match tokio::task::spawn_blocking(move || {
-                    copy::WlCopy::copy_to_clipboard(&text_for_copy)
-                })
-                .await
-                {
-                    Ok(Ok(())) => {}
-                    Ok(Err(e)) => {
-                        eprintln!("Clipboard copy failed: {}", e);
-                        continue;
-                    }
-                    Err(e) => {
-                        eprintln!("Clipboard worker failed: {}", e);
+            let mut reconnect =
+                sonori::portal_status::listen(sonori::portal_status::PortalCommand::ReconnectPaste);
+            let mut portal = connect_portal_input(false, &config_store).await;
+
+            loop {
+                let stamped = tokio::select! {
+                    stamped = paste_rx.recv() => match stamped {
+                        Some(stamped) => stamped,
+                        None => break,
+                    },
+                    Some(()) = reconnect.recv() => {
+                        // End the old session first so the portal asks again
+                        portal = None;
+                        portal = connect_portal_input(true, &config_store).await;
                         continue;
                     }
+                };
+                if sonori::privacy_wipe::registry().is_stale(&stamped) {
+                    sonori::transcription_stats::finish_segment(stamped.item.0, false);
+                    continue;
                 }
-
-                // Give clipboard managers a short moment before paste injection.
-                tokio::time::sleep(Duration::from_millis(50)).await;
-
-                if let Some(portal) = portal.as_ref() {
-                    let result = if paste_shortcut == "ctrl_v" {
-                        portal.paste_via_ctrl_v().await
-                    } else {
-                        portal.paste_via_ctrl_shift_v().await
-                    };
-
-                    if let Err(e) = result {
-                        eprintln!("Portal paste failed: {}", e);
+                let (segment, text) = stamped.item;
+                // Read per segment so a changed injection mode applies to the next one
+                let portal_config = config_store.snapshot().portal_config;
+                if copy_only_if_paste_blocked(&text, &portal_config, &usage_tracker).await {
+                    sonori::transcription_stats::finish_segment(segment, true);
+                    continue;
+                }
+                let plan = match portal.as_ref() {
+                    Some(portal) if portal_config.injection_mode == InjectionMode::Type => {
+                        portal_input::plan_injection(&text, |c| portal.can_type(c))
                     }
-                } else {
-                    let paste_shortcut = paste_shortcut.clone();
-                    match tokio::task::spawn_blocking(move || {
-                        copy::paste_via_keystroke(&paste_shortcut)
-                    })
-                    .await
-                    {
-                        Ok(Ok(())) => {}
-                        Ok(Err(e)) => eprintln!("Paste fallback failed: {}", e),
-                        Err(e) => eprintln!("Paste fallback worker failed: {}", e),
+                    _ => vec![portal_input::Injection::Paste(text)],
+                };
+                let key_delay = Duration::from_millis(portal_config.type_key_delay_ms);
+
+                let mut injected = true;
+                for step in plan {
+                    match (step, portal.as_ref()) {
+                        (portal_input::Injection::Type(text), Some(portal)) => {
+                            match portal.type_text(&text, key_delay).await {
+                                Ok(()) => usage_tracker.lock().add_sink("typed"),
+                                Err(e) => {
+                                    tracing::warn!("Portal typing failed: {}", e);
+                                    sonori::portal_status::record_error("RemoteDesktop", &e);
+                                    usage_tracker.lock().add_error();
+                                    injected = false;
+                                    break;
+                                }
+                            }
+                        }
+                        (portal_input::Injection::Type(text), None)
+                        | (portal_input::Injection::Paste(text), _) => {
+                            if !paste_through_clipboard(
+                                text,
+                                portal.as_ref(),
+                                &paste_shortcut,
+                                &usage_tracker,
+                            )
+                            .await
+                            {
+                                injected = false;
+                                break;
+                            }
+                        }
                     }
                 }
+                sonori::transcription_stats::finish_segment(segment, injected);
             }
         });
     } else {
+        if app_config.portal_config.injection_mode == InjectionMode::Type {
+            tracing::warn!("injection_mode = \"type\" needs enable_xdg_portal; pasting instead");
+        }
+        let usage_tracker = usage_tracker.clone();
+        let config_store = config_store.clone();
         tokio::spawn(async move {
-            while let Some(text) = paste_rx.recv().await {
-                let text_for_copy = text.clone();
+            while let Some(stamped) = paste_rx.recv().await {
+                if sonori::privacy_wipe::registry().is_stale(&stamped) {
+                    sonori::transcription_stats::finish_segment(stamped.item.0, false);
+                    continue;
+                }
+                let (segment, text_for_copy) = stamped.item;
+                let portal_config = config_store.snapshot().portal_config;
+                if copy_only_if_paste_blocked(&text_for_copy, &portal_config, &usage_tracker).await
+                {
+                    sonori::transcription_stats::finish_segment(segment, true);
+                    continue;
+                }
                 match tokio::task::spawn_blocking(move || {
                     copy::WlCopy::copy_to_clipboard(&text_for_copy)
                 })
                 .await
                 {
-                    Ok(Ok(())) => {}
+                    Ok(Ok(())) => usage_tracker.lock().add_sink("clipboard"),
                     Ok(Err(e)) => {
-                        eprintln!("Clipboard copy failed: {}", e);
+                        tracing::warn!("Clipboard copy failed: {}", e);
+                        usage_tracker.lock().add_error();
+                        sonori::transcription_stats::finish_segment(segment, false);
                         continue;
                     }
                     Err(e) => {
-                        eprintln!("Clipboard worker failed: {}", e);
+                        tracing::warn!("Clipboard worker failed: {}", e);
+                        usage_tracker.lock().add_error();
+                        sonori::transcription_stats::finish_segment(segment, false);
                         continue;
                     }
                 }
@@ -683,10 +1532,18 @@ async fn run_gui_mode(
                 })
                 .await
                 {
-                    Ok(Ok(())) => {}
-                    Ok(Err(e)) => eprintln!("Paste failed: {}", e),
-                    Err(e) => eprintln!("Paste worker failed: {}", e),
+                    Ok(Ok(())) => usage_tracker.lock().add_sink("paste"),
+                    Ok(Err(e)) => {
+                        tracing::warn!("Paste failed: {}", e);
+                        usage_tracker.lock().add_error();
+                    }
+                    Err(e) => {
+                        tracing::warn!("Paste worker failed: {}", e);
+                        usage_tracker.lock().add_error();
+                    }
                 }
+                // The text is on the clipboard even when the keystroke failed
+                sonori::transcription_stats::finish_segment(segment, true);
             }
         });
     }
@@ -694,10 +1551,124 @@ async fn run_gui_mode(
     let running = transcriber.get_running();
     let recording = transcriber.get_recording();
     let manual_session_sender = transcriber.get_manual_session_sender();
+
+    // Privacy wipe sinks, run in this order. Cancelling the session comes first so
+    // nothing new is produced while the rest is cleared.
+    {
+        let registry = sonori::privacy_wipe::registry();
+
+        let manual_session_sender = manual_session_sender.clone();
+        let transcription_mode = transcriber.get_transcription_mode_ref();
+        let recording = recording.clone();
+        registry.register("session", move || {
+            if !recording.load(Ordering::Relaxed) {
+                return;
+            }
+            match TranscriptionMode::from_u8(transcription_mode.load(Ordering::Relaxed)) {
+                TranscriptionMode::Manual => {
+                    let command = ManualSessionCommand::CancelSession { responder: None };
+                    match manual_session_sender.try_send(command) {
+                        Ok(()) => sonori::recording_archive::session_cancelled(),
+                        Err(e) => {
+                            tracing::warn!("Privacy wipe: failed to cancel the session: {}", e)
+                        }
+                    }
+                }
+                // Real-time mode has no cancel; stop listening instead
+                TranscriptionMode::RealTime => recording.store(false, Ordering::Relaxed),
+            }
+        });
+
+        registry.register("transcript_history", move || {
+            transcript_history_for_wipe.write().clear();
+            sonori::transcript_spill::clear();
+        });
+
+        let audio_data = audio_visualization_data.clone();
+        registry.register("overlay", move || {
+            let mut audio_data = audio_data.write();
+            audio_data.transcript.clear();
+            audio_data.reset_requested = true;
+        });
+
+        if app_config.debug_config.save_transcript_history {
+            let path = app_config.debug_config.transcript_history_path.clone();
+            let start_len = sonori::transcript_writer::transcript_history_len(&path);
+            registry.register("history_file", move || {
+                if let Err(e) =
+                    sonori::transcript_writer::truncate_transcript_history(&path, start_len)
+                {
+                    tracing::warn!(
+                        "Privacy wipe: failed to truncate the transcript history: {}",
+                        e
+                    );
+                }
+            });
+        }
+
+        let usage_tracker = usage_tracker.clone();
+        registry.register("usage", move || {
+            let words = session_words.swap(0, Ordering::Relaxed);
+            usage_tracker.lock().remove_words(words as usize);
+            sonori::daily_words::forget_words(words);
+        });
+
+        registry.register("subtitles", sonori::subtitle_track::clear);
+        registry.register("session_history", sonori::session_history::clear);
+        registry.register("undo", sonori::segment_undo::clear);
+        registry.register("tray", sonori::system_tray::clear);
+        registry.register("notifications", sonori::notifications::clear);
+        registry.register("captions", sonori::caption_server::clear);
+        registry.register(
+            "transcript_files",
+            sonori::transcript_writer::wipe_auto_save,
+        );
+
+        registry.register("clipboard", sonori::privacy_wipe::spawn_clear_clipboard);
+    }
+
+    tokio::spawn(sonori::subtitle_track::run_recording_monitor(
+        running.clone(),
+        recording.clone(),
+    ));
+    tokio::spawn(sonori::clipping::run_clipping_monitor(
+        running.clone(),
+        recording.clone(),
+        audio_visualization_data.clone(),
+    ));
     let transcription_mode_ref = transcriber.get_transcription_mode_ref();
     let backend_status = transcriber.get_backend_status();
+    tokio::spawn(sonori::notifications::run_monitor(
+        running.clone(),
+        recording.clone(),
+        backend_status.clone(),
+    ));
     let backend_command_tx = transcriber.backend_command_sender();
 
+    // Auto rules: per-session model/template overrides by time of day
+    if !app_config.auto_rules.is_empty() {
+        let errors = sonori::auto_rules::validate_rules(
+            &app_config.auto_rules,
+            &app_config.enhancement_config.templates,
+        );
+        if errors.is_empty() {
+            let ctx = sonori::auto_rules::AutoRulesContext {
+                rules: app_config.auto_rules.clone(),
+                config: config_store.clone(),
+                recording: recording.clone(),
+                running: running.clone(),
+                backend_command_tx: backend_command_tx.clone(),
+                enhancer: enhancer_for_rules,
+            };
+            tokio::spawn(sonori::auto_rules::run_auto_rules_monitor(ctx));
+        } else {
+            for error in &errors {
+                tracing::warn!("Invalid config: {}", error);
+            }
+            tracing::warn!("Auto rules disabled until the errors above are fixed");
+        }
+    }
+
     // System tray: start if enabled in configuration
     let (tray_update_tx, tray_command_rx) = if app_config.window_behavior_config.show_in_system_tray
     {
@@ -709,11 +1680,11 @@ async fn run_gui_mode(
         .await
         {
             Ok((update_tx, command_rx)) => {
-                println!("System tray initialized successfully");
+                tracing::info!("System tray initialized successfully");
                 (Some(update_tx), Some(command_rx))
             }
             Err(e) => {
-                eprintln!("Failed to initialize system tray: {}", e);
+                tracing::warn!("Failed to initialize system tray: {}", e);
                 (None, None)
             }
         }
@@ -721,71 +1692,545 @@ async fn run_gui_mode(
         (None, None)
     };
 
-    // Global shortcuts: register Super+\ (or configured) to toggle manual session
-    if app_config.portal_config.enable_global_shortcuts {
-        let accelerator = app_config.portal_config.manual_toggle_accelerator.clone();
-        let shortcut_mode = app_config.portal_config.shortcut_mode;
-        let manual_tx = manual_session_sender.clone();
-        let mode_ref = transcription_mode_ref.clone();
-        let recording_ref = recording.clone();
-        let running_ref = running.clone();
-        tokio::spawn(async move {
-            if let Err(e) = crate::global_shortcuts::run_listener(
-                &accelerator,
-                shortcut_mode,
-                manual_tx,
-                mode_ref,
-                recording_ref,
-                running_ref,
-            )
-            .await
-            {
-                eprintln!("Global shortcuts failed: {}", e);
-            }
-        });
-    }
+    // Global shortcuts: register Super+\ (or configured) to toggle manual session.
+    // Providers (portal, compositor IPC, evdev) all feed the same event channel.
+    let hotkey_channel = if app_config.portal_config.enable_global_shortcuts {
+        let (events_tx, events_rx) = tokio::sync::mpsc::channel(32);
+        let hotkey_status = Arc::new(parking_lot::RwLock::new(
+            sonori::hotkeys::HotkeyStatus::default(),
+        ));
+        tokio::spawn(sonori::hotkeys::run_dispatcher(
+            events_rx,
+            app_config.portal_config.shortcut_mode,
+            manual_session_sender.clone(),
+            transcription_mode_ref.clone(),
+            recording.clone(),
+            sonori::hotkeys::ShortcutTargets {
+                transcript_history: transcript_history.clone(),
+                audio_data: audio_visualization_data.clone(),
+            },
+        ));
+        tokio::spawn(global_shortcuts::supervise_hotkey_chain(
+            config_store.clone(),
+            events_tx.clone(),
+            hotkey_status.clone(),
+            running.clone(),
+        ));
+        Some((events_tx, hotkey_status))
+    } else {
+        None
+    };
 
     // IPC server: enable external control via CLI (for niri/sway keybindings)
     {
-        let ipc_server = ipc::IpcServer::new(
+        let mut ipc_server = ipc::IpcServer::new(
             manual_session_sender.clone(),
             transcription_mode_ref.clone(),
             recording.clone(),
             running.clone(),
         );
+        if let Some((events_tx, hotkey_status)) = hotkey_channel {
+            ipc_server = ipc_server.with_shortcut_events(events_tx, hotkey_status);
+        }
+        if app_config.general_config.enable_dbus {
+            let service = sonori::dbus_control::ControlService::new(
+                ipc_server.session_control(),
+                transcript_history_for_dbus,
+            );
+            let running = running.clone();
+            tokio::spawn(async move {
+                if let Err(e) = sonori::dbus_control::run(service, running).await {
+                    tracing::warn!("D-Bus control service error: {}", e);
+                }
+            });
+        }
+        if app_config.stream_config.enabled {
+            let stream_config = app_config.stream_config.clone();
+            let control = ipc_server.session_control();
+            let running = running.clone();
+            tokio::spawn(async move {
+                if let Err(e) = sonori::transcript_stream::run(
+                    &stream_config,
+                    control,
+                    transcript_history_for_stream,
+                    running,
+                )
+                .await
+                {
+                    tracing::warn!("Transcript stream error: {}", e);
+                }
+            });
+        }
+        if app_config.captions_config.enabled {
+            let captions_config = app_config.captions_config.clone();
+            let running = running.clone();
+            tokio::spawn(async move {
+                if let Err(e) = sonori::caption_server::run(&captions_config, running).await {
+                    tracing::warn!("Caption server error: {}", e);
+                }
+            });
+        }
         tokio::spawn(async move {
             if let Err(e) = ipc_server.run().await {
-                eprintln!("IPC server error: {}", e);
+                tracing::warn!("IPC server error: {}", e);
             }
         });
     }
 
-    // Run the UI with AtomicBool values directly and pass the configuration
-    ui::run_with_audio_data(
+    // Run the UI with AtomicBool values directly
+    let ui_result = ui::run_with_audio_data(
         audio_visualization_data,
-        running,
+        running.clone(),
         recording,
         magic_mode_enabled,
-        app_config,
+        config_store.clone(),
         Some(manual_session_sender),
-        transcription_mode_ref,
+        transcription_mode_ref.clone(),
         tray_update_tx,
         tray_command_rx,
         Some(backend_status),
         backend_command_tx,
     );
 
+    if let Err(e) = ui_result {
+        // Transcription does not depend on the GPU, so keep working without the overlay
+        tracing::error!("GUI initialization failed: {}", e);
+        if running.load(Ordering::Relaxed) {
+            sonori::notifications::notify(
+                sonori::notifications::NotificationEvent::GuiUnavailable(e),
+            );
+            tracing::info!("Continuing in CLI mode (shortcuts, IPC and auto-paste stay active)");
+            let mode = TranscriptionMode::from_u8(transcription_mode_ref.load(Ordering::Relaxed));
+            let cli_config = config_store.snapshot();
+            return run_cli_mode(transcriber, mode, &cli_config, cli_post_pipeline).await;
+        }
+    }
+
     // UI has exited, perform cleanup
+    sonori::transcript_writer::flush_auto_save();
+    sonori::transcript_spill::clear();
     let mut transcriber = transcriber;
     transcriber.shutdown().await?;
 
     Ok(())
 }
 
-fn init_tracing() {
-    let filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("speechcore=info"));
-    let _ = tracing_subscriber::fmt().with_env_filter(filter).try_init();
+/// Copy a segment to the clipboard; false when copying failed
+async fn copy_to_clipboard(
+    text: String,
+    usage_tracker: &Arc<parking_lot::Mutex<sonori::usage_log::UsageTracker>>,
+) -> bool {
+    match tokio::task::spawn_blocking(move || copy::WlCopy::copy_to_clipboard(&text)).await {
+        Ok(Ok(())) => {
+            usage_tracker.lock().add_sink("clipboard");
+            true
+        }
+        Ok(Err(e)) => {
+            tracing::warn!("Clipboard copy failed: {}", e);
+            usage_tracker.lock().add_error();
+            false
+        }
+        Err(e) => {
+            tracing::warn!("Clipboard worker failed: {}", e);
+            usage_tracker.lock().add_error();
+            false
+        }
+    }
+}
+
+/// Copy without pasting when auto-paste is off or the per-app paste rules
+/// exclude the focused app; true when the segment was handled this way
+async fn copy_only_if_paste_blocked(
+    text: &str,
+    portal_config: &sonori::config::PortalConfig,
+    usage_tracker: &Arc<parking_lot::Mutex<sonori::usage_log::UsageTracker>>,
+) -> bool {
+    if !sonori::auto_paste::enabled() {
+        if copy_to_clipboard(text.to_string(), usage_tracker).await {
+            usage_tracker
+                .lock()
+                .add_sink(sonori::auto_paste::CLIPBOARD_ONLY_SINK);
+        }
+        return true;
+    }
+    let Some(app) = sonori::focused_app::paste_blocked(
+        &portal_config.paste_allowlist,
+        &portal_config.paste_blocklist,
+    ) else {
+        return false;
+    };
+    tracing::info!(
+        "Paste rules: not pasting into {} ({:?}); copied to the clipboard only",
+        app.app_id,
+        app.title
+    );
+    if copy_to_clipboard(text.to_string(), usage_tracker).await {
+        usage_tracker.lock().add_sink("paste_blocked");
+    }
+    true
+}
+
+/// Create the RemoteDesktop session used to paste and type, publishing the
+/// outcome for the settings panel. `regrant` forgets the stored restore tokens
+/// first, so the portal shows its permission dialog again.
+async fn connect_portal_input(
+    regrant: bool,
+    config_store: &ConfigStore,
+) -> Option<portal_input::PortalInput> {
+    use sonori::portal_status::{self, SessionState};
+
+    if regrant {
+        let mut tokens = sonori::portal_tokens::PortalTokens::load();
+        tokens.remote_keyboard = None;
+        tokens.remote_screencast = None;
+        if let Err(e) = tokens.save() {
+            tracing::warn!("Failed to reset portal restore tokens: {}", e);
+        }
+        tracing::info!("Reconnecting the portal paste session on request");
+    }
+    portal_status::update(|status| status.paste = SessionState::Connecting);
+
+    let portal = match portal_input::PortalInput::new().await {
+        Ok(portal) => portal,
+        Err(e) => {
+            tracing::warn!(
+                "Portal integration disabled: {}. Falling back to wtype/dotool.",
+                e
+            );
+            portal_status::record_error("RemoteDesktop", format!("{:#}", e));
+            portal_status::update(|status| status.paste = SessionState::Failed);
+            return None;
+        }
+    };
+    portal_status::update(|status| status.paste = SessionState::Active);
+
+    if config_store.snapshot().portal_config.injection_mode == InjectionMode::Type
+        && !portal.can_type('a')
+    {
+        tracing::warn!("No xkb keymap for typing; injection_mode = \"type\" will paste instead");
+    }
+    Some(portal)
+}
+
+/// Copy `text` to the clipboard and send the paste shortcut, via the portal when
+/// there is a session. Returns false if the clipboard could not be set.
+async fn paste_through_clipboard(
+    text: String,
+    portal: Option<&portal_input::PortalInput>,
+    paste_shortcut: &str,
+    usage_tracker: &Arc<parking_lot::Mutex<sonori::usage_log::UsageTracker>>,
+) -> bool {
+    if !copy_to_clipboard(text, usage_tracker).await {
+        return false;
+    }
+
+    // Give clipboard managers a short moment before paste injection.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    if let Some(portal) = portal {
+        let result = if paste_shortcut == "ctrl_v" {
+            portal.paste_via_ctrl_v().await
+        } else {
+            portal.paste_via_ctrl_shift_v().await
+        };
+
+        match result {
+            Ok(()) => usage_tracker.lock().add_sink("paste"),
+            Err(e) => {
+                tracing::warn!("Portal paste failed: {}", e);
+                sonori::portal_status::record_error("RemoteDesktop", &e);
+                usage_tracker.lock().add_error();
+            }
+        }
+    } else {
+        let paste_shortcut = paste_shortcut.to_string();
+        match tokio::task::spawn_blocking(move || copy::paste_via_keystroke(&paste_shortcut)).await
+        {
+            Ok(Ok(())) => usage_tracker.lock().add_sink("paste"),
+            Ok(Err(e)) => {
+                tracing::warn!("Paste fallback failed: {}", e);
+                usage_tracker.lock().add_error();
+            }
+            Err(e) => {
+                tracing::warn!("Paste fallback worker failed: {}", e);
+                usage_tracker.lock().add_error();
+            }
+        }
+    }
+    true
+}
+
+/// Mirror model download progress in a desktop notification, replaced in 5% steps
+async fn notify_download_progress(
+    mut progress: tokio::sync::watch::Receiver<sonori::model_catalog::DownloadProgress>,
+) {
+    let mut shown = None;
+    loop {
+        let current = progress.borrow_and_update().clone();
+        let step = (current.phase.clone(), current.percent() / 5);
+        if shown.as_ref() != Some(&step) {
+            let failed = matches!(
+                current.phase,
+                sonori::model_catalog::DownloadPhase::Failed(_)
+            );
+            // The notifications module announces a finished download itself
+            if current.phase == sonori::model_catalog::DownloadPhase::Done
+                && sonori::notifications::announces_download_complete()
+            {
+                return;
+            }
+            let result = tokio::process::Command::new("notify-send")
+                .args([
+                    "--app-name=Sonori",
+                    "--hint=string:x-canonical-private-synchronous:sonori-model-download",
+                ])
+                .arg(format!("--hint=int:value:{}", current.percent()))
+                .arg(if failed {
+                    "--urgency=critical"
+                } else {
+                    "--urgency=low"
+                })
+                .arg("Sonori")
+                .arg(current.describe())
+                .status()
+                .await;
+            if let Err(e) = result {
+                tracing::warn!("Could not send desktop notification: {}", e);
+                return;
+            }
+            shown = Some(step);
+        }
+        if current.is_finished() || progress.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Read the usage log and print aggregate statistics
+fn print_usage_summary(since: Option<&str>) -> anyhow::Result<()> {
+    use sonori::usage_log;
+
+    let since = match since {
+        Some(value) => {
+            let window = usage_log::parse_since(value).ok_or_else(|| {
+                anyhow::anyhow!("Invalid --since value '{}': use e.g. 12h, 30d or 2w", value)
+            })?;
+            Some(chrono::Local::now() - window)
+        }
+        None => None,
+    };
+
+    let path = usage_log::usage_log_path()
+        .ok_or_else(|| anyhow::anyhow!("Cannot determine usage log location (HOME not set)"))?;
+    let records = usage_log::read_usage_records(&path)?;
+    if records.is_empty() {
+        println!(
+            "No usage data in {}. Enable it with `usage_log = true` in [debug_config].",
+            path.display()
+        );
+    } else {
+        let summary = usage_log::summarize(&records, since);
+        print!("{}", usage_log::format_summary(&summary));
+    }
+
+    print_daily_goal_summary();
+    Ok(())
+}
+
+/// Print today's progress and the current streak when a daily word goal is set
+fn print_daily_goal_summary() {
+    use sonori::daily_words;
+
+    let (app_config, _) = read_app_config_with_path();
+    let goal = app_config.usage.daily_word_goal;
+    if goal == 0 {
+        return;
+    }
+    let Some(path) = daily_words::daily_words_path() else {
+        return;
+    };
+
+    let words = daily_words::load(&path);
+    let today = chrono::Local::now().date_naive();
+    let streak = words.streak(today, goal);
+    println!();
+    println!(
+        "Today: {}",
+        daily_words::format_progress(words.words_on(today), goal)
+    );
+    println!(
+        "Goal streak: {} day{}",
+        streak,
+        if streak == 1 { "" } else { "s" }
+    );
+}
+
+/// List input devices, and the PipeWire sources for
+/// `audio_capture_config.target_object` when that API is in use
+fn print_audio_devices() -> anyhow::Result<()> {
+    let capture_config = sonori::config::read_app_config().audio_capture_config;
+    let api =
+        sonori::pipewire_capture::resolve_api(capture_config.api).map_err(anyhow::Error::msg)?;
+    if api == sonori::config::CaptureApi::Pipewire {
+        let nodes = sonori::pipewire_capture::list_sources().map_err(anyhow::Error::msg)?;
+        if nodes.is_empty() {
+            println!("No PipeWire sources found");
+        } else {
+            println!("PipeWire sources (audio_capture_config.target_object):");
+            for node in &nodes {
+                let marker = if node.is_default { " (default)" } else { "" };
+                let serial = node
+                    .serial
+                    .map(|serial| format!("  serial {}", serial))
+                    .unwrap_or_default();
+                let description = node
+                    .description
+                    .as_deref()
+                    .map(|description| format!("  {}", description))
+                    .unwrap_or_default();
+                println!("  {}{}{}{}", node.name, marker, serial, description);
+            }
+        }
+        println!();
+    }
+
+    let devices = sonori::audio_devices::list_input_devices().map_err(anyhow::Error::msg)?;
+    if devices.is_empty() {
+        println!("No audio input devices found");
+        return Ok(());
+    }
+    println!("Audio input devices:");
+    for device in &devices {
+        let marker = if device.is_default { " (default)" } else { "" };
+        let rate = match device.native_sample_rate {
+            Some(rate) if rate as usize != sonori::config::SAMPLE_RATE => format!(
+                "  {} Hz native (captured at {} Hz)",
+                rate,
+                sonori::config::SAMPLE_RATE
+            ),
+            Some(rate) => format!("  {} Hz native", rate),
+            None => String::new(),
+        };
+        let channels = device
+            .channels
+            .map(|channels| format!(", {} ch", channels))
+            .unwrap_or_default();
+        println!("  {}{}{}{}", device.name, marker, rate, channels);
+    }
+    Ok(())
+}
+
+/// List outputs for `display_config.output_name`
+fn print_outputs() -> anyhow::Result<()> {
+    let outputs = sonori::wayland_outputs::list_outputs().map_err(anyhow::Error::msg)?;
+    if outputs.is_empty() {
+        println!("No Wayland outputs found");
+        return Ok(());
+    }
+    println!("Wayland outputs:");
+    for output in &outputs {
+        println!(
+            "  {}  {}x{} @ {:.2} Hz{}",
+            output.name.as_deref().unwrap_or("(unnamed)"),
+            output.width,
+            output.height,
+            output.refresh_mhz as f64 / 1000.0,
+            output
+                .description
+                .as_deref()
+                .map(|description| format!("  {}", description))
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Config the post-processing stages are built from, to tell when they need rebuilding
+fn post_pipeline_inputs(
+    config: &AppConfig,
+) -> (
+    sonori::config::PostProcessConfig,
+    String,
+    speechcore::BackendType,
+    Vec<String>,
+) {
+    (
+        config.post_process_config.clone(),
+        config.general_config.language.clone(),
+        config.backend_config.backend,
+        config.common_transcription_options.vocabulary.clone(),
+    )
+}
+
+/// Context of a committed segment for the post-processing stages
+fn segment_context(session_id: Option<&str>) -> SegmentContext {
+    SegmentContext {
+        session_id: session_id.map(str::to_string),
+    }
+}
+
+/// `--export-profile`: the config file, with `--profile` layered over it if given
+fn export_profile(name: &str, base_profile: Option<&str>) -> anyhow::Result<()> {
+    let (mut app_config, _) = read_app_config_with_path();
+    if let Some(base_profile) = base_profile {
+        app_config = Profile::load(base_profile)
+            .and_then(|profile| profile.apply(&app_config))
+            .map_err(anyhow::Error::msg)?;
+    }
+    let path = sonori::profiles::export(&app_config, name).map_err(anyhow::Error::msg)?;
+    println!("Profile '{}' written to {}", name, path.display());
+    Ok(())
+}
+
+fn print_gpu_probe() -> anyhow::Result<()> {
+    let (app_config, _) = read_app_config_with_path();
+    println!("GPU:");
+    for line in sonori::gpu_probe::describe(&app_config) {
+        println!("  {}", line);
+    }
+    Ok(())
+}
+
+/// Print the transcription device, the global shortcut providers available
+/// here and, if Sonori is running, which one the fallback chain actually selected
+async fn print_capabilities() -> anyhow::Result<()> {
+    let (app_config, _) = read_app_config_with_path();
+    let portal_config = &app_config.portal_config;
+
+    let backend = app_config.backend_config.backend;
+    println!(
+        "Transcription device: {:?} on {}",
+        backend,
+        sonori::gpu_probe::resolve(&app_config, backend).device
+    );
+
+    println!("Global shortcuts:");
+    if !portal_config.enable_global_shortcuts {
+        println!("  disabled (enable_global_shortcuts = false)");
+        return Ok(());
+    }
+    println!("  accelerator: {}", portal_config.manual_toggle_accelerator);
+    for line in global_shortcuts::probe_hotkey_providers(portal_config).await {
+        println!("  {}", line);
+    }
+
+    match ipc::send_command(IpcCommand::Hotkeys).await {
+        Ok(response) => match response.hotkeys {
+            Some(status) => {
+                println!("  running instance:");
+                for line in status.describe() {
+                    println!("    {}", line);
+                }
+            }
+            None => println!(
+                "  running instance: {}",
+                response.message.unwrap_or_default()
+            ),
+        },
+        Err(_) => println!("  running instance: not running"),
+    }
+
+    Ok(())
 }
 
 /// Handle IPC subcommands by sending them to the running Sonori instance
@@ -795,8 +2240,14 @@ async fn handle_ipc_command(cmd: Command) -> anyhow::Result<()> {
         Command::Start => IpcCommand::Start,
         Command::Stop => IpcCommand::Stop,
         Command::Cancel => IpcCommand::Cancel,
+        Command::Wipe => IpcCommand::Wipe,
         Command::Status => IpcCommand::Status,
+        Command::Quit => IpcCommand::Quit,
         Command::SwitchMode { mode } => IpcCommand::SwitchMode { mode },
+        Command::Shortcut { action } => match action {
+            ShortcutAction::Press => IpcCommand::ShortcutPressed,
+            ShortcutAction::Release => IpcCommand::ShortcutReleased,
+        },
     };
 
     match ipc::send_command(ipc_cmd).await {
@@ -0,0 +1,331 @@
+//! Microphone mute state of the default source, followed through `pactl
+//! subscribe`, with pause/resume on mute and `sync_os_mute`.
+
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use speechcore::{ManualSessionCommand, TranscriptionMode};
+use tokio::io::{AsyncBufReadExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdout};
+use tokio::sync::mpsc::Sender;
+
+/// How often the recording flag is checked for user toggles
+const RECORDING_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+/// How long a manual session may take to stop or start after a mute switch
+const SESSION_SWITCH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The overlay should show the mic muted indicator
+static MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the source is muted in a way the user should see. A mute applied by
+/// `sync_os_mute` while Sonori is idle does not count.
+pub fn muted() -> bool {
+    MUTED.load(Ordering::Relaxed)
+}
+
+/// Mute state from `pactl get-source-mute` output ("Mute: yes")
+fn parse_mute_output(output: &str) -> Option<bool> {
+    match output.trim().strip_prefix("Mute:")?.trim() {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Read the default source's mute switch; `None` when `pactl` cannot tell
+fn read_source_mute() -> Option<bool> {
+    let output = std::process::Command::new("pactl")
+        .args(["get-source-mute", "@DEFAULT_SOURCE@"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    parse_mute_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether a `pactl subscribe` line can change the default source's mute:
+/// a source changed, or the server switched its default
+fn is_source_event(line: &str) -> bool {
+    line.starts_with("Event 'change' on ")
+        && (line.contains(" on source #") || line.contains(" on server"))
+}
+
+/// Running `pactl subscribe`; the process is killed when this is dropped
+struct Subscription {
+    _child: Child,
+    lines: Lines<BufReader<ChildStdout>>,
+}
+
+fn subscribe() -> std::io::Result<Subscription> {
+    let mut child = tokio::process::Command::new("pactl")
+        .arg("subscribe")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    Ok(Subscription {
+        _child: child,
+        lines: BufReader::new(stdout).lines(),
+    })
+}
+
+/// Next event line; `None` once the subscription ended, pending forever without one
+async fn next_event(subscription: &mut Option<Subscription>) -> Option<String> {
+    match subscription {
+        Some(subscription) => subscription.lines.next_line().await.ok().flatten(),
+        None => std::future::pending().await,
+    }
+}
+
+/// Wait until the runtime reports `expected` recording state, or give up
+async fn wait_for_recording(recording: &AtomicBool, expected: bool) {
+    let deadline = tokio::time::Instant::now() + SESSION_SWITCH_TIMEOUT;
+    while recording.load(Ordering::Relaxed) != expected {
+        if tokio::time::Instant::now() >= deadline {
+            tracing::warn!(
+                "Manual session did not {} after the mute switch",
+                if expected { "start" } else { "stop" }
+            );
+            return;
+        }
+        tokio::time::sleep(RECORDING_CHECK_INTERVAL).await;
+    }
+}
+
+/// Set the default source's mute switch, logging failures
+fn set_source_mute(mute: bool) {
+    let result = std::process::Command::new("pactl")
+        .args([
+            "set-source-mute",
+            "@DEFAULT_SOURCE@",
+            if mute { "1" } else { "0" },
+        ])
+        .status();
+    match result {
+        Ok(status) if status.success() => {
+            tracing::info!(
+                "{} the microphone (sync_os_mute)",
+                if mute { "Muted" } else { "Unmuted" }
+            );
+        }
+        Ok(status) => tracing::warn!("pactl set-source-mute failed: {}", status),
+        Err(e) => tracing::warn!("Could not run pactl: {}", e),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MuteAction {
+    /// The source was muted during a recording: stop recording
+    Pause,
+    /// The source was unmuted after a pause: record again
+    Resume,
+    /// Recording was toggled with `sync_os_mute` on: mute or unmute the source
+    SetOsMute(bool),
+}
+
+/// Reacts to mute switches and recording toggles between two checks
+#[derive(Debug, Default)]
+struct MuteTracker {
+    muted: bool,
+    was_recording: bool,
+    /// Recording was stopped by a mute and should resume on unmute
+    paused: bool,
+}
+
+impl MuteTracker {
+    fn step(&mut self, muted: bool, recording: bool, sync_os_mute: bool) -> Option<MuteAction> {
+        let action = if recording != self.was_recording {
+            // The user toggled recording; that overrides a pending resume
+            self.paused = false;
+            sync_os_mute.then_some(MuteAction::SetOsMute(!recording))
+        } else if muted && !self.muted && recording {
+            self.paused = true;
+            Some(MuteAction::Pause)
+        } else if !muted && self.muted && self.paused {
+            self.paused = false;
+            Some(MuteAction::Resume)
+        } else {
+            None
+        };
+        self.muted = muted;
+        // Pausing and resuming flip the recording flag; that is not a user toggle
+        self.was_recording = match action {
+            Some(MuteAction::Pause) => false,
+            Some(MuteAction::Resume) => true,
+            _ => recording,
+        };
+        action
+    }
+
+    /// Whether the overlay should show the mute
+    fn indicator(&self, sync_os_mute: bool) -> bool {
+        self.muted && (self.was_recording || self.paused || !sync_os_mute)
+    }
+}
+
+/// Follow the default source's mute switch: publish it for the overlay, pause
+/// and resume recording, and apply `sync_os_mute`. A manual session cannot be
+/// paused, so a mute stops it (the audio so far is transcribed) and an unmute
+/// starts a new one.
+pub async fn run_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    transcription_mode: Arc<AtomicU8>,
+    manual_session_tx: Sender<ManualSessionCommand>,
+    sync_os_mute: bool,
+) {
+    // Subscribe before the first read so no switch in between is missed
+    let mut subscription = match subscribe() {
+        Ok(subscription) => Some(subscription),
+        Err(e) => {
+            tracing::debug!("Could not run pactl subscribe: {}", e);
+            None
+        }
+    };
+    let mut muted = match tokio::task::spawn_blocking(read_source_mute).await {
+        Ok(Some(muted)) => muted,
+        _ => {
+            tracing::debug!("Microphone mute state unavailable (no pactl); mute sync is off");
+            return;
+        }
+    };
+    let mut tracker = MuteTracker {
+        muted,
+        was_recording: recording.load(Ordering::Relaxed),
+        paused: false,
+    };
+    MUTED.store(tracker.indicator(sync_os_mute), Ordering::Relaxed);
+    let mut os_muted_by_us = false;
+    let mut interval = tokio::time::interval(RECORDING_CHECK_INTERVAL);
+
+    while running.load(Ordering::Relaxed) {
+        tokio::select! {
+            event = next_event(&mut subscription) => match event {
+                Some(line) if is_source_event(&line) => {
+                    let read = tokio::task::spawn_blocking(read_source_mute).await;
+                    if let Ok(Some(now_muted)) = read {
+                        muted = now_muted;
+                    }
+                }
+                Some(_) => continue,
+                None => {
+                    tracing::warn!("pactl subscribe ended; mute switches are no longer followed");
+                    subscription = None;
+                }
+            },
+            _ = interval.tick() => {}
+        }
+        let manual = matches!(
+            TranscriptionMode::from_u8(transcription_mode.load(Ordering::Relaxed)),
+            TranscriptionMode::Manual
+        );
+
+        match tracker.step(muted, recording.load(Ordering::Relaxed), sync_os_mute) {
+            Some(MuteAction::Pause) => {
+                tracing::info!("Microphone muted; pausing recording");
+                if manual {
+                    let command = ManualSessionCommand::StopSession { responder: None };
+                    match manual_session_tx.send(command).await {
+                        Ok(()) => wait_for_recording(&recording, false).await,
+                        Err(e) => tracing::warn!("Failed to stop the manual session: {}", e),
+                    }
+                } else {
+                    recording.store(false, Ordering::Relaxed);
+                }
+                crate::toast::show(crate::i18n::tr("toast.mic_muted_paused"));
+            }
+            Some(MuteAction::Resume) => {
+                tracing::info!("Microphone unmuted; resuming recording");
+                if manual {
+                    let command = ManualSessionCommand::StartSession { responder: None };
+                    match manual_session_tx.send(command).await {
+                        Ok(()) => wait_for_recording(&recording, true).await,
+                        Err(e) => tracing::warn!("Failed to start the manual session: {}", e),
+                    }
+                } else {
+                    recording.store(true, Ordering::Relaxed);
+                }
+            }
+            Some(MuteAction::SetOsMute(mute)) => {
+                os_muted_by_us = mute;
+                tokio::task::spawn_blocking(move || set_source_mute(mute));
+            }
+            None => {}
+        }
+        MUTED.store(tracker.indicator(sync_os_mute), Ordering::Relaxed);
+    }
+
+    // Do not leave the microphone muted for other applications after exit
+    if os_muted_by_us {
+        let _ = tokio::task::spawn_blocking(|| set_source_mute(false)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pactl_mute_output() {
+        assert_eq!(parse_mute_output("Mute: yes\n"), Some(true));
+        assert_eq!(parse_mute_output("Mute: no"), Some(false));
+        assert_eq!(parse_mute_output("No such entity"), None);
+    }
+
+    #[test]
+    fn only_source_and_server_changes_trigger_a_read() {
+        assert!(is_source_event("Event 'change' on source #52"));
+        assert!(is_source_event("Event 'change' on server #-1"));
+        assert!(!is_source_event("Event 'change' on source-output #140"));
+        assert!(!is_source_event("Event 'new' on source #53"));
+        assert!(!is_source_event("Event 'change' on sink #48"));
+    }
+
+    #[test]
+    fn mute_pauses_recording_until_unmuted() {
+        let mut tracker = MuteTracker {
+            was_recording: true,
+            ..Default::default()
+        };
+        assert_eq!(tracker.step(true, true, false), Some(MuteAction::Pause));
+        assert!(tracker.indicator(false));
+        // The monitor cleared the recording flag itself
+        assert_eq!(tracker.step(true, false, false), None);
+        assert_eq!(tracker.step(false, false, false), Some(MuteAction::Resume));
+        assert_eq!(tracker.step(false, true, false), None);
+
+        // Muting while idle only shows the indicator
+        assert_eq!(tracker.step(false, false, false), None);
+        assert_eq!(tracker.step(true, false, false), None);
+        assert!(tracker.indicator(false));
+    }
+
+    #[test]
+    fn user_toggle_cancels_resume_and_syncs_os_mute() {
+        let mut tracker = MuteTracker {
+            was_recording: true,
+            ..Default::default()
+        };
+        assert_eq!(tracker.step(true, true, true), Some(MuteAction::Pause));
+        assert_eq!(tracker.step(true, false, true), None);
+        assert!(tracker.indicator(true));
+
+        // Starting by hand while paused unmutes and forgets the pause
+        assert_eq!(
+            tracker.step(true, true, true),
+            Some(MuteAction::SetOsMute(false))
+        );
+        assert_eq!(tracker.step(false, true, true), None);
+        assert_eq!(
+            tracker.step(false, false, true),
+            Some(MuteAction::SetOsMute(true))
+        );
+        // An idle mute applied by the sync is not shown
+        assert_eq!(tracker.step(true, false, true), None);
+        assert!(!tracker.indicator(true));
+        assert!(tracker.indicator(false));
+    }
+}
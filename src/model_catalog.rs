@@ -0,0 +1,600 @@
+//! Approximate download sizes and licenses of the speech models offered in Sonori.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use speechcore::BackendType::{CTranslate2, Moonshine, Nemotron, Parakeet, WhisperCpp};
+use speechcore::{BackendConfig, BackendType};
+
+use crate::config::AppConfig;
+
+/// Confirmation threshold divisor applied on metered connections
+const METERED_THRESHOLD_DIVISOR: u64 = 10;
+
+/// Relative difference between table and server size that gets logged
+const SIZE_DISCREPANCY_TOLERANCE: f64 = 0.15;
+
+/// How often the model cache is measured while a download runs
+const DOWNLOAD_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A CTranslate2 download that stops growing this long near its size is converting
+const CONVERSION_STALL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelInfo {
+    pub backend: BackendType,
+    pub model: &'static str,
+    /// Approximate download size in MB (full precision for Whisper.cpp)
+    pub size_mb: u64,
+    /// SPDX identifier (or name) of the model weights' license
+    pub license: &'static str,
+    /// File or directory name in the speechcore model cache
+    pub cache_name: &'static str,
+}
+
+const fn entry(
+    backend: BackendType,
+    model: &'static str,
+    size_mb: u64,
+    license: &'static str,
+    cache_name: &'static str,
+) -> ModelInfo {
+    ModelInfo {
+        backend,
+        model,
+        size_mb,
+        license,
+        cache_name,
+    }
+}
+
+static MODELS: &[ModelInfo] = &[
+    entry(WhisperCpp, "tiny", 75, "MIT", "ggml-tiny"),
+    entry(WhisperCpp, "tiny.en", 75, "MIT", "ggml-tiny.en"),
+    entry(WhisperCpp, "base", 142, "MIT", "ggml-base"),
+    entry(WhisperCpp, "base.en", 142, "MIT", "ggml-base.en"),
+    entry(WhisperCpp, "small", 466, "MIT", "ggml-small"),
+    entry(WhisperCpp, "small.en", 466, "MIT", "ggml-small.en"),
+    entry(WhisperCpp, "medium", 1530, "MIT", "ggml-medium"),
+    entry(WhisperCpp, "medium.en", 1530, "MIT", "ggml-medium.en"),
+    entry(WhisperCpp, "large-v1", 3090, "MIT", "ggml-large-v1"),
+    entry(WhisperCpp, "large-v2", 3090, "MIT", "ggml-large-v2"),
+    entry(WhisperCpp, "large-v3", 3100, "MIT", "ggml-large-v3"),
+    entry(
+        WhisperCpp,
+        "large-v3-turbo",
+        1620,
+        "MIT",
+        "ggml-large-v3-turbo",
+    ),
+    entry(CTranslate2, "tiny.en", 150, "MIT", "tiny.en-ct2"),
+    entry(CTranslate2, "base.en", 290, "MIT", "base.en-ct2"),
+    entry(CTranslate2, "small.en", 970, "MIT", "small.en-ct2"),
+    entry(CTranslate2, "medium.en", 3060, "MIT", "medium.en-ct2"),
+    entry(CTranslate2, "large-v3", 6170, "MIT", "large-v3-ct2"),
+    entry(Moonshine, "tiny", 110, "MIT", "moonshine-tiny-onnx"),
+    entry(Moonshine, "base", 250, "MIT", "moonshine-base-onnx"),
+    entry(
+        Parakeet,
+        "parakeet-tdt-0.6b-v3",
+        660,
+        "CC-BY-4.0",
+        "parakeet-tdt-v3-int8",
+    ),
+    entry(
+        Parakeet,
+        "parakeet-tdt-0.6b-v2",
+        640,
+        "CC-BY-4.0",
+        "parakeet-tdt-v2-int8",
+    ),
+    entry(
+        Nemotron,
+        "nemotron-3.5-asr-streaming-0.6b",
+        650,
+        "NVIDIA-Open-Model-License",
+        "nemotron-3.5-asr-streaming-0.6b",
+    ),
+];
+
+/// Look up a model in the size/license table
+pub fn lookup(backend: BackendType, model: &str) -> Option<&'static ModelInfo> {
+    MODELS
+        .iter()
+        .find(|info| info.backend == backend && info.model == model)
+}
+
+/// The only language a model can transcribe, for backends without language
+/// selection. Moonshine models are English unless the name carries a language
+/// tag (e.g. `tiny-ko`).
+pub fn fixed_language(backend: BackendType, model: &str) -> Option<String> {
+    match backend {
+        Moonshine => Some(
+            model
+                .split_once('-')
+                .map(|(_, language)| language.to_string())
+                .unwrap_or_else(|| "en".to_string()),
+        ),
+        _ => None,
+    }
+}
+
+/// Whether the backend's models write punctuation and capitalization themselves.
+/// Moonshine's output is lowercase and unpunctuated.
+pub fn emits_punctuation(backend: BackendType) -> bool {
+    !matches!(backend, Moonshine)
+}
+
+/// Reject a language the configured model cannot transcribe (including "auto")
+pub fn check_language(backend: BackendType, model: &str, language: &str) -> Result<(), String> {
+    match fixed_language(backend, model) {
+        Some(supported) if !supported.eq_ignore_ascii_case(language) => Err(format!(
+            "{:?} model '{}' only transcribes '{}' and cannot detect languages, but language is \
+             set to '{}'. Set language = \"{}\", or use a multilingual backend (whisper_cpp, \
+             parakeet) for other languages.",
+            backend, model, supported, language, supported
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Check that `path` (`general_config.model_path`) has the layout `backend` loads
+pub fn check_model_path(backend: BackendType, path: &Path) -> Result<(), String> {
+    let fail = |expected: &str| {
+        Err(format!(
+            "model_path {} does not look like a {:?} model: expected {}",
+            path.display(),
+            backend,
+            expected
+        ))
+    };
+    if !path.exists() {
+        return Err(format!("model_path {} does not exist", path.display()));
+    }
+    match backend {
+        WhisperCpp => {
+            let is_model_file = path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext == "gguf" || ext == "bin");
+            if !is_model_file {
+                return fail("a single GGML/GGUF file (.bin or .gguf), not a directory");
+            }
+        }
+        CTranslate2 => {
+            let missing = missing_files(path, &["model.bin", "config.json"]);
+            if !missing.is_empty() {
+                return fail(&format!(
+                    "a converted CTranslate2 directory; missing {}",
+                    missing.join(", ")
+                ));
+            }
+        }
+        Moonshine => {
+            let missing = missing_files(path, &["encoder_model.onnx", "decoder_model_merged.onnx"]);
+            if !missing.is_empty() {
+                return fail(&format!(
+                    "a directory with the Moonshine ONNX pair; missing {}",
+                    missing.join(", ")
+                ));
+            }
+        }
+        Parakeet | Nemotron => {
+            let has_onnx = std::fs::read_dir(path).is_ok_and(|entries| {
+                entries
+                    .flatten()
+                    .any(|entry| entry.path().extension().is_some_and(|ext| ext == "onnx"))
+            });
+            let mut missing = missing_files(path, &["tokens.txt"]);
+            if !has_onnx {
+                missing.insert(0, "*.onnx");
+            }
+            if !missing.is_empty() {
+                return fail(&format!(
+                    "a sherpa-onnx model directory; missing {}",
+                    missing.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Files of `names` that are not in the directory `dir`
+fn missing_files<'a>(dir: &Path, names: &[&'a str]) -> Vec<&'a str> {
+    names
+        .iter()
+        .copied()
+        .filter(|name| !dir.join(name).is_file())
+        .collect()
+}
+
+/// Whisper.cpp file suffix and size factor for the configured quantization
+fn whisper_quantization(backend_config: &BackendConfig) -> (&'static str, f64) {
+    let level = format!("{:?}", backend_config.quantization_level).to_lowercase();
+    match level.trim_matches('"') {
+        "high" => ("", 1.0),
+        "low" => ("-q5_1", 0.37),
+        _ => ("-q8_0", 0.53),
+    }
+}
+
+/// Approximate download size in MB for a model with the given backend settings
+pub fn download_size_mb(backend_config: &BackendConfig, model: &str) -> Option<u64> {
+    let info = lookup(backend_config.backend, model)?;
+    if info.backend == WhisperCpp {
+        let (_, factor) = whisper_quantization(backend_config);
+        return Some((info.size_mb as f64 * factor).round() as u64);
+    }
+    Some(info.size_mb)
+}
+
+/// Human-readable size, e.g. "466 MB" or "3.1 GB"
+pub fn format_size(size_mb: u64) -> String {
+    if size_mb >= 1000 {
+        format!("{:.1} GB", size_mb as f64 / 1000.0)
+    } else {
+        format!("{} MB", size_mb)
+    }
+}
+
+/// Label for a model select entry, e.g. "large-v3 (3.1 GB)"
+pub fn model_label(backend_config: &BackendConfig, model: &str) -> String {
+    match download_size_mb(backend_config, model) {
+        Some(size) => format!("{} ({})", model, format_size(size)),
+        None => model.to_string(),
+    }
+}
+
+/// The speechcore model cache (`SPEECHCORE_MODEL_DIR` or `~/.cache/speechcore/models`)
+pub fn model_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("SPEECHCORE_MODEL_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let cache = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(cache.join("speechcore").join("models"))
+}
+
+/// Where the speech runtime stores `info` in the model cache
+fn cache_path(backend_config: &BackendConfig, info: &ModelInfo) -> Option<PathBuf> {
+    let dir = model_cache_dir()?;
+    if info.backend == WhisperCpp {
+        let (suffix, _) = whisper_quantization(backend_config);
+        return Some(dir.join(format!("{}{}.bin", info.cache_name, suffix)));
+    }
+    Some(dir.join(info.cache_name))
+}
+
+fn is_cached(backend_config: &BackendConfig, info: &ModelInfo) -> bool {
+    cache_path(backend_config, info).is_some_and(|path| path.exists())
+}
+
+/// A model download that is about to happen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingDownload {
+    pub model: String,
+    pub size_mb: u64,
+    pub license: &'static str,
+    /// Direct file URL when the download is a single known file (used to verify the size)
+    pub url: Option<String>,
+    /// Whether the download is followed by a conversion (CTranslate2)
+    pub converts: bool,
+}
+
+impl PendingDownload {
+    pub fn describe(&self) -> String {
+        format!(
+            "{} ({}, license {})",
+            self.model,
+            format_size(self.size_mb),
+            self.license
+        )
+    }
+}
+
+/// The download `config` would trigger, if the model is known and not cached yet
+pub fn pending_download(config: &AppConfig) -> Option<PendingDownload> {
+    if config.general_config.model_path.is_some() {
+        return None;
+    }
+    let backend_config = &config.backend_config;
+    let model = config.general_config.model.as_str();
+    let info = lookup(backend_config.backend, model)?;
+    if is_cached(backend_config, info) {
+        return None;
+    }
+
+    let url = (info.backend == WhisperCpp).then(|| {
+        let (suffix, _) = whisper_quantization(backend_config);
+        format!(
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{}{}.bin",
+            info.cache_name, suffix
+        )
+    });
+
+    Some(PendingDownload {
+        model: model.to_string(),
+        size_mb: download_size_mb(backend_config, model)?,
+        license: info.license,
+        url,
+        converts: info.backend == CTranslate2,
+    })
+}
+
+/// Error for `general_config.offline` when the configured model is not in the cache
+pub fn offline_missing_model(config: &AppConfig) -> Option<String> {
+    if config.general_config.model_path.is_some() {
+        return None;
+    }
+    let backend_config = &config.backend_config;
+    let model = config.general_config.model.as_str();
+    let info = lookup(backend_config.backend, model)?;
+    if is_cached(backend_config, info) {
+        return None;
+    }
+    let location = match cache_path(backend_config, info) {
+        Some(path) => path.display().to_string(),
+        None => "the model cache (set SPEECHCORE_MODEL_DIR)".to_string(),
+    };
+    Some(format!(
+        "Offline mode: model '{}' is not downloaded. Place it at {} \
+         (or point SPEECHCORE_MODEL_DIR at a directory that has it), \
+         or start once without --offline to download it",
+        model, location
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadPhase {
+    Downloading,
+    /// Converting the downloaded weights; the duration is unknown
+    Converting,
+    Done,
+    Failed(String),
+}
+
+/// Progress of a model download as measured in the model cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub model: String,
+    pub bytes_done: u64,
+    /// Approximate total from the size table
+    pub total_bytes: u64,
+    pub phase: DownloadPhase,
+}
+
+impl DownloadProgress {
+    pub fn new(download: &PendingDownload) -> Self {
+        Self {
+            model: download.model.clone(),
+            bytes_done: 0,
+            total_bytes: download.size_mb * 1_000_000,
+            phase: DownloadPhase::Downloading,
+        }
+    }
+
+    /// Percent done, capped at 99 until the download is finished (the total is approximate)
+    pub fn percent(&self) -> u8 {
+        match self.phase {
+            DownloadPhase::Done => 100,
+            _ if self.total_bytes == 0 => 0,
+            _ => (self.bytes_done * 100 / self.total_bytes).min(99) as u8,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match &self.phase {
+            DownloadPhase::Downloading => {
+                format!("Downloading model {}… {}%", self.model, self.percent())
+            }
+            DownloadPhase::Converting => format!("Converting model {}…", self.model),
+            DownloadPhase::Done => format!("Model {} ready", self.model),
+            DownloadPhase::Failed(error) => format!("Model {} failed: {}", self.model, error),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase, DownloadPhase::Done | DownloadPhase::Failed(_))
+    }
+}
+
+/// Total size of the files below `path`
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| disk_usage(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Publish download progress by measuring how much the model cache grew.
+///
+/// The downloader runs inside speechcore and reports nothing back, so this
+/// runs next to it until the caller marks the progress Done or Failed.
+pub async fn run_download_monitor(
+    download: PendingDownload,
+    progress: std::sync::Arc<tokio::sync::watch::Sender<DownloadProgress>>,
+) {
+    let Some(dir) = model_cache_dir() else {
+        return;
+    };
+    let measure = move || disk_usage(&dir);
+    let baseline = tokio::task::spawn_blocking(measure.clone())
+        .await
+        .unwrap_or(0);
+    let mut last_growth = Instant::now();
+    let mut interval = tokio::time::interval(DOWNLOAD_POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        if progress.borrow().is_finished() || progress.is_closed() {
+            return;
+        }
+        let Ok(usage) = tokio::task::spawn_blocking(measure.clone()).await else {
+            return;
+        };
+        let bytes_done = usage.saturating_sub(baseline);
+        progress.send_if_modified(|current| {
+            if current.is_finished() {
+                return false;
+            }
+            if bytes_done > current.bytes_done {
+                last_growth = Instant::now();
+            }
+            let converting = download.converts
+                && bytes_done >= current.total_bytes / 10 * 9
+                && last_growth.elapsed() >= CONVERSION_STALL;
+            let phase = if converting {
+                DownloadPhase::Converting
+            } else {
+                DownloadPhase::Downloading
+            };
+            let changed = current.bytes_done != bytes_done || current.phase != phase;
+            current.bytes_done = bytes_done;
+            current.phase = phase;
+            changed
+        });
+    }
+}
+
+/// Downloads at or above this size need confirmation
+pub fn confirmation_threshold_mb(configured_mb: u64, metered: bool) -> u64 {
+    if metered {
+        configured_mb / METERED_THRESHOLD_DIVISOR
+    } else {
+        configured_mb
+    }
+}
+
+/// Whether NetworkManager reports the primary connection as metered (yes or guessed yes).
+///
+/// Returns false when NetworkManager is unavailable.
+pub fn network_is_metered() -> bool {
+    // The blocking D-Bus client runs its own executor; keep it off runtime threads
+    std::thread::spawn(|| -> zbus::Result<u32> {
+        let connection = zbus::blocking::Connection::system()?;
+        let proxy = zbus::blocking::Proxy::new(
+            &connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+        )?;
+        proxy.get_property::<u32>("Metered")
+    })
+    .join()
+    .ok()
+    .and_then(Result::ok)
+    // NMMetered: 1 = yes, 3 = guess-yes
+    .is_some_and(|metered| metered == 1 || metered == 3)
+}
+
+/// Compare the table size with the server's content length and log discrepancies
+pub async fn verify_download_size(download: PendingDownload) {
+    let Some(url) = download.url else {
+        return;
+    };
+    let response = match reqwest::Client::new().head(&url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::warn!("Could not check download size of {}: {}", download.model, e);
+            return;
+        }
+    };
+    let Some(actual_bytes) = response.content_length() else {
+        return;
+    };
+
+    let actual_mb = actual_bytes as f64 / 1_000_000.0;
+    let expected_mb = download.size_mb as f64;
+    if (actual_mb - expected_mb).abs() / expected_mb > SIZE_DISCREPANCY_TOLERANCE {
+        tracing::warn!(
+            "Model size table is off for {}: expected ~{}, server reports {}",
+            download.model,
+            format_size(download.size_mb),
+            format_size(actual_mb.round() as u64)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_size(75), "75 MB");
+        assert_eq!(format_size(3100), "3.1 GB");
+    }
+
+    #[test]
+    fn metered_connections_lower_threshold() {
+        assert_eq!(confirmation_threshold_mb(500, false), 500);
+        assert_eq!(confirmation_threshold_mb(500, true), 50);
+    }
+
+    #[test]
+    fn looks_up_models_per_backend() {
+        assert!(lookup(WhisperCpp, "large-v3").is_some());
+        assert!(lookup(Parakeet, "parakeet-tdt-0.6b-v2").is_some());
+        assert!(lookup(CTranslate2, "large-v3-turbo").is_none());
+    }
+
+    #[test]
+    fn download_percent_stays_below_100_until_done() {
+        let mut progress = DownloadProgress {
+            model: "large-v3".to_string(),
+            bytes_done: 1_650_000_000,
+            total_bytes: 1_600_000_000,
+            phase: DownloadPhase::Downloading,
+        };
+        assert_eq!(progress.percent(), 99);
+        progress.bytes_done = 400_000_000;
+        assert_eq!(progress.describe(), "Downloading model large-v3… 25%");
+        progress.phase = DownloadPhase::Done;
+        assert_eq!(progress.percent(), 100);
+        assert!(progress.is_finished());
+    }
+
+    #[test]
+    fn moonshine_rejects_other_languages() {
+        assert!(check_language(Moonshine, "base", "en").is_ok());
+        assert!(check_language(Moonshine, "base", "de").is_err());
+        assert!(check_language(Moonshine, "tiny", "auto").is_err());
+        assert!(check_language(Moonshine, "tiny-ko", "ko").is_ok());
+        assert!(check_language(WhisperCpp, "base", "auto").is_ok());
+    }
+
+    #[test]
+    fn model_path_must_match_backend_layout() {
+        let dir = std::env::temp_dir().join(format!("sonori-model-path-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("model.bin"), b"").unwrap();
+
+        let err = check_model_path(CTranslate2, &dir).unwrap_err();
+        assert!(err.contains("missing config.json"), "{err}");
+        std::fs::write(dir.join("config.json"), b"{}").unwrap();
+        assert!(check_model_path(CTranslate2, &dir).is_ok());
+
+        assert!(check_model_path(WhisperCpp, &dir).is_err());
+        assert!(check_model_path(WhisperCpp, &dir.join("model.bin")).is_ok());
+        assert!(check_model_path(Moonshine, &dir)
+            .unwrap_err()
+            .contains("encoder_model.onnx, decoder_model_merged.onnx"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(check_model_path(CTranslate2, &dir)
+            .unwrap_err()
+            .contains("does not exist"));
+    }
+}
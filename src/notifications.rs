@@ -0,0 +1,352 @@
+//! Desktop notifications for key events, sent through the XDG Notification portal.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use ashpd::desktop::notification::{Button, Notification, NotificationProxy, Priority};
+use futures_util::StreamExt;
+use parking_lot::{Mutex, RwLock};
+use speechcore::{BackendStatus, BackendStatusState};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::config::NotificationsConfig;
+
+/// Portal ID of the transcription notification; a newer one replaces it
+const TRANSCRIPTION_ID: &str = "sonori-transcription";
+/// Action name of the transcription notification's "Copy" button
+const COPY_ACTION: &str = "copy";
+/// Characters of transcript shown in the notification body
+const PREVIEW_CHARS: usize = 200;
+/// Quiet time after the last segment before a stopped recording counts as complete
+const SETTLE_TIME: Duration = Duration::from_millis(1500);
+
+static SENDER: OnceLock<UnboundedSender<NotificationEvent>> = OnceLock::new();
+static CONFIG: OnceLock<NotificationsConfig> = OnceLock::new();
+
+/// Segments committed since the last transcription notification, and when the latest arrived
+static PENDING: Mutex<(String, Option<Instant>)> = Mutex::new((String::new(), None));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationEvent {
+    SessionStarted,
+    /// A recording's transcript is final; carries the full text
+    TranscriptionComplete(String),
+    BackendError(String),
+    /// The overlay could not start and Sonori continues in the terminal
+    GuiUnavailable(String),
+    /// A model finished downloading; carries the model name
+    DownloadComplete(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum EventKind {
+    SessionStarted,
+    TranscriptionComplete,
+    BackendError,
+    GuiUnavailable,
+    DownloadComplete,
+}
+
+impl NotificationEvent {
+    fn kind(&self) -> EventKind {
+        match self {
+            Self::SessionStarted => EventKind::SessionStarted,
+            Self::TranscriptionComplete(_) => EventKind::TranscriptionComplete,
+            Self::BackendError(_) => EventKind::BackendError,
+            Self::GuiUnavailable(_) => EventKind::GuiUnavailable,
+            Self::DownloadComplete(_) => EventKind::DownloadComplete,
+        }
+    }
+}
+
+impl NotificationsConfig {
+    fn wants(&self, kind: EventKind) -> bool {
+        match kind {
+            // One-shot notice that the overlay is gone, so it ignores the opt-in
+            EventKind::GuiUnavailable => true,
+            EventKind::SessionStarted => self.enabled && self.session_started,
+            EventKind::TranscriptionComplete => self.enabled && self.transcription_complete,
+            EventKind::BackendError => self.enabled && self.backend_error,
+            EventKind::DownloadComplete => self.enabled && self.download_complete,
+        }
+    }
+}
+
+/// Start the notification task; without `enabled` only the overlay failure notice is sent
+pub fn init(config: &NotificationsConfig) {
+    let _ = CONFIG.set(config.clone());
+    if !config.enabled {
+        return;
+    }
+    let (tx, rx) = mpsc::unbounded_channel();
+    if SENDER.set(tx).is_ok() {
+        tokio::spawn(run_notifier(
+            rx,
+            Duration::from_secs(config.min_interval_secs),
+        ));
+    }
+}
+
+/// Whether model download completion is announced here (instead of the progress notification)
+pub fn announces_download_complete() -> bool {
+    CONFIG
+        .get()
+        .is_some_and(|config| config.wants(EventKind::DownloadComplete))
+}
+
+/// Queue a notification if its event is enabled
+pub fn notify(event: NotificationEvent) {
+    let wanted = CONFIG
+        .get()
+        .is_some_and(|config| config.wants(event.kind()));
+    if !wanted {
+        return;
+    }
+    match SENDER.get() {
+        Some(sender) => {
+            let _ = sender.send(event);
+        }
+        None => {
+            tokio::spawn(send_once(event));
+        }
+    }
+}
+
+/// Collect a committed segment for the next transcription notification
+pub fn record_segment(text: &str) {
+    if SENDER.get().is_none() {
+        return;
+    }
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    let mut pending = PENDING.lock();
+    if !pending.0.is_empty() {
+        pending.0.push(' ');
+    }
+    pending.0.push_str(text);
+    pending.1 = Some(Instant::now());
+}
+
+/// Forget collected segments (privacy wipe)
+pub fn clear() {
+    *PENDING.lock() = (String::new(), None);
+}
+
+/// Take the collected transcript once recording has stopped and it has settled
+fn take_settled(recording: bool, now: Instant) -> Option<String> {
+    let mut pending = PENDING.lock();
+    let settled = pending
+        .1
+        .is_some_and(|last| now.duration_since(last) >= SETTLE_TIME);
+    if recording || !settled {
+        return None;
+    }
+    Some(std::mem::take(&mut *pending).0)
+}
+
+/// Turn recording starts, settled transcripts and backend failures into notifications
+pub async fn run_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    backend_status: Arc<RwLock<BackendStatus>>,
+) {
+    if SENDER.get().is_none() {
+        return;
+    }
+    let mut interval = tokio::time::interval(Duration::from_millis(250));
+    let mut was_recording = recording.load(Ordering::Relaxed);
+    let mut last_error: Option<String> = None;
+
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+
+        let is_recording = recording.load(Ordering::Relaxed);
+        if is_recording && !was_recording {
+            notify(NotificationEvent::SessionStarted);
+        }
+        was_recording = is_recording;
+
+        if let Some(text) = take_settled(is_recording, Instant::now()) {
+            notify(NotificationEvent::TranscriptionComplete(text));
+        }
+
+        let error = match &backend_status.read().state {
+            BackendStatusState::Error(message) => Some(message.clone()),
+            _ => None,
+        };
+        if let Some(message) = error.as_ref().filter(|_| error != last_error) {
+            notify(NotificationEvent::BackendError(message.clone()));
+        }
+        last_error = error;
+    }
+}
+
+/// Drops events that repeat a kind within `min_interval`
+#[derive(Debug)]
+struct RateLimiter {
+    min_interval: Duration,
+    last_sent: HashMap<EventKind, Instant>,
+}
+
+impl RateLimiter {
+    fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_sent: HashMap::new(),
+        }
+    }
+
+    fn allow(&mut self, kind: EventKind, now: Instant) -> bool {
+        match self.last_sent.get(&kind) {
+            Some(&last) if now.duration_since(last) < self.min_interval => false,
+            _ => {
+                self.last_sent.insert(kind, now);
+                true
+            }
+        }
+    }
+}
+
+/// First `max_chars` characters of `text`, cut at a word boundary where possible
+fn preview(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > 0 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end())
+}
+
+fn build(event: &NotificationEvent) -> (&'static str, Notification) {
+    match event {
+        NotificationEvent::SessionStarted => (
+            "sonori-session",
+            Notification::new("Sonori is recording").priority(Priority::Low),
+        ),
+        NotificationEvent::TranscriptionComplete(text) => (
+            TRANSCRIPTION_ID,
+            Notification::new("Transcription complete")
+                .body(preview(text, PREVIEW_CHARS).as_str())
+                .button(Button::new("Copy", COPY_ACTION)),
+        ),
+        NotificationEvent::BackendError(message) => (
+            "sonori-backend",
+            Notification::new("Sonori backend failed to load")
+                .body(message.as_str())
+                .priority(Priority::High),
+        ),
+        NotificationEvent::GuiUnavailable(message) => (
+            "sonori-gui",
+            Notification::new("Sonori overlay unavailable")
+                .body(format!("{}\nTranscription continues in the terminal.", message).as_str())
+                .priority(Priority::High),
+        ),
+        NotificationEvent::DownloadComplete(model) => (
+            "sonori-download",
+            Notification::new("Model download complete")
+                .body(format!("Model {} is ready", model).as_str()),
+        ),
+    }
+}
+
+/// Send a single notification without the notifier task (notifications disabled)
+async fn send_once(event: NotificationEvent) {
+    let (id, notification) = build(&event);
+    let result = match NotificationProxy::new().await {
+        Ok(proxy) => proxy.add_notification(id, notification).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        tracing::warn!("Could not send desktop notification: {}", e);
+    }
+}
+
+async fn run_notifier(mut events: UnboundedReceiver<NotificationEvent>, min_interval: Duration) {
+    let proxy = match NotificationProxy::new().await {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            tracing::warn!(
+                "Desktop notifications unavailable (notification portal): {}",
+                e
+            );
+            return;
+        }
+    };
+    let mut actions = match proxy.receive_action_invoked().await {
+        Ok(actions) => Box::pin(actions),
+        Err(e) => {
+            tracing::warn!("Notification buttons unavailable: {}", e);
+            return;
+        }
+    };
+    let mut limiter = RateLimiter::new(min_interval);
+    // Full text behind the "Copy" button of the current transcription notification
+    let mut copy_text = String::new();
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let Some(event) = event else { return };
+                if !limiter.allow(event.kind(), Instant::now()) {
+                    tracing::debug!("Notification rate-limited: {:?}", event.kind());
+                    continue;
+                }
+                let (id, notification) = build(&event);
+                match proxy.add_notification(id, notification).await {
+                    Ok(()) => {
+                        if let NotificationEvent::TranscriptionComplete(text) = event {
+                            copy_text = text;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Could not send desktop notification: {}", e),
+                }
+            }
+            Some(action) = actions.next() => {
+                if action.id() != TRANSCRIPTION_ID || action.name() != COPY_ACTION {
+                    continue;
+                }
+                let text = copy_text.clone();
+                tokio::task::spawn_blocking(move || {
+                    if let Err(e) = crate::copy::WlCopy::copy_to_clipboard(&text) {
+                        tracing::warn!("Notification copy failed: {}", e);
+                    }
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_spaces_out_each_kind_separately() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(Duration::from_secs(10));
+        assert!(limiter.allow(EventKind::TranscriptionComplete, start));
+        assert!(!limiter.allow(
+            EventKind::TranscriptionComplete,
+            start + Duration::from_secs(3)
+        ));
+        assert!(limiter.allow(EventKind::SessionStarted, start + Duration::from_secs(3)));
+        assert!(limiter.allow(
+            EventKind::TranscriptionComplete,
+            start + Duration::from_secs(10)
+        ));
+    }
+
+    #[test]
+    fn preview_cuts_long_text_at_a_word() {
+        assert_eq!(preview("short text", 20), "short text");
+        assert_eq!(preview("the quick brown fox", 12), "the quick…");
+        assert_eq!(preview("abcdefghij", 4), "abcd…");
+    }
+}
@@ -0,0 +1,242 @@
+//! Crash handling for the speech pipeline.
+
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::{Mutex, RwLock};
+use speechcore::AudioVisualizationData;
+
+use crate::sound_generator::SoundType;
+use crate::sound_player::SoundPlayer;
+
+/// How often the monitor checks for a recorded failure
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Path fragments of crates whose panics kill the speech pipeline
+const PIPELINE_CRATES: &[&str] = &["speechcore", "/ort-", "whisper-rs", "ct2rs"];
+/// File name fragments of the capture side of the pipeline
+const AUDIO_FILES: &[&str] = &["audio", "capture", "vad", "silero"];
+
+/// The first pipeline panic of this run
+static FAILURE: Mutex<Option<PipelineFailure>> = Mutex::new(None);
+/// The terminal shows a `\r` status line that a panic message must not overwrite
+static CLI_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Pipeline task a panic was raised in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineTask {
+    /// Capture, resampling and voice activity detection
+    Audio,
+    /// Backend loading and inference
+    Transcription,
+}
+
+impl PipelineTask {
+    /// Attribute a panic location to a pipeline task; `None` for Sonori's own code
+    fn from_location(file: &str) -> Option<Self> {
+        let file = file.replace('\\', "/");
+        if !PIPELINE_CRATES.iter().any(|name| file.contains(name)) {
+            return None;
+        }
+        let file_name = file.rsplit('/').next().unwrap_or(&file);
+        if AUDIO_FILES.iter().any(|name| file_name.contains(name)) {
+            Some(Self::Audio)
+        } else {
+            Some(Self::Transcription)
+        }
+    }
+}
+
+impl fmt::Display for PipelineTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Audio => "audio",
+            Self::Transcription => "transcription",
+        })
+    }
+}
+
+/// A panic that took down a pipeline task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineFailure {
+    pub task: PipelineTask,
+    pub message: String,
+    /// `file:line` the panic was raised at
+    pub location: String,
+    /// Last segment delivered before the panic
+    pub last_segment_id: Option<u64>,
+}
+
+impl fmt::Display for PipelineFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Transcription stopped: the {} task crashed ({})",
+            self.task, self.message
+        )?;
+        match self.last_segment_id {
+            Some(id) => write!(f, " after segment {}", id),
+            None => write!(f, " before the first segment"),
+        }
+    }
+}
+
+/// Text of a panic payload; panics carry a `&str` or a `String`
+fn payload_text(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(text) = payload.downcast_ref::<&str>() {
+        text.to_string()
+    } else if let Some(text) = payload.downcast_ref::<String>() {
+        text.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+/// Install the panic hook; call once, before the speech runtime starts.
+///
+/// The previous hook still prints the panic message and backtrace. In CLI mode
+/// the status line is ended first so the message starts on a fresh line.
+pub fn install(cli_mode: bool) {
+    CLI_MODE.store(cli_mode, Ordering::Relaxed);
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if CLI_MODE.load(Ordering::Relaxed) {
+            let mut stdout = std::io::stdout().lock();
+            let _ = writeln!(stdout);
+            let _ = stdout.flush();
+        }
+        previous(info);
+
+        let Some(location) = info.location() else {
+            return;
+        };
+        let Some(task) = PipelineTask::from_location(location.file()) else {
+            return;
+        };
+        let failure = PipelineFailure {
+            task,
+            message: payload_text(info.payload()),
+            location: format!("{}:{}", location.file(), location.line()),
+            last_segment_id: crate::transcription_stats::last_segment_id(),
+        };
+        tracing::error!(
+            "Speech pipeline {} task panicked at {} (thread {}, last segment {}): {}",
+            failure.task,
+            failure.location,
+            std::thread::current().name().unwrap_or("unnamed"),
+            failure
+                .last_segment_id
+                .map_or_else(|| "none".to_string(), |id| id.to_string()),
+            failure.message
+        );
+        // A panic while the lock is held must not deadlock the hook
+        if let Some(mut slot) = FAILURE.try_lock() {
+            slot.get_or_insert(failure);
+        }
+    }));
+}
+
+/// The pipeline failure of this run, if a pipeline task panicked
+pub fn failure() -> Option<PipelineFailure> {
+    FAILURE.lock().clone()
+}
+
+/// React to a pipeline panic recorded by the hook.
+///
+/// The CLI run ends by clearing `running`. In GUI mode clearing `running` would
+/// close the overlay before the failure could be read, so recording is stopped
+/// instead and the failure replaces the transcript until the user quits.
+pub async fn run_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    audio_data: Arc<RwLock<AudioVisualizationData>>,
+    sound_player: Option<Arc<SoundPlayer>>,
+) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+
+        let Some(failure) = failure() else {
+            continue;
+        };
+        if let Some(player) = &sound_player {
+            player.play(SoundType::PipelineError);
+        }
+        if CLI_MODE.load(Ordering::Relaxed) {
+            tracing::error!("{}; shutting down", failure);
+            running.store(false, Ordering::Relaxed);
+        } else {
+            recording.store(false, Ordering::Relaxed);
+            audio_data.write().transcript = format!("{}. Restart Sonori to resume.", failure);
+            crate::toast::show(crate::i18n::tr("toast.pipeline_crashed"));
+        }
+        return;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_outside_pipeline_crates_are_ignored() {
+        assert_eq!(PipelineTask::from_location("src/ui/window.rs"), None);
+        assert_eq!(
+            PipelineTask::from_location("/rustc/abc/library/core/src/option.rs"),
+            None
+        );
+    }
+
+    #[test]
+    fn pipeline_panics_are_attributed_by_file_name() {
+        let checkout = "/home/u/.cargo/git/checkouts/speechcore-1a2b/a72da83/src";
+        assert_eq!(
+            PipelineTask::from_location(&format!("{}/audio_processor.rs", checkout)),
+            Some(PipelineTask::Audio)
+        );
+        assert_eq!(
+            PipelineTask::from_location(&format!("{}/silero_audio_processor.rs", checkout)),
+            Some(PipelineTask::Audio)
+        );
+        assert_eq!(
+            PipelineTask::from_location(&format!("{}/backend/whisper_cpp.rs", checkout)),
+            Some(PipelineTask::Transcription)
+        );
+        assert_eq!(
+            PipelineTask::from_location(
+                "/home/u/.cargo/registry/src/index.crates.io-6f17/ort-2.0.0-rc.10/src/session/mod.rs"
+            ),
+            Some(PipelineTask::Transcription)
+        );
+    }
+
+    #[test]
+    fn payload_text_reads_str_and_string_payloads() {
+        assert_eq!(payload_text(&"boom"), "boom");
+        assert_eq!(payload_text(&String::from("onnx failed")), "onnx failed");
+        assert_eq!(payload_text(&42u32), "non-string panic payload");
+    }
+
+    #[test]
+    fn failure_message_names_task_and_last_segment() {
+        let failure = PipelineFailure {
+            task: PipelineTask::Transcription,
+            message: "model load failed".to_string(),
+            location: "backend.rs:10".to_string(),
+            last_segment_id: Some(7),
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Transcription stopped: the transcription task crashed (model load failed) after segment 7"
+        );
+        let failure = PipelineFailure {
+            last_segment_id: None,
+            ..failure
+        };
+        assert!(failure.to_string().ends_with("before the first segment"));
+    }
+}
@@ -0,0 +1,280 @@
+//! PipeWire stream identity and source selection (`audio_capture_config`).
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::config::{AudioCaptureConfig, CaptureApi, APPLICATION_ID};
+
+/// Properties the PipeWire ALSA plugin applies to the streams it opens
+const PROPS_ENV: &str = "PIPEWIRE_PROPS";
+/// Node the PipeWire ALSA plugin connects its streams to
+const NODE_ENV: &str = "PIPEWIRE_NODE";
+
+/// Source node the stream was pointed at by `configure_stream`, when it was found
+static CAPTURE_TARGET: OnceLock<String> = OnceLock::new();
+
+/// A PipeWire audio source node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipewireNode {
+    /// `node.name`, what `target.object` and WirePlumber rules match
+    pub name: String,
+    /// `node.description`, the name desktop mixers show
+    pub description: Option<String>,
+    /// `object.serial`, stable for the node's lifetime
+    pub serial: Option<u64>,
+    /// `media.class`, e.g. `Audio/Source` or `Audio/Source/Virtual`
+    pub media_class: String,
+    /// The session's default source
+    pub is_default: bool,
+}
+
+/// Whether a PipeWire daemon is listening for this session
+pub fn pipewire_running() -> bool {
+    let runtime_dir = std::env::var_os("PIPEWIRE_RUNTIME_DIR")
+        .or_else(|| std::env::var_os("XDG_RUNTIME_DIR"))
+        .map(PathBuf::from);
+    let remote = std::env::var("PIPEWIRE_REMOTE").unwrap_or_else(|_| "pipewire-0".to_string());
+    runtime_dir.is_some_and(|dir| dir.join(remote).exists())
+}
+
+/// The API `api` resolves to on this system. `auto` falls back to PortAudio
+/// without PipeWire; an explicit `pipewire` fails instead.
+pub fn resolve_api(api: CaptureApi) -> Result<CaptureApi, String> {
+    match api {
+        CaptureApi::Auto if pipewire_running() => Ok(CaptureApi::Pipewire),
+        CaptureApi::Auto => Ok(CaptureApi::Portaudio),
+        CaptureApi::Pipewire if !pipewire_running() => Err(
+            "audio_capture_config.api = \"pipewire\", but no PipeWire daemon is running; \
+             use \"auto\" or \"portaudio\""
+                .to_string(),
+        ),
+        api => Ok(api),
+    }
+}
+
+/// Audio source nodes, read from `pw-dump`
+pub fn list_sources() -> Result<Vec<PipewireNode>, String> {
+    let output = Command::new("pw-dump")
+        .output()
+        .map_err(|e| format!("Could not run pw-dump: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("pw-dump failed: {}", output.status));
+    }
+    parse_dump(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Sources found at startup, for the settings window (empty without PipeWire)
+pub fn startup_sources() -> &'static [PipewireNode] {
+    static SOURCES: OnceLock<Vec<PipewireNode>> = OnceLock::new();
+    SOURCES.get_or_init(|| {
+        if !pipewire_running() {
+            return Vec::new();
+        }
+        list_sources().unwrap_or_else(|e| {
+            tracing::warn!("{}", e);
+            Vec::new()
+        })
+    })
+}
+
+fn parse_dump(json: &str) -> Result<Vec<PipewireNode>, String> {
+    let objects: Vec<serde_json::Value> =
+        serde_json::from_str(json).map_err(|e| format!("Unreadable pw-dump output: {}", e))?;
+
+    let default_source = objects
+        .iter()
+        .filter(|object| object["type"] == "PipeWire:Interface:Metadata")
+        .filter(|object| object["props"]["metadata.name"] == "default")
+        .filter_map(|object| object["metadata"].as_array())
+        .flatten()
+        .find(|entry| entry["key"] == "default.audio.source")
+        .and_then(|entry| entry["value"]["name"].as_str());
+
+    Ok(objects
+        .iter()
+        .filter(|object| object["type"] == "PipeWire:Interface:Node")
+        .filter_map(|object| {
+            let props = &object["info"]["props"];
+            let media_class = props["media.class"].as_str()?;
+            if !media_class.starts_with("Audio/Source") {
+                return None;
+            }
+            let name = props["node.name"].as_str()?;
+            Some(PipewireNode {
+                name: name.to_string(),
+                description: props["node.description"].as_str().map(str::to_string),
+                serial: props["object.serial"].as_u64(),
+                media_class: media_class.to_string(),
+                is_default: default_source == Some(name),
+            })
+        })
+        .collect())
+}
+
+/// Node matching `wanted`: a serial or exact (case-insensitive) name or
+/// description first, then a substring of either
+pub fn find_node<'a>(nodes: &'a [PipewireNode], wanted: &str) -> Option<&'a PipewireNode> {
+    let wanted = wanted.trim().to_lowercase();
+    if wanted.is_empty() {
+        return None;
+    }
+    let labels = |node: &'a PipewireNode| {
+        std::iter::once(node.name.to_lowercase())
+            .chain(node.description.as_deref().map(str::to_lowercase))
+    };
+    nodes
+        .iter()
+        .find(|node| {
+            node.serial
+                .is_some_and(|serial| serial.to_string() == wanted)
+                || labels(node).any(|label| label == wanted)
+        })
+        .or_else(|| {
+            nodes
+                .iter()
+                .find(|node| labels(node).any(|label| label.contains(&wanted)))
+        })
+}
+
+/// `node.name` of the configured capture target, if it was found at startup
+pub fn capture_target() -> Option<&'static str> {
+    CAPTURE_TARGET.get().map(String::as_str)
+}
+
+/// `PIPEWIRE_PROPS` value naming the stream and, if given, its target node
+fn stream_properties(target: Option<&str>) -> String {
+    let mut props = vec![
+        ("application.name", "sonori"),
+        ("application.id", APPLICATION_ID),
+        ("node.name", "sonori"),
+        ("node.description", "Sonori"),
+        ("media.type", "Audio"),
+        ("media.category", "Capture"),
+        ("media.role", "Communication"),
+    ];
+    if let Some(target) = target {
+        props.push(("target.object", target));
+    }
+    let props: Vec<String> = props
+        .iter()
+        .map(|(key, value)| format!("{} = \"{}\"", key, value.replace(['\\', '"'], "")))
+        .collect();
+    format!("{{ {} }}", props.join(" "))
+}
+
+/// Resolve the capture API and, on PipeWire, set the stream properties and
+/// target for the ALSA plugin in the environment. Call while the process is
+/// still single-threaded, before anything opens an input.
+pub fn configure_stream(config: &AudioCaptureConfig) -> Result<CaptureApi, String> {
+    let api = resolve_api(config.api)?;
+    let wanted = config
+        .target_object
+        .as_deref()
+        .map(str::trim)
+        .filter(|target| !target.is_empty());
+
+    if api == CaptureApi::Portaudio {
+        if let Some(wanted) = wanted {
+            tracing::warn!(
+                "target_object = '{}' is ignored: it needs the PipeWire capture API",
+                wanted
+            );
+        }
+        tracing::info!("Capture API: portaudio");
+        return Ok(api);
+    }
+
+    // The description a mixer shows is friendlier to configure, but only a
+    // node.name or serial is accepted as a target
+    let target = wanted.map(|wanted| match list_sources() {
+        Ok(nodes) => match find_node(&nodes, wanted) {
+            Some(node) => {
+                tracing::info!("Capture target: {} (matches '{}')", node.name, wanted);
+                let _ = CAPTURE_TARGET.set(node.name.clone());
+                node.name.clone()
+            }
+            None => {
+                tracing::warn!(
+                    "target_object: no PipeWire source matches '{}'; requesting it as given. \
+                     Run `sonori --list-audio-devices` to see the available nodes.",
+                    wanted
+                );
+                wanted.to_string()
+            }
+        },
+        Err(e) => {
+            tracing::warn!("{}; requesting target_object '{}' as given", e, wanted);
+            wanted.to_string()
+        }
+    });
+
+    if std::env::var_os(PROPS_ENV).is_some() {
+        tracing::info!(
+            "{} is already set; leaving the stream properties to it",
+            PROPS_ENV
+        );
+    } else {
+        std::env::set_var(PROPS_ENV, stream_properties(target.as_deref()));
+    }
+    if let Some(target) = &target {
+        std::env::set_var(NODE_ENV, target);
+    }
+    tracing::info!("Capture API: pipewire (stream node 'sonori')");
+    Ok(api)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"[
+        {"id": 40, "type": "PipeWire:Interface:Node", "info": {"props": {
+            "node.name": "alsa_input.usb-Mic", "node.description": "USB Mic",
+            "media.class": "Audio/Source", "object.serial": 61}}},
+        {"id": 41, "type": "PipeWire:Interface:Node", "info": {"props": {
+            "node.name": "echo-cancel-source", "node.description": "Echo-Cancel Source",
+            "media.class": "Audio/Source/Virtual", "object.serial": 75}}},
+        {"id": 42, "type": "PipeWire:Interface:Node", "info": {"props": {
+            "node.name": "alsa_output.pci-Speakers", "media.class": "Audio/Sink",
+            "object.serial": 62}}},
+        {"id": 43, "type": "PipeWire:Interface:Metadata", "props": {"metadata.name": "default"},
+         "metadata": [
+            {"subject": 0, "key": "default.audio.sink", "value": {"name": "alsa_output.pci-Speakers"}},
+            {"subject": 0, "key": "default.audio.source", "value": {"name": "echo-cancel-source"}}
+         ]}
+    ]"#;
+
+    #[test]
+    fn only_sources_are_listed_with_the_default_marked() {
+        let nodes = parse_dump(DUMP).unwrap();
+        let names: Vec<&str> = nodes.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, ["alsa_input.usb-Mic", "echo-cancel-source"]);
+        assert!(!nodes[0].is_default);
+        assert!(nodes[1].is_default);
+        assert_eq!(nodes[1].serial, Some(75));
+        assert!(parse_dump("not json").is_err());
+    }
+
+    #[test]
+    fn targets_match_serial_name_or_description() {
+        let nodes = parse_dump(DUMP).unwrap();
+        let found = |wanted| find_node(&nodes, wanted).map(|node| node.name.as_str());
+        assert_eq!(found("75"), Some("echo-cancel-source"));
+        assert_eq!(found("usb mic"), Some("alsa_input.usb-Mic"));
+        assert_eq!(found("echo-cancel"), Some("echo-cancel-source"));
+        assert_eq!(found("HDMI"), None);
+        assert_eq!(found("  "), None);
+    }
+
+    #[test]
+    fn stream_properties_name_the_client_and_target() {
+        let props = stream_properties(Some("echo-cancel-source"));
+        assert!(props.starts_with("{ application.name = \"sonori\""));
+        assert!(props.contains("media.category = \"Capture\""));
+        assert!(props.ends_with("target.object = \"echo-cancel-source\" }"));
+        assert!(!stream_properties(None).contains("target.object"));
+        // A quote in the target cannot end the value early
+        assert!(stream_properties(Some("a\" b")).contains("target.object = \"a b\""));
+    }
+}
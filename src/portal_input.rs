@@ -1,19 +1,123 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use anyhow::{Context, Result};
 use ashpd::desktop::remote_desktop::{DeviceType, KeyState, RemoteDesktop};
 use ashpd::desktop::screencast::{CursorMode, Screencast, SourceType};
 use ashpd::desktop::PersistMode;
 use ashpd::desktop::Session;
 use ashpd::zbus;
-use xkbcommon::xkb::keysyms;
+use xkbcommon::xkb::{self, keysyms};
 
 use crate::portal_tokens::PortalTokens;
 
+/// Offset between xkb keycodes and the evdev keycodes the portal expects
+const EVDEV_OFFSET: u32 = 8;
+
+/// evdev KEY_LEFTSHIFT, used if the keymap has no Shift_L
+const EVDEV_LEFT_SHIFT: u32 = 42;
+
+/// A key on the keymap and whether it needs Shift (level 2) to produce the keysym
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyStroke {
+    evdev_code: u32,
+    shift: bool,
+}
+
+/// Keysym to key lookup built from the default xkb keymap (XKB_DEFAULT_* or the
+/// system default layout). Only the first two shift levels are used; anything
+/// needing AltGr or a compose sequence is left to the paste path.
+struct TypingKeymap {
+    strokes: HashMap<u32, KeyStroke>,
+    shift_code: u32,
+}
+
+impl TypingKeymap {
+    fn from_system() -> Option<Self> {
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        let keymap = xkb::Keymap::new_from_names(
+            &context,
+            "",
+            "",
+            "",
+            "",
+            None,
+            xkb::KEYMAP_COMPILE_NO_FLAGS,
+        )?;
+
+        let mut strokes = HashMap::new();
+        let (min, max) = (keymap.min_keycode().raw(), keymap.max_keycode().raw());
+        // Unshifted keys first, so e.g. "1" never maps to a shifted keypad key
+        for level in 0..2 {
+            for raw in min..=max {
+                let keycode = xkb::Keycode::new(raw);
+                if raw < EVDEV_OFFSET || level >= keymap.num_levels_for_key(keycode, 0) {
+                    continue;
+                }
+                for sym in keymap.key_get_syms_by_level(keycode, 0, level) {
+                    if sym.raw() == keysyms::KEY_NoSymbol {
+                        continue;
+                    }
+                    strokes.entry(sym.raw()).or_insert(KeyStroke {
+                        evdev_code: raw - EVDEV_OFFSET,
+                        shift: level == 1,
+                    });
+                }
+            }
+        }
+
+        let shift_code = strokes
+            .get(&keysyms::KEY_Shift_L)
+            .map(|stroke| stroke.evdev_code)
+            .unwrap_or(EVDEV_LEFT_SHIFT);
+        Some(Self {
+            strokes,
+            shift_code,
+        })
+    }
+
+    fn stroke_for(&self, c: char) -> Option<KeyStroke> {
+        let keysym = match c {
+            '\n' => keysyms::KEY_Return,
+            '\t' => keysyms::KEY_Tab,
+            c if c.is_control() => return None,
+            c => xkb::utf32_to_keysym(c as u32).raw(),
+        };
+        self.strokes.get(&keysym).copied()
+    }
+}
+
+/// One step of injecting a segment in `type` mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Injection {
+    /// Characters that can be typed key by key
+    Type(String),
+    /// Characters the keymap cannot produce, pasted through the clipboard
+    Paste(String),
+}
+
+/// Split `text` into runs of typeable characters and runs that need the paste path
+pub fn plan_injection(text: &str, can_type: impl Fn(char) -> bool) -> Vec<Injection> {
+    let mut plan: Vec<Injection> = Vec::new();
+    for c in text.chars() {
+        let typed = can_type(c);
+        match plan.last_mut() {
+            Some(Injection::Type(run)) if typed => run.push(c),
+            Some(Injection::Paste(run)) if !typed => run.push(c),
+            _ if typed => plan.push(Injection::Type(c.to_string())),
+            _ => plan.push(Injection::Paste(c.to_string())),
+        }
+    }
+    plan
+}
+
 /// Manages an XDG Desktop Portal RemoteDesktop session to inject keystrokes
 pub struct PortalInput {
     _connection: zbus::Connection,
     rd: RemoteDesktop<'static>,
     rd_session: Session<'static, RemoteDesktop<'static>>,
     _screencast_active: bool,
+    typing_keymap: Option<TypingKeymap>,
 }
 
 impl PortalInput {
@@ -25,7 +129,7 @@ impl PortalInput {
         match Self::try_new_internal(connection.clone(), false).await {
             Ok(instance) => Ok(instance),
             Err(first_err) => {
-                eprintln!(
+                tracing::warn!(
                     "Portal keyboard session without screencast failed ({}), retrying with screencast",
                     first_err
                 );
@@ -47,7 +151,7 @@ impl PortalInput {
 
         if tokens_updated {
             if let Err(e) = tokens.save() {
-                eprintln!("Failed to persist portal restore tokens: {}", e);
+                tracing::warn!("Failed to persist portal restore tokens: {}", e);
             }
         }
 
@@ -56,6 +160,7 @@ impl PortalInput {
             rd,
             rd_session,
             _screencast_active: start_screencast,
+            typing_keymap: TypingKeymap::from_system(),
         })
     }
 
@@ -114,6 +219,52 @@ impl PortalInput {
         Ok((rd_session, tokens_updated))
     }
 
+    /// Whether `c` can be typed on the keymap (false for everything if no keymap loaded)
+    pub fn can_type(&self, c: char) -> bool {
+        self.typing_keymap
+            .as_ref()
+            .is_some_and(|keymap| keymap.stroke_for(c).is_some())
+    }
+
+    /// Type `text` key by key via keycodes, holding Shift for second-level keysyms.
+    /// Characters without a key are skipped; run `plan_injection` with `can_type` first.
+    pub async fn type_text(&self, text: &str, key_delay: Duration) -> Result<()> {
+        let keymap = self
+            .typing_keymap
+            .as_ref()
+            .context("No xkb keymap available for typing")?;
+
+        for c in text.chars() {
+            let Some(stroke) = keymap.stroke_for(c) else {
+                continue;
+            };
+            if stroke.shift {
+                self.notify_keycode(keymap.shift_code, KeyState::Pressed)
+                    .await?;
+            }
+            self.notify_keycode(stroke.evdev_code, KeyState::Pressed)
+                .await?;
+            self.notify_keycode(stroke.evdev_code, KeyState::Released)
+                .await?;
+            if stroke.shift {
+                self.notify_keycode(keymap.shift_code, KeyState::Released)
+                    .await?;
+            }
+            if !key_delay.is_zero() {
+                tokio::time::sleep(key_delay).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn notify_keycode(&self, evdev_code: u32, state: KeyState) -> Result<()> {
+        self.rd
+            .notify_keyboard_keycode(&self.rd_session, evdev_code as i32, state)
+            .await?;
+        Ok(())
+    }
+
     /// Send Ctrl+V via keysym to paste from clipboard
     pub async fn paste_via_ctrl_v(&self) -> Result<()> {
         // Press Control
@@ -202,3 +353,24 @@ impl PortalInput {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmappable_runs_fall_back_to_paste() {
+        let plan = plan_injection("Hi 👋 there €5", |c| c.is_ascii());
+        assert_eq!(
+            plan,
+            [
+                Injection::Type("Hi ".to_string()),
+                Injection::Paste("👋".to_string()),
+                Injection::Type(" there ".to_string()),
+                Injection::Paste("€".to_string()),
+                Injection::Type("5".to_string()),
+            ]
+        );
+        assert!(plan_injection("", |_| true).is_empty());
+    }
+}
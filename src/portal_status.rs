@@ -0,0 +1,120 @@
+//! Live state of the portal integrations, shown in the settings panel's
+//! Integration tab.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::hotkeys::HotkeyProvider;
+use crate::i18n::tr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// Turned off in the config
+    Disabled,
+    /// Waiting for the portal, possibly on its permission dialog
+    Connecting,
+    Active,
+    Failed,
+}
+
+impl SessionState {
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionState::Disabled => tr("session.disabled"),
+            SessionState::Connecting => tr("session.connecting"),
+            SessionState::Active => tr("session.active"),
+            SessionState::Failed => tr("session.failed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortalStatus {
+    /// Global shortcuts, whichever provider delivers them
+    pub shortcuts: SessionState,
+    /// Provider delivering shortcuts while `shortcuts` is active
+    pub shortcut_provider: Option<HotkeyProvider>,
+    /// Accelerators bound with that provider
+    pub accelerators: Vec<String>,
+    /// RemoteDesktop (and ScreenCast fallback) session used to paste and type
+    pub paste: SessionState,
+    /// Most recent portal error, prefixed with the portal it came from
+    pub last_error: Option<String>,
+}
+
+/// Re-init requests from the settings panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortalCommand {
+    /// Drop the shortcut session and run the provider chain again
+    RebindShortcuts,
+    /// Forget the paste session's restore tokens and create it again
+    ReconnectPaste,
+}
+
+static STATUS: Mutex<PortalStatus> = Mutex::new(PortalStatus {
+    shortcuts: SessionState::Disabled,
+    shortcut_provider: None,
+    accelerators: Vec::new(),
+    paste: SessionState::Disabled,
+    last_error: None,
+});
+
+/// Bumped on every change so the UI knows when to redraw
+static REVISION: AtomicU64 = AtomicU64::new(0);
+
+static REBIND_SHORTCUTS: Mutex<Option<UnboundedSender<()>>> = Mutex::new(None);
+static RECONNECT_PASTE: Mutex<Option<UnboundedSender<()>>> = Mutex::new(None);
+
+fn worker(command: PortalCommand) -> &'static Mutex<Option<UnboundedSender<()>>> {
+    match command {
+        PortalCommand::RebindShortcuts => &REBIND_SHORTCUTS,
+        PortalCommand::ReconnectPaste => &RECONNECT_PASTE,
+    }
+}
+
+pub fn snapshot() -> PortalStatus {
+    STATUS.lock().clone()
+}
+
+pub fn revision() -> u64 {
+    REVISION.load(Ordering::Relaxed)
+}
+
+/// Change the published status
+pub fn update(change: impl FnOnce(&mut PortalStatus)) {
+    let mut status = STATUS.lock();
+    let before = status.clone();
+    change(&mut status);
+    if *status != before {
+        REVISION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Remember a portal failure for the settings panel
+pub fn record_error(portal: &str, error: impl fmt::Display) {
+    let message = format!("{}: {}", portal, error);
+    update(|status| status.last_error = Some(message));
+}
+
+/// Register the worker that handles `command`; a later call replaces it
+pub fn listen(command: PortalCommand) -> UnboundedReceiver<()> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    *worker(command).lock() = Some(tx);
+    rx
+}
+
+/// Ask the worker behind `command` to set its session up again.
+/// Returns false when no worker runs (the integration is disabled).
+pub fn request(command: PortalCommand) -> bool {
+    let sent = worker(command)
+        .lock()
+        .as_ref()
+        .is_some_and(|tx| tx.send(()).is_ok());
+    if !sent {
+        tracing::debug!("No portal worker for {:?}", command);
+    }
+    sent
+}
@@ -0,0 +1,192 @@
+//! Spoken punctuation and formatting ("comma", "new line", "open quote").
+
+use std::collections::BTreeMap;
+
+use super::PostStage;
+
+/// Word that makes the following command be kept as text
+const ESCAPE_WORD: &str = "literal";
+
+/// Defaults for `post_process_config.dictation_commands` (English)
+pub fn default_commands() -> BTreeMap<String, String> {
+    [
+        ("comma", ","),
+        ("period", "."),
+        ("full stop", "."),
+        ("question mark", "?"),
+        ("exclamation mark", "!"),
+        ("exclamation point", "!"),
+        ("colon", ":"),
+        ("semicolon", ";"),
+        ("new line", "\n"),
+        ("new paragraph", "\n\n"),
+        ("open quote", "\""),
+        ("close quote", "\""),
+        ("open parenthesis", "("),
+        ("close parenthesis", ")"),
+    ]
+    .iter()
+    .map(|(spoken, text)| (spoken.to_string(), text.to_string()))
+    .collect()
+}
+
+pub struct DictationCommands {
+    /// Normalized command words and their replacement, longest command first
+    commands: Vec<(Vec<String>, String)>,
+}
+
+impl DictationCommands {
+    pub fn new(commands: &BTreeMap<String, String>) -> Self {
+        let mut commands: Vec<(Vec<String>, String)> = commands
+            .iter()
+            .map(|(spoken, text)| {
+                (
+                    spoken.split_whitespace().map(normalize).collect::<Vec<_>>(),
+                    text.clone(),
+                )
+            })
+            .filter(|(words, _)| !words.is_empty() && words.iter().all(|word| !word.is_empty()))
+            .collect();
+        commands.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        Self { commands }
+    }
+
+    /// Command starting at `words[0]`: its length in words and replacement
+    fn command_at(&self, words: &[String]) -> Option<(usize, &str)> {
+        self.commands
+            .iter()
+            .find(|(command, _)| words.starts_with(command))
+            .map(|(command, text)| (command.len(), text.as_str()))
+    }
+}
+
+impl PostStage for DictationCommands {
+    fn process(&self, text: &str) -> String {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let normalized: Vec<String> = words.iter().map(|word| normalize(word)).collect();
+        let mut output = Output::default();
+        let mut i = 0;
+        while i < words.len() {
+            if normalized[i] == ESCAPE_WORD {
+                if let Some((len, _)) = self.command_at(&normalized[i + 1..]) {
+                    for word in &words[i + 1..i + 1 + len] {
+                        output.push_word(word);
+                    }
+                    i += 1 + len;
+                    continue;
+                }
+            }
+            match self.command_at(&normalized[i..]) {
+                Some((len, replacement)) => {
+                    output.push_command(replacement);
+                    i += len;
+                }
+                None => {
+                    output.push_word(words[i]);
+                    i += 1;
+                }
+            }
+        }
+        output.text
+    }
+}
+
+#[derive(Default)]
+struct Output {
+    text: String,
+    /// No space before the next word (after a line break or opening bracket/quote)
+    glue_next: bool,
+    quote_open: bool,
+    /// The text ends with a word rather than a command's replacement
+    after_word: bool,
+}
+
+impl Output {
+    fn push_word(&mut self, word: &str) {
+        if !self.text.is_empty() && !self.glue_next {
+            self.text.push(' ');
+        }
+        self.text.push_str(word);
+        self.glue_next = false;
+        self.after_word = true;
+    }
+
+    fn push_command(&mut self, replacement: &str) {
+        let after_word = std::mem::replace(&mut self.after_word, false);
+        if replacement.contains('\n') {
+            let kept = self.text.trim_end_matches(' ').len();
+            self.text.truncate(kept);
+            self.text.push_str(replacement);
+            self.glue_next = true;
+            return;
+        }
+
+        let opens = match replacement {
+            "\"" => {
+                self.quote_open = !self.quote_open;
+                self.quote_open
+            }
+            _ => replacement.ends_with(['(', '[', '{']),
+        };
+        if opens {
+            self.push_word(replacement);
+            self.glue_next = true;
+            self.after_word = false;
+        } else {
+            // The model often punctuates the word before the command ("Hello, comma")
+            if after_word && replacement.starts_with(is_sentence_punctuation) {
+                let kept = self.text.trim_end_matches(is_sentence_punctuation).len();
+                self.text.truncate(kept);
+            }
+            self.text.push_str(replacement);
+            self.glue_next = false;
+        }
+    }
+}
+
+fn is_sentence_punctuation(c: char) -> bool {
+    matches!(c, ',' | '.' | ';' | ':' | '!' | '?')
+}
+
+/// Lowercase with surrounding punctuation removed, for matching command words
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(text: &str) -> String {
+        DictationCommands::new(&default_commands()).process(text)
+    }
+
+    #[test]
+    fn replaces_commands_at_start_end_and_in_a_row() {
+        assert_eq!(process("Comma hello"), ", hello");
+        assert_eq!(process("See you soon period"), "See you soon.");
+        assert_eq!(process("Hello, Comma. world Period."), "Hello, world.");
+        assert_eq!(
+            process("done period new paragraph next question mark"),
+            "done.\n\nnext?"
+        );
+        assert_eq!(process("wait comma comma"), "wait,,");
+    }
+
+    #[test]
+    fn quotes_lines_and_escapes() {
+        assert_eq!(
+            process("he said open quote hi close quote new line ok"),
+            "he said \"hi\"\nok"
+        );
+        assert_eq!(
+            process("type literal comma and literal new line"),
+            "type comma and new line"
+        );
+        assert_eq!(
+            process("a newline is not a command"),
+            "a newline is not a command"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Drops phantom segments Whisper produces from trailing silence.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use regex::Regex;
+
+use super::PostStageError;
+use crate::config::PostProcessConfig;
+
+/// Patterns for `post_process_config.hallucination_patterns`
+pub fn default_patterns() -> Vec<String> {
+    [
+        r"(?i)^(subtitles|captions|transcription) by\b",
+        r"(?i)^thanks? (you )?for watching[.!]*$",
+        r"(?i)^please subscribe\b",
+        r"(?i)^[\[(]\s*(music|silence|blank_audio|no speech)\s*[\])]$",
+    ]
+    .iter()
+    .map(|pattern| pattern.to_string())
+    .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropReason {
+    Pattern,
+    Repeat,
+}
+
+/// Segments dropped since startup, per rule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DropCounts {
+    pub pattern: u64,
+    pub repeat: u64,
+}
+
+pub struct HallucinationFilter {
+    patterns: Vec<Regex>,
+    drop_repeats: bool,
+    /// Last kept segment, normalized for comparison
+    previous: Mutex<Option<String>>,
+    dropped_pattern: AtomicU64,
+    dropped_repeat: AtomicU64,
+}
+
+impl HallucinationFilter {
+    pub fn new(patterns: &[String], drop_repeats: bool) -> Result<Self, PostStageError> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| PostStageError::InvalidPattern {
+                    pattern: pattern.clone(),
+                    message: e.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            patterns,
+            drop_repeats,
+            previous: Mutex::new(None),
+            dropped_pattern: AtomicU64::new(0),
+            dropped_repeat: AtomicU64::new(0),
+        })
+    }
+
+    pub fn from_config(config: &PostProcessConfig) -> Result<Self, PostStageError> {
+        Self::new(
+            &config.hallucination_patterns,
+            config.drop_repeated_segments,
+        )
+    }
+
+    /// Whether there is anything to check
+    pub fn is_active(&self) -> bool {
+        !self.patterns.is_empty() || self.drop_repeats
+    }
+
+    /// Check a committed segment; a kept segment becomes the previous one
+    pub fn check(&self, segment: &str) -> Option<DropReason> {
+        let text = segment.trim();
+        if text.is_empty() {
+            return None;
+        }
+        if self.patterns.iter().any(|pattern| pattern.is_match(text)) {
+            self.dropped_pattern.fetch_add(1, Ordering::Relaxed);
+            return Some(DropReason::Pattern);
+        }
+
+        let normalized = text.to_lowercase();
+        let mut previous = self.previous.lock();
+        if self.drop_repeats && previous.as_deref() == Some(normalized.as_str()) {
+            self.dropped_repeat.fetch_add(1, Ordering::Relaxed);
+            return Some(DropReason::Repeat);
+        }
+        *previous = Some(normalized);
+        None
+    }
+
+    pub fn dropped(&self) -> DropCounts {
+        DropCounts {
+            pattern: self.dropped_pattern.load(Ordering::Relaxed),
+            repeat: self.dropped_repeat.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_known_phrases_and_repeats() {
+        let filter = HallucinationFilter::new(&default_patterns(), true).unwrap();
+        assert_eq!(
+            filter.check("Subtitles by the Amara.org community"),
+            Some(DropReason::Pattern)
+        );
+        assert_eq!(filter.check("[BLANK_AUDIO]"), Some(DropReason::Pattern));
+        assert_eq!(filter.check("Thank you."), None);
+        assert_eq!(filter.check("thank you."), Some(DropReason::Repeat));
+        assert_eq!(filter.check("The subtitles by the door."), None);
+        assert_eq!(filter.check("Thank you."), None);
+        assert_eq!(
+            filter.dropped(),
+            DropCounts {
+                pattern: 2,
+                repeat: 1
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let result = HallucinationFilter::new(&["(unclosed".to_string()], false);
+        assert!(matches!(
+            result,
+            Err(PostStageError::InvalidPattern { pattern, .. }) if pattern == "(unclosed"
+        ));
+    }
+}
@@ -0,0 +1,646 @@
+//! Inverse text normalization: spoken numbers written the way they are typed.
+
+use super::PostStage;
+
+const UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const ORDINAL_UNITS: [&str; 20] = [
+    "",
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+];
+const ORDINAL_TENS: [&str; 10] = [
+    "",
+    "",
+    "twentieth",
+    "thirtieth",
+    "fortieth",
+    "fiftieth",
+    "sixtieth",
+    "seventieth",
+    "eightieth",
+    "ninetieth",
+];
+const SCALES: [(&str, u64); 3] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Whether the stage applies to `language` (a `general_config.language` value)
+pub fn supports_language(language: &str) -> bool {
+    let language = language.trim().to_lowercase();
+    language == "en"
+        || language == "english"
+        || language.starts_with("en-")
+        || language.starts_with("en_")
+}
+
+pub struct InverseTextNormalization;
+
+impl PostStage for InverseTextNormalization {
+    fn process(&self, text: &str) -> String {
+        let tokens = tokenize(text);
+        let mut output = Output::default();
+        let mut i = 0;
+        while i < tokens.len() {
+            let end = phrase_end(&tokens, i);
+            let words: Vec<&str> = tokens[i..end].iter().map(|t| t.lower.as_str()).collect();
+
+            let rewrite = date(&tokens, i, &words)
+                .or_else(|| time(&words))
+                .or_else(|| currency(&words))
+                .or_else(|| percent(&words))
+                .or_else(|| standalone(&words));
+            if let Some((text, len)) = rewrite {
+                let last = &tokens[i + len - 1];
+                let mut trail = last.trail;
+                // "p.m." already ends the abbreviation with the dot
+                if text.ends_with('.') {
+                    trail = trail.strip_prefix('.').unwrap_or(trail);
+                }
+                output.push(&format!("{}{}{}", tokens[i].lead, text, trail));
+                output.hyphen_next = last.hyphen_after;
+                i += len;
+                continue;
+            }
+
+            // A run of number words that is not one number stays as spoken
+            let run = if continues_number(words[0]) {
+                words.iter().take_while(|w| continues_number(w)).count()
+            } else {
+                1
+            };
+            for token in &tokens[i..i + run] {
+                output.push(&format!("{}{}{}", token.lead, token.core, token.trail));
+                output.hyphen_next = token.hyphen_after;
+            }
+            i += run;
+        }
+        output.text
+    }
+}
+
+struct Token<'a> {
+    lead: &'a str,
+    core: &'a str,
+    trail: &'a str,
+    lower: String,
+    /// Part of a hyphenated number ("twenty-three") followed by the next part
+    hyphen_after: bool,
+}
+
+/// Split on whitespace into words with their surrounding punctuation separated;
+/// hyphenated numbers are split into their parts
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for raw in text.split_whitespace() {
+        let Some(start) = raw.find(char::is_alphanumeric) else {
+            tokens.push(Token {
+                lead: raw,
+                core: "",
+                trail: "",
+                lower: String::new(),
+                hyphen_after: false,
+            });
+            continue;
+        };
+        let end = raw
+            .rfind(char::is_alphanumeric)
+            .map(|index| index + raw[index..].chars().next().map_or(1, char::len_utf8))
+            .unwrap_or(raw.len());
+        let (lead, core, trail) = (&raw[..start], &raw[start..end], &raw[end..]);
+
+        let parts: Vec<&str> = core.split('-').collect();
+        if parts.len() > 1
+            && parts
+                .iter()
+                .all(|part| is_number_word(&part.to_lowercase()))
+        {
+            let last = parts.len() - 1;
+            for (index, part) in parts.into_iter().enumerate() {
+                tokens.push(Token {
+                    lead: if index == 0 { lead } else { "" },
+                    core: part,
+                    trail: if index == last { trail } else { "" },
+                    lower: part.to_lowercase(),
+                    hyphen_after: index != last,
+                });
+            }
+        } else {
+            tokens.push(Token {
+                lead,
+                core,
+                trail,
+                lower: core.to_lowercase(),
+                hyphen_after: false,
+            });
+        }
+    }
+    tokens
+}
+
+/// End (exclusive) of the words from `start` that punctuation does not separate
+fn phrase_end(tokens: &[Token<'_>], start: usize) -> usize {
+    let mut end = start + 1;
+    while end < tokens.len() && tokens[end - 1].trail.is_empty() && tokens[end].lead.is_empty() {
+        end += 1;
+    }
+    end
+}
+
+#[derive(Default)]
+struct Output {
+    text: String,
+    hyphen_next: bool,
+}
+
+impl Output {
+    fn push(&mut self, piece: &str) {
+        if self.hyphen_next {
+            self.text.push('-');
+        } else if !self.text.is_empty() {
+            self.text.push(' ');
+        }
+        self.text.push_str(piece);
+    }
+}
+
+fn unit(word: &str) -> Option<u64> {
+    UNITS.iter().position(|w| *w == word).map(|v| v as u64)
+}
+
+fn tens(word: &str) -> Option<u64> {
+    TENS.iter()
+        .position(|w| !w.is_empty() && *w == word)
+        .map(|v| v as u64 * 10)
+}
+
+fn scale(word: &str) -> Option<u64> {
+    SCALES.iter().find(|(w, _)| *w == word).map(|(_, v)| *v)
+}
+
+fn ordinal_word(word: &str) -> Option<u64> {
+    if let Some(v) = ORDINAL_UNITS
+        .iter()
+        .position(|w| !w.is_empty() && *w == word)
+    {
+        return Some(v as u64);
+    }
+    ORDINAL_TENS
+        .iter()
+        .position(|w| !w.is_empty() && *w == word)
+        .map(|v| v as u64 * 10)
+}
+
+fn is_number_word(word: &str) -> bool {
+    unit(word).is_some()
+        || tens(word).is_some()
+        || scale(word).is_some()
+        || ordinal_word(word).is_some()
+        || word == "hundred"
+}
+
+/// Number words plus the "oh" of "nineteen oh five"
+fn continues_number(word: &str) -> bool {
+    is_number_word(word) || word == "oh"
+}
+
+fn digit(word: &str) -> Option<u64> {
+    match word {
+        "oh" => Some(0),
+        _ => unit(word).filter(|&u| u < 10),
+    }
+}
+
+/// 1..=99: "seven", "fifteen", "forty two"
+fn below_hundred(words: &[&str]) -> Option<(u64, usize)> {
+    let first = *words.first()?;
+    if let Some(t) = tens(first) {
+        if let Some(u) = words
+            .get(1)
+            .and_then(|w| unit(w))
+            .filter(|u| (1..10).contains(u))
+        {
+            return Some((t + u, 2));
+        }
+        return Some((t, 1));
+    }
+    unit(first).filter(|&u| u > 0).map(|u| (u, 1))
+}
+
+/// 1..=9999 without scale words: "three hundred and five", "fifteen hundred"
+fn below_thousand(words: &[&str]) -> Option<(u64, usize)> {
+    let (high, len) = below_hundred(words)?;
+    if words.get(len) != Some(&"hundred") {
+        return Some((high, len));
+    }
+    let mut value = high * 100;
+    let mut end = len + 1;
+    let rest = if words.get(end) == Some(&"and") {
+        end + 1
+    } else {
+        end
+    };
+    if let Some((low, low_len)) = below_hundred(&words[rest.min(words.len())..]) {
+        value += low;
+        end = rest + low_len;
+    }
+    Some((value, end))
+}
+
+fn cardinal(words: &[&str]) -> Option<(u64, usize)> {
+    if words.first() == Some(&"zero") {
+        return Some((0, 1));
+    }
+    let mut total = 0;
+    let mut i = 0;
+    let mut last_scale = u64::MAX;
+    while let Some((value, len)) = below_thousand(&words[i..]) {
+        match words
+            .get(i + len)
+            .and_then(|w| scale(w))
+            .filter(|&s| s < last_scale)
+        {
+            Some(s) => {
+                total += value * s;
+                i += len + 1;
+                last_scale = s;
+                // "two thousand and five"
+                if words.get(i) == Some(&"and") {
+                    if let Some((low, low_len)) = below_hundred(&words[i + 1..]) {
+                        total += low;
+                        i += 1 + low_len;
+                        break;
+                    }
+                }
+            }
+            None => {
+                total += value;
+                i += len;
+                break;
+            }
+        }
+    }
+    (i > 0).then_some((total, i))
+}
+
+/// 1st..99th: "third", "twentieth", "twenty first"
+fn ordinal(words: &[&str]) -> Option<(u64, usize)> {
+    let first = *words.first()?;
+    if let Some(t) = tens(first) {
+        let u = words.get(1).and_then(|w| ordinal_word(w));
+        if let Some(u) = u.filter(|u| (1..10).contains(u)) {
+            return Some((t + u, 2));
+        }
+    }
+    ordinal_word(first).map(|v| (v, 1))
+}
+
+/// "three point one four" -> "3.14"
+fn decimal(words: &[&str]) -> Option<(String, usize)> {
+    let (integer, len) = cardinal(words)?;
+    if words.get(len) != Some(&"point") {
+        return None;
+    }
+    let digits: String = words[len + 1..]
+        .iter()
+        .map_while(|w| digit(w))
+        .map(|d| char::from(b'0' + d as u8))
+        .collect();
+    if digits.is_empty() {
+        return None;
+    }
+    Some((format!("{}.{}", integer, digits), len + 1 + digits.len()))
+}
+
+/// "twenty twenty four", "nineteen oh five", "nineteen hundred", "two thousand ten"
+fn year(words: &[&str]) -> Option<(u64, usize)> {
+    if let Some((high, len)) = below_hundred(words).filter(|(v, _)| (10..100).contains(v)) {
+        let rest = &words[len..];
+        let low = match rest.first() {
+            Some(&"hundred") => Some((0, 1)),
+            Some(&"oh") => rest
+                .get(1)
+                .and_then(|w| unit(w))
+                .filter(|u| (1..10).contains(u))
+                .map(|u| (u, 2)),
+            _ => below_hundred(rest).filter(|(v, _)| *v >= 10),
+        };
+        if let Some((low, low_len)) = low {
+            return Some((high * 100 + low, len + low_len));
+        }
+    }
+    cardinal(words).filter(|(v, _)| (1000..3000).contains(v))
+}
+
+/// "March third", "May 5th"-style dates with an optional year after them
+fn date(tokens: &[Token<'_>], start: usize, words: &[&str]) -> Option<(String, usize)> {
+    let month = MONTHS.iter().find(|m| m.eq_ignore_ascii_case(words[0]))?;
+    // Lowercase "may" is nearly always the verb ("you may first ask")
+    if *month == "May" && !tokens[start].core.starts_with('M') {
+        return None;
+    }
+
+    let rest = &words[1..];
+    let (day, day_len, day_text) = match ordinal(rest) {
+        Some((day, len)) => (day, len, format!("{}{}", day, ordinal_suffix(day))),
+        None => {
+            let (day, len) = below_hundred(rest)?;
+            (day, len, day.to_string())
+        }
+    };
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+    let mut len = 1 + day_len;
+    let mut text = format!("{} {}", month, day_text);
+
+    // The year may follow in the same phrase or after a comma
+    let year_start = start + len;
+    let after_comma = tokens[year_start - 1].trail == ",";
+    if year_start < tokens.len() && (words.len() > len || after_comma) {
+        let end = phrase_end(tokens, year_start);
+        let year_words: Vec<&str> = tokens[year_start..end]
+            .iter()
+            .map(|t| t.lower.as_str())
+            .collect();
+        let first = year_words[0];
+        let parsed = if first.len() == 4 && first.bytes().all(|b| b.is_ascii_digit()) {
+            first.parse().ok().map(|y| (y, 1))
+        } else {
+            year(&year_words)
+        };
+        match parsed {
+            Some((year, year_len)) => {
+                text.push_str(&format!(", {}", year));
+                len += year_len;
+            }
+            // "march twenty twenty four" could be a day and a number or a year
+            None if !after_comma && continues_number(first) => return None,
+            None => {}
+        }
+    }
+    Some((text, len))
+}
+
+/// "three thirty pm" -> "3:30 p.m."; needs the a.m./p.m. to count as a time
+fn time(words: &[&str]) -> Option<(String, usize)> {
+    let (hour, mut len) = below_hundred(words).filter(|(h, _)| (1..=12).contains(h))?;
+    let mut minutes = None;
+    if words.get(len) == Some(&"oh") {
+        let m = words
+            .get(len + 1)
+            .and_then(|w| unit(w))
+            .filter(|m| (1..10).contains(m))?;
+        minutes = Some(m);
+        len += 2;
+    } else if let Some((m, l)) = below_hundred(&words[len..]).filter(|(m, _)| (10..60).contains(m))
+    {
+        minutes = Some(m);
+        len += l;
+    }
+    let meridiem = match words.get(len)?.replace('.', "").as_str() {
+        "am" => "a.m.",
+        "pm" => "p.m.",
+        _ => return None,
+    };
+    let text = match minutes {
+        Some(m) => format!("{}:{:02} {}", hour, m, meridiem),
+        None => format!("{} {}", hour, meridiem),
+    };
+    Some((text, len + 1))
+}
+
+/// "twenty three dollars and five cents" -> "$23.05"
+fn currency(words: &[&str]) -> Option<(String, usize)> {
+    let (amount, mut len) = cardinal(words)?;
+    let symbol = match *words.get(len)? {
+        "dollar" | "dollars" => "$",
+        "euro" | "euros" => "€",
+        _ => return None,
+    };
+    len += 1;
+
+    let cents_start = if words.get(len) == Some(&"and") {
+        len + 1
+    } else {
+        len
+    };
+    let mut cents = None;
+    if let Some((c, l)) = below_hundred(&words[cents_start.min(words.len())..]) {
+        if matches!(words.get(cents_start + l), Some(&("cent" | "cents"))) {
+            cents = Some(c);
+            len = cents_start + l + 1;
+        }
+    }
+
+    let text = match cents {
+        Some(c) => format!("{}{}.{:02}", symbol, group_thousands(amount), c),
+        None => format!("{}{}", symbol, group_thousands(amount)),
+    };
+    Some((text, len))
+}
+
+/// "five percent", "three point five per cent"
+fn percent(words: &[&str]) -> Option<(String, usize)> {
+    let (amount, len) =
+        decimal(words).or_else(|| cardinal(words).map(|(v, l)| (v.to_string(), l)))?;
+    match (words.get(len), words.get(len + 1)) {
+        (Some(&"percent"), _) => Some((format!("{}%", amount), len + 1)),
+        (Some(&"per"), Some(&"cent")) => Some((format!("{}%", amount), len + 2)),
+        _ => None,
+    }
+}
+
+/// Decimals, ordinals above tenth and cardinals above ten
+fn standalone(words: &[&str]) -> Option<(String, usize)> {
+    let followed_by_number = |len: usize| words.get(len).is_some_and(|w| continues_number(w));
+
+    if let Some((text, len)) = decimal(words) {
+        return (!followed_by_number(len)).then_some((text, len));
+    }
+    if let Some((value, len)) = ordinal(words) {
+        // "a twenty second delay" is twenty seconds
+        let eligible = value > 10 && words[len - 1] != "second" && !followed_by_number(len);
+        return eligible.then(|| (format!("{}{}", value, ordinal_suffix(value)), len));
+    }
+    let (value, len) = cardinal(words)?;
+    if value <= 10 || followed_by_number(len) {
+        return None;
+    }
+    let text = if value >= 10_000 {
+        group_thousands(value)
+    } else {
+        value.to_string()
+    };
+    Some((text, len))
+}
+
+fn ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// 1234567 -> "1,234,567"
+fn group_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, c) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(cases: &[(&str, &str)]) {
+        for (input, expected) in cases {
+            assert_eq!(
+                InverseTextNormalization.process(input),
+                *expected,
+                "input: {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn cardinals_and_ordinals() {
+        check(&[
+            ("I have twenty three apples", "I have 23 apples"),
+            ("I have twenty-three apples", "I have 23 apples"),
+            ("one hundred and five", "105"),
+            ("two thousand and five", "2005"),
+            ("twelve thousand five hundred people", "12,500 people"),
+            ("three million", "3,000,000"),
+            ("the twenty first century", "the 21st century"),
+            ("her thirteenth birthday", "her 13th birthday"),
+        ]);
+    }
+
+    #[test]
+    fn currency_percentages_and_decimals() {
+        check(&[
+            ("twenty three dollars and five cents", "$23.05"),
+            ("Twenty three dollars.", "$23."),
+            ("it costs one hundred dollars", "it costs $100"),
+            ("fifteen hundred euros", "€1,500"),
+            ("one million dollars", "$1,000,000"),
+            ("about five percent", "about 5%"),
+            ("twelve per cent", "12%"),
+            ("three point five percent of users", "3.5% of users"),
+            ("pi is three point one four", "pi is 3.14"),
+        ]);
+    }
+
+    #[test]
+    fn times_and_dates() {
+        check(&[
+            ("meet at three thirty pm", "meet at 3:30 p.m."),
+            ("at seven a.m. tomorrow", "at 7 a.m. tomorrow"),
+            ("at seven a.m.", "at 7 a.m."),
+            ("eleven oh five pm", "11:05 p.m."),
+            ("march third twenty twenty four", "March 3rd, 2024"),
+            (
+                "on June second, twenty twenty five, we",
+                "on June 2nd, 2025, we",
+            ),
+            ("since May fifth", "since May 5th"),
+            ("October twelve nineteen oh five", "October 12, 1905"),
+            ("December first, 2024", "December 1st, 2024"),
+        ]);
+    }
+
+    #[test]
+    fn ambiguous_phrases_stay_as_spoken() {
+        check(&[
+            ("one of the two options", "one of the two options"),
+            ("first of all", "first of all"),
+            ("wait a second", "wait a second"),
+            ("a twenty second delay", "a twenty second delay"),
+            ("twenty twenty vision", "twenty twenty vision"),
+            ("in twenty twenty four", "in twenty twenty four"),
+            ("nineteen oh five", "nineteen oh five"),
+            ("one-third of it", "one-third of it"),
+            ("you may first ask", "you may first ask"),
+            ("march forward", "march forward"),
+            ("march twenty twenty four", "march twenty twenty four"),
+            ("at three thirty", "at three thirty"),
+            ("I am here", "I am here"),
+            ("twenty, three", "20, three"),
+        ]);
+    }
+
+    #[test]
+    fn language_gate() {
+        assert!(supports_language("en"));
+        assert!(supports_language("en-US"));
+        assert!(!supports_language("de"));
+        assert!(!supports_language("auto"));
+    }
+}
@@ -0,0 +1,547 @@
+//! Post-processing of committed transcripts as an ordered pipeline of named stages.
+
+pub mod dictation;
+pub mod hallucination;
+pub mod itn;
+#[cfg(feature = "post-stage-plugins")]
+pub mod plugin;
+pub mod punctuation;
+pub mod replacements;
+
+use std::fmt;
+
+use crate::config::{AppConfig, PostProcessConfig};
+use hallucination::HallucinationFilter;
+
+/// Name of the built-in stage that strips leading dashes
+pub const LEADING_DASHES: &str = "leading_dashes";
+/// Name of the built-in stage that strips trailing dashes
+pub const TRAILING_DASHES: &str = "trailing_dashes";
+/// Name of the built-in stage that collapses and trims whitespace
+pub const WHITESPACE: &str = "whitespace";
+/// Name of the built-in stage that writes spoken numbers, dates and amounts as digits
+pub const INVERSE_TEXT_NORMALIZATION: &str = "inverse_text_normalization";
+/// Name of the built-in stage that turns spoken punctuation into characters
+pub const DICTATION_COMMANDS: &str = "dictation_commands";
+/// Name of the built-in stage that restores punctuation and capitalization
+pub const PUNCTUATION: &str = "punctuation";
+/// Name of the built-in stage that applies the replacement list and profanity masking
+pub const REPLACEMENTS: &str = "replacements";
+
+/// What is known about a committed segment besides its text
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SegmentContext {
+    /// Recording session the segment belongs to
+    pub session_id: Option<String>,
+}
+
+/// A text transform applied to every committed transcript
+pub trait PostStage: Send + Sync {
+    fn process(&self, text: &str) -> String;
+
+    /// Process a committed segment; stages that use the context override this
+    fn process_segment(&self, text: &str, _context: &SegmentContext) -> String {
+        self.process(text)
+    }
+}
+
+impl<F> PostStage for F
+where
+    F: Fn(&str) -> String + Send + Sync,
+{
+    fn process(&self, text: &str) -> String {
+        self(text)
+    }
+}
+
+/// Where a stage is inserted relative to the stages registered before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StagePosition<'a> {
+    First,
+    Last,
+    Before(&'a str),
+    After(&'a str),
+}
+
+#[derive(Debug)]
+pub enum PostStageError {
+    /// Stage names must be non-empty
+    EmptyName,
+    /// Two stages were registered under the same name
+    DuplicateName(String),
+    /// A stage was positioned relative to a stage that is not registered (yet)
+    UnknownAnchor { stage: String, anchor: String },
+    /// A plugin library could not be loaded or lacks the entry point
+    PluginLoad { path: String, message: String },
+    /// A plugin was built against a different stage ABI
+    AbiMismatch {
+        path: String,
+        expected: u32,
+        found: u32,
+    },
+    /// A `hallucination_patterns` entry is not a valid regex
+    InvalidPattern { pattern: String, message: String },
+    /// A regex `replacements` entry is not a valid regex
+    InvalidReplacement { pattern: String, message: String },
+}
+
+impl fmt::Display for PostStageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "Post-processing stage names must not be empty"),
+            Self::DuplicateName(name) => {
+                write!(f, "Post-processing stage '{}' is registered twice", name)
+            }
+            Self::UnknownAnchor { stage, anchor } => write!(
+                f,
+                "Cannot place post-processing stage '{}' next to '{}': no such stage registered before it",
+                stage, anchor
+            ),
+            Self::PluginLoad { path, message } => {
+                write!(f, "Failed to load post-processing plugin {}: {}", path, message)
+            }
+            Self::AbiMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Post-processing plugin {} uses stage ABI version {}, but this build of sonori expects version {}",
+                path, found, expected
+            ),
+            Self::InvalidPattern { pattern, message } => {
+                write!(f, "Invalid hallucination pattern '{}': {}", pattern, message)
+            }
+            Self::InvalidReplacement { pattern, message } => {
+                write!(f, "Invalid replacement pattern '{}': {}", pattern, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PostStageError {}
+
+struct Registration {
+    name: String,
+    position: Position,
+    stage: Box<dyn PostStage>,
+}
+
+// Owned copy of `StagePosition`
+enum Position {
+    First,
+    Last,
+    Before(String),
+    After(String),
+}
+
+impl From<StagePosition<'_>> for Position {
+    fn from(position: StagePosition<'_>) -> Self {
+        match position {
+            StagePosition::First => Self::First,
+            StagePosition::Last => Self::Last,
+            StagePosition::Before(anchor) => Self::Before(anchor.to_string()),
+            StagePosition::After(anchor) => Self::After(anchor.to_string()),
+        }
+    }
+}
+
+/// Collects stage registrations; ordering is resolved and checked in [`build`](Self::build)
+///
+/// Built-in stages are registered through the same API by
+/// [`PostPipeline::builder_from_config`]:
+///
+/// ```no_run
+/// use sonori::config::PostProcessConfig;
+/// use sonori::post_stages::{PostPipeline, PostStage, StagePosition};
+///
+/// struct Shout;
+///
+/// impl PostStage for Shout {
+///     fn process(&self, text: &str) -> String {
+///         text.to_uppercase()
+///     }
+/// }
+///
+/// let pipeline = PostPipeline::builder_from_config(&PostProcessConfig::default(), "en")
+///     .with_post_stage_at("shout", StagePosition::Before("whitespace"), Box::new(Shout))
+///     .build()
+///     .expect("valid stage order");
+/// assert_eq!(pipeline.process("- hello  there"), "HELLO THERE");
+/// ```
+#[derive(Default)]
+pub struct PostPipelineBuilder {
+    registrations: Vec<Registration>,
+    hallucination_filter: Option<HallucinationFilter>,
+    error: Option<PostStageError>,
+}
+
+impl PostPipelineBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage at the end of the pipeline
+    pub fn with_post_stage(self, name: &str, stage: Box<dyn PostStage>) -> Self {
+        self.with_post_stage_at(name, StagePosition::Last, stage)
+    }
+
+    /// Insert a stage at `position`; anchors refer to stages registered earlier
+    pub fn with_post_stage_at(
+        mut self,
+        name: &str,
+        position: StagePosition<'_>,
+        stage: Box<dyn PostStage>,
+    ) -> Self {
+        self.registrations.push(Registration {
+            name: name.to_string(),
+            position: position.into(),
+            stage,
+        });
+        self
+    }
+
+    /// Drop phantom segments in [`PostPipeline::process_final`] (see [`hallucination`])
+    pub fn with_hallucination_filter(mut self, filter: HallucinationFilter) -> Self {
+        self.hallucination_filter = Some(filter);
+        self
+    }
+
+    /// Load a cdylib stage and append it (see [`plugin`])
+    #[cfg(feature = "post-stage-plugins")]
+    pub fn with_plugin(mut self, path: &std::path::Path) -> Self {
+        match plugin::load(path) {
+            Ok((name, stage)) => self.with_post_stage(&name, stage),
+            Err(e) => {
+                self.error.get_or_insert(e);
+                self
+            }
+        }
+    }
+
+    pub fn build(self) -> Result<PostPipeline, PostStageError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut stages: Vec<(String, Box<dyn PostStage>)> = Vec::new();
+        for registration in self.registrations {
+            let Registration {
+                name,
+                position,
+                stage,
+            } = registration;
+            if name.trim().is_empty() {
+                return Err(PostStageError::EmptyName);
+            }
+            if stages.iter().any(|(existing, _)| *existing == name) {
+                return Err(PostStageError::DuplicateName(name));
+            }
+
+            let find = |anchor: &str| stages.iter().position(|(existing, _)| existing == anchor);
+            let index = match &position {
+                Position::First => Some(0),
+                Position::Last => Some(stages.len()),
+                Position::Before(anchor) => find(anchor),
+                Position::After(anchor) => find(anchor).map(|index| index + 1),
+            };
+            let Some(index) = index else {
+                let anchor = match position {
+                    Position::Before(anchor) | Position::After(anchor) => anchor,
+                    Position::First | Position::Last => unreachable!(),
+                };
+                return Err(PostStageError::UnknownAnchor {
+                    stage: name,
+                    anchor,
+                });
+            };
+            stages.insert(index, (name, stage));
+        }
+
+        Ok(PostPipeline {
+            stages,
+            hallucination_filter: self.hallucination_filter,
+        })
+    }
+}
+
+/// Ordered post-processing stages
+#[derive(Default)]
+pub struct PostPipeline {
+    stages: Vec<(String, Box<dyn PostStage>)>,
+    hallucination_filter: Option<HallucinationFilter>,
+}
+
+impl PostPipeline {
+    pub fn builder() -> PostPipelineBuilder {
+        PostPipelineBuilder::new()
+    }
+
+    /// A builder with the stages enabled in `[post_process_config]` already registered.
+    ///
+    /// When post-processing is disabled the builder starts empty, so only stages
+    /// registered afterwards run. `language` is the transcription language; stages
+    /// that only understand English are skipped for other languages.
+    pub fn builder_from_config(config: &PostProcessConfig, language: &str) -> PostPipelineBuilder {
+        Self::builder_with(config, language, None)
+    }
+
+    /// Like [`builder_from_config`](Self::builder_from_config), plus the
+    /// `punctuation` stage when `punctuation_mode` is set and the backend does
+    /// not punctuate its own output
+    pub fn builder_from_app_config(config: &AppConfig) -> PostPipelineBuilder {
+        Self::builder_with(
+            &config.post_process_config,
+            &config.general_config.language,
+            punctuation::Punctuation::from_config(config),
+        )
+    }
+
+    fn builder_with(
+        config: &PostProcessConfig,
+        language: &str,
+        punctuation: Option<punctuation::Punctuation>,
+    ) -> PostPipelineBuilder {
+        let mut builder = PostPipelineBuilder::new();
+        if !config.enabled {
+            return builder;
+        }
+        if config.remove_leading_dashes {
+            builder = builder.with_post_stage(LEADING_DASHES, Box::new(strip_leading_dashes));
+        }
+        if config.remove_trailing_dashes {
+            builder = builder.with_post_stage(TRAILING_DASHES, Box::new(strip_trailing_dashes));
+        }
+        if config.normalize_whitespace {
+            builder = builder.with_post_stage(WHITESPACE, Box::new(normalize_whitespace));
+        }
+        // Before `dictation_commands`, so "three point five" becomes 3.5 before
+        // spoken punctuation is inserted
+        if config.inverse_text_normalization {
+            if itn::supports_language(language) {
+                builder = builder.with_post_stage(
+                    INVERSE_TEXT_NORMALIZATION,
+                    Box::new(itn::InverseTextNormalization),
+                );
+            } else {
+                tracing::info!(
+                    "Inverse text normalization is English-only; skipped for language {:?}",
+                    language
+                );
+            }
+        }
+        // After `whitespace`, which would flatten the line breaks it inserts
+        if config.dictation_commands_enabled {
+            builder = builder.with_post_stage(
+                DICTATION_COMMANDS,
+                Box::new(dictation::DictationCommands::new(
+                    &config.dictation_commands,
+                )),
+            );
+        }
+        // After `dictation_commands`, so spoken punctuation is kept and followed
+        // by a capital; before `replacements`, so replaced casing wins
+        if let Some(stage) = punctuation {
+            builder = builder.with_post_stage(PUNCTUATION, Box::new(stage));
+        }
+        // Last, so it sees the text as it will be pasted
+        match replacements::Replacements::new(&config.replacements, config.mask_profanity) {
+            Ok(stage) if stage.is_active() => {
+                builder = builder.with_post_stage(REPLACEMENTS, Box::new(stage));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                builder.error.get_or_insert(e);
+            }
+        }
+        match HallucinationFilter::from_config(config) {
+            Ok(filter) if filter.is_active() => {
+                builder = builder.with_hallucination_filter(filter);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                builder.error.get_or_insert(e);
+            }
+        }
+
+        #[cfg(feature = "post-stage-plugins")]
+        for path in &config.plugins {
+            builder = builder.with_plugin(std::path::Path::new(path));
+        }
+        #[cfg(not(feature = "post-stage-plugins"))]
+        if !config.plugins.is_empty() {
+            tracing::warn!(
+                "Ignoring {} post-processing plugin(s): sonori was built without the post-stage-plugins feature",
+                config.plugins.len()
+            );
+        }
+
+        builder
+    }
+
+    pub fn from_config(config: &PostProcessConfig, language: &str) -> Result<Self, PostStageError> {
+        Self::builder_from_config(config, language).build()
+    }
+
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, PostStageError> {
+        Self::builder_from_app_config(config).build()
+    }
+
+    /// Stage names in execution order
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub fn process(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (_, stage) in &self.stages {
+            text = stage.process(&text);
+        }
+        text
+    }
+
+    /// Process a committed segment; `None` when the hallucination filter drops it.
+    ///
+    /// Only final segments go through here, so live previews never count as the
+    /// previous segment for repeat detection, nor towards the replacement count
+    /// in the statistics.
+    pub fn process_final(&self, text: &str, context: &SegmentContext) -> Option<String> {
+        let _ = replacements::take_applied();
+        let mut text = text.to_string();
+        for (_, stage) in &self.stages {
+            text = stage.process_segment(&text, context);
+        }
+        let applied = replacements::take_applied();
+        let kept = |text: String| {
+            if applied > 0 {
+                crate::transcription_stats::record_replacements(applied);
+            }
+            Some(text)
+        };
+        let Some(filter) = &self.hallucination_filter else {
+            return kept(text);
+        };
+        let Some(reason) = filter.check(&text) else {
+            return kept(text);
+        };
+        let dropped = filter.dropped();
+        tracing::info!(
+            "Dropped likely hallucination ({:?}): {:?} [dropped so far: {} by pattern, {} repeated]",
+            reason, text, dropped.pattern, dropped.repeat
+        );
+        None
+    }
+}
+
+fn strip_leading_dashes(text: &str) -> String {
+    text.trim_start()
+        .trim_start_matches('-')
+        .trim_start()
+        .to_string()
+}
+
+fn strip_trailing_dashes(text: &str) -> String {
+    text.trim_end().trim_end_matches('-').trim_end().to_string()
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suffix(tag: &'static str) -> Box<dyn PostStage> {
+        Box::new(move |text: &str| format!("{}{}", text, tag))
+    }
+
+    #[test]
+    fn builtins_run_in_registration_order() {
+        let pipeline = PostPipeline::from_config(&PostProcessConfig::default(), "en").unwrap();
+        assert_eq!(
+            pipeline.stage_names(),
+            vec![LEADING_DASHES, TRAILING_DASHES, WHITESPACE]
+        );
+        assert_eq!(pipeline.process(" - hello   world -- "), "hello world");
+    }
+
+    #[test]
+    fn number_normalization_runs_before_dictation_commands_in_english() {
+        let config = PostProcessConfig {
+            inverse_text_normalization: true,
+            dictation_commands_enabled: true,
+            ..PostProcessConfig::default()
+        };
+        let pipeline = PostPipeline::from_config(&config, "en").unwrap();
+        assert_eq!(
+            pipeline.stage_names(),
+            vec![
+                LEADING_DASHES,
+                TRAILING_DASHES,
+                WHITESPACE,
+                INVERSE_TEXT_NORMALIZATION,
+                DICTATION_COMMANDS
+            ]
+        );
+        assert_eq!(
+            pipeline.process("it grew three point five percent period"),
+            "it grew 3.5%."
+        );
+
+        let german = PostPipeline::from_config(&config, "de").unwrap();
+        assert!(!german.stage_names().contains(&INVERSE_TEXT_NORMALIZATION));
+    }
+
+    #[test]
+    fn punctuation_runs_after_dictation_commands_for_moonshine() {
+        let mut config = AppConfig::default();
+        config.post_process_config.dictation_commands_enabled = true;
+        config.post_process_config.punctuation_mode = crate::config::PunctuationMode::Rules;
+        let whisper = PostPipeline::from_app_config(&config).unwrap();
+        assert!(!whisper.stage_names().contains(&PUNCTUATION));
+
+        config.backend_config.backend = speechcore::BackendType::Moonshine;
+        let pipeline = PostPipeline::from_app_config(&config).unwrap();
+        assert_eq!(pipeline.stage_names().last(), Some(&PUNCTUATION));
+        assert_eq!(
+            pipeline.process_final("done period what now", &SegmentContext::default()),
+            Some("Done. What now?".to_string())
+        );
+    }
+
+    #[test]
+    fn inserts_relative_to_named_stages() {
+        let pipeline = PostPipeline::builder()
+            .with_post_stage("a", suffix("a"))
+            .with_post_stage("c", suffix("c"))
+            .with_post_stage_at("b", StagePosition::After("a"), suffix("b"))
+            .with_post_stage_at("start", StagePosition::Before("a"), suffix("0"))
+            .with_post_stage_at("first", StagePosition::First, suffix("!"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            pipeline.stage_names(),
+            vec!["first", "start", "a", "b", "c"]
+        );
+        assert_eq!(pipeline.process(""), "!0abc");
+    }
+
+    #[test]
+    fn reports_ordering_conflicts() {
+        let duplicate = PostPipeline::builder()
+            .with_post_stage("a", suffix("a"))
+            .with_post_stage("a", suffix("a"))
+            .build();
+        assert!(matches!(duplicate, Err(PostStageError::DuplicateName(name)) if name == "a"));
+
+        let unknown = PostPipeline::builder()
+            .with_post_stage_at("b", StagePosition::After("later"), suffix("b"))
+            .with_post_stage("later", suffix("l"))
+            .build();
+        assert!(matches!(
+            unknown,
+            Err(PostStageError::UnknownAnchor { stage, anchor }) if stage == "b" && anchor == "later"
+        ));
+    }
+}
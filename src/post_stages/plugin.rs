@@ -0,0 +1,145 @@
+//! Experimental loader for post-processing stages compiled as cdylibs.
+
+use std::ffi::{c_char, CStr};
+use std::path::Path;
+
+use libloading::{Library, Symbol};
+
+use super::{PostStage, PostStageError};
+
+/// Stage ABI version implemented by this build
+pub const POST_STAGE_ABI_VERSION: u32 = 1;
+
+const ENTRY_POINT: &[u8] = b"sonori_post_stage\0";
+
+/// Descriptor returned by a plugin's `sonori_post_stage` entry point
+///
+/// ```c
+/// typedef struct {
+///     uint32_t abi_version;          /* must equal SONORI_POST_STAGE_ABI_VERSION (1) */
+///     const char *name;              /* NUL-terminated UTF-8, unique per pipeline */
+///     const char *version;           /* NUL-terminated UTF-8, informational */
+///     uint8_t *(*process)(const uint8_t *input, size_t input_len, size_t *output_len);
+///     void (*free_output)(uint8_t *output, size_t output_len);
+/// } SonoriPostStage;
+///
+/// const SonoriPostStage *sonori_post_stage(void);
+/// ```
+///
+/// `process` receives UTF-8 text (not NUL-terminated) and returns a buffer of
+/// UTF-8 text allocated by the plugin, which sonori hands back to `free_output`.
+/// Returning NULL or invalid UTF-8 leaves the text unchanged.
+#[repr(C)]
+pub struct SonoriPostStage {
+    pub abi_version: u32,
+    pub name: *const c_char,
+    pub version: *const c_char,
+    pub process:
+        unsafe extern "C" fn(input: *const u8, input_len: usize, output_len: *mut usize) -> *mut u8,
+    pub free_output: unsafe extern "C" fn(output: *mut u8, output_len: usize),
+}
+
+struct PluginStage {
+    name: String,
+    descriptor: *const SonoriPostStage,
+    // Keeps the code behind `descriptor` mapped; dropped last
+    _library: Library,
+}
+
+// The descriptor is immutable static data; plugins must make `process` thread-safe
+unsafe impl Send for PluginStage {}
+unsafe impl Sync for PluginStage {}
+
+impl PostStage for PluginStage {
+    fn process(&self, text: &str) -> String {
+        let mut output_len = 0usize;
+        // SAFETY: the descriptor was validated in `load` and the library is still loaded
+        unsafe {
+            let descriptor = &*self.descriptor;
+            let output = (descriptor.process)(text.as_ptr(), text.len(), &mut output_len);
+            if output.is_null() {
+                tracing::warn!(
+                    "Post-processing plugin '{}' failed; text left unchanged",
+                    self.name
+                );
+                return text.to_string();
+            }
+            let bytes = std::slice::from_raw_parts(output, output_len);
+            let result = match std::str::from_utf8(bytes) {
+                Ok(processed) => processed.to_string(),
+                Err(_) => {
+                    tracing::warn!(
+                        "Post-processing plugin '{}' returned invalid UTF-8; text left unchanged",
+                        self.name
+                    );
+                    text.to_string()
+                }
+            };
+            (descriptor.free_output)(output, output_len);
+            result
+        }
+    }
+}
+
+/// Load a plugin library and return its stage name and stage
+pub fn load(path: &Path) -> Result<(String, Box<dyn PostStage>), PostStageError> {
+    let display = path.display().to_string();
+    let load_error = |message: String| PostStageError::PluginLoad {
+        path: display.clone(),
+        message,
+    };
+
+    // SAFETY: loading a library runs its initializers; plugins are trusted code
+    // the user configured explicitly
+    let library = unsafe { Library::new(path) }.map_err(|e| load_error(e.to_string()))?;
+    let descriptor = unsafe {
+        let entry: Symbol<unsafe extern "C" fn() -> *const SonoriPostStage> = library
+            .get(ENTRY_POINT)
+            .map_err(|e| load_error(format!("missing sonori_post_stage entry point ({})", e)))?;
+        entry()
+    };
+    if descriptor.is_null() {
+        return Err(load_error("sonori_post_stage returned NULL".to_string()));
+    }
+
+    // Only the leading version field is common to all ABI versions
+    let found = unsafe { *(descriptor as *const u32) };
+    if found != POST_STAGE_ABI_VERSION {
+        return Err(PostStageError::AbiMismatch {
+            path: display,
+            expected: POST_STAGE_ABI_VERSION,
+            found,
+        });
+    }
+
+    let (name, version) = unsafe {
+        let descriptor = &*descriptor;
+        (
+            c_string(descriptor.name)
+                .ok_or_else(|| load_error("invalid stage name".to_string()))?,
+            c_string(descriptor.version).unwrap_or_default(),
+        )
+    };
+    tracing::info!(
+        "Loaded post-processing plugin '{}' {} from {}",
+        name,
+        version,
+        display
+    );
+
+    Ok((
+        name.clone(),
+        Box::new(PluginStage {
+            name,
+            descriptor,
+            _library: library,
+        }),
+    ))
+}
+
+unsafe fn c_string(ptr: *const c_char) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+    CStr::from_ptr(ptr).to_str().ok().map(str::to_string)
+}
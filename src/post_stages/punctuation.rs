@@ -0,0 +1,279 @@
+//! Sentence punctuation and capitalization for backends that emit neither
+//! (`post_process_config.punctuation_mode`).
+
+use parking_lot::Mutex;
+use regex::{NoExpand, Regex, RegexBuilder};
+
+use super::{PostStage, SegmentContext};
+use crate::config::{AppConfig, PunctuationMode};
+
+/// Words that open an English question
+const QUESTION_WORDS: &[&str] = &[
+    "who",
+    "what",
+    "when",
+    "where",
+    "why",
+    "how",
+    "which",
+    "whose",
+    "is",
+    "are",
+    "am",
+    "was",
+    "were",
+    "do",
+    "does",
+    "did",
+    "can",
+    "could",
+    "would",
+    "will",
+    "should",
+    "shall",
+    "have",
+    "has",
+    "isn't",
+    "aren't",
+    "don't",
+    "doesn't",
+    "didn't",
+    "can't",
+    "won't",
+    "wouldn't",
+    "shouldn't",
+    "couldn't",
+];
+
+#[derive(Default)]
+struct SentenceState {
+    session_id: Option<String>,
+    /// The last committed segment left its sentence open
+    open: bool,
+}
+
+pub struct Punctuation {
+    english: bool,
+    /// Capitalized vocabulary terms, matched as whole words ignoring case
+    proper_nouns: Vec<(Regex, String)>,
+    pronoun_i: Regex,
+    state: Mutex<SentenceState>,
+}
+
+impl Punctuation {
+    pub fn new(language: &str, vocabulary: &[String]) -> Self {
+        let proper_nouns = vocabulary
+            .iter()
+            .map(|term| term.trim())
+            .filter(|term| term.chars().any(char::is_uppercase))
+            .filter_map(|term| {
+                let regex = RegexBuilder::new(&format!(r"\b{}\b", regex::escape(term)))
+                    .case_insensitive(true)
+                    .build()
+                    .ok()?;
+                Some((regex, term.to_string()))
+            })
+            .collect();
+        Self {
+            english: super::itn::supports_language(language),
+            proper_nouns,
+            pronoun_i: Regex::new(r"\bi\b(\.)?").expect("valid pronoun pattern"),
+            state: Mutex::new(SentenceState::default()),
+        }
+    }
+
+    /// The stage for `config`, or `None` when it is off or the backend punctuates
+    /// its own output
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let backend = config.backend_config.backend;
+        match config.post_process_config.punctuation_mode {
+            PunctuationMode::Off => return None,
+            mode if crate::model_catalog::emits_punctuation(backend) => {
+                tracing::info!(
+                    "punctuation_mode = \"{}\" skipped: {:?} punctuates its own output",
+                    mode,
+                    backend
+                );
+                return None;
+            }
+            PunctuationMode::Rules => {}
+        }
+        Some(Self::new(
+            &config.general_config.language,
+            &config.common_transcription_options.vocabulary,
+        ))
+    }
+
+    /// Punctuate one segment, closing its sentence if `close` is set; returns
+    /// the text and whether its sentence stays open
+    fn punctuate(&self, text: &str, continues: bool, close: bool) -> (String, bool) {
+        let text = text.trim();
+        if !text.chars().any(char::is_alphanumeric) {
+            return (text.to_string(), continues);
+        }
+
+        let mut text = capitalize_sentences(text, !continues);
+        if self.english {
+            text = self
+                .pronoun_i
+                .replace_all(&text, |caps: &regex::Captures| match caps.get(1) {
+                    // "i.e." is not the pronoun
+                    Some(_) => caps[0].to_string(),
+                    None => "I".to_string(),
+                })
+                .into_owned();
+        }
+        for (regex, term) in &self.proper_nouns {
+            text = regex.replace_all(&text, NoExpand(term)).into_owned();
+        }
+
+        if let Some(last) = text.chars().last().filter(|c| ".?!…:;,".contains(*c)) {
+            let open = !".?!…".contains(last);
+            return (text, open);
+        }
+        if !close {
+            return (text, true);
+        }
+        text.push(if self.english && is_question(&text) {
+            '?'
+        } else {
+            '.'
+        });
+        (text, false)
+    }
+}
+
+impl PostStage for Punctuation {
+    /// Live previews: cased like the final text, but not closed yet
+    fn process(&self, text: &str) -> String {
+        let continues = self.state.lock().open;
+        self.punctuate(text, continues, false).0
+    }
+
+    /// Committed segments end where the VAD heard a pause, so each one closes
+    /// its sentence
+    fn process_segment(&self, text: &str, context: &SegmentContext) -> String {
+        let mut state = self.state.lock();
+        if state.session_id != context.session_id {
+            // A new recording starts a new sentence
+            *state = SentenceState {
+                session_id: context.session_id.clone(),
+                open: false,
+            };
+        }
+        let (text, open) = self.punctuate(text, state.open, true);
+        state.open = open;
+        text
+    }
+}
+
+/// Uppercase the first letter of the text (if `first` is set) and of every
+/// sentence after `.`, `?` or `!`; abbreviations like "e.g." end no sentence
+fn capitalize_sentences(text: &str, first: bool) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut capitalize = first;
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_whitespace() {
+            let abbreviation = word
+                .strip_suffix('.')
+                .is_some_and(|stem| stem.contains('.'));
+            capitalize |= word.ends_with(['.', '?', '!']) && !abbreviation;
+            word.clear();
+            output.push(c);
+            continue;
+        }
+        word.push(c);
+        if capitalize && c.is_alphanumeric() {
+            output.extend(c.to_uppercase());
+            capitalize = false;
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// Whether the last sentence of `text` opens with a question word
+fn is_question(text: &str) -> bool {
+    let Some(sentence) = text
+        .split(['.', '?', '!', '\n'])
+        .rfind(|sentence| !sentence.trim().is_empty())
+    else {
+        return false;
+    };
+    sentence
+        .split_whitespace()
+        .next()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .to_lowercase()
+        })
+        .is_some_and(|word| QUESTION_WORDS.contains(&word.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage() -> Punctuation {
+        Punctuation::new("en", &["Kubernetes".to_string(), "wgpu".to_string()])
+    }
+
+    fn segment(session_id: &str) -> SegmentContext {
+        SegmentContext {
+            session_id: Some(session_id.to_string()),
+        }
+    }
+
+    #[test]
+    fn segments_close_their_sentences() {
+        let stage = stage();
+        assert_eq!(
+            stage.process_segment("so i deployed it on kubernetes", &segment("s1")),
+            "So I deployed it on Kubernetes."
+        );
+        assert_eq!(
+            stage.process_segment("what did you try", &segment("s1")),
+            "What did you try?"
+        );
+        // A segment left open by its own punctuation continues in lowercase
+        assert_eq!(
+            stage.process_segment("the wgpu backend,", &segment("s1")),
+            "The wgpu backend,"
+        );
+        assert_eq!(
+            stage.process_segment("crashed twice", &segment("s1")),
+            "crashed twice."
+        );
+    }
+
+    #[test]
+    fn existing_punctuation_is_kept_and_capitalized_after() {
+        let stage = stage();
+        assert_eq!(
+            stage.process_segment("done. next one, i.e. the last", &segment("s1")),
+            "Done. Next one, i.e. the last."
+        );
+        assert_eq!(
+            stage.process_segment("is it ready?", &segment("s1")),
+            "Is it ready?"
+        );
+        // Live previews are not closed
+        assert_eq!(stage.process("left open"), "Left open");
+        // A new session starts a new sentence
+        stage.process_segment("and then,", &segment("s1"));
+        assert_eq!(stage.process_segment("hello", &segment("s2")), "Hello.");
+    }
+
+    #[test]
+    fn only_unpunctuated_backends_get_the_stage() {
+        let mut config = AppConfig::default();
+        config.post_process_config.punctuation_mode = PunctuationMode::Rules;
+        assert!(Punctuation::from_config(&config).is_none());
+        config.backend_config.backend = speechcore::BackendType::Moonshine;
+        assert!(Punctuation::from_config(&config).is_some());
+        config.post_process_config.punctuation_mode = PunctuationMode::Off;
+        assert!(Punctuation::from_config(&config).is_none());
+    }
+}
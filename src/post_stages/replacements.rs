@@ -0,0 +1,275 @@
+//! Word replacement list and profanity masking.
+
+use std::cell::Cell;
+use std::cmp::Reverse;
+
+use regex::{Regex, RegexBuilder};
+
+use super::{PostStage, PostStageError};
+use crate::config::Replacement;
+
+/// Swear words masked by `mask_profanity`, as regex fragments
+const PROFANITY: &[&str] = &[
+    r"\w*fuck\w*",
+    r"\w*shit\w*",
+    r"bitch\w*",
+    r"cunts?",
+    r"assholes?",
+    r"bastards?",
+    r"piss(ed|es|ing)?",
+    r"goddamn\w*",
+    r"damn(ed|it)?",
+];
+
+thread_local! {
+    /// Replacements applied on this thread since the last `take_applied`
+    static APPLIED: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Replacements applied on this thread since the last call.
+///
+/// Stages run synchronously, so [`PostPipeline::process_final`] can read this
+/// after processing a committed segment; live previews are never counted.
+///
+/// [`PostPipeline::process_final`]: super::PostPipeline::process_final
+pub fn take_applied() -> u64 {
+    APPLIED.with(|applied| applied.replace(0))
+}
+
+enum Action {
+    Replace { text: String, expand: bool },
+    Mask,
+}
+
+struct Rule {
+    regex: Regex,
+    action: Action,
+}
+
+pub struct Replacements {
+    rules: Vec<Rule>,
+}
+
+impl Replacements {
+    pub fn new(entries: &[Replacement], mask_profanity: bool) -> Result<Self, PostStageError> {
+        let mut rules = Vec::new();
+        for entry in entries {
+            if entry.pattern.trim().is_empty() {
+                continue;
+            }
+            let source = if entry.is_regex {
+                entry.pattern.clone()
+            } else {
+                literal_pattern(entry.pattern.trim())
+            };
+            rules.push(Rule {
+                regex: compile(&source, &entry.pattern)?,
+                action: Action::Replace {
+                    text: entry.replacement.clone(),
+                    expand: entry.is_regex,
+                },
+            });
+        }
+        if mask_profanity {
+            let source = format!(r"\b(?:{})\b", PROFANITY.join("|"));
+            rules.push(Rule {
+                regex: compile(&source, "built-in profanity list")?,
+                action: Action::Mask,
+            });
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether there is anything to replace
+    pub fn is_active(&self) -> bool {
+        !self.rules.is_empty()
+    }
+}
+
+impl PostStage for Replacements {
+    fn process(&self, text: &str) -> String {
+        let mut matches: Vec<(usize, usize, usize)> = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            for found in rule.regex.find_iter(text) {
+                if !found.is_empty() {
+                    matches.push((found.start(), found.end(), index));
+                }
+            }
+        }
+        if matches.is_empty() {
+            return text.to_string();
+        }
+        matches.sort_by_key(|&(start, end, index)| (start, Reverse(end), index));
+
+        let mut output = String::with_capacity(text.len());
+        let mut position = 0;
+        let mut applied = 0;
+        for (start, end, index) in matches {
+            if start < position {
+                continue;
+            }
+            let rule = &self.rules[index];
+            let matched = &text[start..end];
+            let replacement = match &rule.action {
+                Action::Mask => "*".repeat(matched.chars().count()),
+                Action::Replace { text: with, expand } => {
+                    let mut expanded = String::new();
+                    match rule.regex.captures_at(text, start).filter(|_| *expand) {
+                        Some(captures) => captures.expand(with, &mut expanded),
+                        None => expanded.push_str(with),
+                    }
+                    match_case(&expanded, matched)
+                }
+            };
+
+            output.push_str(&text[position..start]);
+            // Removing a word must not leave a double space or a space before punctuation
+            let rest = &text[end..];
+            if replacement.is_empty()
+                && output.ends_with(' ')
+                && (rest.is_empty() || rest.starts_with(|c: char| !c.is_alphanumeric()))
+            {
+                output.pop();
+            }
+            output.push_str(&replacement);
+            position = end;
+            applied += 1;
+        }
+        output.push_str(&text[position..]);
+
+        APPLIED.with(|count| count.set(count.get() + applied));
+        output
+    }
+}
+
+fn compile(source: &str, pattern: &str) -> Result<Regex, PostStageError> {
+    RegexBuilder::new(source)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| PostStageError::InvalidReplacement {
+            pattern: pattern.to_string(),
+            message: e.to_string(),
+        })
+}
+
+/// Regex for a literal word or phrase; word boundaries only where the pattern
+/// starts or ends with a word character, so "c++" still matches
+fn literal_pattern(pattern: &str) -> String {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    format!(
+        "{}{}{}",
+        if is_word(pattern.chars().next()) {
+            r"\b"
+        } else {
+            ""
+        },
+        regex::escape(pattern),
+        if is_word(pattern.chars().last()) {
+            r"\b"
+        } else {
+            ""
+        },
+    )
+}
+
+/// Give a lowercase replacement the capitalization of the text it replaces;
+/// replacements with capitals of their own are kept as written
+fn match_case(replacement: &str, matched: &str) -> String {
+    if replacement.chars().any(char::is_uppercase) {
+        return replacement.to_string();
+    }
+    let letters: Vec<char> = matched.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()) {
+        return replacement.to_uppercase();
+    }
+    if letters.first().is_some_and(|c| c.is_uppercase()) {
+        let mut chars = replacement.chars();
+        if let Some(first) = chars.next() {
+            return first.to_uppercase().chain(chars).collect();
+        }
+    }
+    replacement.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pattern: &str, replacement: &str, is_regex: bool) -> Replacement {
+        Replacement {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            is_regex,
+        }
+    }
+
+    #[test]
+    fn matches_words_ignoring_case_and_keeps_capitalization() {
+        let stage = Replacements::new(&[entry("colour", "color", false)], false).unwrap();
+        assert_eq!(
+            stage.process("Colour me, COLOUR you, colourful colour."),
+            "Color me, COLOR you, colourful color."
+        );
+        assert_eq!(take_applied(), 3);
+
+        let stage = Replacements::new(&[entry("sonori", "Sonori", false)], false).unwrap();
+        assert_eq!(stage.process("SONORI and sonori"), "Sonori and Sonori");
+    }
+
+    #[test]
+    fn overlapping_patterns_take_the_earliest_then_longest_match() {
+        let stage = Replacements::new(
+            &[
+                entry("new", "old", false),
+                entry("new york", "NYC", false),
+                entry("york city", "nowhere", false),
+            ],
+            false,
+        )
+        .unwrap();
+        assert_eq!(stage.process("new york city is new"), "NYC city is old");
+
+        // Same span: the entry listed first wins, and output is never re-matched
+        let stage = Replacements::new(
+            &[entry("cat", "dog", false), entry("c.t", "bird", true)],
+            false,
+        )
+        .unwrap();
+        assert_eq!(stage.process("Cat cut"), "Dog bird");
+        let _ = take_applied();
+    }
+
+    #[test]
+    fn regex_captures_removal_and_masking() {
+        let stage = Replacements::new(
+            &[
+                entry(r"(\d+) ?percent", "$1%", true),
+                entry("um", "", false),
+            ],
+            true,
+        )
+        .unwrap();
+        assert_eq!(stage.process("Um, it is 5 percent"), ", it is 5%");
+        assert_eq!(stage.process("so um, that is um fine"), "so, that is fine");
+        assert_eq!(
+            stage.process("What the fuck, this is Bullshit"),
+            "What the ****, this is ********"
+        );
+        assert_eq!(
+            stage.process("a classic grass pass"),
+            "a classic grass pass"
+        );
+        let _ = take_applied();
+    }
+
+    #[test]
+    fn rejects_invalid_regex() {
+        let result = Replacements::new(&[entry("(unclosed", "", true)], false);
+        assert!(matches!(
+            result,
+            Err(PostStageError::InvalidReplacement { pattern, .. }) if pattern == "(unclosed"
+        ));
+        // Literal patterns are escaped
+        assert!(Replacements::new(&[entry("(unclosed", "", false)], false).is_ok());
+    }
+}
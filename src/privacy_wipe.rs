@@ -0,0 +1,157 @@
+//! Privacy wipe: removes every trace of the current session in one action.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+
+type WipeFn = Box<dyn Fn() + Send + Sync>;
+
+/// Ordered set of wipe sinks plus the generation used to invalidate queued items
+pub struct WipeRegistry {
+    sinks: Mutex<Vec<(&'static str, WipeFn)>>,
+    generation: AtomicU64,
+    discard_pending: AtomicBool,
+}
+
+impl Default for WipeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WipeRegistry {
+    pub const fn new() -> Self {
+        Self {
+            sinks: Mutex::new(Vec::new()),
+            generation: AtomicU64::new(0),
+            discard_pending: AtomicBool::new(false),
+        }
+    }
+
+    /// Register a sink; sinks run in registration order
+    pub fn register(&self, name: &'static str, sink: impl Fn() + Send + Sync + 'static) {
+        self.sinks.lock().push((name, Box::new(sink)));
+    }
+
+    /// Names of the registered sinks in wipe order
+    pub fn sink_names(&self) -> Vec<&'static str> {
+        self.sinks.lock().iter().map(|(name, _)| *name).collect()
+    }
+
+    /// Current wipe generation; items stamped with an older one are stale
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Tag an item for a queue so its consumer can drop it after a wipe
+    pub fn stamp<T>(&self, item: T) -> Stamped<T> {
+        Stamped {
+            generation: self.generation(),
+            item,
+        }
+    }
+
+    /// Whether `stamped` was queued before the most recent wipe
+    pub fn is_stale<T>(&self, stamped: &Stamped<T>) -> bool {
+        stamped.generation != self.generation()
+    }
+
+    /// Transcripts still in flight for the wiped session should be discarded.
+    ///
+    /// Set by a wipe and cleared by `resume` once a new recording starts.
+    pub fn discarding(&self) -> bool {
+        self.discard_pending.load(Ordering::SeqCst)
+    }
+
+    pub fn resume(&self) {
+        self.discard_pending.store(false, Ordering::SeqCst);
+    }
+
+    /// Invalidate queued items and run every sink; returns the number of sinks run
+    pub fn wipe(&self) -> usize {
+        // Bump first so consumers drop items even while sinks are still running
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.discard_pending.store(true, Ordering::SeqCst);
+        let sinks = self.sinks.lock();
+        for (_, sink) in sinks.iter() {
+            sink();
+        }
+        sinks.len()
+    }
+}
+
+/// A queued item tagged with the wipe generation it was produced in
+#[derive(Debug, Clone)]
+pub struct Stamped<T> {
+    generation: u64,
+    pub item: T,
+}
+
+static REGISTRY: WipeRegistry = WipeRegistry::new();
+
+/// The process-wide registry used by the tray, IPC, shortcut and overlay entry points
+pub fn registry() -> &'static WipeRegistry {
+    &REGISTRY
+}
+
+/// Wipe the current session everywhere and tell the user
+pub fn wipe() -> usize {
+    let count = REGISTRY.wipe();
+    tracing::info!("Privacy wipe: cleared {} sinks", count);
+    crate::toast::show(crate::i18n::tr("toast.session_wiped"));
+    count
+}
+
+/// Clear the Wayland clipboard in the background
+pub fn spawn_clear_clipboard() {
+    std::thread::spawn(|| {
+        match std::process::Command::new("wl-copy")
+            .arg("--clear")
+            .status()
+        {
+            Ok(status) if status.success() => {}
+            Ok(status) => tracing::warn!("wl-copy --clear failed: {}", status),
+            Err(e) => tracing::warn!("Could not run wl-copy: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+
+    #[test]
+    fn each_registered_sink_drops_its_queued_items() {
+        let registry = WipeRegistry::new();
+        let queues: Vec<Arc<Mutex<VecDeque<String>>>> = (0..3)
+            .map(|i| Arc::new(Mutex::new(VecDeque::from(vec![format!("queued {}", i)]))))
+            .collect();
+        for (queue, name) in queues.iter().zip(["history", "overlay", "paste"]) {
+            let queue = queue.clone();
+            registry.register(name, move || queue.lock().clear());
+        }
+
+        assert_eq!(registry.sink_names(), vec!["history", "overlay", "paste"]);
+        assert_eq!(registry.wipe(), 3);
+        assert!(queues.iter().all(|queue| queue.lock().is_empty()));
+    }
+
+    #[test]
+    fn items_queued_before_a_wipe_are_stale() {
+        let registry = WipeRegistry::new();
+        let before = registry.stamp("secret");
+        assert!(!registry.is_stale(&before));
+        assert!(!registry.discarding());
+
+        registry.wipe();
+        let after = registry.stamp("new");
+        assert!(registry.is_stale(&before));
+        assert!(!registry.is_stale(&after));
+        assert!(registry.discarding());
+
+        registry.resume();
+        assert!(!registry.discarding());
+    }
+}
@@ -0,0 +1,229 @@
+//! Settings profiles: partial config files in `~/.config/sonori/profiles/`.
+
+use std::path::PathBuf;
+
+use crate::config::{config_value, merge_toml, set_field, user_config_path, AppConfig};
+
+/// A profile file, as written: only the fields it overrides
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    name: String,
+    overrides: toml::Value,
+}
+
+impl Profile {
+    /// Read and validate `profiles/<name>.toml`
+    pub fn load(name: &str) -> Result<Self, String> {
+        let path = profile_path(name)?;
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read profile {}: {}", path.display(), e))?;
+        Self::parse(name, &contents)
+    }
+
+    fn parse(name: &str, contents: &str) -> Result<Self, String> {
+        let overrides: toml::Value = toml::from_str(contents)
+            .map_err(|e| format!("Profile '{}' is not valid TOML: {}", name, e))?;
+        let profile = Self {
+            name: name.to_string(),
+            overrides,
+        };
+        // Catch type errors now rather than when the profile is switched to
+        profile.apply(&AppConfig::default())?;
+        Ok(profile)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// `base` with this profile's fields applied
+    pub fn apply(&self, base: &AppConfig) -> Result<AppConfig, String> {
+        let mut merged = config_value(base);
+        merge_toml(&mut merged, self.overrides.clone());
+        merged
+            .try_into()
+            .map_err(|e: toml::de::Error| format!("Profile '{}': {}", self.name, e))
+    }
+
+    /// Dotted paths of the fields this profile sets
+    pub fn fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+        leaf_fields("", &self.overrides, &mut fields);
+        fields
+    }
+
+    /// Whether this profile sets `field` or a section containing it
+    pub fn sets(&self, field: &str) -> bool {
+        self.fields().iter().any(|set| {
+            field == set
+                || field
+                    .strip_prefix(set.as_str())
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+
+    /// Override `field` with `value`; `None` removes the override
+    pub fn set(&mut self, field: &str, value: Option<toml::Value>) {
+        set_field(&mut self.overrides, field, value);
+    }
+
+    /// Write the profile back to its file
+    pub fn save(&self) -> Result<(), String> {
+        write_profile(&self.name, &self.overrides).map(|_| ())
+    }
+}
+
+fn leaf_fields(path: &str, value: &toml::Value, fields: &mut Vec<String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                leaf_fields(&field, value, fields);
+            }
+        }
+        _ => fields.push(path.to_string()),
+    }
+}
+
+/// `~/.config/sonori/profiles`
+pub fn profiles_dir() -> Option<PathBuf> {
+    user_config_path().and_then(|path| path.parent().map(|dir| dir.join("profiles")))
+}
+
+/// Profile names become file names, so keep them to a safe alphabet
+fn profile_path(name: &str) -> Result<PathBuf, String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!(
+            "Invalid profile name '{}': use letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    profiles_dir()
+        .map(|dir| dir.join(format!("{}.toml", name)))
+        .ok_or_else(|| "Unable to determine the profiles directory. Set HOME.".to_string())
+}
+
+fn write_profile(name: &str, value: &toml::Value) -> Result<PathBuf, String> {
+    let path = profile_path(name)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let contents =
+        toml::to_string_pretty(value).map_err(|e| format!("Failed to serialize profile: {}", e))?;
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Profiles offered in the settings window: `profiles.available`, or every
+/// profile file when that is empty
+pub fn available(config: &AppConfig) -> Vec<String> {
+    if !config.profiles.available.is_empty() {
+        return config.profiles.available.clone();
+    }
+    let Some(entries) = profiles_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Write the whole effective `config` as profile `name`, returning its path.
+/// The profile list is left out so it keeps coming from the base config.
+pub fn export(config: &AppConfig, name: &str) -> Result<PathBuf, String> {
+    let mut value = config_value(config);
+    if let Some(table) = value.as_table_mut() {
+        table.remove("profiles");
+    }
+    write_profile(name, &value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_sections_merge_per_field() {
+        let mut base = AppConfig::default();
+        base.sound_config.enabled = false;
+        base.whisper_cpp_options.gpu_backend = crate::config::GpuBackend::Vulkan;
+
+        let profile = Profile::parse(
+            "meetings",
+            "[sound_config]\nvolume = 0.2\n\n[general_config]\nlanguage = \"de\"\n",
+        )
+        .unwrap();
+        let config = profile.apply(&base).unwrap();
+        assert_eq!(config.sound_config.volume, 0.2);
+        assert_eq!(config.general_config.language, "de");
+        // Untouched fields, in touched and untouched sections, come from the base
+        assert!(!config.sound_config.enabled);
+        assert_eq!(
+            config.whisper_cpp_options.gpu_backend,
+            crate::config::GpuBackend::Vulkan
+        );
+        assert_eq!(
+            profile.fields(),
+            ["general_config.language", "sound_config.volume"]
+        );
+    }
+
+    #[test]
+    fn arrays_are_replaced_and_maps_merged() {
+        let mut base = AppConfig::default();
+        base.post_process_config.hallucination_patterns = vec!["a".into(), "b".into()];
+        base.post_process_config.dictation_commands =
+            [("comma".to_string(), ",".to_string())].into();
+
+        let profile = Profile::parse(
+            "code",
+            "[post_process_config]\nhallucination_patterns = [\"c\"]\n\n\
+             [post_process_config.dictation_commands]\n\"new line\" = \"\\n\"\n",
+        )
+        .unwrap();
+        let config = profile.apply(&base).unwrap();
+        assert_eq!(config.post_process_config.hallucination_patterns, ["c"]);
+        let commands = &config.post_process_config.dictation_commands;
+        assert_eq!(commands.get("comma").map(String::as_str), Some(","));
+        assert_eq!(commands.get("new line").map(String::as_str), Some("\n"));
+        assert!(profile.sets("post_process_config.dictation_commands.new line"));
+        assert!(!profile.sets("post_process_config.dictation_commands.comma"));
+    }
+
+    #[test]
+    fn invalid_profiles_are_rejected() {
+        assert!(Profile::parse("broken", "[sound_config\nvolume = 0.2").is_err());
+        assert!(Profile::parse("typed", "[sound_config]\nvolume = \"loud\"").is_err());
+        assert!(profile_path("../config").is_err());
+        assert!(profile_path("").is_err());
+    }
+
+    #[test]
+    fn overrides_can_be_edited() {
+        let mut profile = Profile::parse("quiet", "[sound_config]\nvolume = 0.2\n").unwrap();
+        assert!(profile.sets("sound_config.volume"));
+        assert!(!profile.sets("sound_config.enabled"));
+        profile.set("sound_config.volume", Some(toml::Value::Float(0.4)));
+        profile.set("ui_config.font_size", Some(toml::Value::Float(14.0)));
+        let config = profile.apply(&AppConfig::default()).unwrap();
+        assert_eq!(config.sound_config.volume, 0.4);
+        assert_eq!(config.ui_config.font_size, 14.0);
+        profile.set("sound_config.volume", None);
+        assert_eq!(profile.fields(), ["ui_config.font_size"]);
+    }
+}
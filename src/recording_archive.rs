@@ -0,0 +1,240 @@
+//! Audio archive behind `recording_config`.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::Mutex;
+use speechcore::TranscriptionMode;
+
+use crate::config::{CancelledRecording, RecordingConfig, RecordingFormat, RecordingMode};
+
+/// How long after a session ends its file may still appear
+const WRITE_GRACE: Duration = Duration::from_secs(5);
+
+struct Archive {
+    dir: PathBuf,
+    on_cancel: CancelledRecording,
+    /// When the current (or last) manual session started; `None` after a real-time recording
+    session_start: Option<SystemTime>,
+    /// File of that session, once found
+    file: Option<PathBuf>,
+    cancelled: bool,
+}
+
+static ARCHIVE: Mutex<Option<Archive>> = Mutex::new(None);
+
+/// Settings speechcore cannot honour yet, one message each
+pub fn unsupported_settings(config: &RecordingConfig) -> Vec<String> {
+    let mut notes = Vec::new();
+    if !config.enabled {
+        return notes;
+    }
+    let format = match config.format {
+        RecordingFormat::Wav => None,
+        RecordingFormat::Flac => Some("flac"),
+        RecordingFormat::OggOpus => Some("ogg_opus"),
+    };
+    if let Some(format) = format {
+        notes.push(format!(
+            "recording_config.format = \"{}\": the speech runtime only writes WAV; saving WAV instead",
+            format
+        ));
+    }
+    if config.mode == RecordingMode::All {
+        notes.push(
+            "recording_config.mode = \"all\": the speech runtime only archives manual sessions; \
+             real-time recordings are not saved"
+                .to_string(),
+        );
+    }
+    notes
+}
+
+/// Enable the archive when `recording_config.enabled` is set
+pub fn init(config: &RecordingConfig) {
+    *ARCHIVE.lock() = config.enabled.then(|| Archive {
+        dir: PathBuf::from(&config.directory),
+        on_cancel: config.on_cancel,
+        session_start: None,
+        file: None,
+        cancelled: false,
+    });
+}
+
+/// A manual session was cancelled; its file is handled once it is written
+pub fn session_cancelled() {
+    if let Some(archive) = ARCHIVE.lock().as_mut() {
+        archive.cancelled = true;
+    }
+}
+
+/// Name of the audio file of the session the latest segment belongs to, if archived
+pub fn current_file_name() -> Option<String> {
+    let mut guard = ARCHIVE.lock();
+    let archive = guard.as_mut()?;
+    if archive.file.is_none() {
+        archive.file = find_recording(&archive.dir, archive.session_start?);
+    }
+    archive
+        .file
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+/// Newest WAV in `dir` written at or after `since`
+fn find_recording(dir: &Path, since: SystemTime) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "wav"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            (modified >= since).then(|| (modified, entry.path()))
+        })
+        .filter(|(_, path)| !is_marked(path))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+fn is_marked(path: &Path) -> bool {
+    path.file_stem()
+        .is_some_and(|stem| stem.to_string_lossy().ends_with(".cancelled"))
+}
+
+/// `recording_x.wav` -> `recording_x.cancelled.wav`
+fn marked_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.cancelled.{}", stem, ext.to_string_lossy()),
+        None => format!("{}.cancelled", stem),
+    };
+    path.with_file_name(name)
+}
+
+fn discard(path: &Path, on_cancel: CancelledRecording) {
+    let result = match on_cancel {
+        CancelledRecording::Delete => std::fs::remove_file(path),
+        CancelledRecording::Mark => std::fs::rename(path, marked_path(path)),
+    };
+    match result {
+        Ok(()) => tracing::info!(
+            "Recording of the cancelled session {}: {}",
+            match on_cancel {
+                CancelledRecording::Delete => "deleted",
+                CancelledRecording::Mark => "marked",
+            },
+            path.display()
+        ),
+        Err(e) => tracing::warn!(
+            "Could not handle the recording of the cancelled session {}: {}",
+            path.display(),
+            e
+        ),
+    }
+}
+
+/// Track recordings and handle the files of cancelled manual sessions
+pub async fn run_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    transcription_mode: Arc<AtomicU8>,
+) {
+    if ARCHIVE.lock().is_none() {
+        return;
+    }
+    let mut interval = tokio::time::interval(Duration::from_millis(200));
+    let mut was_recording = false;
+    // End of a cancelled session whose file has not been found yet
+    let mut cancelled_at: Option<SystemTime> = None;
+
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+        let is_recording = recording.load(Ordering::Relaxed);
+
+        let mut guard = ARCHIVE.lock();
+        let Some(archive) = guard.as_mut() else {
+            return;
+        };
+        if is_recording && !was_recording {
+            let manual = matches!(
+                TranscriptionMode::from_u8(transcription_mode.load(Ordering::Relaxed)),
+                TranscriptionMode::Manual
+            );
+            archive.session_start = manual.then(SystemTime::now);
+            archive.file = None;
+            archive.cancelled = false;
+            cancelled_at = None;
+        } else if !is_recording && was_recording && archive.cancelled {
+            cancelled_at = Some(SystemTime::now());
+        }
+        was_recording = is_recording;
+
+        let Some(ended) = cancelled_at else {
+            continue;
+        };
+        if let Some(path) = archive
+            .session_start
+            .and_then(|start| find_recording(&archive.dir, start))
+        {
+            discard(&path, archive.on_cancel);
+            archive.file = None;
+            archive.session_start = None;
+            cancelled_at = None;
+        } else if ended.elapsed().unwrap_or_default() >= WRITE_GRACE {
+            // Nothing was written for this session
+            cancelled_at = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_newest_unmarked_recording_since_session_start() {
+        let dir = std::env::temp_dir().join(format!("sonori-archive-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let start = SystemTime::now() - Duration::from_secs(1);
+        std::fs::write(dir.join("recording_1.cancelled.wav"), b"x").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"x").unwrap();
+        assert_eq!(find_recording(&dir, start), None);
+
+        std::fs::write(dir.join("recording_2.wav"), b"x").unwrap();
+        assert_eq!(
+            find_recording(&dir, start),
+            Some(dir.join("recording_2.wav"))
+        );
+        assert_eq!(
+            find_recording(&dir, SystemTime::now() + Duration::from_secs(60)),
+            None
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cancelled_recordings_keep_their_extension() {
+        assert_eq!(
+            marked_path(Path::new("/tmp/recording_20251211_143022.wav")),
+            PathBuf::from("/tmp/recording_20251211_143022.cancelled.wav")
+        );
+        assert!(is_marked(&marked_path(Path::new("a.wav"))));
+    }
+
+    #[test]
+    fn unsupported_formats_and_modes_are_reported() {
+        let mut config = RecordingConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(unsupported_settings(&config).is_empty());
+        config.format = RecordingFormat::Flac;
+        config.mode = RecordingMode::All;
+        assert_eq!(unsupported_settings(&config).len(), 2);
+        config.enabled = false;
+        assert!(unsupported_settings(&config).is_empty());
+    }
+}
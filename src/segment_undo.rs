@@ -0,0 +1,111 @@
+//! Undo of the last delivered segment (Ctrl+Z in the overlay, `u` in the CLI).
+
+use std::sync::Arc;
+
+use parking_lot::{Mutex, RwLock};
+use speechcore::AudioVisualizationData;
+
+/// Segments kept for undo; older ones can no longer be removed
+const MAX_SEGMENTS: usize = 100;
+
+/// Transcript the GUI undoes in
+pub struct UndoTargets {
+    pub transcript_history: Arc<RwLock<String>>,
+    pub audio_data: Arc<RwLock<AudioVisualizationData>>,
+}
+
+static SEGMENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static TARGETS: Mutex<Option<UndoTargets>> = Mutex::new(None);
+
+pub fn attach(targets: UndoTargets) {
+    *TARGETS.lock() = Some(targets);
+}
+
+/// Remember a segment as it is appended to the transcript
+pub fn record_segment(text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let mut segments = SEGMENTS.lock();
+    if segments.len() == MAX_SEGMENTS {
+        segments.remove(0);
+    }
+    segments.push(text.to_string());
+}
+
+/// Forget every remembered segment (privacy wipe)
+pub fn clear() {
+    SEGMENTS.lock().clear();
+}
+
+/// Remove the last segment from `transcript`; returns the removed text
+pub fn undo_in(transcript: &mut String) -> Option<String> {
+    remove_last(&mut SEGMENTS.lock(), transcript)
+}
+
+/// Remove the last segment from the attached transcript history and overlay,
+/// then put the corrected transcript on the clipboard
+pub fn undo() -> Option<String> {
+    let targets = TARGETS.lock();
+    let targets = targets.as_ref()?;
+    let (removed, corrected) = {
+        let mut history = targets.transcript_history.write();
+        let removed = undo_in(&mut history);
+        (removed, history.clone())
+    };
+    let Some(removed) = removed else {
+        crate::toast::show(crate::i18n::tr("toast.nothing_to_undo"));
+        return None;
+    };
+
+    targets.audio_data.write().transcript = corrected.clone();
+    if corrected.is_empty() {
+        crate::privacy_wipe::spawn_clear_clipboard();
+    } else {
+        std::thread::spawn(move || {
+            if let Err(e) = crate::copy::WlCopy::copy_to_clipboard(&corrected) {
+                tracing::warn!("Failed to copy corrected transcript: {}", e);
+            }
+        });
+    }
+    crate::toast::show(crate::i18n::tr("toast.removed_segment"));
+    Some(removed)
+}
+
+fn remove_last(segments: &mut Vec<String>, transcript: &mut String) -> Option<String> {
+    let segment = segments.pop()?;
+    if !transcript.ends_with(segment.as_str()) {
+        segments.clear();
+        return None;
+    }
+    transcript.truncate(transcript.len() - segment.len());
+    let kept = transcript.trim_end().len();
+    transcript.truncate(kept);
+    Some(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_segments_newest_first_until_stale() {
+        let mut segments = vec![
+            "Hello there.".to_string(),
+            "Thanks for watching!".to_string(),
+        ];
+        let mut transcript = "Hello there. Thanks for watching!".to_string();
+
+        assert_eq!(
+            remove_last(&mut segments, &mut transcript).as_deref(),
+            Some("Thanks for watching!")
+        );
+        assert_eq!(transcript, "Hello there.");
+
+        // The transcript was reset after "Hello there." was remembered
+        transcript = "Something else".to_string();
+        assert_eq!(remove_last(&mut segments, &mut transcript), None);
+        assert!(segments.is_empty());
+        assert_eq!(transcript, "Something else");
+    }
+}
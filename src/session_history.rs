@@ -0,0 +1,234 @@
+//! Bounded in-memory list of past sessions (`debug_config.session_history_limit`).
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use parking_lot::Mutex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionEntry {
+    pub id: u64,
+    pub started: DateTime<Local>,
+    pub duration: Duration,
+    pub text: String,
+}
+
+struct OpenSession {
+    started: DateTime<Local>,
+    started_at: Instant,
+    text: String,
+}
+
+pub struct SessionHistory {
+    /// Oldest first
+    entries: VecDeque<SessionEntry>,
+    limit: usize,
+    next_id: u64,
+    open: Option<OpenSession>,
+    /// Entry that segments arriving after a stop still belong to
+    late_target: Option<u64>,
+}
+
+impl SessionHistory {
+    pub const fn new(limit: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            limit,
+            next_id: 1,
+            open: None,
+            late_target: None,
+        }
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.trim();
+    }
+
+    /// Start a session; an unfinished one is closed first
+    pub fn start(&mut self, now: Instant) {
+        self.close(now);
+        self.late_target = None;
+        self.open = Some(OpenSession {
+            started: Local::now(),
+            started_at: now,
+            text: String::new(),
+        });
+    }
+
+    /// Close the running session; later segments are added to its entry
+    pub fn stop(&mut self, now: Instant) {
+        self.late_target = self.close(now);
+    }
+
+    /// Close the running session and keep recording into a new one (transcript reset)
+    pub fn cut(&mut self, now: Instant) {
+        let was_open = self.open.is_some();
+        self.close(now);
+        self.late_target = None;
+        if was_open {
+            self.start(now);
+        }
+    }
+
+    pub fn record(&mut self, text: &str, now: Instant) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        if let Some(id) = self.late_target.filter(|_| self.open.is_none()) {
+            if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+                push_segment(&mut entry.text, text);
+                return;
+            }
+        }
+        if self.open.is_none() {
+            self.start(now);
+        }
+        if let Some(open) = self.open.as_mut() {
+            push_segment(&mut open.text, text);
+        }
+    }
+
+    /// Finished sessions, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &SessionEntry> {
+        self.entries.iter()
+    }
+
+    pub fn get(&self, id: u64) -> Option<&SessionEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.open = None;
+        self.late_target = None;
+    }
+
+    fn close(&mut self, now: Instant) -> Option<u64> {
+        let open = self.open.take()?;
+        if open.text.is_empty() || self.limit == 0 {
+            return None;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push_back(SessionEntry {
+            id,
+            started: open.started,
+            duration: now.saturating_duration_since(open.started_at),
+            text: open.text,
+        });
+        self.trim();
+        Some(id)
+    }
+
+    fn trim(&mut self) {
+        while self.entries.len() > self.limit {
+            self.entries.pop_front();
+        }
+    }
+}
+
+fn push_segment(text: &mut String, segment: &str) {
+    if !text.is_empty() {
+        text.push(' ');
+    }
+    text.push_str(segment);
+}
+
+static HISTORY: Mutex<SessionHistory> = Mutex::new(SessionHistory::new(20));
+
+pub fn init(limit: usize) {
+    HISTORY.lock().set_limit(limit);
+}
+
+pub fn session_started() {
+    HISTORY.lock().start(Instant::now());
+}
+
+pub fn session_stopped() {
+    HISTORY.lock().stop(Instant::now());
+}
+
+/// Cut the running session at a transcript reset
+pub fn transcript_reset() {
+    HISTORY.lock().cut(Instant::now());
+    crate::transcript_spill::clear();
+}
+
+/// Add a final segment to the running session
+pub fn record_segment(text: &str) {
+    HISTORY.lock().record(text, Instant::now());
+}
+
+/// Finished sessions, oldest first
+pub fn entries() -> Vec<SessionEntry> {
+    HISTORY.lock().entries().cloned().collect()
+}
+
+/// Forget every session (privacy wipe)
+pub fn clear() {
+    HISTORY.lock().clear();
+}
+
+/// One-line summary for the CLI `h` command
+pub fn format_entry(entry: &SessionEntry) -> String {
+    format!(
+        "#{} {} ({}s): {}",
+        entry.id,
+        entry.started.format("%H:%M:%S"),
+        entry.duration.as_secs(),
+        entry.text
+    )
+}
+
+/// Follow the recording flag so each recording becomes its own session
+pub async fn run_recording_monitor(running: Arc<AtomicBool>, recording: Arc<AtomicBool>) {
+    let mut interval = tokio::time::interval(Duration::from_millis(100));
+    let mut was_recording = false;
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+        let is_recording = recording.load(Ordering::Relaxed);
+        if is_recording && !was_recording {
+            session_started();
+        } else if !is_recording && was_recording {
+            session_stopped();
+        }
+        was_recording = is_recording;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(history: &SessionHistory) -> Vec<String> {
+        history.entries().map(|entry| entry.text.clone()).collect()
+    }
+
+    #[test]
+    fn sessions_are_cut_on_stop_and_reset_and_capped() {
+        let t0 = Instant::now();
+        let mut history = SessionHistory::new(2);
+
+        history.start(t0);
+        history.record("hello", t0);
+        history.stop(t0 + Duration::from_secs(3));
+        // Manual mode delivers the transcription after the stop
+        history.record("world", t0 + Duration::from_secs(4));
+        assert_eq!(texts(&history), ["hello world"]);
+        assert_eq!(history.get(1).unwrap().duration, Duration::from_secs(3));
+
+        history.start(t0 + Duration::from_secs(10));
+        history.record("first", t0 + Duration::from_secs(11));
+        history.cut(t0 + Duration::from_secs(12));
+        history.record("second", t0 + Duration::from_secs(13));
+        history.cut(t0 + Duration::from_secs(14));
+        history.cut(t0 + Duration::from_secs(15));
+        assert_eq!(texts(&history), ["first", "second"]);
+        assert_eq!(history.entries().map(|e| e.id).collect::<Vec<_>>(), [2, 3]);
+    }
+}
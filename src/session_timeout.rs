@@ -0,0 +1,194 @@
+//! Warning before a manual session reaches `max_recording_duration_secs`.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use speechcore::{ManualSessionCommand, TranscriptionMode};
+use tokio::sync::mpsc::Sender;
+
+use crate::sound_generator::SoundType;
+use crate::sound_player::SoundPlayer;
+
+/// Stop this long before the cap so the last audio still fits the buffer
+const STOP_MARGIN: Duration = Duration::from_millis(500);
+
+/// When the running session hits the cap; set once the warning is due
+static SESSION_END: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Time left in the current manual session, once the warning is due
+pub fn remaining() -> Option<Duration> {
+    SESSION_END
+        .lock()
+        .map(|end| end.saturating_duration_since(Instant::now()))
+}
+
+/// Countdown text such as "15s left"
+pub fn countdown_label(remaining: Duration) -> String {
+    crate::i18n::tr_args(
+        "overlay.countdown",
+        &[("secs", &(remaining.as_secs_f32().ceil() as u64))],
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeoutEvent {
+    /// The warning threshold was crossed; the session ends at this instant
+    Warn(Instant),
+    /// The session is about to hit the cap and should be stopped
+    Stop,
+    /// The session ended
+    Ended,
+}
+
+/// Length of one manual session against the cap
+#[derive(Debug)]
+struct SessionClock {
+    max: Duration,
+    /// Offset of the warning from the session start; `None` = no warning
+    warn_after: Option<Duration>,
+    started: Option<Instant>,
+    warned: bool,
+    stop_sent: bool,
+}
+
+impl SessionClock {
+    fn new(max_secs: u32, warning_percent: f32) -> Self {
+        let max = Duration::from_secs(max_secs as u64);
+        let warn_after = (warning_percent > 0.0 && warning_percent < 100.0)
+            .then(|| max.mul_f32(warning_percent / 100.0));
+        Self {
+            max,
+            warn_after,
+            started: None,
+            warned: false,
+            stop_sent: false,
+        }
+    }
+
+    fn step(&mut self, in_session: bool, now: Instant) -> Option<TimeoutEvent> {
+        let Some(started) = self.started else {
+            if in_session {
+                self.started = Some(now);
+                self.warned = false;
+                self.stop_sent = false;
+            }
+            return None;
+        };
+        if !in_session {
+            self.started = None;
+            return Some(TimeoutEvent::Ended);
+        }
+        if self.max.is_zero() {
+            return None;
+        }
+
+        let elapsed = now.duration_since(started);
+        if !self.stop_sent && elapsed + STOP_MARGIN >= self.max {
+            self.stop_sent = true;
+            return Some(TimeoutEvent::Stop);
+        }
+        match self.warn_after {
+            Some(warn_after) if !self.warned && elapsed >= warn_after => {
+                self.warned = true;
+                Some(TimeoutEvent::Warn(started + self.max))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Warn about and then stop manual sessions that run into the length cap
+pub async fn run_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    transcription_mode: Arc<AtomicU8>,
+    manual_session_sender: Sender<ManualSessionCommand>,
+    manual_config: crate::config::ManualModeConfig,
+    sound_player: Option<Arc<SoundPlayer>>,
+) {
+    let mut clock = SessionClock::new(
+        manual_config.recording_duration_secs(),
+        manual_config.timeout_warning_percent,
+    );
+    let mut interval = tokio::time::interval(Duration::from_millis(100));
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+        let in_session = recording.load(Ordering::Relaxed)
+            && matches!(
+                TranscriptionMode::from_u8(transcription_mode.load(Ordering::Relaxed)),
+                TranscriptionMode::Manual
+            );
+
+        match clock.step(in_session, Instant::now()) {
+            Some(TimeoutEvent::Warn(end)) => {
+                *SESSION_END.lock() = Some(end);
+                let left = end.saturating_duration_since(Instant::now());
+                tracing::info!(
+                    "Session limit: {} (max_recording_duration_secs)",
+                    countdown_label(left)
+                );
+                if let Some(player) = &sound_player {
+                    player.play(SoundType::SessionWarning);
+                }
+            }
+            Some(TimeoutEvent::Stop) => {
+                tracing::info!("Session limit reached; stopping and transcribing");
+                let command = ManualSessionCommand::StopSession { responder: None };
+                if let Err(e) = manual_session_sender.try_send(command) {
+                    tracing::warn!("Failed to stop the session at its limit: {}", e);
+                }
+            }
+            Some(TimeoutEvent::Ended) => *SESSION_END.lock() = None,
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_clock_warns_then_stops_before_the_cap() {
+        let t0 = Instant::now();
+        let mut clock = SessionClock::new(100, 90.0);
+
+        assert_eq!(clock.step(true, t0), None);
+        assert_eq!(clock.step(true, t0 + Duration::from_secs(89)), None);
+        assert_eq!(
+            clock.step(true, t0 + Duration::from_secs(90)),
+            Some(TimeoutEvent::Warn(t0 + Duration::from_secs(100)))
+        );
+        assert_eq!(clock.step(true, t0 + Duration::from_secs(95)), None);
+        assert_eq!(
+            clock.step(true, t0 + Duration::from_millis(99_600)),
+            Some(TimeoutEvent::Stop)
+        );
+        assert_eq!(clock.step(true, t0 + Duration::from_secs(100)), None);
+        assert_eq!(
+            clock.step(false, t0 + Duration::from_secs(101)),
+            Some(TimeoutEvent::Ended)
+        );
+    }
+
+    #[test]
+    fn zero_percent_disables_only_the_warning() {
+        let t0 = Instant::now();
+        let mut clock = SessionClock::new(10, 0.0);
+
+        clock.step(true, t0);
+        assert_eq!(clock.step(true, t0 + Duration::from_secs(9)), None);
+        assert_eq!(
+            clock.step(true, t0 + Duration::from_millis(9_500)),
+            Some(TimeoutEvent::Stop)
+        );
+    }
+
+    #[test]
+    fn countdown_label_rounds_up() {
+        assert_eq!(countdown_label(Duration::from_millis(14_200)), "15s left");
+        assert_eq!(countdown_label(Duration::ZERO), "0s left");
+    }
+}
@@ -0,0 +1,183 @@
+//! Single-instance lock.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::ipc::{self, IpcCommand};
+
+/// How long `--replace` waits for the old instance to exit
+const REPLACE_TIMEOUT: Duration = Duration::from_secs(10);
+const REPLACE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Path of the lock file holding the running instance's PID
+pub fn lock_path() -> PathBuf {
+    ipc::runtime_dir().join("sonori").join("instance.lock")
+}
+
+/// Held for the lifetime of the instance. The lock is an advisory lock on the
+/// open file, so the kernel releases it even if the process crashes.
+#[derive(Debug)]
+pub struct InstanceLock {
+    file: File,
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // The file stays; only the lock guards it. Clear the PID while still holding it.
+        let _ = self.file.set_len(0);
+    }
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Process name from `/proc/<pid>/comm`
+fn process_name(pid: &str) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        .ok()
+        .map(|name| name.trim().to_string())
+}
+
+/// Whether `pid` is a live process running this program (not this process)
+fn is_live_instance(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return false;
+    }
+    let own_name = process_name("self").unwrap_or_else(|| "sonori".to_string());
+    process_name(&pid.to_string()).is_some_and(|name| name == own_name)
+}
+
+fn open_lock_file(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .context("Failed to open lock file")
+}
+
+/// Take the lock without waiting; `Ok(false)` while another process holds it
+fn try_lock(file: &File) -> Result<bool> {
+    match file.try_lock() {
+        Ok(()) => Ok(true),
+        Err(TryLockError::WouldBlock) => Ok(false),
+        Err(TryLockError::Error(e)) => Err(e).context("Failed to lock the lock file"),
+    }
+}
+
+/// Record this process's PID in a lock file this process holds
+fn write_pid(mut file: &File) -> Result<()> {
+    file.set_len(0)?;
+    writeln!(file, "{}", std::process::id()).context("Failed to write lock file")
+}
+
+/// PID written by the lock holder, which writes it right after locking
+async fn holder_pid(path: &Path) -> Option<u32> {
+    for _ in 0..10 {
+        if let Some(pid) = read_pid(path) {
+            return Some(pid);
+        }
+        tokio::time::sleep(REPLACE_POLL_INTERVAL).await;
+    }
+    None
+}
+
+/// Take the lock, or fail if another instance is running. With `replace` the
+/// other instance is shut down first.
+pub async fn acquire(replace: bool) -> Result<InstanceLock> {
+    let path = lock_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create runtime directory")?;
+    }
+    let file = open_lock_file(&path)?;
+
+    if !try_lock(&file)? {
+        match holder_pid(&path).await {
+            Some(pid) if replace => shut_down(pid, &file).await?,
+            Some(pid) => bail!(
+                "Sonori is already running (PID {}); use --replace to take over",
+                pid
+            ),
+            None => bail!("Sonori is already running, but its lock file names no PID"),
+        }
+    }
+
+    write_pid(&file)?;
+    Ok(InstanceLock { file })
+}
+
+/// Ask the instance `pid` to quit and wait until it has released the lock
+async fn shut_down(pid: u32, file: &File) -> Result<()> {
+    tracing::info!("Replacing running Sonori instance (PID {})...", pid);
+    // CLI instances have no control socket but exit cleanly on Ctrl+C
+    if let Err(e) = ipc::send_command_to(IpcCommand::Quit, pid).await {
+        tracing::debug!("Quit over the control socket failed: {}", e);
+        if !is_live_instance(pid) {
+            bail!("PID {} in the lock file is not a Sonori process", pid);
+        }
+        // SAFETY: kill has no memory-safety preconditions
+        if unsafe { libc::kill(pid as libc::pid_t, libc::SIGINT) } != 0 {
+            return Err(std::io::Error::last_os_error())
+                .with_context(|| format!("Could not signal the running instance (PID {})", pid));
+        }
+    }
+
+    let deadline = Instant::now() + REPLACE_TIMEOUT;
+    while !try_lock(file)? {
+        if Instant::now() >= deadline {
+            return Err(anyhow!(
+                "Running instance (PID {}) did not exit within {} s",
+                pid,
+                REPLACE_TIMEOUT.as_secs()
+            ));
+        }
+        tokio::time::sleep(REPLACE_POLL_INTERVAL).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn own_process_is_not_another_instance() {
+        assert!(!is_live_instance(std::process::id()));
+    }
+
+    #[test]
+    fn dead_pid_is_stale() {
+        // Far above any default pid_max
+        assert!(!is_live_instance(u32::MAX - 1));
+    }
+
+    #[test]
+    fn lock_excludes_a_second_holder_until_dropped() {
+        let dir = std::env::temp_dir().join(format!("sonori-lock-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("instance.lock");
+        // A PID left by a crashed instance does not hold the lock
+        std::fs::write(&path, "4294967294\n").unwrap();
+
+        let first = open_lock_file(&path).unwrap();
+        assert!(try_lock(&first).unwrap());
+        write_pid(&first).unwrap();
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+
+        let second = open_lock_file(&path).unwrap();
+        assert!(!try_lock(&second).unwrap());
+
+        drop(InstanceLock { file: first });
+        assert_eq!(read_pid(&path), None);
+        assert!(try_lock(&second).unwrap());
+
+        drop(second);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
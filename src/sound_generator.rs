@@ -8,6 +8,10 @@ pub enum SoundType {
     SessionStart,
     SessionComplete,
     SessionCancel,
+    /// A manual session is close to `max_recording_duration_secs`
+    SessionWarning,
+    /// The speech pipeline died and transcription stopped
+    PipelineError,
 }
 
 // Musical note frequencies (Hz)
@@ -48,6 +52,14 @@ impl SoundGenerator {
             SoundType::SessionCancel,
             generator.generate_double_tap(C4, 0.06, 0.04, 0.3),
         );
+        generator.cache.insert(
+            SoundType::SessionWarning,
+            generator.generate_double_tap(G5, 0.07, 0.07, 0.35),
+        );
+        generator.cache.insert(
+            SoundType::PipelineError,
+            generator.generate_three_tone(E5, C5, C4, 0.12, 0.4),
+        );
 
         generator
     }
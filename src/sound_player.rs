@@ -27,7 +27,7 @@ impl SoundPlayer {
             let device = match host.default_output_device() {
                 Some(d) => d,
                 None => {
-                    eprintln!("No audio output device available for sound playback");
+                    tracing::warn!("No audio output device available for sound playback");
                     return;
                 }
             };
@@ -35,7 +35,7 @@ impl SoundPlayer {
             let config = match device.default_output_config() {
                 Ok(c) => c,
                 Err(e) => {
-                    eprintln!("Failed to get default output config: {}", e);
+                    tracing::warn!("Failed to get default output config: {}", e);
                     return;
                 }
             };
@@ -45,7 +45,7 @@ impl SoundPlayer {
 
             while let Ok((sound_type, volume)) = sound_rx.recv() {
                 if let Err(e) = Self::play_sound_internal(&device, &generator, sound_type, volume) {
-                    eprintln!("Failed to play sound {:?}: {}", sound_type, e);
+                    tracing::warn!("Failed to play sound {:?}: {}", sound_type, e);
                 }
             }
         });
@@ -74,6 +74,12 @@ impl SoundPlayer {
         *self.volume.lock() = volume.clamp(0.0, 1.0);
     }
 
+    /// Take over `enabled` and `volume` from a changed configuration
+    pub fn apply_config(&self, config: &SoundConfig) {
+        self.set_enabled(config.enabled);
+        self.set_volume(config.volume);
+    }
+
     fn play_sound_internal(
         device: &cpal::Device,
         generator: &SoundGenerator,
@@ -104,7 +110,7 @@ impl SoundPlayer {
                     }
                 }
             },
-            |err| eprintln!("Audio stream error: {}", err),
+            |err| tracing::warn!("Audio stream error: {}", err),
             None,
         )?;
 
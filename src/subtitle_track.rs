@@ -0,0 +1,158 @@
+//! Coarse per-segment timing of the current recording, for SRT/VTT export.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::config::AppConfig;
+use crate::transcript_writer::{self, SubtitleSegment};
+
+struct Track {
+    started: Option<Instant>,
+    stopped: Option<Instant>,
+    /// Text and delivery time since `started`
+    segments: Vec<(String, Duration)>,
+}
+
+static TRACK: Mutex<Track> = Mutex::new(Track {
+    started: None,
+    stopped: None,
+    segments: Vec::new(),
+});
+
+/// Start a new track; the previous recording's segments are dropped
+pub fn recording_started() {
+    let mut track = TRACK.lock();
+    track.started = Some(Instant::now());
+    track.stopped = None;
+    track.segments.clear();
+}
+
+pub fn recording_stopped() {
+    let mut track = TRACK.lock();
+    if track.started.is_some() && track.stopped.is_none() {
+        track.stopped = Some(Instant::now());
+    }
+}
+
+/// Record a final segment as delivered now
+pub fn record_segment(text: &str) {
+    if text.trim().is_empty() {
+        return;
+    }
+    let mut track = TRACK.lock();
+    let started = *track.started.get_or_insert_with(Instant::now);
+    track
+        .segments
+        .push((text.trim().to_string(), started.elapsed()));
+}
+
+/// Forget the current track (privacy wipe)
+pub fn clear() {
+    let mut track = TRACK.lock();
+    track.segments.clear();
+}
+
+/// Segments of the current (or last) recording with estimated times
+pub fn segments() -> Vec<SubtitleSegment> {
+    let track = TRACK.lock();
+    let recorded = match (track.started, track.stopped) {
+        (Some(started), Some(stopped)) => stopped.duration_since(started),
+        (Some(started), None) => started.elapsed(),
+        _ => Duration::ZERO,
+    };
+    time_segments(&track.segments, recorded)
+}
+
+/// Export the current track with `subtitle_config` into `debug_config.recording_dir`
+pub fn export(config: &AppConfig) -> Result<PathBuf, std::io::Error> {
+    transcript_writer::export_subtitles(
+        &segments(),
+        config.subtitle_config.format,
+        config.subtitle_config.max_line_length,
+        std::path::Path::new(&config.debug_config.recording_dir),
+    )
+}
+
+fn time_segments(delivered: &[(String, Duration)], recorded: Duration) -> Vec<SubtitleSegment> {
+    let live = delivered
+        .iter()
+        .take_while(|(_, at)| *at <= recorded)
+        .count();
+    let mut segments = Vec::with_capacity(delivered.len());
+    let mut previous_end = Duration::ZERO;
+    for (text, at) in &delivered[..live] {
+        segments.push(SubtitleSegment {
+            start: previous_end,
+            end: *at,
+            text: text.clone(),
+        });
+        previous_end = *at;
+    }
+
+    let late = &delivered[live..];
+    let late_chars: usize = late.iter().map(|(text, _)| text.chars().count()).sum();
+    let span = recorded.saturating_sub(previous_end);
+    let span_start = previous_end;
+    let mut chars_through = 0;
+    for (text, _) in late {
+        chars_through += text.chars().count();
+        let end = span_start + span.mul_f64(chars_through as f64 / late_chars.max(1) as f64);
+        segments.push(SubtitleSegment {
+            start: previous_end,
+            end,
+            text: text.clone(),
+        });
+        previous_end = end;
+    }
+    segments
+}
+
+/// Follow the recording flag so each recording starts a fresh track
+pub async fn run_recording_monitor(running: Arc<AtomicBool>, recording: Arc<AtomicBool>) {
+    let mut interval = tokio::time::interval(Duration::from_millis(100));
+    let mut was_recording = false;
+    while running.load(Ordering::Relaxed) {
+        interval.tick().await;
+        let is_recording = recording.load(Ordering::Relaxed);
+        if is_recording && !was_recording {
+            recording_started();
+        } else if !is_recording && was_recording {
+            recording_stopped();
+        }
+        was_recording = is_recording;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: f64) -> Duration {
+        Duration::from_secs_f64(s)
+    }
+
+    #[test]
+    fn live_segments_end_on_delivery_and_late_ones_share_the_rest() {
+        let delivered = [
+            ("one".to_string(), secs(2.0)),
+            ("two".to_string(), secs(5.0)),
+            ("aaa".to_string(), secs(12.0)),
+            ("bbbbbbbbb".to_string(), secs(12.5)),
+        ];
+        let segments = time_segments(&delivered, secs(10.0));
+        let times: Vec<(Duration, Duration)> = segments.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(
+            times,
+            [
+                (secs(0.0), secs(2.0)),
+                (secs(2.0), secs(5.0)),
+                (secs(5.0), secs(6.25)),
+                (secs(6.25), secs(10.0)),
+            ]
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use zbus::object_server::SignalEmitter;
 use zbus::{connection, interface, Connection};
 
 use speechcore::TranscriptionMode;
@@ -12,6 +15,13 @@ pub enum TrayCommand {
     ToggleRecording,
     ToggleManualSession,
     SwitchMode,
+    ToggleClickThrough,
+    ToggleAutoPaste,
+    PrivacyWipe,
+    ExportSubtitles,
+    CopyLastSegment,
+    CopyTranscript,
+    OpenSettings,
     Quit,
 }
 
@@ -21,6 +31,57 @@ pub enum TrayUpdate {
     Recording(bool),
     Mode(TranscriptionMode),
     Transcript(String),
+    /// The overlay's click-through state changed
+    ClickThrough(bool),
+    /// Auto-paste was switched on or off
+    AutoPaste(bool),
+}
+
+/// Characters of the latest segment shown in the menu
+const PREVIEW_CHARS: usize = 60;
+
+/// Latest delivered segment, shown in the menu and copied by "Copy Last Segment"
+static LATEST_SEGMENT: Mutex<String> = Mutex::new(String::new());
+
+/// Menu layout revision; bumped on every change, announced at most once a second
+static LAYOUT_REVISION: AtomicU32 = AtomicU32::new(1);
+
+fn bump_layout_revision() {
+    LAYOUT_REVISION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Remember a delivered segment for the menu
+pub fn record_segment(text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    *LATEST_SEGMENT.lock() = text.to_string();
+    bump_layout_revision();
+}
+
+/// Forget the latest segment (privacy wipe)
+pub fn clear() {
+    LATEST_SEGMENT.lock().clear();
+    bump_layout_revision();
+}
+
+pub fn latest_segment() -> String {
+    LATEST_SEGMENT.lock().clone()
+}
+
+/// Menu label for the latest segment, shortened to `PREVIEW_CHARS`
+fn segment_preview(segment: &str) -> String {
+    if segment.is_empty() {
+        return "No transcript yet".to_string();
+    }
+    let mut chars = segment.chars();
+    let preview: String = chars.by_ref().take(PREVIEW_CHARS).collect();
+    if chars.next().is_some() {
+        format!("“{}…”", preview.trim_end())
+    } else {
+        format!("“{}”", preview)
+    }
 }
 
 /// StatusNotifierItem implementation
@@ -81,6 +142,16 @@ impl StatusNotifierItem {
         "audio-input-microphone"
     }
 
+    /// OverlayIconName property - marks the icon while segments are only copied
+    #[zbus(property)]
+    async fn overlay_icon_name(&self) -> &str {
+        if crate::auto_paste::enabled() {
+            ""
+        } else {
+            "edit-copy"
+        }
+    }
+
     /// ToolTip property - returns (icon_name, icon_pixmap, title, description)
     #[zbus(property)]
     async fn tool_tip(&self) -> (String, Vec<(i32, i32, Vec<u8>)>, String, String) {
@@ -96,7 +167,17 @@ impl StatusNotifierItem {
             TranscriptionMode::Manual => "Manual",
         };
 
-        let description = format!("{} | {}", mode, status);
+        let paste = if crate::auto_paste::enabled() {
+            "Auto-paste"
+        } else {
+            "Clipboard only"
+        };
+
+        let mut description = format!("{} | {} | {}", mode, status, paste);
+        if let Some((words, goal)) = crate::daily_words::today() {
+            description.push('\n');
+            description.push_str(&crate::daily_words::format_progress(words, goal));
+        }
 
         (
             self.icon_name().await.to_string(),
@@ -111,6 +192,14 @@ impl StatusNotifierItem {
     async fn menu(&self) -> zbus::zvariant::ObjectPath<'static> {
         "/StatusNotifierItem/menu".try_into().unwrap()
     }
+
+    /// Tells the tray host to fetch the tooltip again
+    #[zbus(signal)]
+    async fn new_tool_tip(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    /// Tells the tray host to fetch the overlay icon again
+    #[zbus(signal)]
+    async fn new_overlay_icon(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
 }
 
 /// Menu item IDs for DBusMenu
@@ -118,6 +207,15 @@ const MENU_TOGGLE_RECORDING: i32 = 1;
 const MENU_TOGGLE_MODE: i32 = 2;
 const MENU_SEPARATOR: i32 = 3;
 const MENU_QUIT: i32 = 4;
+const MENU_PRIVACY_WIPE: i32 = 5;
+const MENU_EXPORT_SUBTITLES: i32 = 6;
+const MENU_LATEST_SEGMENT: i32 = 7;
+const MENU_COPY_LAST_SEGMENT: i32 = 8;
+const MENU_COPY_TRANSCRIPT: i32 = 9;
+const MENU_OPEN_SETTINGS: i32 = 10;
+const MENU_SEPARATOR_TRANSCRIPT: i32 = 11;
+const MENU_CLICK_THROUGH: i32 = 12;
+const MENU_AUTO_PASTE: i32 = 13;
 
 #[interface(name = "com.canonical.dbusmenu")]
 impl DbusMenu {
@@ -145,6 +243,49 @@ impl DbusMenu {
         // Build menu items
         let mut items = Vec::new();
 
+        // Latest segment (informational)
+        let latest_segment = latest_segment();
+        let mut latest_props = HashMap::new();
+        latest_props.insert(
+            "label".to_string(),
+            Value::new(segment_preview(&latest_segment)),
+        );
+        latest_props.insert("enabled".to_string(), Value::new(false));
+        items.push(Value::new((
+            MENU_LATEST_SEGMENT,
+            latest_props,
+            Vec::<Value>::new(),
+        )));
+
+        let mut copy_last_props = HashMap::new();
+        copy_last_props.insert("label".to_string(), Value::new("Copy Last Segment"));
+        copy_last_props.insert(
+            "enabled".to_string(),
+            Value::new(!latest_segment.is_empty()),
+        );
+        items.push(Value::new((
+            MENU_COPY_LAST_SEGMENT,
+            copy_last_props,
+            Vec::<Value>::new(),
+        )));
+
+        let mut copy_all_props = HashMap::new();
+        copy_all_props.insert("label".to_string(), Value::new("Copy Full Transcript"));
+        copy_all_props.insert("enabled".to_string(), Value::new(true));
+        items.push(Value::new((
+            MENU_COPY_TRANSCRIPT,
+            copy_all_props,
+            Vec::<Value>::new(),
+        )));
+
+        let mut separator_props = HashMap::new();
+        separator_props.insert("type".to_string(), Value::new("separator"));
+        items.push(Value::new((
+            MENU_SEPARATOR_TRANSCRIPT,
+            separator_props,
+            Vec::<Value>::new(),
+        )));
+
         // Item 1: Start/Stop Recording
         let mut item1_props = HashMap::new();
         item1_props.insert(
@@ -175,6 +316,59 @@ impl DbusMenu {
         let item2 = Value::new((MENU_TOGGLE_MODE, item2_props, Vec::<Value>::new()));
         items.push(item2);
 
+        // Click-through overlay
+        let mut click_through_props = HashMap::new();
+        click_through_props.insert("label".to_string(), Value::new("Click-through Overlay"));
+        click_through_props.insert("toggle-type".to_string(), Value::new("checkmark"));
+        click_through_props.insert(
+            "toggle-state".to_string(),
+            Value::new(i32::from(crate::click_through::is_enabled())),
+        );
+        click_through_props.insert("enabled".to_string(), Value::new(true));
+        items.push(Value::new((
+            MENU_CLICK_THROUGH,
+            click_through_props,
+            Vec::<Value>::new(),
+        )));
+
+        // Auto-paste; when off, segments are only copied to the clipboard
+        let mut auto_paste_props = HashMap::new();
+        auto_paste_props.insert("label".to_string(), Value::new("Auto-paste"));
+        auto_paste_props.insert("toggle-type".to_string(), Value::new("checkmark"));
+        auto_paste_props.insert(
+            "toggle-state".to_string(),
+            Value::new(i32::from(crate::auto_paste::enabled())),
+        );
+        auto_paste_props.insert("enabled".to_string(), Value::new(true));
+        items.push(Value::new((
+            MENU_AUTO_PASTE,
+            auto_paste_props,
+            Vec::<Value>::new(),
+        )));
+
+        // Privacy wipe
+        let mut wipe_props = HashMap::new();
+        wipe_props.insert("label".to_string(), Value::new("Wipe Session"));
+        wipe_props.insert("enabled".to_string(), Value::new(true));
+        let wipe_item = Value::new((MENU_PRIVACY_WIPE, wipe_props, Vec::<Value>::new()));
+        items.push(wipe_item);
+
+        // Subtitle export of the last recording
+        let mut export_props = HashMap::new();
+        export_props.insert("label".to_string(), Value::new("Export Subtitles"));
+        export_props.insert("enabled".to_string(), Value::new(true));
+        let export_item = Value::new((MENU_EXPORT_SUBTITLES, export_props, Vec::<Value>::new()));
+        items.push(export_item);
+
+        let mut settings_props = HashMap::new();
+        settings_props.insert("label".to_string(), Value::new("Settings"));
+        settings_props.insert("enabled".to_string(), Value::new(true));
+        items.push(Value::new((
+            MENU_OPEN_SETTINGS,
+            settings_props,
+            Vec::<Value>::new(),
+        )));
+
         // Item 3: Separator
         let mut item3_props = HashMap::new();
         item3_props.insert("type".to_string(), Value::new("separator"));
@@ -193,7 +387,7 @@ impl DbusMenu {
         let layout = (0, root_props, items);
 
         // Revision number (increment when menu changes)
-        (1u32, layout)
+        (LAYOUT_REVISION.load(Ordering::Relaxed), layout)
     }
 
     /// Handle menu item activation
@@ -215,6 +409,13 @@ impl DbusMenu {
                 }
             }
             MENU_TOGGLE_MODE => Some(TrayCommand::SwitchMode),
+            MENU_CLICK_THROUGH => Some(TrayCommand::ToggleClickThrough),
+            MENU_AUTO_PASTE => Some(TrayCommand::ToggleAutoPaste),
+            MENU_PRIVACY_WIPE => Some(TrayCommand::PrivacyWipe),
+            MENU_EXPORT_SUBTITLES => Some(TrayCommand::ExportSubtitles),
+            MENU_COPY_LAST_SEGMENT => Some(TrayCommand::CopyLastSegment),
+            MENU_COPY_TRANSCRIPT => Some(TrayCommand::CopyTranscript),
+            MENU_OPEN_SETTINGS => Some(TrayCommand::OpenSettings),
             MENU_QUIT => Some(TrayCommand::Quit),
             _ => None,
         };
@@ -224,6 +425,14 @@ impl DbusMenu {
         }
     }
 
+    /// Tells the tray host to fetch the layout again
+    #[zbus(signal)]
+    async fn layout_updated(
+        emitter: &SignalEmitter<'_>,
+        revision: u32,
+        parent: i32,
+    ) -> zbus::Result<()>;
+
     /// DBusMenu version
     #[zbus(property)]
     async fn version(&self) -> u32 {
@@ -286,20 +495,43 @@ pub async fn run_system_tray(
 
     tokio::spawn(async move {
         // Keep the connection alive for the lifetime of the app
-        let _conn = conn;
+        let conn = conn;
+        // Segments can arrive several times a second; the menu is re-announced
+        // at most once a second so the tray host does not flicker
+        let mut announce = tokio::time::interval(Duration::from_secs(1));
+        let mut announced_revision = LAYOUT_REVISION.load(Ordering::Relaxed);
 
         while running.load(Ordering::Relaxed) {
-            if let Some(update) = update_rx.recv().await {
-                match update {
-                    TrayUpdate::Recording(recording) => {
-                        is_recording_clone.store(recording, Ordering::Relaxed);
-                        // Note: Properties will update when queried by the tray
+            tokio::select! {
+                update = update_rx.recv() => {
+                    let Some(update) = update else {
+                        break;
+                    };
+                    match update {
+                        TrayUpdate::Recording(recording) => {
+                            is_recording_clone.store(recording, Ordering::Relaxed);
+                        }
+                        TrayUpdate::Mode(mode) => {
+                            transcription_mode_clone.store(mode.as_u8(), Ordering::Relaxed);
+                        }
+                        TrayUpdate::Transcript(text) => record_segment(&text),
+                        // The menu reads the state itself; only the layout changes
+                        TrayUpdate::ClickThrough(_) => {}
+                        TrayUpdate::AutoPaste(_) => {
+                            if let Err(e) = announce_auto_paste(&conn).await {
+                                tracing::warn!("Failed to update tray icon: {}", e);
+                            }
+                        }
                     }
-                    TrayUpdate::Mode(mode) => {
-                        transcription_mode_clone.store(mode.as_u8(), Ordering::Relaxed);
-                    }
-                    TrayUpdate::Transcript(_text) => {
-                        // No longer displaying transcript preview
+                    bump_layout_revision();
+                }
+                _ = announce.tick() => {
+                    let revision = LAYOUT_REVISION.load(Ordering::Relaxed);
+                    if revision != announced_revision {
+                        announced_revision = revision;
+                        if let Err(e) = announce_layout(&conn, revision).await {
+                            tracing::warn!("Failed to update tray menu: {}", e);
+                        }
                     }
                 }
             }
@@ -309,6 +541,24 @@ pub async fn run_system_tray(
     Ok((update_tx, command_rx))
 }
 
+async fn announce_layout(conn: &Connection, revision: u32) -> zbus::Result<()> {
+    let menu = conn
+        .object_server()
+        .interface::<_, DbusMenu>("/StatusNotifierItem/menu")
+        .await?;
+    DbusMenu::layout_updated(menu.signal_emitter(), revision, 0).await
+}
+
+/// Have the tray host re-read the tooltip and overlay icon, which show the auto-paste state
+async fn announce_auto_paste(conn: &Connection) -> zbus::Result<()> {
+    let item = conn
+        .object_server()
+        .interface::<_, StatusNotifierItem>("/StatusNotifierItem")
+        .await?;
+    StatusNotifierItem::new_tool_tip(item.signal_emitter()).await?;
+    StatusNotifierItem::new_overlay_icon(item.signal_emitter()).await
+}
+
 /// Register our tray icon with the StatusNotifierWatcher
 async fn register_with_watcher(conn: &Connection) -> Result<()> {
     let proxy = zbus::Proxy::new(
@@ -329,3 +579,18 @@ async fn register_with_watcher(conn: &Connection) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_is_shortened_to_sixty_chars() {
+        assert_eq!(segment_preview(""), "No transcript yet");
+        assert_eq!(segment_preview("Hello there."), "“Hello there.”");
+        let long = "word ".repeat(20);
+        let preview = segment_preview(&long);
+        assert!(preview.ends_with("…”"));
+        assert_eq!(preview.chars().count(), PREVIEW_CHARS + 2);
+    }
+}
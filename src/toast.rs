@@ -0,0 +1,42 @@
+//! Short-lived notices shown in the overlay's status bar.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+/// How long a toast stays visible once it reaches the front of the queue
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+const MAX_QUEUED_TOASTS: usize = 4;
+
+struct Toast {
+    text: String,
+    shown_at: Option<Instant>,
+}
+
+static TOASTS: Mutex<VecDeque<Toast>> = Mutex::new(VecDeque::new());
+
+/// Queue a toast; the oldest queued toast is dropped if too many are pending
+pub fn show(text: impl Into<String>) {
+    let mut toasts = TOASTS.lock();
+    if toasts.len() >= MAX_QUEUED_TOASTS {
+        toasts.pop_front();
+    }
+    toasts.push_back(Toast {
+        text: text.into(),
+        shown_at: None,
+    });
+}
+
+/// Text of the toast to display now, if any
+pub fn current() -> Option<String> {
+    let mut toasts = TOASTS.lock();
+    while let Some(front) = toasts.front_mut() {
+        let shown_at = *front.shown_at.get_or_insert_with(Instant::now);
+        if shown_at.elapsed() < TOAST_DURATION {
+            return Some(front.text.clone());
+        }
+        toasts.pop_front();
+    }
+    None
+}
@@ -0,0 +1,137 @@
+//! Bounded in-memory transcript history.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+
+use crate::config::TranscriptConfig;
+
+struct Spill {
+    path: PathBuf,
+    /// History size that triggers a spill; 0 keeps everything in memory
+    max_bytes: usize,
+    /// Text has been written to `path` since start (or the last reset)
+    spilled: bool,
+}
+
+static SPILL: Mutex<Option<Spill>> = Mutex::new(None);
+
+pub fn init(config: &TranscriptConfig) {
+    let path = PathBuf::from(&config.output_dir)
+        .join(format!(".sonori-history-{}.txt", std::process::id()));
+    *SPILL.lock() = Some(Spill {
+        path,
+        max_bytes: config.history_memory_kb as usize * 1024,
+        spilled: false,
+    });
+}
+
+/// Byte offset of the space where `text` is cut so that at most `keep` bytes stay
+fn split_point(text: &str, keep: usize) -> Option<usize> {
+    let mut cut = text.len().checked_sub(keep)?;
+    while !text.is_char_boundary(cut) {
+        cut += 1;
+    }
+    text[cut..].find(' ').map(|space| cut + space)
+}
+
+/// Move the oldest text of `history` to the spill file once it is over the limit
+pub fn enforce(history: &mut String) {
+    let mut spill = SPILL.lock();
+    let Some(spill) = spill.as_mut().filter(|s| s.max_bytes > 0) else {
+        return;
+    };
+    if history.len() <= spill.max_bytes {
+        return;
+    }
+    // Keep three quarters so the next segments do not spill again right away
+    let Some(cut) = split_point(history, spill.max_bytes / 4 * 3) else {
+        return;
+    };
+
+    let result = (|| {
+        if let Some(parent) = spill.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&spill.path)?;
+        if spill.spilled {
+            file.write_all(b" ")?;
+        }
+        file.write_all(history[..cut].as_bytes())
+    })();
+    match result {
+        Ok(()) => {
+            spill.spilled = true;
+            history.drain(..=cut);
+        }
+        // Keep the text in memory rather than losing it
+        Err(e) => tracing::warn!(
+            "Failed to spill transcript history to {}: {}",
+            spill.path.display(),
+            e
+        ),
+    }
+}
+
+/// Whether older text has been moved out of the in-memory history
+pub fn is_truncated() -> bool {
+    SPILL.lock().as_ref().is_some_and(|s| s.spilled)
+}
+
+/// The whole transcript: spilled text followed by the in-memory `tail`
+pub fn full_transcript(tail: &str) -> String {
+    let spill = SPILL.lock();
+    let Some(spill) = spill.as_ref().filter(|s| s.spilled) else {
+        return tail.to_string();
+    };
+    match fs::read_to_string(&spill.path) {
+        Ok(mut text) => {
+            if !tail.is_empty() {
+                text.push(' ');
+                text.push_str(tail);
+            }
+            text
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to read spilled transcript {}: {}",
+                spill.path.display(),
+                e
+            );
+            tail.to_string()
+        }
+    }
+}
+
+/// Forget spilled text (transcript reset, privacy wipe and shutdown)
+pub fn clear() {
+    if let Some(spill) = SPILL.lock().as_mut().filter(|s| s.spilled) {
+        spill.spilled = false;
+        if let Err(e) = fs::remove_file(&spill.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("Failed to remove {}: {}", spill.path.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_point_keeps_whole_words() {
+        let text = "one two three four";
+        // 7 bytes kept would start inside "three"; cut at the following space
+        assert_eq!(split_point(text, 7), Some(13));
+        assert_eq!(&text[13 + 1..], "four");
+        assert_eq!(split_point(text, 100), None);
+        assert_eq!(split_point("one twothreefour", 4), None);
+        assert_eq!(split_point("héé x", 3), Some(5));
+    }
+}
@@ -0,0 +1,315 @@
+//! Transcript streaming socket (`stream_config`).
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+
+use crate::config::StreamConfig;
+use crate::ipc::{IpcResponse, SessionControl};
+
+/// Lines queued per client before further lines are dropped
+const CLIENT_QUEUE: usize = 256;
+
+/// One delivered segment as sent to clients
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamSegment {
+    pub session_id: Option<String>,
+    pub text: String,
+    /// RFC 3339 local time the segment was delivered
+    pub timestamp: String,
+    /// "manual" or "realtime"
+    pub mode: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum StreamCommand {
+    Start,
+    Stop,
+    GetTranscript,
+}
+
+#[derive(Serialize)]
+struct TranscriptReply<'a> {
+    success: bool,
+    transcript: &'a str,
+}
+
+struct Client {
+    id: u64,
+    line_tx: mpsc::Sender<String>,
+    /// Lines dropped since the last warning
+    dropped: u64,
+}
+
+struct Broadcast {
+    control: SessionControl,
+    clients: Vec<Client>,
+    next_id: u64,
+}
+
+/// Set while the socket is being served
+static BROADCAST: Mutex<Option<Broadcast>> = Mutex::new(None);
+
+/// Default socket path: `$XDG_RUNTIME_DIR/sonori.sock`
+pub fn default_socket_path() -> PathBuf {
+    crate::ipc::runtime_dir().join("sonori.sock")
+}
+
+/// Send a delivered segment to every client; a no-op while streaming is off
+pub fn record_segment(session_id: Option<&str>, text: &str) {
+    let mut broadcast = BROADCAST.lock();
+    let Some(broadcast) = broadcast.as_mut() else {
+        return;
+    };
+    if broadcast.clients.is_empty() {
+        return;
+    }
+
+    let segment = StreamSegment {
+        session_id: session_id.map(str::to_string),
+        text: text.to_string(),
+        timestamp: chrono::Local::now().to_rfc3339(),
+        mode: broadcast
+            .control
+            .status()
+            .status
+            .map(|status| status.mode)
+            .unwrap_or_default(),
+    };
+    let line = match serde_json::to_string(&segment) {
+        Ok(line) => line,
+        Err(e) => {
+            tracing::warn!("Failed to encode transcript segment: {}", e);
+            return;
+        }
+    };
+
+    broadcast
+        .clients
+        .retain_mut(|client| match client.line_tx.try_send(line.clone()) {
+            Ok(()) => {
+                if client.dropped > 0 {
+                    tracing::warn!(
+                        "Transcript stream client {} caught up after {} dropped line(s)",
+                        client.id,
+                        client.dropped
+                    );
+                    client.dropped = 0;
+                }
+                true
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                if client.dropped == 0 {
+                    tracing::warn!(
+                        "Transcript stream client {} is not keeping up; dropping segments",
+                        client.id
+                    );
+                }
+                client.dropped += 1;
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+}
+
+/// Serve the streaming socket until shutdown
+pub async fn run(
+    config: &StreamConfig,
+    control: SessionControl,
+    transcript: Arc<RwLock<String>>,
+    running: Arc<AtomicBool>,
+) -> Result<()> {
+    let socket_path = config
+        .socket_path
+        .clone()
+        .unwrap_or_else(default_socket_path);
+    let listener = bind(&socket_path).await?;
+    tracing::info!("Transcript stream listening on {:?}", socket_path);
+
+    *BROADCAST.lock() = Some(Broadcast {
+        control: control.clone(),
+        clients: Vec::new(),
+        next_id: 1,
+    });
+
+    loop {
+        tokio::select! {
+            accept_result = listener.accept() => {
+                match accept_result {
+                    Ok((stream, _)) => {
+                        let (id, line_rx, line_tx) = {
+                            let (line_tx, line_rx) = mpsc::channel(CLIENT_QUEUE);
+                            let mut broadcast = BROADCAST.lock();
+                            let Some(broadcast) = broadcast.as_mut() else {
+                                break;
+                            };
+                            let id = broadcast.next_id;
+                            broadcast.next_id += 1;
+                            broadcast.clients.push(Client {
+                                id,
+                                line_tx: line_tx.clone(),
+                                dropped: 0,
+                            });
+                            (id, line_rx, line_tx)
+                        };
+                        tokio::spawn(serve_client(
+                            stream,
+                            line_tx,
+                            line_rx,
+                            control.clone(),
+                            transcript.clone(),
+                            id,
+                        ));
+                    }
+                    Err(e) => tracing::warn!("Transcript stream accept error: {}", e),
+                }
+            }
+            _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Dropping the senders ends every client's writer
+    *BROADCAST.lock() = None;
+    let _ = std::fs::remove_file(&socket_path);
+    tracing::info!("Transcript stream shut down");
+    Ok(())
+}
+
+/// Bind the socket, replacing a stale one but never one a live process answers on
+async fn bind(socket_path: &Path) -> Result<UnixListener> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).await.is_ok() {
+            bail!(
+                "{} is in use by another running process; set stream_config.socket_path to another path",
+                socket_path.display()
+            );
+        }
+        std::fs::remove_file(socket_path)
+            .with_context(|| format!("Failed to remove stale socket {}", socket_path.display()))?;
+    }
+    if let Some(socket_dir) = socket_path.parent() {
+        std::fs::create_dir_all(socket_dir).context("Failed to create socket directory")?;
+    }
+
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("Failed to bind {}", socket_path.display()))?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))
+        .context("Failed to set socket permissions")?;
+    Ok(listener)
+}
+
+async fn serve_client(
+    stream: UnixStream,
+    line_tx: mpsc::Sender<String>,
+    mut line_rx: mpsc::Receiver<String>,
+    control: SessionControl,
+    transcript: Arc<RwLock<String>>,
+    id: u64,
+) {
+    let (reader, mut writer) = stream.into_split();
+
+    // Segments and replies share the queue, so replies keep their place in the stream
+    let write_task = tokio::spawn(async move {
+        while let Some(mut line) = line_rx.recv().await {
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(reader).lines();
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::warn!("Transcript stream client {} read error: {}", id, e);
+                break;
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let reply = match serde_json::from_str::<StreamCommand>(line) {
+            Ok(command) => execute(command, &control, &transcript).await,
+            Err(e) => encode(&IpcResponse::error(format!("Invalid command: {}", e))),
+        };
+        if line_tx.send(reply).await.is_err() {
+            break;
+        }
+    }
+
+    // The writer ends once both senders are gone
+    remove_client(id);
+    drop(line_tx);
+    let _ = write_task.await;
+}
+
+fn remove_client(id: u64) {
+    if let Some(broadcast) = BROADCAST.lock().as_mut() {
+        broadcast.clients.retain(|client| client.id != id);
+    }
+}
+
+async fn execute(
+    command: StreamCommand,
+    control: &SessionControl,
+    transcript: &RwLock<String>,
+) -> String {
+    match command {
+        StreamCommand::Start => encode(&control.start().await),
+        StreamCommand::Stop => encode(&control.stop().await),
+        StreamCommand::GetTranscript => {
+            let transcript = crate::transcript_spill::full_transcript(&transcript.read());
+            encode(&TranscriptReply {
+                success: true,
+                transcript: &transcript,
+            })
+        }
+    }
+}
+
+fn encode(reply: &impl Serialize) -> String {
+    serde_json::to_string(reply).unwrap_or_else(|e| {
+        format!(
+            r#"{{"success":false,"message":"Failed to encode reply: {}"}}"#,
+            e
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn replaces_stale_sockets_but_not_live_ones() {
+        let socket_path =
+            std::env::temp_dir().join(format!("sonori-stream-test-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&socket_path);
+
+        // A socket file nobody listens on is left behind by a crashed instance
+        drop(std::os::unix::net::UnixListener::bind(&socket_path).unwrap());
+        let listener = bind(&socket_path).await.unwrap();
+        assert!(bind(&socket_path).await.is_err());
+
+        drop(listener);
+        let _ = std::fs::remove_file(&socket_path);
+    }
+}
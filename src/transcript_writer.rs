@@ -1,6 +1,14 @@
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use parking_lot::Mutex;
+
+use crate::config::{SubtitleFormat, TranscriptConfig, TranscriptRotation};
 
 /// Append a transcript entry to the history file with timestamp
 pub fn append_to_transcript_history(
@@ -27,3 +35,492 @@ pub fn append_to_transcript_history(
     file.write_all(entry.as_bytes())?;
     Ok(())
 }
+
+/// Current size of the history file in bytes (0 if it does not exist yet)
+pub fn transcript_history_len(history_path: &str) -> u64 {
+    fs::metadata(history_path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Cut the history file back to `len` bytes, dropping entries appended since
+pub fn truncate_transcript_history(history_path: &str, len: u64) -> Result<(), std::io::Error> {
+    match OpenOptions::new().write(true).open(history_path) {
+        Ok(file) => {
+            if file.metadata()?.len() > len {
+                file.set_len(len)?;
+            }
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// One line of the transcript history file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptHistoryEntry {
+    /// "YYYY-MM-DD HH:MM:SS" as written by `append_to_transcript_history`
+    pub timestamp: String,
+    pub text: String,
+}
+
+impl TranscriptHistoryEntry {
+    pub fn date(&self) -> &str {
+        self.timestamp.split(' ').next().unwrap_or("")
+    }
+
+    pub fn time(&self) -> &str {
+        self.timestamp.split(' ').nth(1).unwrap_or("")
+    }
+}
+
+/// Parse one history line; lines without a timestamp are kept with an empty one
+pub fn parse_history_line(line: &str) -> Option<TranscriptHistoryEntry> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    let parsed = line
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+        .map(|(timestamp, text)| TranscriptHistoryEntry {
+            timestamp: timestamp.to_string(),
+            text: text.to_string(),
+        });
+    Some(parsed.unwrap_or_else(|| TranscriptHistoryEntry {
+        timestamp: String::new(),
+        text: line.to_string(),
+    }))
+}
+
+/// Read all entries of the history file, oldest first
+pub fn read_transcript_history(
+    history_path: &str,
+) -> Result<Vec<TranscriptHistoryEntry>, std::io::Error> {
+    match fs::read_to_string(history_path) {
+        Ok(contents) => Ok(contents.lines().filter_map(parse_history_line).collect()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Expand the strftime placeholders of a file name template; an invalid
+/// template falls back to the date so segments are never lost
+fn expand_filename_template(template: &str, now: DateTime<Local>) -> String {
+    use std::fmt::Write as _;
+    let mut name = String::new();
+    if write!(name, "{}", now.format(template)).is_err() || name.trim().is_empty() {
+        tracing::warn!(
+            "Invalid transcript filename_template '{}'; using the date instead",
+            template
+        );
+        name = now.format("sonori-%Y-%m-%d.txt").to_string();
+    }
+    name
+}
+
+/// Buffered writer behind `transcript_config`: segments are appended to the
+/// active file on each flush, and a new file is started per day or session
+pub struct TranscriptAutoSave {
+    dir: PathBuf,
+    filename_template: String,
+    rotation: TranscriptRotation,
+    /// Day ("YYYY-MM-DD") or session number the active file belongs to
+    active_key: Option<String>,
+    active_path: Option<PathBuf>,
+    session: u64,
+    buffer: String,
+    /// Length of each file written since start (or the last wipe) before it was touched
+    wipe_marks: Vec<(PathBuf, u64)>,
+}
+
+impl TranscriptAutoSave {
+    pub fn new(config: &TranscriptConfig) -> Self {
+        Self {
+            dir: PathBuf::from(&config.output_dir),
+            filename_template: config.filename_template.clone(),
+            rotation: config.rotation,
+            active_key: None,
+            active_path: None,
+            session: 0,
+            buffer: String::new(),
+            wipe_marks: Vec::new(),
+        }
+    }
+
+    /// A new recording session started; with session rotation the next segment opens a new file
+    pub fn start_session(&mut self) {
+        self.session += 1;
+    }
+
+    fn rotation_key(&self, now: DateTime<Local>) -> String {
+        match self.rotation {
+            TranscriptRotation::Daily => now.format("%Y-%m-%d").to_string(),
+            TranscriptRotation::Session => self.session.to_string(),
+        }
+    }
+
+    /// Buffer a segment, first flushing the previous file if it is due for rotation.
+    /// `audio_file` names the archived recording the segment came from.
+    pub fn append(
+        &mut self,
+        text: &str,
+        audio_file: Option<&str>,
+        now: DateTime<Local>,
+    ) -> Result<(), std::io::Error> {
+        if text.trim().is_empty() {
+            return Ok(());
+        }
+        let key = self.rotation_key(now);
+        if self.active_key.as_deref() != Some(key.as_str()) {
+            self.flush()?;
+            let path = self
+                .dir
+                .join(expand_filename_template(&self.filename_template, now));
+            tracing::info!("Transcript auto-save: writing to {}", path.display());
+            self.active_key = Some(key);
+            self.active_path = Some(path);
+        }
+        self.buffer.push_str(&format!(
+            "[{}] {}",
+            now.format("%Y-%m-%d %H:%M:%S"),
+            text.trim()
+        ));
+        if let Some(audio_file) = audio_file {
+            self.buffer.push_str(&format!(" (audio: {})", audio_file));
+        }
+        self.buffer.push('\n');
+        Ok(())
+    }
+
+    /// Write buffered text to the active file
+    pub fn flush(&mut self) -> Result<(), std::io::Error> {
+        let Some(path) = self.active_path.as_ref() else {
+            return Ok(());
+        };
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        if !self.wipe_marks.iter().any(|(marked, _)| marked == path) {
+            self.wipe_marks.push((
+                path.clone(),
+                transcript_history_len(&path.to_string_lossy()),
+            ));
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(self.buffer.as_bytes())?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Drop buffered text and cut every file written since start (or the last wipe) back
+    pub fn wipe(&mut self) {
+        self.buffer.clear();
+        for (path, len) in self.wipe_marks.drain(..) {
+            if let Err(e) = truncate_transcript_history(&path.to_string_lossy(), len) {
+                tracing::warn!("Privacy wipe: failed to truncate {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+static AUTO_SAVE: Mutex<Option<TranscriptAutoSave>> = Mutex::new(None);
+
+/// Enable the auto-save when `transcript_config.enabled` is set
+pub fn init_auto_save(config: &TranscriptConfig) {
+    *AUTO_SAVE.lock() = config.enabled.then(|| TranscriptAutoSave::new(config));
+}
+
+/// Buffer a delivered segment for the auto-save (no-op when it is disabled)
+pub fn auto_save_segment(text: &str) {
+    if let Some(auto_save) = AUTO_SAVE.lock().as_mut() {
+        let audio_file = crate::recording_archive::current_file_name();
+        if let Err(e) = auto_save.append(text, audio_file.as_deref(), Local::now()) {
+            tracing::warn!("Transcript auto-save failed: {}", e);
+        }
+    }
+}
+
+pub fn auto_save_session_started() {
+    if let Some(auto_save) = AUTO_SAVE.lock().as_mut() {
+        auto_save.start_session();
+    }
+}
+
+/// Write buffered segments now; called on the flush interval and on shutdown
+pub fn flush_auto_save() {
+    if let Some(auto_save) = AUTO_SAVE.lock().as_mut() {
+        if let Err(e) = auto_save.flush() {
+            tracing::warn!("Transcript auto-save flush failed: {}", e);
+        }
+    }
+}
+
+pub fn wipe_auto_save() {
+    if let Some(auto_save) = AUTO_SAVE.lock().as_mut() {
+        auto_save.wipe();
+    }
+}
+
+/// Start a new session whenever recording starts and flush every `interval`
+/// while running; shutdown paths flush once more
+pub async fn run_auto_save_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    interval: Duration,
+) {
+    let tick = Duration::from_millis(100);
+    let mut ticker = tokio::time::interval(tick);
+    let mut since_flush = Duration::ZERO;
+    let mut was_recording = false;
+    while running.load(Ordering::Relaxed) {
+        ticker.tick().await;
+        let is_recording = recording.load(Ordering::Relaxed);
+        if is_recording && !was_recording {
+            auto_save_session_started();
+        }
+        was_recording = is_recording;
+
+        since_flush += tick;
+        if since_flush >= interval {
+            since_flush = Duration::ZERO;
+            flush_auto_save();
+        }
+    }
+}
+
+/// Shortest time a subtitle cue stays on screen
+const MIN_CUE_DURATION: Duration = Duration::from_millis(700);
+/// Lines shown together in one cue
+const LINES_PER_CUE: usize = 2;
+
+/// One transcript segment with times relative to the start of the recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleSegment {
+    pub start: Duration,
+    pub end: Duration,
+    pub text: String,
+}
+
+impl SubtitleFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+
+    fn timestamp(self, time: Duration) -> String {
+        let millis = time.as_millis();
+        let (hours, minutes, seconds) =
+            (millis / 3_600_000, millis / 60_000 % 60, millis / 1000 % 60);
+        let separator = match self {
+            SubtitleFormat::Srt => ',',
+            SubtitleFormat::Vtt => '.',
+        };
+        format!(
+            "{:02}:{:02}:{:02}{}{:03}",
+            hours,
+            minutes,
+            seconds,
+            separator,
+            millis % 1000
+        )
+    }
+}
+
+/// Greedy word wrap; a single word longer than `max_len` gets a line of its own
+fn wrap_words(text: &str, max_len: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_len {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Split segments into cues of at most `LINES_PER_CUE` wrapped lines.
+///
+/// Segments may overlap or arrive out of order after chunk-overlap dedup, so
+/// each one starts no earlier than the previous one ended and lasts at least
+/// `MIN_CUE_DURATION`; a long segment's time is shared by its cues in
+/// proportion to their length.
+fn build_cues(segments: &[SubtitleSegment], max_line_length: usize) -> Vec<SubtitleSegment> {
+    let max_line_length = max_line_length.max(1);
+    let mut cues = Vec::new();
+    let mut previous_end = Duration::ZERO;
+    for segment in segments {
+        let lines = wrap_words(&segment.text, max_line_length);
+        if lines.is_empty() {
+            continue;
+        }
+        let start = segment.start.max(previous_end);
+        let end = segment.end.max(start + MIN_CUE_DURATION);
+        let groups: Vec<String> = lines
+            .chunks(LINES_PER_CUE)
+            .map(|group| group.join("\n"))
+            .collect();
+        let cue_count = groups.len();
+        let total_chars: usize = groups.iter().map(|g| g.chars().count()).sum();
+        let span = end - start;
+        let mut cue_start = start;
+        let mut chars_through = 0;
+        for (index, text) in groups.into_iter().enumerate() {
+            chars_through += text.chars().count();
+            let cue_end = if index + 1 == cue_count {
+                end
+            } else {
+                start + span.mul_f64(chars_through as f64 / total_chars.max(1) as f64)
+            };
+            cues.push(SubtitleSegment {
+                start: cue_start,
+                end: cue_end,
+                text,
+            });
+            cue_start = cue_end;
+        }
+        previous_end = end;
+    }
+    cues
+}
+
+/// Render segments as an SRT or WebVTT document
+pub fn format_subtitles(
+    segments: &[SubtitleSegment],
+    format: SubtitleFormat,
+    max_line_length: usize,
+) -> String {
+    let mut out = String::new();
+    if format == SubtitleFormat::Vtt {
+        out.push_str("WEBVTT\n\n");
+    }
+    for (index, cue) in build_cues(segments, max_line_length).iter().enumerate() {
+        if format == SubtitleFormat::Srt {
+            out.push_str(&format!("{}\n", index + 1));
+        }
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format.timestamp(cue.start),
+            format.timestamp(cue.end),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// Write segments to a new timestamped subtitle file in `dir` and return its path
+pub fn export_subtitles(
+    segments: &[SubtitleSegment],
+    format: SubtitleFormat,
+    max_line_length: usize,
+    dir: &Path,
+) -> Result<PathBuf, std::io::Error> {
+    if segments
+        .iter()
+        .all(|segment| segment.text.trim().is_empty())
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "no transcript to export",
+        ));
+    }
+    fs::create_dir_all(dir)?;
+    let name = format!(
+        "sonori-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    );
+    let path = dir.join(name);
+    fs::write(&path, format_subtitles(segments, format, max_line_length))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: u64, end_ms: u64, text: &str) -> SubtitleSegment {
+        SubtitleSegment {
+            start: Duration::from_millis(start_ms),
+            end: Duration::from_millis(end_ms),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn auto_save_rotates_per_day_and_session() {
+        use chrono::TimeZone;
+        let dir = std::env::temp_dir().join(format!("sonori-autosave-{}", std::process::id()));
+        let config = TranscriptConfig {
+            enabled: true,
+            output_dir: dir.to_string_lossy().to_string(),
+            filename_template: "%Y-%m-%d.txt".to_string(),
+            flush_interval_secs: 5,
+            rotation: TranscriptRotation::Daily,
+            history_memory_kb: 0,
+        };
+        let day1 = Local.with_ymd_and_hms(2026, 3, 1, 23, 59, 0).unwrap();
+        let day2 = Local.with_ymd_and_hms(2026, 3, 2, 0, 1, 0).unwrap();
+
+        let mut auto_save = TranscriptAutoSave::new(&config);
+        auto_save.append("first", None, day1).unwrap();
+        auto_save.start_session();
+        auto_save
+            .append("second", Some("recording_20260301_235800.wav"), day1)
+            .unwrap();
+        auto_save.append("third", None, day2).unwrap();
+        auto_save.flush().unwrap();
+        let first = fs::read_to_string(dir.join("2026-03-01.txt")).unwrap();
+        assert_eq!(first.lines().count(), 2);
+        assert!(first.ends_with("] second (audio: recording_20260301_235800.wav)\n"));
+        assert!(fs::read_to_string(dir.join("2026-03-02.txt"))
+            .unwrap()
+            .ends_with("] third\n"));
+
+        auto_save.wipe();
+        assert!(fs::read_to_string(dir.join("2026-03-02.txt"))
+            .unwrap()
+            .is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn srt_and_vtt_cues() {
+        let segments = [
+            segment(0, 1500, "hello there"),
+            segment(1500, 3_723_004, "bye"),
+        ];
+        assert_eq!(
+            format_subtitles(&segments, SubtitleFormat::Srt, 42),
+            "1\n00:00:00,000 --> 00:00:01,500\nhello there\n\n\
+             2\n00:00:01,500 --> 01:02:03,004\nbye\n\n"
+        );
+        assert!(format_subtitles(&segments, SubtitleFormat::Vtt, 42)
+            .starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nhello there\n\n"));
+    }
+
+    #[test]
+    fn overlapping_segments_are_pushed_back() {
+        let cues = build_cues(&[segment(0, 2000, "one"), segment(1500, 1600, "two")], 42);
+        assert_eq!(cues[1].start, Duration::from_millis(2000));
+        assert_eq!(cues[1].end, Duration::from_millis(2000) + MIN_CUE_DURATION);
+    }
+
+    #[test]
+    fn long_segments_wrap_into_several_cues() {
+        let cues = build_cues(&[segment(0, 6000, "aaaa bbbb cccc dddd eeee")], 9);
+        let texts: Vec<&str> = cues.iter().map(|cue| cue.text.as_str()).collect();
+        assert_eq!(texts, ["aaaa bbbb\ncccc dddd", "eeee"]);
+        assert_eq!(cues[0].end, cues[1].start);
+        assert_eq!(cues[1].end, Duration::from_millis(6000));
+    }
+}
@@ -0,0 +1,739 @@
+//! Machine-readable statistics for one run, for comparing backends and settings.
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use speechcore::{AudioVisualizationData, ProcessingState};
+
+/// Version of the export schema. Bump when fields change meaning or are removed;
+/// new fields are only ever appended.
+pub const STATS_SCHEMA_VERSION: u32 = 1;
+
+/// Column names of `TranscriptionStats::to_csv_row`
+pub const CSV_HEADER: &str = "v,backend,model,segments,words,audio_secs,speech_secs,\
+inference_secs,rtf,avg_confidence,dropped_segments,hallucinations,latency_p50_ms,\
+latency_p95_ms,replacements";
+
+/// Poll interval while recording or transcribing; bounds the precision of the
+/// polled stage boundaries
+const ACTIVE_TICK: Duration = Duration::from_millis(20);
+const IDLE_TICK: Duration = Duration::from_millis(100);
+
+/// Segments waiting for their injection; older ones are dropped unfinished
+const MAX_OPEN_TIMELINES: usize = 64;
+
+/// One delivered segment (`debug_config.stats_detailed`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SegmentRecord {
+    /// Speech detected since the previous segment
+    pub duration_secs: f64,
+    /// From the end of that speech to delivery; `None` if speech had not ended
+    pub latency_secs: Option<f64>,
+    pub words: usize,
+    pub backend: String,
+}
+
+/// Per-run aggregates as exported
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsExport {
+    pub v: u32,
+    pub backend: String,
+    pub model: String,
+    pub segments: usize,
+    pub words: usize,
+    /// Wall-clock recording time
+    pub audio_secs: f64,
+    /// Time in which voice activity was detected
+    pub speech_secs: f64,
+    /// Time the backend spent transcribing
+    pub inference_secs: f64,
+    /// Real-time factor (inference time / recording time)
+    pub rtf: Option<f64>,
+    /// Mean segment confidence; `None` while the backend reports none
+    pub avg_confidence: Option<f64>,
+    /// Final segments discarded because their session had ended
+    pub dropped_segments: u32,
+    /// Final segments dropped by the hallucination filter
+    pub hallucinations: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segment_records: Option<Vec<SegmentRecord>>,
+    pub latency: LatencyBreakdown,
+    /// Words or phrases changed by the replacement list and profanity masking
+    pub replacements: u64,
+}
+
+/// Percentiles of one stage over the run, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct StagePercentiles {
+    pub count: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+}
+
+/// Per-stage latency percentiles; `None` for a stage without samples
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyBreakdown {
+    /// End of speech to transcription start
+    pub queue: Option<StagePercentiles>,
+    /// Transcription start to end
+    pub inference: Option<StagePercentiles>,
+    /// Transcription end to the final text arriving
+    pub broadcast: Option<StagePercentiles>,
+    /// Arrival to delivery (post-processing and Magic Mode)
+    pub post_process: Option<StagePercentiles>,
+    /// Delivery to the finished paste or clipboard copy
+    pub paste: Option<StagePercentiles>,
+    /// End of speech to the last stage the segment reached
+    pub total: Option<StagePercentiles>,
+}
+
+/// Stage durations of one segment in milliseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct StageDurations {
+    queue: Option<f64>,
+    inference: Option<f64>,
+    broadcast: Option<f64>,
+    post_process: Option<f64>,
+    paste: Option<f64>,
+    total: Option<f64>,
+}
+
+impl StageDurations {
+    fn fields(&self) -> [(&'static str, Option<f64>); 6] {
+        [
+            ("queue", self.queue),
+            ("inference", self.inference),
+            ("broadcast", self.broadcast),
+            ("post", self.post_process),
+            ("paste", self.paste),
+            ("total", self.total),
+        ]
+    }
+
+    /// One line for `debug_config.log_latency_per_segment`
+    fn log_line(&self, id: u64) -> String {
+        let stages: Vec<String> = self
+            .fields()
+            .iter()
+            .map(|(name, ms)| match ms {
+                Some(ms) => format!("{} {:.0} ms", name, ms),
+                None => format!("{} -", name),
+            })
+            .collect();
+        format!("Latency #{}: {}", id, stages.join(", "))
+    }
+}
+
+/// Timestamps of one segment on its way from speech to injection
+#[derive(Debug, Clone)]
+struct SegmentTimeline {
+    id: u64,
+    speech_end: Option<Instant>,
+    transcribe_start: Option<Instant>,
+    transcribe_end: Option<Instant>,
+    received: Instant,
+    delivered: Instant,
+    injected: Option<Instant>,
+}
+
+/// Milliseconds from `from` to `to`, if both are known and in order
+fn span_ms(from: Option<Instant>, to: Option<Instant>) -> Option<f64> {
+    let (from, to) = (from?, to?);
+    to.checked_duration_since(from)
+        .map(|d| d.as_secs_f64() * 1000.0)
+}
+
+impl SegmentTimeline {
+    fn durations(&self) -> StageDurations {
+        StageDurations {
+            queue: span_ms(self.speech_end, self.transcribe_start),
+            inference: span_ms(self.transcribe_start, self.transcribe_end),
+            broadcast: span_ms(self.transcribe_end, Some(self.received)),
+            post_process: span_ms(Some(self.received), Some(self.delivered)),
+            paste: span_ms(Some(self.delivered), self.injected),
+            total: span_ms(
+                self.speech_end,
+                Some(self.injected.unwrap_or(self.delivered)),
+            ),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn stage_percentiles(samples: &[f64]) -> Option<StagePercentiles> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    Some(StagePercentiles {
+        count: sorted.len(),
+        p50_ms: percentile(&sorted, 50.0),
+        p95_ms: percentile(&sorted, 95.0),
+    })
+}
+
+/// Stage samples of all finished segments, in milliseconds
+#[derive(Debug)]
+struct LatencySamples {
+    queue: Vec<f64>,
+    inference: Vec<f64>,
+    broadcast: Vec<f64>,
+    post_process: Vec<f64>,
+    paste: Vec<f64>,
+    total: Vec<f64>,
+}
+
+impl LatencySamples {
+    const fn new() -> Self {
+        Self {
+            queue: Vec::new(),
+            inference: Vec::new(),
+            broadcast: Vec::new(),
+            post_process: Vec::new(),
+            paste: Vec::new(),
+            total: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, durations: &StageDurations) {
+        for (samples, value) in [
+            (&mut self.queue, durations.queue),
+            (&mut self.inference, durations.inference),
+            (&mut self.broadcast, durations.broadcast),
+            (&mut self.post_process, durations.post_process),
+            (&mut self.paste, durations.paste),
+            (&mut self.total, durations.total),
+        ] {
+            samples.extend(value);
+        }
+    }
+
+    fn breakdown(&self) -> LatencyBreakdown {
+        LatencyBreakdown {
+            queue: stage_percentiles(&self.queue),
+            inference: stage_percentiles(&self.inference),
+            broadcast: stage_percentiles(&self.broadcast),
+            post_process: stage_percentiles(&self.post_process),
+            paste: stage_percentiles(&self.paste),
+            total: stage_percentiles(&self.total),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TranscriptionStats {
+    backend: String,
+    model: String,
+    detailed: bool,
+    segments: usize,
+    words: usize,
+    audio_secs: f64,
+    speech_secs: f64,
+    inference_secs: f64,
+    confidence_sum: f64,
+    confidence_count: usize,
+    dropped_segments: u32,
+    hallucinations: u32,
+    replacements: u64,
+    segment_records: Vec<SegmentRecord>,
+    /// Speech since the last delivered segment
+    pending_speech_secs: f64,
+    speech_ended_at: Option<Instant>,
+    log_latency: bool,
+    next_segment_id: u64,
+    was_recording: bool,
+    was_speaking: bool,
+    was_transcribing: bool,
+    last_speech_end: Option<Instant>,
+    transcribe_start: Option<Instant>,
+    transcribe_end: Option<Instant>,
+    /// Delivered segments whose injection has not finished
+    open_timelines: VecDeque<SegmentTimeline>,
+    latency: LatencySamples,
+}
+
+impl TranscriptionStats {
+    pub const fn new() -> Self {
+        Self {
+            backend: String::new(),
+            model: String::new(),
+            detailed: false,
+            segments: 0,
+            words: 0,
+            audio_secs: 0.0,
+            speech_secs: 0.0,
+            inference_secs: 0.0,
+            confidence_sum: 0.0,
+            confidence_count: 0,
+            dropped_segments: 0,
+            hallucinations: 0,
+            replacements: 0,
+            segment_records: Vec::new(),
+            pending_speech_secs: 0.0,
+            speech_ended_at: None,
+            log_latency: false,
+            next_segment_id: 1,
+            was_recording: false,
+            was_speaking: false,
+            was_transcribing: false,
+            last_speech_end: None,
+            transcribe_start: None,
+            transcribe_end: None,
+            open_timelines: VecDeque::new(),
+            latency: LatencySamples::new(),
+        }
+    }
+
+    /// Account one poll interval of the recording and processing state
+    fn tick(
+        &mut self,
+        tick: Duration,
+        recording: bool,
+        speaking: bool,
+        transcribing: bool,
+        now: Instant,
+    ) {
+        // In manual mode the end of speech is when recording stops
+        if (self.was_speaking && !speaking) || (self.was_recording && !recording) {
+            self.last_speech_end = Some(now);
+        }
+        if transcribing && !self.was_transcribing {
+            self.transcribe_start = Some(now);
+            self.transcribe_end = None;
+        } else if !transcribing && self.was_transcribing {
+            self.transcribe_end = Some(now);
+        }
+        self.was_recording = recording;
+        self.was_speaking = speaking;
+        self.was_transcribing = transcribing;
+
+        let secs = tick.as_secs_f64();
+        if recording {
+            self.audio_secs += secs;
+        }
+        if recording && speaking {
+            self.speech_secs += secs;
+            self.pending_speech_secs += secs;
+            self.speech_ended_at = None;
+        } else if self.pending_speech_secs > 0.0 && self.speech_ended_at.is_none() {
+            self.speech_ended_at = Some(now);
+        }
+        if transcribing {
+            self.inference_secs += secs;
+        }
+    }
+
+    /// Count a delivered segment and open its latency timeline; returns its id
+    fn add_segment(
+        &mut self,
+        text: &str,
+        confidence: Option<f32>,
+        received: Instant,
+        now: Instant,
+    ) -> u64 {
+        let id = self.next_segment_id;
+        self.next_segment_id += 1;
+        // Transcription marks belong to one pass; later segments of the same
+        // pass report no inference stage rather than a repeated one
+        self.open_timelines.push_back(SegmentTimeline {
+            id,
+            speech_end: self.last_speech_end,
+            transcribe_start: self.transcribe_start.take(),
+            transcribe_end: self.transcribe_end.take(),
+            received,
+            delivered: now,
+            injected: None,
+        });
+        if self.open_timelines.len() > MAX_OPEN_TIMELINES {
+            self.open_timelines.pop_front();
+        }
+
+        let words = text.split_whitespace().count();
+        self.segments += 1;
+        self.words += words;
+        if let Some(confidence) = confidence {
+            self.confidence_sum += confidence as f64;
+            self.confidence_count += 1;
+        }
+        if self.detailed {
+            self.segment_records.push(SegmentRecord {
+                duration_secs: self.pending_speech_secs,
+                latency_secs: self
+                    .speech_ended_at
+                    .map(|ended| now.saturating_duration_since(ended).as_secs_f64()),
+                words,
+                backend: self.backend.clone(),
+            });
+        }
+        self.pending_speech_secs = 0.0;
+        self.speech_ended_at = None;
+        id
+    }
+
+    /// Close a segment's timeline, with the injection time if it was pasted or copied
+    fn finish_segment(&mut self, id: u64, injected: Option<Instant>) -> Option<StageDurations> {
+        let index = self.open_timelines.iter().position(|t| t.id == id)?;
+        let mut timeline = self.open_timelines.remove(index)?;
+        timeline.injected = injected;
+        let durations = timeline.durations();
+        self.latency.add(&durations);
+        Some(durations)
+    }
+
+    pub fn export(&self) -> StatsExport {
+        StatsExport {
+            v: STATS_SCHEMA_VERSION,
+            backend: self.backend.clone(),
+            model: self.model.clone(),
+            segments: self.segments,
+            words: self.words,
+            audio_secs: self.audio_secs,
+            speech_secs: self.speech_secs,
+            inference_secs: self.inference_secs,
+            rtf: (self.audio_secs > 0.0 && self.inference_secs > 0.0)
+                .then(|| self.inference_secs / self.audio_secs),
+            avg_confidence: (self.confidence_count > 0)
+                .then(|| self.confidence_sum / self.confidence_count as f64),
+            dropped_segments: self.dropped_segments,
+            hallucinations: self.hallucinations,
+            segment_records: self.detailed.then(|| self.segment_records.clone()),
+            latency: self.latency.breakdown(),
+            replacements: self.replacements,
+        }
+    }
+
+    /// Human-readable summary with a latency breakdown section
+    pub fn report(&self) -> String {
+        let stats = self.export();
+        let mut text = format!(
+            "Segments: {} ({} words), {} dropped as stale, {} as hallucinations\n",
+            stats.segments, stats.words, stats.dropped_segments, stats.hallucinations
+        );
+        if stats.replacements > 0 {
+            text.push_str(&format!("Replacements: {}\n", stats.replacements));
+        }
+        text.push_str(&format!(
+            "Recording: {:.1}s ({:.1}s speech), inference {:.1}s",
+            stats.audio_secs, stats.speech_secs, stats.inference_secs
+        ));
+        if let Some(rtf) = stats.rtf {
+            text.push_str(&format!(", RTF {:.3}", rtf));
+        }
+        text.push('\n');
+
+        let latency = &stats.latency;
+        let stages = [
+            ("queue", latency.queue),
+            ("inference", latency.inference),
+            ("broadcast", latency.broadcast),
+            ("post-process", latency.post_process),
+            ("paste", latency.paste),
+            ("total", latency.total),
+        ];
+        if stages.iter().any(|(_, stage)| stage.is_some()) {
+            text.push_str("Latency (p50 / p95):\n");
+            for (name, stage) in stages {
+                if let Some(stage) = stage {
+                    text.push_str(&format!(
+                        "  {:<13} {:>7.0} ms / {:>7.0} ms  ({} segments)\n",
+                        name, stage.p50_ms, stage.p95_ms, stage.count
+                    ));
+                }
+            }
+        }
+        text
+    }
+
+    /// Pretty-printed JSON of `export()`
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.export()).unwrap_or_default()
+    }
+
+    /// One CSV line matching `CSV_HEADER` (aggregates only), without a newline
+    pub fn to_csv_row(&self) -> String {
+        let stats = self.export();
+        let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+        [
+            stats.v.to_string(),
+            csv_field(&stats.backend),
+            csv_field(&stats.model),
+            stats.segments.to_string(),
+            stats.words.to_string(),
+            stats.audio_secs.to_string(),
+            stats.speech_secs.to_string(),
+            stats.inference_secs.to_string(),
+            optional(stats.rtf),
+            optional(stats.avg_confidence),
+            stats.dropped_segments.to_string(),
+            stats.hallucinations.to_string(),
+            optional(stats.latency.total.map(|total| total.p50_ms)),
+            optional(stats.latency.total.map(|total| total.p95_ms)),
+            stats.replacements.to_string(),
+        ]
+        .join(",")
+    }
+}
+
+impl Default for TranscriptionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Quote a field that contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+static STATS: Mutex<TranscriptionStats> = Mutex::new(TranscriptionStats::new());
+
+/// Start counting for this run
+pub fn init(backend: &str, model: &str, detailed: bool, log_latency: bool) {
+    *STATS.lock() = TranscriptionStats {
+        backend: backend.to_string(),
+        model: model.to_string(),
+        detailed,
+        log_latency,
+        ..TranscriptionStats::new()
+    };
+}
+
+/// Count a delivered final segment that arrived at `received`; returns the id
+/// that `finish_segment` closes its latency timeline with
+pub fn record_segment(text: &str, confidence: Option<f32>, received: Instant) -> u64 {
+    STATS
+        .lock()
+        .add_segment(text, confidence, received, Instant::now())
+}
+
+/// Close a segment's latency timeline; `injected` is false when it was not
+/// pasted or copied (withheld, dropped or failed)
+pub fn finish_segment(id: u64, injected: bool) {
+    let mut stats = STATS.lock();
+    let Some(durations) = stats.finish_segment(id, injected.then(Instant::now)) else {
+        return;
+    };
+    if stats.log_latency {
+        tracing::info!("{}", durations.log_line(id));
+    }
+}
+
+/// Id of the last delivered segment; `None` before the first one or while the
+/// statistics are locked, so a panic hook can call it without deadlocking
+pub fn last_segment_id() -> Option<u64> {
+    let stats = STATS.try_lock()?;
+    Some(stats.next_segment_id - 1).filter(|&id| id > 0)
+}
+
+/// Count a final segment discarded because its session had ended
+pub fn record_stale() {
+    STATS.lock().dropped_segments += 1;
+}
+
+/// Count a final segment dropped by the hallucination filter
+pub fn record_hallucination() {
+    STATS.lock().hallucinations += 1;
+}
+
+/// Count replacements applied to a delivered final segment
+pub fn record_replacements(count: u64) {
+    STATS.lock().replacements += count;
+}
+
+pub fn to_json() -> String {
+    STATS.lock().to_json()
+}
+
+pub fn report() -> String {
+    let mut text = STATS.lock().report();
+    let (computed, reused) = crate::ui::spectogram::frame_counts();
+    if computed + reused > 0 {
+        text.push_str(&format!(
+            "Spectrogram: {} frames computed, {} reused ({:.0}% skipped)\n",
+            computed,
+            reused,
+            reused as f64 * 100.0 / (computed + reused) as f64
+        ));
+    }
+    let (hits, appends, misses) = crate::ui::text_processor::layout_cache_counts();
+    if hits + appends + misses > 0 {
+        text.push_str(&format!(
+            "Text layout: {} cached, {} appended, {} full re-wraps\n",
+            hits, appends, misses
+        ));
+    }
+    text
+}
+
+/// Poll the recording and processing state and accumulate the timings
+pub async fn run_monitor(
+    running: Arc<AtomicBool>,
+    recording: Arc<AtomicBool>,
+    audio_data: Arc<RwLock<AudioVisualizationData>>,
+) {
+    let mut last = Instant::now();
+    let mut active = false;
+    while running.load(Ordering::Relaxed) {
+        // Poll finely only while there is something to time
+        tokio::time::sleep(if active { ACTIVE_TICK } else { IDLE_TICK }).await;
+        let now = Instant::now();
+        let (speaking, transcribing) = {
+            let audio_data = audio_data.read();
+            (
+                audio_data.is_speaking,
+                audio_data.processing_state == ProcessingState::Transcribing,
+            )
+        };
+        let is_recording = recording.load(Ordering::Relaxed);
+        STATS.lock().tick(
+            now.duration_since(last),
+            is_recording,
+            speaking,
+            transcribing,
+            now,
+        );
+        last = now;
+        active = is_recording || transcribing;
+    }
+}
+
+/// Write the run's statistics to `path`: a row appended to a `.csv` file (with
+/// the header when the file is new), JSON otherwise
+pub fn export_to(path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let stats = STATS.lock();
+    let is_csv = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    if !is_csv {
+        return fs::write(path, stats.to_json() + "\n");
+    }
+
+    let is_new = fs::metadata(path).map(|m| m.len() == 0).unwrap_or(true);
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "{}", CSV_HEADER)?;
+    }
+    writeln!(file, "{}", stats.to_csv_row())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_stats(detailed: bool) -> TranscriptionStats {
+        let mut stats = TranscriptionStats {
+            backend: "WhisperCpp".to_string(),
+            model: "small.en".to_string(),
+            detailed,
+            ..TranscriptionStats::new()
+        };
+        stats.audio_secs = 10.0;
+        stats.speech_secs = 4.0;
+        stats.inference_secs = 2.5;
+        stats.pending_speech_secs = 4.0;
+        stats.hallucinations = 1;
+        let now = Instant::now();
+        stats.add_segment("hello there world", None, now, now);
+        stats
+    }
+
+    #[test]
+    fn json_field_names_and_order_are_stable() {
+        let json = serde_json::to_string(&sample_stats(false).export()).unwrap();
+        assert_eq!(
+            json,
+            "{\"v\":1,\"backend\":\"WhisperCpp\",\"model\":\"small.en\",\"segments\":1,\
+             \"words\":3,\"audio_secs\":10.0,\"speech_secs\":4.0,\"inference_secs\":2.5,\
+             \"rtf\":0.25,\"avg_confidence\":null,\"dropped_segments\":0,\"hallucinations\":1,\
+             \"latency\":{\"queue\":null,\"inference\":null,\"broadcast\":null,\
+             \"post_process\":null,\"paste\":null,\"total\":null},\"replacements\":0}"
+        );
+    }
+
+    #[test]
+    fn detailed_export_adds_segment_records() {
+        let export = sample_stats(true).export();
+        let records = export.segment_records.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].duration_secs, 4.0);
+        assert_eq!(records[0].words, 3);
+        assert_eq!(records[0].latency_secs, None);
+
+        let json = serde_json::to_string(&records[0]).unwrap();
+        assert_eq!(
+            json,
+            "{\"duration_secs\":4.0,\"latency_secs\":null,\"words\":3,\"backend\":\"WhisperCpp\"}"
+        );
+    }
+
+    #[test]
+    fn csv_row_matches_header() {
+        let row = sample_stats(false).to_csv_row();
+        assert_eq!(row, "1,WhisperCpp,small.en,1,3,10,4,2.5,0.25,,0,1,,,0");
+        assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count());
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn segment_timeline_splits_latency_into_stages() {
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+        let mut stats = TranscriptionStats::new();
+        stats.tick(ms(20), true, true, false, t0);
+        stats.tick(ms(20), true, false, false, t0 + ms(100));
+        stats.tick(ms(20), true, false, true, t0 + ms(150));
+        stats.tick(ms(20), true, false, false, t0 + ms(450));
+
+        let id = stats.add_segment("hi", None, t0 + ms(460), t0 + ms(470));
+        let durations = stats.finish_segment(id, Some(t0 + ms(570))).unwrap();
+        assert_eq!(durations.queue, Some(50.0));
+        assert_eq!(durations.inference, Some(300.0));
+        assert_eq!(durations.broadcast, Some(10.0));
+        assert_eq!(durations.post_process, Some(10.0));
+        assert_eq!(durations.paste, Some(100.0));
+        assert_eq!(durations.total, Some(470.0));
+        assert!(stats.finish_segment(id, None).is_none());
+
+        // A second segment of the same pass has no inference stage of its own
+        let second = stats.add_segment("there", None, t0 + ms(480), t0 + ms(480));
+        let durations = stats.finish_segment(second, None).unwrap();
+        assert_eq!(durations.inference, None);
+        assert_eq!(durations.paste, None);
+        assert_eq!(durations.total, Some(380.0));
+        assert_eq!(
+            durations.log_line(second),
+            "Latency #2: queue -, inference -, broadcast -, post 0 ms, paste -, total 380 ms"
+        );
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let samples: Vec<f64> = (1..=20).map(f64::from).collect();
+        let stage = stage_percentiles(&samples).unwrap();
+        assert_eq!(stage.count, 20);
+        assert_eq!(stage.p50_ms, 10.0);
+        assert_eq!(stage.p95_ms, 19.0);
+        assert_eq!(stage_percentiles(&[7.0]).unwrap().p95_ms, 7.0);
+        assert!(stage_percentiles(&[]).is_none());
+    }
+}
@@ -7,8 +7,8 @@ use winit::{
     cursor::CursorIcon,
     dpi::{LogicalPosition, LogicalSize, PhysicalSize},
     event::{DeviceEvent, DeviceId, ElementState, KeyEvent, Modifiers, MouseButton, WindowEvent},
-    event_loop::{ActiveEventLoop, DeviceEvents, EventLoop},
-    keyboard::{KeyCode, PhysicalKey},
+    event_loop::{ActiveEventLoop, ControlFlow, DeviceEvents, EventLoop},
+    keyboard::{Key, KeyCode, NamedKey, PhysicalKey},
     monitor::{MonitorHandle, VideoMode},
     platform::wayland::ActiveEventLoopExtWayland,
     window::{WindowAttributes, WindowId},
@@ -20,8 +20,8 @@ use super::settings_window::SettingsWindow;
 use super::window::WindowState;
 
 // Constants from window.rs
-use super::window::MARGIN;
-use crate::config::{AppConfig, CustomWindowPosition, DisplayConfig, WindowPosition};
+use super::window::{IDLE_POLL_INTERVAL, MARGIN};
+use crate::config::{AppConfig, ConfigStore, CustomWindowPosition, DisplayConfig, WindowPosition};
 use speechcore::{AudioVisualizationData, BackendStatus};
 
 const DRAG_DEBUG_ENV: &str = "SONORI_DRAG_DEBUG";
@@ -36,6 +36,7 @@ pub fn run() {
     let event_loop = EventLoop::new()
         .expect("Failed to create event loop. Ensure a display server (Wayland/X11) is available.");
     let app_config = crate::config::AppConfig::default();
+    let config_store = crate::config::ConfigStore::in_memory(app_config.clone());
     let mut app = WindowApp {
         windows: HashMap::new(),
         audio_data: None,
@@ -44,6 +45,7 @@ pub fn run() {
         magic_mode_enabled: None,
         current_modifiers: Modifiers::default(),
         config: app_config,
+        config_store,
         manual_session_sender: None,
         transcription_mode_ref: Arc::new(AtomicU8::new(
             speechcore::TranscriptionMode::RealTime.as_u8(),
@@ -55,10 +57,18 @@ pub fn run() {
         settings_window: None,
         settings_window_id: None,
         window_drag: None,
+        wipe_generation: crate::privacy_wipe::registry().generation(),
+        init_error: None,
+        overlay_placement: None,
+        click_through: false,
+        auto_paste: true,
     };
     event_loop
         .run_app(&mut app)
         .expect("Event loop exited with error");
+    if let Some(e) = app.init_error {
+        tracing::error!("Failed to initialize window: {}", e);
+    }
 }
 
 pub fn run_with_audio_data(
@@ -66,16 +76,22 @@ pub fn run_with_audio_data(
     running: Arc<AtomicBool>,
     recording: Arc<AtomicBool>,
     magic_mode_enabled: Arc<AtomicBool>,
-    config: AppConfig,
+    config_store: ConfigStore,
     manual_session_sender: Option<tokio::sync::mpsc::Sender<speechcore::ManualSessionCommand>>,
     transcription_mode_ref: Arc<AtomicU8>,
     tray_update_tx: Option<tokio::sync::mpsc::UnboundedSender<crate::system_tray::TrayUpdate>>,
     tray_command_rx: Option<tokio::sync::mpsc::UnboundedReceiver<crate::system_tray::TrayCommand>>,
     backend_status: Option<Arc<RwLock<BackendStatus>>>,
     backend_command_tx: Option<tokio::sync::mpsc::UnboundedSender<speechcore::BackendCommand>>,
-) {
-    let event_loop = EventLoop::new()
-        .expect("Failed to create event loop. Ensure a display server (Wayland/X11) is available.");
+) -> Result<(), String> {
+    let event_loop = EventLoop::new().map_err(|e| {
+        format!(
+            "Failed to create event loop: {e}. Ensure a display server (Wayland/X11) is available."
+        )
+    })?;
+    let config = config_store.snapshot();
+    let config_generation = config_store.generation();
+    crate::click_through::set(config.window_behavior_config.click_through);
     let mut app = WindowApp {
         windows: HashMap::new(),
         audio_data: Some(audio_data),
@@ -84,6 +100,8 @@ pub fn run_with_audio_data(
         magic_mode_enabled: Some(magic_mode_enabled),
         current_modifiers: Modifiers::default(),
         config,
+        config_store,
+        config_generation,
         manual_session_sender,
         transcription_mode_ref,
         tray_update_tx,
@@ -93,11 +111,21 @@ pub fn run_with_audio_data(
         settings_window: None,
         settings_window_id: None,
         window_drag: None,
+        wipe_generation: crate::privacy_wipe::registry().generation(),
+        init_error: None,
+        overlay_placement: None,
+        click_through: false,
+        auto_paste: true,
     };
 
     event_loop
         .run_app(&mut app)
-        .expect("Event loop exited with error");
+        .map_err(|e| format!("Event loop exited with error: {e}"))?;
+
+    match app.init_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
 }
 
 pub struct WindowApp {
@@ -108,6 +136,10 @@ pub struct WindowApp {
     pub magic_mode_enabled: Option<Arc<AtomicBool>>,
     pub current_modifiers: Modifiers,
     pub config: AppConfig,
+    /// Shared with the settings window; the only runtime source of config changes
+    pub config_store: ConfigStore,
+    /// Store generation `config` was last taken from
+    config_generation: u64,
     pub manual_session_sender: Option<tokio::sync::mpsc::Sender<speechcore::ManualSessionCommand>>,
     pub transcription_mode_ref: Arc<AtomicU8>,
     pub tray_update_tx: Option<tokio::sync::mpsc::UnboundedSender<crate::system_tray::TrayUpdate>>,
@@ -118,6 +150,25 @@ pub struct WindowApp {
     pub settings_window: Option<SettingsWindow>,
     pub settings_window_id: Option<WindowId>,
     window_drag: Option<WindowDragState>,
+    /// Privacy wipe generation last applied to the overlay's scroll state
+    wipe_generation: u64,
+    /// Set when the overlay window could not be initialized (e.g. no usable GPU)
+    init_error: Option<String>,
+    /// Where the overlay was last placed; `None` until it is first created
+    overlay_placement: Option<OverlayPlacement>,
+    /// Click-through state last applied to the overlay
+    click_through: bool,
+    /// Auto-paste state last reported to the tray
+    auto_paste: bool,
+}
+
+/// Outputs as seen when the overlay was placed, to notice when it has to move
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OverlayPlacement {
+    /// `display_config.output_name` the overlay is pinned to, if it was connected
+    pinned: Option<String>,
+    /// Number of connected outputs
+    monitors: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -185,12 +236,12 @@ impl WindowApp {
                     device,
                     queue,
                     format,
-                    &self.config,
+                    self.config_store.clone(),
                     self.backend_command_tx.clone(),
                 ) {
                     Ok(settings_win) => settings_win,
                     Err(e) => {
-                        eprintln!("{}", e);
+                        tracing::warn!("{}", e);
                         return;
                     }
                 };
@@ -199,7 +250,7 @@ impl WindowApp {
                 self.settings_window = Some(settings_win);
             }
             Err(e) => {
-                eprintln!("Failed to create settings window: {}", e);
+                tracing::warn!("Failed to create settings window: {}", e);
             }
         }
     }
@@ -226,7 +277,9 @@ impl WindowApp {
             return false;
         };
 
-        let Some(physical_monitor_size) = current_monitor_size(event_loop) else {
+        let Some(physical_monitor_size) =
+            current_monitor_size(event_loop, &self.config.display_config)
+        else {
             return false;
         };
 
@@ -244,7 +297,7 @@ impl WindowApp {
         let debug_enabled = std::env::var_os(DRAG_DEBUG_ENV).is_some();
 
         if debug_enabled {
-            eprintln!(
+            tracing::debug!(
                 "drag:start scale={scale_factor:.3} pos=({}, {}) pointer=({:.1}, {:.1}) monitor=({}, {}) window=({}, {})",
                 start_position.x,
                 start_position.y,
@@ -344,7 +397,7 @@ impl WindowApp {
             let rounded_position = round_window_position(drag.position);
 
             if drag.debug_enabled {
-                eprintln!(
+                tracing::debug!(
                     "drag:move update={} pointer_events={} raw_events={} raw=({:.1}, {:.1}) pointer=({:.1}, {:.1}) error=({:.1}, {:.1}) catch_up=({:.1}, {:.1}) applied=({:.1}, {:.1}) pos=({}, {})",
                     drag.update_count,
                     drag.pointer_event_count,
@@ -387,7 +440,7 @@ impl WindowApp {
         self.window_drag = None;
         let position = round_window_position(drag.position);
         if drag.debug_enabled {
-            eprintln!(
+            tracing::debug!(
                 "drag:end pos=({}, {}) pointer_events={} raw_events={} updates={}",
                 position.x,
                 position.y,
@@ -425,21 +478,57 @@ impl WindowApp {
         self.config.display_config.window_position = WindowPosition::Custom;
         self.config.display_config.custom_window_position = Some(custom_position);
 
-        let (mut app_config, _) = crate::config::read_app_config_with_path();
-        app_config.display_config.window_position = WindowPosition::Custom;
-        app_config.display_config.custom_window_position = Some(custom_position);
-
-        if let Err(e) = crate::config::write_app_config(&app_config) {
-            eprintln!("Failed to persist dragged window position: {}", e);
+        let result = self.config_store.update(|config| {
+            config.display_config.window_position = WindowPosition::Custom;
+            config.display_config.custom_window_position = Some(custom_position);
+        });
+        if let Err(e) = result {
+            tracing::warn!("Failed to persist dragged window position: {}", e);
         }
+        self.config_generation = self.config_store.generation();
+    }
+
+    fn overlay_output_changed(&mut self, event_loop: &dyn ActiveEventLoop) -> bool {
+        let Some(placement) = &mut self.overlay_placement else {
+            return false;
+        };
+        let monitor_names: Vec<Option<String>> = event_loop
+            .available_monitors()
+            .map(|monitor| monitor.name().map(|name| name.to_string()))
+            .collect();
+        // A newly connected output may take the overlay; its removal must be noticed
+        placement.monitors = placement.monitors.max(monitor_names.len());
+        overlay_needs_migration(
+            placement,
+            &monitor_names,
+            self.config.display_config.output_name.as_deref(),
+            !self.windows.is_empty(),
+        )
+    }
+
+    /// Recreate the overlay on the output it belongs on now
+    fn migrate_overlay(&mut self, event_loop: &dyn ActiveEventLoop) {
+        tracing::info!("Outputs changed; recreating the overlay");
+        self.window_drag = None;
+        self.windows.clear();
+        self.can_create_surfaces(event_loop);
     }
 
     fn apply_runtime_config(&mut self, event_loop: &dyn ActiveEventLoop, config: AppConfig) {
         let display_config = config.display_config.clone();
         let ui_config = config.ui_config.clone();
+        let theme_config = config.theme.clone();
+        if config.window_behavior_config.click_through
+            != self.config.window_behavior_config.click_through
+        {
+            crate::click_through::set(config.window_behavior_config.click_through);
+        }
+        if config.portal_config.auto_paste != self.config.portal_config.auto_paste {
+            crate::auto_paste::set_enabled(config.portal_config.auto_paste);
+        }
         self.config = config;
 
-        let physical_monitor_size = current_monitor_size(event_loop);
+        let physical_monitor_size = current_monitor_size(event_loop, &self.config.display_config);
         let window_ids: Vec<WindowId> = self.windows.keys().copied().collect();
         for window_id in window_ids {
             let position = {
@@ -447,7 +536,7 @@ impl WindowApp {
                     continue;
                 };
 
-                window.apply_runtime_config(&display_config, &ui_config);
+                window.apply_runtime_config(&display_config, &ui_config, &theme_config);
 
                 let Some(physical_monitor_size) = physical_monitor_size else {
                     continue;
@@ -470,14 +559,59 @@ impl WindowApp {
     }
 }
 
-fn current_monitor_size(event_loop: &dyn ActiveEventLoop) -> Option<PhysicalSize<u32>> {
-    event_loop
-        .available_monitors()
-        .next()
-        .and_then(|monitor| monitor.current_video_mode())
+fn is_help_key(key: &Key) -> bool {
+    matches!(key, Key::Character(c) if c.as_str() == "?") || matches!(key, Key::Named(NamedKey::F1))
+}
+
+fn current_monitor_size(
+    event_loop: &dyn ActiveEventLoop,
+    display_config: &DisplayConfig,
+) -> Option<PhysicalSize<u32>> {
+    overlay_monitor(event_loop, display_config.output_name.as_deref())
+        .and_then(|(monitor, _)| monitor.current_video_mode())
         .map(|mode| mode.size())
 }
 
+/// The monitor for the overlay: the output named `output_name` when it is
+/// connected (`true`: pin the overlay to it), otherwise the first one
+fn overlay_monitor(
+    event_loop: &dyn ActiveEventLoop,
+    output_name: Option<&str>,
+) -> Option<(MonitorHandle, bool)> {
+    let monitors: Vec<MonitorHandle> = event_loop.available_monitors().collect();
+    if let Some(output_name) = output_name {
+        if let Some(monitor) = monitors
+            .iter()
+            .find(|monitor| monitor.name().as_deref() == Some(output_name))
+        {
+            return Some((monitor.clone(), true));
+        }
+    }
+    monitors.into_iter().next().map(|monitor| (monitor, false))
+}
+
+/// Whether the overlay has to be recreated: an output went away (the compositor
+/// closes layer surfaces on unplugged outputs), it is gone, or the output it
+/// should be pinned to changed
+fn overlay_needs_migration(
+    placement: &OverlayPlacement,
+    monitor_names: &[Option<String>],
+    output_name: Option<&str>,
+    has_overlay: bool,
+) -> bool {
+    if monitor_names.is_empty() {
+        return false;
+    }
+    let target = output_name.filter(|name| {
+        monitor_names
+            .iter()
+            .any(|monitor| monitor.as_deref() == Some(*name))
+    });
+    !has_overlay
+        || monitor_names.len() < placement.monitors
+        || placement.pinned.as_deref() != target
+}
+
 fn configured_window_position(
     display_config: &DisplayConfig,
     monitor_size: LogicalSize<u32>,
@@ -619,7 +753,7 @@ impl ApplicationHandler for WindowApp {
         // Check running flag on resume and exit if shutting down
         if let Some(running) = &self.running {
             if !running.load(std::sync::atomic::Ordering::Relaxed) {
-                println!("App resumed but running flag is false - exiting event loop");
+                tracing::debug!("App resumed but running flag is false - exiting event loop");
                 event_loop.exit();
             }
         }
@@ -645,13 +779,66 @@ impl ApplicationHandler for WindowApp {
         // Periodic check when event loop is idle - ensures shutdown happens even without events
         if let Some(running) = &self.running {
             if !running.load(std::sync::atomic::Ordering::Relaxed) {
-                println!("Event loop idle but running flag is false - exiting event loop");
+                tracing::debug!("Event loop idle but running flag is false - exiting event loop");
                 event_loop.exit();
                 return;
             }
         }
 
+        // A privacy wipe may come from the tray, IPC or a shortcut; reset the
+        // overlay's scroll state along with the cleared transcript
+        let wipe_generation = crate::privacy_wipe::registry().generation();
+        if wipe_generation != self.wipe_generation {
+            self.wipe_generation = wipe_generation;
+            for window in self.windows.values_mut() {
+                window.reset_transcript();
+                window.window.request_redraw();
+            }
+            self.notify_tray_about_recording();
+        }
+
+        // Edits to the config file are published to the store
+        let config_generation = self.config_store.generation();
+        if config_generation != self.config_generation {
+            self.config_generation = config_generation;
+            self.apply_runtime_config(event_loop, self.config_store.snapshot());
+            if let Some(settings_window) = &mut self.settings_window {
+                settings_window.config_reloaded();
+            }
+        }
+
+        // Portal sessions change in the background; keep the Integration tab current
+        if let Some(settings_window) = &self.settings_window {
+            settings_window.poll_background_changes();
+        }
+
+        if self.overlay_output_changed(event_loop) {
+            self.migrate_overlay(event_loop);
+        }
+
+        // Click-through may be toggled from the tray or a global shortcut
+        let click_through = crate::click_through::is_enabled();
+        if click_through != self.click_through {
+            self.click_through = click_through;
+            for window in self.windows.values_mut() {
+                window.set_click_through(click_through);
+            }
+            if let Some(tray_tx) = &self.tray_update_tx {
+                let _ = tray_tx.send(crate::system_tray::TrayUpdate::ClickThrough(click_through));
+            }
+        }
+
+        // Auto-paste may be toggled from the tray or a global shortcut
+        let auto_paste = crate::auto_paste::enabled();
+        if auto_paste != self.auto_paste {
+            self.auto_paste = auto_paste;
+            if let Some(tray_tx) = &self.tray_update_tx {
+                let _ = tray_tx.send(crate::system_tray::TrayUpdate::AutoPaste(auto_paste));
+            }
+        }
+
         // Process tray commands if available
+        let mut open_settings = false;
         if let Some(tray_rx) = &mut self.tray_command_rx {
             let mut notify_recording = false;
             while let Ok(command) = tray_rx.try_recv() {
@@ -677,8 +864,47 @@ impl ApplicationHandler for WindowApp {
                         }
                         notify_recording = true;
                     }
+                    crate::system_tray::TrayCommand::ToggleClickThrough => {
+                        crate::click_through::toggle();
+                    }
+                    crate::system_tray::TrayCommand::ToggleAutoPaste => {
+                        crate::auto_paste::toggle();
+                    }
+                    crate::system_tray::TrayCommand::PrivacyWipe => {
+                        crate::privacy_wipe::wipe();
+                        notify_recording = true;
+                    }
+                    crate::system_tray::TrayCommand::ExportSubtitles => {
+                        match crate::subtitle_track::export(&self.config_store.snapshot()) {
+                            Ok(path) => {
+                                tracing::info!("Subtitles exported to {}", path.display());
+                                crate::toast::show(format!("Subtitles saved: {}", path.display()));
+                            }
+                            Err(e) => {
+                                tracing::warn!("Subtitle export failed: {}", e);
+                                crate::toast::show(format!("Subtitle export failed: {}", e));
+                            }
+                        }
+                    }
+                    crate::system_tray::TrayCommand::CopyLastSegment => {
+                        let segment = crate::system_tray::latest_segment();
+                        std::thread::spawn(move || {
+                            match crate::copy::WlCopy::copy_to_clipboard(&segment) {
+                                Ok(()) => crate::toast::show("Copied last segment"),
+                                Err(e) => tracing::warn!("Failed to copy last segment: {}", e),
+                            }
+                        });
+                    }
+                    crate::system_tray::TrayCommand::CopyTranscript => {
+                        if let Some(window) = self.windows.values().next() {
+                            window.copy_transcript();
+                        }
+                    }
+                    crate::system_tray::TrayCommand::OpenSettings => {
+                        open_settings = true;
+                    }
                     crate::system_tray::TrayCommand::Quit => {
-                        println!("Quit requested from system tray");
+                        tracing::info!("Quit requested from system tray");
                         if let Some(running) = &self.running {
                             running.store(false, std::sync::atomic::Ordering::Relaxed);
                         }
@@ -690,6 +916,18 @@ impl ApplicationHandler for WindowApp {
                 self.notify_tray_about_recording();
             }
         }
+        if open_settings {
+            self.open_settings_window(event_loop);
+        }
+
+        // Idle overlays draw no frames; wake up regularly to redraw them when
+        // their data changes and to keep handling tray commands and shutdown
+        for window in self.windows.values_mut() {
+            window.poll_idle();
+        }
+        event_loop.set_control_flow(ControlFlow::WaitUntil(
+            std::time::Instant::now() + IDLE_POLL_INTERVAL,
+        ));
     }
 
     fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
@@ -697,7 +935,25 @@ impl ApplicationHandler for WindowApp {
             .with_decorations(false)
             .with_transparent(true);
 
-        if let Some((_, screen)) = event_loop.available_monitors().enumerate().next() {
+        let output_name = self.config.display_config.output_name.clone();
+        let monitor = overlay_monitor(event_loop, output_name.as_deref());
+        let pinned = match &monitor {
+            Some((_, true)) => output_name.clone(),
+            _ => None,
+        };
+        self.overlay_placement = Some(OverlayPlacement {
+            pinned,
+            monitors: event_loop.available_monitors().count(),
+        });
+        if let Some(name) = &output_name {
+            if !matches!(monitor, Some((_, true))) {
+                tracing::warn!(
+                    "Output {name:?} is not connected; the compositor places the overlay"
+                );
+            }
+        }
+
+        if let Some((screen, pin_to_output)) = monitor {
             let Some(mode) = screen.current_video_mode() else {
                 return;
             };
@@ -711,12 +967,13 @@ impl ApplicationHandler for WindowApp {
             }
             .to_string();
             let model_name = self.config.general_config.model.clone();
-            let mut window_state = create_window(
+            let window_state = create_window(
                 event_loop,
                 window_attributes.with_title("Sonori"),
                 1.0,
                 mode,
                 screen,
+                pin_to_output,
                 self.running.clone(),
                 self.recording.clone(),
                 self.magic_mode_enabled.clone(),
@@ -727,16 +984,29 @@ impl ApplicationHandler for WindowApp {
                 self.transcription_mode_ref.clone(),
                 &self.config.display_config,
                 &self.config.ui_config,
+                &self.config.theme,
                 self.config.enhancement_config.enabled,
                 &backend_name,
                 &model_name,
                 self.backend_status.clone(),
                 self.backend_command_tx.clone(),
             );
+            let mut window_state = match window_state {
+                Ok(window_state) => window_state,
+                Err(e) => {
+                    // Leave the event loop; run_with_audio_data reports the error to the caller
+                    self.init_error = Some(e);
+                    event_loop.exit();
+                    return;
+                }
+            };
 
             if let Some(audio_data) = &self.audio_data {
                 window_state.set_audio_data(audio_data.clone());
             }
+            if self.click_through {
+                window_state.set_click_through(true);
+            }
 
             let window_id = window_state.window.id();
             self.windows.insert(window_id, window_state);
@@ -752,7 +1022,7 @@ impl ApplicationHandler for WindowApp {
         // IMMEDIATE: Check running flag and exit event loop if shutting down
         if let Some(running) = &self.running {
             if !running.load(std::sync::atomic::Ordering::Relaxed) {
-                println!("Running flag is false - exiting event loop immediately");
+                tracing::debug!("Running flag is false - exiting event loop immediately");
                 event_loop.exit();
                 return;
             }
@@ -761,6 +1031,7 @@ impl ApplicationHandler for WindowApp {
         if Some(window_id) == self.settings_window_id {
             let mut applied_config = None;
             let mut close_settings = false;
+            let mut help_requested = false;
 
             if let Some(sw) = &mut self.settings_window {
                 match event {
@@ -786,12 +1057,22 @@ impl ApplicationHandler for WindowApp {
                             },
                         ..
                     } => {
-                        if key_code == KeyCode::Escape {
-                            close_settings = true;
+                        if sw.is_capturing_shortcut() {
+                            // Every key, Escape and the help keys included, goes to the recorder
+                            sw.handle_shortcut_key(key_code, self.current_modifiers.state());
+                        } else if sw.is_help_visible() {
+                            // Any key (including Escape) only dismisses the quick help
+                            sw.hide_help();
+                        } else if is_help_key(logical_key) {
+                            help_requested = true;
                         } else {
+                            // Escape closes an open dropdown first, then the settings window
                             let shift = self.current_modifiers.state().shift_key();
                             if sw.handle_key(logical_key, shift) {
                                 sw.window.request_redraw();
+                                applied_config = sw.take_applied_config();
+                            } else if key_code == KeyCode::Escape {
+                                close_settings = true;
                             }
                         }
                     }
@@ -801,6 +1082,7 @@ impl ApplicationHandler for WindowApp {
                         if state == ElementState::Pressed {
                             sw.handle_click(position.x as f32, position.y as f32);
                             applied_config = sw.take_applied_config();
+                            help_requested = sw.take_help_request();
                             if sw.close_requested() {
                                 close_settings = true;
                             }
@@ -811,16 +1093,31 @@ impl ApplicationHandler for WindowApp {
                     WindowEvent::PointerMoved { position, .. } => {
                         sw.handle_mouse_move(position.x as f32, position.y as f32);
                     }
+                    WindowEvent::MouseWheel { delta, .. } => {
+                        sw.handle_scroll(delta);
+                    }
+                    WindowEvent::ModifiersChanged(modifiers) => {
+                        self.current_modifiers = modifiers;
+                    }
                     _ => {}
                 }
             }
 
+            if help_requested {
+                let current_mode = speechcore::TranscriptionMode::from_u8(
+                    self.transcription_mode_ref.load(Ordering::Relaxed),
+                );
+                if let Some(sw) = &mut self.settings_window {
+                    sw.show_help(crate::help::quick_help(&self.config, current_mode));
+                }
+            }
             if close_settings {
                 self.settings_window = None;
                 self.settings_window_id = None;
             }
             if let Some(config) = applied_config {
                 self.apply_runtime_config(event_loop, config);
+                self.config_generation = self.config_store.generation();
             }
             return;
         }
@@ -835,21 +1132,40 @@ impl ApplicationHandler for WindowApp {
                 event:
                     KeyEvent {
                         physical_key: PhysicalKey::Code(key_code),
+                        ref logical_key,
                         state: ElementState::Pressed,
                         ..
                     },
                 ..
             } => {
                 if let Some(window) = self.windows.get_mut(&window_id) {
-                    // Tab - Toggle manual session (temporary, works when window focused)
-                    // TODO: Once global shortcut (Super+Tab) works unfocused, remove this
-                    if key_code == KeyCode::Tab {
-                        let current_mode = speechcore::TranscriptionMode::from_u8(
-                            self.transcription_mode_ref.load(Ordering::Relaxed),
-                        );
+                    let current_mode = speechcore::TranscriptionMode::from_u8(
+                        self.transcription_mode_ref.load(Ordering::Relaxed),
+                    );
+                    if window.is_help_visible() {
+                        // Any key dismisses the quick help
+                        window.hide_help();
+                    } else if is_help_key(logical_key) {
+                        window.show_help(crate::help::quick_help(&self.config, current_mode));
+                    } else if key_code == KeyCode::Delete
+                        && self.current_modifiers.state().control_key()
+                        && self.current_modifiers.state().shift_key()
+                    {
+                        crate::privacy_wipe::wipe();
+                    } else if key_code == KeyCode::KeyZ
+                        && self.current_modifiers.state().control_key()
+                    {
+                        crate::segment_undo::undo();
+                    } else if key_code == KeyCode::Tab {
+                        // Tab - Toggle manual session (temporary, works when window focused)
+                        // TODO: Once global shortcut (Super+Tab) works unfocused, remove this
                         if current_mode == speechcore::TranscriptionMode::Manual {
                             window.toggle_manual_session();
                         }
+                    } else if key_code == KeyCode::Escape
+                        && current_mode == speechcore::TranscriptionMode::Manual
+                    {
+                        window.cancel_manual_session();
                     }
                 }
                 return;
@@ -857,6 +1173,26 @@ impl ApplicationHandler for WindowApp {
             _ => {}
         }
 
+        // While quick help is shown, clicks dismiss it and the wheel scrolls it
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            if window.is_help_visible() {
+                match &event {
+                    WindowEvent::PointerButton {
+                        state: ElementState::Pressed,
+                        ..
+                    } => {
+                        window.hide_help();
+                        return;
+                    }
+                    WindowEvent::MouseWheel { delta, .. } => {
+                        window.scroll_help(*delta);
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
         if let WindowEvent::PointerButton { button, state, .. } = &event {
             let mouse_button = (*button).mouse_button();
             if mouse_button == MouseButton::Left {
@@ -888,12 +1224,21 @@ impl ApplicationHandler for WindowApp {
             self.apply_queued_window_drag(window_id);
         }
 
+        // The compositor closes the overlay when its output is unplugged
+        if matches!(event, WindowEvent::CloseRequested)
+            && self.windows.contains_key(&window_id)
+            && self.overlay_output_changed(event_loop)
+        {
+            self.migrate_overlay(event_loop);
+            return;
+        }
+
         // Handle other window events
         if let Some(window) = self.windows.get_mut(&window_id) {
             let mut should_notify_recording = false;
             match event {
                 WindowEvent::CloseRequested => {
-                    println!("Window close requested");
+                    tracing::debug!("Window close requested");
                     // First quit to set the running flag to false
                     window.quit();
                     // Don't call event_loop.exit() here as it can cause segfaults
@@ -953,7 +1298,8 @@ fn create_window(
     w: WindowAttributes,
     scale_factor: f64,
     monitor_mode: VideoMode,
-    _monitor: MonitorHandle,
+    monitor: MonitorHandle,
+    pin_to_output: bool,
     running: Option<Arc<AtomicBool>>,
     recording: Option<Arc<AtomicBool>>,
     magic_mode_enabled: Option<Arc<AtomicBool>>,
@@ -962,12 +1308,13 @@ fn create_window(
     transcription_mode_ref: Arc<AtomicU8>,
     display_config: &crate::config::DisplayConfig,
     ui_config: &crate::config::UiConfig,
+    theme_config: &crate::config::ThemeConfig,
     enhancement_enabled: bool,
     backend_name: &str,
     model_name: &str,
     backend_status: Option<Arc<RwLock<BackendStatus>>>,
     backend_command_tx: Option<tokio::sync::mpsc::UnboundedSender<speechcore::BackendCommand>>,
-) -> WindowState {
+) -> Result<WindowState, String> {
     // Get monitor dimensions from video mode
     let monitor_size = monitor_mode.size();
     let monitor_width = monitor_size.width;
@@ -1020,14 +1367,17 @@ fn create_window(
             positioning_window_size,
         );
 
-        let wayland_attrs = WindowAttributesWayland::default()
+        let mut wayland_attrs = WindowAttributesWayland::default()
             .with_layer_shell()
             .with_anchor(anchor)
             .with_layer(Layer::Overlay)
             .with_margin(top_margin, right_margin, bottom_margin, left_margin)
-            // FIXME: Specifying output causes crashes on niri - let compositor choose
-            // .with_output(monitor.native_id())
             .with_keyboard_interactivity(keyboard_mode);
+        // FIXME: Specifying output causes crashes on niri, so the compositor chooses
+        // unless display_config.output_name asks for a specific output
+        if pin_to_output {
+            wayland_attrs = wayland_attrs.with_output(monitor.native_id());
+        }
 
         w = w
             .with_platform_attributes(Box::new(wayland_attrs))
@@ -1045,9 +1395,12 @@ fn create_window(
 
     ev.listen_device_events(DeviceEvents::Always);
 
+    let window = ev
+        .create_window(w)
+        .map_err(|e| format!("Failed to create application window: {e}"))?;
+
     WindowState::new(
-        ev.create_window(w)
-            .expect("Failed to create application window"),
+        window,
         running,
         recording,
         magic_mode_enabled,
@@ -1056,6 +1409,7 @@ fn create_window(
         transcription_mode_ref,
         display_config,
         ui_config,
+        theme_config,
         logical_width,
         logical_height,
         spectrogram_width,
@@ -1069,3 +1423,55 @@ fn create_window(
         backend_command_tx,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<Option<String>> {
+        names.iter().map(|name| Some(name.to_string())).collect()
+    }
+
+    #[test]
+    fn overlay_follows_its_output() {
+        let pinned = OverlayPlacement {
+            pinned: Some("DP-1".to_string()),
+            monitors: 2,
+        };
+        assert!(!overlay_needs_migration(
+            &pinned,
+            &names(&["eDP-1", "DP-1"]),
+            Some("DP-1"),
+            true
+        ));
+        assert!(overlay_needs_migration(
+            &pinned,
+            &names(&["eDP-1"]),
+            Some("DP-1"),
+            true
+        ));
+
+        let fallback = OverlayPlacement {
+            pinned: None,
+            monitors: 1,
+        };
+        assert!(!overlay_needs_migration(
+            &fallback,
+            &names(&["eDP-1"]),
+            Some("DP-1"),
+            true
+        ));
+        assert!(overlay_needs_migration(
+            &fallback,
+            &names(&["eDP-1", "DP-1"]),
+            Some("DP-1"),
+            true
+        ));
+        assert!(!overlay_needs_migration(
+            &fallback,
+            &[],
+            Some("DP-1"),
+            false
+        ));
+    }
+}
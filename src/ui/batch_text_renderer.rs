@@ -1,3 +1,4 @@
+use glyphon::cosmic_text::Align;
 use glyphon::{
     Attrs, Buffer, Cache, Color, Family, FontSystem, Metrics, Resolution, Shaping, SwashCache,
     TextArea, TextAtlas, TextBounds, TextRenderer as GlyphonTextRenderer, Viewport,
@@ -13,6 +14,8 @@ pub struct TextItem {
     pub scale: f32,
     pub color: [f32; 4],
     pub max_width: f32,
+    /// Center each line within `max_width` from its shaped glyph width
+    pub centered: bool,
 }
 
 pub struct BatchTextRenderer {
@@ -24,7 +27,9 @@ pub struct BatchTextRenderer {
     device: Arc<Device>,
     queue: Arc<Queue>,
     size: PhysicalSize<u32>,
+    render_scale: f32,
     buffers: Vec<Buffer>,
+    measure_buffer: Buffer,
     _cache_ref: Cache,
 }
 
@@ -46,6 +51,7 @@ impl BatchTextRenderer {
         let mut atlas = TextAtlas::new(&device, &queue, &cache_ref, surface_format);
         let renderer =
             GlyphonTextRenderer::new(&mut atlas, &device, wgpu::MultisampleState::default(), None);
+        let measure_buffer = Buffer::new(&mut font_system, Metrics::new(10.0, 11.0));
 
         Self {
             font_system,
@@ -56,20 +62,55 @@ impl BatchTextRenderer {
             device,
             queue,
             size,
+            render_scale: 1.0,
             buffers: Vec::new(),
+            measure_buffer,
             _cache_ref: cache_ref,
         }
     }
 
     pub fn resize(&mut self, size: PhysicalSize<u32>) {
         self.size = size;
-        self.viewport.update(
-            &self.queue,
-            Resolution {
-                width: size.width,
-                height: size.height,
-            },
+        self.viewport.update(&self.queue, self.resolution());
+    }
+
+    /// Render into a target drawn at `scale` times the logical size; item
+    /// positions stay logical while glyphs are rasterized at the target size
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+        self.viewport.update(&self.queue, self.resolution());
+    }
+
+    /// Measure the rendered width of text at a given scale (single line, no wrapping)
+    pub fn measure_text(&mut self, text: &str, scale: f32) -> f32 {
+        if text.is_empty() {
+            return 0.0;
+        }
+        let font_size = 10.0 * scale;
+        let buffer = &mut self.measure_buffer;
+        buffer.set_metrics(
+            &mut self.font_system,
+            Metrics::new(font_size, font_size * 1.1),
         );
+        buffer.set_size(&mut self.font_system, None, None);
+        buffer.set_text(
+            &mut self.font_system,
+            text,
+            &Attrs::new().family(Family::SansSerif),
+            Shaping::Advanced,
+        );
+        buffer.shape_until_scroll(&mut self.font_system, true);
+        buffer
+            .layout_runs()
+            .map(|run| run.line_w)
+            .next()
+            .unwrap_or(0.0)
+    }
+
+    fn resolution(&self) -> Resolution {
+        let (width, height) =
+            super::render_scale::scaled_size(self.size.width, self.size.height, self.render_scale);
+        Resolution { width, height }
     }
 
     pub fn render_batch(
@@ -82,13 +123,8 @@ impl BatchTextRenderer {
             return;
         }
 
-        self.viewport.update(
-            &self.queue,
-            Resolution {
-                width: self.size.width,
-                height: self.size.height,
-            },
-        );
+        let resolution = self.resolution();
+        self.viewport.update(&self.queue, resolution);
 
         while self.buffers.len() < items.len() {
             self.buffers
@@ -117,6 +153,11 @@ impl BatchTextRenderer {
                 &Attrs::new().family(Family::SansSerif).color(color),
                 Shaping::Advanced,
             );
+            if item.centered {
+                for line in buffer.lines.iter_mut() {
+                    line.set_align(Some(Align::Center));
+                }
+            }
 
             buffer.shape_until_scroll(&mut self.font_system, true);
         }
@@ -134,14 +175,14 @@ impl BatchTextRenderer {
 
                 TextArea {
                     buffer,
-                    left: item.x,
-                    top: item.y,
-                    scale: 1.0,
+                    left: item.x * self.render_scale,
+                    top: item.y * self.render_scale,
+                    scale: self.render_scale,
                     bounds: TextBounds {
                         left: 0,
                         top: 0,
-                        right: self.size.width as i32,
-                        bottom: self.size.height as i32,
+                        right: resolution.width as i32,
+                        bottom: resolution.height as i32,
                     },
                     default_color: color,
                     custom_glyphs: &[],
@@ -158,7 +199,7 @@ impl BatchTextRenderer {
             text_areas,
             &mut self.swash_cache,
         ) {
-            eprintln!("Failed to prepare batched settings text: {}", e);
+            tracing::warn!("Failed to prepare batched settings text: {}", e);
             return;
         }
 
@@ -177,13 +218,13 @@ impl BatchTextRenderer {
             occlusion_query_set: None,
         });
 
-        render_pass.set_scissor_rect(0, 0, self.size.width, self.size.height);
+        render_pass.set_scissor_rect(0, 0, resolution.width, resolution.height);
 
         if let Err(e) = self
             .renderer
             .render(&self.atlas, &self.viewport, &mut render_pass)
         {
-            eprintln!("Failed to render batched settings text: {}", e);
+            tracing::warn!("Failed to render batched settings text: {}", e);
         }
 
         drop(render_pass);
@@ -2,6 +2,8 @@ use std::time::Instant;
 use wgpu::{util::DeviceExt, RenderPipeline, TextureView};
 use winit::dpi::PhysicalSize;
 
+use super::render_scale;
+
 // Animation constants
 const FADE_ANIMATION_DURATION: f32 = 0.2; // 200ms
 const FADE_ANIMATION_SPEED: f32 = 5.0; // Speed factor for easing
@@ -36,6 +38,7 @@ pub struct ButtonPanel {
     target_progress: f32,    // Target animation state
     animation_start_time: Option<Instant>,
     size: PhysicalSize<u32>,
+    render_scale: f32,
 }
 
 impl ButtonPanel {
@@ -126,6 +129,7 @@ impl ButtonPanel {
             target_progress: 0.0,
             animation_start_time: None,
             size,
+            render_scale: 1.0,
         }
     }
 
@@ -169,6 +173,11 @@ impl ButtonPanel {
         }
     }
 
+    /// Whether a fade is in progress
+    pub fn is_animating(&self) -> bool {
+        self.animation_start_time.is_some()
+    }
+
     /// Get current animation progress (0.0 = hidden, 1.0 = visible)
     pub fn animation_progress(&self) -> f32 {
         self.animation_progress
@@ -213,7 +222,7 @@ impl ButtonPanel {
         });
 
         // Set viewport to restrict rendering to button area only
-        render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+        render_scale::set_viewport(&mut render_pass, self.render_scale, x, y, width, height);
 
         render_pass.set_pipeline(&self.render_pipeline);
         render_pass.set_bind_group(0, hover_bind_group, &[]);
@@ -225,4 +234,8 @@ impl ButtonPanel {
     pub fn resize(&mut self, new_size: PhysicalSize<u32>) {
         self.size = new_size;
     }
+
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+    }
 }
@@ -9,9 +9,11 @@ struct VertexOutput {
     @location(0) uv: vec2<f32>,
 };
 
-// Uniform for hover animation
+// Uniform for hover animation and theme colors (see render_pipeline::HoverUniforms)
 struct HoverUniforms {
     opacity_multiplier: f32,
+    panel_color: vec4<f32>,
+    button_color: vec4<f32>,
 }
 
 @group(0) @binding(0)
@@ -44,10 +46,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let edge_width = 0.005;
     let alpha = 1.0 - clamp(dist_to_edge / edge_width + 0.5, 0.0, 1.0);
 
-    // Semi-transparent gray background with hover animation
-    // Base opacity is 0.15, fades in to full 0.15 * opacity_multiplier on hover
-    let base_opacity = 0.15;
-    let animated_opacity = base_opacity * hover.opacity_multiplier;
+    // Semi-transparent background with hover animation
+    // Fades in to the theme's button color alpha (0.15 by default) on hover
+    let animated_opacity = hover.button_color.a * hover.opacity_multiplier;
 
-    return vec4<f32>(0.2, 0.2, 0.2, alpha * animated_opacity);
+    return vec4<f32>(hover.button_color.rgb, alpha * animated_opacity);
 }
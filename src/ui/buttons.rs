@@ -10,6 +10,7 @@ use winit::{
 use speechcore::TranscriptionMode;
 
 use super::button_texture::ButtonTexture;
+use super::render_scale;
 
 // Button base sizes (will be scaled dynamically)
 const COPY_BUTTON_BASE_SIZE: f32 = 16.0; // Base size for scaling calculations
@@ -99,6 +100,7 @@ pub struct ButtonManager {
     config: wgpu::TextureFormat,
     window_width: u32,
     window_height: u32,
+    render_scale: f32,
 }
 
 impl Button {
@@ -119,7 +121,7 @@ impl Button {
             match ButtonTexture::create_default(device, queue, format) {
                 Ok(texture) => Some(texture),
                 Err(e) => {
-                    println!("Failed to create default texture: {}", e);
+                    tracing::warn!("Failed to create default texture: {}", e);
                     None
                 }
             }
@@ -522,6 +524,7 @@ impl Button {
         encoder: &mut wgpu::CommandEncoder,
         queue: &wgpu::Queue,
         transcription_mode: Option<speechcore::TranscriptionMode>,
+        render_scale: f32,
     ) {
         // Update rotation buffer if needed
         if self.button_type == ButtonType::Close
@@ -575,7 +578,14 @@ impl Button {
         let scaled_y = center_y - (scaled_height / 2.0);
 
         // Set viewport with animation scaling
-        render_pass.set_viewport(scaled_x, scaled_y, scaled_width, scaled_height, 0.0, 1.0);
+        render_scale::set_viewport(
+            &mut render_pass,
+            render_scale,
+            scaled_x,
+            scaled_y,
+            scaled_width,
+            scaled_height,
+        );
 
         render_pass.set_pipeline(&self.pipeline);
 
@@ -729,6 +739,7 @@ impl ButtonManager {
             config: format,
             window_width: window_size.width,
             window_height: window_size.height,
+            render_scale: 1.0,
         }
     }
 
@@ -953,6 +964,11 @@ impl ButtonManager {
         // RecordToggle button will dynamically switch between play/pause textures
     }
 
+    /// Scale of the render target relative to the window; hit-testing stays logical
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+    }
+
     pub fn resize(&mut self, window_size: PhysicalSize<u32>) {
         // Update stored window dimensions
         self.window_width = window_size.width;
@@ -1188,7 +1204,13 @@ impl ButtonManager {
             // Render all buttons
             for button_type in self.ordered_button_keys(true) {
                 if let Some(button) = self.buttons.get(&button_type) {
-                    button.render(view, encoder, queue, Some(self.transcription_mode));
+                    button.render(
+                        view,
+                        encoder,
+                        queue,
+                        Some(self.transcription_mode),
+                        self.render_scale,
+                    );
                 }
             }
         }
@@ -1211,13 +1233,14 @@ impl ButtonManager {
             // Reset magic_mode_active when leaving Manual mode to prevent stale state
             if old_mode == TranscriptionMode::Manual && self.magic_mode_active {
                 self.magic_mode_active = false;
-                println!("ButtonManager: Reset magic_mode_active when leaving Manual mode");
+                tracing::debug!("ButtonManager: Reset magic_mode_active when leaving Manual mode");
             }
 
             self.transcription_mode = mode;
-            println!(
+            tracing::debug!(
                 "ButtonManager: Switching from {:?} to {:?} mode",
-                old_mode, mode
+                old_mode,
+                mode
             );
 
             // Update button layout for the new mode
@@ -1260,7 +1283,7 @@ impl ButtonManager {
         for button_type in current_types {
             if !new_button_types.contains(&button_type) {
                 self.buttons.remove(&button_type);
-                println!("ButtonManager: Removed button {:?}", button_type);
+                tracing::debug!("ButtonManager: Removed button {:?}", button_type);
             }
         }
 
@@ -1268,7 +1291,7 @@ impl ButtonManager {
         for &button_type in &new_button_types {
             if !self.buttons.contains_key(&button_type) {
                 self.add_button(button_type);
-                println!("ButtonManager: Added button {:?}", button_type);
+                tracing::debug!("ButtonManager: Added button {:?}", button_type);
             }
         }
 
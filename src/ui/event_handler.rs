@@ -1,5 +1,4 @@
 use std::cell::Cell;
-use std::process::Command;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::Arc;
 use winit::{
@@ -115,18 +114,11 @@ impl EventHandler {
     pub fn copy_transcript(audio_data: &Option<Arc<RwLock<AudioVisualizationData>>>) {
         if let Some(audio_data) = audio_data {
             let audio_data_lock = audio_data.read();
-            let transcript = audio_data_lock.transcript.clone();
+            let transcript = crate::transcript_spill::full_transcript(&audio_data_lock.transcript);
             drop(audio_data_lock);
 
-            // Use wl-copy command for clipboard
-            if let Err(e) = Command::new("wl-copy")
-                .arg(&transcript)
-                .spawn()
-                .map(|child| child.wait_with_output())
-            {
-                println!("Failed to copy to clipboard: {:?}", e);
-            } else {
-                println!("Copied transcript to clipboard using wl-copy");
+            if let Err(e) = crate::copy::WlCopy::copy_to_clipboard(&transcript) {
+                tracing::warn!("Failed to copy to clipboard: {}", e);
             }
         }
     }
@@ -145,6 +137,7 @@ impl EventHandler {
 
             // Set the reset flag
             audio_data_lock.reset_requested = true;
+            crate::session_history::transcript_reset();
 
             // Reset UI state
             *last_transcript_len = 0;
@@ -158,9 +151,10 @@ impl EventHandler {
             // IMMEDIATE: Atomic toggle - UI thread continues instantly
             let was_recording = recording.load(Ordering::Relaxed);
             recording.store(!was_recording, Ordering::Relaxed);
-            println!(
+            tracing::debug!(
                 "Recording state toggled atomically: {} -> {} (non-blocking)",
-                was_recording, !was_recording
+                was_recording,
+                !was_recording
             );
             // All transcription threads will detect this change via their atomic flag polling
         }
@@ -201,7 +195,7 @@ impl EventHandler {
                         );
                     }
                     ButtonType::Close => {
-                        println!("Close button clicked, initiating shutdown sequence");
+                        tracing::debug!("Close button clicked, initiating shutdown sequence");
                         // First set the running flag to false
                         Self::quit(running);
 
@@ -220,7 +214,7 @@ impl EventHandler {
                             .map(|rec| rec.load(Ordering::Relaxed))
                             .unwrap_or(false);
 
-                        println!("Manual RecordToggle clicked (current state: {}) - UI continues immediately", is_currently_recording);
+                        tracing::debug!("Manual RecordToggle clicked (current state: {}) - UI continues immediately", is_currently_recording);
 
                         if let Some(sender) = &self.manual_session_sender {
                             let sender = sender.clone();
@@ -237,21 +231,21 @@ impl EventHandler {
                                 };
 
                                 if let Err(e) = sender.send(command).await {
-                                    eprintln!("Failed to send manual session command: {}", e);
+                                    tracing::warn!("Failed to send manual session command: {}", e);
                                 } else {
-                                    println!(
+                                    tracing::debug!(
                                         "Manual session command sent successfully (background)"
                                     );
                                 }
                             });
                         } else {
-                            eprintln!("Manual session sender not available");
+                            tracing::warn!("Manual session sender not available");
                         }
                         // UI thread continues immediately - manual session processor handles the command
                     }
                     ButtonType::Accept => {
                         // Accept functionality is now handled by RecordToggle button
-                        println!("Accept button clicked but should not be used directly - use RecordToggle instead");
+                        tracing::debug!("Accept button clicked but should not be used directly - use RecordToggle instead");
                     }
                     ButtonType::ModeToggle => {
                         // IMMEDIATE UI response: Calculate new mode and send command asynchronously
@@ -267,9 +261,10 @@ impl EventHandler {
                             }
                         };
 
-                        println!(
+                        tracing::debug!(
                             "Mode toggle clicked: {:?} -> {:?} (UI continues immediately)",
-                            current_mode, new_mode
+                            current_mode,
+                            new_mode
                         );
 
                         // ASYNC: Send mode switch command without blocking UI
@@ -280,13 +275,17 @@ impl EventHandler {
                                     .send(speechcore::ManualSessionCommand::SwitchMode(new_mode))
                                     .await
                                 {
-                                    eprintln!("Failed to send mode switch command: {}", e);
+                                    tracing::warn!("Failed to send mode switch command: {}", e);
                                 } else {
-                                    println!("Mode switch command sent successfully (background)");
+                                    tracing::debug!(
+                                        "Mode switch command sent successfully (background)"
+                                    );
                                 }
                             });
                         } else {
-                            eprintln!("Manual session sender not available for mode switching");
+                            tracing::warn!(
+                                "Manual session sender not available for mode switching"
+                            );
                         }
                         // UI thread continues immediately - transcription system handles mode switch
                     }
@@ -297,7 +296,7 @@ impl EventHandler {
                         if let Some(magic_mode) = &self.magic_mode_enabled {
                             magic_mode.store(new_state, Ordering::Relaxed);
                         }
-                        println!(
+                        tracing::debug!(
                             "Magic mode toggled: now {}",
                             if new_state { "ON" } else { "OFF" }
                         );
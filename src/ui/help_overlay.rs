@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use winit::dpi::PhysicalSize;
+
+use super::batch_text_renderer::{BatchTextRenderer, TextItem};
+use super::widgets::{WidgetDrawStats, WidgetRenderer};
+use crate::help::HelpSection;
+
+const PANEL_INSET: f32 = 6.0;
+const PADDING: f32 = 8.0;
+const TITLE_HEIGHT: f32 = 16.0;
+const SECTION_GAP: f32 = 6.0;
+
+/// Quick help panel drawn over a window's content.
+///
+/// The content is generated by `crate::help` from the runtime configuration.
+/// Any key or click dismisses it; the scroll wheel scrolls long help.
+pub struct HelpOverlay {
+    visible: bool,
+    sections: Vec<HelpSection>,
+    scroll_offset: f32,
+    content_height: f32,
+    batch_text_renderer: BatchTextRenderer,
+    widget_renderer: WidgetRenderer,
+}
+
+impl HelpOverlay {
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        size: PhysicalSize<u32>,
+        surface_format: wgpu::TextureFormat,
+    ) -> Self {
+        Self {
+            visible: false,
+            sections: Vec::new(),
+            scroll_offset: 0.0,
+            content_height: 0.0,
+            batch_text_renderer: BatchTextRenderer::new(
+                Arc::new(device.clone()),
+                Arc::new(queue.clone()),
+                size,
+                surface_format,
+            ),
+            widget_renderer: WidgetRenderer::new(device, surface_format),
+        }
+    }
+
+    pub fn show(&mut self, sections: Vec<HelpSection>) {
+        self.sections = sections;
+        self.scroll_offset = 0.0;
+        self.visible = true;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Widget draw statistics since the last call (for debug stats)
+    pub fn take_widget_draw_stats(&self) -> WidgetDrawStats {
+        self.widget_renderer.take_draw_stats()
+    }
+
+    pub fn resize(&mut self, size: PhysicalSize<u32>) {
+        self.batch_text_renderer.resize(size);
+    }
+
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.batch_text_renderer.set_render_scale(scale);
+        self.widget_renderer.set_render_scale(scale);
+    }
+
+    /// Scroll the help content by `delta` pixels (positive scrolls down)
+    pub fn scroll(&mut self, delta: f32, window_height: u32) {
+        let visible_height = window_height as f32 - (PANEL_INSET + PADDING) * 2.0;
+        let max_offset = (self.content_height - visible_height).max(0.0);
+        self.scroll_offset = (self.scroll_offset + delta).clamp(0.0, max_offset);
+    }
+
+    pub fn render(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        queue: &wgpu::Queue,
+        window_width: u32,
+        window_height: u32,
+        scale: f32,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        let panel_width = window_width as f32 - PANEL_INSET * 2.0;
+        let panel_height = window_height as f32 - PANEL_INSET * 2.0;
+
+        self.widget_renderer.draw_rounded_rect(
+            encoder,
+            view,
+            queue,
+            PANEL_INSET,
+            PANEL_INSET,
+            panel_width,
+            panel_height,
+            8.0,
+            [0.003, 0.003, 0.005, 0.96],
+            window_width,
+            window_height,
+        );
+
+        let line_height = 12.0 * scale;
+        let title_height = TITLE_HEIGHT * scale.max(1.0);
+        let key_column = (panel_width * 0.38).max(60.0);
+        let text_x = PANEL_INSET + PADDING;
+        let top = PANEL_INSET + PADDING;
+        let bottom = PANEL_INSET + panel_height - PADDING;
+
+        let mut text_items: Vec<TextItem> = Vec::new();
+        let mut y = top - self.scroll_offset;
+
+        for section in &self.sections {
+            if y + title_height > top && y < bottom {
+                text_items.push(TextItem {
+                    text: section.title.to_string(),
+                    x: text_x,
+                    y,
+                    scale,
+                    color: [0.010, 0.787, 0.214, 1.0],
+                    max_width: panel_width - PADDING * 2.0,
+                    centered: false,
+                });
+            }
+            y += title_height;
+
+            for entry in &section.entries {
+                if y >= top && y + line_height <= bottom {
+                    text_items.push(TextItem {
+                        text: entry.keys.clone(),
+                        x: text_x,
+                        y,
+                        scale: scale * 0.9,
+                        color: [0.885, 0.885, 0.930, 1.0],
+                        max_width: key_column - 4.0,
+                        centered: false,
+                    });
+                    text_items.push(TextItem {
+                        text: entry.action.clone(),
+                        x: text_x + key_column,
+                        y,
+                        scale: scale * 0.9,
+                        color: [0.604, 0.604, 0.670, 1.0],
+                        max_width: panel_width - key_column - PADDING * 2.0,
+                        centered: false,
+                    });
+                }
+                y += line_height;
+            }
+            y += SECTION_GAP;
+        }
+
+        self.content_height = y + self.scroll_offset - top;
+        // A scale change can shrink the content below the current offset
+        let max_offset = (self.content_height - (bottom - top)).max(0.0);
+        self.scroll_offset = self.scroll_offset.min(max_offset);
+
+        self.widget_renderer
+            .flush(encoder, view, window_width, window_height);
+        self.batch_text_renderer
+            .render_batch(encoder, view, &text_items);
+    }
+}
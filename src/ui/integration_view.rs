@@ -0,0 +1,236 @@
+use std::cell::Cell;
+
+use super::batch_text_renderer::TextItem;
+use super::widgets::WidgetRenderer;
+use crate::config::APPLICATION_ID;
+use crate::i18n::{tr, tr_args};
+use crate::portal_status::{self, PortalCommand, PortalStatus, SessionState};
+
+const LINE_HEIGHT: f32 = 22.0;
+const BUTTON_HEIGHT: f32 = 28.0;
+const BUTTON_WIDTH: f32 = 130.0;
+const BUTTON_GAP: f32 = 8.0;
+const LABEL_COLOR: [f32; 4] = [0.5, 0.5, 0.55, 1.0];
+const VALUE_COLOR: [f32; 4] = [0.8, 0.8, 0.85, 1.0];
+
+/// Status lines above the buttons: application id, shortcuts, bound keys, paste
+const STATUS_LINES: usize = 4;
+
+fn state_color(state: SessionState) -> [f32; 4] {
+    match state {
+        SessionState::Active => [0.010, 0.787, 0.214, 1.0],
+        SessionState::Failed => [0.90, 0.35, 0.35, 1.0],
+        SessionState::Connecting | SessionState::Disabled => VALUE_COLOR,
+    }
+}
+
+/// Label, value and value color of each status line
+fn status_lines(status: &PortalStatus) -> [(&'static str, String, [f32; 4]); STATUS_LINES] {
+    let shortcuts = match (status.shortcuts, status.shortcut_provider) {
+        (SessionState::Active, Some(provider)) => {
+            tr_args("integration.active_via", &[("provider", &provider)])
+        }
+        (state, _) => state.label().to_string(),
+    };
+    let accelerators = if status.accelerators.is_empty() {
+        tr("integration.none").to_string()
+    } else {
+        status.accelerators.join(", ")
+    };
+    [
+        (
+            tr("integration.application_id"),
+            APPLICATION_ID.to_string(),
+            VALUE_COLOR,
+        ),
+        (
+            tr("integration.global_shortcuts"),
+            shortcuts,
+            state_color(status.shortcuts),
+        ),
+        (tr("integration.bound_shortcuts"), accelerators, VALUE_COLOR),
+        (
+            tr("integration.paste_session"),
+            status.paste.label().to_string(),
+            state_color(status.paste),
+        ),
+    ]
+}
+
+/// Portal session status with buttons that bring the permission dialogs back
+pub struct IntegrationView {
+    x: f32,
+    y: f32,
+    width: f32,
+    /// `portal_status::revision()` at the last render
+    shown_revision: Cell<u64>,
+}
+
+impl IntegrationView {
+    pub fn new(x: f32, y: f32, width: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            shown_revision: Cell::new(u64::MAX),
+        }
+    }
+
+    pub fn set_bounds(&mut self, x: f32, y: f32, width: f32) {
+        self.x = x;
+        self.y = y;
+        self.width = width;
+    }
+
+    /// Whether the portal status changed since the tab was last drawn
+    pub fn is_stale(&self) -> bool {
+        self.shown_revision.get() != portal_status::revision()
+    }
+
+    fn buttons_y(&self) -> f32 {
+        self.y + STATUS_LINES as f32 * LINE_HEIGHT + 10.0
+    }
+
+    fn buttons(&self) -> [(PortalCommand, &'static str, f32); 2] {
+        [
+            (
+                PortalCommand::RebindShortcuts,
+                tr("integration.rebind"),
+                self.x,
+            ),
+            (
+                PortalCommand::ReconnectPaste,
+                tr("integration.reconnect"),
+                self.x + BUTTON_WIDTH + BUTTON_GAP,
+            ),
+        ]
+    }
+
+    /// Send a re-init request when a button is clicked
+    pub fn handle_click(&mut self, x: f32, y: f32) -> bool {
+        let buttons_y = self.buttons_y();
+        if y < buttons_y || y > buttons_y + BUTTON_HEIGHT {
+            return false;
+        }
+        let Some((command, _, _)) = self
+            .buttons()
+            .into_iter()
+            .find(|&(_, _, button_x)| x >= button_x && x <= button_x + BUTTON_WIDTH)
+        else {
+            return false;
+        };
+        if !portal_status::request(command) {
+            crate::toast::show(match command {
+                PortalCommand::RebindShortcuts => "Global shortcuts are disabled in the config",
+                PortalCommand::ReconnectPaste => "The XDG portal is disabled in the config",
+            });
+        }
+        true
+    }
+
+    pub fn render(
+        &self,
+        y_offset: f32,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        widget_renderer: &WidgetRenderer,
+        text_items: &mut Vec<TextItem>,
+        queue: &wgpu::Queue,
+        window_width: u32,
+        window_height: u32,
+    ) {
+        self.shown_revision.set(portal_status::revision());
+        let status = portal_status::snapshot();
+        let value_x = self.x + 120.0;
+
+        for (index, (label, value, color)) in status_lines(&status).into_iter().enumerate() {
+            let y = self.y + y_offset + 6.0 + index as f32 * LINE_HEIGHT;
+            text_items.push(TextItem {
+                text: label.to_string(),
+                x: self.x + 4.0,
+                y,
+                scale: 1.0,
+                color: LABEL_COLOR,
+                max_width: value_x - self.x,
+                centered: false,
+            });
+            text_items.push(TextItem {
+                text: value,
+                x: value_x,
+                y,
+                scale: 1.0,
+                color,
+                max_width: (self.x + self.width - value_x).max(1.0),
+                centered: false,
+            });
+        }
+
+        let buttons_y = self.buttons_y() + y_offset;
+        for (_, label, button_x) in self.buttons() {
+            widget_renderer.draw_rounded_rect(
+                encoder,
+                view,
+                queue,
+                button_x,
+                buttons_y,
+                BUTTON_WIDTH,
+                BUTTON_HEIGHT,
+                8.0,
+                [0.15, 0.15, 0.2, 1.0],
+                window_width,
+                window_height,
+            );
+            text_items.push(TextItem {
+                text: label.to_string(),
+                x: button_x,
+                y: buttons_y + (BUTTON_HEIGHT - 14.0) / 2.0,
+                scale: 1.0,
+                color: VALUE_COLOR,
+                max_width: BUTTON_WIDTH,
+                centered: true,
+            });
+        }
+
+        let error = status
+            .last_error
+            .unwrap_or_else(|| tr("integration.no_errors").to_string());
+        text_items.push(TextItem {
+            text: tr_args("integration.last_error", &[("error", &error)]),
+            x: self.x + 4.0,
+            y: buttons_y + BUTTON_HEIGHT + 12.0,
+            scale: 1.0,
+            color: LABEL_COLOR,
+            max_width: self.width,
+            centered: false,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_lines_name_the_active_provider_and_keys() {
+        let status = PortalStatus {
+            shortcuts: SessionState::Active,
+            shortcut_provider: Some(crate::hotkeys::HotkeyProvider::Portal),
+            accelerators: vec!["LOGO+backslash".to_string()],
+            paste: SessionState::Failed,
+            last_error: None,
+        };
+        let lines = status_lines(&status);
+        assert_eq!(lines[1].1, "active via XDG GlobalShortcuts portal");
+        assert_eq!(lines[2].1, "LOGO+backslash");
+        assert_eq!(lines[3].1, "not available");
+
+        let status = PortalStatus {
+            shortcuts: SessionState::Connecting,
+            accelerators: Vec::new(),
+            ..status
+        };
+        let lines = status_lines(&status);
+        assert_eq!(lines[1].1, "connecting");
+        assert_eq!(lines[2].1, "none");
+    }
+}
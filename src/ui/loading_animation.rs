@@ -11,6 +11,7 @@ pub struct LoadingAnimation {
     start_time: std::time::Instant,
     animation_duration: std::time::Duration,
     last_processing_state: Option<ProcessingState>,
+    render_scale: f32,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -39,9 +40,15 @@ impl LoadingAnimation {
             start_time: std::time::Instant::now(),
             animation_duration: std::time::Duration::from_millis(800), // Faster 0.8 second cycle
             last_processing_state: None,
+            render_scale: 1.0,
         }
     }
 
+    /// Scale of the render target relative to the window (see `render_scale`)
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+    }
+
     /// Set animation state based on processing state
     pub fn set_processing_state(&mut self, processing_state: ProcessingState) {
         // Only reset start time if the processing state actually changed
@@ -74,6 +81,14 @@ impl LoadingAnimation {
         let elapsed = self.start_time.elapsed();
         let progress = (elapsed.as_secs_f32() / self.animation_duration.as_secs_f32()) % 1.0;
 
+        // Every quad is derived from these, so scaling them maps the animation
+        // onto the render target
+        let (center_x, center_y, size) = (
+            center_x * self.render_scale,
+            center_y * self.render_scale,
+            size * self.render_scale,
+        );
+
         match self.state {
             AnimationState::Dots => {
                 self.render_dots_animation(encoder, view, center_x, center_y, size, color, progress)
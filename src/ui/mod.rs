@@ -5,12 +5,16 @@ pub mod button_texture;
 pub mod buttons;
 pub mod event_handler;
 pub mod gpu_utils;
+pub mod help_overlay;
+pub mod integration_view;
 pub mod layout_manager;
 pub mod loading_animation;
 pub mod render_context;
 pub mod render_pipeline;
+pub mod render_scale;
 pub mod scroll_state;
 pub mod scrollbar;
+pub mod session_history;
 pub mod settings_panel;
 pub mod settings_window;
 pub mod spectogram;
@@ -18,6 +22,7 @@ pub mod status_bar;
 pub mod text_processor;
 pub mod text_renderer;
 pub mod text_window;
+pub mod theme;
 pub mod timer_badge;
 pub mod tooltip;
 pub mod typewriter;
@@ -1,11 +1,26 @@
 use wgpu::{self, util::DeviceExt};
 
+use super::render_scale;
+
+/// Uniform shared by the panel shaders (`HoverUniforms` in text_window.wgsl,
+/// button_panel.wgsl and rounded_rect.wgsl)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct HoverUniforms {
+    pub opacity_multiplier: f32,
+    pub _padding: [f32; 3],
+    pub panel_color: [f32; 4],
+    pub button_color: [f32; 4],
+}
+
 pub struct RenderPipelines {
     pub rounded_rect_pipeline: wgpu::RenderPipeline,
     pub rounded_rect_vertices: wgpu::Buffer,
     pub hover_uniform_buffer: wgpu::Buffer,
     pub hover_bind_group: wgpu::BindGroup,
     pub hover_bind_group_layout: wgpu::BindGroupLayout,
+    /// Scale of the render target relative to the window (see `render_scale`)
+    pub render_scale: f32,
 }
 
 impl RenderPipelines {
@@ -108,11 +123,11 @@ impl RenderPipelines {
             usage: wgpu::BufferUsages::VERTEX,
         });
 
-        // Create uniform buffer for hover animation
-        // Start with opacity_multiplier = 0.0 (base opacity)
+        // Create uniform buffer for hover animation and theme colors
+        // Start with opacity_multiplier = 0.0 (base opacity); colors are written every frame
         let hover_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Hover Uniform Buffer"),
-            contents: bytemuck::cast_slice(&[0.0f32]),
+            contents: bytemuck::bytes_of(&<HoverUniforms as bytemuck::Zeroable>::zeroed()),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
@@ -132,6 +147,7 @@ impl RenderPipelines {
             hover_uniform_buffer,
             hover_bind_group,
             hover_bind_group_layout,
+            render_scale: 1.0,
         }
     }
 
@@ -182,13 +198,13 @@ impl RenderPipelines {
         });
 
         // Set viewport to cover just the spectrogram area
-        render_pass.set_viewport(
+        render_scale::set_viewport(
+            &mut render_pass,
+            self.render_scale,
             spec_x,
             spec_y,
             spectrogram_width,
             spectrogram_height,
-            0.0,
-            1.0,
         );
 
         render_pass.set_pipeline(&self.rounded_rect_pipeline);
@@ -0,0 +1,329 @@
+//! Reduced-resolution rendering for the overlay (`display_config.render_scale`).
+
+/// Smallest accepted render scale
+pub const MIN_RENDER_SCALE: f32 = 0.5;
+/// Full resolution; no offscreen target is created
+pub const MAX_RENDER_SCALE: f32 = 1.0;
+
+/// Auto scale for software adapters such as llvmpipe
+const SOFTWARE_RENDER_SCALE: f32 = 0.5;
+/// Auto scale for adapters that report very low limits
+const LOW_LIMITS_RENDER_SCALE: f32 = 0.75;
+/// Adapters whose 2D texture limit is below this are treated as low-end
+const LOW_LIMITS_MAX_TEXTURE_DIMENSION: u32 = 4096;
+
+pub fn clamp_render_scale(scale: f32) -> f32 {
+    if scale.is_finite() {
+        scale.clamp(MIN_RENDER_SCALE, MAX_RENDER_SCALE)
+    } else {
+        MAX_RENDER_SCALE
+    }
+}
+
+/// Pick the render scale and a short reason for the log.
+///
+/// A configured value always wins; otherwise software renderers and adapters
+/// with very low limits get a reduced scale.
+pub fn choose_render_scale(
+    configured: Option<f32>,
+    device_type: wgpu::DeviceType,
+    max_texture_dimension_2d: u32,
+) -> (f32, &'static str) {
+    if let Some(scale) = configured {
+        return (clamp_render_scale(scale), "configured");
+    }
+    if device_type == wgpu::DeviceType::Cpu {
+        (SOFTWARE_RENDER_SCALE, "software renderer")
+    } else if max_texture_dimension_2d < LOW_LIMITS_MAX_TEXTURE_DIMENSION {
+        (LOW_LIMITS_RENDER_SCALE, "low adapter limits")
+    } else {
+        (MAX_RENDER_SCALE, "default")
+    }
+}
+
+/// Render scale for `adapter`, logged once so the decision is visible
+pub fn render_scale_for_adapter(configured: Option<f32>, adapter: &wgpu::Adapter) -> f32 {
+    let info = adapter.get_info();
+    let (scale, reason) = choose_render_scale(
+        configured,
+        info.device_type,
+        adapter.limits().max_texture_dimension_2d,
+    );
+    tracing::info!(
+        "Overlay render scale {:.2} ({}, adapter '{}')",
+        scale,
+        reason,
+        info.name
+    );
+    scale
+}
+
+/// Size of the render target for a logical size (never below 1x1)
+pub fn scaled_size(width: u32, height: u32, scale: f32) -> (u32, u32) {
+    (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    )
+}
+
+/// Set a viewport given in logical pixels on a render target drawn at `scale`
+pub fn set_viewport(
+    render_pass: &mut wgpu::RenderPass<'_>,
+    scale: f32,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+) {
+    render_pass.set_viewport(
+        x * scale,
+        y * scale,
+        width * scale,
+        height * scale,
+        0.0,
+        1.0,
+    );
+}
+
+/// Scale a logical (x, y, width, height) pixel rect, e.g. for scissor rects
+pub fn scale_rect(rect: (u32, u32, u32, u32), scale: f32) -> (u32, u32, u32, u32) {
+    let (x, y, width, height) = rect;
+    (
+        (x as f32 * scale).floor() as u32,
+        (y as f32 * scale).floor() as u32,
+        (width as f32 * scale).ceil() as u32,
+        (height as f32 * scale).ceil() as u32,
+    )
+}
+
+/// Offscreen texture the overlay renders into below full resolution
+pub struct ScaledTarget {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    sampler: wgpu::Sampler,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    pipeline: wgpu::RenderPipeline,
+    format: wgpu::TextureFormat,
+}
+
+impl ScaledTarget {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        scale: f32,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Scaled Target Blit Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("blit.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Scaled Target Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Scaled Target Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Scaled Target Blit Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                // The offscreen texture already holds the composited frame
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Scaled Target Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let (texture, view, bind_group) = Self::create_texture(
+            device,
+            format,
+            &bind_group_layout,
+            &sampler,
+            scaled_size(width, height, scale),
+        );
+
+        Self {
+            texture,
+            view,
+            sampler,
+            bind_group_layout,
+            bind_group,
+            pipeline,
+            format,
+        }
+    }
+
+    fn create_texture(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        (width, height): (u32, u32),
+    ) -> (wgpu::Texture, wgpu::TextureView, wgpu::BindGroup) {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Scaled Overlay Target"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Scaled Target Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        });
+        (texture, view, bind_group)
+    }
+
+    /// Recreate the texture for a new logical size or scale
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32, scale: f32) {
+        let size = scaled_size(width, height, scale);
+        if (self.texture.width(), self.texture.height()) == size {
+            return;
+        }
+        let (texture, view, bind_group) = Self::create_texture(
+            device,
+            self.format,
+            &self.bind_group_layout,
+            &self.sampler,
+            size,
+        );
+        self.texture = texture;
+        self.view = view;
+        self.bind_group = bind_group;
+    }
+
+    /// View the overlay components render into
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Stretch the rendered frame over the whole surface
+    pub fn blit(&self, encoder: &mut wgpu::CommandEncoder, surface_view: &wgpu::TextureView) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Scaled Target Blit Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_scale_follows_the_adapter_and_config_wins() {
+        assert_eq!(
+            choose_render_scale(None, wgpu::DeviceType::Cpu, 8192),
+            (SOFTWARE_RENDER_SCALE, "software renderer")
+        );
+        assert_eq!(
+            choose_render_scale(None, wgpu::DeviceType::IntegratedGpu, 2048).0,
+            LOW_LIMITS_RENDER_SCALE
+        );
+        assert_eq!(
+            choose_render_scale(None, wgpu::DeviceType::DiscreteGpu, 16384).0,
+            MAX_RENDER_SCALE
+        );
+        assert_eq!(
+            choose_render_scale(Some(0.8), wgpu::DeviceType::Cpu, 2048),
+            (0.8, "configured")
+        );
+        assert_eq!(
+            choose_render_scale(Some(0.1), wgpu::DeviceType::DiscreteGpu, 16384).0,
+            MIN_RENDER_SCALE
+        );
+    }
+
+    #[test]
+    fn scaled_rects_cover_the_logical_rect() {
+        assert_eq!(scaled_size(240, 190, 0.5), (120, 95));
+        assert_eq!(scaled_size(1, 1, 0.5), (1, 1));
+        // Scissor rects round outward so clipped text is never cut short
+        assert_eq!(scale_rect((3, 5, 101, 33), 0.5), (1, 2, 51, 17));
+    }
+}
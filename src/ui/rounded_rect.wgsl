@@ -9,9 +9,11 @@ struct VertexOutput {
     @location(0) uv: vec2<f32>,
 };
 
-// Uniform for hover animation
+// Uniform for hover animation and theme colors (see render_pipeline::HoverUniforms)
 struct HoverUniforms {
     opacity_multiplier: f32,
+    panel_color: vec4<f32>,
+    button_color: vec4<f32>,
 }
 
 @group(0) @binding(0)
@@ -62,7 +64,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let target_opacity = 0.6;
     let animated_opacity = base_opacity + (target_opacity - base_opacity) * hover.opacity_multiplier;
 
-    let main_color = vec4<f32>(0.0, 0.0, 0.0, main_alpha * animated_opacity);
+    let main_color = vec4<f32>(hover.panel_color.rgb, main_alpha * animated_opacity * hover.panel_color.a);
 
     // Blend shadow and main rectangle: shadow behind, main on top
     let result = mix(shadow_color, main_color, main_alpha);
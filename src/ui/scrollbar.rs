@@ -1,5 +1,7 @@
 use wgpu::util::DeviceExt;
 
+use super::render_scale;
+
 pub const SCROLLBAR_WIDTH: u32 = 6;
 
 pub struct Scrollbar {
@@ -10,6 +12,8 @@ pub struct Scrollbar {
     pub scroll_offset: f32,
     pub max_scroll_offset: f32,
     pub auto_scroll: bool,
+    /// Scale of the render target relative to the window (see `render_scale`)
+    pub render_scale: f32,
 }
 
 impl Scrollbar {
@@ -153,9 +157,39 @@ impl Scrollbar {
             scroll_offset: 0.0,
             max_scroll_offset: 0.0,
             auto_scroll: true,
+            render_scale: 1.0,
         }
     }
 
+    /// Thumb top and height within a track of `track_height` pixels
+    pub fn thumb_geometry(
+        track_height: f32,
+        scroll_offset: f32,
+        max_scroll_offset: f32,
+    ) -> (f32, f32) {
+        // Ensure the ratio calculation is correct to size the thumb correctly
+        let content_height = track_height + max_scroll_offset;
+        let visible_ratio = if content_height > 0.0 {
+            track_height / content_height
+        } else {
+            1.0
+        };
+
+        // Minimum height for the thumb, but make sure it's proportional to content
+        let thumb_height = (track_height * visible_ratio).max(20.0).min(track_height);
+
+        // Calculate scroll progress (0.0 to 1.0)
+        let scroll_progress = if max_scroll_offset > 0.0 {
+            scroll_offset / max_scroll_offset
+        } else {
+            0.0
+        };
+
+        // Calculate where to place the thumb within the track
+        let available_track = track_height - thumb_height;
+        (scroll_progress * available_track, thumb_height)
+    }
+
     pub fn render(
         &self,
         view: &wgpu::TextureView,
@@ -182,13 +216,13 @@ impl Scrollbar {
         // Set viewport for scrollbar track - Ensure exact height calculation
         // Use the full text_area_height value
         let track_height = (text_area_height - gap) as f32;
-        render_pass.set_viewport(
+        render_scale::set_viewport(
+            &mut render_pass,
+            self.render_scale,
             (window_width - SCROLLBAR_WIDTH) as f32,
             0.0,
             SCROLLBAR_WIDTH as f32,
             track_height,
-            0.0,
-            1.0,
         );
 
         // Draw scrollbar track
@@ -197,37 +231,17 @@ impl Scrollbar {
         render_pass.set_vertex_buffer(0, self.vertices.slice(..4 * 8));
         render_pass.draw(0..4, 0..1);
 
-        // Calculate thumb position and size
-        // Ensure the ratio calculation is correct to size the thumb correctly
-        let content_height = track_height + self.max_scroll_offset;
-        let visible_ratio = if content_height > 0.0 {
-            track_height / content_height
-        } else {
-            1.0
-        };
-
-        // Minimum height for the thumb, but make sure it's proportional to content
-        let thumb_height = (track_height * visible_ratio).max(20.0).min(track_height);
-
-        // Calculate scroll progress (0.0 to 1.0)
-        let scroll_progress = if self.max_scroll_offset > 0.0 {
-            self.scroll_offset / self.max_scroll_offset
-        } else {
-            0.0
-        };
-
-        // Calculate where to place the thumb within the track
-        let available_track = track_height - thumb_height;
-        let thumb_top = scroll_progress * available_track;
+        let (thumb_top, thumb_height) =
+            Self::thumb_geometry(track_height, self.scroll_offset, self.max_scroll_offset);
 
         // Set viewport for scrollbar thumb
-        render_pass.set_viewport(
+        render_scale::set_viewport(
+            &mut render_pass,
+            self.render_scale,
             (window_width - SCROLLBAR_WIDTH) as f32,
             thumb_top,
             SCROLLBAR_WIDTH as f32,
             thumb_height,
-            0.0,
-            1.0,
         );
 
         // Draw scrollbar thumb
@@ -238,13 +252,13 @@ impl Scrollbar {
 
         // Draw auto-scroll indicator
         if self.auto_scroll {
-            render_pass.set_viewport(
+            render_scale::set_viewport(
+                &mut render_pass,
+                self.render_scale,
                 (window_width - SCROLLBAR_WIDTH) as f32,
                 track_height - 5.0,
                 SCROLLBAR_WIDTH as f32,
                 5.0,
-                0.0,
-                1.0,
             );
 
             render_pass.set_pipeline(&self.pipeline);
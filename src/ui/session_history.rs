@@ -0,0 +1,298 @@
+use super::batch_text_renderer::TextItem;
+use super::widgets::{VirtualList, VisibleRow, WidgetRenderer};
+use crate::session_history::{self, SessionEntry};
+use crate::transcript_writer::{self, TranscriptHistoryEntry};
+
+const DAY_ROW_HEIGHT: f32 = 22.0;
+const ENTRY_ROW_HEIGHT: f32 = 26.0;
+const TIME_COLUMN_WIDTH: f32 = 56.0;
+const CHAR_WIDTH: f32 = 6.0;
+const SESSIONS_HEADER: &str = "Sessions since start";
+
+enum HistoryRow {
+    Day(String),
+    Entry(TranscriptHistoryEntry),
+    Session(SessionEntry),
+}
+
+impl HistoryRow {
+    fn height(&self) -> f32 {
+        match self {
+            HistoryRow::Day(_) => DAY_ROW_HEIGHT,
+            HistoryRow::Entry(_) | HistoryRow::Session(_) => ENTRY_ROW_HEIGHT,
+        }
+    }
+
+    /// Time column and text of a selectable row
+    fn content(&self) -> Option<(String, &str)> {
+        match self {
+            HistoryRow::Day(_) => None,
+            HistoryRow::Entry(entry) => Some((entry.time().to_string(), &entry.text)),
+            HistoryRow::Session(session) => Some((
+                session.started.format("%H:%M:%S").to_string(),
+                &session.text,
+            )),
+        }
+    }
+}
+
+/// In-memory sessions first, then the history file; each newest first, with a
+/// header row whenever the day changes
+fn build_rows(
+    sessions: Vec<SessionEntry>,
+    entries: Vec<TranscriptHistoryEntry>,
+) -> Vec<HistoryRow> {
+    let mut rows = Vec::with_capacity(sessions.len() + 1 + entries.len() + entries.len() / 8);
+    if !sessions.is_empty() {
+        rows.push(HistoryRow::Day(SESSIONS_HEADER.to_string()));
+        rows.extend(sessions.into_iter().rev().map(HistoryRow::Session));
+    }
+    let mut current_day: Option<String> = None;
+    for entry in entries.into_iter().rev() {
+        if current_day.as_deref() != Some(entry.date()) {
+            current_day = Some(entry.date().to_string());
+            rows.push(HistoryRow::Day(entry.date().to_string()));
+        }
+        rows.push(HistoryRow::Entry(entry));
+    }
+    rows
+}
+
+/// Shorten `text` to roughly fit `width` pixels on one line
+fn fit_line(text: &str, width: f32) -> String {
+    let max_chars = (width / CHAR_WIDTH).max(1.0) as usize;
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut fitted: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    fitted.push('\u{2026}');
+    fitted
+}
+
+/// Browser for this run's sessions and the transcript history file, rendered through a `VirtualList`
+pub struct SessionHistoryView {
+    list: VirtualList,
+    rows: Vec<HistoryRow>,
+    history_enabled: bool,
+    history_path: String,
+    message: Option<String>,
+}
+
+impl SessionHistoryView {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            list: VirtualList::new(x, y, width, height),
+            rows: Vec::new(),
+            history_enabled: false,
+            history_path: String::new(),
+            message: None,
+        }
+    }
+
+    pub fn configure(&mut self, history_enabled: bool, history_path: &str) {
+        self.history_enabled = history_enabled;
+        self.history_path = history_path.to_string();
+    }
+
+    pub fn set_bounds(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.list.set_bounds(x, y, width, height);
+    }
+
+    /// Re-read the sessions and the history file (called when the tab is opened)
+    pub fn reload(&mut self) {
+        self.message = None;
+        match transcript_writer::read_transcript_history(&self.history_path) {
+            Ok(entries) => self.rows = build_rows(session_history::entries(), entries),
+            Err(e) => {
+                self.rows.clear();
+                self.message = Some(format!("Could not read history: {}", e));
+            }
+        }
+        if self.rows.is_empty() && self.message.is_none() {
+            self.message = Some(if self.history_enabled {
+                "No transcripts saved yet".to_string()
+            } else {
+                "Enable save_transcript_history to keep a history".to_string()
+            });
+        }
+        self.list.set_selected(None);
+        self.list.invalidate();
+        self.list
+            .sync(self.rows.len(), |index| self.rows[index].height());
+        self.list.scroll_to_top();
+    }
+
+    pub fn handle_scroll(&mut self, pixels: f32) {
+        self.list.scroll_by(pixels);
+    }
+
+    /// Select the clicked entry; clicking the selected entry again copies it
+    pub fn handle_click(&mut self, x: f32, y: f32) -> bool {
+        if !self.list.contains(x, y) {
+            return false;
+        }
+        let previous = self.list.selected();
+        let Some(index) = self.list.item_at(x, y) else {
+            return true;
+        };
+        let Some((_, text)) = self.rows[index].content() else {
+            return true;
+        };
+        if previous == Some(index) {
+            let text = text.to_string();
+            std::thread::spawn(
+                move || match crate::copy::WlCopy::copy_to_clipboard(&text) {
+                    Ok(()) => crate::toast::show(crate::i18n::tr("toast.copied")),
+                    Err(e) => tracing::warn!("Failed to copy history entry: {}", e),
+                },
+            );
+        } else {
+            self.list.handle_click(x, y);
+        }
+        true
+    }
+
+    pub fn render(
+        &self,
+        y_offset: f32,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        widget_renderer: &WidgetRenderer,
+        text_items: &mut Vec<TextItem>,
+        queue: &wgpu::Queue,
+        window_width: u32,
+        window_height: u32,
+    ) {
+        if let Some(message) = &self.message {
+            text_items.push(TextItem {
+                text: message.clone(),
+                x: self.list.x + 4.0,
+                y: self.list.y + y_offset + 6.0,
+                scale: 1.0,
+                color: [0.5, 0.5, 0.55, 1.0],
+                max_width: self.list.width,
+                centered: false,
+            });
+            return;
+        }
+
+        self.list.render(|row: VisibleRow| {
+            // No clipping in the batch renderers: clamp backgrounds and only
+            // draw text for rows that are fully inside the list
+            let Some((clip_y, clip_height)) = self.list.clip_rect(&row) else {
+                return;
+            };
+            let fully_visible = clip_height >= row.height;
+            match &self.rows[row.index] {
+                HistoryRow::Day(day) => {
+                    if fully_visible {
+                        text_items.push(TextItem {
+                            text: day.clone(),
+                            x: row.x + 4.0,
+                            y: row.y + y_offset + 5.0,
+                            scale: 1.0,
+                            color: [0.010, 0.787, 0.214, 1.0],
+                            max_width: row.width,
+                            centered: false,
+                        });
+                    }
+                }
+                row_kind => {
+                    let Some((time, text)) = row_kind.content() else {
+                        return;
+                    };
+                    let background = if row.selected {
+                        [0.021, 0.021, 0.033, 1.0]
+                    } else {
+                        [0.012, 0.012, 0.016, 1.0]
+                    };
+                    widget_renderer.draw_rounded_rect(
+                        encoder,
+                        view,
+                        queue,
+                        row.x,
+                        clip_y + y_offset + 1.0,
+                        row.width,
+                        (clip_height - 2.0).max(0.0),
+                        6.0,
+                        background,
+                        window_width,
+                        window_height,
+                    );
+                    if fully_visible {
+                        text_items.push(TextItem {
+                            text: time,
+                            x: row.x + 6.0,
+                            y: row.y + y_offset + 7.0,
+                            scale: 1.0,
+                            color: [0.4, 0.4, 0.45, 1.0],
+                            max_width: TIME_COLUMN_WIDTH,
+                            centered: false,
+                        });
+                        let text_width = row.width - TIME_COLUMN_WIDTH - 8.0;
+                        text_items.push(TextItem {
+                            text: fit_line(text, text_width),
+                            x: row.x + TIME_COLUMN_WIDTH,
+                            y: row.y + y_offset + 7.0,
+                            scale: 1.0,
+                            color: [0.8, 0.8, 0.85, 1.0],
+                            max_width: text_width,
+                            centered: false,
+                        });
+                    }
+                }
+            }
+        });
+
+        if let Some((x, y, width, height)) = self.list.thumb_rect() {
+            widget_renderer.draw_rounded_rect(
+                encoder,
+                view,
+                queue,
+                x,
+                y + y_offset,
+                width,
+                height,
+                width / 2.0,
+                [0.3, 0.3, 0.3, 0.5],
+                window_width,
+                window_height,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: &str, text: &str) -> TranscriptHistoryEntry {
+        TranscriptHistoryEntry {
+            timestamp: timestamp.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn groups_entries_by_day_newest_first() {
+        let rows = build_rows(
+            Vec::new(),
+            vec![
+                entry("2026-03-01 09:00:00", "first"),
+                entry("2026-03-02 10:00:00", "second"),
+                entry("2026-03-02 11:00:00", "third"),
+            ],
+        );
+        let labels: Vec<String> = rows
+            .iter()
+            .map(|row| match row {
+                HistoryRow::Day(day) => day.clone(),
+                row => row.content().unwrap().1.to_string(),
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["2026-03-02", "third", "second", "2026-03-01", "first"]
+        );
+    }
+}
@@ -1,13 +1,22 @@
 use std::sync::Arc;
 use wgpu;
 use winit::dpi::PhysicalSize;
-use winit::keyboard::{Key, NamedKey};
+use winit::keyboard::{Key, KeyCode, ModifiersState, NamedKey};
 
 use super::batch_text_renderer::{BatchTextRenderer, TextItem};
-use super::widgets::{Select, SelectOption, Slider, Toggle, WidgetRenderer};
+use super::integration_view::IntegrationView;
+use super::session_history::SessionHistoryView;
+use super::theme::Theme;
+use super::widgets::{
+    ConfirmDialog, Select, SelectOption, ShortcutCapture, Slider, Toggle, WidgetDrawStats,
+    WidgetRenderer,
+};
 use crate::config::{
-    AppConfig, ShortcutMode, SpectrogramSkin, VadSensitivity, VisualThemePreset, WindowPosition,
+    AppConfig, GpuBackend, PortalConfig, ShortcutMode, SpectrogramScale, SpectrogramSkin, UiConfig,
+    VadSensitivity, VisualThemePreset, WindowPosition,
 };
+use crate::hotkeys::{Accelerator, ShortcutAction};
+use crate::i18n::{tr, tr_args};
 use speechcore::BackendType;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,16 +26,22 @@ pub enum SettingsTab {
     Behavior,
     Display,
     Appearance,
+    History,
+    Integration,
 }
 
 impl SettingsTab {
+    const COUNT: usize = 7;
+
     pub fn label(&self) -> &'static str {
         match self {
-            SettingsTab::Backend => "Backend",
-            SettingsTab::Audio => "Audio",
-            SettingsTab::Behavior => "Behavior",
-            SettingsTab::Display => "Display",
-            SettingsTab::Appearance => "Appearance",
+            SettingsTab::Backend => tr("settings.tab.backend"),
+            SettingsTab::Audio => tr("settings.tab.audio"),
+            SettingsTab::Behavior => tr("settings.tab.behavior"),
+            SettingsTab::Display => tr("settings.tab.display"),
+            SettingsTab::Appearance => tr("settings.tab.appearance"),
+            SettingsTab::History => tr("settings.tab.history"),
+            SettingsTab::Integration => tr("settings.tab.integration"),
         }
     }
 
@@ -37,8 +52,14 @@ impl SettingsTab {
             SettingsTab::Behavior,
             SettingsTab::Display,
             SettingsTab::Appearance,
+            SettingsTab::History,
+            SettingsTab::Integration,
         ]
     }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,13 +67,51 @@ enum DropdownId {
     Backend,
     Model,
     Language,
+    GpuBackend,
     VadSensitivity,
+    InputDevice,
     ShortcutMode,
     PasteShortcut,
     Vsync,
+    SpectrogramScale,
     VisualTheme,
     SpectrogramSkin,
     WindowPosition,
+    Profile,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToggleId {
+    EnglishOnly,
+    Gpu,
+    Sound,
+    AutoPaste,
+    ClearOnSession,
+    PostProcessing,
+    Typewriter,
+    Enhancement,
+    SystemTray,
+    RecordingIndicator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SliderId {
+    Threads,
+    Volume,
+    TargetFps,
+    FontSize,
+}
+
+/// A row of the active tab that keyboard focus can land on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FocusRow {
+    Select(DropdownId),
+    Toggle(ToggleId),
+    Slider(SliderId),
+    /// The toggle shortcut recorder
+    Shortcut,
+    Reset,
+    Apply,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -64,8 +123,11 @@ struct SettingsTooltip {
 pub struct SettingsPanel {
     pub is_open: bool,
     pub close_requested: bool,
+    help_requested: bool,
     active_tab: SettingsTab,
     batch_text_renderer: BatchTextRenderer,
+    /// Rendered width of each tab label, in `SettingsTab::all()` order
+    tab_label_widths: Vec<f32>,
     overlay_text_renderer: Option<BatchTextRenderer>,
     widget_renderer: WidgetRenderer,
     device: Arc<wgpu::Device>,
@@ -86,12 +148,14 @@ pub struct SettingsPanel {
     language_select: Select,
     show_language_select: bool,
     gpu_toggle: Toggle,
+    gpu_backend_select: Select,
     threads_slider: Slider,
 
     // Audio tab widgets
     vad_sensitivity_select: Select,
     sound_toggle: Toggle,
     volume_slider: Slider,
+    input_device_select: Select,
 
     // Behavior tab widgets
     auto_paste_toggle: Toggle,
@@ -101,13 +165,17 @@ pub struct SettingsPanel {
 
     // Behavior tab additions
     shortcut_mode_select: Select,
+    toggle_shortcut_capture: ShortcutCapture,
     paste_shortcut_select: Select,
     enhancement_toggle: Toggle,
+    /// Switches profiles as soon as an option is chosen; never part of Apply
+    profile_select: Select,
 
     // Display tab widgets
     vsync_select: Select,
     target_fps_slider: Slider,
     system_tray_toggle: Toggle,
+    spectrogram_scale_select: Select,
 
     // Appearance tab widgets
     visual_theme_select: Select,
@@ -116,11 +184,29 @@ pub struct SettingsPanel {
     font_size_slider: Slider,
     recording_indicator_toggle: Toggle,
 
+    // History tab
+    history_view: SessionHistoryView,
+
+    // Integration tab
+    integration_view: IntegrationView,
+
     // Apply button state
     apply_requested: bool,
     has_pending_changes: bool,
+    confirm_dialog: ConfirmDialog,
+
+    /// Theme accent for the active tab, the Apply button and the focus ring
+    accent: [f32; 4],
 
     open_dropdown: Option<DropdownId>,
+    /// Keyboard focus per tab (indexed by tab order); `None` until an arrow key is used
+    focused_rows: [Option<usize>; SettingsTab::COUNT],
+    /// Rows of the active tab scrolled out above the content area
+    scroll_rows: usize,
+    /// Wheel lines not yet turned into whole steps (touchpads send fractions)
+    wheel_lines: f32,
+    /// Last pointer position, for routing the wheel to the widget under it
+    pointer: (f32, f32),
     hovered_tooltip: Option<SettingsTooltip>,
     tooltip_anchor_x: f32,
     hover_start: std::time::Instant,
@@ -134,12 +220,62 @@ const WIDGET_X: f32 = 14.0;
 const ROW_HEIGHT: f32 = 26.0;
 const SPACING: f32 = 6.0;
 const APPLY_BUTTON_HEIGHT: f32 = 28.0;
+const ACTION_BUTTON_WIDTH: f32 = 80.0;
+const ACTION_BUTTON_GAP: f32 = 8.0;
 const TOOLTIP_DELAY_MS: u128 = 150;
+const TAB_PADDING: f32 = 8.0;
+
+/// Tab (x, width) pairs sized to their measured label widths so every label fits
+fn tab_layout(usable_width: f32, label_widths: &[f32]) -> Vec<(f32, f32)> {
+    let natural = |label_width: &f32| label_width + TAB_PADDING;
+    let total: f32 = label_widths.iter().map(natural).sum();
+    let mut x = 0.0;
+    label_widths
+        .iter()
+        .map(|label_width| {
+            let width = usable_width * natural(label_width) / total;
+            let tab_x = x;
+            x += width;
+            (tab_x, width)
+        })
+        .collect()
+}
+
+/// Area below the tab bar used by the history list
+fn history_bounds(window_width: u32, window_height: u32) -> (f32, f32, f32, f32) {
+    (
+        WIDGET_X,
+        CONTENT_Y,
+        default_width(window_width),
+        (window_height as f32 - CONTENT_Y - 8.0).max(0.0),
+    )
+}
+
+/// x of the Reset and Apply buttons, centered under the rows
+fn action_buttons_x(window_width: u32) -> (f32, f32) {
+    let total_width = ACTION_BUTTON_WIDTH * 2.0 + ACTION_BUTTON_GAP;
+    let start_x = WIDGET_X + (default_width(window_width) - total_width) / 2.0;
+    (start_x, start_x + ACTION_BUTTON_WIDTH + ACTION_BUTTON_GAP)
+}
 
 fn default_width(window_width: u32) -> f32 {
     window_width as f32 - 28.0
 }
 
+/// Shortcuts bound to other actions, which the toggle shortcut must not reuse
+fn reserved_accelerators(portal_config: &PortalConfig) -> Vec<(String, Accelerator)> {
+    let actions = ShortcutAction::ALL
+        .into_iter()
+        .map(|action| (action.description(), action.accelerator(portal_config)));
+    std::iter::once((
+        "Privacy Wipe",
+        portal_config.privacy_wipe_accelerator.as_str(),
+    ))
+    .chain(actions)
+    .filter_map(|(name, accelerator)| Some((name.to_string(), Accelerator::parse(accelerator)?)))
+    .collect()
+}
+
 fn backend_has_english_toggle(backend: BackendType) -> bool {
     matches!(backend, BackendType::WhisperCpp)
 }
@@ -153,6 +289,83 @@ fn backend_has_language_select(backend: BackendType, english_only: bool) -> bool
     }
 }
 
+fn gpu_backend_label(backend: GpuBackend) -> &'static str {
+    match backend {
+        GpuBackend::Auto => "Auto",
+        GpuBackend::Vulkan => "Vulkan",
+        GpuBackend::Cuda => "CUDA",
+        GpuBackend::None => "None",
+    }
+}
+
+/// "None" followed by the profile names; `active` is added when it is not listed
+fn profile_options(names: &[String], active: Option<&str>) -> Vec<SelectOption> {
+    let mut options = vec![SelectOption {
+        label: tr("settings.profile_none").to_string(),
+        value: String::new(),
+    }];
+    let unlisted = active.filter(|active| !names.iter().any(|name| name == active));
+    for name in names.iter().map(String::as_str).chain(unlisted) {
+        options.push(SelectOption {
+            label: name.to_string(),
+            value: name.to_string(),
+        });
+    }
+    options
+}
+
+/// GPU backend row label with the device the configured backend resolved to
+fn gpu_backend_row_label(config: &AppConfig) -> String {
+    let resolution = crate::gpu_probe::resolve(config, config.backend_config.backend);
+    tr_args(
+        "settings.gpu_backend_resolved",
+        &[("device", &resolution.device.api())],
+    )
+}
+
+/// "System default" plus the PipeWire sources found at startup; a configured
+/// target that matches none of them is kept as its own option so it is not
+/// lost on apply
+fn input_device_options(configured: Option<&str>) -> (Vec<SelectOption>, usize) {
+    let sources = crate::pipewire_capture::startup_sources();
+    let mut options = vec![SelectOption {
+        label: "System default".into(),
+        value: String::new(),
+    }];
+    options.extend(sources.iter().map(|source| {
+        SelectOption {
+            label: source
+                .description
+                .clone()
+                .unwrap_or_else(|| source.name.clone()),
+            value: source.name.clone(),
+        }
+    }));
+    let Some(configured) = configured.filter(|name| !name.trim().is_empty()) else {
+        return (options, 0);
+    };
+    match crate::pipewire_capture::find_node(sources, configured) {
+        Some(source) => {
+            let index = options
+                .iter()
+                .position(|option| option.value == source.name)
+                .unwrap_or(0);
+            (options, index)
+        }
+        None => {
+            options.push(SelectOption {
+                label: format!("{} (not found)", configured),
+                value: configured.to_string(),
+            });
+            let index = options.len() - 1;
+            (options, index)
+        }
+    }
+}
+
+/// Model Select value standing for `general_config.model_path`
+const CUSTOM_MODEL: &str = "custom";
+
 fn models_for_backend(backend: BackendType, english_only: bool) -> Vec<SelectOption> {
     let names: &[&str] = match backend {
         BackendType::WhisperCpp => {
@@ -176,10 +389,15 @@ fn models_for_backend(backend: BackendType, english_only: bool) -> Vec<SelectOpt
         BackendType::Parakeet => &["parakeet-tdt-0.6b-v3", "parakeet-tdt-0.6b-v2"],
         BackendType::Nemotron => &["nemotron-3.5-asr-streaming-0.6b"],
     };
+    // Sizes shown at the default quantization; the download dialog uses the configured one
+    let sizing = speechcore::BackendConfig {
+        backend,
+        ..speechcore::BackendConfig::default()
+    };
     names
         .iter()
         .map(|n| SelectOption {
-            label: n.to_string(),
+            label: crate::model_catalog::model_label(&sizing, n),
             value: n.to_string(),
         })
         .collect()
@@ -284,8 +502,12 @@ impl SettingsPanel {
     ) -> Self {
         let device = Arc::new(device.clone());
         let queue = Arc::new(queue.clone());
-        let batch_text_renderer =
+        let mut batch_text_renderer =
             BatchTextRenderer::new(device.clone(), queue.clone(), size, config.format);
+        let tab_label_widths = SettingsTab::all()
+            .iter()
+            .map(|tab| batch_text_renderer.measure_text(tab.label(), 1.0))
+            .collect();
 
         let widget_renderer = WidgetRenderer::new(&device, config.format);
 
@@ -293,7 +515,7 @@ impl SettingsPanel {
 
         // Backend tab widgets
         let backend_select = Select::new(
-            "Backend",
+            tr("settings.backend"),
             vec![
                 SelectOption {
                     label: "CTranslate2".into(),
@@ -323,7 +545,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let english_only_toggle = Toggle::new(
-            "English only",
+            tr("settings.english_only"),
             true,
             WIDGET_X,
             CONTENT_Y + ROW_HEIGHT + SPACING,
@@ -331,7 +553,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let model_select = Select::new(
-            "Model",
+            tr("settings.model"),
             models_for_backend(BackendType::WhisperCpp, true),
             0,
             WIDGET_X,
@@ -340,7 +562,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let language_select = Select::new(
-            "Language",
+            tr("settings.language"),
             languages_for_backend(BackendType::WhisperCpp),
             0,
             WIDGET_X,
@@ -349,28 +571,43 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let gpu_toggle = Toggle::new(
-            "GPU acceleration",
+            tr("settings.gpu"),
             false,
             WIDGET_X,
             CONTENT_Y + 3.0 * (ROW_HEIGHT + SPACING),
             w,
             ROW_HEIGHT,
         );
+        let gpu_backend_select = Select::new(
+            tr("settings.gpu_backend"),
+            GpuBackend::ALL
+                .iter()
+                .map(|backend| SelectOption {
+                    label: gpu_backend_label(*backend).to_string(),
+                    value: backend.as_str().to_string(),
+                })
+                .collect(),
+            0,
+            WIDGET_X,
+            CONTENT_Y + 4.0 * (ROW_HEIGHT + SPACING),
+            w,
+            ROW_HEIGHT,
+        );
         let threads_slider = Slider::new(
-            "Threads",
+            tr("settings.threads"),
             4.0,
             1.0,
             8.0,
             1.0,
             WIDGET_X,
-            CONTENT_Y + 4.0 * (ROW_HEIGHT + SPACING),
+            CONTENT_Y + 5.0 * (ROW_HEIGHT + SPACING),
             w,
             ROW_HEIGHT,
         );
 
         // Audio tab widgets
         let vad_sensitivity_select = Select::new(
-            "VAD sensitivity",
+            tr("settings.vad_sensitivity"),
             vec![
                 SelectOption {
                     label: "Low".into(),
@@ -392,7 +629,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let sound_toggle = Toggle::new(
-            "Sound feedback",
+            tr("settings.sound_feedback"),
             true,
             WIDGET_X,
             CONTENT_Y + ROW_HEIGHT + SPACING,
@@ -400,7 +637,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let volume_slider = Slider::new(
-            "Volume",
+            tr("settings.volume"),
             0.5,
             0.0,
             1.0,
@@ -410,11 +647,28 @@ impl SettingsPanel {
             w,
             ROW_HEIGHT,
         );
+        let (input_device_options, input_device_index) = input_device_options(None);
+        let input_device_select = Select::new(
+            tr("settings.input_device"),
+            input_device_options,
+            input_device_index,
+            WIDGET_X,
+            CONTENT_Y + 3.0 * (ROW_HEIGHT + SPACING),
+            w,
+            ROW_HEIGHT,
+        );
 
         // Behavior tab widgets
-        let auto_paste_toggle = Toggle::new("Auto-paste", true, WIDGET_X, CONTENT_Y, w, ROW_HEIGHT);
+        let auto_paste_toggle = Toggle::new(
+            tr("settings.auto_paste"),
+            true,
+            WIDGET_X,
+            CONTENT_Y,
+            w,
+            ROW_HEIGHT,
+        );
         let clear_on_session_toggle = Toggle::new(
-            "Clear on new session",
+            tr("settings.clear_on_session"),
             true,
             WIDGET_X,
             CONTENT_Y + ROW_HEIGHT + SPACING,
@@ -422,7 +676,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let post_processing_toggle = Toggle::new(
-            "Post-processing",
+            tr("settings.post_processing"),
             true,
             WIDGET_X,
             CONTENT_Y + 2.0 * (ROW_HEIGHT + SPACING),
@@ -430,7 +684,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let typewriter_toggle = Toggle::new(
-            "Typewriter effect",
+            tr("settings.typewriter"),
             false,
             WIDGET_X,
             CONTENT_Y + 3.0 * (ROW_HEIGHT + SPACING),
@@ -438,7 +692,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let shortcut_mode_select = Select::new(
-            "Shortcut mode",
+            tr("settings.shortcut_mode"),
             vec![
                 SelectOption {
                     label: "Toggle".into(),
@@ -455,8 +709,16 @@ impl SettingsPanel {
             w,
             ROW_HEIGHT,
         );
+        let toggle_shortcut_capture = ShortcutCapture::new(
+            tr("settings.toggle_shortcut"),
+            &PortalConfig::default().manual_toggle_accelerator,
+            WIDGET_X,
+            CONTENT_Y + 5.0 * (ROW_HEIGHT + SPACING),
+            w,
+            ROW_HEIGHT,
+        );
         let paste_shortcut_select = Select::new(
-            "Paste shortcut",
+            tr("settings.paste_shortcut"),
             vec![
                 SelectOption {
                     label: "Ctrl+Shift+V".into(),
@@ -469,22 +731,31 @@ impl SettingsPanel {
             ],
             0,
             WIDGET_X,
-            CONTENT_Y + 5.0 * (ROW_HEIGHT + SPACING),
+            CONTENT_Y + 6.0 * (ROW_HEIGHT + SPACING),
             w,
             ROW_HEIGHT,
         );
         let enhancement_toggle = Toggle::new(
-            "Magic mode",
+            tr("settings.magic_mode"),
             false,
             WIDGET_X,
-            CONTENT_Y + 6.0 * (ROW_HEIGHT + SPACING),
+            CONTENT_Y + 7.0 * (ROW_HEIGHT + SPACING),
+            w,
+            ROW_HEIGHT,
+        );
+        let profile_select = Select::new(
+            tr("settings.profile"),
+            profile_options(&[], None),
+            0,
+            WIDGET_X,
+            CONTENT_Y + 8.0 * (ROW_HEIGHT + SPACING),
             w,
             ROW_HEIGHT,
         );
 
         // Display tab widgets
         let vsync_select = Select::new(
-            "VSync mode",
+            tr("settings.vsync"),
             vec![
                 SelectOption {
                     label: "Enabled".into(),
@@ -514,7 +785,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let target_fps_slider = Slider::new(
-            "Target FPS",
+            tr("settings.target_fps"),
             60.0,
             15.0,
             240.0,
@@ -525,17 +796,39 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let system_tray_toggle = Toggle::new(
-            "System tray",
+            tr("settings.system_tray"),
             true,
             WIDGET_X,
             CONTENT_Y + 2.0 * (ROW_HEIGHT + SPACING),
             w,
             ROW_HEIGHT,
         );
+        let spectrogram_scale_select = Select::new(
+            tr("settings.spectrogram_scale"),
+            vec![
+                SelectOption {
+                    label: "Linear".into(),
+                    value: "linear".into(),
+                },
+                SelectOption {
+                    label: "Mel".into(),
+                    value: "mel".into(),
+                },
+                SelectOption {
+                    label: "Log".into(),
+                    value: "log".into(),
+                },
+            ],
+            0,
+            WIDGET_X,
+            CONTENT_Y + 3.0 * (ROW_HEIGHT + SPACING),
+            w,
+            ROW_HEIGHT,
+        );
 
         // Appearance tab widgets
         let visual_theme_select = Select::new(
-            "Visual theme",
+            tr("settings.visual_theme"),
             vec![
                 SelectOption {
                     label: "Focus".into(),
@@ -557,7 +850,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let spectrogram_skin_select = Select::new(
-            "Spectrogram skin",
+            tr("settings.spectrogram_skin"),
             vec![
                 SelectOption {
                     label: "Bars".into(),
@@ -579,7 +872,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let window_position_select = Select::new(
-            "Window position",
+            tr("settings.window_position"),
             vec![
                 SelectOption {
                     label: "Bottom Left".into(),
@@ -629,7 +922,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let font_size_slider = Slider::new(
-            "Font size",
+            tr("settings.font_size"),
             10.0,
             6.0,
             24.0,
@@ -640,7 +933,7 @@ impl SettingsPanel {
             ROW_HEIGHT,
         );
         let recording_indicator_toggle = Toggle::new(
-            "Recording indicator",
+            tr("settings.recording_indicator"),
             true,
             WIDGET_X,
             CONTENT_Y + 4.0 * (ROW_HEIGHT + SPACING),
@@ -651,8 +944,10 @@ impl SettingsPanel {
         Self {
             is_open: false,
             close_requested: false,
+            help_requested: false,
             active_tab: SettingsTab::Backend,
             batch_text_renderer,
+            tab_label_widths,
             overlay_text_renderer: None,
             widget_renderer,
             device,
@@ -671,23 +966,28 @@ impl SettingsPanel {
             language_select,
             show_language_select: false,
             gpu_toggle,
+            gpu_backend_select,
             threads_slider,
 
             vad_sensitivity_select,
             sound_toggle,
             volume_slider,
+            input_device_select,
 
             auto_paste_toggle,
             clear_on_session_toggle,
             post_processing_toggle,
             typewriter_toggle,
             shortcut_mode_select,
+            toggle_shortcut_capture,
             paste_shortcut_select,
             enhancement_toggle,
+            profile_select,
 
             vsync_select,
             target_fps_slider,
             system_tray_toggle,
+            spectrogram_scale_select,
 
             visual_theme_select,
             spectrogram_skin_select,
@@ -695,10 +995,26 @@ impl SettingsPanel {
             font_size_slider,
             recording_indicator_toggle,
 
+            history_view: {
+                let (x, y, width, height) = history_bounds(size.width, size.height);
+                SessionHistoryView::new(x, y, width, height)
+            },
+            integration_view: {
+                let (x, y, width, _) = history_bounds(size.width, size.height);
+                IntegrationView::new(x, y, width)
+            },
+
             apply_requested: false,
             has_pending_changes: false,
+            confirm_dialog: ConfirmDialog::new(),
+
+            accent: Theme::dark(&UiConfig::default()).accent,
 
             open_dropdown: None,
+            focused_rows: [None; SettingsTab::COUNT],
+            scroll_rows: 0,
+            wheel_lines: 0.0,
+            pointer: (0.0, 0.0),
             hovered_tooltip: None,
             tooltip_anchor_x: 0.0,
             hover_start: std::time::Instant::now(),
@@ -716,6 +1032,9 @@ impl SettingsPanel {
         self.window_width = size.width;
         self.window_height = size.height;
         self.recalculate_positions(size.width);
+        let (x, y, width, height) = history_bounds(size.width, size.height);
+        self.history_view.set_bounds(x, y, width, height);
+        self.integration_view.set_bounds(x, y, width);
     }
 
     pub fn toggle(&mut self) {
@@ -737,6 +1056,11 @@ impl SettingsPanel {
         }
     }
 
+    /// Widget draw statistics since the last call (for debug stats)
+    pub fn take_widget_draw_stats(&self) -> WidgetDrawStats {
+        self.widget_renderer.take_draw_stats()
+    }
+
     pub fn needs_redraw(&self) -> bool {
         self.animation_active
             || self.english_only_toggle.is_animating()
@@ -754,6 +1078,25 @@ impl SettingsPanel {
                 .is_some_and(|_| self.hover_start.elapsed().as_millis() < TOOLTIP_DELAY_MS)
     }
 
+    /// Whether key presses should go to the toggle shortcut recorder
+    pub fn is_capturing_shortcut(&self) -> bool {
+        self.toggle_shortcut_capture.is_capturing()
+    }
+
+    /// Record a key press as the new toggle shortcut
+    pub fn handle_shortcut_key(&mut self, code: KeyCode, modifiers: ModifiersState) -> bool {
+        let handled = self.toggle_shortcut_capture.handle_key(code, modifiers);
+        self.mark_pending_if_widget_changed();
+        handled
+    }
+
+    /// Whether the open Integration tab shows an outdated portal status
+    pub fn integration_stale(&self) -> bool {
+        self.is_open
+            && self.active_tab == SettingsTab::Integration
+            && self.integration_view.is_stale()
+    }
+
     fn content_y_offset(&self) -> f32 {
         (1.0 - self.animation_progress.clamp(0.0, 1.0)) * 80.0
     }
@@ -767,13 +1110,17 @@ impl SettingsPanel {
             DropdownId::Backend => &self.backend_select,
             DropdownId::Model => &self.model_select,
             DropdownId::Language => &self.language_select,
+            DropdownId::GpuBackend => &self.gpu_backend_select,
             DropdownId::VadSensitivity => &self.vad_sensitivity_select,
+            DropdownId::InputDevice => &self.input_device_select,
             DropdownId::ShortcutMode => &self.shortcut_mode_select,
             DropdownId::PasteShortcut => &self.paste_shortcut_select,
             DropdownId::Vsync => &self.vsync_select,
+            DropdownId::SpectrogramScale => &self.spectrogram_scale_select,
             DropdownId::VisualTheme => &self.visual_theme_select,
             DropdownId::SpectrogramSkin => &self.spectrogram_skin_select,
             DropdownId::WindowPosition => &self.window_position_select,
+            DropdownId::Profile => &self.profile_select,
         }
     }
 
@@ -782,14 +1129,170 @@ impl SettingsPanel {
             DropdownId::Backend => &mut self.backend_select,
             DropdownId::Model => &mut self.model_select,
             DropdownId::Language => &mut self.language_select,
+            DropdownId::GpuBackend => &mut self.gpu_backend_select,
             DropdownId::VadSensitivity => &mut self.vad_sensitivity_select,
+            DropdownId::InputDevice => &mut self.input_device_select,
             DropdownId::ShortcutMode => &mut self.shortcut_mode_select,
             DropdownId::PasteShortcut => &mut self.paste_shortcut_select,
             DropdownId::Vsync => &mut self.vsync_select,
+            DropdownId::SpectrogramScale => &mut self.spectrogram_scale_select,
             DropdownId::VisualTheme => &mut self.visual_theme_select,
             DropdownId::SpectrogramSkin => &mut self.spectrogram_skin_select,
             DropdownId::WindowPosition => &mut self.window_position_select,
+            DropdownId::Profile => &mut self.profile_select,
+        }
+    }
+
+    fn toggle_ref(&self, id: ToggleId) -> &Toggle {
+        match id {
+            ToggleId::EnglishOnly => &self.english_only_toggle,
+            ToggleId::Gpu => &self.gpu_toggle,
+            ToggleId::Sound => &self.sound_toggle,
+            ToggleId::AutoPaste => &self.auto_paste_toggle,
+            ToggleId::ClearOnSession => &self.clear_on_session_toggle,
+            ToggleId::PostProcessing => &self.post_processing_toggle,
+            ToggleId::Typewriter => &self.typewriter_toggle,
+            ToggleId::Enhancement => &self.enhancement_toggle,
+            ToggleId::SystemTray => &self.system_tray_toggle,
+            ToggleId::RecordingIndicator => &self.recording_indicator_toggle,
+        }
+    }
+
+    fn toggle_mut(&mut self, id: ToggleId) -> &mut Toggle {
+        match id {
+            ToggleId::EnglishOnly => &mut self.english_only_toggle,
+            ToggleId::Gpu => &mut self.gpu_toggle,
+            ToggleId::Sound => &mut self.sound_toggle,
+            ToggleId::AutoPaste => &mut self.auto_paste_toggle,
+            ToggleId::ClearOnSession => &mut self.clear_on_session_toggle,
+            ToggleId::PostProcessing => &mut self.post_processing_toggle,
+            ToggleId::Typewriter => &mut self.typewriter_toggle,
+            ToggleId::Enhancement => &mut self.enhancement_toggle,
+            ToggleId::SystemTray => &mut self.system_tray_toggle,
+            ToggleId::RecordingIndicator => &mut self.recording_indicator_toggle,
+        }
+    }
+
+    fn slider_ref(&self, id: SliderId) -> &Slider {
+        match id {
+            SliderId::Threads => &self.threads_slider,
+            SliderId::Volume => &self.volume_slider,
+            SliderId::TargetFps => &self.target_fps_slider,
+            SliderId::FontSize => &self.font_size_slider,
+        }
+    }
+
+    fn slider_mut(&mut self, id: SliderId) -> &mut Slider {
+        match id {
+            SliderId::Threads => &mut self.threads_slider,
+            SliderId::Volume => &mut self.volume_slider,
+            SliderId::TargetFps => &mut self.target_fps_slider,
+            SliderId::FontSize => &mut self.font_size_slider,
+        }
+    }
+
+    /// Focusable rows of `tab` from top to bottom, ending with Reset and Apply
+    fn focus_rows(&self, tab: SettingsTab) -> Vec<FocusRow> {
+        let mut rows = match tab {
+            SettingsTab::Backend => {
+                let mut rows = vec![FocusRow::Select(DropdownId::Backend)];
+                if self.show_english_toggle {
+                    rows.push(FocusRow::Toggle(ToggleId::EnglishOnly));
+                }
+                rows.push(FocusRow::Select(DropdownId::Model));
+                if self.show_language_select {
+                    rows.push(FocusRow::Select(DropdownId::Language));
+                }
+                rows.push(FocusRow::Toggle(ToggleId::Gpu));
+                rows.push(FocusRow::Select(DropdownId::GpuBackend));
+                rows.push(FocusRow::Slider(SliderId::Threads));
+                rows
+            }
+            SettingsTab::Audio => vec![
+                FocusRow::Select(DropdownId::VadSensitivity),
+                FocusRow::Toggle(ToggleId::Sound),
+                FocusRow::Slider(SliderId::Volume),
+                FocusRow::Select(DropdownId::InputDevice),
+            ],
+            SettingsTab::Behavior => vec![
+                FocusRow::Toggle(ToggleId::AutoPaste),
+                FocusRow::Toggle(ToggleId::ClearOnSession),
+                FocusRow::Toggle(ToggleId::PostProcessing),
+                FocusRow::Toggle(ToggleId::Typewriter),
+                FocusRow::Select(DropdownId::ShortcutMode),
+                FocusRow::Shortcut,
+                FocusRow::Select(DropdownId::PasteShortcut),
+                FocusRow::Toggle(ToggleId::Enhancement),
+                FocusRow::Select(DropdownId::Profile),
+            ],
+            SettingsTab::Display => vec![
+                FocusRow::Select(DropdownId::Vsync),
+                FocusRow::Slider(SliderId::TargetFps),
+                FocusRow::Toggle(ToggleId::SystemTray),
+                FocusRow::Select(DropdownId::SpectrogramScale),
+            ],
+            SettingsTab::Appearance => vec![
+                FocusRow::Select(DropdownId::VisualTheme),
+                FocusRow::Select(DropdownId::SpectrogramSkin),
+                FocusRow::Select(DropdownId::WindowPosition),
+                FocusRow::Slider(SliderId::FontSize),
+                FocusRow::Toggle(ToggleId::RecordingIndicator),
+            ],
+            SettingsTab::History | SettingsTab::Integration => return Vec::new(),
+        };
+        rows.push(FocusRow::Reset);
+        rows.push(FocusRow::Apply);
+        rows
+    }
+
+    /// The focused row of the active tab, clamped to rows that still exist
+    fn focused_row(&self) -> Option<FocusRow> {
+        let rows = self.focus_rows(self.active_tab);
+        let index = self.focused_rows[self.active_tab.index()]?;
+        rows.get(index.min(rows.len().checked_sub(1)?)).copied()
+    }
+
+    fn move_focus(&mut self, delta: i32) -> bool {
+        let count = self.focus_rows(self.active_tab).len();
+        if count == 0 {
+            return false;
         }
+        let focused = &mut self.focused_rows[self.active_tab.index()];
+        let next = match *focused {
+            Some(index) => (index.min(count - 1) as i64 + delta as i64).rem_euclid(count as i64),
+            None if delta > 0 => 0,
+            None => count as i64 - 1,
+        };
+        *focused = Some(next as usize);
+        self.clear_tooltip();
+        self.scroll_focus_into_view();
+        true
+    }
+
+    /// Screen-independent rect (x, y, width, height) of a focusable row
+    fn focus_rect(&self, row: FocusRow, window_width: u32) -> (f32, f32, f32, f32) {
+        let w = default_width(window_width);
+        let y = match row {
+            FocusRow::Select(id) => self.select_ref(id).y,
+            FocusRow::Toggle(id) => self.toggle_ref(id).y,
+            FocusRow::Slider(id) => self.slider_ref(id).y,
+            FocusRow::Shortcut => self.toggle_shortcut_capture.y,
+            FocusRow::Reset | FocusRow::Apply => {
+                let (reset_x, apply_x) = action_buttons_x(window_width);
+                let x = if row == FocusRow::Reset {
+                    reset_x
+                } else {
+                    apply_x
+                };
+                return (
+                    x,
+                    self.buttons_y_for_tab(self.active_tab),
+                    ACTION_BUTTON_WIDTH,
+                    APPLY_BUTTON_HEIGHT,
+                );
+            }
+        };
+        (WIDGET_X, y, w, ROW_HEIGHT)
     }
 
     fn active_select_box_at(&self, x: f32, y: f32) -> Option<DropdownId> {
@@ -801,27 +1304,41 @@ impl SettingsPanel {
                     Some(DropdownId::Model)
                 } else if self.show_language_select && self.language_select.hit_select_box(x, y) {
                     Some(DropdownId::Language)
+                } else if self.gpu_backend_select.hit_select_box(x, y) {
+                    Some(DropdownId::GpuBackend)
+                } else {
+                    None
+                }
+            }
+            SettingsTab::Audio => {
+                if self.vad_sensitivity_select.hit_select_box(x, y) {
+                    Some(DropdownId::VadSensitivity)
+                } else if self.input_device_select.hit_select_box(x, y) {
+                    Some(DropdownId::InputDevice)
                 } else {
                     None
                 }
             }
-            SettingsTab::Audio => self
-                .vad_sensitivity_select
-                .hit_select_box(x, y)
-                .then_some(DropdownId::VadSensitivity),
             SettingsTab::Behavior => {
                 if self.shortcut_mode_select.hit_select_box(x, y) {
                     Some(DropdownId::ShortcutMode)
                 } else if self.paste_shortcut_select.hit_select_box(x, y) {
                     Some(DropdownId::PasteShortcut)
+                } else if self.profile_select.hit_select_box(x, y) {
+                    Some(DropdownId::Profile)
+                } else {
+                    None
+                }
+            }
+            SettingsTab::Display => {
+                if self.vsync_select.hit_select_box(x, y) {
+                    Some(DropdownId::Vsync)
+                } else if self.spectrogram_scale_select.hit_select_box(x, y) {
+                    Some(DropdownId::SpectrogramScale)
                 } else {
                     None
                 }
             }
-            SettingsTab::Display => self
-                .vsync_select
-                .hit_select_box(x, y)
-                .then_some(DropdownId::Vsync),
             SettingsTab::Appearance => {
                 if self.visual_theme_select.hit_select_box(x, y) {
                     Some(DropdownId::VisualTheme)
@@ -833,16 +1350,105 @@ impl SettingsPanel {
                     None
                 }
             }
+            SettingsTab::History | SettingsTab::Integration => None,
         }
     }
 
     fn open_only_dropdown(&mut self, id: DropdownId) {
         self.close_all_dropdowns();
         self.clear_tooltip();
-        self.select_mut(id).set_expanded(true);
+        let scroll = self.content_scroll();
+        let bottom = self.window_height as f32 - 4.0;
+        let select = self.select_mut(id);
+        select.set_available_space(CONTENT_Y + scroll, bottom + scroll);
+        select.set_expanded(true);
         self.open_dropdown = Some(id);
     }
 
+    /// Commit `index` in the open dropdown, refreshing backend-dependent options if needed
+    fn choose_open_dropdown_option(&mut self, id: DropdownId, index: usize, window_width: u32) {
+        let previous_index = self.select_ref(id).selected_index;
+        self.select_mut(id).choose_index(index);
+        if id == DropdownId::Backend && self.backend_select.selected_index != previous_index {
+            self.refresh_backend_dependent_options(window_width);
+        }
+        self.close_all_dropdowns();
+        self.mark_pending_if_widget_changed();
+    }
+
+    /// Handle `lines` wheel lines (positive scrolls down): the open dropdown
+    /// scrolls, a slider under the pointer steps, a select under it cycles its
+    /// options and anywhere else the tab content scrolls.
+    /// Returns true if the wheel was consumed.
+    pub fn handle_scroll(&mut self, lines: f32) -> bool {
+        if !self.is_open || self.confirm_dialog.is_visible() {
+            return false;
+        }
+        if let Some(id) = self.open_dropdown {
+            let rows = if lines > 0.0 {
+                lines.ceil() as i32
+            } else {
+                lines.floor() as i32
+            };
+            self.select_mut(id).scroll_by(rows);
+            return true;
+        }
+        if self.active_tab == SettingsTab::History {
+            self.history_view.handle_scroll(lines * ROW_HEIGHT);
+            return true;
+        }
+
+        // Widgets change by whole steps, however finely the wheel reports
+        self.wheel_lines += lines;
+        let steps = self.wheel_lines.trunc();
+        self.wheel_lines -= steps;
+        let steps = steps as i32;
+        if steps == 0 {
+            return true;
+        }
+
+        let (x, y) = self.pointer;
+        let y = self.local_y(y) + self.content_scroll();
+        let hit = |wx: f32, wy: f32, width: f32| {
+            x >= wx && x <= wx + width && y >= wy && y < wy + ROW_HEIGHT
+        };
+        let row = self
+            .focus_rows(self.active_tab)
+            .into_iter()
+            .find(|row| match *row {
+                FocusRow::Select(id) => {
+                    let select = self.select_ref(id);
+                    hit(select.x, select.y, select.width)
+                }
+                FocusRow::Slider(id) => {
+                    let slider = self.slider_ref(id);
+                    hit(slider.x, slider.y, slider.width)
+                }
+                _ => false,
+            })
+            .filter(|_| y >= CONTENT_Y + self.content_scroll());
+
+        match row {
+            // Wheel up raises the value, like on most sliders
+            Some(FocusRow::Slider(id)) => {
+                self.slider_mut(id).step_by(-steps);
+            }
+            Some(FocusRow::Select(id)) => {
+                if self.select_mut(id).cycle(steps) && id == DropdownId::Backend {
+                    let window_width = self.window_width;
+                    self.refresh_backend_dependent_options(window_width);
+                }
+            }
+            _ => {
+                self.scroll_content_by(steps);
+                self.clear_tooltip();
+                return true;
+            }
+        }
+        self.mark_pending_if_widget_changed();
+        true
+    }
+
     fn handle_select_click(&mut self, id: DropdownId, x: f32, y: f32) -> bool {
         if self.open_dropdown == Some(id) && self.select_ref(id).is_expanded() {
             if let Some(index) = self.select_ref(id).dropdown_option_at(x, y) {
@@ -884,26 +1490,63 @@ impl SettingsPanel {
         self.recalculate_positions(window_width);
     }
 
+    /// Switch the model list to the English-only or multilingual counterparts
+    fn english_only_changed(&mut self, window_width: u32) {
+        let english_only = self.english_only_toggle.value;
+        let backend = match self.backend_select.selected_index {
+            0 => BackendType::CTranslate2,
+            1 => BackendType::WhisperCpp,
+            2 => BackendType::Moonshine,
+            3 => BackendType::Parakeet,
+            4 => BackendType::Nemotron,
+            _ => BackendType::CTranslate2,
+        };
+        let old_model = self.model_select.selected_value().to_string();
+        self.model_select.options = models_for_backend(backend, english_only);
+        let counterpart = if english_only {
+            format!("{}.en", old_model)
+        } else {
+            old_model.trim_end_matches(".en").to_string()
+        };
+        self.model_select.selected_index = self
+            .model_select
+            .options
+            .iter()
+            .position(|o| o.value == counterpart)
+            .unwrap_or(0);
+        self.model_select.mark_changed();
+        self.show_language_select = backend_has_language_select(backend, english_only);
+        if self.show_language_select {
+            self.language_select.options = languages_for_backend(backend);
+            self.language_select.selected_index = 0;
+        }
+        self.recalculate_positions(window_width);
+    }
+
     fn widgets_have_pending_changes(&self) -> bool {
         self.backend_select.has_changed()
             || self.english_only_toggle.has_changed()
             || self.model_select.has_changed()
             || self.language_select.has_changed()
             || self.gpu_toggle.has_changed()
+            || self.gpu_backend_select.has_changed()
             || self.threads_slider.has_changed()
             || self.vad_sensitivity_select.has_changed()
             || self.sound_toggle.has_changed()
             || self.volume_slider.has_changed()
+            || self.input_device_select.has_changed()
             || self.auto_paste_toggle.has_changed()
             || self.clear_on_session_toggle.has_changed()
             || self.post_processing_toggle.has_changed()
             || self.typewriter_toggle.has_changed()
             || self.shortcut_mode_select.has_changed()
+            || self.toggle_shortcut_capture.has_changed()
             || self.paste_shortcut_select.has_changed()
             || self.enhancement_toggle.has_changed()
             || self.vsync_select.has_changed()
             || self.target_fps_slider.has_changed()
             || self.system_tray_toggle.has_changed()
+            || self.spectrogram_scale_select.has_changed()
             || self.visual_theme_select.has_changed()
             || self.spectrogram_skin_select.has_changed()
             || self.window_position_select.has_changed()
@@ -936,13 +1579,16 @@ impl SettingsPanel {
     }
 
     fn tooltip_for_position(&self, y: f32) -> Option<SettingsTooltip> {
+        if self.row_hidden(y + SPACING) {
+            return None;
+        }
         let hit = |widget_y: f32| -> bool { y >= widget_y && y < widget_y + ROW_HEIGHT };
 
         macro_rules! tip {
             ($wy:expr, $text:expr) => {
                 if hit($wy) {
                     return Some(SettingsTooltip {
-                        text: $text,
+                        text: tr($text),
                         row_y: $wy,
                     });
                 }
@@ -951,67 +1597,49 @@ impl SettingsPanel {
 
         match self.active_tab {
             SettingsTab::Backend => {
-                tip!(self.backend_select.y, "Transcription engine");
+                tip!(self.backend_select.y, "tip.backend");
                 if self.show_english_toggle {
-                    tip!(self.english_only_toggle.y, "English-only models are faster");
+                    tip!(self.english_only_toggle.y, "tip.english_only");
                 }
-                tip!(
-                    self.model_select.y,
-                    "Larger models are more accurate, but slower"
-                );
+                tip!(self.model_select.y, "tip.model");
                 if self.show_language_select {
-                    tip!(self.language_select.y, "Language used for transcription");
+                    tip!(self.language_select.y, "tip.language");
                 }
-                tip!(self.gpu_toggle.y, "Use GPU when supported");
-                tip!(self.threads_slider.y, "CPU threads used for transcription");
+                tip!(self.gpu_toggle.y, "tip.gpu");
+                tip!(self.gpu_backend_select.y, "tip.gpu_backend");
+                tip!(self.threads_slider.y, "tip.threads");
             }
             SettingsTab::Audio => {
-                tip!(self.vad_sensitivity_select.y, "Voice detection sensitivity");
-                tip!(self.sound_toggle.y, "Play start and stop sounds");
-                tip!(self.volume_slider.y, "Sound feedback volume");
+                tip!(self.vad_sensitivity_select.y, "tip.vad_sensitivity");
+                tip!(self.sound_toggle.y, "tip.sound_feedback");
+                tip!(self.volume_slider.y, "tip.volume");
+                tip!(self.input_device_select.y, "tip.input_device");
             }
             SettingsTab::Behavior => {
-                tip!(
-                    self.auto_paste_toggle.y,
-                    "Paste transcript into the focused app"
-                );
-                tip!(
-                    self.clear_on_session_toggle.y,
-                    "Clear old transcript on new recording"
-                );
-                tip!(
-                    self.post_processing_toggle.y,
-                    "Clean transcription artifacts"
-                );
-                tip!(
-                    self.typewriter_toggle.y,
-                    "Animate text character by character"
-                );
-                tip!(
-                    self.shortcut_mode_select.y,
-                    "Toggle records until stopped; push-to-talk holds"
-                );
-                tip!(self.paste_shortcut_select.y, "Shortcut sent when pasting");
-                tip!(
-                    self.enhancement_toggle.y,
-                    "Enhance transcripts with local AI"
-                );
+                tip!(self.auto_paste_toggle.y, "tip.auto_paste");
+                tip!(self.clear_on_session_toggle.y, "tip.clear_on_session");
+                tip!(self.post_processing_toggle.y, "tip.post_processing");
+                tip!(self.typewriter_toggle.y, "tip.typewriter");
+                tip!(self.shortcut_mode_select.y, "tip.shortcut_mode");
+                tip!(self.toggle_shortcut_capture.y, "tip.toggle_shortcut");
+                tip!(self.paste_shortcut_select.y, "tip.paste_shortcut");
+                tip!(self.enhancement_toggle.y, "tip.magic_mode");
+                tip!(self.profile_select.y, "tip.profile");
             }
             SettingsTab::Display => {
-                tip!(self.vsync_select.y, "Sync frames to display refresh");
-                tip!(self.target_fps_slider.y, "Frame-rate cap when VSync is off");
-                tip!(self.system_tray_toggle.y, "Show a system tray icon");
+                tip!(self.vsync_select.y, "tip.vsync");
+                tip!(self.target_fps_slider.y, "tip.target_fps");
+                tip!(self.system_tray_toggle.y, "tip.system_tray");
+                tip!(self.spectrogram_scale_select.y, "tip.spectrogram_scale");
             }
             SettingsTab::Appearance => {
-                tip!(self.visual_theme_select.y, "Curated overlay appearance");
-                tip!(self.spectrogram_skin_select.y, "Audio visualization style");
-                tip!(self.window_position_select.y, "Overlay position preset");
-                tip!(self.font_size_slider.y, "Transcription text size");
-                tip!(
-                    self.recording_indicator_toggle.y,
-                    "Show recording indicator"
-                );
+                tip!(self.visual_theme_select.y, "tip.visual_theme");
+                tip!(self.spectrogram_skin_select.y, "tip.spectrogram_skin");
+                tip!(self.window_position_select.y, "tip.window_position");
+                tip!(self.font_size_slider.y, "tip.font_size");
+                tip!(self.recording_indicator_toggle.y, "tip.recording_indicator");
             }
+            SettingsTab::History | SettingsTab::Integration => {}
         }
 
         None
@@ -1021,7 +1649,67 @@ impl SettingsPanel {
         CONTENT_Y + (self.tab_row_count(tab) as f32) * (ROW_HEIGHT + SPACING) + 12.0
     }
 
+    /// Rows the active tab can scroll before its buttons reach the bottom edge
+    fn max_scroll_rows(&self) -> usize {
+        if matches!(
+            self.active_tab,
+            SettingsTab::History | SettingsTab::Integration
+        ) {
+            return 0;
+        }
+        let content_bottom = self.buttons_y_for_tab(self.active_tab) + APPLY_BUTTON_HEIGHT + 8.0;
+        let overflow = content_bottom - self.window_height as f32;
+        if overflow <= 0.0 {
+            0
+        } else {
+            (overflow / (ROW_HEIGHT + SPACING)).ceil() as usize
+        }
+    }
+
+    /// Vertical scroll of the tab content in pixels, clamped to the content height
+    fn content_scroll(&self) -> f32 {
+        self.scroll_rows.min(self.max_scroll_rows()) as f32 * (ROW_HEIGHT + SPACING)
+    }
+
+    fn scroll_content_by(&mut self, rows: i32) {
+        let current = self.scroll_rows.min(self.max_scroll_rows());
+        let next = (current as i64 + rows as i64).clamp(0, self.max_scroll_rows() as i64) as usize;
+        self.scroll_rows = next;
+    }
+
+    /// Scroll just enough for the keyboard-focused row to be fully visible
+    fn scroll_focus_into_view(&mut self) {
+        let Some(row) = self.focused_row() else {
+            return;
+        };
+        let (_, y, _, h) = self.focus_rect(row, self.window_width);
+        let step = ROW_HEIGHT + SPACING;
+        let scroll = self.content_scroll();
+        if y < CONTENT_Y + scroll {
+            self.scroll_rows = ((y - CONTENT_Y).max(0.0) / step).floor() as usize;
+        } else if y + h + 8.0 > self.window_height as f32 + scroll {
+            self.scroll_rows = ((y + h + 8.0 - self.window_height as f32) / step).ceil() as usize;
+        }
+        self.scroll_rows = self.scroll_rows.min(self.max_scroll_rows());
+    }
+
+    /// Whether a row at widget-space `y` is scrolled out above the content area
+    fn row_hidden(&self, y: f32) -> bool {
+        y < CONTENT_Y + self.content_scroll()
+    }
+
+    /// Whether widgets hold changes that were not applied yet
+    pub fn has_pending_changes(&self) -> bool {
+        self.has_pending_changes
+    }
+
     pub fn populate_from_config(&mut self, config: &AppConfig) {
+        self.accent = Theme::from_config(&config.theme, &config.ui_config).accent;
+        self.history_view.configure(
+            config.debug_config.save_transcript_history,
+            &config.debug_config.transcript_history_path,
+        );
+
         // Backend
         let backend = config.backend_config.backend;
         self.backend_select.selected_index = match backend {
@@ -1041,6 +1729,16 @@ impl SettingsPanel {
             .iter()
             .position(|o| o.value == config.general_config.model)
             .unwrap_or(0);
+        if config.general_config.model_path.is_some() {
+            self.model_select.options.insert(
+                0,
+                SelectOption {
+                    label: "(custom)".to_string(),
+                    value: CUSTOM_MODEL.to_string(),
+                },
+            );
+            self.model_select.selected_index = 0;
+        }
         self.show_language_select = backend_has_language_select(backend, english_only);
         if self.show_language_select {
             self.language_select.options = languages_for_backend(backend);
@@ -1052,6 +1750,11 @@ impl SettingsPanel {
                 .unwrap_or(0);
         }
         self.gpu_toggle.set_value(config.backend_config.gpu_enabled);
+        self.gpu_backend_select.selected_index = GpuBackend::ALL
+            .iter()
+            .position(|backend| *backend == config.whisper_cpp_options.gpu_backend)
+            .unwrap_or(0);
+        self.gpu_backend_select.label = gpu_backend_row_label(config);
         self.threads_slider.value = config.backend_config.threads as f32;
         self.recalculate_positions(self.window_width);
 
@@ -1063,10 +1766,14 @@ impl SettingsPanel {
         };
         self.sound_toggle.set_value(config.sound_config.enabled);
         self.volume_slider.value = config.sound_config.volume;
+        let (options, index) =
+            input_device_options(config.audio_capture_config.target_object.as_deref());
+        self.input_device_select.options = options;
+        self.input_device_select.selected_index = index;
 
         // Behavior
         self.auto_paste_toggle
-            .set_value(config.portal_config.enable_xdg_portal);
+            .set_value(config.portal_config.auto_paste);
         self.clear_on_session_toggle
             .set_value(config.manual_mode_config.clear_on_new_session);
         self.post_processing_toggle
@@ -1077,6 +1784,10 @@ impl SettingsPanel {
             ShortcutMode::Toggle => 0,
             ShortcutMode::PushToTalk => 1,
         };
+        self.toggle_shortcut_capture
+            .set_value(&config.portal_config.manual_toggle_accelerator);
+        self.toggle_shortcut_capture
+            .set_reserved(reserved_accelerators(&config.portal_config));
         self.paste_shortcut_select.selected_index =
             match config.portal_config.paste_shortcut.as_str() {
                 "ctrl_v" => 1,
@@ -1097,6 +1808,11 @@ impl SettingsPanel {
         self.target_fps_slider.value = config.display_config.target_fps as f32;
         self.system_tray_toggle
             .set_value(config.window_behavior_config.show_in_system_tray);
+        self.spectrogram_scale_select.selected_index = match config.ui_config.spectrogram_scale {
+            SpectrogramScale::Linear => 0,
+            SpectrogramScale::Mel => 1,
+            SpectrogramScale::Log => 2,
+        };
 
         // Appearance
         self.visual_theme_select.selected_index = match config.ui_config.visual_theme {
@@ -1151,6 +1867,7 @@ impl SettingsPanel {
             self.model_select.options = new_options;
             self.model_select.selected_index = new_selected;
             config.general_config.model = self.model_select.selected_value().to_string();
+            config.general_config.model_path = None;
             needs_backend_reload = true;
             any_changed = true;
         }
@@ -1161,7 +1878,11 @@ impl SettingsPanel {
             any_changed = true;
         }
         if let Some(_idx) = self.model_select.take_changed() {
-            config.general_config.model = self.model_select.selected_value().to_string();
+            // Picking a listed model replaces the local `model_path` model
+            if self.model_select.selected_value() != CUSTOM_MODEL {
+                config.general_config.model = self.model_select.selected_value().to_string();
+                config.general_config.model_path = None;
+            }
             needs_backend_reload = true;
             any_changed = true;
         }
@@ -1175,6 +1896,11 @@ impl SettingsPanel {
             needs_backend_reload = true;
             any_changed = true;
         }
+        if let Some(idx) = self.gpu_backend_select.take_changed() {
+            config.whisper_cpp_options.gpu_backend = GpuBackend::ALL[idx];
+            needs_backend_reload = true;
+            any_changed = true;
+        }
         if let Some(val) = self.threads_slider.take_changed() {
             config.backend_config.threads = val as usize;
             needs_backend_reload = true;
@@ -1197,9 +1923,15 @@ impl SettingsPanel {
             config.sound_config.volume = val;
             any_changed = true;
         }
+        if self.input_device_select.take_changed().is_some() {
+            let name = self.input_device_select.selected_value();
+            config.audio_capture_config.target_object =
+                (!name.is_empty()).then(|| name.to_string());
+            any_changed = true;
+        }
 
         if let Some(val) = self.auto_paste_toggle.take_changed() {
-            config.portal_config.enable_xdg_portal = val;
+            config.portal_config.auto_paste = val;
             any_changed = true;
         }
         if let Some(val) = self.clear_on_session_toggle.take_changed() {
@@ -1221,6 +1953,10 @@ impl SettingsPanel {
             };
             any_changed = true;
         }
+        if let Some(accelerator) = self.toggle_shortcut_capture.take_changed() {
+            config.portal_config.manual_toggle_accelerator = accelerator;
+            any_changed = true;
+        }
         if let Some(idx) = self.paste_shortcut_select.take_changed() {
             config.portal_config.paste_shortcut = match idx {
                 1 => "ctrl_v".to_string(),
@@ -1251,6 +1987,14 @@ impl SettingsPanel {
             config.window_behavior_config.show_in_system_tray = val;
             any_changed = true;
         }
+        if let Some(idx) = self.spectrogram_scale_select.take_changed() {
+            config.ui_config.spectrogram_scale = match idx {
+                1 => SpectrogramScale::Mel,
+                2 => SpectrogramScale::Log,
+                _ => SpectrogramScale::Linear,
+            };
+            any_changed = true;
+        }
 
         if let Some(idx) = self.visual_theme_select.take_changed() {
             config.ui_config.visual_theme = match idx {
@@ -1294,10 +2038,94 @@ impl SettingsPanel {
             config.ui_config.show_recording_indicator = val;
             any_changed = true;
         }
+        // Backends without a language select only accept their model's language
+        if let Some(language) = crate::model_catalog::fixed_language(
+            config.backend_config.backend,
+            &config.general_config.model,
+        ) {
+            config.general_config.language = language;
+        }
 
         (any_changed, needs_backend_reload)
     }
 
+    pub fn take_help_request(&mut self) -> bool {
+        std::mem::take(&mut self.help_requested)
+    }
+
+    /// Ask the user to confirm a large model download before applying
+    pub fn confirm_download(
+        &mut self,
+        download: &crate::model_catalog::PendingDownload,
+        metered: bool,
+    ) {
+        self.close_all_dropdowns();
+        self.clear_tooltip();
+        let mut lines = vec![
+            format!(
+                "{} needs a {} download.",
+                download.model,
+                crate::model_catalog::format_size(download.size_mb)
+            ),
+            format!("License: {}", download.license),
+        ];
+        if metered {
+            lines.push("Your connection is metered.".to_string());
+        }
+        self.confirm_dialog
+            .show("Download model?", lines, "Download");
+    }
+
+    /// Ask before a profile switch that reloads the backend, naming the
+    /// download it needs, if any
+    pub fn confirm_profile_reload(
+        &mut self,
+        profile: Option<&str>,
+        download: Option<&crate::model_catalog::PendingDownload>,
+    ) {
+        self.close_all_dropdowns();
+        self.clear_tooltip();
+        let profile = profile.unwrap_or_else(|| tr("settings.profile_none"));
+        let mut lines = vec![tr_args("dialog.profile_reload", &[("profile", &profile)])];
+        if let Some(download) = download {
+            lines.push(tr_args(
+                "dialog.profile_download",
+                &[
+                    ("model", &download.model),
+                    ("size", &crate::model_catalog::format_size(download.size_mb)),
+                ],
+            ));
+        }
+        self.confirm_dialog.show(
+            tr("dialog.profile_title"),
+            lines,
+            tr("dialog.profile_confirm"),
+        );
+    }
+
+    /// The answer to the confirmation dialog, once (true = confirmed)
+    pub fn take_dialog_decision(&mut self) -> Option<bool> {
+        self.confirm_dialog.take_decision()
+    }
+
+    /// Offer `names` in the profile select, with `active` selected
+    pub fn set_profiles(&mut self, names: &[String], active: Option<&str>) {
+        let options = profile_options(names, active);
+        self.profile_select.selected_index = options
+            .iter()
+            .position(|option| Some(option.value.as_str()) == active)
+            .unwrap_or(0);
+        self.profile_select.options = options;
+        self.profile_select.clear_changed();
+    }
+
+    /// The profile chosen since the last call, once; `Some(None)` switches the profile off
+    pub fn take_profile_change(&mut self) -> Option<Option<String>> {
+        self.profile_select.take_changed()?;
+        let name = self.profile_select.selected_value();
+        Some((!name.is_empty()).then(|| name.to_string()))
+    }
+
     pub fn take_apply_request(&mut self) -> bool {
         let v = self.apply_requested;
         self.apply_requested = false;
@@ -1316,20 +2144,24 @@ impl SettingsPanel {
         self.model_select.clear_changed();
         self.language_select.clear_changed();
         self.gpu_toggle.clear_changed();
+        self.gpu_backend_select.clear_changed();
         self.threads_slider.clear_changed();
         self.vad_sensitivity_select.clear_changed();
         self.sound_toggle.clear_changed();
         self.volume_slider.clear_changed();
+        self.input_device_select.clear_changed();
         self.auto_paste_toggle.clear_changed();
         self.clear_on_session_toggle.clear_changed();
         self.post_processing_toggle.clear_changed();
         self.typewriter_toggle.clear_changed();
         self.shortcut_mode_select.clear_changed();
+        self.toggle_shortcut_capture.clear_changed();
         self.paste_shortcut_select.clear_changed();
         self.enhancement_toggle.clear_changed();
         self.vsync_select.clear_changed();
         self.target_fps_slider.clear_changed();
         self.system_tray_toggle.clear_changed();
+        self.spectrogram_scale_select.clear_changed();
         self.visual_theme_select.clear_changed();
         self.spectrogram_skin_select.clear_changed();
         self.window_position_select.clear_changed();
@@ -1340,7 +2172,7 @@ impl SettingsPanel {
     fn tab_row_count(&self, tab: SettingsTab) -> usize {
         match tab {
             SettingsTab::Backend => {
-                let mut rows = 4;
+                let mut rows = 5;
                 if self.show_english_toggle {
                     rows += 1;
                 }
@@ -1349,10 +2181,11 @@ impl SettingsPanel {
                 }
                 rows
             }
-            SettingsTab::Audio => 3,
-            SettingsTab::Behavior => 7,
-            SettingsTab::Display => 3,
+            SettingsTab::Audio => 4,
+            SettingsTab::Behavior => 9,
+            SettingsTab::Display => 4,
             SettingsTab::Appearance => 5,
+            SettingsTab::History | SettingsTab::Integration => 0,
         }
     }
 
@@ -1393,6 +2226,11 @@ impl SettingsPanel {
         self.gpu_toggle.width = w;
         self.gpu_toggle.height = ROW_HEIGHT;
         y += step;
+        self.gpu_backend_select.x = x;
+        self.gpu_backend_select.y = y;
+        self.gpu_backend_select.width = w;
+        self.gpu_backend_select.height = ROW_HEIGHT;
+        y += step;
         self.threads_slider.x = x;
         self.threads_slider.y = y;
         self.threads_slider.width = w;
@@ -1414,6 +2252,11 @@ impl SettingsPanel {
         self.volume_slider.y = y;
         self.volume_slider.width = w;
         self.volume_slider.height = ROW_HEIGHT;
+        y += step;
+        self.input_device_select.x = x;
+        self.input_device_select.y = y;
+        self.input_device_select.width = w;
+        self.input_device_select.height = ROW_HEIGHT;
 
         // Behavior tab
         y = CONTENT_Y;
@@ -1442,6 +2285,11 @@ impl SettingsPanel {
         self.shortcut_mode_select.width = w;
         self.shortcut_mode_select.height = ROW_HEIGHT;
         y += step;
+        self.toggle_shortcut_capture.x = x;
+        self.toggle_shortcut_capture.y = y;
+        self.toggle_shortcut_capture.width = w;
+        self.toggle_shortcut_capture.height = ROW_HEIGHT;
+        y += step;
         self.paste_shortcut_select.x = x;
         self.paste_shortcut_select.y = y;
         self.paste_shortcut_select.width = w;
@@ -1451,6 +2299,11 @@ impl SettingsPanel {
         self.enhancement_toggle.y = y;
         self.enhancement_toggle.width = w;
         self.enhancement_toggle.height = ROW_HEIGHT;
+        y += step;
+        self.profile_select.x = x;
+        self.profile_select.y = y;
+        self.profile_select.width = w;
+        self.profile_select.height = ROW_HEIGHT;
 
         // Display tab
         y = CONTENT_Y;
@@ -1468,6 +2321,11 @@ impl SettingsPanel {
         self.system_tray_toggle.y = y;
         self.system_tray_toggle.width = w;
         self.system_tray_toggle.height = ROW_HEIGHT;
+        y += step;
+        self.spectrogram_scale_select.x = x;
+        self.spectrogram_scale_select.y = y;
+        self.spectrogram_scale_select.width = w;
+        self.spectrogram_scale_select.height = ROW_HEIGHT;
 
         // Appearance tab
         y = CONTENT_Y;
@@ -1497,11 +2355,21 @@ impl SettingsPanel {
         self.recording_indicator_toggle.height = ROW_HEIGHT;
     }
 
-    pub fn handle_click(&mut self, x: f32, y: f32, window_width: u32, _window_height: u32) -> bool {
+    pub fn handle_click(&mut self, x: f32, y: f32, window_width: u32, window_height: u32) -> bool {
         if !self.is_open {
             return false;
         }
 
+        if self
+            .confirm_dialog
+            .handle_click(x, y, window_width, window_height)
+        {
+            return true;
+        }
+
+        // The mouse takes over from keyboard focus
+        self.focused_rows[self.active_tab.index()] = None;
+
         let y = self.local_y(y);
         let tab_bar_height = 24.0f32;
         let tab_bar_y = 8.0f32;
@@ -1519,33 +2387,52 @@ impl SettingsPanel {
             return true;
         }
 
+        // Check help button
+        let help_x = close_x - close_size - 4.0;
+        if x >= help_x && x <= help_x + close_size && y >= tab_bar_y && y <= tab_bar_y + close_size
+        {
+            self.help_requested = true;
+            self.close_all_dropdowns();
+            return true;
+        }
+
         let tabs = SettingsTab::all();
-        let tab_count = tabs.len() as f32;
-        let usable_width = close_x;
-        let tab_width = usable_width / tab_count;
+        let usable_width = help_x;
 
         // Check if click is on a tab
         if y >= tab_bar_y && y <= tab_bar_y + tab_bar_height && x < usable_width {
-            let tab_index = (x / tab_width) as usize;
-            if tab_index < tabs.len() {
+            let tab_index = tab_layout(usable_width, &self.tab_label_widths)
+                .iter()
+                .position(|&(tab_x, tab_width)| x >= tab_x && x < tab_x + tab_width);
+            if let Some(tab_index) = tab_index {
                 self.active_tab = tabs[tab_index];
+                self.scroll_rows = 0;
                 self.close_all_dropdowns();
+                self.toggle_shortcut_capture.cancel_capture();
+                if self.active_tab == SettingsTab::History {
+                    self.history_view.reload();
+                }
                 return true;
             }
         }
 
+        // Rows scrolled out above the content area take no clicks
+        if !matches!(
+            self.active_tab,
+            SettingsTab::History | SettingsTab::Integration
+        ) && y < CONTENT_Y - SPACING
+        {
+            self.close_all_dropdowns();
+            return true;
+        }
+        let y = y + self.content_scroll();
+
         if let Some(open_id) = self.open_dropdown {
             if self.select_ref(open_id).is_expanded() {
-                if let Some(index) = self.select_ref(open_id).dropdown_option_at(x, y) {
-                    let previous_index = self.select_ref(open_id).selected_index;
-                    self.select_mut(open_id).choose_index(index);
-                    if open_id == DropdownId::Backend
-                        && self.backend_select.selected_index != previous_index
-                    {
-                        self.refresh_backend_dependent_options(window_width);
-                    }
-                    self.close_all_dropdowns();
-                    self.mark_pending_if_widget_changed();
+                // The option is chosen on release unless the press drags the
+                // list; presses on the scrollbar or padding keep it open
+                if self.select_ref(open_id).hit_dropdown(x, y) {
+                    self.select_mut(open_id).begin_drag(x, y);
                     return true;
                 }
 
@@ -1566,15 +2453,20 @@ impl SettingsPanel {
             return true;
         }
 
+        // The history list has no Apply/Reset buttons
+        if self.active_tab == SettingsTab::History {
+            self.history_view.handle_click(x, y);
+            return true;
+        }
+        if self.active_tab == SettingsTab::Integration {
+            self.integration_view.handle_click(x, y);
+            return true;
+        }
+
         // Check Apply and Reset buttons
         let buttons_y = self.buttons_y_for_tab(self.active_tab);
-        let w = default_width(window_width);
-        let btn_width = 80.0f32;
-        let btn_gap = 8.0f32;
-        let total_width = btn_width * 2.0 + btn_gap;
-        let start_x = WIDGET_X + (w - total_width) / 2.0;
-        let reset_btn_x = start_x;
-        let apply_btn_x = start_x + btn_width + btn_gap;
+        let btn_width = ACTION_BUTTON_WIDTH;
+        let (reset_btn_x, apply_btn_x) = action_buttons_x(window_width);
 
         if y >= buttons_y && y <= buttons_y + APPLY_BUTTON_HEIGHT {
             if x >= reset_btn_x && x <= reset_btn_x + btn_width {
@@ -1606,35 +2498,7 @@ impl SettingsPanel {
                     && self.english_only_toggle.handle_click(x, y)
                 {
                     widget_clicked = true;
-                    let english_only = self.english_only_toggle.value;
-                    let backend = match self.backend_select.selected_index {
-                        0 => BackendType::CTranslate2,
-                        1 => BackendType::WhisperCpp,
-                        2 => BackendType::Moonshine,
-                        3 => BackendType::Parakeet,
-                        4 => BackendType::Nemotron,
-                        _ => BackendType::CTranslate2,
-                    };
-                    let old_model = self.model_select.selected_value().to_string();
-                    self.model_select.options = models_for_backend(backend, english_only);
-                    let counterpart = if english_only {
-                        format!("{}.en", old_model)
-                    } else {
-                        old_model.trim_end_matches(".en").to_string()
-                    };
-                    self.model_select.selected_index = self
-                        .model_select
-                        .options
-                        .iter()
-                        .position(|o| o.value == counterpart)
-                        .unwrap_or(0);
-                    self.model_select.mark_changed();
-                    self.show_language_select = backend_has_language_select(backend, english_only);
-                    if self.show_language_select {
-                        self.language_select.options = languages_for_backend(backend);
-                        self.language_select.selected_index = 0;
-                    }
-                    self.recalculate_positions(window_width);
+                    self.english_only_changed(window_width);
                 }
                 if !widget_clicked && self.handle_select_click(DropdownId::Model, x, y) {
                     widget_clicked = true;
@@ -1648,6 +2512,9 @@ impl SettingsPanel {
                 if !widget_clicked && self.gpu_toggle.handle_click(x, y) {
                     widget_clicked = true;
                 }
+                if !widget_clicked && self.handle_select_click(DropdownId::GpuBackend, x, y) {
+                    widget_clicked = true;
+                }
                 if !widget_clicked && self.threads_slider.handle_click(x, y) {
                     widget_clicked = true;
                 }
@@ -1662,14 +2529,25 @@ impl SettingsPanel {
                 if !widget_clicked && self.volume_slider.handle_click(x, y) {
                     widget_clicked = true;
                 }
+                if !widget_clicked && self.handle_select_click(DropdownId::InputDevice, x, y) {
+                    widget_clicked = true;
+                }
             }
             SettingsTab::Behavior => {
-                if self.handle_select_click(DropdownId::ShortcutMode, x, y) {
+                if self.toggle_shortcut_capture.handle_click(x, y) {
+                    widget_clicked = true;
+                } else {
+                    self.toggle_shortcut_capture.cancel_capture();
+                }
+                if !widget_clicked && self.handle_select_click(DropdownId::ShortcutMode, x, y) {
                     widget_clicked = true;
                 }
                 if !widget_clicked && self.handle_select_click(DropdownId::PasteShortcut, x, y) {
                     widget_clicked = true;
                 }
+                if !widget_clicked && self.handle_select_click(DropdownId::Profile, x, y) {
+                    widget_clicked = true;
+                }
                 if !widget_clicked && self.auto_paste_toggle.handle_click(x, y) {
                     widget_clicked = true;
                 }
@@ -1713,7 +2591,11 @@ impl SettingsPanel {
                 if !widget_clicked && self.system_tray_toggle.handle_click(x, y) {
                     widget_clicked = true;
                 }
+                if !widget_clicked && self.handle_select_click(DropdownId::SpectrogramScale, x, y) {
+                    widget_clicked = true;
+                }
             }
+            SettingsTab::History | SettingsTab::Integration => {}
         }
 
         if widget_clicked {
@@ -1724,7 +2606,14 @@ impl SettingsPanel {
     }
 
     pub fn handle_mouse_move(&mut self, x: f32, y: f32) {
-        let y = self.local_y(y);
+        if self.confirm_dialog.is_visible() {
+            return;
+        }
+        self.pointer = (x, y);
+        let y = self.local_y(y) + self.content_scroll();
+        if let Some(id) = self.open_dropdown {
+            self.select_mut(id).drag_to(y);
+        }
         match self.active_tab {
             SettingsTab::Backend => {
                 self.backend_select.handle_mouse_move(x, y);
@@ -1732,9 +2621,11 @@ impl SettingsPanel {
                 if self.show_language_select {
                     self.language_select.handle_mouse_move(x, y);
                 }
+                self.gpu_backend_select.handle_mouse_move(x, y);
             }
             SettingsTab::Audio => {
                 self.vad_sensitivity_select.handle_mouse_move(x, y);
+                self.input_device_select.handle_mouse_move(x, y);
             }
             SettingsTab::Behavior => {
                 self.shortcut_mode_select.handle_mouse_move(x, y);
@@ -1742,12 +2633,14 @@ impl SettingsPanel {
             }
             SettingsTab::Display => {
                 self.vsync_select.handle_mouse_move(x, y);
+                self.spectrogram_scale_select.handle_mouse_move(x, y);
             }
             SettingsTab::Appearance => {
                 self.visual_theme_select.handle_mouse_move(x, y);
                 self.spectrogram_skin_select.handle_mouse_move(x, y);
                 self.window_position_select.handle_mouse_move(x, y);
             }
+            SettingsTab::History | SettingsTab::Integration => {}
         }
 
         // Route drag to active tab sliders
@@ -1771,6 +2664,12 @@ impl SettingsPanel {
     }
 
     pub fn handle_mouse_release(&mut self) {
+        if let Some(id) = self.open_dropdown {
+            if let Some(index) = self.select_mut(id).end_drag() {
+                let window_width = self.window_width;
+                self.choose_open_dropdown_option(id, index, window_width);
+            }
+        }
         self.threads_slider.handle_release();
         self.volume_slider.handle_release();
         self.target_fps_slider.handle_release();
@@ -1791,6 +2690,16 @@ impl SettingsPanel {
     }
 
     pub fn handle_key(&mut self, key: &Key, shift: bool) -> bool {
+        if self.confirm_dialog.handle_key(key) {
+            return true;
+        }
+
+        if let Some(id) = self.open_dropdown {
+            if self.handle_dropdown_key(id, key) {
+                return true;
+            }
+        }
+
         match key {
             Key::Named(NamedKey::Tab) => {
                 let tabs = SettingsTab::all();
@@ -1800,9 +2709,106 @@ impl SettingsPanel {
                 } else {
                     self.active_tab = tabs[(current + 1) % tabs.len()];
                 }
+                self.scroll_rows = 0;
+                self.close_all_dropdowns();
+                true
+            }
+            Key::Named(NamedKey::ArrowDown) => self.move_focus(1),
+            Key::Named(NamedKey::ArrowUp) => self.move_focus(-1),
+            Key::Named(NamedKey::ArrowLeft) => self.adjust_focused_row(-1),
+            Key::Named(NamedKey::ArrowRight) => self.adjust_focused_row(1),
+            Key::Named(NamedKey::Space | NamedKey::Enter) => self.activate_focused_row(),
+            _ => false,
+        }
+    }
+
+    /// Left/Right: step the focused slider or cycle the focused select
+    fn adjust_focused_row(&mut self, delta: i32) -> bool {
+        match self.focused_row() {
+            Some(FocusRow::Slider(id)) => {
+                self.slider_mut(id).step_by(delta);
+            }
+            Some(FocusRow::Select(id)) => {
+                if self.select_mut(id).cycle(delta) && id == DropdownId::Backend {
+                    let window_width = self.window_width;
+                    self.refresh_backend_dependent_options(window_width);
+                }
+            }
+            _ => return false,
+        }
+        self.mark_pending_if_widget_changed();
+        true
+    }
+
+    /// Space/Enter: flip the focused toggle, open the focused select or press a button
+    fn activate_focused_row(&mut self) -> bool {
+        let Some(row) = self.focused_row() else {
+            return false;
+        };
+        match row {
+            FocusRow::Toggle(id) => {
+                self.toggle_mut(id).toggle();
+                if id == ToggleId::EnglishOnly {
+                    let window_width = self.window_width;
+                    self.english_only_changed(window_width);
+                }
+                self.mark_pending_if_widget_changed();
+            }
+            FocusRow::Select(id) => self.open_only_dropdown(id),
+            FocusRow::Shortcut => self.toggle_shortcut_capture.start_capture(),
+            FocusRow::Slider(_) => return false,
+            FocusRow::Reset => self.reset_tab_to_defaults(),
+            FocusRow::Apply => self.apply_requested = true,
+        }
+        true
+    }
+
+    /// Keyboard navigation and type-ahead for the open dropdown
+    fn handle_dropdown_key(&mut self, id: DropdownId, key: &Key) -> bool {
+        match key {
+            Key::Named(NamedKey::ArrowDown) => {
+                self.select_mut(id).move_highlight(1);
+                true
+            }
+            Key::Named(NamedKey::ArrowUp) => {
+                self.select_mut(id).move_highlight(-1);
+                true
+            }
+            Key::Named(NamedKey::PageDown) => {
+                self.select_mut(id).move_highlight(8);
+                true
+            }
+            Key::Named(NamedKey::PageUp) => {
+                self.select_mut(id).move_highlight(-8);
+                true
+            }
+            // Space extends a type-ahead query that is still being typed
+            Key::Named(NamedKey::Space) if self.select_ref(id).type_ahead_active() => {
+                self.select_mut(id).type_ahead(" ");
+                true
+            }
+            Key::Named(NamedKey::Enter | NamedKey::Space) => {
+                match self.select_ref(id).highlighted_index() {
+                    Some(index) => {
+                        let window_width = self.window_width;
+                        self.choose_open_dropdown_option(id, index, window_width);
+                    }
+                    None => self.close_all_dropdowns(),
+                }
+                true
+            }
+            Key::Named(NamedKey::Escape) => {
                 self.close_all_dropdowns();
                 true
             }
+            Key::Named(NamedKey::Backspace) => {
+                self.select_mut(id).type_ahead_backspace();
+                true
+            }
+            Key::Character(text) => {
+                self.select_mut(id).type_ahead(text.as_str());
+                true
+            }
             _ => false,
         }
     }
@@ -1811,13 +2817,17 @@ impl SettingsPanel {
         self.backend_select.set_expanded(false);
         self.model_select.set_expanded(false);
         self.language_select.set_expanded(false);
+        self.gpu_backend_select.set_expanded(false);
         self.vad_sensitivity_select.set_expanded(false);
+        self.input_device_select.set_expanded(false);
         self.shortcut_mode_select.set_expanded(false);
         self.paste_shortcut_select.set_expanded(false);
         self.vsync_select.set_expanded(false);
+        self.spectrogram_scale_select.set_expanded(false);
         self.visual_theme_select.set_expanded(false);
         self.spectrogram_skin_select.set_expanded(false);
         self.window_position_select.set_expanded(false);
+        self.profile_select.set_expanded(false);
         self.open_dropdown = None;
         self.clear_tooltip();
     }
@@ -1861,6 +2871,11 @@ impl SettingsPanel {
                 self.gpu_toggle
                     .set_value(defaults.backend_config.gpu_enabled);
                 self.gpu_toggle.mark_changed();
+                self.gpu_backend_select.selected_index = GpuBackend::ALL
+                    .iter()
+                    .position(|backend| *backend == defaults.whisper_cpp_options.gpu_backend)
+                    .unwrap_or(0);
+                self.gpu_backend_select.mark_changed();
                 self.threads_slider.value = defaults.backend_config.threads as f32;
                 self.threads_slider.mark_changed();
                 self.recalculate_positions(self.window_width);
@@ -1876,10 +2891,12 @@ impl SettingsPanel {
                 self.sound_toggle.mark_changed();
                 self.volume_slider.value = defaults.sound_config.volume;
                 self.volume_slider.mark_changed();
+                self.input_device_select.selected_index = 0;
+                self.input_device_select.mark_changed();
             }
             SettingsTab::Behavior => {
                 self.auto_paste_toggle
-                    .set_value(defaults.portal_config.enable_xdg_portal);
+                    .set_value(defaults.portal_config.auto_paste);
                 self.auto_paste_toggle.mark_changed();
                 self.clear_on_session_toggle
                     .set_value(defaults.manual_mode_config.clear_on_new_session);
@@ -1896,6 +2913,9 @@ impl SettingsPanel {
                         ShortcutMode::PushToTalk => 1,
                     };
                 self.shortcut_mode_select.mark_changed();
+                self.toggle_shortcut_capture
+                    .set_value(&defaults.portal_config.manual_toggle_accelerator);
+                self.toggle_shortcut_capture.mark_changed();
                 self.paste_shortcut_select.selected_index =
                     match defaults.portal_config.paste_shortcut.as_str() {
                         "ctrl_v" => 1,
@@ -1922,6 +2942,13 @@ impl SettingsPanel {
                 self.system_tray_toggle
                     .set_value(defaults.window_behavior_config.show_in_system_tray);
                 self.system_tray_toggle.mark_changed();
+                self.spectrogram_scale_select.selected_index =
+                    match defaults.ui_config.spectrogram_scale {
+                        SpectrogramScale::Linear => 0,
+                        SpectrogramScale::Mel => 1,
+                        SpectrogramScale::Log => 2,
+                    };
+                self.spectrogram_scale_select.mark_changed();
             }
             SettingsTab::Appearance => {
                 self.visual_theme_select.selected_index = match defaults.ui_config.visual_theme {
@@ -1957,6 +2984,7 @@ impl SettingsPanel {
                     .set_value(defaults.ui_config.show_recording_indicator);
                 self.recording_indicator_toggle.mark_changed();
             }
+            SettingsTab::History | SettingsTab::Integration => return,
         }
         self.has_pending_changes = true;
     }
@@ -2057,13 +3085,38 @@ impl SettingsPanel {
             scale: 1.0,
             color: [0.604, 0.604, 0.604, 1.0],
             max_width: close_size,
+            centered: false,
+        });
+
+        // Help button, left of the close button
+        let help_x = close_x - close_size - 4.0;
+        self.widget_renderer.draw_rounded_rect(
+            encoder,
+            view,
+            queue,
+            help_x,
+            close_y,
+            close_size,
+            close_size,
+            close_size / 2.0,
+            [0.027, 0.027, 0.040, 0.7],
+            window_width,
+            window_height,
+        );
+
+        text_items.push(TextItem {
+            text: "?".to_string(),
+            x: help_x + 8.0,
+            y: close_y + 3.0,
+            scale: 1.0,
+            color: [0.604, 0.604, 0.604, 1.0],
+            max_width: close_size,
+            centered: false,
         });
 
         // Tab bar background
         let tabs = SettingsTab::all();
-        let tab_count = tabs.len() as f32;
-        let usable_width = close_x;
-        let tab_width = usable_width / tab_count;
+        let usable_width = help_x;
         let tab_bar_y = 8.0f32 + y_offset;
 
         self.widget_renderer.draw_rounded_rect(
@@ -2080,9 +3133,11 @@ impl SettingsPanel {
             window_height,
         );
 
-        for (i, tab) in tabs.iter().enumerate() {
+        for (tab, (tab_x, tab_width)) in tabs
+            .iter()
+            .zip(tab_layout(usable_width, &self.tab_label_widths))
+        {
             let is_active = *tab == self.active_tab;
-            let tab_x = (i as f32) * tab_width;
 
             // Active tab pill highlight
             if is_active {
@@ -2107,17 +3162,14 @@ impl SettingsPanel {
                 [0.171, 0.171, 0.214, 0.7]
             };
 
-            let char_width = 6.5f32;
-            let text_width = tab.label().len() as f32 * char_width;
-            let centered_x = tab_x + (tab_width - text_width) / 2.0;
-
             text_items.push(TextItem {
                 text: tab.label().to_string(),
-                x: centered_x,
+                x: tab_x,
                 y: tab_bar_y + 4.0,
                 scale: 1.0,
                 color,
                 max_width: tab_width,
+                centered: true,
             });
 
             // Active tab accent underline
@@ -2131,7 +3183,7 @@ impl SettingsPanel {
                     tab_width - 8.0,
                     1.5,
                     0.75,
-                    [0.010, 0.787, 0.214, 1.0],
+                    self.accent,
                     window_width,
                     window_height,
                 );
@@ -2153,376 +3205,77 @@ impl SettingsPanel {
             window_height,
         );
 
-        // Render active tab's widgets with an animation offset. Widget layout state
-        // remains stable; only the frame-local paint position changes.
+        // Render active tab's widgets with an animation offset and the tab's
+        // scroll. Widget layout state remains stable; only the frame-local paint
+        // position changes. Rows scrolled above the content area are skipped.
         let content_y_offset = y_offset;
-        let row_y = |y: f32| y + content_y_offset;
-        match self.active_tab {
-            SettingsTab::Backend => {
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.backend_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.backend_select.render_at(
-                    row_y(self.backend_select.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                if self.show_english_toggle {
-                    self.draw_row_bg(
-                        encoder,
-                        view,
-                        queue,
-                        row_y(self.english_only_toggle.y),
-                        window_width,
-                        window_height,
-                    );
-                    self.english_only_toggle.render_at(
-                        row_y(self.english_only_toggle.y),
-                        encoder,
-                        view,
-                        &self.widget_renderer,
-                        &mut text_items,
-                        queue,
-                        window_width,
-                        window_height,
-                    );
-                }
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.model_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.model_select.render_at(
-                    row_y(self.model_select.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                if self.show_language_select {
-                    self.draw_row_bg(
-                        encoder,
-                        view,
-                        queue,
-                        row_y(self.language_select.y),
-                        window_width,
-                        window_height,
-                    );
-                    self.language_select.render_at(
-                        row_y(self.language_select.y),
-                        encoder,
-                        view,
-                        &self.widget_renderer,
-                        &mut text_items,
-                        queue,
-                        window_width,
-                        window_height,
-                    );
-                }
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.gpu_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.gpu_toggle.render_at(
-                    row_y(self.gpu_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.threads_slider.y),
-                    window_width,
-                    window_height,
-                );
-                self.threads_slider.render_at(
-                    row_y(self.threads_slider.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-            }
-            SettingsTab::Audio => {
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.vad_sensitivity_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.vad_sensitivity_select.render_at(
-                    row_y(self.vad_sensitivity_select.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.sound_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.sound_toggle.render_at(
-                    row_y(self.sound_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.volume_slider.y),
-                    window_width,
-                    window_height,
-                );
-                self.volume_slider.render_at(
-                    row_y(self.volume_slider.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-            }
-            SettingsTab::Behavior => {
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.auto_paste_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.auto_paste_toggle.render_at(
-                    row_y(self.auto_paste_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.clear_on_session_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.clear_on_session_toggle.render_at(
-                    row_y(self.clear_on_session_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.post_processing_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.post_processing_toggle.render_at(
-                    row_y(self.post_processing_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.typewriter_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.typewriter_toggle.render_at(
-                    row_y(self.typewriter_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.shortcut_mode_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.shortcut_mode_select.render_at(
-                    row_y(self.shortcut_mode_select.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.paste_shortcut_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.paste_shortcut_select.render_at(
-                    row_y(self.paste_shortcut_select.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.enhancement_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.enhancement_toggle.render_at(
-                    row_y(self.enhancement_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-            }
-            SettingsTab::Display => {
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.vsync_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.vsync_select.render_at(
-                    row_y(self.vsync_select.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.target_fps_slider.y),
-                    window_width,
-                    window_height,
-                );
-                self.target_fps_slider.render_at(
-                    row_y(self.target_fps_slider.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.system_tray_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.system_tray_toggle.render_at(
-                    row_y(self.system_tray_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
+        let content_scroll = self.content_scroll();
+        let row_y = |y: f32| y + content_y_offset - content_scroll;
+
+        // Keyboard focus ring: an accent rect that the row drawn on top leaves as a border
+        if let Some((row, (x, y, w, h))) = self
+            .focused_row()
+            .map(|row| (row, self.focus_rect(row, window_width)))
+            .filter(|(_, (_, y, _, _))| !self.row_hidden(*y))
+        {
+            self.widget_renderer.draw_rounded_rect(
+                encoder,
+                view,
+                queue,
+                x - 1.5,
+                row_y(y) - 1.5,
+                w + 3.0,
+                h + 3.0,
+                if matches!(row, FocusRow::Reset | FocusRow::Apply) {
+                    9.5
+                } else {
+                    7.5
+                },
+                self.accent,
+                window_width,
+                window_height,
+            );
+        }
+
+        if self.active_tab == SettingsTab::History {
+            self.history_view.render(
+                content_y_offset,
+                encoder,
+                view,
+                &self.widget_renderer,
+                &mut text_items,
+                queue,
+                window_width,
+                window_height,
+            );
+        }
+        if self.active_tab == SettingsTab::Integration {
+            self.integration_view.render(
+                content_y_offset,
+                encoder,
+                view,
+                &self.widget_renderer,
+                &mut text_items,
+                queue,
+                window_width,
+                window_height,
+            );
+        }
+        for row in self.focus_rows(self.active_tab) {
+            let y = match row {
+                FocusRow::Select(id) => self.select_ref(id).y,
+                FocusRow::Toggle(id) => self.toggle_ref(id).y,
+                FocusRow::Slider(id) => self.slider_ref(id).y,
+                FocusRow::Shortcut => self.toggle_shortcut_capture.y,
+                FocusRow::Reset | FocusRow::Apply => continue,
+            };
+            if self.row_hidden(y) {
+                continue;
             }
-            SettingsTab::Appearance => {
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.visual_theme_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.visual_theme_select.render_at(
-                    row_y(self.visual_theme_select.y),
+            self.draw_row_bg(encoder, view, queue, row_y(y), window_width, window_height);
+            match row {
+                FocusRow::Select(id) => self.select_ref(id).render_at(
+                    row_y(y),
                     encoder,
                     view,
                     &self.widget_renderer,
@@ -2530,17 +3283,9 @@ impl SettingsPanel {
                     queue,
                     window_width,
                     window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.spectrogram_skin_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.spectrogram_skin_select.render_at(
-                    row_y(self.spectrogram_skin_select.y),
+                ),
+                FocusRow::Toggle(id) => self.toggle_ref(id).render_at(
+                    row_y(y),
                     encoder,
                     view,
                     &self.widget_renderer,
@@ -2548,17 +3293,9 @@ impl SettingsPanel {
                     queue,
                     window_width,
                     window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.window_position_select.y),
-                    window_width,
-                    window_height,
-                );
-                self.window_position_select.render_at(
-                    row_y(self.window_position_select.y),
+                ),
+                FocusRow::Slider(id) => self.slider_ref(id).render_at(
+                    row_y(y),
                     encoder,
                     view,
                     &self.widget_renderer,
@@ -2566,17 +3303,10 @@ impl SettingsPanel {
                     queue,
                     window_width,
                     window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.font_size_slider.y),
-                    window_width,
-                    window_height,
-                );
-                self.font_size_slider.render_at(
-                    row_y(self.font_size_slider.y),
+                ),
+                FocusRow::Shortcut => self.toggle_shortcut_capture.render_at(
+                    row_y(y),
+                    self.accent,
                     encoder,
                     view,
                     &self.widget_renderer,
@@ -2584,38 +3314,20 @@ impl SettingsPanel {
                     queue,
                     window_width,
                     window_height,
-                );
-                self.draw_row_bg(
-                    encoder,
-                    view,
-                    queue,
-                    row_y(self.recording_indicator_toggle.y),
-                    window_width,
-                    window_height,
-                );
-                self.recording_indicator_toggle.render_at(
-                    row_y(self.recording_indicator_toggle.y),
-                    encoder,
-                    view,
-                    &self.widget_renderer,
-                    &mut text_items,
-                    queue,
-                    window_width,
-                    window_height,
-                );
+                ),
+                FocusRow::Reset | FocusRow::Apply => {}
             }
         }
 
         // Apply and Reset buttons
+        if !matches!(
+            self.active_tab,
+            SettingsTab::History | SettingsTab::Integration
+        ) && !self.row_hidden(self.buttons_y_for_tab(self.active_tab))
         {
-            let buttons_y = self.buttons_y_for_tab(self.active_tab) + content_y_offset;
-            let w = default_width(window_width);
-            let btn_width = 80.0f32;
-            let btn_gap = 8.0f32;
-            let total_width = btn_width * 2.0 + btn_gap;
-            let start_x = WIDGET_X + (w - total_width) / 2.0;
-            let reset_btn_x = start_x;
-            let apply_btn_x = start_x + btn_width + btn_gap;
+            let buttons_y = row_y(self.buttons_y_for_tab(self.active_tab));
+            let btn_width = ACTION_BUTTON_WIDTH;
+            let (reset_btn_x, apply_btn_x) = action_buttons_x(window_width);
 
             // Reset button
             self.widget_renderer.draw_rounded_rect(
@@ -2631,25 +3343,19 @@ impl SettingsPanel {
                 window_width,
                 window_height,
             );
-            {
-                let label = "Reset";
-                let char_width = 6.5f32;
-                let text_width = label.len() as f32 * char_width;
-                let text_x = reset_btn_x + (btn_width - text_width) / 2.0;
-                let text_y = buttons_y + (APPLY_BUTTON_HEIGHT - 14.0) / 2.0;
-                text_items.push(TextItem {
-                    text: label.to_string(),
-                    x: text_x,
-                    y: text_y,
-                    scale: 1.0,
-                    color: [0.8, 0.8, 0.85, 1.0],
-                    max_width: btn_width,
-                });
-            }
+            text_items.push(TextItem {
+                text: tr("settings.reset").to_string(),
+                x: reset_btn_x,
+                y: buttons_y + (APPLY_BUTTON_HEIGHT - 14.0) / 2.0,
+                scale: 1.0,
+                color: [0.8, 0.8, 0.85, 1.0],
+                max_width: btn_width,
+                centered: true,
+            });
 
             // Apply button
             let (bg_color, text_color) = if self.has_pending_changes {
-                ([0.010, 0.787, 0.214, 1.0], [1.0, 1.0, 1.0, 1.0])
+                (self.accent, [1.0, 1.0, 1.0, 1.0])
             } else {
                 ([0.027, 0.027, 0.040, 0.5], [0.300, 0.300, 0.340, 1.0])
             };
@@ -2667,21 +3373,15 @@ impl SettingsPanel {
                 window_width,
                 window_height,
             );
-            {
-                let label = "Apply";
-                let char_width = 6.5f32;
-                let text_width = label.len() as f32 * char_width;
-                let text_x = apply_btn_x + (btn_width - text_width) / 2.0;
-                let text_y = buttons_y + (APPLY_BUTTON_HEIGHT - 14.0) / 2.0;
-                text_items.push(TextItem {
-                    text: label.to_string(),
-                    x: text_x,
-                    y: text_y,
-                    scale: 1.0,
-                    color: text_color,
-                    max_width: btn_width,
-                });
-            }
+            text_items.push(TextItem {
+                text: tr("settings.apply").to_string(),
+                x: apply_btn_x,
+                y: buttons_y + (APPLY_BUTTON_HEIGHT - 14.0) / 2.0,
+                scale: 1.0,
+                color: text_color,
+                max_width: btn_width,
+                centered: true,
+            });
         }
 
         // Flush all batched widget rects (row bgs, controls)
@@ -2715,8 +3415,9 @@ impl SettingsPanel {
                 let padding_x = 8.0f32;
                 let padding_y = 5.0f32;
                 let font_size = 10.5f32;
-                let char_width = font_size * 0.58;
-                let raw_text_width = tooltip.text.chars().count() as f32 * char_width;
+                let raw_text_width = self
+                    .batch_text_renderer
+                    .measure_text(tooltip.text, font_size / 10.0);
                 let max_tip_width = (window_width as f32 - 8.0).max(1.0);
                 let min_tip_width = 80.0f32.min(max_tip_width);
                 let tip_width = (raw_text_width + padding_x * 2.0)
@@ -2780,10 +3481,21 @@ impl SettingsPanel {
                     scale: font_size / 10.0,
                     color: [0.880, 0.880, 0.920, 1.0],
                     max_width: text_width,
+                    centered: false,
                 });
             }
         }
 
+        self.confirm_dialog.render(
+            encoder,
+            view,
+            &self.widget_renderer,
+            &mut overlay_text_items,
+            queue,
+            window_width,
+            window_height,
+        );
+
         // Flush overlay rects, then render only overlay text. Base row text was
         // already rendered before dropdowns so it cannot bleed through menus.
         self.widget_renderer
@@ -1,11 +1,36 @@
 use std::sync::Arc;
 
-use winit::dpi::PhysicalSize;
-use winit::keyboard::Key;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::MouseScrollDelta;
+use winit::keyboard::{Key, KeyCode, ModifiersState};
 use winit::window::Window;
 
+use super::help_overlay::HelpOverlay;
 use super::settings_panel::SettingsPanel;
-use crate::config::AppConfig;
+use super::widgets::DrawStatsReporter;
+use crate::config::{AppConfig, ConfigStore};
+use crate::config_watch::ConfigDiff;
+use crate::i18n::{tr, tr_args};
+use crate::model_catalog::{self, PendingDownload};
+use crate::profiles::{self, Profile};
+
+/// What a confirmed change publishes
+enum Commit {
+    /// Edited settings, written to the config file
+    Settings,
+    /// A profile switch, published without writing anything
+    Profile(Option<Profile>),
+}
+
+/// Settings held back until the user confirms a large model download or a
+/// profile switch that reloads the backend
+struct PendingApply {
+    previous: AppConfig,
+    config: AppConfig,
+    needs_reload: bool,
+    download: Option<PendingDownload>,
+    commit: Commit,
+}
 
 pub struct SettingsWindow {
     pub window: Arc<dyn Window>,
@@ -14,8 +39,12 @@ pub struct SettingsWindow {
     queue: wgpu::Queue,
     config: wgpu::SurfaceConfiguration,
     panel: SettingsPanel,
+    help_overlay: HelpOverlay,
     backend_command_tx: Option<tokio::sync::mpsc::UnboundedSender<speechcore::BackendCommand>>,
+    config_store: ConfigStore,
     applied_config: Option<AppConfig>,
+    pending_apply: Option<PendingApply>,
+    draw_stats: Option<DrawStatsReporter>,
 }
 
 impl SettingsWindow {
@@ -26,7 +55,7 @@ impl SettingsWindow {
         device: wgpu::Device,
         queue: wgpu::Queue,
         surface_format: wgpu::TextureFormat,
-        initial_config: &AppConfig,
+        config_store: ConfigStore,
         backend_command_tx: Option<tokio::sync::mpsc::UnboundedSender<speechcore::BackendCommand>>,
     ) -> Result<Self, String> {
         let window: Arc<dyn Window> = Arc::from(window);
@@ -75,7 +104,14 @@ impl SettingsWindow {
         panel.is_open = true;
         panel.animation_progress = 1.0;
         panel.animation_active = false;
-        panel.populate_from_config(initial_config);
+        let initial_config = config_store.snapshot();
+        panel.populate_from_config(&initial_config);
+        panel.set_profiles(
+            &profiles::available(&initial_config),
+            config_store.profile_name().as_deref(),
+        );
+
+        let help_overlay = HelpOverlay::new(&device, &queue, size, surface_format);
 
         Ok(Self {
             window,
@@ -84,8 +120,15 @@ impl SettingsWindow {
             queue,
             config,
             panel,
+            help_overlay,
             backend_command_tx,
+            config_store,
             applied_config: None,
+            pending_apply: None,
+            draw_stats: initial_config
+                .debug_config
+                .log_stats_enabled
+                .then(|| DrawStatsReporter::new("Settings")),
         })
     }
 
@@ -141,14 +184,53 @@ impl SettingsWindow {
             self.config.height,
         );
 
+        self.help_overlay.render(
+            &mut encoder,
+            &view,
+            &self.queue,
+            self.config.width,
+            self.config.height,
+            1.0,
+        );
+
         self.queue.submit(std::iter::once(encoder.finish()));
         output.present();
 
+        let mut frame_stats = self.panel.take_widget_draw_stats();
+        let help_stats = self.help_overlay.take_widget_draw_stats();
+        frame_stats.draw_calls += help_stats.draw_calls;
+        frame_stats.rects += help_stats.rects;
+        if let Some(reporter) = &mut self.draw_stats {
+            reporter.record_frame(frame_stats);
+        }
+
         if self.panel.needs_redraw() {
             self.window.request_redraw();
         }
     }
 
+    /// Redraw when state shown by the panel changed in the background
+    pub fn poll_background_changes(&self) {
+        if self.panel.integration_stale() {
+            self.window.request_redraw();
+        }
+    }
+
+    /// Show a configuration published from outside the window, unless the
+    /// user is in the middle of editing
+    pub fn config_reloaded(&mut self) {
+        if self.panel.has_pending_changes() || self.pending_apply.is_some() {
+            return;
+        }
+        let config = self.config_store.snapshot();
+        self.panel.populate_from_config(&config);
+        self.panel.set_profiles(
+            &profiles::available(&config),
+            self.config_store.profile_name().as_deref(),
+        );
+        self.window.request_redraw();
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
         if width == 0 || height == 0 {
             return;
@@ -157,14 +239,22 @@ impl SettingsWindow {
         self.config.height = height;
         self.surface.configure(&self.device, &self.config);
         self.panel.resize(PhysicalSize::new(width, height));
+        self.help_overlay.resize(PhysicalSize::new(width, height));
     }
 
     pub fn handle_click(&mut self, x: f32, y: f32) {
+        if self.help_overlay.is_visible() {
+            self.help_overlay.hide();
+            self.window.request_redraw();
+            return;
+        }
         self.panel
             .handle_click(x, y, self.config.width, self.config.height);
         if self.panel.take_apply_request() {
             self.apply_settings_changes();
         }
+        self.resolve_profile_change();
+        self.resolve_dialog_decision();
         self.window.request_redraw();
     }
 
@@ -175,11 +265,66 @@ impl SettingsWindow {
 
     pub fn handle_mouse_release(&mut self) {
         self.panel.handle_mouse_release();
+        self.resolve_profile_change();
+        self.window.request_redraw();
+    }
+
+    pub fn handle_scroll(&mut self, delta: MouseScrollDelta) {
+        // Treat pixel deltas as 20px lines, matching dropdown row granularity
+        let lines = match delta {
+            MouseScrollDelta::LineDelta(_, y) => -y,
+            MouseScrollDelta::PixelDelta(PhysicalPosition { y, .. }) => -y as f32 / 20.0,
+        };
+        if self.help_overlay.is_visible() {
+            self.help_overlay.scroll(lines * 20.0, self.config.height);
+        } else if !self.panel.handle_scroll(lines) {
+            return;
+        }
+        self.resolve_profile_change();
         self.window.request_redraw();
     }
 
     pub fn handle_key(&mut self, key: &Key, shift: bool) -> bool {
-        self.panel.handle_key(key, shift)
+        let handled = self.panel.handle_key(key, shift);
+        if self.panel.take_apply_request() {
+            self.apply_settings_changes();
+            self.window.request_redraw();
+        }
+        self.resolve_profile_change();
+        if self.pending_apply.is_some() {
+            self.resolve_dialog_decision();
+            self.window.request_redraw();
+        }
+        handled
+    }
+
+    pub fn is_capturing_shortcut(&self) -> bool {
+        self.panel.is_capturing_shortcut()
+    }
+
+    /// Key press while the toggle shortcut recorder waits for a combination
+    pub fn handle_shortcut_key(&mut self, code: KeyCode, modifiers: ModifiersState) {
+        if self.panel.handle_shortcut_key(code, modifiers) {
+            self.window.request_redraw();
+        }
+    }
+
+    pub fn take_help_request(&mut self) -> bool {
+        self.panel.take_help_request()
+    }
+
+    pub fn show_help(&mut self, sections: Vec<crate::help::HelpSection>) {
+        self.help_overlay.show(sections);
+        self.window.request_redraw();
+    }
+
+    pub fn hide_help(&mut self) {
+        self.help_overlay.hide();
+        self.window.request_redraw();
+    }
+
+    pub fn is_help_visible(&self) -> bool {
+        self.help_overlay.is_visible()
     }
 
     pub fn close_requested(&self) -> bool {
@@ -191,27 +336,196 @@ impl SettingsWindow {
     }
 
     fn apply_settings_changes(&mut self) {
-        let (mut app_config, _) = crate::config::read_app_config_with_path();
+        let mut app_config = self.config_store.snapshot();
         let previous_config = app_config.clone();
         let (any_changed, needs_reload) = self.panel.apply_pending_changes(&mut app_config);
-        if any_changed {
-            log_settings_changes(&previous_config, &app_config, needs_reload);
+        if !any_changed {
+            return;
+        }
 
-            if let Err(e) = crate::config::write_app_config(&app_config) {
-                eprintln!("Failed to write config: {}", e);
+        if needs_reload && app_config.general_config.offline {
+            if let Some(missing) = model_catalog::offline_missing_model(&app_config) {
+                tracing::warn!("{}; settings not applied", missing);
+                crate::toast::show("Model not downloaded (offline mode)");
+                self.panel.populate_from_config(&previous_config);
                 return;
             }
-            self.panel.populate_from_config(&app_config);
-            self.panel.clear_pending_changes();
-            self.applied_config = Some(app_config.clone());
-            if needs_reload {
-                if let Some(tx) = &self.backend_command_tx {
-                    let _ = tx.send(speechcore::BackendCommand::Reload {
-                        backend_config: app_config.backend_config.clone(),
-                        model_name: app_config.general_config.model.clone(),
+        }
+
+        if needs_reload {
+            if let Some(download) = model_catalog::pending_download(&app_config) {
+                let metered = model_catalog::network_is_metered();
+                let threshold = model_catalog::confirmation_threshold_mb(
+                    app_config.general_config.download_confirm_threshold_mb,
+                    metered,
+                );
+                if download.size_mb >= threshold {
+                    self.panel.confirm_download(&download, metered);
+                    self.pending_apply = Some(PendingApply {
+                        previous: previous_config,
+                        config: app_config,
+                        needs_reload,
+                        download: Some(download),
+                        commit: Commit::Settings,
                     });
+                    return;
+                }
+            }
+        }
+
+        self.commit_settings(previous_config, app_config, needs_reload, Commit::Settings);
+    }
+
+    /// Switch to the profile chosen in the panel, asking first when the
+    /// switch reloads the backend
+    fn resolve_profile_change(&mut self) {
+        let Some(name) = self.panel.take_profile_change() else {
+            return;
+        };
+        let active = self.config_store.profile_name();
+        if name == active {
+            return;
+        }
+        let previous_config = self.config_store.snapshot();
+        let switched = name
+            .as_deref()
+            .map(Profile::load)
+            .transpose()
+            .and_then(|profile| {
+                let config = self.config_store.profile_config(profile.as_ref())?;
+                Ok((profile, config))
+            });
+        let (profile, app_config) = match switched {
+            Ok(switched) => switched,
+            Err(e) => {
+                tracing::warn!("Profile not switched: {}", e);
+                crate::toast::show(tr("toast.profile_invalid"));
+                self.panel
+                    .set_profiles(&profiles::available(&previous_config), active.as_deref());
+                return;
+            }
+        };
+
+        let diff = ConfigDiff::between(&previous_config, &app_config);
+        if !diff.restart.is_empty() {
+            tracing::warn!(
+                "Profile {}: {} need a restart",
+                name.as_deref().unwrap_or("(none)"),
+                diff.restart.join(", ")
+            );
+        }
+        let needs_reload = !diff.backend.is_empty();
+        if !needs_reload {
+            self.commit_settings(previous_config, app_config, false, Commit::Profile(profile));
+            return;
+        }
+
+        if app_config.general_config.offline {
+            if let Some(missing) = model_catalog::offline_missing_model(&app_config) {
+                tracing::warn!("{}; profile not switched", missing);
+                crate::toast::show("Model not downloaded (offline mode)");
+                self.panel
+                    .set_profiles(&profiles::available(&previous_config), active.as_deref());
+                return;
+            }
+        }
+        let download = model_catalog::pending_download(&app_config);
+        self.panel
+            .confirm_profile_reload(name.as_deref(), download.as_ref());
+        self.pending_apply = Some(PendingApply {
+            previous: previous_config,
+            config: app_config,
+            needs_reload,
+            download,
+            commit: Commit::Profile(profile),
+        });
+    }
+
+    /// Finish or drop changes waiting on the confirmation dialog
+    fn resolve_dialog_decision(&mut self) {
+        let Some(confirmed) = self.panel.take_dialog_decision() else {
+            return;
+        };
+        let Some(pending) = self.pending_apply.take() else {
+            return;
+        };
+        if confirmed {
+            if let Some(download) = &pending.download {
+                tracing::info!("Model download confirmed: {}", download.describe());
+            }
+            self.commit_settings(
+                pending.previous,
+                pending.config,
+                pending.needs_reload,
+                pending.commit,
+            );
+        } else {
+            match &pending.download {
+                Some(download) => tracing::info!(
+                    "Model download declined: {}; settings not applied",
+                    download.describe()
+                ),
+                None => tracing::info!("Profile switch declined"),
+            }
+            self.panel.populate_from_config(&pending.previous);
+            self.panel.set_profiles(
+                &profiles::available(&pending.previous),
+                self.config_store.profile_name().as_deref(),
+            );
+        }
+    }
+
+    fn commit_settings(
+        &mut self,
+        previous_config: AppConfig,
+        app_config: AppConfig,
+        needs_reload: bool,
+        commit: Commit,
+    ) {
+        log_settings_changes(&previous_config, &app_config, needs_reload);
+
+        match commit {
+            Commit::Settings => {
+                if let Err(e) = self.config_store.replace(app_config.clone()) {
+                    tracing::warn!("Failed to write config: {}", e);
+                    return;
                 }
             }
+            Commit::Profile(profile) => {
+                let name = profile
+                    .as_ref()
+                    .map(|profile| profile.name().to_string())
+                    .unwrap_or_else(|| tr("settings.profile_none").to_string());
+                tracing::info!("Switched to profile {}", name);
+                crate::toast::show(tr_args("toast.profile_switched", &[("profile", &name)]));
+                self.config_store
+                    .switch_profile(profile, app_config.clone());
+            }
+        }
+        self.panel.populate_from_config(&app_config);
+        self.panel.set_profiles(
+            &profiles::available(&app_config),
+            self.config_store.profile_name().as_deref(),
+        );
+        self.panel.clear_pending_changes();
+        // The shortcut chain reads the new accelerator from the config store
+        if app_config.portal_config.manual_toggle_accelerator
+            != previous_config.portal_config.manual_toggle_accelerator
+        {
+            crate::portal_status::request(crate::portal_status::PortalCommand::RebindShortcuts);
+        }
+        self.applied_config = Some(app_config.clone());
+        if needs_reload {
+            if let Some(download) = model_catalog::pending_download(&app_config) {
+                tokio::spawn(model_catalog::verify_download_size(download));
+            }
+            if let Some(tx) = &self.backend_command_tx {
+                crate::gpu_probe::log_resolution(&app_config);
+                let _ = tx.send(speechcore::BackendCommand::Reload {
+                    backend_config: crate::gpu_probe::effective_backend_config(&app_config),
+                    model_name: app_config.general_config.model.clone(),
+                });
+            }
         }
     }
 }
@@ -231,6 +545,21 @@ fn log_settings_changes(previous: &AppConfig, next: &AppConfig, needs_reload: bo
             previous.general_config.model, next.general_config.model
         ));
     }
+    if previous.whisper_cpp_options.gpu_backend != next.whisper_cpp_options.gpu_backend {
+        changes.push(format!(
+            "gpu backend {} -> {}",
+            previous.whisper_cpp_options.gpu_backend, next.whisper_cpp_options.gpu_backend
+        ));
+    }
+    if previous.portal_config.manual_toggle_accelerator
+        != next.portal_config.manual_toggle_accelerator
+    {
+        changes.push(format!(
+            "toggle shortcut {} -> {}",
+            previous.portal_config.manual_toggle_accelerator,
+            next.portal_config.manual_toggle_accelerator
+        ));
+    }
     if previous.ui_config.typewriter_effect != next.ui_config.typewriter_effect {
         changes.push(format!(
             "typewriter {} -> {}",
@@ -243,13 +572,13 @@ fn log_settings_changes(previous: &AppConfig, next: &AppConfig, needs_reload: bo
     }
 
     if changes.is_empty() {
-        println!("Applying settings changes: backend reload queued");
+        tracing::info!("Applying settings changes: backend reload queued");
     } else if needs_reload {
-        println!(
+        tracing::info!(
             "Applying settings changes: {} (backend reload queued)",
             changes.join(", ")
         );
     } else {
-        println!("Applying settings changes: {}", changes.join(", "));
+        tracing::info!("Applying settings changes: {}", changes.join(", "));
     }
 }
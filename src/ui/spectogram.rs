@@ -1,4 +1,5 @@
 use rustfft::{num_complex::Complex, FftPlanner};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 use wgpu::{util::DeviceExt, Buffer, Device, Queue, RenderPipeline, TextureView};
@@ -8,6 +9,9 @@ use winit::dpi::PhysicalSize;
 #[derive(Debug, Clone)]
 pub struct SpectrogramConfig {
     pub skin: crate::config::SpectrogramSkin,
+    pub scale: crate::config::SpectrogramScale,
+    /// dB span below full scale mapped onto bar height with the mel and log scales
+    pub dynamic_range_db: f32,
     pub fft_size: usize,
     pub animation_speed: f32,
     pub min_amplitude: f32,
@@ -24,13 +28,17 @@ pub struct SpectrogramConfig {
     pub min_edge_factor: f32,
     pub edge_factor_range: f32,
     pub bar_spacing_multiplier: f32,
-    pub bar_color: [f32; 4],
+    /// Bar colors at low and full amplitude
+    pub bar_color_low: [f32; 4],
+    pub bar_color_high: [f32; 4],
 }
 
 impl Default for SpectrogramConfig {
     fn default() -> Self {
         Self {
             skin: crate::config::SpectrogramSkin::Bars,
+            scale: crate::config::SpectrogramScale::Linear,
+            dynamic_range_db: 60.0,
             fft_size: 512,
             animation_speed: 0.85,
             min_amplitude: 0.025,
@@ -47,7 +55,8 @@ impl Default for SpectrogramConfig {
             min_edge_factor: 0.75,
             edge_factor_range: 0.25,
             bar_spacing_multiplier: 1.0,
-            bar_color: [1.0, 1.0, 1.0, 1.0],
+            bar_color_low: [1.0, 1.0, 1.0, 1.0],
+            bar_color_high: [1.0, 1.0, 1.0, 1.0],
         }
     }
 }
@@ -55,6 +64,23 @@ impl Default for SpectrogramConfig {
 // Legacy constants for backward compatibility (will be removed in future)
 const FFT_SIZE: usize = 512;
 const METER_BOTTOM_PADDING_PX: f32 = 3.0;
+/// Lowest frequency shown by the log scale; speech has little energy below it
+const LOG_SCALE_MIN_HZ: f32 = 50.0;
+/// Largest bar movement per step that still counts as at rest
+const SETTLED_CHANGE: f32 = 1e-4;
+
+/// Frames whose bar targets were computed from new samples
+static COMPUTED_FRAMES: AtomicU64 = AtomicU64::new(0);
+/// Frames that reused the targets because no new samples had arrived
+static REUSED_FRAMES: AtomicU64 = AtomicU64::new(0);
+
+/// Spectrogram frames computed and reused since startup, for the run report
+pub fn frame_counts() -> (u64, u64) {
+    (
+        COMPUTED_FRAMES.load(Ordering::Relaxed),
+        REUSED_FRAMES.load(Ordering::Relaxed),
+    )
+}
 
 pub struct Spectrogram {
     // Configuration
@@ -75,12 +101,19 @@ pub struct Spectrogram {
     // Animation state
     last_update: Instant,
     is_speaking: bool,
+    // No bar moved noticeably in the last animation step
+    settled: bool,
+    // Samples the current targets were computed from; the audio buffer is
+    // refreshed far less often than frames are drawn
+    last_samples: Vec<f32>,
 
     // FFT resources
-    _fft: Arc<dyn rustfft::Fft<f32>>,
-    _fft_input: Vec<Complex<f32>>,
-    _fft_output: Vec<Complex<f32>>,
-    _window: Vec<f32>, // Hann window for better frequency resolution
+    fft: Arc<dyn rustfft::Fft<f32>>,
+    fft_buffer: Vec<Complex<f32>>,
+    window: Vec<f32>, // Hann window for better frequency resolution
+    window_sum: f32,
+    // FFT bin range per bar for the mel and log scales, rebuilt on resize
+    bin_ranges: Vec<(usize, usize)>,
 
     // Performance optimization: cached values
     bar_instance_template: Vec<BarInstanceTemplate>,
@@ -264,17 +297,23 @@ impl Spectrogram {
         // Setup FFT processing
         let mut planner = FftPlanner::new();
         let fft = planner.plan_fft_forward(FFT_SIZE);
-        let fft_input = vec![Complex { re: 0.0, im: 0.0 }; FFT_SIZE];
-        let fft_output = vec![Complex { re: 0.0, im: 0.0 }; FFT_SIZE];
+        let fft_buffer = vec![Complex { re: 0.0, im: 0.0 }; FFT_SIZE];
 
         // Pre-compute Hann window coefficients
         // The Hann window function is applied to audio samples to reduce spectral leakage
         // in the frequency domain. The formula is 0.5 * (1 - cos(2π * i / (N-1)))
-        let window = (0..FFT_SIZE)
+        let window: Vec<f32> = (0..FFT_SIZE)
             .map(|i| {
                 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / (FFT_SIZE - 1) as f32).cos())
             })
             .collect();
+        let window_sum = window.iter().sum();
+        let bin_ranges = frequency_bin_ranges(
+            config.scale,
+            num_bins,
+            FFT_SIZE,
+            crate::config::SAMPLE_RATE as f32,
+        );
 
         Self {
             config: SpectrogramConfig::default(),
@@ -288,10 +327,13 @@ impl Spectrogram {
             size,
             last_update: Instant::now(),
             is_speaking: false,
-            _fft: fft,
-            _fft_input: fft_input,
-            _fft_output: fft_output,
-            _window: window,
+            settled: false,
+            last_samples: Vec::new(),
+            fft,
+            fft_buffer,
+            window,
+            window_sum,
+            bin_ranges,
             bar_instance_template,
             cached_instances,
         }
@@ -325,6 +367,7 @@ impl Spectrogram {
 
                 self.cached_instances.clear();
                 self.cached_instances.reserve(optimal_bins);
+                self.rebuild_bin_ranges();
             }
         }
 
@@ -332,9 +375,20 @@ impl Spectrogram {
         self.update_instance_buffer();
     }
 
-    pub fn apply_ui_config(&mut self, ui_config: &crate::config::UiConfig) {
-        self.config.bar_color = ui_config.effective_spectrogram_color();
+    pub fn apply_ui_config(
+        &mut self,
+        ui_config: &crate::config::UiConfig,
+        theme: &super::theme::Theme,
+    ) {
+        self.last_samples.clear();
+        self.config.bar_color_low = theme.spectrogram_low;
+        self.config.bar_color_high = theme.spectrogram_high;
         self.config.skin = ui_config.spectrogram_skin;
+        self.config.dynamic_range_db = ui_config.spectrogram_dynamic_range_db.clamp(20.0, 120.0);
+        if self.config.scale != ui_config.spectrogram_scale {
+            self.config.scale = ui_config.spectrogram_scale;
+            self.rebuild_bin_ranges();
+        }
         apply_skin_config(&mut self.config);
         let new_template =
             create_bar_instance_template(self.bar_data.len(), self.size.width, &self.config);
@@ -342,6 +396,52 @@ impl Spectrogram {
         self.update_instance_buffer();
     }
 
+    fn rebuild_bin_ranges(&mut self) {
+        self.last_samples.clear();
+        self.bin_ranges = frequency_bin_ranges(
+            self.config.scale,
+            self.bar_data.len(),
+            FFT_SIZE,
+            crate::config::SAMPLE_RATE as f32,
+        );
+    }
+
+    /// Whether bars show FFT magnitudes instead of the sample envelope
+    fn uses_frequency_scale(&self) -> bool {
+        self.config.scale != crate::config::SpectrogramScale::Linear
+            && self.config.skin != crate::config::SpectrogramSkin::Waveform
+    }
+
+    /// Runs the FFT over the most recent samples and writes one dB-mapped level per bar
+    fn fill_frequency_levels(&mut self, audio_samples: &[f32], levels: &mut [f32]) {
+        let tail = &audio_samples[audio_samples.len().saturating_sub(FFT_SIZE)..];
+        for (i, slot) in self.fft_buffer.iter_mut().enumerate() {
+            let sample = tail.get(i).copied().unwrap_or(0.0);
+            *slot = Complex {
+                re: sample * self.window[i],
+                im: 0.0,
+            };
+        }
+        self.fft.process(&mut self.fft_buffer);
+
+        let range_db = self.config.dynamic_range_db;
+        let norm = 2.0 / self.window_sum.max(f32::EPSILON);
+        for (level, &(lo, hi)) in levels.iter_mut().zip(&self.bin_ranges) {
+            let magnitude = self.fft_buffer[lo..hi]
+                .iter()
+                .map(|bin| bin.norm())
+                .fold(0.0f32, f32::max);
+            let db = 20.0 * (magnitude * norm).max(1e-9).log10();
+            let scaled = ((db + range_db) / range_db).clamp(0.0, 1.0);
+            *level = (scaled * self.config.max_bar_height).max(self.config.min_amplitude);
+        }
+    }
+
+    /// Whether the bars have come to rest, so the overlay can stop drawing frames
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+
     /// Processes audio samples and updates the target bar heights
     ///
     /// This is a key performance-critical function that converts audio samples
@@ -356,9 +456,20 @@ impl Spectrogram {
             self.is_speaking = false;
             // Set target bars to zero for decay animation
             self.target_bar_data.fill(0.0);
+            self.last_samples.clear();
+            self.animate_bars();
+            return;
+        }
+
+        // Same buffer as last frame: the targets are still valid, only animate
+        if audio_samples == self.last_samples.as_slice() {
+            REUSED_FRAMES.fetch_add(1, Ordering::Relaxed);
             self.animate_bars();
             return;
         }
+        self.last_samples.clear();
+        self.last_samples.extend_from_slice(audio_samples);
+        COMPUTED_FRAMES.fetch_add(1, Ordering::Relaxed);
 
         let audio_energy = {
             let sample_step = (audio_samples.len() / 20).max(1);
@@ -378,34 +489,40 @@ impl Spectrogram {
         let mut smoothed_data = std::mem::take(&mut self.target_bar_data);
         smoothed_data.resize(num_bars, 0.0);
 
-        // Process audio samples to calculate bar heights. Each visual bar gets a
-        // small bucket of raw samples so skins can use peak/RMS dynamics without
-        // depending on one arbitrary sample.
-        for (i, value) in smoothed_data.iter_mut().enumerate().take(num_bars) {
-            let (start_idx, end_idx) = sample_range_for_bar(i, num_bars, audio_samples.len());
-            let stats = audio_bucket_stats(&audio_samples[start_idx..end_idx]);
-
-            *value = match self.config.skin {
-                crate::config::SpectrogramSkin::Waveform => {
-                    let sign = if stats.signed_peak >= 0.0 { 1.0 } else { -1.0 };
-                    let envelope = stats.rms * 0.70 + stats.peak_abs * 0.30;
-                    let shaped = (envelope * self.config.sample_amplification).sqrt()
-                        * self.config.scaled_amplification;
-                    (sign * shaped).clamp(-self.config.max_bar_height, self.config.max_bar_height)
-                }
-                crate::config::SpectrogramSkin::Meter => {
-                    let envelope = stats.peak_abs * 0.78 + stats.rms * 0.22;
-                    (envelope.sqrt() * self.config.scaled_amplification)
-                        .min(self.config.max_bar_height)
-                        .max(self.config.min_amplitude)
-                }
-                crate::config::SpectrogramSkin::Bars => {
-                    let envelope = stats.avg_abs * 0.55 + stats.rms * 0.30 + stats.peak_abs * 0.15;
-                    (envelope.sqrt() * self.config.scaled_amplification)
-                        .min(self.config.max_bar_height)
-                        .max(self.config.min_amplitude)
-                }
-            };
+        if self.uses_frequency_scale() {
+            self.fill_frequency_levels(audio_samples, &mut smoothed_data);
+        } else {
+            // Process audio samples to calculate bar heights. Each visual bar gets a
+            // small bucket of raw samples so skins can use peak/RMS dynamics without
+            // depending on one arbitrary sample.
+            for (i, value) in smoothed_data.iter_mut().enumerate().take(num_bars) {
+                let (start_idx, end_idx) = sample_range_for_bar(i, num_bars, audio_samples.len());
+                let stats = audio_bucket_stats(&audio_samples[start_idx..end_idx]);
+
+                *value = match self.config.skin {
+                    crate::config::SpectrogramSkin::Waveform => {
+                        let sign = if stats.signed_peak >= 0.0 { 1.0 } else { -1.0 };
+                        let envelope = stats.rms * 0.70 + stats.peak_abs * 0.30;
+                        let shaped = (envelope * self.config.sample_amplification).sqrt()
+                            * self.config.scaled_amplification;
+                        (sign * shaped)
+                            .clamp(-self.config.max_bar_height, self.config.max_bar_height)
+                    }
+                    crate::config::SpectrogramSkin::Meter => {
+                        let envelope = stats.peak_abs * 0.78 + stats.rms * 0.22;
+                        (envelope.sqrt() * self.config.scaled_amplification)
+                            .min(self.config.max_bar_height)
+                            .max(self.config.min_amplitude)
+                    }
+                    crate::config::SpectrogramSkin::Bars => {
+                        let envelope =
+                            stats.avg_abs * 0.55 + stats.rms * 0.30 + stats.peak_abs * 0.15;
+                        (envelope.sqrt() * self.config.scaled_amplification)
+                            .min(self.config.max_bar_height)
+                            .max(self.config.min_amplitude)
+                    }
+                };
+            }
         }
 
         // Apply smoothing without cloning the entire array
@@ -484,7 +601,9 @@ impl Spectrogram {
         let decay_factor = 1.0 - (idle_decay * capped_dt);
 
         // Update all bars in a single pass
+        let mut max_change = 0.0f32;
         for (i, bar) in self.bar_data.iter_mut().enumerate() {
+            let before = *bar;
             let target = self.target_bar_data[i];
             let diff = target - *bar;
 
@@ -509,7 +628,9 @@ impl Spectrogram {
             } else {
                 *bar = (*bar).clamp(self.config.min_amplitude, self.config.max_amplitude);
             }
+            max_change = max_change.max((*bar - before).abs());
         }
+        self.settled = max_change < SETTLED_CHANGE;
 
         self.update_instance_buffer();
     }
@@ -649,11 +770,16 @@ fn fill_bar_instances(
             }
         };
 
+        let level = adjusted_amplitude.abs();
+        let bar_color: [f32; 4] = std::array::from_fn(|i| {
+            config.bar_color_low[i]
+                + (config.bar_color_high[i] - config.bar_color_low[i]) * level.min(1.0)
+        });
         let color = [
-            config.bar_color[0],
-            config.bar_color[1],
-            config.bar_color[2],
-            adjusted_amplitude.abs().max(config.min_opacity) * config.bar_color[3],
+            bar_color[0],
+            bar_color[1],
+            bar_color[2],
+            level.max(config.min_opacity) * bar_color[3],
         ];
 
         instances.push(BarInstance {
@@ -729,6 +855,44 @@ fn sample_range_for_bar(index: usize, bar_count: usize, sample_count: usize) ->
     (start.min(sample_count), end.min(sample_count))
 }
 
+/// Splits the positive FFT bins into one `[lo, hi)` range per bar along the given scale.
+/// Ranges are never empty, so narrow low-frequency bars may repeat a bin.
+fn frequency_bin_ranges(
+    scale: crate::config::SpectrogramScale,
+    bar_count: usize,
+    fft_size: usize,
+    sample_rate: f32,
+) -> Vec<(usize, usize)> {
+    use crate::config::SpectrogramScale;
+
+    let bin_count = fft_size / 2;
+    if bar_count == 0 || bin_count < 2 {
+        return Vec::new();
+    }
+
+    let nyquist = sample_rate / 2.0;
+    let hz_per_bin = nyquist / bin_count as f32;
+    let to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let from_mel = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+
+    // Frequency at fraction `t` of the way along the axis
+    let edge_hz = |t: f32| match scale {
+        SpectrogramScale::Linear => t * nyquist,
+        SpectrogramScale::Mel => from_mel(t * to_mel(nyquist)),
+        SpectrogramScale::Log => LOG_SCALE_MIN_HZ * (nyquist / LOG_SCALE_MIN_HZ).powf(t),
+    };
+    // Skip the DC bin
+    let edge_bin = |t: f32| ((edge_hz(t) / hz_per_bin).round() as usize).clamp(1, bin_count);
+
+    (0..bar_count)
+        .map(|i| {
+            let lo = edge_bin(i as f32 / bar_count as f32).min(bin_count - 1);
+            let hi = edge_bin((i + 1) as f32 / bar_count as f32).max(lo + 1);
+            (lo, hi)
+        })
+        .collect()
+}
+
 fn audio_bucket_stats(samples: &[f32]) -> AudioBucketStats {
     if samples.is_empty() {
         return AudioBucketStats::default();
@@ -766,6 +930,27 @@ mod tests {
         assert_eq!(sample_range_for_bar(3, 4, 2), (1, 2));
     }
 
+    #[test]
+    fn frequency_bin_ranges_cover_spectrum_in_order() {
+        use crate::config::SpectrogramScale;
+
+        for scale in [SpectrogramScale::Mel, SpectrogramScale::Log] {
+            let ranges = frequency_bin_ranges(scale, 64, 512, 16000.0);
+
+            assert_eq!(ranges.len(), 64);
+            assert!(ranges
+                .iter()
+                .all(|&(lo, hi)| lo >= 1 && hi > lo && hi <= 256));
+            assert!(ranges.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+            assert_eq!(ranges.last().unwrap().1, 256);
+        }
+
+        // Perceptual scales give the low end more bars than a linear split
+        let linear = frequency_bin_ranges(SpectrogramScale::Linear, 64, 512, 16000.0);
+        let mel = frequency_bin_ranges(SpectrogramScale::Mel, 64, 512, 16000.0);
+        assert!(mel[16].0 < linear[16].0);
+    }
+
     #[test]
     fn audio_bucket_stats_keeps_dominant_signed_peak() {
         let stats = audio_bucket_stats(&[-0.2, 0.1, 0.35, -0.25]);
@@ -4,11 +4,17 @@ use parking_lot::RwLock;
 use wgpu::{self, util::DeviceExt};
 use winit::dpi::PhysicalSize;
 
+use super::render_scale;
 use super::text_renderer::TextRenderer;
 use crate::config::UiConfig;
+use crate::i18n::{tr, tr_args};
 use speechcore::{BackendStatus, BackendStatusState};
 
 const ERROR_FADE_DURATION_SECS: f64 = 10.0;
+/// Idle mic dot while the capture device is open
+const MIC_OPEN_COLOR: [f32; 4] = [1.0, 0.7, 0.2, 0.9];
+/// Idle mic dot once the capture device is closed
+const MIC_CLOSED_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 0.6];
 
 pub struct StatusBar {
     pipeline: wgpu::RenderPipeline,
@@ -24,6 +30,7 @@ pub struct StatusBar {
     show_recording_indicator: bool,
     pulse_phase: f32,
     last_update: std::time::Instant,
+    render_scale: f32,
 }
 
 impl StatusBar {
@@ -42,7 +49,7 @@ impl StatusBar {
 
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Status Bar Uniform Buffer"),
-            contents: bytemuck::cast_slice(&[0.0f32, -1.0f32, -1.0f32, 0.0f32]),
+            contents: bytemuck::cast_slice(&[0.0f32, -1.0f32, -1.0f32, -1.0f32]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
@@ -163,6 +170,7 @@ impl StatusBar {
             show_recording_indicator: ui_config.show_recording_indicator,
             pulse_phase: 0.0,
             last_update: std::time::Instant::now(),
+            render_scale: 1.0,
         }
     }
 
@@ -178,6 +186,25 @@ impl StatusBar {
         self.recording_timer_renderer.resize(size);
     }
 
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+        self.left_text_renderer.set_render_scale(scale);
+        self.right_text_renderer.set_render_scale(scale);
+        self.recording_dot_renderer.set_render_scale(scale);
+        self.recording_timer_renderer.set_render_scale(scale);
+    }
+
+    /// Whether the status line needs frames: recording pulse, loading sweep or
+    /// an error fading out
+    pub fn is_animating(&self) -> bool {
+        let status = self.status.read();
+        status.is_recording
+            || matches!(
+                status.state,
+                BackendStatusState::Loading(_) | BackendStatusState::Error(_)
+            )
+    }
+
     pub fn render(
         &mut self,
         encoder: &mut wgpu::CommandEncoder,
@@ -236,17 +263,22 @@ impl StatusBar {
 
             let (status_text, status_color) = if let Some(progress) = status.download_progress {
                 (
-                    format!("Downloading {:.0}%", progress * 100.0),
+                    tr_args(
+                        "status.downloading",
+                        &[("percent", &format!("{:.0}", progress * 100.0))],
+                    ),
                     [0.2, 0.7, 1.0, 0.9], // blue
                 )
             } else {
                 match &status.state {
-                    BackendStatusState::Ready => ("Ready".to_string(), [0.3, 0.85, 0.4, 0.9]),
+                    BackendStatusState::Ready => {
+                        (tr("status.ready").to_string(), [0.3, 0.85, 0.4, 0.9])
+                    }
                     BackendStatusState::Loading(msg) => {
                         let text = if msg.is_empty() {
-                            "Loading...".to_string()
+                            tr("status.loading").to_string()
                         } else {
-                            format!("Loading: {}", msg)
+                            tr_args("status.loading_detail", &[("detail", msg)])
                         };
                         (text, [1.0, 0.85, 0.2, 0.9])
                     }
@@ -276,6 +308,40 @@ impl StatusBar {
             )
         };
 
+        // A lost input, a muted mic, then sustained clipping take over the status line until cleared
+        let (status_text, status_color) = if let Some(loss) = crate::audio_devices::input_loss() {
+            let text = match loss {
+                crate::audio_devices::InputLoss::Disconnected => tr("status.mic_disconnected"),
+                crate::audio_devices::InputLoss::Stalled => tr("status.mic_stalled"),
+            };
+            (text.to_string(), [1.0, 0.3, 0.3, 1.0])
+        } else if crate::mic_mute::muted() {
+            (tr("status.mic_muted").to_string(), [1.0, 0.55, 0.15, 1.0])
+        } else if crate::clipping::warning_active() {
+            let mut warning = tr("status.clipping").to_string();
+            if crate::clipping::mixer_available() {
+                warning.push_str(&tr_args(
+                    "status.clipping_click",
+                    &[("db", &crate::clipping::VOLUME_STEP_DB)],
+                ));
+            }
+            (warning, [1.0, 0.55, 0.15, 1.0])
+        } else {
+            (status_text, status_color)
+        };
+
+        // Transient toasts (e.g. auto rule notices) temporarily replace the status text
+        let (status_text, status_color) = match crate::toast::current() {
+            Some(toast) => (toast, [0.45, 0.75, 1.0, 0.95]),
+            None => (status_text, status_color),
+        };
+
+        // Daily word goal progress, drawn along the top edge
+        let goal_progress = match crate::daily_words::today() {
+            Some((words, goal)) => (words as f32 / goal as f32).min(1.0),
+            None => -1.0,
+        };
+
         // Compute loading sweep phase (animated indeterminate bar)
         let loading_phase = if is_loading && download_progress < 0.0 {
             // Only show sweep when loading without a download progress bar
@@ -288,7 +354,7 @@ impl StatusBar {
         queue.write_buffer(
             &self.uniform_buffer,
             0,
-            bytemuck::cast_slice(&[error_tint, download_progress, loading_phase, 0.0f32]),
+            bytemuck::cast_slice(&[error_tint, download_progress, loading_phase, goal_progress]),
         );
 
         // Render background
@@ -308,13 +374,13 @@ impl StatusBar {
                 occlusion_query_set: None,
             });
 
-            render_pass.set_viewport(
+            render_scale::set_viewport(
+                &mut render_pass,
+                self.render_scale,
                 bar_x as f32,
                 bar_y as f32,
                 bar_width as f32,
                 bar_height as f32,
-                0.0,
-                1.0,
             );
 
             render_pass.set_pipeline(&self.pipeline);
@@ -432,6 +498,30 @@ impl StatusBar {
                     None,
                 );
             }
+        } else if self.show_recording_indicator {
+            // Mic state while idle: amber while the capture device is still open,
+            // grey once it is closed
+            let dot_text = "\u{25CF}";
+            let dot_color = if crate::audio_devices::device_open() {
+                MIC_OPEN_COLOR
+            } else {
+                MIC_CLOSED_COLOR
+            };
+            let dot_width = self
+                .recording_dot_renderer
+                .measure_text(dot_text, text_scale);
+            self.recording_dot_renderer.render_text(
+                view,
+                encoder,
+                dot_text,
+                bar_x as f32 + bar_width as f32 - dot_width - left_padding,
+                bar_y as f32 + text_y_offset,
+                text_scale,
+                dot_color,
+                bar_width,
+                bar_height,
+                None,
+            );
         }
     }
 }
@@ -2,7 +2,7 @@ struct StatusUniforms {
     error_tint: f32,
     download_progress: f32, // -1.0 = no download, 0.0-1.0 = progress
     loading_phase: f32,     // -1.0 = not loading, >= 0.0 = animated sweep phase
-    _pad: f32,
+    goal_progress: f32,     // -1.0 = no daily word goal, 0.0-1.0 = progress
 }
 
 struct VertexOutput {
@@ -56,5 +56,11 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         alpha = 0.6;
     }
 
+    // Daily word goal progress along the top edge, just under the text area (~2px)
+    if status.goal_progress >= 0.0 && in.uv.y > 0.89 && in.uv.x <= status.goal_progress {
+        color = vec3<f32>(0.01, 0.79, 0.21);
+        alpha = 0.85;
+    }
+
     return vec4<f32>(color, alpha);
 }
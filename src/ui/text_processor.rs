@@ -1,7 +1,61 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Layouts answered entirely from the cache
+static LAYOUT_HITS: AtomicU64 = AtomicU64::new(0);
+/// Layouts that only re-wrapped appended text
+static LAYOUT_APPENDS: AtomicU64 = AtomicU64::new(0);
+/// Layouts that re-wrapped the whole text
+static LAYOUT_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Layout cache hits, incremental appends and full re-wraps since startup
+pub fn layout_cache_counts() -> (u64, u64, u64) {
+    (
+        LAYOUT_HITS.load(Ordering::Relaxed),
+        LAYOUT_APPENDS.load(Ordering::Relaxed),
+        LAYOUT_MISSES.load(Ordering::Relaxed),
+    )
+}
+
+/// Word-wrap position: lines so far and characters on the current line
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WrapState {
+    lines: f32,
+    line_chars: f32,
+}
+
+impl WrapState {
+    const START: Self = Self {
+        lines: 1.0,
+        line_chars: 0.0,
+    };
+}
+
+/// Wrapped line count of the last laid out text
+#[derive(Debug, Default)]
+struct LayoutCache {
+    text: String,
+    chars_per_line: f32,
+    /// Byte offset of the last word, which appended text may still extend
+    resume_at: usize,
+    /// Wrap state just before that word
+    resume_state: Option<WrapState>,
+    line_count: f32,
+}
+
+/// Cleaned form of the last transcript
+#[derive(Debug, Default)]
+struct CleanCache {
+    raw: String,
+    /// Collapsed text; may end in a single space that is trimmed on output
+    cleaned: String,
+}
+
 pub struct TextProcessor {
     pub char_width: f32,
     pub line_height: f32,
     pub buffer_lines: f32,
+    clean_cache: CleanCache,
+    layout_cache: LayoutCache,
 }
 
 impl TextProcessor {
@@ -10,6 +64,8 @@ impl TextProcessor {
             char_width,
             line_height,
             buffer_lines,
+            clean_cache: CleanCache::default(),
+            layout_cache: LayoutCache::default(),
         }
     }
 
@@ -22,31 +78,29 @@ impl TextProcessor {
     }
 
     /// Clean up whitespace in the text, removing consecutive spaces
-    pub fn clean_whitespace(&self, text: &str) -> String {
-        let text = text.trim();
-
-        // Replace consecutive whitespace with a single space
-        let mut result = String::with_capacity(text.len());
-        let mut last_was_whitespace = false;
-
-        for c in text.chars() {
-            if c.is_whitespace() {
-                if !last_was_whitespace {
-                    result.push(' ');
-                    last_was_whitespace = true;
+    ///
+    /// The result is cached; when the transcript only grew, just the appended
+    /// part is cleaned.
+    pub fn clean_whitespace(&mut self, text: &str) -> &str {
+        let cache = &mut self.clean_cache;
+        if text != cache.raw {
+            let appended = match text.strip_prefix(cache.raw.as_str()) {
+                Some(tail) if !cache.raw.is_empty() => tail,
+                _ => {
+                    cache.cleaned.clear();
+                    text
                 }
-            } else {
-                result.push(c);
-                last_was_whitespace = false;
-            }
+            };
+            push_collapsed(&mut cache.cleaned, appended);
+            cache.raw.clear();
+            cache.raw.push_str(text);
         }
-
-        result
+        cache.cleaned.trim_end()
     }
 
     /// Calculate the number of lines and whether scrolling is needed
     pub fn calculate_layout(
-        &self,
+        &mut self,
         text: &str,
         viewport_width: f32,
         visible_height: f32,
@@ -57,20 +111,7 @@ impl TextProcessor {
         // Calculate visible lines in the viewport
         let visible_lines = visible_height / self.line_height;
 
-        // Count words and estimate line breaks
-        let mut line_count = 1.0;
-        let mut current_line_chars = 0.0;
-
-        for word in text.split_whitespace() {
-            let word_len = word.len() as f32;
-
-            if current_line_chars + word_len + 1.0 > chars_per_line {
-                line_count += 1.0;
-                current_line_chars = word_len + 1.0;
-            } else {
-                current_line_chars += word_len + 1.0;
-            }
-        }
+        let line_count = self.wrapped_line_count(text, chars_per_line);
 
         // Determine if scrollbar is needed
         let need_scrollbar = line_count > visible_lines + self.buffer_lines;
@@ -89,6 +130,110 @@ impl TextProcessor {
             visible_lines,
         }
     }
+
+    /// Line count of `text` wrapped at `chars_per_line`, reusing the last wrap
+    /// when the text is unchanged or only appended to
+    fn wrapped_line_count(&mut self, text: &str, chars_per_line: f32) -> f32 {
+        let cache = &mut self.layout_cache;
+        let same_width = cache.chars_per_line == chars_per_line;
+        if same_width && text == cache.text {
+            LAYOUT_HITS.fetch_add(1, Ordering::Relaxed);
+            return cache.line_count;
+        }
+
+        let resume = cache
+            .resume_state
+            .filter(|_| same_width && text.starts_with(&cache.text[..cache.resume_at]));
+        let (start, state) = match resume {
+            Some(state) => {
+                LAYOUT_APPENDS.fetch_add(1, Ordering::Relaxed);
+                (cache.resume_at, state)
+            }
+            None => {
+                LAYOUT_MISSES.fetch_add(1, Ordering::Relaxed);
+                (0, WrapState::START)
+            }
+        };
+
+        let (end_state, resume_at, resume_state) = wrap_words(text, start, state, chars_per_line);
+        cache.text.clear();
+        cache.text.push_str(text);
+        cache.chars_per_line = chars_per_line;
+        cache.resume_at = resume_at;
+        cache.resume_state = Some(resume_state);
+        cache.line_count = end_state.lines;
+        end_state.lines
+    }
+}
+
+/// Append `text` to `out` with runs of whitespace collapsed to one space and
+/// leading whitespace dropped
+fn push_collapsed(out: &mut String, text: &str) {
+    let mut last_was_whitespace = out.is_empty() || out.ends_with(' ');
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_was_whitespace {
+                out.push(' ');
+                last_was_whitespace = true;
+            }
+        } else {
+            out.push(c);
+            last_was_whitespace = false;
+        }
+    }
+}
+
+/// Word-wrap `text[start..]` from `state`
+///
+/// Returns the final state plus the offset of the last word and the state
+/// before it, where wrapping can resume once more text is appended.
+fn wrap_words(
+    text: &str,
+    start: usize,
+    mut state: WrapState,
+    chars_per_line: f32,
+) -> (WrapState, usize, WrapState) {
+    let mut resume_at = start;
+    let mut resume_state = state;
+    let mut in_word = false;
+
+    for (offset, c) in text[start..].char_indices() {
+        let offset = start + offset;
+        if c.is_whitespace() {
+            if in_word {
+                state = place_word(state, offset - resume_at, chars_per_line);
+                in_word = false;
+            }
+        } else if !in_word {
+            resume_at = offset;
+            resume_state = state;
+            in_word = true;
+        }
+    }
+    if in_word {
+        state = place_word(state, text.len() - resume_at, chars_per_line);
+    } else {
+        resume_at = text.len();
+        resume_state = state;
+    }
+
+    (state, resume_at, resume_state)
+}
+
+/// Place a word of `len` bytes, breaking the line when it does not fit
+fn place_word(state: WrapState, len: usize, chars_per_line: f32) -> WrapState {
+    let word_len = len as f32;
+    if state.line_chars + word_len + 1.0 > chars_per_line {
+        WrapState {
+            lines: state.lines + 1.0,
+            line_chars: word_len + 1.0,
+        }
+    } else {
+        WrapState {
+            lines: state.lines,
+            line_chars: state.line_chars + word_len + 1.0,
+        }
+    }
 }
 
 pub struct TextLayoutInfo {
@@ -97,3 +242,47 @@ pub struct TextLayoutInfo {
     pub max_scroll_offset: f32,
     pub visible_lines: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_whitespace_appends_match_a_full_clean() {
+        let mut processor = TextProcessor::new(8.0, 20.0, 4.0);
+        let steps = [
+            "  hello",
+            "  hello   wor",
+            "  hello   world  ",
+            "  hello   world  again\n",
+        ];
+        for step in steps {
+            let incremental = processor.clean_whitespace(step).to_string();
+            let full = TextProcessor::new(8.0, 20.0, 4.0)
+                .clean_whitespace(step)
+                .to_string();
+            assert_eq!(incremental, full);
+        }
+        assert_eq!(processor.clean_whitespace("hello"), "hello");
+    }
+
+    #[test]
+    fn incremental_layout_matches_a_full_layout() {
+        let mut processor = TextProcessor::new(8.0, 20.0, 4.0);
+        let mut text = String::new();
+        for word in "the quick brown fox jumps over the lazy dog again and again".split(' ') {
+            // Grow the last word one character at a time, as live transcripts do
+            for c in word.chars() {
+                text.push(c);
+                let incremental = processor.calculate_layout(&text, 120.0, 100.0);
+                let full = TextProcessor::new(8.0, 20.0, 4.0).calculate_layout(&text, 120.0, 100.0);
+                assert_eq!(incremental.line_count, full.line_count, "{text:?}");
+            }
+            text.push(' ');
+        }
+
+        let wide = processor.calculate_layout(&text, 400.0, 100.0);
+        let full = TextProcessor::new(8.0, 20.0, 4.0).calculate_layout(&text, 400.0, 100.0);
+        assert_eq!(wide.line_count, full.line_count);
+    }
+}
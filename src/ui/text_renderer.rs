@@ -19,6 +19,7 @@ pub struct TextRenderer {
     device: Arc<Device>,
     queue: Arc<Queue>,
     size: PhysicalSize<u32>,
+    render_scale: f32,
     _surface_format: wgpu::TextureFormat,
     _cache_ref: Cache,
     viewport: Viewport,
@@ -79,6 +80,7 @@ impl TextRenderer {
             device,
             queue,
             size,
+            render_scale: 1.0,
             _surface_format: surface_format,
             _cache_ref: cache_ref,
             viewport,
@@ -98,16 +100,26 @@ impl TextRenderer {
         );
 
         // Update the viewport resolution
-        self.viewport.update(
-            &self.queue,
-            Resolution {
-                width: size.width,
-                height: size.height,
-            },
-        );
+        self.viewport.update(&self.queue, self.resolution());
         self.cached_layout = None;
     }
 
+    /// Render into a target drawn at `scale` times the logical size.
+    ///
+    /// Positions and wrapping stay logical; glyphs are rasterized at the pixel
+    /// size they occupy in the scaled target, so they are not minified from
+    /// full-size bitmaps and the atlas shrinks with the scale.
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+        self.viewport.update(&self.queue, self.resolution());
+    }
+
+    fn resolution(&self) -> Resolution {
+        let (width, height) =
+            super::render_scale::scaled_size(self.size.width, self.size.height, self.render_scale);
+        Resolution { width, height }
+    }
+
     /// Measure the rendered width of text at a given scale (single line, no wrapping)
     pub fn measure_text(&mut self, text: &str, scale: f32) -> f32 {
         if text.is_empty() {
@@ -197,22 +209,27 @@ impl TextRenderer {
             });
         }
 
-        self.viewport.update(
-            &self.queue,
-            Resolution {
-                width: self.size.width,
-                height: self.size.height,
-            },
-        );
+        let resolution = self.resolution();
+        self.viewport.update(&self.queue, resolution);
 
-        let (clip_x, clip_y, clip_w, clip_h) =
-            clip_rect.unwrap_or((0, 0, self.size.width, self.size.height));
+        // Clip in target pixels, kept inside the target
+        let (clip_x, clip_y, clip_w, clip_h) = super::render_scale::scale_rect(
+            clip_rect.unwrap_or((0, 0, self.size.width, self.size.height)),
+            self.render_scale,
+        );
+        let clip_x = clip_x.min(resolution.width);
+        let clip_y = clip_y.min(resolution.height);
+        let clip_w = clip_w.min(resolution.width - clip_x);
+        let clip_h = clip_h.min(resolution.height - clip_y);
+        if clip_w == 0 || clip_h == 0 {
+            return;
+        }
 
         let text_area = TextArea {
             buffer: &self.buffer,
-            left: x,
-            top: y,
-            scale: 1.0,
+            left: x * self.render_scale,
+            top: y * self.render_scale,
+            scale: self.render_scale,
             bounds: TextBounds {
                 left: clip_x as i32,
                 top: clip_y as i32,
@@ -1,12 +1,14 @@
 use wgpu::{self, util::DeviceExt};
 use winit::dpi::PhysicalSize;
 
+use super::render_scale;
 use super::text_renderer::TextRenderer;
 
 pub struct TextWindow {
     pipeline: wgpu::RenderPipeline,
     vertices: wgpu::Buffer,
     text_renderer: TextRenderer,
+    render_scale: f32,
 }
 
 impl TextWindow {
@@ -90,6 +92,7 @@ impl TextWindow {
             pipeline,
             vertices,
             text_renderer,
+            render_scale: 1.0,
         }
     }
 
@@ -97,6 +100,11 @@ impl TextWindow {
         self.text_renderer.resize(size);
     }
 
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+        self.text_renderer.set_render_scale(scale);
+    }
+
     pub fn render(
         &mut self,
         encoder: &mut wgpu::CommandEncoder,
@@ -126,13 +134,13 @@ impl TextWindow {
             occlusion_query_set: None,
         });
 
-        render_pass.set_viewport(
+        render_scale::set_viewport(
+            &mut render_pass,
+            self.render_scale,
             0.0,                             // x position
             0.0,                             // y position
             text_area_width as f32,          // width
             (text_area_height - gap) as f32, // height
-            0.0,                             // min depth
-            1.0,                             // max depth
         );
 
         render_pass.set_pipeline(&self.pipeline);
@@ -185,13 +193,13 @@ impl TextWindow {
             occlusion_query_set: None,
         });
 
-        render_pass.set_viewport(
+        render_scale::set_viewport(
+            &mut render_pass,
+            self.render_scale,
             0.0,                             // x position
             0.0,                             // y position
             text_area_width as f32,          // width
             (text_area_height - gap) as f32, // height
-            0.0,                             // min depth
-            1.0,                             // max depth
         );
 
         render_pass.set_pipeline(&self.pipeline);
@@ -1,6 +1,8 @@
-// Uniform for hover animation
+// Uniform for hover animation and theme colors (see render_pipeline::HoverUniforms)
 struct HoverUniforms {
     opacity_multiplier: f32,
+    panel_color: vec4<f32>,
+    button_color: vec4<f32>,
 }
 
 @group(0) @binding(0)
@@ -18,5 +20,5 @@ fn fs_main() -> @location(0) vec4<f32> {
     let target_opacity = 0.9;
     let animated_opacity = base_opacity + (target_opacity - base_opacity) * hover.opacity_multiplier;
 
-    return vec4<f32>(0.0, 0.0, 0.0, animated_opacity);
+    return vec4<f32>(hover.panel_color.rgb, animated_opacity * hover.panel_color.a);
 }
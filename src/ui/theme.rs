@@ -0,0 +1,88 @@
+//! Overlay colors resolved from `[theme]`.
+
+use crate::config::{ThemeConfig, ThemePreset, UiConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub text_idle: [f32; 4],
+    pub text_speaking: [f32; 4],
+    pub text_low_confidence: [f32; 4],
+    pub panel_bg: [f32; 4],
+    pub button_bg: [f32; 4],
+    pub accent: [f32; 4],
+    pub spectrogram_low: [f32; 4],
+    pub spectrogram_high: [f32; 4],
+}
+
+impl Theme {
+    pub fn dark(ui_config: &UiConfig) -> Self {
+        let spectrogram = ui_config.effective_spectrogram_color();
+        Self {
+            text_idle: ui_config.effective_idle_color(),
+            text_speaking: ui_config.effective_speaking_color(),
+            text_low_confidence: [0.60, 0.60, 0.65, 1.0],
+            panel_bg: [0.0, 0.0, 0.0, 1.0],
+            button_bg: [0.2, 0.2, 0.2, 0.15],
+            accent: [0.010, 0.787, 0.214, 1.0],
+            spectrogram_low: spectrogram,
+            spectrogram_high: spectrogram,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            text_idle: [0.10, 0.10, 0.12, 1.0],
+            text_speaking: [0.0, 0.42, 0.24, 1.0],
+            text_low_confidence: [0.45, 0.45, 0.50, 1.0],
+            panel_bg: [0.97, 0.97, 0.98, 1.0],
+            button_bg: [0.15, 0.15, 0.18, 0.8],
+            accent: [0.0, 0.55, 0.17, 1.0],
+            spectrogram_low: [0.35, 0.35, 0.40, 1.0],
+            spectrogram_high: [0.0, 0.50, 0.30, 1.0],
+        }
+    }
+
+    pub fn from_config(theme: &ThemeConfig, ui_config: &UiConfig) -> Self {
+        let preset = match theme.preset {
+            ThemePreset::Dark => Self::dark(ui_config),
+            ThemePreset::Light => Self::light(),
+        };
+        Self {
+            text_idle: theme.text_idle.unwrap_or(preset.text_idle),
+            text_speaking: theme.text_speaking.unwrap_or(preset.text_speaking),
+            text_low_confidence: theme
+                .text_low_confidence
+                .unwrap_or(preset.text_low_confidence),
+            panel_bg: theme.panel_bg.unwrap_or(preset.panel_bg),
+            button_bg: theme.button_bg.unwrap_or(preset.button_bg),
+            accent: theme.accent.unwrap_or(preset.accent),
+            spectrogram_low: theme.spectrogram_low.unwrap_or(preset.spectrogram_low),
+            spectrogram_high: theme.spectrogram_high.unwrap_or(preset.spectrogram_high),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_apply_on_top_of_the_preset() {
+        let ui_config = UiConfig::default();
+        let theme = ThemeConfig {
+            preset: ThemePreset::Light,
+            accent: Some([1.0, 0.0, 0.0, 1.0]),
+            ..ThemeConfig::default()
+        };
+        let resolved = Theme::from_config(&theme, &ui_config);
+        assert_eq!(resolved.accent, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(resolved.text_idle, Theme::light().text_idle);
+
+        let dark = Theme::from_config(&ThemeConfig::default(), &ui_config);
+        assert_eq!(dark.text_idle, ui_config.idle_color);
+        assert_eq!(
+            dark.spectrogram_high,
+            ui_config.effective_spectrogram_color()
+        );
+    }
+}
@@ -140,7 +140,11 @@ impl TimerBadge {
 
         if let Some(start_time) = self.recording_start {
             let elapsed = start_time.elapsed();
-            let time_str = Self::format_duration(elapsed);
+            let mut time_str = Self::format_duration(elapsed);
+            if let Some(remaining) = crate::session_timeout::remaining() {
+                time_str.push_str(" · ");
+                time_str.push_str(&crate::session_timeout::countdown_label(remaining));
+            }
 
             // Calculate text dimensions and position
             let font_size = 16.0;
@@ -174,7 +178,7 @@ impl TimerBadge {
             }
 
             let Some(timer_buffer) = self.cached_timer_buffer.as_ref() else {
-                eprintln!("Timer badge buffer missing after cache update");
+                tracing::warn!("Timer badge buffer missing after cache update");
                 return;
             };
 
@@ -245,7 +249,7 @@ impl TimerBadge {
                         custom_glyphs: &[],
                     });
                 } else {
-                    eprintln!("Timer badge indicator buffer missing after cache update");
+                    tracing::warn!("Timer badge indicator buffer missing after cache update");
                 }
             }
 
@@ -274,7 +278,7 @@ impl TimerBadge {
                 text_areas,
                 &mut self.swash_cache,
             ) {
-                eprintln!("Failed to prepare timer text: {}", e);
+                tracing::warn!("Failed to prepare timer text: {}", e);
                 return;
             }
 
@@ -297,7 +301,7 @@ impl TimerBadge {
                 self.text_renderer
                     .render(&self.text_atlas, &self.viewport, &mut render_pass)
             {
-                eprintln!("Failed to render timer text: {}", e);
+                tracing::warn!("Failed to render timer text: {}", e);
             }
 
             drop(render_pass);
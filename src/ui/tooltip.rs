@@ -78,6 +78,7 @@ pub struct Tooltip {
     text_renderer: glyphon::TextRenderer,
     _cache: glyphon::Cache,
     viewport: glyphon::Viewport,
+    render_scale: f32,
 }
 
 impl Tooltip {
@@ -253,9 +254,15 @@ impl Tooltip {
             text_renderer,
             _cache: cache,
             viewport,
+            render_scale: 1.0,
         }
     }
 
+    /// Rasterize the tooltip text for a target drawn at `scale` times the window size
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+    }
+
     /// Get tooltip text for a button type
     fn get_tooltip_text(button_type: ButtonType) -> &'static str {
         match button_type {
@@ -525,31 +532,34 @@ impl Tooltip {
         use glyphon::{Color, TextArea, TextBounds};
 
         let Some(buffer) = self.cached_buffers.get(&button_type) else {
-            eprintln!("Tooltip text buffer missing for {:?}", button_type);
+            tracing::warn!("Tooltip text buffer missing for {:?}", button_type);
             return;
         };
 
+        let scale = self.render_scale;
         let text_area = TextArea {
             buffer,
-            left: text_x,
-            top: text_y,
-            scale: 1.0,
+            left: text_x * scale,
+            top: text_y * scale,
+            scale,
             bounds: TextBounds {
-                left: text_x as i32,
-                top: text_y as i32,
-                right: (text_x + text_width) as i32,
-                bottom: (text_y + text_height) as i32,
+                left: (text_x * scale) as i32,
+                top: (text_y * scale) as i32,
+                right: ((text_x + text_width) * scale).ceil() as i32,
+                bottom: ((text_y + text_height) * scale).ceil() as i32,
             },
             default_color: Color::rgb(255, 255, 255),
             custom_glyphs: &[],
         };
 
         // Update viewport size
+        let (target_width, target_height) =
+            super::render_scale::scaled_size(window_width, window_height, scale);
         self.viewport.update(
             &self.queue,
             glyphon::Resolution {
-                width: window_width,
-                height: window_height,
+                width: target_width,
+                height: target_height,
             },
         );
 
@@ -563,7 +573,7 @@ impl Tooltip {
             [text_area],
             &mut self.swash_cache,
         ) {
-            eprintln!("Failed to prepare tooltip text: {}", e);
+            tracing::warn!("Failed to prepare tooltip text: {}", e);
             return;
         }
 
@@ -587,7 +597,7 @@ impl Tooltip {
             self.text_renderer
                 .render(&self.text_atlas, &self.viewport, &mut render_pass)
         {
-            eprintln!("Failed to render tooltip text: {}", e);
+            tracing::warn!("Failed to render tooltip text: {}", e);
         }
 
         drop(render_pass);
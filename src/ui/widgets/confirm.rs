@@ -0,0 +1,231 @@
+use super::widget_renderer::WidgetRenderer;
+use crate::ui::batch_text_renderer::TextItem;
+
+const DIALOG_PADDING: f32 = 10.0;
+const BUTTON_WIDTH: f32 = 80.0;
+const BUTTON_HEIGHT: f32 = 24.0;
+const BUTTON_GAP: f32 = 8.0;
+const LINE_HEIGHT: f32 = 14.0;
+
+/// Modal yes/no dialog drawn over a panel.
+///
+/// Enter or the confirm button accepts, Escape or the cancel button declines;
+/// clicks elsewhere inside the dialog are swallowed.
+pub struct ConfirmDialog {
+    visible: bool,
+    title: String,
+    lines: Vec<String>,
+    confirm_label: String,
+    decision: Option<bool>,
+}
+
+impl Default for ConfirmDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfirmDialog {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            title: String::new(),
+            lines: Vec::new(),
+            confirm_label: "OK".to_string(),
+            decision: None,
+        }
+    }
+
+    pub fn show(&mut self, title: &str, lines: Vec<String>, confirm_label: &str) {
+        self.title = title.to_string();
+        self.lines = lines;
+        self.confirm_label = confirm_label.to_string();
+        self.decision = None;
+        self.visible = true;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// The user's answer, once (true = confirmed)
+    pub fn take_decision(&mut self) -> Option<bool> {
+        self.decision.take()
+    }
+
+    fn decide(&mut self, confirmed: bool) {
+        self.visible = false;
+        self.decision = Some(confirmed);
+    }
+
+    fn dialog_rect(&self, window_width: u32, window_height: u32) -> (f32, f32, f32, f32) {
+        let width = (window_width as f32 - 40.0).min(320.0);
+        let height =
+            DIALOG_PADDING * 3.0 + LINE_HEIGHT * (self.lines.len() as f32 + 1.5) + BUTTON_HEIGHT;
+        let x = (window_width as f32 - width) / 2.0;
+        let y = ((window_height as f32 - height) / 2.0).max(4.0);
+        (x, y, width, height)
+    }
+
+    /// (cancel_x, confirm_x, buttons_y)
+    fn button_positions(&self, window_width: u32, window_height: u32) -> (f32, f32, f32) {
+        let (x, y, width, height) = self.dialog_rect(window_width, window_height);
+        let start_x = x + (width - (BUTTON_WIDTH * 2.0 + BUTTON_GAP)) / 2.0;
+        let buttons_y = y + height - DIALOG_PADDING - BUTTON_HEIGHT;
+        (start_x, start_x + BUTTON_WIDTH + BUTTON_GAP, buttons_y)
+    }
+
+    /// Handle a click while visible; always consumes it (the dialog is modal)
+    pub fn handle_click(&mut self, x: f32, y: f32, window_width: u32, window_height: u32) -> bool {
+        if !self.visible {
+            return false;
+        }
+        let (cancel_x, confirm_x, buttons_y) = self.button_positions(window_width, window_height);
+        if y >= buttons_y && y <= buttons_y + BUTTON_HEIGHT {
+            if x >= cancel_x && x <= cancel_x + BUTTON_WIDTH {
+                self.decide(false);
+            } else if x >= confirm_x && x <= confirm_x + BUTTON_WIDTH {
+                self.decide(true);
+            }
+        }
+        true
+    }
+
+    /// Enter confirms, Escape cancels; other keys are swallowed while visible
+    pub fn handle_key(&mut self, key: &winit::keyboard::Key) -> bool {
+        use winit::keyboard::{Key, NamedKey};
+
+        if !self.visible {
+            return false;
+        }
+        match key {
+            Key::Named(NamedKey::Enter) => self.decide(true),
+            Key::Named(NamedKey::Escape) => self.decide(false),
+            _ => {}
+        }
+        true
+    }
+
+    pub fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        widget_renderer: &WidgetRenderer,
+        text_items: &mut Vec<TextItem>,
+        queue: &wgpu::Queue,
+        window_width: u32,
+        window_height: u32,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        // Dim the panel behind the dialog
+        widget_renderer.draw_rounded_rect(
+            encoder,
+            view,
+            queue,
+            0.0,
+            0.0,
+            window_width as f32,
+            window_height as f32,
+            0.0,
+            [0.0, 0.0, 0.0, 0.55],
+            window_width,
+            window_height,
+        );
+
+        let (x, y, width, height) = self.dialog_rect(window_width, window_height);
+        widget_renderer.draw_rounded_rect(
+            encoder,
+            view,
+            queue,
+            x,
+            y,
+            width,
+            height,
+            8.0,
+            [0.190, 0.190, 0.230, 0.98],
+            window_width,
+            window_height,
+        );
+        widget_renderer.draw_rounded_rect(
+            encoder,
+            view,
+            queue,
+            x + 1.0,
+            y + 1.0,
+            width - 2.0,
+            height - 2.0,
+            7.0,
+            [0.040, 0.040, 0.055, 0.98],
+            window_width,
+            window_height,
+        );
+
+        let text_width = width - DIALOG_PADDING * 2.0;
+        let mut line_y = y + DIALOG_PADDING;
+        text_items.push(TextItem {
+            text: self.title.clone(),
+            x: x + DIALOG_PADDING,
+            y: line_y,
+            scale: 1.0,
+            color: [0.885, 0.885, 0.930, 1.0],
+            max_width: text_width,
+            centered: false,
+        });
+        line_y += LINE_HEIGHT * 1.5;
+        for line in &self.lines {
+            text_items.push(TextItem {
+                text: line.clone(),
+                x: x + DIALOG_PADDING,
+                y: line_y,
+                scale: 0.9,
+                color: [0.604, 0.604, 0.670, 1.0],
+                max_width: text_width,
+                centered: false,
+            });
+            line_y += LINE_HEIGHT;
+        }
+
+        let (cancel_x, confirm_x, buttons_y) = self.button_positions(window_width, window_height);
+        let buttons = [
+            (
+                cancel_x,
+                "Cancel",
+                [0.15, 0.15, 0.2, 1.0],
+                [0.8, 0.8, 0.85, 1.0],
+            ),
+            (
+                confirm_x,
+                self.confirm_label.as_str(),
+                [0.010, 0.787, 0.214, 1.0],
+                [1.0, 1.0, 1.0, 1.0],
+            ),
+        ];
+        for (button_x, label, bg_color, text_color) in buttons {
+            widget_renderer.draw_rounded_rect(
+                encoder,
+                view,
+                queue,
+                button_x,
+                buttons_y,
+                BUTTON_WIDTH,
+                BUTTON_HEIGHT,
+                8.0,
+                bg_color,
+                window_width,
+                window_height,
+            );
+            text_items.push(TextItem {
+                text: label.to_string(),
+                x: button_x,
+                y: buttons_y + (BUTTON_HEIGHT - 14.0) / 2.0,
+                scale: 1.0,
+                color: text_color,
+                max_width: BUTTON_WIDTH,
+                centered: true,
+            });
+        }
+    }
+}
@@ -1,11 +1,17 @@
+pub mod confirm;
 pub mod label;
 pub mod select;
+pub mod shortcut_capture;
 pub mod slider;
 pub mod toggle;
+pub mod virtual_list;
 pub mod widget_renderer;
 
+pub use confirm::ConfirmDialog;
 pub use label::SectionLabel;
 pub use select::{Select, SelectOption};
+pub use shortcut_capture::ShortcutCapture;
 pub use slider::Slider;
 pub use toggle::Toggle;
-pub use widget_renderer::WidgetRenderer;
+pub use virtual_list::{VirtualList, VisibleRow};
+pub use widget_renderer::{DrawStatsReporter, WidgetDrawStats, WidgetRenderer};
@@ -4,6 +4,10 @@ use crate::ui::batch_text_renderer::TextItem;
 const DROPDOWN_ITEM_HEIGHT: f32 = 22.0;
 const CHEVRON_WIDTH: f32 = 16.0;
 const SELECT_BOX_WIDTH: f32 = 140.0;
+const SCROLLBAR_WIDTH: f32 = 4.0;
+const TYPE_AHEAD_RESET: std::time::Duration = std::time::Duration::from_millis(1000);
+/// Pointer travel after which a press in the open list scrolls instead of choosing
+const DRAG_THRESHOLD: f32 = 4.0;
 
 pub struct SelectOption {
     pub label: String,
@@ -21,6 +25,26 @@ pub struct Select {
     pub expanded: bool,
     hovered_index: Option<usize>,
     changed: bool,
+    // Dropdown layout, computed from the space available when opening
+    visible_count: usize,
+    scroll_offset: usize,
+    opens_upward: bool,
+    space_top: f32,
+    space_bottom: f32,
+    // Keyboard navigation and type-ahead
+    highlighted_index: Option<usize>,
+    type_ahead: String,
+    last_type_ahead: Option<std::time::Instant>,
+    // Press-and-drag scrolling of the open list
+    drag: Option<ListDrag>,
+}
+
+/// A press in the open list: where it started and the scroll offset at that time
+struct ListDrag {
+    x: f32,
+    y: f32,
+    scroll_offset: usize,
+    moved: bool,
 }
 
 impl Select {
@@ -44,6 +68,15 @@ impl Select {
             expanded: false,
             hovered_index: None,
             changed: false,
+            visible_count: 0,
+            scroll_offset: 0,
+            opens_upward: false,
+            space_top: f32::MIN,
+            space_bottom: f32::MAX,
+            highlighted_index: None,
+            type_ahead: String::new(),
+            last_type_ahead: None,
+            drag: None,
         }
     }
 
@@ -51,8 +84,67 @@ impl Select {
         self.x + self.width - SELECT_BOX_WIDTH
     }
 
+    /// Constrain the dropdown to the vertical range `[top, bottom]` (widget coordinates).
+    /// Long lists scroll internally, and the menu flips upward when there is more room above.
+    pub fn set_available_space(&mut self, top: f32, bottom: f32) {
+        self.space_top = top;
+        self.space_bottom = bottom;
+        if self.expanded {
+            self.layout_dropdown();
+        }
+    }
+
+    fn layout_dropdown(&mut self) {
+        let option_count = self.options.len();
+        let space_below = (self.space_bottom - (self.y + self.height)).max(0.0);
+        let space_above = (self.y - self.space_top).max(0.0);
+        let fits = |space: f32| ((space / DROPDOWN_ITEM_HEIGHT) as usize).min(option_count);
+
+        self.opens_upward =
+            fits(space_below) < option_count && fits(space_above) > fits(space_below);
+        let available = if self.opens_upward {
+            fits(space_above)
+        } else {
+            fits(space_below)
+        };
+        self.visible_count = available.max(1).min(option_count);
+        self.scroll_offset = 0;
+        self.ensure_visible(self.selected_index);
+    }
+
+    fn visible_count(&self) -> usize {
+        if self.visible_count == 0 {
+            self.options.len()
+        } else {
+            self.visible_count
+        }
+    }
+
+    fn max_scroll_offset(&self) -> usize {
+        self.options.len().saturating_sub(self.visible_count())
+    }
+
+    fn ensure_visible(&mut self, index: usize) {
+        let visible = self.visible_count();
+        if index < self.scroll_offset {
+            self.scroll_offset = index;
+        } else if index >= self.scroll_offset + visible {
+            self.scroll_offset = index + 1 - visible;
+        }
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+    }
+
+    /// Top edge of the open dropdown relative to a select rendered at `y`
+    fn dropdown_top(&self, y: f32) -> f32 {
+        if self.opens_upward {
+            y - self.dropdown_height()
+        } else {
+            y + self.height
+        }
+    }
+
     pub fn dropdown_height(&self) -> f32 {
-        self.options.len() as f32 * DROPDOWN_ITEM_HEIGHT
+        self.visible_count() as f32 * DROPDOWN_ITEM_HEIGHT
     }
 
     pub fn is_expanded(&self) -> bool {
@@ -61,9 +153,144 @@ impl Select {
 
     pub fn set_expanded(&mut self, expanded: bool) {
         self.expanded = expanded;
-        if !expanded {
+        self.type_ahead.clear();
+        self.drag = None;
+        if expanded {
+            self.highlighted_index = Some(self.selected_index);
+            self.layout_dropdown();
+        } else {
             self.hovered_index = None;
+            self.highlighted_index = None;
+        }
+    }
+
+    /// Scroll the open dropdown by whole rows (positive scrolls down)
+    pub fn scroll_by(&mut self, rows: i32) -> bool {
+        if !self.expanded {
+            return false;
         }
+        let next =
+            (self.scroll_offset as i64 + rows as i64).clamp(0, self.max_scroll_offset() as i64);
+        let changed = next as usize != self.scroll_offset;
+        self.scroll_offset = next as usize;
+        changed
+    }
+
+    /// Start a press in the open list; it chooses an option or scrolls once dragged
+    pub fn begin_drag(&mut self, x: f32, y: f32) {
+        if self.expanded {
+            self.drag = Some(ListDrag {
+                x,
+                y,
+                scroll_offset: self.scroll_offset,
+                moved: false,
+            });
+        }
+    }
+
+    /// Scroll the list with the pointer; returns true if the visible rows changed
+    pub fn drag_to(&mut self, y: f32) -> bool {
+        let max_scroll_offset = self.max_scroll_offset() as i64;
+        let Some(drag) = self.drag.as_mut() else {
+            return false;
+        };
+        let travel = drag.y - y;
+        if travel.abs() > DRAG_THRESHOLD {
+            drag.moved = true;
+        }
+        if !drag.moved {
+            return false;
+        }
+        let rows = (travel / DROPDOWN_ITEM_HEIGHT).round() as i64;
+        let next = (drag.scroll_offset as i64 + rows).clamp(0, max_scroll_offset) as usize;
+        let changed = next != self.scroll_offset;
+        self.scroll_offset = next;
+        changed
+    }
+
+    /// Finish a press; one that never moved picks the option it started on
+    pub fn end_drag(&mut self) -> Option<usize> {
+        let drag = self.drag.take()?;
+        if drag.moved {
+            None
+        } else {
+            self.dropdown_option_at(drag.x, drag.y)
+        }
+    }
+
+    /// Move the keyboard highlight by `delta` rows
+    pub fn move_highlight(&mut self, delta: i32) {
+        if !self.expanded || self.options.is_empty() {
+            return;
+        }
+        let current = self.highlighted_index.unwrap_or(self.selected_index) as i64;
+        let next = (current + delta as i64).clamp(0, self.options.len() as i64 - 1) as usize;
+        self.highlighted_index = Some(next);
+        self.type_ahead.clear();
+        self.ensure_visible(next);
+    }
+
+    pub fn highlighted_index(&self) -> Option<usize> {
+        self.highlighted_index
+    }
+
+    /// Append typed text to the type-ahead query and jump to the first matching option.
+    /// Prefix matches win over substring matches. Returns true if an option matched.
+    pub fn type_ahead(&mut self, text: &str) -> bool {
+        if !self.expanded {
+            return false;
+        }
+        if self
+            .last_type_ahead
+            .is_some_and(|last| last.elapsed() > TYPE_AHEAD_RESET)
+        {
+            self.type_ahead.clear();
+        }
+        self.last_type_ahead = Some(std::time::Instant::now());
+        self.type_ahead.push_str(&text.to_lowercase());
+
+        let query = self.type_ahead.as_str();
+        let matched = self
+            .options
+            .iter()
+            .position(|o| o.label.to_lowercase().starts_with(query))
+            .or_else(|| {
+                self.options
+                    .iter()
+                    .position(|o| o.label.to_lowercase().contains(query))
+            });
+
+        if let Some(index) = matched {
+            self.highlighted_index = Some(index);
+            self.ensure_visible(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove the last type-ahead character
+    pub fn type_ahead_backspace(&mut self) {
+        self.type_ahead.pop();
+        self.last_type_ahead = Some(std::time::Instant::now());
+    }
+
+    /// Whether a type-ahead query is still being typed (a space then extends it)
+    pub fn type_ahead_active(&self) -> bool {
+        !self.type_ahead.is_empty()
+            && self
+                .last_type_ahead
+                .is_some_and(|last| last.elapsed() <= TYPE_AHEAD_RESET)
+    }
+
+    /// Select the option `delta` places away, wrapping around at either end
+    pub fn cycle(&mut self, delta: i32) -> bool {
+        if self.options.is_empty() {
+            return false;
+        }
+        let len = self.options.len() as i64;
+        let next = (self.selected_index as i64 + delta as i64).rem_euclid(len) as usize;
+        self.choose_index(next)
     }
 
     pub fn has_changed(&self) -> bool {
@@ -84,19 +311,31 @@ impl Select {
 
     pub fn dropdown_option_at(&self, click_x: f32, click_y: f32) -> Option<usize> {
         let box_x = self.select_box_x();
-        let dropdown_y = self.y + self.height;
+        let dropdown_y = self.dropdown_top(self.y);
         if click_x >= box_x
             && click_x <= box_x + SELECT_BOX_WIDTH
             && click_y >= dropdown_y
             && click_y < dropdown_y + self.dropdown_height()
         {
-            let index = ((click_y - dropdown_y) / DROPDOWN_ITEM_HEIGHT) as usize;
-            (index < self.options.len()).then_some(index)
+            let row = ((click_y - dropdown_y) / DROPDOWN_ITEM_HEIGHT) as usize;
+            let index = self.scroll_offset + row;
+            (row < self.visible_count() && index < self.options.len()).then_some(index)
         } else {
             None
         }
     }
 
+    /// Whether a point lies inside the open dropdown (including its scrollbar)
+    pub fn hit_dropdown(&self, x: f32, y: f32) -> bool {
+        let box_x = self.select_box_x();
+        let dropdown_y = self.dropdown_top(self.y);
+        self.expanded
+            && x >= box_x
+            && x <= box_x + SELECT_BOX_WIDTH
+            && y >= dropdown_y
+            && y < dropdown_y + self.dropdown_height()
+    }
+
     pub fn choose_index(&mut self, index: usize) -> bool {
         if index < self.options.len() && index != self.selected_index {
             self.selected_index = index;
@@ -140,23 +379,7 @@ impl Select {
             return;
         }
 
-        let box_x = self.select_box_x();
-        let dropdown_y = self.y + self.height;
-
-        if x >= box_x
-            && x <= box_x + SELECT_BOX_WIDTH
-            && y >= dropdown_y
-            && y <= dropdown_y + self.dropdown_height()
-        {
-            let index = ((y - dropdown_y) / DROPDOWN_ITEM_HEIGHT) as usize;
-            if index < self.options.len() {
-                self.hovered_index = Some(index);
-            } else {
-                self.hovered_index = None;
-            }
-        } else {
-            self.hovered_index = None;
-        }
+        self.hovered_index = self.dropdown_option_at(x, y);
     }
 
     pub fn mark_changed(&mut self) {
@@ -223,6 +446,7 @@ impl Select {
             scale: 1.0,
             color: [0.604, 0.604, 0.670, 1.0],
             max_width: label_width,
+            centered: false,
         });
 
         // Draw select box background
@@ -242,7 +466,17 @@ impl Select {
 
         // Collect selected option text item. Dropdowns are rendered in a later overlay
         // pass, so controls beneath them should remain stable instead of disappearing.
-        if let Some(option) = self.options.get(self.selected_index) {
+        if self.expanded && !self.type_ahead.is_empty() {
+            text_items.push(TextItem {
+                text: format!("{}\u{2502}", self.type_ahead),
+                x: box_x + 6.0,
+                y: y + 4.0,
+                scale: 1.0,
+                color: [0.885, 0.885, 0.930, 1.0],
+                max_width: SELECT_BOX_WIDTH - CHEVRON_WIDTH - 10.0,
+                centered: false,
+            });
+        } else if let Some(option) = self.options.get(self.selected_index) {
             text_items.push(TextItem {
                 text: option.label.clone(),
                 x: box_x + 6.0,
@@ -250,6 +484,7 @@ impl Select {
                 scale: 1.0,
                 color: [0.604, 0.604, 0.670, 1.0],
                 max_width: SELECT_BOX_WIDTH - CHEVRON_WIDTH - 10.0,
+                centered: false,
             });
         }
 
@@ -266,6 +501,7 @@ impl Select {
             scale: 0.85,
             color: [0.171, 0.171, 0.214, 0.7],
             max_width: CHEVRON_WIDTH,
+            centered: false,
         });
     }
 
@@ -285,7 +521,14 @@ impl Select {
         }
 
         let box_x = self.select_box_x();
-        let dropdown_y = y + self.height;
+        let dropdown_y = self.dropdown_top(y);
+        let visible = self.visible_count();
+        let scrollable = self.options.len() > visible;
+        let item_width = if scrollable {
+            SELECT_BOX_WIDTH - SCROLLBAR_WIDTH - 4.0
+        } else {
+            SELECT_BOX_WIDTH
+        };
 
         // Draw dropdown background over the select box area
         widget_renderer.draw_rounded_rect(
@@ -302,14 +545,22 @@ impl Select {
             window_height,
         );
 
-        for (i, option) in self.options.iter().enumerate() {
-            let item_y = dropdown_y + i as f32 * DROPDOWN_ITEM_HEIGHT;
+        let visible_options = self
+            .options
+            .iter()
+            .enumerate()
+            .skip(self.scroll_offset)
+            .take(visible);
+
+        for (row, (i, option)) in visible_options.enumerate() {
+            let item_y = dropdown_y + row as f32 * DROPDOWN_ITEM_HEIGHT;
 
             let is_hovered = self.hovered_index == Some(i);
+            let is_highlighted = self.highlighted_index == Some(i);
             let is_selected = i == self.selected_index;
 
-            if is_hovered || is_selected {
-                let highlight_color = if is_hovered {
+            if is_hovered || is_highlighted || is_selected {
+                let highlight_color = if is_hovered || is_highlighted {
                     [0.051, 0.051, 0.073, 0.9]
                 } else {
                     [0.005, 0.262, 0.073, 0.5]
@@ -321,7 +572,7 @@ impl Select {
                     queue,
                     box_x + 2.0,
                     item_y + 1.0,
-                    SELECT_BOX_WIDTH - 4.0,
+                    item_width - 4.0,
                     DROPDOWN_ITEM_HEIGHT - 2.0,
                     3.0,
                     highlight_color,
@@ -330,7 +581,7 @@ impl Select {
                 );
             }
 
-            let text_color = if is_selected {
+            let text_color = if is_selected || is_highlighted {
                 [1.0, 1.0, 1.0, 1.0]
             } else {
                 [0.604, 0.604, 0.604, 0.9]
@@ -342,8 +593,143 @@ impl Select {
                 y: item_y + 4.0,
                 scale: 1.0,
                 color: text_color,
-                max_width: SELECT_BOX_WIDTH - 12.0,
+                max_width: item_width - 12.0,
+                centered: false,
             });
         }
+
+        // Scrollbar track and thumb, matching the transcript scrollbar colors
+        if scrollable {
+            let track_x = box_x + SELECT_BOX_WIDTH - SCROLLBAR_WIDTH - 2.0;
+            let track_height = self.dropdown_height() - 4.0;
+            widget_renderer.draw_rounded_rect(
+                encoder,
+                view,
+                queue,
+                track_x,
+                dropdown_y + 2.0,
+                SCROLLBAR_WIDTH,
+                track_height,
+                SCROLLBAR_WIDTH / 2.0,
+                [0.0, 0.0, 0.0, 0.15],
+                window_width,
+                window_height,
+            );
+
+            let thumb_height = (track_height * visible as f32 / self.options.len() as f32)
+                .max(SCROLLBAR_WIDTH * 2.0);
+            let max_offset = self.max_scroll_offset().max(1) as f32;
+            let thumb_y = dropdown_y
+                + 2.0
+                + (track_height - thumb_height) * (self.scroll_offset as f32 / max_offset);
+            widget_renderer.draw_rounded_rect(
+                encoder,
+                view,
+                queue,
+                track_x,
+                thumb_y,
+                SCROLLBAR_WIDTH,
+                thumb_height,
+                SCROLLBAR_WIDTH / 2.0,
+                [0.3, 0.3, 0.3, 0.5],
+                window_width,
+                window_height,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select_with(count: usize, y: f32) -> Select {
+        let options = (0..count)
+            .map(|i| SelectOption {
+                label: format!("option-{i:02}"),
+                value: i.to_string(),
+            })
+            .collect();
+        Select::new("Test", options, 0, 10.0, y, 300.0, 28.0)
+    }
+
+    #[test]
+    fn long_dropdown_scrolls_and_hit_tests_visible_rows() {
+        let mut select = select_with(30, 50.0);
+        select.set_available_space(42.0, 300.0);
+        select.set_expanded(true);
+
+        // (300 - 78) / 22 rows fit below the select box
+        assert_eq!(select.dropdown_height(), 10.0 * DROPDOWN_ITEM_HEIGHT);
+        assert!(select.scroll_by(5));
+
+        let x = select.select_box_x() + 10.0;
+        let first_row_y = 50.0 + 28.0 + 1.0;
+        assert_eq!(select.dropdown_option_at(x, first_row_y), Some(5));
+
+        // Scrolling clamps so the last option stays on the bottom row
+        assert!(select.scroll_by(100));
+        assert_eq!(select.dropdown_option_at(x, first_row_y), Some(20));
+        assert!(!select.scroll_by(1));
+    }
+
+    #[test]
+    fn dropdown_flips_upward_when_more_room_above() {
+        let mut select = select_with(8, 250.0);
+        select.set_available_space(42.0, 300.0);
+        select.set_expanded(true);
+
+        let x = select.select_box_x() + 10.0;
+        let top = 250.0 - select.dropdown_height();
+        assert_eq!(select.dropdown_option_at(x, top + 1.0), Some(0));
+        assert_eq!(select.dropdown_option_at(x, 250.0 + 30.0), None);
+    }
+
+    #[test]
+    fn type_ahead_prefers_prefix_and_scrolls_match_into_view() {
+        let mut select = select_with(30, 50.0);
+        select.options[25].label = "Nemotron".to_string();
+        select.options[3].label = "Parakeet (nemo)".to_string();
+        select.set_available_space(42.0, 300.0);
+        select.set_expanded(true);
+
+        assert!(select.type_ahead("n"));
+        assert!(select.type_ahead("E"));
+        assert_eq!(select.highlighted_index(), Some(25));
+
+        let x = select.select_box_x() + 10.0;
+        let last_row_y = 78.0 + 9.0 * DROPDOWN_ITEM_HEIGHT + 1.0;
+        assert_eq!(select.dropdown_option_at(x, last_row_y), Some(25));
+    }
+
+    #[test]
+    fn dragging_the_list_scrolls_instead_of_choosing() {
+        let mut select = select_with(30, 50.0);
+        select.set_available_space(42.0, 300.0);
+        select.set_expanded(true);
+        let x = select.select_box_x() + 10.0;
+        let first_row_y = 50.0 + 28.0 + 1.0;
+
+        // A press that does not move picks its option
+        select.begin_drag(x, first_row_y);
+        assert!(!select.drag_to(first_row_y + 2.0));
+        assert_eq!(select.end_drag(), Some(0));
+
+        // Dragging up three rows scrolls the list down by three
+        select.begin_drag(x, first_row_y + 100.0);
+        assert!(select.drag_to(first_row_y + 100.0 - 3.0 * DROPDOWN_ITEM_HEIGHT));
+        assert_eq!(select.dropdown_option_at(x, first_row_y), Some(3));
+        assert_eq!(select.end_drag(), None);
+    }
+
+    #[test]
+    fn cycle_wraps_and_marks_changed() {
+        let mut select = select_with(3, 50.0);
+        assert!(select.cycle(-1));
+        assert_eq!(select.selected_index, 2);
+        assert!(select.cycle(1));
+        assert_eq!(select.selected_index, 0);
+        assert!(select.has_changed());
+        assert!(!select_with(1, 50.0).cycle(1));
     }
 }
@@ -0,0 +1,437 @@
+use winit::keyboard::{KeyCode, ModifiersState};
+
+use super::widget_renderer::WidgetRenderer;
+use crate::hotkeys::{Accelerator, Modifiers};
+use crate::ui::batch_text_renderer::TextItem;
+
+const BOX_WIDTH: f32 = 170.0;
+const BOX_HEIGHT: f32 = 20.0;
+const WAITING_TEXT: &str = "Press a shortcut\u{2026}";
+
+/// xkb keysym name of a physical key, as written in accelerators. Named after
+/// the US layout so a shortcut means the same key whatever layout is active.
+pub fn keysym_name(code: KeyCode) -> Option<&'static str> {
+    let name = match code {
+        KeyCode::KeyA => "a",
+        KeyCode::KeyB => "b",
+        KeyCode::KeyC => "c",
+        KeyCode::KeyD => "d",
+        KeyCode::KeyE => "e",
+        KeyCode::KeyF => "f",
+        KeyCode::KeyG => "g",
+        KeyCode::KeyH => "h",
+        KeyCode::KeyI => "i",
+        KeyCode::KeyJ => "j",
+        KeyCode::KeyK => "k",
+        KeyCode::KeyL => "l",
+        KeyCode::KeyM => "m",
+        KeyCode::KeyN => "n",
+        KeyCode::KeyO => "o",
+        KeyCode::KeyP => "p",
+        KeyCode::KeyQ => "q",
+        KeyCode::KeyR => "r",
+        KeyCode::KeyS => "s",
+        KeyCode::KeyT => "t",
+        KeyCode::KeyU => "u",
+        KeyCode::KeyV => "v",
+        KeyCode::KeyW => "w",
+        KeyCode::KeyX => "x",
+        KeyCode::KeyY => "y",
+        KeyCode::KeyZ => "z",
+        KeyCode::Digit0 => "0",
+        KeyCode::Digit1 => "1",
+        KeyCode::Digit2 => "2",
+        KeyCode::Digit3 => "3",
+        KeyCode::Digit4 => "4",
+        KeyCode::Digit5 => "5",
+        KeyCode::Digit6 => "6",
+        KeyCode::Digit7 => "7",
+        KeyCode::Digit8 => "8",
+        KeyCode::Digit9 => "9",
+        KeyCode::F1 => "F1",
+        KeyCode::F2 => "F2",
+        KeyCode::F3 => "F3",
+        KeyCode::F4 => "F4",
+        KeyCode::F5 => "F5",
+        KeyCode::F6 => "F6",
+        KeyCode::F7 => "F7",
+        KeyCode::F8 => "F8",
+        KeyCode::F9 => "F9",
+        KeyCode::F10 => "F10",
+        KeyCode::F11 => "F11",
+        KeyCode::F12 => "F12",
+        KeyCode::F13 => "F13",
+        KeyCode::F14 => "F14",
+        KeyCode::F15 => "F15",
+        KeyCode::F16 => "F16",
+        KeyCode::F17 => "F17",
+        KeyCode::F18 => "F18",
+        KeyCode::F19 => "F19",
+        KeyCode::F20 => "F20",
+        KeyCode::F21 => "F21",
+        KeyCode::F22 => "F22",
+        KeyCode::F23 => "F23",
+        KeyCode::F24 => "F24",
+        KeyCode::Backslash => "backslash",
+        KeyCode::Slash => "slash",
+        KeyCode::Comma => "comma",
+        KeyCode::Period => "period",
+        KeyCode::Semicolon => "semicolon",
+        KeyCode::Quote => "apostrophe",
+        KeyCode::BracketLeft => "bracketleft",
+        KeyCode::BracketRight => "bracketright",
+        KeyCode::Minus => "minus",
+        KeyCode::Equal => "equal",
+        KeyCode::Backquote => "grave",
+        KeyCode::Space => "space",
+        KeyCode::Enter => "Return",
+        KeyCode::Tab => "Tab",
+        KeyCode::Escape => "Escape",
+        KeyCode::Backspace => "BackSpace",
+        KeyCode::Delete => "Delete",
+        KeyCode::Insert => "Insert",
+        KeyCode::Home => "Home",
+        KeyCode::End => "End",
+        KeyCode::PageUp => "Page_Up",
+        KeyCode::PageDown => "Page_Down",
+        KeyCode::ArrowUp => "Up",
+        KeyCode::ArrowDown => "Down",
+        KeyCode::ArrowLeft => "Left",
+        KeyCode::ArrowRight => "Right",
+        KeyCode::Pause => "Pause",
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn is_modifier_key(code: KeyCode) -> bool {
+    matches!(
+        code,
+        KeyCode::ShiftLeft
+            | KeyCode::ShiftRight
+            | KeyCode::ControlLeft
+            | KeyCode::ControlRight
+            | KeyCode::AltLeft
+            | KeyCode::AltRight
+            | KeyCode::MetaLeft
+            | KeyCode::MetaRight
+            | KeyCode::CapsLock
+            | KeyCode::Fn
+    )
+}
+
+/// Accelerator for `code` pressed with `modifiers`. `Ok(None)` while only
+/// modifiers are held; `Err` explains a combination that cannot be bound.
+pub fn capture_accelerator(
+    code: KeyCode,
+    modifiers: ModifiersState,
+) -> Result<Option<Accelerator>, &'static str> {
+    if is_modifier_key(code) {
+        return Ok(None);
+    }
+    let key = keysym_name(code).ok_or("Unsupported key")?;
+    let accelerator = Accelerator {
+        modifiers: Modifiers {
+            ctrl: modifiers.control_key(),
+            alt: modifiers.alt_key(),
+            shift: modifiers.shift_key(),
+            logo: modifiers.meta_key(),
+        },
+        key: key.to_string(),
+    };
+    match accelerator.global_shortcut_problem() {
+        Some(problem) => Err(problem),
+        None => Ok(Some(accelerator)),
+    }
+}
+
+/// Row that records a key combination: click it, then press the new shortcut.
+/// Escape cancels; invalid combinations are explained in place of the label.
+pub struct ShortcutCapture {
+    pub label: String,
+    /// Accelerator in config form, e.g. `<Super>backslash`
+    pub value: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Shortcuts bound to other actions, with the action's name
+    reserved: Vec<(String, Accelerator)>,
+    capturing: bool,
+    feedback: Option<String>,
+    changed: bool,
+}
+
+impl ShortcutCapture {
+    pub fn new(label: &str, value: &str, x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            label: label.to_string(),
+            value: value.to_string(),
+            x,
+            y,
+            width,
+            height,
+            reserved: Vec::new(),
+            capturing: false,
+            feedback: None,
+            changed: false,
+        }
+    }
+
+    /// Shortcuts a capture must not reuse
+    pub fn set_reserved(&mut self, reserved: Vec<(String, Accelerator)>) {
+        self.reserved = reserved;
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.cancel_capture();
+    }
+
+    fn box_x(&self) -> f32 {
+        self.x + self.width - BOX_WIDTH
+    }
+
+    pub fn handle_click(&mut self, click_x: f32, click_y: f32) -> bool {
+        let box_y = self.y + (self.height - BOX_HEIGHT) / 2.0;
+        let hit = click_x >= self.box_x()
+            && click_x <= self.box_x() + BOX_WIDTH
+            && click_y >= box_y
+            && click_y <= box_y + BOX_HEIGHT;
+        if hit {
+            if self.capturing {
+                self.cancel_capture();
+            } else {
+                self.start_capture();
+            }
+        }
+        hit
+    }
+
+    pub fn start_capture(&mut self) {
+        self.capturing = true;
+        self.feedback = None;
+    }
+
+    pub fn cancel_capture(&mut self) {
+        self.capturing = false;
+        self.feedback = None;
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    /// Feed a key press while capturing; returns false when not capturing
+    pub fn handle_key(&mut self, code: KeyCode, modifiers: ModifiersState) -> bool {
+        if !self.capturing {
+            return false;
+        }
+        if code == KeyCode::Escape && modifiers.is_empty() {
+            self.cancel_capture();
+            return true;
+        }
+        match capture_accelerator(code, modifiers) {
+            Ok(None) => {}
+            Ok(Some(accelerator)) => {
+                if let Some((name, _)) = self
+                    .reserved
+                    .iter()
+                    .find(|(_, reserved)| reserved.same_combo(&accelerator))
+                {
+                    self.feedback = Some(format!("{} is used by {}", accelerator, name));
+                    return true;
+                }
+                let value = accelerator.to_string();
+                if value != self.value {
+                    self.value = value;
+                    self.changed = true;
+                }
+                self.cancel_capture();
+            }
+            Err(problem) => self.feedback = Some(problem.to_string()),
+        }
+        true
+    }
+
+    pub fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    pub fn has_changed(&self) -> bool {
+        self.changed
+    }
+
+    pub fn clear_changed(&mut self) {
+        self.changed = false;
+    }
+
+    pub fn take_changed(&mut self) -> Option<String> {
+        if self.changed {
+            self.changed = false;
+            Some(self.value.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn render_at(
+        &self,
+        y: f32,
+        accent: [f32; 4],
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        widget_renderer: &WidgetRenderer,
+        text_items: &mut Vec<TextItem>,
+        queue: &wgpu::Queue,
+        window_width: u32,
+        window_height: u32,
+    ) {
+        let (label, label_color) = match &self.feedback {
+            Some(feedback) => (feedback.clone(), [0.90, 0.35, 0.35, 1.0]),
+            None => (self.label.clone(), [0.604, 0.604, 0.670, 1.0]),
+        };
+        text_items.push(TextItem {
+            text: label,
+            x: self.x + 4.0,
+            y: y + 4.0,
+            scale: 1.0,
+            color: label_color,
+            max_width: self.width - BOX_WIDTH - 8.0,
+            centered: false,
+        });
+
+        let box_x = self.box_x();
+        let box_y = y + (self.height - BOX_HEIGHT) / 2.0;
+        let background = if self.capturing {
+            [accent[0] * 0.35, accent[1] * 0.35, accent[2] * 0.35, 1.0]
+        } else {
+            [0.064, 0.064, 0.083, 1.0]
+        };
+        widget_renderer.draw_rounded_rect(
+            encoder,
+            view,
+            queue,
+            box_x,
+            box_y,
+            BOX_WIDTH,
+            BOX_HEIGHT,
+            6.0,
+            background,
+            window_width,
+            window_height,
+        );
+
+        let text = if self.capturing {
+            WAITING_TEXT
+        } else {
+            self.value.as_str()
+        };
+        text_items.push(TextItem {
+            text: text.to_string(),
+            x: box_x + 4.0,
+            y: box_y + (BOX_HEIGHT - 14.0) / 2.0,
+            scale: 1.0,
+            color: [0.8, 0.8, 0.85, 1.0],
+            max_width: BOX_WIDTH - 8.0,
+            centered: true,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_physical_keys_to_keysym_names() {
+        assert_eq!(keysym_name(KeyCode::Backslash), Some("backslash"));
+        assert_eq!(keysym_name(KeyCode::KeyQ), Some("q"));
+        assert_eq!(keysym_name(KeyCode::Digit0), Some("0"));
+        assert_eq!(keysym_name(KeyCode::F13), Some("F13"));
+        assert_eq!(keysym_name(KeyCode::Enter), Some("Return"));
+        assert_eq!(keysym_name(KeyCode::PageDown), Some("Page_Down"));
+        assert_eq!(keysym_name(KeyCode::NumpadAdd), None);
+    }
+
+    #[test]
+    fn every_mapped_key_is_known_to_the_evdev_fallback() {
+        let codes = [
+            KeyCode::KeyA,
+            KeyCode::KeyM,
+            KeyCode::Digit5,
+            KeyCode::F1,
+            KeyCode::F24,
+            KeyCode::Backslash,
+            KeyCode::Quote,
+            KeyCode::Backquote,
+            KeyCode::Space,
+            KeyCode::Enter,
+            KeyCode::Backspace,
+            KeyCode::PageUp,
+            KeyCode::PageDown,
+            KeyCode::ArrowLeft,
+            KeyCode::Pause,
+        ];
+        for code in codes {
+            let name = keysym_name(code).unwrap();
+            assert!(
+                crate::evdev_input::key_code(name).is_some(),
+                "{} has no evdev code",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn captures_combinations_in_config_form() {
+        let captured = capture_accelerator(KeyCode::Backslash, ModifiersState::META).unwrap();
+        assert_eq!(captured.unwrap().to_string(), "<Super>backslash");
+
+        let captured = capture_accelerator(
+            KeyCode::KeyD,
+            ModifiersState::CONTROL | ModifiersState::ALT | ModifiersState::SHIFT,
+        )
+        .unwrap();
+        assert_eq!(captured.unwrap().to_string(), "<Ctrl><Alt><Shift>d");
+
+        // Modifiers alone keep waiting for the key
+        assert_eq!(
+            capture_accelerator(KeyCode::MetaLeft, ModifiersState::META),
+            Ok(None)
+        );
+        assert!(capture_accelerator(KeyCode::KeyA, ModifiersState::empty()).is_err());
+        assert_eq!(
+            capture_accelerator(KeyCode::NumpadAdd, ModifiersState::META),
+            Err("Unsupported key")
+        );
+    }
+
+    #[test]
+    fn capture_rejects_reserved_shortcuts_and_escape_cancels() {
+        let mut capture =
+            ShortcutCapture::new("Toggle shortcut", "<Super>backslash", 0.0, 0.0, 300.0, 26.0);
+        capture.set_reserved(vec![(
+            "privacy wipe".to_string(),
+            Accelerator::parse("<Super><Shift>BackSpace").unwrap(),
+        )]);
+
+        capture.start_capture();
+        capture.handle_key(
+            KeyCode::Backspace,
+            ModifiersState::META | ModifiersState::SHIFT,
+        );
+        assert!(capture.is_capturing());
+        assert!(capture.feedback.is_some());
+
+        capture.handle_key(KeyCode::F9, ModifiersState::META);
+        assert!(!capture.is_capturing());
+        assert_eq!(capture.take_changed().as_deref(), Some("<Super>F9"));
+
+        capture.start_capture();
+        capture.handle_key(KeyCode::Escape, ModifiersState::empty());
+        assert!(!capture.is_capturing());
+        assert_eq!(capture.value, "<Super>F9");
+        assert!(!capture.has_changed());
+    }
+}
@@ -123,6 +123,24 @@ impl Slider {
         self.dragging = false;
     }
 
+    /// Move the value by `steps` steps (a hundredth of the range without a step)
+    pub fn step_by(&mut self, steps: i32) -> bool {
+        let new_val = if self.step > 0.0 {
+            // Snap like a click so repeated steps do not accumulate rounding error
+            ((self.value / self.step).round() + steps as f32) * self.step
+        } else {
+            self.value + steps as f32 * (self.max - self.min) / 100.0
+        }
+        .clamp(self.min, self.max);
+        if (new_val - self.value).abs() > f32::EPSILON {
+            self.value = new_val;
+            self.changed = true;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn mark_changed(&mut self) {
         self.changed = true;
     }
@@ -187,6 +205,7 @@ impl Slider {
             scale: 1.0,
             color: [0.604, 0.604, 0.670, 1.0],
             max_width: label_width,
+            centered: false,
         });
 
         let track_x = self.track_x();
@@ -258,6 +277,7 @@ impl Slider {
             scale: 1.0,
             color: [0.262, 0.262, 0.319, 0.9],
             max_width: VALUE_DISPLAY_WIDTH,
+            centered: false,
         });
     }
 }
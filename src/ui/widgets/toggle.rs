@@ -47,16 +47,21 @@ impl Toggle {
             && click_y >= toggle_y
             && click_y <= toggle_y + TOGGLE_HEIGHT
         {
-            self.value = !self.value;
-            self.changed = true;
-            self.animation_from = self.animation_progress;
-            self.animation_active = true;
-            self.animation_start = std::time::Instant::now();
+            self.toggle();
             return true;
         }
         false
     }
 
+    /// Flip the value with the same animation as a click
+    pub fn toggle(&mut self) {
+        self.value = !self.value;
+        self.changed = true;
+        self.animation_from = self.animation_progress;
+        self.animation_active = true;
+        self.animation_start = std::time::Instant::now();
+    }
+
     pub fn update_animation(&mut self) {
         if !self.animation_active {
             return;
@@ -150,6 +155,7 @@ impl Toggle {
             scale: 1.0,
             color: [0.604, 0.604, 0.670, 1.0],
             max_width: self.width - TOGGLE_WIDTH - 8.0,
+            centered: false,
         });
 
         let toggle_x = self.x + self.width - TOGGLE_WIDTH;
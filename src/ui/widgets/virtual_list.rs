@@ -0,0 +1,308 @@
+use std::ops::Range;
+
+use crate::ui::scrollbar::Scrollbar;
+
+/// Rows rendered above and below the viewport so scrolling never shows a gap
+const DEFAULT_OVERSCAN: usize = 2;
+
+/// Width of the scroll thumb drawn at the right edge of the list
+pub const LIST_SCROLLBAR_WIDTH: f32 = 4.0;
+
+/// A row handed to the render callback, positioned in window coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisibleRow {
+    pub index: usize,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub selected: bool,
+}
+
+/// Scrollable list that only lays out and renders the rows in view.
+///
+/// Row offsets are prefix sums of the item heights, rebuilt only when the item
+/// count changes or `invalidate` is called, so finding the visible range and
+/// hit-testing are binary searches instead of walks over every item.
+pub struct VirtualList {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub overscan: usize,
+    scroll_offset: f32,
+    selected: Option<usize>,
+    // offsets[i] is the top of row i relative to the content; offsets[count] is the content height
+    offsets: Vec<f32>,
+    dirty: bool,
+}
+
+impl VirtualList {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            overscan: DEFAULT_OVERSCAN,
+            scroll_offset: 0.0,
+            selected: None,
+            offsets: vec![0.0],
+            dirty: true,
+        }
+    }
+
+    pub fn set_bounds(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.x = x;
+        self.y = y;
+        self.width = width;
+        self.height = height;
+        self.clamp_scroll();
+    }
+
+    /// Force item heights to be measured again on the next `sync`
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Update the row layout for `count` items; cheap when nothing changed
+    pub fn sync(&mut self, count: usize, item_height: impl Fn(usize) -> f32) {
+        if !self.dirty && self.offsets.len() == count + 1 {
+            return;
+        }
+        self.offsets.clear();
+        self.offsets.reserve(count + 1);
+        let mut top = 0.0;
+        self.offsets.push(top);
+        for index in 0..count {
+            top += item_height(index).max(0.0);
+            self.offsets.push(top);
+        }
+        self.dirty = false;
+        if self.selected.is_some_and(|selected| selected >= count) {
+            self.selected = None;
+        }
+        self.clamp_scroll();
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn content_height(&self) -> f32 {
+        self.offsets.last().copied().unwrap_or(0.0)
+    }
+
+    pub fn max_scroll_offset(&self) -> f32 {
+        (self.content_height() - self.height).max(0.0)
+    }
+
+    pub fn scroll_offset(&self) -> f32 {
+        self.scroll_offset
+    }
+
+    pub fn scroll_by(&mut self, delta: f32) {
+        self.scroll_offset += delta;
+        self.clamp_scroll();
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = 0.0;
+    }
+
+    /// Scroll the least amount needed to show the whole row
+    pub fn scroll_to_item(&mut self, index: usize) {
+        if index >= self.len() {
+            return;
+        }
+        let top = self.offsets[index];
+        let bottom = self.offsets[index + 1];
+        if top < self.scroll_offset {
+            self.scroll_offset = top;
+        } else if bottom > self.scroll_offset + self.height {
+            self.scroll_offset = bottom - self.height;
+        }
+        self.clamp_scroll();
+    }
+
+    fn clamp_scroll(&mut self) {
+        self.scroll_offset = self.scroll_offset.clamp(0.0, self.max_scroll_offset());
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn set_selected(&mut self, selected: Option<usize>) {
+        self.selected = selected.filter(|&index| index < self.len());
+    }
+
+    /// Index of the row containing content position `content_y`
+    fn row_at_content_y(&self, content_y: f32) -> Option<usize> {
+        if content_y < 0.0 || content_y >= self.content_height() {
+            return None;
+        }
+        // First offset greater than content_y starts the row after ours
+        let next = self.offsets.partition_point(|&top| top <= content_y);
+        Some(next - 1)
+    }
+
+    /// Rows intersecting the viewport, widened by `overscan` rows on each side
+    pub fn visible_range(&self) -> Range<usize> {
+        let count = self.len();
+        if count == 0 || self.height <= 0.0 {
+            return 0..0;
+        }
+        let first = self.row_at_content_y(self.scroll_offset).unwrap_or(count);
+        let bottom = (self.scroll_offset + self.height).min(self.content_height());
+        let last = self
+            .offsets
+            .partition_point(|&top| top < bottom)
+            .saturating_sub(1);
+        first.saturating_sub(self.overscan)..(last + 1 + self.overscan).min(count)
+    }
+
+    /// Call `render_item` for each row in `visible_range`, top to bottom.
+    ///
+    /// Overscan rows lie partly or fully outside the viewport; callers that
+    /// cannot clip should skip or clamp them using `clip_rect`.
+    pub fn render(&self, mut render_item: impl FnMut(VisibleRow)) {
+        for index in self.visible_range() {
+            let top = self.offsets[index];
+            render_item(VisibleRow {
+                index,
+                x: self.x,
+                y: self.y + top - self.scroll_offset,
+                width: self.width - LIST_SCROLLBAR_WIDTH,
+                height: self.offsets[index + 1] - top,
+                selected: self.selected == Some(index),
+            });
+        }
+    }
+
+    /// The part of `row` inside the viewport as (y, height), if any
+    pub fn clip_rect(&self, row: &VisibleRow) -> Option<(f32, f32)> {
+        let top = row.y.max(self.y);
+        let bottom = (row.y + row.height).min(self.y + self.height);
+        (bottom > top).then_some((top, bottom - top))
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// Map a window position through the scroll offset to an item index
+    pub fn item_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.contains(x, y) {
+            return None;
+        }
+        self.row_at_content_y(y - self.y + self.scroll_offset)
+    }
+
+    /// Select the clicked row; returns it, or None if the click missed every row
+    pub fn handle_click(&mut self, x: f32, y: f32) -> Option<usize> {
+        let index = self.item_at(x, y)?;
+        self.selected = Some(index);
+        Some(index)
+    }
+
+    /// Thumb rectangle (x, y, width, height) in window coordinates, if the list scrolls
+    pub fn thumb_rect(&self) -> Option<(f32, f32, f32, f32)> {
+        let max_scroll = self.max_scroll_offset();
+        if max_scroll <= 0.0 {
+            return None;
+        }
+        let (top, height) = Scrollbar::thumb_geometry(self.height, self.scroll_offset, max_scroll);
+        Some((
+            self.x + self.width - LIST_SCROLLBAR_WIDTH,
+            self.y + top,
+            LIST_SCROLLBAR_WIDTH,
+            height,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn height(index: usize) -> f32 {
+        if index % 10 == 0 {
+            20.0
+        } else {
+            26.0
+        }
+    }
+
+    #[test]
+    fn batch_size_stays_bounded_for_ten_thousand_items() {
+        let mut list = VirtualList::new(0.0, 40.0, 400.0, 300.0);
+        list.sync(10_000, height);
+        assert_eq!(list.len(), 10_000);
+
+        // Each row pushes one rect and one text item, like the history view
+        let max_rows = (300.0 / 20.0) as usize + 1 + 2 * list.overscan;
+        let mut offset = 0.0;
+        while offset <= list.max_scroll_offset() {
+            list.scroll_by(offset - list.scroll_offset());
+            let mut batch = Vec::new();
+            list.render(|row| {
+                batch.push(("rect", row.index));
+                batch.push(("text", row.index));
+            });
+            assert!(!batch.is_empty());
+            assert!(batch.len() <= 2 * max_rows, "{} items", batch.len());
+            offset += 997.0;
+        }
+    }
+
+    #[test]
+    fn visible_rows_cover_the_viewport() {
+        let mut list = VirtualList::new(0.0, 0.0, 200.0, 100.0);
+        list.overscan = 0;
+        list.sync(1_000, height);
+        list.scroll_by(1_234.0);
+
+        let mut rows = Vec::new();
+        list.render(|row| rows.push(row));
+        let first = rows.first().unwrap();
+        let last = rows.last().unwrap();
+        assert!(first.y <= 0.0 && first.y + first.height > 0.0);
+        assert!(last.y < 100.0 && last.y + last.height >= 100.0);
+        assert!(rows.windows(2).all(|w| w[1].index == w[0].index + 1));
+    }
+
+    #[test]
+    fn hit_testing_and_selection_follow_the_scroll_offset() {
+        let mut list = VirtualList::new(10.0, 50.0, 200.0, 100.0);
+        list.sync(100, |_| 25.0);
+        assert_eq!(list.item_at(20.0, 50.0), Some(0));
+        assert_eq!(list.item_at(20.0, 49.0), None);
+
+        list.scroll_by(60.0);
+        // Content y = 60 + 10 = 70 -> row 2
+        assert_eq!(list.handle_click(20.0, 60.0), Some(2));
+        assert_eq!(list.selected(), Some(2));
+
+        let mut selected = Vec::new();
+        list.render(|row| {
+            if row.selected {
+                selected.push((row.index, row.y));
+            }
+        });
+        assert_eq!(selected, vec![(2, 50.0 + 50.0 - 60.0)]);
+
+        list.scroll_to_item(99);
+        assert_eq!(list.scroll_offset(), list.max_scroll_offset());
+        assert_eq!(list.item_at(20.0, 149.0), Some(99));
+
+        // Shrinking the list drops a selection that no longer exists
+        list.sync(2, |_| 25.0);
+        assert_eq!(list.selected(), None);
+        assert_eq!(list.scroll_offset(), 0.0);
+    }
+}
@@ -1,25 +1,45 @@
-struct WidgetUniforms {
-    color: vec4<f32>,
-    rect: vec4<f32>,      // x, y, width, height (in pixels)
-    corner_radius: f32,
-    viewport_width: f32,
-    viewport_height: f32,
-    _padding: f32,
+struct Viewport {
+    size: vec2<f32>,      // viewport width, height (in pixels)
+    _padding: vec2<f32>,
 };
 
-var<push_constant> uniforms: WidgetUniforms;
+var<push_constant> viewport: Viewport;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) rect: vec4<f32>,
+    @location(1) @interpolate(flat) color: vec4<f32>,
+    @location(2) @interpolate(flat) corner_radius: f32,
+};
 
 @vertex
-fn vs_main(@location(0) position: vec2<f32>) -> @builtin(position) vec4<f32> {
-    return vec4<f32>(position, 0.0, 1.0);
+fn vs_main(
+    @location(0) corner: vec2<f32>,
+    @location(1) rect: vec4<f32>,      // x, y, width, height (in pixels)
+    @location(2) color: vec4<f32>,
+    @location(3) corner_radius: f32,
+) -> VertexOutput {
+    // Cover the rect plus a 1px margin for the anti-aliased edge
+    let pixel = rect.xy - vec2<f32>(1.0) + corner * (rect.zw + vec2<f32>(2.0));
+    let ndc = vec2<f32>(
+        pixel.x / viewport.size.x * 2.0 - 1.0,
+        1.0 - pixel.y / viewport.size.y * 2.0,
+    );
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc, 0.0, 1.0);
+    out.rect = rect;
+    out.color = color;
+    out.corner_radius = corner_radius;
+    return out;
 }
 
 @fragment
-fn fs_main(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
-    let pos = frag_coord.xy;
-    let rect_min = uniforms.rect.xy;
-    let rect_max = uniforms.rect.xy + uniforms.rect.zw;
-    let r = uniforms.corner_radius;
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let pos = in.position.xy;
+    let rect_min = in.rect.xy;
+    let rect_max = in.rect.xy + in.rect.zw;
+    let r = in.corner_radius;
 
     // Outside rect bounds entirely - discard
     if pos.x < rect_min.x || pos.x > rect_max.x || pos.y < rect_min.y || pos.y > rect_max.y {
@@ -31,5 +51,5 @@ fn fs_main(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
     let dist = length(pos - center);
     let alpha = 1.0 - smoothstep(r - 0.5, r + 0.5, dist);
 
-    return vec4<f32>(uniforms.color.rgb, uniforms.color.a * alpha);
+    return vec4<f32>(in.color.rgb, in.color.a * alpha);
 }
@@ -1,25 +1,76 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use wgpu::{self, util::DeviceExt};
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct WidgetUniforms {
-    color: [f32; 4],
+struct ViewportUniforms {
+    size: [f32; 2], // viewport width, height in pixels
+    _padding: [f32; 2],
+}
+
+/// Per-rect instance data, consumed by one instanced draw per flush
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct RectInstance {
     rect: [f32; 4], // x, y, width, height in pixels
+    color: [f32; 4],
     corner_radius: f32,
-    viewport_width: f32,
-    viewport_height: f32,
-    _padding: f32,
+    _padding: [f32; 3],
+}
+
+/// Draw call and rect counts accumulated since the last `take_draw_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WidgetDrawStats {
+    pub draw_calls: u32,
+    pub rects: u32,
 }
 
-struct PendingRect {
-    uniforms: WidgetUniforms,
+/// Logs average widget draw calls per frame every few seconds (debug stats)
+pub struct DrawStatsReporter {
+    label: &'static str,
+    frames: u32,
+    totals: WidgetDrawStats,
+    since: std::time::Instant,
+}
+
+impl DrawStatsReporter {
+    const REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+    pub fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            frames: 0,
+            totals: WidgetDrawStats::default(),
+            since: std::time::Instant::now(),
+        }
+    }
+
+    pub fn record_frame(&mut self, frame: WidgetDrawStats) {
+        self.frames += 1;
+        self.totals.draw_calls += frame.draw_calls;
+        self.totals.rects += frame.rects;
+
+        if self.since.elapsed() >= Self::REPORT_INTERVAL {
+            let frames = self.frames.max(1) as f32;
+            tracing::debug!(
+                "{} UI stats: {:.1} widget draw calls/frame, {:.1} rects/frame over {} frames",
+                self.label,
+                self.totals.draw_calls as f32 / frames,
+                self.totals.rects as f32 / frames,
+                self.frames
+            );
+            *self = Self::new(self.label);
+        }
+    }
 }
 
 pub struct WidgetRenderer {
+    device: wgpu::Device,
     pipeline: wgpu::RenderPipeline,
     vertices: wgpu::Buffer,
-    pending_rects: RefCell<Vec<PendingRect>>,
+    pending_rects: RefCell<Vec<RectInstance>>,
+    stats: Cell<WidgetDrawStats>,
+    render_scale: f32,
 }
 
 impl WidgetRenderer {
@@ -33,8 +84,8 @@ impl WidgetRenderer {
             label: Some("Widget Pipeline Layout"),
             bind_group_layouts: &[],
             push_constant_ranges: &[wgpu::PushConstantRange {
-                stages: wgpu::ShaderStages::FRAGMENT,
-                range: 0..std::mem::size_of::<WidgetUniforms>() as u32,
+                stages: wgpu::ShaderStages::VERTEX,
+                range: 0..std::mem::size_of::<ViewportUniforms>() as u32,
             }],
         });
 
@@ -44,11 +95,22 @@ impl WidgetRenderer {
             vertex: wgpu::VertexState {
                 module: &shader,
                 entry_point: Some("vs_main"),
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: 8,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    attributes: &wgpu::vertex_attr_array![0 => Float32x2],
-                }],
+                buffers: &[
+                    wgpu::VertexBufferLayout {
+                        array_stride: 8,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                    },
+                    wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<RectInstance>() as u64,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![
+                            1 => Float32x4,
+                            2 => Float32x4,
+                            3 => Float32
+                        ],
+                    },
+                ],
                 compilation_options: Default::default(),
             },
             fragment: Some(wgpu::FragmentState {
@@ -79,21 +141,36 @@ impl WidgetRenderer {
         let vertices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Widget Vertices"),
             contents: bytemuck::cast_slice(&[
-                -1.0f32, -1.0, // bottom-left
-                1.0, -1.0, // bottom-right
-                -1.0, 1.0, // top-left
-                1.0, 1.0, // top-right
+                0.0f32, 1.0, // bottom-left
+                1.0, 1.0, // bottom-right
+                0.0, 0.0, // top-left
+                1.0, 0.0, // top-right
             ]),
             usage: wgpu::BufferUsages::VERTEX,
         });
 
         Self {
+            device: device.clone(),
             pipeline,
             vertices,
             pending_rects: RefCell::new(Vec::new()),
+            stats: Cell::new(WidgetDrawStats::default()),
+            render_scale: 1.0,
         }
     }
 
+    /// Render into a target drawn at `scale` times the logical size; rects are
+    /// still queued in logical pixels and scaled on flush
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+    }
+
+    /// Return and reset the draw statistics (call once per frame)
+    pub fn take_draw_stats(&self) -> WidgetDrawStats {
+        self.stats.take()
+    }
+
+    /// Queue a rounded rect; nothing is drawn until `flush`
     pub fn draw_rounded_rect(
         &self,
         _encoder: &mut wgpu::CommandEncoder,
@@ -105,21 +182,18 @@ impl WidgetRenderer {
         height: f32,
         corner_radius: f32,
         color: [f32; 4],
-        window_width: u32,
-        window_height: u32,
+        _window_width: u32,
+        _window_height: u32,
     ) {
-        self.pending_rects.borrow_mut().push(PendingRect {
-            uniforms: WidgetUniforms {
-                color,
-                rect: [x, y, width, height],
-                corner_radius,
-                viewport_width: window_width as f32,
-                viewport_height: window_height as f32,
-                _padding: 0.0,
-            },
+        self.pending_rects.borrow_mut().push(RectInstance {
+            rect: [x, y, width, height],
+            color,
+            corner_radius,
+            _padding: [0.0; 3],
         });
     }
 
+    /// Draw all queued rects in submission order with a single instanced draw
     pub fn flush(
         &self,
         encoder: &mut wgpu::CommandEncoder,
@@ -127,11 +201,29 @@ impl WidgetRenderer {
         window_width: u32,
         window_height: u32,
     ) {
-        let rects = self.pending_rects.borrow();
+        let mut rects = self.pending_rects.borrow_mut();
         if rects.is_empty() {
             return;
         }
 
+        let scale = self.render_scale;
+        if scale != 1.0 {
+            for instance in rects.iter_mut() {
+                instance.rect = instance.rect.map(|value| value * scale);
+                instance.corner_radius *= scale;
+            }
+        }
+        let (target_width, target_height) =
+            crate::ui::render_scale::scaled_size(window_width, window_height, scale);
+
+        let instances = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Widget Instances"),
+                contents: bytemuck::cast_slice(&rects[..]),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Widget Render Pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -150,25 +242,30 @@ impl WidgetRenderer {
         render_pass.set_viewport(
             0.0,
             0.0,
-            window_width as f32,
-            window_height as f32,
+            target_width as f32,
+            target_height as f32,
             0.0,
             1.0,
         );
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_vertex_buffer(0, self.vertices.slice(..));
+        render_pass.set_vertex_buffer(1, instances.slice(..));
+        render_pass.set_push_constants(
+            wgpu::ShaderStages::VERTEX,
+            0,
+            bytemuck::cast_slice(&[ViewportUniforms {
+                size: [target_width as f32, target_height as f32],
+                _padding: [0.0; 2],
+            }]),
+        );
+        render_pass.draw(0..4, 0..rects.len() as u32);
+        drop(render_pass);
 
-        for rect in rects.iter() {
-            render_pass.set_push_constants(
-                wgpu::ShaderStages::FRAGMENT,
-                0,
-                bytemuck::cast_slice(&[rect.uniforms]),
-            );
-            render_pass.draw(0..4, 0..1);
-        }
+        let mut stats = self.stats.get();
+        stats.draw_calls += 1;
+        stats.rects += rects.len() as u32;
+        self.stats.set(stats);
 
-        drop(render_pass);
-        drop(rects);
-        self.pending_rects.borrow_mut().clear();
+        rects.clear();
     }
 }
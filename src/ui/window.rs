@@ -1,3 +1,4 @@
+use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -12,18 +13,21 @@ use winit::{
 use super::button_panel::ButtonPanel;
 use super::buttons::ButtonManager;
 use super::event_handler::EventHandler;
+use super::help_overlay::HelpOverlay;
 use super::layout_manager::LayoutManager;
 use super::loading_animation::LoadingAnimation;
-use super::render_pipeline::RenderPipelines;
+use super::render_pipeline::{HoverUniforms, RenderPipelines};
+use super::render_scale::{self, ScaledTarget};
 use super::scroll_state::ScrollState;
 use super::scrollbar::Scrollbar;
 use super::spectogram::Spectrogram;
 use super::status_bar::StatusBar;
 use super::text_processor::TextProcessor;
 use super::text_window::TextWindow;
+use super::theme::Theme;
 use super::timer_badge::TimerBadge;
 use super::tooltip::Tooltip;
-use crate::config::{DisplayConfig, UiConfig};
+use crate::config::{DisplayConfig, ThemeConfig, UiConfig};
 use parking_lot::RwLock;
 use speechcore::{AudioVisualizationData, BackendStatus, BackendStatusState, ProcessingState};
 
@@ -34,11 +38,46 @@ pub const MARGIN: i32 = 32; // Margin from the bottom of the screen
 pub const GAP: u32 = 0; // Gap replaced by status bar top border
 pub const RIGHT_MARGIN: f32 = 4.0; // Right margin for text area
 pub const LEFT_MARGIN: f32 = 4.0; // Left margin for text area
+/// How often an idle overlay checks whether the data it shows has changed
+pub const IDLE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
 
 fn target_frame_duration(target_fps: u32) -> std::time::Duration {
     std::time::Duration::from_secs_f64(1.0 / target_fps.max(1) as f64)
 }
 
+/// Request an adapter and device for `surface`; `force_fallback` selects a software adapter (llvmpipe)
+fn acquire_gpu(
+    instance: &wgpu::Instance,
+    surface: &wgpu::Surface<'_>,
+    force_fallback: bool,
+) -> Result<(wgpu::Adapter, wgpu::Device, wgpu::Queue), String> {
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::default(),
+        compatible_surface: Some(surface),
+        force_fallback_adapter: force_fallback,
+    }))
+    .map_err(|e| format!("No suitable GPU adapter found: {e}"))?;
+
+    let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        label: None,
+        required_features: wgpu::Features::PUSH_CONSTANTS,
+        required_limits: wgpu::Limits {
+            max_push_constant_size: 128,
+            ..wgpu::Limits::default()
+        },
+        memory_hints: wgpu::MemoryHints::default(),
+        trace: wgpu::Trace::Off,
+    }))
+    .map_err(|e| {
+        format!(
+            "Failed to request GPU device on '{}': {e}",
+            adapter.get_info().name
+        )
+    })?;
+
+    Ok((adapter, device, queue))
+}
+
 fn present_mode_for(
     display_config: &DisplayConfig,
     available_modes: &[wgpu::PresentMode],
@@ -65,14 +104,14 @@ fn present_mode_for(
     if available_modes.contains(&preferred) {
         preferred
     } else if available_modes.contains(&wgpu::PresentMode::Fifo) {
-        println!(
-            "Warning: Preferred vsync mode '{}' not available, falling back to Fifo",
+        tracing::warn!(
+            "Preferred vsync mode '{}' not available, falling back to Fifo",
             display_config.vsync_mode
         );
         wgpu::PresentMode::Fifo
     } else {
-        println!(
-            "Warning: Preferred vsync mode '{}' not available, using first available mode",
+        tracing::warn!(
+            "Preferred vsync mode '{}' not available, using first available mode",
             display_config.vsync_mode
         );
         available_modes
@@ -106,6 +145,7 @@ pub struct WindowState {
     pub event_handler: EventHandler,
     pub loading_animation: LoadingAnimation,
     pub timer_badge: TimerBadge,
+    pub help_overlay: HelpOverlay,
     pub backend_command_tx: Option<tokio::sync::mpsc::UnboundedSender<speechcore::BackendCommand>>,
     pub running: Option<Arc<AtomicBool>>,
     pub recording: Option<Arc<AtomicBool>>,
@@ -125,18 +165,28 @@ pub struct WindowState {
     last_frame_time: Option<std::time::Instant>,
     target_frame_duration: std::time::Duration,
     present_mode: wgpu::PresentMode,
+    // Reduced-resolution rendering (display_config.render_scale)
+    configured_render_scale: Option<f32>,
+    render_scale: f32,
+    scaled_target: Option<ScaledTarget>,
     // Hover animation state
     hover_animation_progress: f32, // 0.0 to 1.0
     _is_hovering: bool,
     last_hover_update: std::time::Instant,
+    // Pointer events pass through the overlay; no hover rendering
+    click_through: bool,
     // Typewriter effect for transcription reveal
     typewriter: super::typewriter::TypewriterEffect,
     ui_config: UiConfig,
+    theme: Theme,
     typewriter_enabled: bool,
     last_processing_state: ProcessingState,
     // Reusable buffers to avoid per-frame allocations
     silence_buffer: Vec<f32>,
     frame_samples: Vec<f32>,
+    // On-demand redraw: frames follow each other only while something moves
+    animating: bool,
+    shown_fingerprint: u64,
 }
 
 impl WindowState {
@@ -150,6 +200,7 @@ impl WindowState {
         transcription_mode_ref: Arc<std::sync::atomic::AtomicU8>,
         display_config: &DisplayConfig,
         ui_config: &UiConfig,
+        theme_config: &ThemeConfig,
         window_width: u32,
         window_height: u32,
         spectrogram_width: u32,
@@ -161,7 +212,7 @@ impl WindowState {
         model_name: &str,
         external_backend_status: Option<Arc<RwLock<BackendStatus>>>,
         backend_command_tx: Option<tokio::sync::mpsc::UnboundedSender<speechcore::BackendCommand>>,
-    ) -> Self {
+    ) -> SResult<Self, String> {
         let window: Arc<dyn Window> = Arc::from(window);
 
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
@@ -169,30 +220,27 @@ impl WindowState {
             ..Default::default()
         });
 
-        let surface = instance.create_surface(window.clone()).expect(
-            "Failed to create GPU surface. Ensure your display server and GPU drivers are working.",
-        );
-
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
-            compatible_surface: Some(&surface),
-            force_fallback_adapter: false,
-        }))
-        .expect("No suitable GPU adapter found. Ensure Vulkan drivers are installed.");
-
-        let (device, queue) = pollster::block_on(adapter.request_device(
-            &wgpu::DeviceDescriptor {
-                label: None,
-                required_features: wgpu::Features::PUSH_CONSTANTS,
-                required_limits: wgpu::Limits {
-                    max_push_constant_size: 128,
-                    ..wgpu::Limits::default()
-                },
-                memory_hints: wgpu::MemoryHints::default(),
-                trace: wgpu::Trace::Off,
-            },
-        ))
-        .expect("Failed to request GPU device. Ensure Vulkan drivers are installed and GPU is available.");
+        let surface = instance.create_surface(window.clone()).map_err(|e| {
+            format!(
+                "Failed to create GPU surface: {e}. Ensure your display server and GPU drivers are working."
+            )
+        })?;
+
+        let (adapter, device, queue) = match acquire_gpu(&instance, &surface, false) {
+            Ok(gpu) => gpu,
+            Err(e) if display_config.allow_software_rendering => {
+                tracing::warn!("{e}; trying software rendering");
+                let gpu = acquire_gpu(&instance, &surface, true)
+                    .map_err(|fallback| format!("{e}; software fallback also failed: {fallback}"))?;
+                tracing::info!("Using software renderer: {}", gpu.0.get_info().name);
+                gpu
+            }
+            Err(e) => {
+                return Err(format!(
+                    "{e}. Ensure Vulkan drivers are installed, or set display_config.allow_software_rendering = true."
+                ))
+            }
+        };
 
         // Use dynamic sizing values
         let fixed_width = window_width;
@@ -232,8 +280,12 @@ impl WindowState {
             // On X11, transparency may still work through the compositor
             let is_wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
             if !is_wayland && surface_caps.alpha_modes[0] == wgpu::CompositeAlphaMode::Opaque {
-                eprintln!("Warning: GPU reports only Opaque alpha mode. Window transparency may not work.");
-                eprintln!("Try: enabling compositor, or using a different compositor like picom.");
+                tracing::warn!(
+                    "GPU reports only Opaque alpha mode. Window transparency may not work."
+                );
+                tracing::warn!(
+                    "Try: enabling compositor, or using a different compositor like picom."
+                );
             }
             surface_caps.alpha_modes[0]
         };
@@ -251,6 +303,9 @@ impl WindowState {
 
         surface.configure(&device, &config);
 
+        let render_scale =
+            render_scale::render_scale_for_adapter(display_config.render_scale, &adapter);
+
         // Create render pipelines
         let render_pipelines = RenderPipelines::new(&device, &config);
 
@@ -372,11 +427,19 @@ impl WindowState {
             ui_config,
         );
 
+        // Create quick help overlay (hidden until requested)
+        let help_overlay = HelpOverlay::new(
+            &device,
+            &queue,
+            PhysicalSize::new(config.width, config.height),
+            config.format,
+        );
+
         // Calculate target frame duration from display config
         let target_frame_duration = target_frame_duration(display_config.target_fps);
         let typewriter_enabled = ui_config.effective_typewriter_enabled();
 
-        Self {
+        let mut state = Self {
             window,
             instance,
             adapter,
@@ -409,6 +472,9 @@ impl WindowState {
             // Timer badge
             timer_badge,
 
+            // Quick help overlay
+            help_overlay,
+
             backend_command_tx,
 
             // Transcriber state references
@@ -433,23 +499,77 @@ impl WindowState {
             target_frame_duration,
             present_mode,
 
+            // Render scale is applied below, once every component exists
+            configured_render_scale: display_config.render_scale,
+            render_scale: render_scale::MAX_RENDER_SCALE,
+            scaled_target: None,
+
             // Hover animation state
             hover_animation_progress: 0.0,
             _is_hovering: false,
             last_hover_update: std::time::Instant::now(),
+            click_through: false,
 
             // Typewriter effect
             typewriter: super::typewriter::TypewriterEffect::new(),
             ui_config: ui_config.clone(),
+            theme: Theme::from_config(theme_config, ui_config),
             typewriter_enabled,
             last_processing_state: ProcessingState::Idle,
 
             // Reusable buffers
             silence_buffer: vec![0.0; 1024],
             frame_samples: Vec::with_capacity(1024),
+
+            animating: true,
+            shown_fingerprint: 0,
+        };
+        state.set_render_scale(render_scale);
+        Ok(state)
+    }
+
+    /// Render the overlay at `scale` times the window size.
+    ///
+    /// Below 1.0 the components draw into an offscreen target that `draw`
+    /// stretches onto the surface; layout and hit-testing are unaffected.
+    fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale;
+        self.scaled_target = (scale < render_scale::MAX_RENDER_SCALE).then(|| {
+            ScaledTarget::new(
+                &self.device,
+                self.config.format,
+                self.config.width,
+                self.config.height,
+                scale,
+            )
+        });
+
+        self.render_pipelines.render_scale = scale;
+        self.scrollbar.render_scale = scale;
+        self.text_window.set_render_scale(scale);
+        self.status_bar.set_render_scale(scale);
+        self.button_manager.set_render_scale(scale);
+        self.button_panel.set_render_scale(scale);
+        self.tooltip.set_render_scale(scale);
+        self.loading_animation.set_render_scale(scale);
+        self.help_overlay.set_render_scale(scale);
+
+        let spectrogram_size = self.spectrogram_render_size();
+        if let Some(spectrogram) = &mut self.spectrogram {
+            spectrogram.resize(spectrogram_size);
         }
     }
 
+    /// The spectrogram draws one bar per target pixel, so its buffers shrink with the scale
+    fn spectrogram_render_size(&self) -> PhysicalSize<u32> {
+        let (width, height) = render_scale::scaled_size(
+            self.spectrogram_width,
+            self.spectrogram_height,
+            self.render_scale,
+        );
+        PhysicalSize::new(width, height)
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
         if width > 0 && height > 0 {
             self.config.width = width;
@@ -470,17 +590,19 @@ impl WindowState {
             self.layout_manager.spectrogram_height = self.spectrogram_height;
             self.layout_manager.text_area_height = self.text_area_height;
 
+            let spectrogram_size = self.spectrogram_render_size();
             if let Some(spectrogram) = &mut self.spectrogram {
-                spectrogram.resize(PhysicalSize::new(
-                    self.spectrogram_width,
-                    self.spectrogram_height,
-                ));
+                spectrogram.resize(spectrogram_size);
+            }
+            if let Some(target) = &mut self.scaled_target {
+                target.resize(&self.device, width, height, self.render_scale);
             }
 
             self.text_window.resize(PhysicalSize::new(width, height));
             self.status_bar.resize(PhysicalSize::new(width, height));
             self.button_manager.resize(PhysicalSize::new(width, height));
             self.button_panel.resize(PhysicalSize::new(width, height));
+            self.help_overlay.resize(PhysicalSize::new(width, height));
         }
     }
 
@@ -490,7 +612,7 @@ impl WindowState {
         // Initialize spectrogram if not already created
         if self.spectrogram.is_none() {
             // Create the spectrogram with the dedicated spectrogram size, not the full window size
-            let size = PhysicalSize::new(self.spectrogram_width, self.spectrogram_height);
+            let size = self.spectrogram_render_size();
             let spectrogram = Spectrogram::new(
                 Arc::new(self.device.clone()),
                 Arc::new(self.queue.clone()),
@@ -498,18 +620,30 @@ impl WindowState {
                 self.config.format,
             );
             let mut spectrogram = spectrogram;
-            spectrogram.apply_ui_config(&self.ui_config);
+            spectrogram.apply_ui_config(&self.ui_config, &self.theme);
             self.spectrogram = Some(spectrogram);
         }
     }
 
-    pub fn apply_runtime_config(&mut self, display_config: &DisplayConfig, ui_config: &UiConfig) {
+    pub fn apply_runtime_config(
+        &mut self,
+        display_config: &DisplayConfig,
+        ui_config: &UiConfig,
+        theme_config: &ThemeConfig,
+    ) {
         self.target_frame_duration = target_frame_duration(display_config.target_fps);
+        if display_config.render_scale != self.configured_render_scale {
+            self.configured_render_scale = display_config.render_scale;
+            let scale =
+                render_scale::render_scale_for_adapter(display_config.render_scale, &self.adapter);
+            self.set_render_scale(scale);
+        }
         self.typewriter_enabled = ui_config.effective_typewriter_enabled();
         self.status_bar.apply_ui_config(ui_config);
         self.timer_badge.apply_ui_config(ui_config);
+        self.theme = Theme::from_config(theme_config, ui_config);
         if let Some(spectrogram) = &mut self.spectrogram {
-            spectrogram.apply_ui_config(ui_config);
+            spectrogram.apply_ui_config(ui_config, &self.theme);
         }
         self.ui_config = ui_config.clone();
         self.window.request_redraw();
@@ -546,7 +680,8 @@ impl WindowState {
 
         // Smooth animation: 0.0 to 1.0 over ~300ms
         let animation_speed = 3.5; // Units per second
-        if self.event_handler.hovering_transcript {
+        let hovering = self.event_handler.hovering_transcript && !self.click_through;
+        if hovering {
             // Fade in when hovering
             self.hover_animation_progress =
                 (self.hover_animation_progress + delta_time * animation_speed).min(1.0);
@@ -563,30 +698,34 @@ impl WindowState {
                 match self.surface.get_current_texture() {
                     Ok(output) => output,
                     Err(e) => {
-                        eprintln!("Failed to get surface texture after reconfigure: {:?}", e);
+                        tracing::warn!("Failed to get surface texture after reconfigure: {:?}", e);
                         return;
                     }
                 }
             }
             Err(wgpu::SurfaceError::Timeout) => {
-                eprintln!("Surface texture acquisition timed out");
+                tracing::warn!("Surface texture acquisition timed out");
                 return;
             }
             Err(wgpu::SurfaceError::OutOfMemory) => {
-                eprintln!("GPU out of memory; shutting down gracefully");
+                tracing::error!("GPU out of memory; shutting down gracefully");
                 if let Some(running) = &self.running {
                     running.store(false, Ordering::Relaxed);
                 }
                 return;
             }
             Err(e) => {
-                eprintln!("Surface error: {:?}", e);
+                tracing::warn!("Surface error: {:?}", e);
                 return;
             }
         };
-        let view = output
+        let surface_view = output
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
+        let view = match &self.scaled_target {
+            Some(target) => target.view(),
+            None => &surface_view,
+        };
 
         let mut encoder = self
             .device
@@ -595,20 +734,26 @@ impl WindowState {
             });
 
         // First clear the screen to transparent
-        self.render_pipelines.draw_background(&mut encoder, &view);
-
-        // Update hover animation uniform buffer
+        self.render_pipelines.draw_background(&mut encoder, view);
+
+        // Update hover animation and theme color uniform buffer
+        let hover_uniforms = HoverUniforms {
+            opacity_multiplier: self.hover_animation_progress,
+            _padding: [0.0; 3],
+            panel_color: self.theme.panel_bg,
+            button_color: self.theme.button_bg,
+        };
         self.queue.write_buffer(
             &self.render_pipelines.hover_uniform_buffer,
             0,
-            bytemuck::cast_slice(&[self.hover_animation_progress]),
+            bytemuck::bytes_of(&hover_uniforms),
         );
 
         // Draw the rounded rectangle background for the spectrogram only
         let (spec_x, spec_y, spec_w, spec_h) = self.layout_manager.get_spectrogram_position();
         self.render_pipelines.draw_spectrogram_background(
             &mut encoder,
-            &view,
+            view,
             spec_x,
             spec_y,
             spec_w,
@@ -645,7 +790,7 @@ impl WindowState {
 
         // Always ensure the spectrogram is initialized
         if self.spectrogram.is_none() {
-            let size = PhysicalSize::new(self.spectrogram_width, self.spectrogram_height);
+            let size = self.spectrogram_render_size();
             let spectrogram = Spectrogram::new(
                 Arc::new(self.device.clone()),
                 Arc::new(self.queue.clone()),
@@ -653,7 +798,7 @@ impl WindowState {
                 self.config.format,
             );
             let mut spectrogram = spectrogram;
-            spectrogram.apply_ui_config(&self.ui_config);
+            spectrogram.apply_ui_config(&self.ui_config, &self.theme);
             self.spectrogram = Some(spectrogram);
         }
 
@@ -663,7 +808,13 @@ impl WindowState {
                 let audio_data_lock = audio_data.read();
                 is_speaking = is_recording && audio_data_lock.is_speaking;
                 let transcript_ref = &audio_data_lock.transcript;
-                display_text = self.text_processor.clean_whitespace(transcript_ref);
+                let cleaned = self.text_processor.clean_whitespace(transcript_ref);
+                // Older text was spilled to disk; mark the cut at the top
+                display_text = if crate::transcript_spill::is_truncated() && !cleaned.is_empty() {
+                    format!("… {}", cleaned)
+                } else {
+                    cleaned.to_owned()
+                };
                 processing_state = audio_data_lock.processing_state;
 
                 // Sync processing state to status bar
@@ -694,7 +845,7 @@ impl WindowState {
                 drop(audio_data_lock);
             } else {
                 if is_recording {
-                    display_text = "Sonori is ready".to_string();
+                    display_text = crate::i18n::tr("overlay.ready").to_string();
                 }
                 is_speaking = false;
                 self.scroll_state.reset();
@@ -714,7 +865,7 @@ impl WindowState {
                 let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     label: Some("Spectrogram Render Pass"),
                     color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &view,
+                        view,
                         resolve_target: None,
                         ops: wgpu::Operations {
                             load: wgpu::LoadOp::Load, // Load existing content
@@ -728,7 +879,14 @@ impl WindowState {
 
                 // Set the viewport using the layout manager
                 let (x, y, width, height) = self.layout_manager.get_spectrogram_position();
-                render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+                render_scale::set_viewport(
+                    &mut render_pass,
+                    self.render_scale,
+                    x,
+                    y,
+                    width,
+                    height,
+                );
 
                 // Use the custom render pass
                 spectrogram.render_with_custom_pass(&mut render_pass);
@@ -773,7 +931,7 @@ impl WindowState {
         self.scroll_state.auto_scroll = self.event_handler.auto_scroll;
 
         // Update with auto-scroll animation
-        self.scroll_state.update_with_auto_scroll();
+        let scrolled = self.scroll_state.update_with_auto_scroll();
 
         // Sync scrollbar state
         self.scrollbar.max_scroll_offset = self.scroll_state.max_scroll_offset;
@@ -831,9 +989,9 @@ impl WindowState {
             self.loading_animation
                 .get_processing_color(processing_state)
         } else if is_speaking {
-            self.ui_config.effective_speaking_color()
+            self.theme.text_speaking
         } else {
-            self.ui_config.effective_idle_color()
+            self.theme.text_idle
         };
 
         // Render loading animation if processing, otherwise render text
@@ -845,7 +1003,7 @@ impl WindowState {
             // Render text window background first
             self.text_window.render_background(
                 &mut encoder,
-                &view,
+                view,
                 text_area_width,
                 text_area_height,
                 self.gap,
@@ -861,7 +1019,7 @@ impl WindowState {
 
             self.loading_animation.render(
                 &mut encoder,
-                &view,
+                view,
                 center_x,
                 center_y,
                 animation_size,
@@ -876,7 +1034,7 @@ impl WindowState {
 
             self.text_window.render_text_only(
                 &mut encoder,
-                &view,
+                view,
                 processing_text,
                 text_area_width,
                 text_area_height,
@@ -890,7 +1048,7 @@ impl WindowState {
             // Render text window (background and text) normally
             self.text_window.render(
                 &mut encoder,
-                &view,
+                view,
                 render_text,
                 text_area_width,
                 text_area_height,
@@ -907,14 +1065,14 @@ impl WindowState {
         {
             let (sb_x, sb_y, sb_w, sb_h) = self.layout_manager.get_status_bar_position();
             self.status_bar
-                .render(&mut encoder, &view, &self.queue, sb_x, sb_y, sb_w, sb_h);
+                .render(&mut encoder, view, &self.queue, sb_x, sb_y, sb_w, sb_h);
         }
 
         // Draw scrollbar only if needed
         if need_scrollbar {
             // Use the scrollbar component to render
             self.scrollbar.render(
-                &view,
+                view,
                 &mut encoder,
                 self.config.width,
                 text_area_height,
@@ -923,13 +1081,13 @@ impl WindowState {
         }
 
         // Update button panel animation based on hover state
-        self.button_panel
-            .set_visible(self.event_handler.hovering_transcript);
+        let hovering = self.event_handler.hovering_transcript && !self.click_through;
+        self.button_panel.set_visible(hovering);
         self.button_panel.update();
 
         // Render the buttons after the text - only when hovering over transcript
         // First make sure the RecordToggle button texture is up-to-date
-        if self.event_handler.hovering_transcript {
+        if hovering {
             // Update RecordToggle button texture based on recording state
             self.button_manager.update_record_toggle_button_texture();
 
@@ -944,13 +1102,13 @@ impl WindowState {
             // Render button panel backgrounds before buttons
             // Two separate panels: one for bottom buttons, one for close button
             self.button_panel.render_with_bounds(
-                &view,
+                view,
                 &mut encoder,
                 bottom_button_bounds,
                 &self.render_pipelines.hover_bind_group,
             );
             self.button_panel.render_with_bounds(
-                &view,
+                view,
                 &mut encoder,
                 close_button_bounds,
                 &self.render_pipelines.hover_bind_group,
@@ -958,11 +1116,11 @@ impl WindowState {
 
             // Only render buttons when hovering over transcript area
             self.button_manager
-                .render(&view, &mut encoder, true, &self.queue);
+                .render(view, &mut encoder, true, &self.queue);
 
             // Render tooltip (after buttons, so it appears on top)
             self.tooltip
-                .render(&view, &mut encoder, self.window_width, self.window_height);
+                .render(view, &mut encoder, self.window_width, self.window_height);
         } else {
             // Not hovering, hide tooltip
             self.tooltip.update(None);
@@ -970,6 +1128,22 @@ impl WindowState {
 
         // Timer badge rendering moved to status bar (recording indicator on right side)
 
+        // Quick help draws last so it covers every other component
+        let help_scale = text_scale.min(1.2) * 0.9;
+        self.help_overlay.render(
+            &mut encoder,
+            view,
+            &self.queue,
+            self.window_width,
+            self.window_height,
+            help_scale,
+        );
+
+        // Stretch the reduced-resolution frame onto the surface
+        if let Some(target) = &self.scaled_target {
+            target.blit(&mut encoder, &surface_view);
+        }
+
         // Submit all rendering commands
         self.queue.submit(std::iter::once(encoder.finish()));
         output.present();
@@ -979,9 +1153,71 @@ impl WindowState {
             self.last_frame_time = Some(std::time::Instant::now());
         }
 
-        // ALWAYS request redraw to keep animation loop going
-        // This ensures spectrogram decay animation continues when paused
-        self.window.request_redraw();
+        // Keep the frame loop going only while something moves; an idle overlay
+        // is redrawn by input events and by `poll_idle` when its data changes
+        self.animating = is_recording
+            || matches!(
+                processing_state,
+                ProcessingState::Loading | ProcessingState::Transcribing
+            )
+            || hovering
+            || scrolled
+            || self.typewriter.is_active()
+            || self.button_panel.is_animating()
+            || self.status_bar.is_animating()
+            || (self.hover_animation_progress > 0.0 && self.hover_animation_progress < 1.0)
+            || self
+                .spectrogram
+                .as_ref()
+                .is_some_and(|spectrogram| !spectrogram.is_settled());
+        if self.animating {
+            self.window.request_redraw();
+        } else {
+            self.shown_fingerprint = self.idle_fingerprint();
+        }
+    }
+
+    /// Hash of what an idle frame shows that can change without an input event
+    fn idle_fingerprint(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        if let Some(audio_data) = &self.audio_data {
+            let audio_data = audio_data.read();
+            audio_data.transcript.hash(&mut hasher);
+            std::mem::discriminant(&audio_data.processing_state).hash(&mut hasher);
+        }
+        self.recording
+            .as_ref()
+            .map(|recording| recording.load(Ordering::Relaxed))
+            .hash(&mut hasher);
+        self.transcription_mode_ref
+            .load(Ordering::Relaxed)
+            .hash(&mut hasher);
+        {
+            let status = self.backend_status.read();
+            match &status.state {
+                BackendStatusState::Ready => 0u8.hash(&mut hasher),
+                BackendStatusState::Loading(msg) => (1u8, msg).hash(&mut hasher),
+                BackendStatusState::Error(msg) => (2u8, msg).hash(&mut hasher),
+            }
+            status.download_progress.map(f32::to_bits).hash(&mut hasher);
+        }
+        crate::toast::current().hash(&mut hasher);
+        crate::audio_devices::input_loss()
+            .map(|loss| loss as u8)
+            .hash(&mut hasher);
+        crate::audio_devices::device_open().hash(&mut hasher);
+        crate::mic_mute::muted().hash(&mut hasher);
+        crate::clipping::warning_active().hash(&mut hasher);
+        crate::daily_words::today().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Redraw an idle overlay whose data changed since its last frame. Called by
+    /// the event loop every `IDLE_POLL_INTERVAL`; cheap when nothing changed.
+    pub fn poll_idle(&mut self) {
+        if !self.animating && self.idle_fingerprint() != self.shown_fingerprint {
+            self.window.request_redraw();
+        }
     }
 
     pub fn handle_scroll(&mut self, delta: MouseScrollDelta) {
@@ -1033,6 +1269,23 @@ impl WindowState {
         position: PhysicalPosition<f64>,
         event_loop: Option<&dyn ActiveEventLoop>,
     ) {
+        // Clicking the status line while it shows the clipping warning lowers the source volume
+        if button == MouseButton::Left
+            && state == ElementState::Pressed
+            && crate::clipping::warning_active()
+            && crate::clipping::mixer_available()
+        {
+            let (sb_x, sb_y, sb_w, sb_h) = self.layout_manager.get_status_bar_position();
+            let inside = position.x >= sb_x as f64
+                && position.x < (sb_x + sb_w) as f64
+                && position.y >= sb_y as f64
+                && position.y < (sb_y + sb_h) as f64;
+            if inside {
+                crate::clipping::spawn_reduce_source_volume();
+                return;
+            }
+        }
+
         let redraw_needed = self.event_handler.handle_mouse_input(
             button,
             state,
@@ -1051,6 +1304,30 @@ impl WindowState {
         }
     }
 
+    pub fn show_help(&mut self, sections: Vec<crate::help::HelpSection>) {
+        self.help_overlay.show(sections);
+        self.tooltip.update(None);
+        self.window.request_redraw();
+    }
+
+    pub fn hide_help(&mut self) {
+        self.help_overlay.hide();
+        self.window.request_redraw();
+    }
+
+    pub fn is_help_visible(&self) -> bool {
+        self.help_overlay.is_visible()
+    }
+
+    pub fn scroll_help(&mut self, delta: MouseScrollDelta) {
+        let pixels = match delta {
+            MouseScrollDelta::LineDelta(_, y) => -y * self.text_processor.line_height,
+            MouseScrollDelta::PixelDelta(PhysicalPosition { y, .. }) => -y as f32,
+        };
+        self.help_overlay.scroll(pixels, self.window_height);
+        self.window.request_redraw();
+    }
+
     pub fn check_settings_requested(&mut self) -> bool {
         if self.event_handler.settings_requested.get() {
             self.event_handler.settings_requested.set(false);
@@ -1064,6 +1341,21 @@ impl WindowState {
         EventHandler::copy_transcript(&self.audio_data);
     }
 
+    /// Let pointer events pass through the overlay by emptying its input region
+    pub fn set_click_through(&mut self, enabled: bool) {
+        if let Err(e) = self.window.set_cursor_hittest(!enabled) {
+            tracing::warn!("Failed to update the overlay input region: {}", e);
+            return;
+        }
+        self.click_through = enabled;
+        if enabled {
+            // No leave event arrives once the pointer stops reaching the surface
+            self.event_handler
+                .handle_cursor_leave(&mut self.button_manager);
+        }
+        self.window.request_redraw();
+    }
+
     pub fn reset_transcript(&mut self) {
         EventHandler::reset_transcript(
             &self.audio_data,
@@ -1082,6 +1374,7 @@ impl WindowState {
 
             // IMMEDIATE: Update button texture (local UI state, non-blocking)
             self.button_manager.update_record_toggle_button_texture();
+            self.window.request_redraw();
 
             // The transcription systems will detect this change asynchronously
             // via their polling of the atomic flag - no blocking here
@@ -1107,15 +1400,31 @@ impl WindowState {
                 };
 
                 if let Err(e) = sender.send(command).await {
-                    eprintln!("Failed to send manual session command: {}", e);
+                    tracing::warn!("Failed to send manual session command: {}", e);
                 }
             });
         } else {
-            eprintln!("Manual session sender not available");
+            tracing::warn!("Manual session sender not available");
         }
         // UI thread continues immediately - manual session processor handles the command
     }
 
+    /// Discard the current manual session; text already shown is kept
+    pub fn cancel_manual_session(&mut self) {
+        if let Some(sender) = &self.event_handler.manual_session_sender {
+            crate::recording_archive::session_cancelled();
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                let command = speechcore::ManualSessionCommand::CancelSession { responder: None };
+                if let Err(e) = sender.send(command).await {
+                    tracing::warn!("Failed to send cancel session command: {}", e);
+                }
+            });
+        } else {
+            tracing::warn!("Manual session sender not available");
+        }
+    }
+
     pub fn toggle_mode(&mut self) {
         // Switch between manual and real-time modes
         let current_mode = speechcore::TranscriptionMode::from_u8(
@@ -1134,11 +1443,11 @@ impl WindowState {
                     .send(speechcore::ManualSessionCommand::SwitchMode(new_mode))
                     .await
                 {
-                    eprintln!("Failed to send mode switch command from tray: {}", e);
+                    tracing::warn!("Failed to send mode switch command from tray: {}", e);
                 }
             });
         } else {
-            eprintln!("Manual session sender not available for mode switching from tray");
+            tracing::warn!("Manual session sender not available for mode switching from tray");
         }
     }
 
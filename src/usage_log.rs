@@ -0,0 +1,476 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Local, Timelike};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use speechcore::{AudioVisualizationData, ProcessingState, TranscriptionMode};
+
+/// Version of the JSON line schema. Bump when fields change meaning or are removed.
+pub const USAGE_LOG_SCHEMA_VERSION: u32 = 1;
+
+/// Maximum size of the usage log before it is rotated to `usage.jsonl.1`
+pub const USAGE_LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+/// One completed session or recording period.
+///
+/// Privacy: this record must never contain transcript text or audio,
+/// only counts and timings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Schema version (`USAGE_LOG_SCHEMA_VERSION` at write time)
+    pub v: u32,
+    /// Session end time (RFC 3339, local offset)
+    pub timestamp: String,
+    /// "manual" or "realtime"
+    pub mode: String,
+    /// Wall-clock recording duration in seconds
+    pub duration_secs: f64,
+    /// Seconds in which voice activity was detected
+    pub speech_secs: f64,
+    /// Number of words delivered
+    pub word_count: usize,
+    pub backend: String,
+    pub model: String,
+    /// Real-time factor (processing time / recording time), if measured
+    pub rtf: Option<f64>,
+    /// Number of delivery errors (clipboard, paste, history file)
+    pub error_count: u32,
+    /// Sinks that received text: "overlay", "history_file", "clipboard", "paste"
+    pub sinks: Vec<String>,
+    /// Highest fraction of clipped (flat-top) input samples in any analysis window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clipped_fraction: Option<f64>,
+}
+
+/// Accumulates statistics for the recording period in progress
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+    active: bool,
+    started_at: Option<std::time::Instant>,
+    duration_secs: f64,
+    speech_secs: f64,
+    processing_secs: f64,
+    word_count: usize,
+    error_count: u32,
+    sinks: Vec<String>,
+    clipped_fraction: f64,
+}
+
+impl UsageTracker {
+    pub fn start(&mut self) {
+        *self = Self {
+            active: true,
+            started_at: Some(std::time::Instant::now()),
+            ..Self::default()
+        };
+    }
+
+    /// Mark the end of recording; words and errors may still arrive while processing finishes
+    pub fn stop_recording(&mut self) {
+        if let Some(started_at) = self.started_at.take() {
+            self.duration_secs = started_at.elapsed().as_secs_f64();
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn add_speech_time(&mut self, secs: f64) {
+        if self.active {
+            self.speech_secs += secs;
+        }
+    }
+
+    pub fn add_processing_time(&mut self, secs: f64) {
+        if self.active {
+            self.processing_secs += secs;
+        }
+    }
+
+    pub fn add_words(&mut self, text: &str) {
+        if self.active {
+            self.word_count += text.split_whitespace().count();
+        }
+    }
+
+    /// Take back words removed by a privacy wipe
+    pub fn remove_words(&mut self, count: usize) {
+        self.word_count = self.word_count.saturating_sub(count);
+    }
+
+    pub fn add_clipping(&mut self, fraction: f64) {
+        if self.active {
+            self.clipped_fraction = self.clipped_fraction.max(fraction);
+        }
+    }
+
+    pub fn add_error(&mut self) {
+        if self.active {
+            self.error_count += 1;
+        }
+    }
+
+    pub fn add_sink(&mut self, sink: &str) {
+        if self.active && !self.sinks.iter().any(|s| s == sink) {
+            self.sinks.push(sink.to_string());
+        }
+    }
+
+    /// Finish the period and build its record. Returns None if nothing was recorded.
+    pub fn finish(&mut self, mode: &str, backend: &str, model: &str) -> Option<UsageRecord> {
+        if !self.active {
+            return None;
+        }
+        self.stop_recording();
+        let tracker = std::mem::take(self);
+
+        if tracker.duration_secs <= 0.0 && tracker.word_count == 0 {
+            return None;
+        }
+
+        let rtf = (tracker.duration_secs > 0.0 && tracker.processing_secs > 0.0)
+            .then(|| tracker.processing_secs / tracker.duration_secs);
+
+        Some(UsageRecord {
+            v: USAGE_LOG_SCHEMA_VERSION,
+            timestamp: Local::now().to_rfc3339(),
+            mode: mode.to_string(),
+            duration_secs: tracker.duration_secs,
+            speech_secs: tracker.speech_secs,
+            word_count: tracker.word_count,
+            backend: backend.to_string(),
+            model: model.to_string(),
+            rtf,
+            error_count: tracker.error_count,
+            sinks: tracker.sinks,
+            clipped_fraction: (tracker.clipped_fraction > 0.0).then_some(tracker.clipped_fraction),
+        })
+    }
+}
+
+/// Default usage log location: `$XDG_STATE_HOME/sonori/usage.jsonl` or `~/.local/state/sonori/usage.jsonl`
+pub fn usage_log_path() -> Option<PathBuf> {
+    if let Some(state_home) = std::env::var_os("XDG_STATE_HOME") {
+        return Some(PathBuf::from(state_home).join("sonori").join("usage.jsonl"));
+    }
+
+    std::env::var_os("HOME").map(|home| {
+        Path::new(&home)
+            .join(".local")
+            .join("state")
+            .join("sonori")
+            .join("usage.jsonl")
+    })
+}
+
+/// Append a record to the usage log, rotating the file once it exceeds `max_bytes`
+pub fn append_usage_record(record: &UsageRecord, path: &Path, max_bytes: u64) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if let Ok(metadata) = fs::metadata(path) {
+        if metadata.len() >= max_bytes {
+            let mut rotated = path.as_os_str().to_owned();
+            rotated.push(".1");
+            fs::rename(path, PathBuf::from(rotated))?;
+        }
+    }
+
+    let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
+
+/// Read all records from the log (and its rotated predecessor), skipping malformed lines
+/// and lines written by a newer schema version.
+pub fn read_usage_records(path: &Path) -> io::Result<Vec<UsageRecord>> {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+
+    let mut records = Vec::new();
+    for file_path in [PathBuf::from(rotated), path.to_path_buf()] {
+        let file = match fs::File::open(&file_path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for line in io::BufReader::new(file).lines() {
+            let line = line?;
+            if let Ok(record) = serde_json::from_str::<UsageRecord>(&line) {
+                if record.v <= USAGE_LOG_SCHEMA_VERSION {
+                    records.push(record);
+                }
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Shared state the usage monitor needs from the transcriber
+pub struct UsageMonitorContext {
+    pub tracker: Arc<Mutex<UsageTracker>>,
+    pub running: Arc<AtomicBool>,
+    pub recording: Arc<AtomicBool>,
+    pub transcription_mode: Arc<AtomicU8>,
+    pub audio_data: Arc<RwLock<AudioVisualizationData>>,
+    pub backend: String,
+    pub model: String,
+    pub log_path: PathBuf,
+}
+
+/// Grace period after recording stops before a session is considered processed
+const FINISH_GRACE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Poll recording/processing state, accumulate timings and write one record per period
+pub async fn run_usage_monitor(ctx: UsageMonitorContext) {
+    let tick = std::time::Duration::from_millis(100);
+    let mut interval = tokio::time::interval(tick);
+    let mut was_recording = false;
+    let mut stopped_at: Option<std::time::Instant> = None;
+    let mut mode = "realtime";
+
+    loop {
+        interval.tick().await;
+
+        let is_running = ctx.running.load(Ordering::Relaxed);
+        let is_recording = ctx.recording.load(Ordering::Relaxed);
+        let (is_speaking, processing_state) = {
+            let audio_data = ctx.audio_data.read();
+            (audio_data.is_speaking, audio_data.processing_state)
+        };
+
+        let record = {
+            let mut tracker = ctx.tracker.lock();
+            if is_recording && !was_recording {
+                tracker.start();
+                stopped_at = None;
+                mode = match TranscriptionMode::from_u8(
+                    ctx.transcription_mode.load(Ordering::Relaxed),
+                ) {
+                    TranscriptionMode::Manual => "manual",
+                    TranscriptionMode::RealTime => "realtime",
+                };
+            }
+            if is_recording && is_speaking {
+                tracker.add_speech_time(tick.as_secs_f64());
+            }
+            if is_recording {
+                tracker.add_clipping(crate::clipping::take_peak_flat_top_fraction());
+            }
+            if processing_state == ProcessingState::Transcribing {
+                tracker.add_processing_time(tick.as_secs_f64());
+            }
+            if !is_recording && was_recording {
+                tracker.stop_recording();
+                stopped_at = Some(std::time::Instant::now());
+            }
+
+            let processing_done = !matches!(
+                processing_state,
+                ProcessingState::Transcribing | ProcessingState::Loading
+            );
+            let finished =
+                stopped_at.is_some_and(|t| t.elapsed() >= FINISH_GRACE) && processing_done;
+            if tracker.is_active() && (finished || !is_running) {
+                stopped_at = None;
+                tracker.finish(mode, &ctx.backend, &ctx.model)
+            } else {
+                None
+            }
+        };
+
+        if let Some(record) = record {
+            if let Err(e) = append_usage_record(&record, &ctx.log_path, USAGE_LOG_MAX_BYTES) {
+                tracing::warn!("Failed to write usage log: {}", e);
+            }
+        }
+
+        was_recording = is_recording;
+        if !is_running {
+            break;
+        }
+    }
+}
+
+/// Parse a `--since` window such as "30d", "12h" or "2w"
+pub fn parse_since(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+    let unit = value.chars().last()?;
+    // Unsigned, so `-5d` is rejected instead of reaching into the future
+    let amount: u32 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    let amount = i64::from(amount);
+    match unit {
+        'h' => chrono::Duration::try_hours(amount),
+        'd' => chrono::Duration::try_days(amount),
+        'w' => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// Aggregate statistics over a set of usage records
+#[derive(Debug, Default, PartialEq)]
+pub struct UsageSummary {
+    pub sessions: usize,
+    pub total_words: usize,
+    pub total_duration_secs: f64,
+    pub total_speech_secs: f64,
+    pub total_errors: u32,
+    /// Sessions in which the input was clipped at the source
+    pub clipped_sessions: usize,
+    /// Words per local hour of day (0-23)
+    pub words_by_hour: [usize; 24],
+    /// Average RTF per "backend/model"
+    pub average_rtf: BTreeMap<String, f64>,
+}
+
+pub fn summarize(records: &[UsageRecord], since: Option<DateTime<Local>>) -> UsageSummary {
+    let mut summary = UsageSummary::default();
+    let mut rtf_totals: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+
+    for record in records {
+        let Ok(timestamp) = DateTime::parse_from_rfc3339(&record.timestamp) else {
+            continue;
+        };
+        let timestamp = timestamp.with_timezone(&Local);
+        if since.is_some_and(|since| timestamp < since) {
+            continue;
+        }
+
+        summary.sessions += 1;
+        summary.total_words += record.word_count;
+        summary.total_duration_secs += record.duration_secs;
+        summary.total_speech_secs += record.speech_secs;
+        summary.total_errors += record.error_count;
+        if record
+            .clipped_fraction
+            .is_some_and(|fraction| fraction >= crate::clipping::CLIPPED_FRACTION_THRESHOLD)
+        {
+            summary.clipped_sessions += 1;
+        }
+        summary.words_by_hour[timestamp.hour() as usize] += record.word_count;
+
+        if let Some(rtf) = record.rtf {
+            let entry = rtf_totals
+                .entry(format!("{}/{}", record.backend, record.model))
+                .or_insert((0.0, 0));
+            entry.0 += rtf;
+            entry.1 += 1;
+        }
+    }
+
+    summary.average_rtf = rtf_totals
+        .into_iter()
+        .map(|(model, (total, count))| (model, total / count as f64))
+        .collect();
+    summary
+}
+
+/// Render a summary for the terminal
+pub fn format_summary(summary: &UsageSummary) -> String {
+    let mut text = String::new();
+    text.push_str(&format!("Sessions:        {}\n", summary.sessions));
+    text.push_str(&format!("Total words:     {}\n", summary.total_words));
+    text.push_str(&format!(
+        "Recording time:  {:.1} min ({:.1} min speech)\n",
+        summary.total_duration_secs / 60.0,
+        summary.total_speech_secs / 60.0
+    ));
+    text.push_str(&format!("Errors:          {}\n", summary.total_errors));
+    if summary.clipped_sessions > 0 {
+        text.push_str(&format!(
+            "Clipped input:   {} session(s); lower your microphone gain\n",
+            summary.clipped_sessions
+        ));
+    }
+
+    let max_words = summary.words_by_hour.iter().copied().max().unwrap_or(0);
+    if max_words > 0 {
+        text.push_str("\nWords by hour of day:\n");
+        for (hour, words) in summary.words_by_hour.iter().enumerate() {
+            let bar_len = words * 40 / max_words;
+            text.push_str(&format!(
+                "  {:02}:00 {:<40} {}\n",
+                hour,
+                "#".repeat(bar_len),
+                words
+            ));
+        }
+    }
+
+    if !summary.average_rtf.is_empty() {
+        text.push_str("\nAverage RTF per model:\n");
+        for (model, rtf) in &summary.average_rtf {
+            text.push_str(&format!("  {:<40} {:.3}\n", model, rtf));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_record() -> UsageRecord {
+        UsageRecord {
+            v: USAGE_LOG_SCHEMA_VERSION,
+            timestamp: "2026-01-05T14:30:00+00:00".to_string(),
+            mode: "manual".to_string(),
+            duration_secs: 12.5,
+            speech_secs: 9.0,
+            word_count: 31,
+            backend: "WhisperCpp".to_string(),
+            model: "small.en".to_string(),
+            rtf: Some(0.25),
+            error_count: 0,
+            sinks: vec!["overlay".to_string(), "paste".to_string()],
+            clipped_fraction: None,
+        }
+    }
+
+    #[test]
+    fn record_serializes_with_schema_version() {
+        let json = serde_json::to_string(&sample_record()).unwrap();
+        assert!(json.starts_with("{\"v\":1,"));
+        assert!(json.contains("\"word_count\":31"));
+        assert!(!json.contains('\n'));
+    }
+
+    #[test]
+    fn record_round_trips() {
+        let record = sample_record();
+        let json = serde_json::to_string(&record).unwrap();
+        let parsed: UsageRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn parse_since_accepts_hours_days_weeks() {
+        assert_eq!(parse_since("12h"), chrono::Duration::try_hours(12));
+        assert_eq!(parse_since("30d"), chrono::Duration::try_days(30));
+        assert_eq!(parse_since("2w"), chrono::Duration::try_weeks(2));
+        assert_eq!(parse_since("30"), None);
+        assert_eq!(parse_since("d"), None);
+        assert_eq!(parse_since("-5d"), None);
+    }
+
+    #[test]
+    fn summary_averages_rtf_per_model() {
+        let mut second = sample_record();
+        second.rtf = Some(0.75);
+        second.word_count = 9;
+
+        let summary = summarize(&[sample_record(), second], None);
+        assert_eq!(summary.sessions, 2);
+        assert_eq!(summary.total_words, 40);
+        assert_eq!(summary.average_rtf["WhisperCpp/small.en"], 0.5);
+    }
+}
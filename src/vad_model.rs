@@ -0,0 +1,254 @@
+//! Integrity checks for the Silero VAD model downloaded by speechcore.
+
+use std::future::Future;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// Every published Silero VAD model is well above this size
+const MIN_MODEL_BYTES: u64 = 1_000_000;
+
+/// ONNX files are protobufs starting with the `ir_version` field (tag 1, varint)
+const ONNX_FIRST_BYTE: u8 = 0x08;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VadModelCheck {
+    /// Matches the hash recorded after a successful load
+    Verified,
+    /// Plausible, but no hash has been recorded yet
+    Unverified,
+    Missing,
+    Truncated {
+        bytes: u64,
+    },
+    NotOnnx,
+    HashMismatch {
+        expected: String,
+        actual: String,
+    },
+}
+
+impl VadModelCheck {
+    pub fn is_usable(&self) -> bool {
+        matches!(self, Self::Verified | Self::Unverified)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Verified => "verified".to_string(),
+            Self::Unverified => "not yet verified".to_string(),
+            Self::Missing => "file is missing".to_string(),
+            Self::Truncated { bytes } => format!("file is truncated ({} bytes)", bytes),
+            Self::NotOnnx => "file is not an ONNX model".to_string(),
+            Self::HashMismatch { expected, actual } => format!(
+                "SHA-256 mismatch (expected {}, found {})",
+                short_hash(expected),
+                short_hash(actual)
+            ),
+        }
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(12)]
+}
+
+/// Where the speech runtime keeps the VAD model in the model cache
+pub fn cached_path() -> Option<PathBuf> {
+    crate::model_catalog::model_cache_dir().map(|dir| dir.join("silero_vad.onnx"))
+}
+
+/// `silero_vad.onnx.sha256`, holding the hash recorded after a successful load
+pub fn hash_path(model: &Path) -> PathBuf {
+    let mut name = model.as_os_str().to_owned();
+    name.push(".sha256");
+    PathBuf::from(name)
+}
+
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Check the model file without loading it
+pub fn check(model: &Path) -> VadModelCheck {
+    let Ok(metadata) = std::fs::metadata(model) else {
+        return VadModelCheck::Missing;
+    };
+    if metadata.len() < MIN_MODEL_BYTES {
+        return VadModelCheck::Truncated {
+            bytes: metadata.len(),
+        };
+    }
+
+    let mut first = [0u8; 1];
+    let first_ok = std::fs::File::open(model)
+        .and_then(|mut file| file.read_exact(&mut first))
+        .is_ok();
+    if !first_ok || first[0] != ONNX_FIRST_BYTE {
+        return VadModelCheck::NotOnnx;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(hash_path(model)) else {
+        return VadModelCheck::Unverified;
+    };
+    let expected = expected.trim().to_string();
+    match sha256_file(model) {
+        Ok(actual) if actual == expected => VadModelCheck::Verified,
+        Ok(actual) => VadModelCheck::HashMismatch { expected, actual },
+        Err(_) => VadModelCheck::Missing,
+    }
+}
+
+/// Record the model hash after it loaded successfully
+pub fn record_hash(model: &Path) -> std::io::Result<()> {
+    let hash_file = hash_path(model);
+    if hash_file.exists() {
+        return Ok(());
+    }
+    std::fs::write(hash_file, sha256_file(model)?)
+}
+
+/// Delete the model and its recorded hash so the next initialization downloads it again
+pub fn remove(model: &Path) {
+    for path in [model.to_path_buf(), hash_path(model)] {
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("Failed to delete {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// What `ensure_usable` had to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VadModelRecovery {
+    Healthy,
+    Redownloaded,
+}
+
+/// Check the model and, if it is unusable, delete it and download it again once.
+///
+/// The error names the file, what was wrong and what was tried.
+pub async fn ensure_usable<F, Fut>(model: &Path, redownload: F) -> Result<VadModelRecovery, String>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let status = check(model);
+    if status.is_usable() {
+        return Ok(VadModelRecovery::Healthy);
+    }
+
+    tracing::warn!(
+        "Silero VAD model at {} is unusable ({}); deleting it and downloading it again",
+        model.display(),
+        status.describe()
+    );
+    remove(model);
+    redownload().await.map_err(|e| {
+        format!(
+            "Silero VAD model at {} was unusable ({}) and downloading it again failed: {}",
+            model.display(),
+            status.describe(),
+            e
+        )
+    })?;
+
+    let status = check(model);
+    if status.is_usable() {
+        tracing::info!("Silero VAD model re-downloaded to {}", model.display());
+        Ok(VadModelRecovery::Redownloaded)
+    } else {
+        Err(format!(
+            "Silero VAD model at {} is still unusable after downloading it again ({}). \
+             Check free disk space and your connection, then delete the file and restart",
+            model.display(),
+            status.describe()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_model(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sonori-vad-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("silero_vad.onnx")
+    }
+
+    fn write_model(path: &Path, bytes: usize) {
+        let mut data = vec![0u8; bytes];
+        if let Some(first) = data.first_mut() {
+            *first = ONNX_FIRST_BYTE;
+        }
+        std::fs::write(path, data).unwrap();
+    }
+
+    #[test]
+    fn detects_truncation_and_later_corruption() {
+        let model = temp_model("check");
+        write_model(&model, 4096);
+        assert_eq!(check(&model), VadModelCheck::Truncated { bytes: 4096 });
+
+        write_model(&model, MIN_MODEL_BYTES as usize);
+        assert_eq!(check(&model), VadModelCheck::Unverified);
+        record_hash(&model).unwrap();
+        assert_eq!(check(&model), VadModelCheck::Verified);
+
+        let mut data = std::fs::read(&model).unwrap();
+        data[100] = 1;
+        std::fs::write(&model, data).unwrap();
+        assert!(matches!(check(&model), VadModelCheck::HashMismatch { .. }));
+
+        std::fs::remove_dir_all(model.parent().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn truncated_model_is_redownloaded_once_then_rejected() {
+        let model = temp_model("recover");
+
+        write_model(&model, 4096);
+        let recovery = ensure_usable(&model, || async {
+            write_model(&model, MIN_MODEL_BYTES as usize);
+            Ok(())
+        })
+        .await;
+        assert_eq!(recovery, Ok(VadModelRecovery::Redownloaded));
+        async fn not_called() -> Result<(), String> {
+            panic!("healthy model downloaded again")
+        }
+        assert_eq!(
+            ensure_usable(&model, not_called).await,
+            Ok(VadModelRecovery::Healthy)
+        );
+
+        write_model(&model, 4096);
+        let error = ensure_usable(&model, || async {
+            write_model(&model, 10);
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+        assert!(error.contains(&model.display().to_string()));
+        assert!(error.contains("still unusable"));
+
+        std::fs::remove_dir_all(model.parent().unwrap()).unwrap();
+    }
+}
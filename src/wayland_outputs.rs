@@ -0,0 +1,87 @@
+//! Output discovery for `display_config.output_name` and `--list-outputs`.
+
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::{wl_output, wl_registry};
+use wayland_client::{Connection, Dispatch, QueueHandle, WEnum};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputInfo {
+    /// Connector name, e.g. "DP-1"
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub width: i32,
+    pub height: i32,
+    pub refresh_mhz: i32,
+}
+
+/// Enumerate the outputs of the Wayland session
+pub fn list_outputs() -> Result<Vec<OutputInfo>, String> {
+    let connection =
+        Connection::connect_to_env().map_err(|e| format!("Failed to connect to Wayland: {}", e))?;
+    let (globals, mut queue) = registry_queue_init::<Outputs>(&connection)
+        .map_err(|e| format!("Failed to read Wayland globals: {}", e))?;
+
+    let handle = queue.handle();
+    let mut outputs = Outputs::default();
+    globals.contents().with_list(|list| {
+        for global in list.iter().filter(|global| global.interface == "wl_output") {
+            let index = outputs.0.len();
+            outputs.0.push(OutputInfo::default());
+            globals.registry().bind::<wl_output::WlOutput, _, _>(
+                global.name,
+                global.version.min(4),
+                &handle,
+                index,
+            );
+        }
+    });
+    queue
+        .roundtrip(&mut outputs)
+        .map_err(|e| format!("Failed to query Wayland outputs: {}", e))?;
+    Ok(outputs.0)
+}
+
+#[derive(Default)]
+struct Outputs(Vec<OutputInfo>);
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for Outputs {
+    fn event(
+        _: &mut Self,
+        _: &wl_registry::WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<wl_output::WlOutput, usize> for Outputs {
+    fn event(
+        outputs: &mut Self,
+        _: &wl_output::WlOutput,
+        event: wl_output::Event,
+        index: &usize,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let Some(output) = outputs.0.get_mut(*index) else {
+            return;
+        };
+        match event {
+            wl_output::Event::Mode {
+                flags: WEnum::Value(flags),
+                width,
+                height,
+                refresh,
+            } if flags.contains(wl_output::Mode::Current) => {
+                output.width = width;
+                output.height = height;
+                output.refresh_mhz = refresh;
+            }
+            wl_output::Event::Name { name } => output.name = Some(name),
+            wl_output::Event::Description { description } => output.description = Some(description),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Sanity checks for the whisper.cpp model file downloaded by speechcore.
+
+use std::future::Future;
+use std::io::Read;
+use std::path::Path;
+
+/// `GGML_FILE_MAGIC` (0x67676d6c) as stored little-endian at the start of the file
+const GGML_MAGIC: [u8; 4] = *b"lmgg";
+
+/// A file below this fraction of the table size is treated as truncated
+/// (the table is only accurate to ~10-15%)
+const MIN_SIZE_FRACTION: f64 = 0.75;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WhisperModelCheck {
+    Plausible,
+    Missing,
+    Truncated { bytes: u64, expected_bytes: u64 },
+    NotGgml,
+}
+
+impl WhisperModelCheck {
+    pub fn is_usable(&self) -> bool {
+        matches!(self, Self::Plausible)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Plausible => "plausible".to_string(),
+            Self::Missing => "file is missing".to_string(),
+            Self::Truncated {
+                bytes,
+                expected_bytes,
+            } => format!(
+                "file is truncated ({} bytes, expected about {})",
+                bytes, expected_bytes
+            ),
+            Self::NotGgml => "file is not a GGML model".to_string(),
+        }
+    }
+}
+
+/// Check the model file without loading it; `expected_mb` comes from the model table
+pub fn check(model: &Path, expected_mb: Option<u64>) -> WhisperModelCheck {
+    let Ok(metadata) = std::fs::metadata(model) else {
+        return WhisperModelCheck::Missing;
+    };
+    if let Some(expected_mb) = expected_mb {
+        let expected_bytes = expected_mb * 1_000_000;
+        if (metadata.len() as f64) < expected_bytes as f64 * MIN_SIZE_FRACTION {
+            return WhisperModelCheck::Truncated {
+                bytes: metadata.len(),
+                expected_bytes,
+            };
+        }
+    }
+
+    let mut magic = [0u8; 4];
+    let magic_ok = std::fs::File::open(model)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok();
+    if !magic_ok || magic != GGML_MAGIC {
+        return WhisperModelCheck::NotGgml;
+    }
+    WhisperModelCheck::Plausible
+}
+
+/// Delete and re-download a truncated or foreign model file once; the error
+/// says which check failed
+pub async fn ensure_usable<F, Fut>(
+    model: &Path,
+    expected_mb: Option<u64>,
+    redownload: F,
+) -> Result<(), String>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let status = check(model, expected_mb);
+    if status.is_usable() {
+        return Ok(());
+    }
+
+    tracing::warn!(
+        "whisper.cpp model at {} is unusable ({}); deleting it and downloading it again",
+        model.display(),
+        status.describe()
+    );
+    if let Err(e) = std::fs::remove_file(model) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!("Failed to delete {}: {}", model.display(), e);
+        }
+    }
+    redownload().await.map_err(|e| {
+        format!(
+            "whisper.cpp model at {} was unusable ({}) and downloading it again failed: {}",
+            model.display(),
+            status.describe(),
+            e
+        )
+    })?;
+
+    let status = check(model, expected_mb);
+    if status.is_usable() {
+        tracing::info!("whisper.cpp model re-downloaded to {}", model.display());
+        Ok(())
+    } else {
+        Err(format!(
+            "whisper.cpp model at {} is still unusable after downloading it again ({}). \
+             Check free disk space and your connection, then delete the file and restart",
+            model.display(),
+            status.describe()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_short_and_foreign_files() {
+        let dir = std::env::temp_dir().join(format!("sonori-ggml-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let model = dir.join("ggml-tiny.bin");
+
+        let mut data = GGML_MAGIC.to_vec();
+        data.resize(1_000_000, 0);
+        std::fs::write(&model, &data).unwrap();
+        assert_eq!(check(&model, Some(1)), WhisperModelCheck::Plausible);
+        assert!(matches!(
+            check(&model, Some(75)),
+            WhisperModelCheck::Truncated { .. }
+        ));
+
+        data[..4].copy_from_slice(b"<htm");
+        std::fs::write(&model, &data).unwrap();
+        assert_eq!(check(&model, None), WhisperModelCheck::NotGgml);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(check(&model, None), WhisperModelCheck::Missing);
+    }
+}